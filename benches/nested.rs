@@ -0,0 +1,92 @@
+use criterion::{Criterion, Throughput};
+use prost::Message;
+
+#[derive(Clone, PartialEq, Message)]
+struct Node {
+    #[prost(string, tag = "1")]
+    name: String,
+    #[prost(message, optional, boxed, tag = "2")]
+    child: Option<Box<Node>>,
+    #[prost(message, repeated, tag = "3")]
+    children: Vec<Node>,
+}
+
+/// Returns a chain of `depth` nested messages.
+fn chain(depth: usize) -> Node {
+    (0..depth).fold(Node::default(), |child, i| Node {
+        name: format!("node {}", i),
+        child: Some(Box::new(child)),
+        children: Vec::new(),
+    })
+}
+
+/// Returns a complete tree of `depth` levels, where each message has `width` children.
+fn tree(depth: usize, width: usize) -> Node {
+    Node {
+        name: format!("depth {}", depth),
+        child: None,
+        children: if depth == 0 {
+            Vec::new()
+        } else {
+            (0..width).map(|_| tree(depth - 1, width)).collect()
+        },
+    }
+}
+
+fn benchmark_nested(criterion: &mut Criterion, name: &str, node: Node) {
+    let name = format!("nested/{}", name);
+    let encoded_len = node.encoded_len() as u64;
+
+    criterion
+        .benchmark_group(&name)
+        .bench_function("encode", {
+            let node = node.clone();
+            move |b| {
+                let mut buf = Vec::<u8>::with_capacity(node.encoded_len());
+                b.iter(|| {
+                    buf.clear();
+                    node.encode(&mut buf).unwrap();
+                    criterion::black_box(&buf);
+                })
+            }
+        })
+        .throughput(Throughput::Bytes(encoded_len));
+
+    // Encoding without the table of nested message lengths, which computes the length of each
+    // nested message again at every level of nesting.
+    criterion
+        .benchmark_group(&name)
+        .bench_function("encode_raw", {
+            let node = node.clone();
+            move |b| {
+                let mut buf = Vec::<u8>::with_capacity(node.encoded_len());
+                b.iter(|| {
+                    buf.clear();
+                    node.encode_raw(&mut buf);
+                    criterion::black_box(&buf);
+                })
+            }
+        })
+        .throughput(Throughput::Bytes(encoded_len));
+
+    criterion
+        .benchmark_group(&name)
+        .bench_function("encoded_len", move |b| {
+            b.iter(|| criterion::black_box(node.encoded_len()))
+        })
+        .throughput(Throughput::Bytes(encoded_len));
+}
+
+fn main() {
+    let mut criterion = Criterion::default().configure_from_args();
+
+    // Benchmark encoding chains of nested messages.
+    benchmark_nested(&mut criterion, "chain_16", chain(16));
+    benchmark_nested(&mut criterion, "chain_64", chain(64));
+    benchmark_nested(&mut criterion, "chain_256", chain(256));
+
+    // Benchmark encoding a tree of 4^6 = 4096 leaf messages.
+    benchmark_nested(&mut criterion, "tree_4x6", tree(6, 4));
+
+    criterion.final_summary();
+}
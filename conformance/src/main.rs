@@ -1,10 +1,13 @@
 use std::io::{self, Read, Write};
 
 use bytes::{Buf, BufMut};
+use prost::json::{Json, JsonOptions, TypeRegistry};
+use prost::text_format::{PrintOptions, TextFormat};
 use prost::Message;
 
 use protobuf::conformance::{
-    conformance_request, conformance_response, ConformanceRequest, ConformanceResponse, WireFormat,
+    conformance_request, conformance_response, ConformanceRequest, ConformanceResponse,
+    TestCategory, WireFormat,
 };
 use protobuf::test_messages::proto2::TestAllTypesProto2;
 use protobuf::test_messages::proto3::TestAllTypesProto3;
@@ -12,6 +15,7 @@ use tests::{roundtrip, RoundtripResult};
 
 fn main() -> io::Result<()> {
     env_logger::init();
+    let registry = type_registry();
     let mut bytes = Vec::new();
 
     loop {
@@ -28,7 +32,7 @@ fn main() -> io::Result<()> {
         io::stdin().read_exact(&mut bytes)?;
 
         let result = match ConformanceRequest::decode(bytes.as_slice()) {
-            Ok(request) => handle_request(request),
+            Ok(request) => handle_request(request, &registry),
             Err(error) => conformance_response::Result::ParseError(format!("{:?}", error)),
         };
 
@@ -48,69 +52,126 @@ fn main() -> io::Result<()> {
     }
 }
 
-fn handle_request(request: ConformanceRequest) -> conformance_response::Result {
-    match request.requested_output_format() {
+/// Returns the registry of the message types which may be packed in `Any` messages by the tests.
+fn type_registry() -> TypeRegistry {
+    let mut registry = TypeRegistry::new();
+    prost_types::register_well_known_types(&mut registry)
+        .register_full_name::<TestAllTypesProto2>(
+            "protobuf_test_messages.proto2.TestAllTypesProto2",
+        )
+        .register_full_name::<TestAllTypesProto3>(
+            "protobuf_test_messages.proto3.TestAllTypesProto3",
+        );
+    registry
+}
+
+fn handle_request(
+    request: ConformanceRequest,
+    registry: &TypeRegistry,
+) -> conformance_response::Result {
+    let output = request.requested_output_format();
+    match output {
         WireFormat::Unspecified => {
             return conformance_response::Result::ParseError(
                 "output format unspecified".to_string(),
             );
         }
-        WireFormat::Json => {
-            return conformance_response::Result::Skipped(
-                "JSON output is not supported".to_string(),
-            );
-        }
         WireFormat::Jspb => {
             return conformance_response::Result::Skipped(
                 "JSPB output is not supported".to_string(),
             );
         }
-        WireFormat::TextFormat => {
-            return conformance_response::Result::Skipped(
-                "TEXT_FORMAT output is not supported".to_string(),
-            );
-        }
-        WireFormat::Protobuf => (),
+        WireFormat::Json | WireFormat::TextFormat | WireFormat::Protobuf => (),
     };
 
-    let buf = match request.payload {
+    let input = match request.payload {
         None => return conformance_response::Result::ParseError("no payload".to_string()),
-        Some(conformance_request::Payload::JsonPayload(_)) => {
-            return conformance_response::Result::Skipped(
-                "JSON input is not supported".to_string(),
-            );
-        }
         Some(conformance_request::Payload::JspbPayload(_)) => {
             return conformance_response::Result::Skipped(
-                "JSON input is not supported".to_string(),
+                "JSPB input is not supported".to_string(),
             );
         }
-        Some(conformance_request::Payload::TextPayload(_)) => {
-            return conformance_response::Result::Skipped(
-                "JSON input is not supported".to_string(),
-            );
-        }
-        Some(conformance_request::Payload::ProtobufPayload(buf)) => buf,
+        Some(conformance_request::Payload::JsonPayload(ref json)) => Input::Json(json),
+        Some(conformance_request::Payload::TextPayload(ref text)) => Input::Text(text),
+        Some(conformance_request::Payload::ProtobufPayload(ref buf)) => Input::Protobuf(buf),
     };
 
-    let roundtrip = match request.message_type.as_str() {
-        "protobuf_test_messages.proto2.TestAllTypesProto2" => roundtrip::<TestAllTypesProto2>(&buf),
-        "protobuf_test_messages.proto3.TestAllTypesProto3" => roundtrip::<TestAllTypesProto3>(&buf),
-        _ => {
-            return conformance_response::Result::ParseError(format!(
-                "unknown message type: {}",
-                request.message_type
-            ));
-        }
+    let options = Options {
+        text: PrintOptions::new().unknown_fields(request.print_unknown_fields),
+        json: JsonOptions::new()
+            .ignore_unknown_fields(
+                request.test_category() == TestCategory::JsonIgnoreUnknownParsingTest,
+            )
+            .type_registry(registry),
     };
+    match request.message_type.as_str() {
+        "protobuf_test_messages.proto2.TestAllTypesProto2" => {
+            handle::<TestAllTypesProto2>(input, output, &options)
+        }
+        "protobuf_test_messages.proto3.TestAllTypesProto3" => {
+            handle::<TestAllTypesProto3>(input, output, &options)
+        }
+        _ => conformance_response::Result::ParseError(format!(
+            "unknown message type: {}",
+            request.message_type
+        )),
+    }
+}
 
-    match roundtrip {
-        RoundtripResult::Ok(buf) => conformance_response::Result::ProtobufPayload(buf),
-        RoundtripResult::DecodeError(error) => {
-            conformance_response::Result::ParseError(error.to_string())
+enum Input<'a> {
+    Protobuf(&'a [u8]),
+    Json(&'a str),
+    Text(&'a str),
+}
+
+struct Options<'a> {
+    text: PrintOptions,
+    json: JsonOptions<'a>,
+}
+
+fn handle<M>(
+    input: Input<'_>,
+    output: WireFormat,
+    options: &Options<'_>,
+) -> conformance_response::Result
+where
+    M: Message + TextFormat + Json + Default,
+{
+    let message = match (input, output) {
+        // Binary to binary conformance is checked with the stricter roundtrip test.
+        (Input::Protobuf(buf), WireFormat::Protobuf) => {
+            return match roundtrip::<M>(buf) {
+                RoundtripResult::Ok(buf) => conformance_response::Result::ProtobufPayload(buf),
+                RoundtripResult::DecodeError(error) => {
+                    conformance_response::Result::ParseError(error.to_string())
+                }
+                RoundtripResult::Error(error) => {
+                    conformance_response::Result::RuntimeError(error.to_string())
+                }
+            };
         }
-        RoundtripResult::Error(error) => {
-            conformance_response::Result::RuntimeError(error.to_string())
+        (Input::Protobuf(buf), _) => match M::decode(buf) {
+            Ok(message) => message,
+            Err(error) => return conformance_response::Result::ParseError(error.to_string()),
+        },
+        (Input::Json(json), _) => match M::parse_json_with_options(json, &options.json) {
+            Ok(message) => message,
+            Err(error) => return conformance_response::Result::ParseError(error.to_string()),
+        },
+        (Input::Text(text), _) => match M::parse_text(text) {
+            Ok(message) => message,
+            Err(error) => return conformance_response::Result::ParseError(error.to_string()),
+        },
+    };
+
+    match output {
+        WireFormat::Json => match message.to_json_with_options(&options.json) {
+            Ok(json) => conformance_response::Result::JsonPayload(json),
+            Err(error) => conformance_response::Result::SerializeError(error.to_string()),
+        },
+        WireFormat::TextFormat => {
+            conformance_response::Result::TextPayload(message.to_text_with_options(&options.text))
         }
+        _ => conformance_response::Result::ProtobufPayload(message.encode_to_vec()),
     }
 }
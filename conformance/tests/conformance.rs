@@ -25,6 +25,8 @@ fn test_conformance() {
         .arg("--enforce_recommended")
         .arg("--failure_list")
         .arg("failing_tests.txt")
+        .arg("--text_format_failure_list")
+        .arg("text_format_failing_tests.txt")
         .arg(proto_conformance)
         .status()
         .expect("failed to execute conformance-test-runner");
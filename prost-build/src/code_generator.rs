@@ -6,6 +6,7 @@ use std::iter;
 use itertools::{Either, Itertools};
 use log::debug;
 use multimap::MultiMap;
+use prost_types::descriptor_proto::ExtensionRange;
 use prost_types::field_descriptor_proto::{Label, Type};
 use prost_types::source_code_info::Location;
 use prost_types::{
@@ -102,6 +103,16 @@ impl<'a> CodeGenerator<'a> {
         }
         code_gen.path.pop();
 
+        if code_gen.config.enable_extensions {
+            code_gen.path.push(7);
+            for (idx, extension) in file.extension.into_iter().enumerate() {
+                code_gen.path.push(idx as i32);
+                code_gen.append_extension(extension);
+                code_gen.path.pop();
+            }
+            code_gen.path.pop();
+        }
+
         if code_gen.config.service_generator.is_some() {
             code_gen.path.push(6);
             for (idx, service) in file.service.into_iter().enumerate() {
@@ -175,6 +186,33 @@ impl<'a> CodeGenerator<'a> {
                 }
             });
 
+        let view = self
+            .config
+            .message_views
+            .get_first(&fq_message_name)
+            .is_some();
+        let view_fields = if view {
+            Some((fields.clone(), oneof_fields.clone()))
+        } else {
+            None
+        };
+        let text_format_fields = if self.config.enable_text_format {
+            Some((fields.clone(), oneof_fields.clone()))
+        } else {
+            None
+        };
+        let json_fields = if self.config.enable_json && !has_json_special_form(&fq_message_name) {
+            Some((fields.clone(), oneof_fields.clone()))
+        } else {
+            None
+        };
+
+        let field_mask_fields = if self.config.enable_field_masks {
+            Some((fields.clone(), oneof_fields.clone()))
+        } else {
+            None
+        };
+
         self.append_doc(&fq_message_name, None);
         self.append_type_attributes(&fq_message_name);
         self.append_message_attributes(&fq_message_name);
@@ -224,11 +262,97 @@ impl<'a> CodeGenerator<'a> {
         }
         self.path.pop();
 
+        let extension_set = self.config.enable_extensions && !message.extension_range.is_empty();
+        if extension_set {
+            self.append_extension_set(&message.extension_range);
+        }
+
+        let unknown_fields = self
+            .config
+            .unknown_fields
+            .get(&fq_message_name)
+            .next()
+            .is_some();
+        if unknown_fields {
+            self.append_unknown_fields();
+        }
+
+        let cached_size = self
+            .config
+            .cached_size
+            .get(&fq_message_name)
+            .next()
+            .is_some();
+        if cached_size {
+            self.append_cached_size();
+        }
+
         self.depth -= 1;
         self.push_indent();
         self.buf.push_str("}\n");
 
-        if !message.enum_type.is_empty() || !nested_types.is_empty() || !oneof_fields.is_empty() {
+        if let Some((fields, oneof_fields)) = &view_fields {
+            self.append_message_view(
+                &message_name,
+                &fq_message_name,
+                fields,
+                &message.oneof_decl,
+                oneof_fields,
+                &map_types,
+                extension_set || unknown_fields || cached_size,
+            );
+        }
+
+        if let Some((fields, oneof_fields)) = &text_format_fields {
+            self.append_text_format(
+                &message_name,
+                &fq_message_name,
+                fields,
+                &message.oneof_decl,
+                oneof_fields,
+                &map_types,
+                extension_set,
+                unknown_fields,
+            );
+        }
+
+        if let Some((fields, oneof_fields)) = &json_fields {
+            self.append_json(
+                &message_name,
+                &fq_message_name,
+                fields,
+                &message.oneof_decl,
+                oneof_fields,
+                &map_types,
+            );
+        }
+
+        if let Some((fields, oneof_fields)) = &field_mask_fields {
+            self.append_field_mask(
+                &message_name,
+                &fq_message_name,
+                fields,
+                &message.oneof_decl,
+                oneof_fields,
+                &map_types,
+                extension_set,
+                unknown_fields,
+            );
+        }
+
+        self.append_projections(&message_name, &fq_message_name);
+
+        let extensions = if self.config.enable_extensions {
+            message.extension
+        } else {
+            Vec::new()
+        };
+
+        if !message.enum_type.is_empty()
+            || !nested_types.is_empty()
+            || !oneof_fields.is_empty()
+            || !extensions.is_empty()
+        {
             self.push_mod(&message_name);
             self.path.push(3);
             for (nested_type, idx) in nested_types {
@@ -253,8 +377,24 @@ impl<'a> CodeGenerator<'a> {
                     Some(fields) => fields,
                     None => continue,
                 };
+                let oneof_view = if view {
+                    Some((oneof.clone(), fields.clone()))
+                } else {
+                    None
+                };
                 self.append_oneof(&fq_message_name, oneof, idx, fields);
+                if let Some((oneof, fields)) = oneof_view {
+                    self.append_oneof_view(&fq_message_name, &oneof, &fields);
+                }
+            }
+
+            self.path.push(6);
+            for (idx, extension) in extensions.into_iter().enumerate() {
+                self.path.push(idx as i32);
+                self.append_extension(extension);
+                self.path.pop();
             }
+            self.path.pop();
 
             self.pop_mod();
         }
@@ -264,6 +404,106 @@ impl<'a> CodeGenerator<'a> {
         }
     }
 
+    fn append_extension_set(&mut self, ranges: &[ExtensionRange]) {
+        let ranges = ranges
+            .iter()
+            .map(|range| format!("{}..{}", range.start(), range.end()))
+            .join(", ");
+        self.push_indent();
+        self.buf
+            .push_str(&format!("#[prost(extensions=\"{}\")]\n", ranges));
+        self.push_indent();
+        self.buf.push_str(&format!(
+            "pub extension_set: {}::ExtensionSet,\n",
+            self.config.prost_path.as_deref().unwrap_or("::prost")
+        ));
+    }
+
+    fn append_extension(&mut self, field: FieldDescriptorProto) {
+        debug!("  extension: {:?}", field.name());
+
+        let fq_name = self.fq_name(field.name());
+        let prost_path = self.config.prost_path.as_deref().unwrap_or("::prost");
+
+        let codec = match field.r#type() {
+            Type::Float => Cow::Borrowed("Float"),
+            Type::Double => Cow::Borrowed("Double"),
+            Type::Int32 => Cow::Borrowed("Int32"),
+            Type::Int64 => Cow::Borrowed("Int64"),
+            Type::Uint32 => Cow::Borrowed("Uint32"),
+            Type::Uint64 => Cow::Borrowed("Uint64"),
+            Type::Sint32 => Cow::Borrowed("Sint32"),
+            Type::Sint64 => Cow::Borrowed("Sint64"),
+            Type::Fixed32 => Cow::Borrowed("Fixed32"),
+            Type::Fixed64 => Cow::Borrowed("Fixed64"),
+            Type::Sfixed32 => Cow::Borrowed("Sfixed32"),
+            Type::Sfixed64 => Cow::Borrowed("Sfixed64"),
+            Type::Bool => Cow::Borrowed("Bool"),
+            Type::String => Cow::Borrowed("String"),
+            Type::Bytes => Cow::Borrowed("Bytes"),
+            Type::Enum => Cow::Borrowed("Enumeration"),
+            Type::Group => Cow::Owned(format!("Group<{}>", self.resolve_ident(field.type_name()))),
+            Type::Message => Cow::Owned(format!(
+                "Message<{}>",
+                self.resolve_ident(field.type_name())
+            )),
+        };
+        let codec = format!("{}::extension::{}", prost_path, codec);
+        let codec = if field.label() == Label::Repeated {
+            let packed = can_pack(&field)
+                && field
+                    .options
+                    .as_ref()
+                    .map_or(self.syntax == Syntax::Proto3, |options| options.packed());
+            format!(
+                "{}::extension::{}<{}>",
+                prost_path,
+                if packed { "Packed" } else { "Repeated" },
+                codec
+            )
+        } else {
+            codec
+        };
+
+        let declaration = format!(
+            "pub const {}: {}::Extension<{}, {}> = {}::Extension::new({}, \"{}\");\n",
+            to_snake(field.name()).to_uppercase(),
+            prost_path,
+            self.resolve_ident(field.extendee()),
+            codec,
+            prost_path,
+            field.number(),
+            fq_name.trim_start_matches('.'),
+        );
+
+        self.append_doc(&fq_name, None);
+        self.push_indent();
+        self.buf.push_str(&declaration);
+    }
+
+    fn append_unknown_fields(&mut self) {
+        self.push_indent();
+        self.buf.push_str("#[prost(unknown_fields)]\n");
+        self.push_indent();
+        self.buf.push_str(&format!(
+            "pub unknown_fields: {}::UnknownFieldSet,\n",
+            self.config.prost_path.as_deref().unwrap_or("::prost")
+        ));
+    }
+
+    fn append_cached_size(&mut self) {
+        self.push_indent();
+        self.buf
+            .push_str("/// The encoded length of the message, held while it is encoded.\n");
+        self.push_indent();
+        self.buf.push_str("#[prost(cached_size)]\n");
+        self.push_indent();
+        self.buf.push_str(&format!(
+            "pub cached_size: {}::CachedSize,\n",
+            self.config.prost_path.as_deref().unwrap_or("::prost")
+        ));
+    }
+
     fn append_type_name(&mut self, message_name: &str, fq_message_name: &str) {
         self.buf.push_str(&format!(
             "impl {}::Name for {} {{\n",
@@ -366,8 +606,16 @@ impl<'a> CodeGenerator<'a> {
         let type_ = field.r#type();
         let repeated = field.label == Some(Label::Repeated as i32);
         let deprecated = self.deprecated(&field);
-        let optional = self.optional(&field);
-        let ty = self.resolve_type(&field, fq_message_name);
+        let optional = self.optional(&field, fq_message_name);
+        let lazy = self.lazy(&field);
+        let mut ty = self.resolve_type(&field, fq_message_name);
+        if lazy {
+            ty = format!(
+                "{}::Lazy<{}>",
+                self.config.prost_path.as_deref().unwrap_or("::prost"),
+                ty
+            );
+        }
 
         let boxed = !repeated
             && ((type_ == Type::Message || type_ == Type::Group)
@@ -399,6 +647,10 @@ impl<'a> CodeGenerator<'a> {
         let type_tag = self.field_type_tag(&field);
         self.buf.push_str(&type_tag);
 
+        if lazy {
+            self.buf.push_str(", lazy");
+        }
+
         if type_ == Type::Bytes {
             let bytes_type = self
                 .config
@@ -416,7 +668,12 @@ impl<'a> CodeGenerator<'a> {
                     self.buf.push_str(", optional");
                 }
             }
-            Label::Required => self.buf.push_str(", required"),
+            Label::Required => {
+                self.buf.push_str(", required");
+                if optional {
+                    self.buf.push_str(", presence");
+                }
+            }
             Label::Repeated => {
                 self.buf.push_str(", repeated");
                 if can_pack(&field)
@@ -542,120 +799,1672 @@ impl<'a> CodeGenerator<'a> {
         ));
     }
 
-    fn append_oneof_field(
-        &mut self,
-        message_name: &str,
-        fq_message_name: &str,
-        oneof: &OneofDescriptorProto,
-        fields: &[(FieldDescriptorProto, usize)],
-    ) {
-        let name = format!(
-            "{}::{}",
-            to_snake(message_name),
-            to_upper_camel(oneof.name())
-        );
-        self.append_doc(fq_message_name, None);
+    fn append_oneof_field(
+        &mut self,
+        message_name: &str,
+        fq_message_name: &str,
+        oneof: &OneofDescriptorProto,
+        fields: &[(FieldDescriptorProto, usize)],
+    ) {
+        let name = format!(
+            "{}::{}",
+            to_snake(message_name),
+            to_upper_camel(oneof.name())
+        );
+        self.append_doc(fq_message_name, None);
+        self.push_indent();
+        self.buf.push_str(&format!(
+            "#[prost(oneof=\"{}\", tags=\"{}\")]\n",
+            name,
+            fields
+                .iter()
+                .map(|&(ref field, _)| field.number())
+                .join(", ")
+        ));
+        self.append_field_attributes(fq_message_name, oneof.name());
+        self.push_indent();
+        self.buf.push_str(&format!(
+            "pub {}: ::core::option::Option<{}>,\n",
+            to_snake(oneof.name()),
+            name
+        ));
+    }
+
+    fn append_oneof(
+        &mut self,
+        fq_message_name: &str,
+        oneof: OneofDescriptorProto,
+        idx: i32,
+        fields: Vec<(FieldDescriptorProto, usize)>,
+    ) {
+        self.path.push(8);
+        self.path.push(idx);
+        self.append_doc(fq_message_name, None);
+        self.path.pop();
+        self.path.pop();
+
+        let oneof_name = format!("{}.{}", fq_message_name, oneof.name());
+        self.append_type_attributes(&oneof_name);
+        self.append_enum_attributes(&oneof_name);
+        self.push_indent();
+        self.buf
+            .push_str("#[allow(clippy::derive_partial_eq_without_eq)]\n");
+        self.buf.push_str(&format!(
+            "#[derive(Clone, PartialEq, {}::Oneof)]\n",
+            self.config.prost_path.as_deref().unwrap_or("::prost")
+        ));
+        self.append_skip_debug(&fq_message_name);
+        self.push_indent();
+        self.buf.push_str("pub enum ");
+        self.buf.push_str(&to_upper_camel(oneof.name()));
+        self.buf.push_str(" {\n");
+
+        self.path.push(2);
+        self.depth += 1;
+        for (field, idx) in fields {
+            let type_ = field.r#type();
+
+            self.path.push(idx as i32);
+            self.append_doc(fq_message_name, Some(field.name()));
+            self.path.pop();
+
+            self.push_indent();
+            let ty_tag = self.field_type_tag(&field);
+            let lazy = self.lazy(&field);
+            self.buf.push_str(&format!(
+                "#[prost({}{}, tag=\"{}\")]\n",
+                ty_tag,
+                if lazy { ", lazy" } else { "" },
+                field.number()
+            ));
+            self.append_field_attributes(&oneof_name, field.name());
+
+            self.push_indent();
+            let mut ty = self.resolve_type(&field, fq_message_name);
+            if lazy {
+                ty = format!(
+                    "{}::Lazy<{}>",
+                    self.config.prost_path.as_deref().unwrap_or("::prost"),
+                    ty
+                );
+            }
+
+            let boxed = ((type_ == Type::Message || type_ == Type::Group)
+                && self
+                    .message_graph
+                    .is_nested(field.type_name(), fq_message_name))
+                || (self
+                    .config
+                    .boxed
+                    .get_first_field(&oneof_name, field.name())
+                    .is_some());
+
+            debug!(
+                "    oneof: {:?}, type: {:?}, boxed: {}",
+                field.name(),
+                ty,
+                boxed
+            );
+
+            if boxed {
+                self.buf.push_str(&format!(
+                    "{}(::prost::alloc::boxed::Box<{}>),\n",
+                    to_upper_camel(field.name()),
+                    ty
+                ));
+            } else {
+                self.buf
+                    .push_str(&format!("{}({}),\n", to_upper_camel(field.name()), ty));
+            }
+        }
+        self.depth -= 1;
+        self.path.pop();
+
+        self.push_indent();
+        self.buf.push_str("}\n");
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn append_message_view(
+        &mut self,
+        message_name: &str,
+        fq_message_name: &str,
+        fields: &[(FieldDescriptorProto, usize)],
+        oneof_decl: &[OneofDescriptorProto],
+        oneof_fields: &MultiMap<i32, (FieldDescriptorProto, usize)>,
+        map_types: &HashMap<String, (FieldDescriptorProto, FieldDescriptorProto)>,
+        default_rest: bool,
+    ) {
+        let prost_path = &self
+            .config
+            .prost_path
+            .as_deref()
+            .unwrap_or("::prost")
+            .to_string();
+        let name = to_upper_camel(message_name);
+        debug!("  message view: {:?}", name);
+
+        // The declaration, decoding and conversion of each field of the view.
+        let mut view_fields = Vec::new();
+        let mut merge_arms = Vec::new();
+        let mut conversions = Vec::new();
+        // Whether a field uses the lifetime of the view other than through views of messages,
+        // which may be recursive.
+        let mut borrows = false;
+
+        for (field, _) in fields {
+            let field_name = to_snake(field.name());
+            let map_entry = field
+                .type_name
+                .as_ref()
+                .and_then(|type_name| map_types.get(type_name));
+
+            let (ty, merge, conversion) = if let Some((key, value)) = map_entry {
+                let key_kind = self.view_kind(key, fq_message_name);
+                let value_kind = self.view_kind(value, fq_message_name);
+                borrows |= key_kind.borrows() || value_kind.borrows();
+                let value_default = if value.r#type() == Type::Enum {
+                    format!(
+                        "{}::default() as i32",
+                        self.resolve_ident(value.type_name())
+                    )
+                } else {
+                    "::core::default::Default::default()".to_string()
+                };
+                (
+                    format!(
+                        "{}::alloc::vec::Vec<({}, {})>",
+                        prost_path,
+                        self.view_type(&key_kind, key, fq_message_name),
+                        self.view_type(&value_kind, value, fq_message_name),
+                    ),
+                    format!(
+                        "{}::view::merge_map({}, {}, {}, &mut self.{}, buf, ctx)",
+                        prost_path,
+                        key_kind.merge_fn(prost_path),
+                        value_kind.merge_fn(prost_path),
+                        value_default,
+                        field_name,
+                    ),
+                    if let ViewKind::View(_) = value_kind {
+                        format!(
+                            "self.{}.iter().map(|(k, v)| ::core::result::Result::Ok(({}, {}))).collect::<::core::result::Result<_, {}::DecodeError>>()?",
+                            field_name,
+                            key_kind.to_owned(prost_path, "k", true, false),
+                            value_kind.to_owned(prost_path, "v", true, false),
+                            prost_path,
+                        )
+                    } else {
+                        format!(
+                            "self.{}.iter().map(|(k, v)| ({}, {})).collect()",
+                            field_name,
+                            key_kind.to_owned(prost_path, "k", true, false),
+                            value_kind.to_owned(prost_path, "v", true, false),
+                        )
+                    },
+                )
+            } else {
+                let kind = self.view_kind(field, fq_message_name);
+                let repeated = field.label() == Label::Repeated;
+                let optional = self.optional(field, fq_message_name);
+                let boxed = !repeated && self.boxed(field, fq_message_name, fq_message_name);
+                let view_boxed = boxed && kind.is_message();
+                borrows |= kind.borrows() || (repeated && kind.has_lifetime());
+
+                let mut ty = self.view_type(&kind, field, fq_message_name);
+                if view_boxed {
+                    ty = format!("{}::alloc::boxed::Box<{}>", prost_path, ty);
+                }
+
+                if repeated {
+                    match kind {
+                        ViewKind::Scalar(ref tag) => (
+                            format!("{}::alloc::vec::Vec<{}>", prost_path, ty),
+                            format!(
+                                "{}::encoding::{}::merge_repeated(wire_type, &mut self.{}, buf, ctx)",
+                                prost_path, tag, field_name,
+                            ),
+                            format!("self.{}.clone()", field_name),
+                        ),
+                        ViewKind::Message | ViewKind::Lazy => (
+                            format!("{}::alloc::vec::Vec<{}>", prost_path, ty),
+                            if field.r#type() == Type::Group {
+                                format!(
+                                    "{}::encoding::group::merge_repeated(tag, wire_type, &mut self.{}, buf, ctx)",
+                                    prost_path, field_name,
+                                )
+                            } else {
+                                format!(
+                                    "{}::encoding::{}::merge_repeated(wire_type, &mut self.{}, buf, ctx)",
+                                    prost_path,
+                                    if matches!(kind, ViewKind::Lazy) { "lazy" } else { "message" },
+                                    field_name,
+                                )
+                            },
+                            format!("self.{}.clone()", field_name),
+                        ),
+                        _ => (
+                            format!("{}::view::RepeatedView<'a, {}>", prost_path, ty),
+                            format!("self.{}.merge(tag, wire_type, buf, ctx)", field_name),
+                            format!(
+                                "self.{}.iter().map(|v| {{ let v = v?; ::core::result::Result::Ok({}) }}).collect::<::core::result::Result<_, {}::DecodeError>>()?",
+                                field_name,
+                                kind.to_owned(prost_path, "v", false, false),
+                                prost_path,
+                            ),
+                        ),
+                    }
+                } else if optional {
+                    let target = if view_boxed {
+                        format!(
+                            "&mut **self.{}.get_or_insert_with(::core::default::Default::default)",
+                            field_name
+                        )
+                    } else {
+                        format!(
+                            "self.{}.get_or_insert_with(::core::default::Default::default)",
+                            field_name
+                        )
+                    };
+                    let conversion = match kind {
+                        ViewKind::Scalar(_) if !boxed => format!("self.{}", field_name),
+                        ViewKind::Str | ViewKind::Bytes(_) if !boxed => {
+                            format!("self.{}.map({})", field_name, kind.to_owned_fn(prost_path))
+                        }
+                        ViewKind::View(_) if !boxed => format!(
+                            "self.{}.as_ref().map({}).transpose()?",
+                            field_name,
+                            kind.to_owned_fn(prost_path)
+                        ),
+                        ViewKind::View(_) => format!(
+                            "self.{}.as_ref().map(|v| ::core::result::Result::<_, {}::DecodeError>::Ok({})).transpose()?",
+                            field_name,
+                            prost_path,
+                            kind.to_owned(prost_path, "v", true, boxed),
+                        ),
+                        ViewKind::Message | ViewKind::Lazy => {
+                            format!("self.{}.clone()", field_name)
+                        }
+                        _ => format!(
+                            "self.{}.as_ref().map(|v| {})",
+                            field_name,
+                            kind.to_owned(prost_path, "v", true, boxed),
+                        ),
+                    };
+                    (
+                        format!("::core::option::Option<{}>", ty),
+                        kind.merge(prost_path, field, &target),
+                        conversion,
+                    )
+                } else {
+                    let target = if view_boxed {
+                        format!("&mut *self.{}", field_name)
+                    } else {
+                        format!("&mut self.{}", field_name)
+                    };
+                    (
+                        ty,
+                        kind.merge(prost_path, field, &target),
+                        kind.to_owned(prost_path, &format!("self.{}", field_name), false, boxed),
+                    )
+                }
+            };
+
+            view_fields.push(format!("pub {}: {},", field_name, ty));
+            merge_arms.push((field.number().to_string(), merge, field_name.clone()));
+            conversions.push(format!("{}: {},", field_name, conversion));
+        }
+
+        for (idx, oneof) in oneof_decl.iter().enumerate() {
+            let fields = match oneof_fields.get_vec(&(idx as i32)) {
+                Some(fields) => fields,
+                None => continue,
+            };
+            let field_name = to_snake(oneof.name());
+            let view_name = format!(
+                "{}::{}View",
+                to_snake(message_name),
+                to_upper_camel(oneof.name())
+            );
+            let kinds = fields
+                .iter()
+                .map(|(field, _)| self.view_kind(field, fq_message_name))
+                .collect::<Vec<_>>();
+            borrows |= kinds.iter().any(ViewKind::borrows);
+
+            view_fields.push(format!(
+                "pub {}: ::core::option::Option<{}{}>,",
+                field_name,
+                view_name,
+                if kinds.iter().any(ViewKind::has_lifetime) {
+                    "<'a>"
+                } else {
+                    ""
+                },
+            ));
+            let tags = fields
+                .iter()
+                .map(|(field, _)| field.number())
+                .sorted()
+                .collect::<Vec<_>>();
+            let (first, last) = (tags[0], tags[tags.len() - 1]);
+            merge_arms.push((
+                if tags.len() > 1 && last - first + 1 == tags.len() as i32 {
+                    format!("{}..={}", first, last)
+                } else {
+                    tags.iter().join(" | ")
+                },
+                format!(
+                    "{}::merge(&mut self.{}, tag, wire_type, buf, ctx)",
+                    view_name, field_name
+                ),
+                field_name.clone(),
+            ));
+            conversions.push(format!(
+                "{}: self.{}.as_ref().map({}::to_oneof).transpose()?,",
+                field_name, field_name, view_name
+            ));
+        }
+
+        if !borrows {
+            view_fields.push("#[doc(hidden)]".to_string());
+            view_fields.push("pub _marker: ::core::marker::PhantomData<&'a ()>,".to_string());
+        }
+        if default_rest {
+            conversions.push("..::core::default::Default::default()".to_string());
+        }
+
+        // The view struct.
+        self.push_indent();
+        self.buf
+            .push_str(&format!("/// A zero-copy view of [`{}`].\n", name));
+        self.push_indent();
+        self.buf
+            .push_str("#[allow(clippy::derive_partial_eq_without_eq)]\n");
+        self.push_indent();
+        self.buf
+            .push_str("#[derive(Clone, PartialEq, Debug, Default)]\n");
+        self.push_indent();
+        self.buf
+            .push_str(&format!("pub struct {}View<'a> {{\n", name));
+        self.depth += 1;
+        for view_field in view_fields {
+            self.push_indent();
+            self.buf.push_str(&view_field);
+            self.buf.push('\n');
+        }
+        self.depth -= 1;
+        self.push_indent();
+        self.buf.push_str("}\n");
+
+        // The `MessageView` implementation.
+        self.push_indent();
+        self.buf.push_str("#[allow(deprecated)]\n");
+        self.push_indent();
+        self.buf.push_str(&format!(
+            "impl<'a> {}::view::MessageView<'a> for {}View<'a> {{\n",
+            prost_path, name
+        ));
+        self.depth += 1;
+        self.push_indent();
+        self.buf.push_str(&format!("type Owned = {};\n", name));
+
+        self.push_indent();
+        self.buf.push_str(&format!(
+            "fn merge_field(&mut self, tag: u32, wire_type: {p}::encoding::WireType, buf: &mut &'a [u8], ctx: {p}::encoding::DecodeContext) -> ::core::result::Result<(), {p}::DecodeError> {{\n",
+            p = prost_path
+        ));
+        self.depth += 1;
+        let skip_field = format!(
+            "{}::encoding::skip_field(wire_type, tag, buf, ctx)",
+            prost_path
+        );
+        if merge_arms.is_empty() {
+            self.push_indent();
+            self.buf.push_str(&skip_field);
+            self.buf.push('\n');
+        } else {
+            self.push_indent();
+            self.buf
+                .push_str(&format!("const STRUCT_NAME: &str = \"{}\";\n", name));
+            self.push_indent();
+            self.buf.push_str("match tag {\n");
+            self.depth += 1;
+            for (tags, merge, field_name) in merge_arms {
+                self.push_indent();
+                self.buf.push_str(&format!(
+                    "{} => {}.map_err(|mut error| {{ error.push(STRUCT_NAME, \"{}\"); error }}),\n",
+                    tags, merge, field_name
+                ));
+            }
+            self.push_indent();
+            self.buf.push_str(&format!("_ => {},\n", skip_field));
+            self.depth -= 1;
+            self.push_indent();
+            self.buf.push_str("}\n");
+        }
+        self.depth -= 1;
+        self.push_indent();
+        self.buf.push_str("}\n");
+
+        self.push_indent();
+        self.buf.push_str(&format!(
+            "fn to_message(&self) -> ::core::result::Result<{}, {}::DecodeError> {{\n",
+            name, prost_path
+        ));
+        self.depth += 1;
+        self.push_indent();
+        self.buf
+            .push_str(&format!("::core::result::Result::Ok({} {{\n", name));
+        self.depth += 1;
+        for conversion in conversions {
+            self.push_indent();
+            self.buf.push_str(&conversion);
+            self.buf.push('\n');
+        }
+        self.depth -= 1;
+        self.push_indent();
+        self.buf.push_str("})\n");
+        self.depth -= 1;
+        self.push_indent();
+        self.buf.push_str("}\n");
+        self.depth -= 1;
+        self.push_indent();
+        self.buf.push_str("}\n");
+    }
+
+    fn append_oneof_view(
+        &mut self,
+        fq_message_name: &str,
+        oneof: &OneofDescriptorProto,
+        fields: &[(FieldDescriptorProto, usize)],
+    ) {
+        let prost_path = &self
+            .config
+            .prost_path
+            .as_deref()
+            .unwrap_or("::prost")
+            .to_string();
+        let oneof_name = format!("{}.{}", fq_message_name, oneof.name());
+        let name = to_upper_camel(oneof.name());
+        let view_name = format!("{}View", name);
+        let lifetime = if fields
+            .iter()
+            .any(|(field, _)| self.view_kind(field, fq_message_name).has_lifetime())
+        {
+            "<'a>"
+        } else {
+            ""
+        };
+        debug!("  oneof view: {:?}", view_name);
+
+        let mut variants = Vec::new();
+        let mut merge_arms = Vec::new();
+        let mut conversions = Vec::new();
+        for (field, _) in fields {
+            let variant = to_upper_camel(field.name());
+            let kind = self.view_kind(field, fq_message_name);
+            let boxed = self.boxed(field, fq_message_name, &oneof_name);
+            let view_boxed = boxed && kind.is_message();
+
+            let ty = self.view_type(&kind, field, fq_message_name);
+            if view_boxed {
+                variants.push(format!(
+                    "{}({}::alloc::boxed::Box<{}>),",
+                    variant, prost_path, ty
+                ));
+            } else {
+                variants.push(format!("{}({}),", variant, ty));
+            }
+
+            let (target, owned_value) = if view_boxed {
+                (
+                    "&mut **value",
+                    format!("{}::alloc::boxed::Box::new(owned_value)", prost_path),
+                )
+            } else {
+                ("value", "owned_value".to_string())
+            };
+            merge_arms.push(format!(
+                "{tag} => match field {{ \
+                    ::core::option::Option::Some({view}::{variant}(value)) => {merge}, \
+                    _ => {{ \
+                        let mut owned_value = ::core::default::Default::default(); \
+                        {merge_owned}.map(|_| *field = ::core::option::Option::Some({view}::{variant}({owned_value}))) \
+                    }}, \
+                }},",
+                tag = field.number(),
+                view = view_name,
+                variant = variant,
+                merge = kind.merge(prost_path, field, target),
+                merge_owned = kind.merge(prost_path, field, "&mut owned_value"),
+                owned_value = owned_value,
+            ));
+            conversions.push(format!(
+                "{}::{}(value) => {}::{}({}),",
+                view_name,
+                variant,
+                name,
+                variant,
+                kind.to_owned(prost_path, "value", true, boxed),
+            ));
+        }
+
+        self.push_indent();
+        self.buf
+            .push_str(&format!("/// A zero-copy view of [`{}`].\n", name));
+        self.push_indent();
+        self.buf
+            .push_str("#[allow(clippy::derive_partial_eq_without_eq)]\n");
+        self.push_indent();
+        self.buf.push_str("#[derive(Clone, PartialEq, Debug)]\n");
+        self.push_indent();
+        self.buf
+            .push_str(&format!("pub enum {}{} {{\n", view_name, lifetime));
+        self.depth += 1;
+        for variant in variants {
+            self.push_indent();
+            self.buf.push_str(&variant);
+            self.buf.push('\n');
+        }
+        self.depth -= 1;
+        self.push_indent();
+        self.buf.push_str("}\n");
+
+        self.push_indent();
+        self.buf.push_str("#[allow(deprecated)]\n");
+        self.push_indent();
+        self.buf
+            .push_str(&format!("impl{l} {v}{l} {{\n", l = lifetime, v = view_name));
+        self.depth += 1;
+        self.push_indent();
+        self.buf.push_str(
+            "/// Decodes a field of the oneof from a buffer, and merges it into `field`.\n",
+        );
+        self.push_indent();
+        self.buf.push_str(&format!(
+            "pub fn merge(field: &mut ::core::option::Option<Self>, tag: u32, wire_type: {p}::encoding::WireType, buf: &mut &{l}[u8], ctx: {p}::encoding::DecodeContext) -> ::core::result::Result<(), {p}::DecodeError> {{\n",
+            p = prost_path,
+            l = if lifetime.is_empty() { "" } else { "'a " },
+        ));
+        self.depth += 1;
+        self.push_indent();
+        self.buf.push_str("match tag {\n");
+        self.depth += 1;
+        for arm in merge_arms {
+            self.push_indent();
+            self.buf.push_str(&arm);
+            self.buf.push('\n');
+        }
+        self.push_indent();
+        self.buf.push_str(&format!(
+            "_ => unreachable!(\"invalid {} tag: {{}}\", tag),\n",
+            view_name
+        ));
+        self.depth -= 1;
+        self.push_indent();
+        self.buf.push_str("}\n");
+        self.depth -= 1;
+        self.push_indent();
+        self.buf.push_str("}\n");
+
+        self.push_indent();
+        self.buf.push_str(&format!(
+            "/// Converts the view to an owned [`{}`], copying the borrowed fields.\n",
+            name
+        ));
+        self.push_indent();
+        self.buf.push_str(&format!(
+            "pub fn to_oneof(&self) -> ::core::result::Result<{}, {}::DecodeError> {{\n",
+            name, prost_path
+        ));
+        self.depth += 1;
+        self.push_indent();
+        self.buf
+            .push_str("::core::result::Result::Ok(match self {\n");
+        self.depth += 1;
+        for conversion in conversions {
+            self.push_indent();
+            self.buf.push_str(&conversion);
+            self.buf.push('\n');
+        }
+        self.depth -= 1;
+        self.push_indent();
+        self.buf.push_str("})\n");
+        self.depth -= 1;
+        self.push_indent();
+        self.buf.push_str("}\n");
+        self.depth -= 1;
+        self.push_indent();
+        self.buf.push_str("}\n");
+    }
+
+    /// Appends the `TextFormat` implementation of a message.
+    #[allow(clippy::too_many_arguments)]
+    fn append_text_format(
+        &mut self,
+        message_name: &str,
+        fq_message_name: &str,
+        fields: &[(FieldDescriptorProto, usize)],
+        oneof_decl: &[OneofDescriptorProto],
+        oneof_fields: &MultiMap<i32, (FieldDescriptorProto, usize)>,
+        map_types: &HashMap<String, (FieldDescriptorProto, FieldDescriptorProto)>,
+        extension_set: bool,
+        unknown_fields: bool,
+    ) {
+        let prost_path = &self
+            .config
+            .prost_path
+            .as_deref()
+            .unwrap_or("::prost")
+            .to_string();
+        let name = to_upper_camel(message_name);
+        debug!("  text format: {:?}", name);
+
+        // The printing code and the parsing match arms of each field, with the number the field is
+        // printed in order of.
+        let mut printed_fields = Vec::new();
+        let mut merge_arms = Vec::new();
+
+        for (field, _) in fields {
+            let ident = to_snake(field.name());
+            let text_name = text_format_name(field);
+
+            if let Some((_, value)) = field
+                .type_name
+                .as_ref()
+                .and_then(|type_name| map_types.get(type_name))
+            {
+                let value_kind = self.text_kind(value);
+                let map_type = self
+                    .config
+                    .map_type
+                    .get_first_field(fq_message_name, field.name())
+                    .copied()
+                    .unwrap_or_default();
+                // Hash map entries are printed in key order, so that the output is deterministic.
+                let entries = match map_type {
+                    MapType::HashMap => format!(
+                        "{{ let mut entries = self.{}.iter().collect::<{}::alloc::vec::Vec<_>>(); \
+                         entries.sort_unstable_by(|a, b| a.0.cmp(b.0)); entries }}",
+                        ident, prost_path
+                    ),
+                    MapType::BTreeMap => format!("&self.{}", ident),
+                };
+                printed_fields.push((
+                    field.number(),
+                    format!(
+                        "for (key, value) in {} {{ printer.block(\"{}\", |printer| {{ printer.scalar(\"key\", key); {}; }}); }}",
+                        entries,
+                        text_name,
+                        value_kind.print("value", value.number(), "value"),
+                    ),
+                ));
+                merge_arms.push(format!(
+                    "\"{}\" => parser.repeated(true, |parser| {{ \
+                     let mut key = ::core::default::Default::default(); \
+                     let mut value = ::core::default::Default::default(); \
+                     parser.block(|parser, name| {{ match name {{ \
+                     \"key\" => parser.scalar(&mut key)?, \
+                     \"value\" => {}?, \
+                     _ => return ::core::result::Result::Ok(false), \
+                     }} ::core::result::Result::Ok(true) }})?; \
+                     self.{}.insert(key, value); \
+                     ::core::result::Result::Ok(()) }})?,",
+                    text_name,
+                    value_kind.merge("&mut value"),
+                    ident,
+                ));
+                continue;
+            }
+
+            let kind = self.text_kind(field);
+            let boxed = self.boxed(field, fq_message_name, fq_message_name);
+            let (print, merge) = if field.label() == Label::Repeated {
+                (
+                    format!(
+                        "for value in &self.{} {{ {}; }}",
+                        ident,
+                        kind.print(&text_name, field.number(), "value")
+                    ),
+                    kind.merge_repeated(&format!("&mut self.{}", ident)),
+                )
+            } else if self.optional(field, fq_message_name) {
+                let value = if boxed { "&**value" } else { "value" };
+                let target = format!(
+                    "self.{}.get_or_insert_with(::core::default::Default::default)",
+                    ident
+                );
+                let target = if boxed {
+                    format!("&mut **{}", target)
+                } else {
+                    target
+                };
+                (
+                    format!(
+                        "if let ::core::option::Option::Some(value) = &self.{} {{ {}; }}",
+                        ident,
+                        kind.print(&text_name, field.number(), value)
+                    ),
+                    kind.merge(&target),
+                )
+            } else {
+                let (value, target) = if boxed {
+                    (format!("&*self.{}", ident), format!("&mut *self.{}", ident))
+                } else {
+                    (format!("&self.{}", ident), format!("&mut self.{}", ident))
+                };
+                let print = kind.print(&text_name, field.number(), &value);
+                // Fields without presence are printed if they are not set to the default value.
+                let print = if field.label() == Label::Required {
+                    format!("{};", print)
+                } else {
+                    format!(
+                        "if !{}::text_format::is_default({}) {{ {}; }}",
+                        prost_path, value, print
+                    )
+                };
+                (print, kind.merge(&target))
+            };
+            printed_fields.push((field.number(), print));
+            merge_arms.push(format!("\"{}\" => {}?,", text_name, merge));
+        }
+
+        for (idx, oneof) in oneof_decl.iter().enumerate() {
+            let fields = match oneof_fields.get_vec(&(idx as i32)) {
+                Some(fields) => fields,
+                None => continue,
+            };
+            let ident = to_snake(oneof.name());
+            let oneof_path = format!(
+                "{}::{}",
+                to_snake(message_name),
+                to_upper_camel(oneof.name())
+            );
+            let oneof_name = format!("{}.{}", fq_message_name, oneof.name());
+
+            let mut print_arms = Vec::new();
+            for (field, _) in fields {
+                let kind = self.text_kind(field);
+                let boxed = self.boxed(field, fq_message_name, &oneof_name);
+                let text_name = text_format_name(field);
+                let variant = format!("{}::{}", oneof_path, to_upper_camel(field.name()));
+                print_arms.push(format!(
+                    "{}(value) => {},",
+                    variant,
+                    kind.print(
+                        &text_name,
+                        field.number(),
+                        if boxed { "&**value" } else { "value" }
+                    )
+                ));
+                merge_arms.push(format!(
+                    "\"{}\" => {{ let mut value = ::core::default::Default::default(); {}?; \
+                     self.{} = ::core::option::Option::Some({}({})); }}",
+                    text_name,
+                    kind.merge("&mut value"),
+                    ident,
+                    variant,
+                    if boxed {
+                        format!("{}::alloc::boxed::Box::new(value)", prost_path)
+                    } else {
+                        "value".to_string()
+                    }
+                ));
+            }
+            printed_fields.push((
+                fields.iter().map(|(field, _)| field.number()).min().unwrap(),
+                format!(
+                    "if let ::core::option::Option::Some(oneof) = &self.{} {{ match oneof {{ {} }} }}",
+                    ident,
+                    print_arms.join(" ")
+                ),
+            ));
+        }
+        printed_fields.sort_by_key(|&(number, _)| number);
+
+        let mut print_fields: Vec<String> =
+            printed_fields.into_iter().map(|(_, print)| print).collect();
+        if extension_set {
+            print_fields.push("printer.unknown_fields(self.extension_set.iter());".to_string());
+        }
+        if unknown_fields {
+            print_fields.push("printer.unknown_fields(self.unknown_fields.iter());".to_string());
+        }
+
+        self.push_indent();
+        self.buf.push_str("#[allow(deprecated)]\n");
+        self.push_indent();
+        self.buf.push_str(&format!(
+            "impl {}::text_format::TextFormat for {} {{\n",
+            prost_path, name
+        ));
+        self.depth += 1;
+
+        self.push_indent();
+        self.buf.push_str(&format!(
+            "fn print_fields(&self, {}printer: &mut {}::text_format::Printer<'_>) {{\n",
+            if print_fields.is_empty() { "_" } else { "" },
+            prost_path
+        ));
+        self.depth += 1;
+        for print in print_fields {
+            self.push_indent();
+            self.buf.push_str(&print);
+            self.buf.push('\n');
+        }
+        self.depth -= 1;
+        self.push_indent();
+        self.buf.push_str("}\n");
+
+        let unused = if merge_arms.is_empty() { "_" } else { "" };
+        self.push_indent();
+        self.buf.push_str(&format!(
+            "fn merge_text_field(&mut self, {u}name: &str, {u}parser: &mut {p}::text_format::Parser<'_>) -> ::core::result::Result<bool, {p}::text_format::ParseError> {{\n",
+            u = unused,
+            p = prost_path
+        ));
+        self.depth += 1;
+        if merge_arms.is_empty() {
+            self.push_indent();
+            self.buf.push_str("::core::result::Result::Ok(false)\n");
+        } else {
+            self.push_indent();
+            self.buf.push_str("match name {\n");
+            self.depth += 1;
+            for merge in merge_arms {
+                self.push_indent();
+                self.buf.push_str(&merge);
+                self.buf.push('\n');
+            }
+            self.push_indent();
+            self.buf
+                .push_str("_ => return ::core::result::Result::Ok(false),\n");
+            self.depth -= 1;
+            self.push_indent();
+            self.buf.push_str("}\n");
+            self.push_indent();
+            self.buf.push_str("::core::result::Result::Ok(true)\n");
+        }
+        self.depth -= 1;
+        self.push_indent();
+        self.buf.push_str("}\n");
+
+        self.depth -= 1;
+        self.push_indent();
+        self.buf.push_str("}\n");
+    }
+
+    /// Returns the kind of a single value of the field in the text format.
+    fn text_kind(&self, field: &FieldDescriptorProto) -> TextKind {
+        if self.lazy(field) {
+            return TextKind::Lazy;
+        }
+        match field.r#type() {
+            Type::Message | Type::Group => TextKind::Message,
+            Type::Enum => TextKind::Enumeration(self.resolve_ident(field.type_name())),
+            _ => TextKind::Scalar,
+        }
+    }
+
+    /// Appends the `Json` implementation of a message.
+    fn append_json(
+        &mut self,
+        message_name: &str,
+        fq_message_name: &str,
+        fields: &[(FieldDescriptorProto, usize)],
+        oneof_decl: &[OneofDescriptorProto],
+        oneof_fields: &MultiMap<i32, (FieldDescriptorProto, usize)>,
+        map_types: &HashMap<String, (FieldDescriptorProto, FieldDescriptorProto)>,
+    ) {
+        let prost_path = &self
+            .config
+            .prost_path
+            .as_deref()
+            .unwrap_or("::prost")
+            .to_string();
+        let name = to_upper_camel(message_name);
+        debug!("  json: {:?}", name);
+
+        // The writing code and the parsing match arms of each field, with the number the field is
+        // written in order of.
+        let mut written_fields = Vec::new();
+        let mut merge_arms = Vec::new();
+
+        for (field, _) in fields {
+            let ident = to_snake(field.name());
+            let names = JsonNames::new(field);
+
+            if let Some((_, value)) = field
+                .type_name
+                .as_ref()
+                .and_then(|type_name| map_types.get(type_name))
+            {
+                let value_kind = self.json_kind(value);
+                let map_type = self
+                    .config
+                    .map_type
+                    .get_first_field(fq_message_name, field.name())
+                    .copied()
+                    .unwrap_or_default();
+                // Hash map entries are written in key order, so that the output is deterministic.
+                let entries = match map_type {
+                    MapType::HashMap => format!(
+                        "{{ let mut entries = self.{}.iter().collect::<{}::alloc::vec::Vec<_>>(); \
+                         entries.sort_unstable_by(|a, b| a.0.cmp(b.0)); entries }}",
+                        ident, prost_path
+                    ),
+                    MapType::BTreeMap => format!("&self.{}", ident),
+                };
+                written_fields.push((
+                    field.number(),
+                    format!(
+                        "if writer.emit_defaults() || !self.{}.is_empty() {{ {}; }}",
+                        ident,
+                        names.write(&format!(
+                            "|writer| writer.map({}, |writer, value| {})",
+                            entries,
+                            value_kind.write("value")
+                        )),
+                    ),
+                ));
+                merge_arms.push(format!(
+                    "{} => parser.map(|parser, key| {{ self.{}.insert(key, {}?); ::core::result::Result::Ok(()) }})?,",
+                    names.pattern(),
+                    ident,
+                    value_kind.parse()
+                ));
+                continue;
+            }
+
+            let kind = self.json_kind(field);
+            let boxed = self.boxed(field, fq_message_name, fq_message_name);
+            let (write, merge) = if field.label() == Label::Repeated {
+                (
+                    format!(
+                        "if writer.emit_defaults() || !self.{}.is_empty() {{ {}; }}",
+                        ident,
+                        names.write(&format!(
+                            "|writer| writer.list(&self.{}, |writer, value| {})",
+                            ident,
+                            kind.write("value")
+                        ))
+                    ),
+                    format!(
+                        "parser.repeated(&mut self.{}, |parser| {})",
+                        ident,
+                        kind.parse()
+                    ),
+                )
+            } else if self.optional(field, fq_message_name) {
+                let value = if boxed { "&**value" } else { "value" };
+                let parse = if boxed {
+                    format!(
+                        "{}.map({}::alloc::boxed::Box::new)",
+                        kind.parse(),
+                        prost_path
+                    )
+                } else {
+                    kind.parse()
+                };
+                (
+                    format!(
+                        "if let ::core::option::Option::Some(value) = &self.{} {{ {}; }}",
+                        ident,
+                        names.write(&format!("|writer| {}", kind.write(value)))
+                    ),
+                    // A `null` value clears the field, unless `null` is a value of the field.
+                    if kind.nullable() {
+                        format!(
+                            "{}.map(|value| self.{} = ::core::option::Option::Some(value))",
+                            parse, ident
+                        )
+                    } else {
+                        format!("parser.optional(&mut self.{}, |parser| {})", ident, parse)
+                    },
+                )
+            } else {
+                let (value, target) = if boxed {
+                    (format!("&*self.{}", ident), format!("&mut *self.{}", ident))
+                } else {
+                    (format!("&self.{}", ident), format!("&mut self.{}", ident))
+                };
+                let write = names.write(&format!("|writer| {}", kind.write(&value)));
+                // Fields without presence are written if they are not set to the default value,
+                // unless default values are emitted.
+                let write = if field.label() == Label::Required {
+                    format!("{};", write)
+                } else {
+                    format!(
+                        "if writer.emit_defaults() || !{}::json::is_default({}) {{ {}; }}",
+                        prost_path, value, write
+                    )
+                };
+                (
+                    write,
+                    format!("parser.field({}, |parser| {})", target, kind.parse()),
+                )
+            };
+            written_fields.push((field.number(), write));
+            merge_arms.push(format!("{} => {}?,", names.pattern(), merge));
+        }
+
+        for (idx, oneof) in oneof_decl.iter().enumerate() {
+            let fields = match oneof_fields.get_vec(&(idx as i32)) {
+                Some(fields) => fields,
+                None => continue,
+            };
+            let ident = to_snake(oneof.name());
+            let oneof_path = format!(
+                "{}::{}",
+                to_snake(message_name),
+                to_upper_camel(oneof.name())
+            );
+            let oneof_name = format!("{}.{}", fq_message_name, oneof.name());
+
+            let mut write_arms = Vec::new();
+            for (field, _) in fields {
+                let kind = self.json_kind(field);
+                let boxed = self.boxed(field, fq_message_name, &oneof_name);
+                let names = JsonNames::new(field);
+                let variant = format!("{}::{}", oneof_path, to_upper_camel(field.name()));
+                write_arms.push(format!(
+                    "{}(value) => {},",
+                    variant,
+                    names.write(&format!(
+                        "|writer| {}",
+                        kind.write(if boxed { "&**value" } else { "value" })
+                    ))
+                ));
+                merge_arms.push(format!(
+                    "{} => if let ::core::option::Option::Some(value) = parser.oneof(self.{}.is_some(), {}, |parser| {})? {{ \
+                     self.{} = ::core::option::Option::Some({}({})); }},",
+                    names.pattern(),
+                    ident,
+                    kind.nullable(),
+                    kind.parse(),
+                    ident,
+                    variant,
+                    if boxed {
+                        format!("{}::alloc::boxed::Box::new(value)", prost_path)
+                    } else {
+                        "value".to_string()
+                    }
+                ));
+            }
+            written_fields.push((
+                fields.iter().map(|(field, _)| field.number()).min().unwrap(),
+                format!(
+                    "if let ::core::option::Option::Some(oneof) = &self.{} {{ match oneof {{ {} }} }}",
+                    ident,
+                    write_arms.join(" ")
+                ),
+            ));
+        }
+        written_fields.sort_by_key(|&(number, _)| number);
+
+        self.push_indent();
+        self.buf.push_str("#[allow(deprecated)]\n");
+        self.push_indent();
+        self.buf.push_str(&format!(
+            "impl {}::json::Json for {} {{\n",
+            prost_path, name
+        ));
+        self.depth += 1;
+
+        let unused = if written_fields.is_empty() { "_" } else { "" };
+        self.push_indent();
+        self.buf.push_str(&format!(
+            "fn write_json_fields(&self, {u}writer: &mut {p}::json::Writer<'_>) -> ::core::result::Result<(), {p}::json::JsonError> {{\n",
+            u = unused,
+            p = prost_path
+        ));
+        self.depth += 1;
+        for (_, write) in written_fields {
+            self.push_indent();
+            self.buf.push_str(&write);
+            self.buf.push('\n');
+        }
+        self.push_indent();
+        self.buf.push_str("::core::result::Result::Ok(())\n");
+        self.depth -= 1;
+        self.push_indent();
+        self.buf.push_str("}\n");
+
+        let unused = if merge_arms.is_empty() { "_" } else { "" };
+        self.push_indent();
+        self.buf.push_str(&format!(
+            "fn merge_json_field(&mut self, {u}name: &str, {u}parser: &mut {p}::json::Parser<'_>) -> ::core::result::Result<bool, {p}::json::JsonError> {{\n",
+            u = unused,
+            p = prost_path
+        ));
+        self.depth += 1;
+        if merge_arms.is_empty() {
+            self.push_indent();
+            self.buf.push_str("::core::result::Result::Ok(false)\n");
+        } else {
+            self.push_indent();
+            self.buf.push_str("match name {\n");
+            self.depth += 1;
+            for merge in merge_arms {
+                self.push_indent();
+                self.buf.push_str(&merge);
+                self.buf.push('\n');
+            }
+            self.push_indent();
+            self.buf
+                .push_str("_ => return ::core::result::Result::Ok(false),\n");
+            self.depth -= 1;
+            self.push_indent();
+            self.buf.push_str("}\n");
+            self.push_indent();
+            self.buf.push_str("::core::result::Result::Ok(true)\n");
+        }
+        self.depth -= 1;
+        self.push_indent();
+        self.buf.push_str("}\n");
+
+        self.depth -= 1;
+        self.push_indent();
+        self.buf.push_str("}\n");
+    }
+
+    /// Appends the `FieldMasked` implementation of a message.
+    fn append_projections(&mut self, message_name: &str, fq_message_name: &str) {
+        let projections: Vec<(String, Vec<String>)> = self
+            .config
+            .projections
+            .get(fq_message_name)
+            .cloned()
+            .collect();
+        if projections.is_empty() {
+            return;
+        }
+        debug!("  projections: {:?}", message_name);
+
+        self.push_indent();
+        self.buf
+            .push_str(&format!("impl {} {{\n", to_upper_camel(message_name)));
+        self.depth += 1;
+        for (name, fields) in projections {
+            let paths = fields
+                .iter()
+                .map(|field| {
+                    let tags = self.projection_path(fq_message_name, field);
+                    format!(
+                        ".path(&[{}])",
+                        tags.iter()
+                            .map(ToString::to_string)
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    )
+                })
+                .collect::<String>();
+            let names = fields
+                .iter()
+                .map(|field| format!("`{}`", field))
+                .collect::<Vec<_>>()
+                .join(", ");
+
+            self.push_indent();
+            self.buf.push_str(&format!(
+                "/// Returns a projection selecting the fields {}.\n",
+                names
+            ));
+            self.push_indent();
+            self.buf.push_str(&format!(
+                "pub fn {}() -> {p}::Projection {{\n",
+                name,
+                p = self.config.prost_path.as_deref().unwrap_or("::prost")
+            ));
+            self.depth += 1;
+            self.push_indent();
+            self.buf.push_str(&format!(
+                "{}::Projection::new(){}\n",
+                self.config.prost_path.as_deref().unwrap_or("::prost"),
+                paths
+            ));
+            self.depth -= 1;
+            self.push_indent();
+            self.buf.push_str("}\n");
+        }
+        self.depth -= 1;
+        self.push_indent();
+        self.buf.push_str("}\n");
+    }
+
+    /// Resolves a path of field names of a projection to the field numbers of the fields.
+    fn projection_path(&self, fq_message_name: &str, path: &str) -> Vec<u32> {
+        let mut tags = Vec::new();
+        let mut message = self.message_graph.get_message(fq_message_name);
+        for name in path.split('.') {
+            let field = message
+                .and_then(|message| message.field.iter().find(|field| field.name() == name))
+                .unwrap_or_else(|| {
+                    panic!(
+                        "projection path `{}` of message `{}` does not name a field",
+                        path, fq_message_name
+                    )
+                });
+            tags.push(field.number() as u32);
+            message = match field.r#type() {
+                Type::Message | Type::Group => self
+                    .message_graph
+                    .get_message(field.type_name())
+                    .filter(|message| !message.options.as_ref().is_some_and(|o| o.map_entry())),
+                _ => None,
+            };
+        }
+        tags
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn append_field_mask(
+        &mut self,
+        message_name: &str,
+        fq_message_name: &str,
+        fields: &[(FieldDescriptorProto, usize)],
+        oneof_decl: &[OneofDescriptorProto],
+        oneof_fields: &MultiMap<i32, (FieldDescriptorProto, usize)>,
+        map_types: &HashMap<String, (FieldDescriptorProto, FieldDescriptorProto)>,
+        extension_set: bool,
+        unknown_fields: bool,
+    ) {
+        let field_mask = format!(
+            "{}::field_mask",
+            self.config.prost_path.as_deref().unwrap_or("::prost")
+        );
+        let name = to_upper_camel(message_name);
+        debug!("  field mask: {:?}", name);
+
+        // The match arms checking the mask of each field, and the statements merging and pruning
+        // each field.
+        let mut check_arms = Vec::new();
+        let mut merges = Vec::new();
+        let mut prunes = Vec::new();
+        // Whether the merging code uses the merge options, and the pruning code uses the default
+        // value of the message.
+        let mut uses_options = false;
+        let mut uses_default = false;
+
+        for (field, _) in fields {
+            let ident = to_snake(field.name());
+            let is_map = field
+                .type_name
+                .as_ref()
+                .is_some_and(|type_name| map_types.contains_key(type_name));
+            let message = self.field_mask_message(field);
+
+            if is_map || field.label() == Label::Repeated {
+                check_arms.push(format!(
+                    "\"{n}\" => {fm}::check_leaf(\"{n}\", mask)?,",
+                    n = field.name(),
+                    fm = field_mask
+                ));
+                merges.push(format!(
+                    "if mask.get(\"{}\").is_some() {{ {}::{}(&mut self.{}, &src.{}, options); }}",
+                    field.name(),
+                    field_mask,
+                    if is_map {
+                        "merge_map"
+                    } else {
+                        "merge_repeated"
+                    },
+                    ident,
+                    ident
+                ));
+                uses_options = true;
+            } else if let Some(ty) = message {
+                check_arms.push(format!(
+                    "\"{n}\" => {fm}::check_message::<{ty}>(\"{n}\", mask)?,",
+                    n = field.name(),
+                    fm = field_mask,
+                    ty = ty
+                ));
+                uses_options = true;
+                if self.optional(field, fq_message_name) {
+                    merges.push(format!(
+                        "if let ::core::option::Option::Some(mask) = mask.get(\"{}\") {{ \
+                         {}::merge_message(&mut self.{}, src.{}.as_ref(), mask, options); }}",
+                        field.name(),
+                        field_mask,
+                        ident,
+                        ident
+                    ));
+                    prunes.push(format!(
+                        "{}::prune_message(&mut self.{}, mask.get(\"{}\"));",
+                        field_mask,
+                        ident,
+                        field.name()
+                    ));
+                } else {
+                    // Required message fields are not optional in the generated struct.
+                    merges.push(format!(
+                        "if let ::core::option::Option::Some(mask) = mask.get(\"{}\") {{ \
+                         {}::merge_message_value(&mut self.{}, &src.{}, mask, options); }}",
+                        field.name(),
+                        field_mask,
+                        ident,
+                        ident
+                    ));
+                    prunes.push(format!(
+                        "if !{}::prune_message_value(&mut self.{}, mask.get(\"{}\")) {{ self.{} = default.{}; }}",
+                        field_mask,
+                        ident,
+                        field.name(),
+                        ident,
+                        ident
+                    ));
+                    uses_default = true;
+                }
+                continue;
+            } else {
+                check_arms.push(format!(
+                    "\"{n}\" => {fm}::check_leaf(\"{n}\", mask)?,",
+                    n = field.name(),
+                    fm = field_mask
+                ));
+                merges.push(format!(
+                    "if mask.get(\"{}\").is_some() {{ self.{}.clone_from(&src.{}); }}",
+                    field.name(),
+                    ident,
+                    ident
+                ));
+            }
+            prunes.push(format!(
+                "if mask.get(\"{}\").is_none() {{ self.{} = default.{}; }}",
+                field.name(),
+                ident,
+                ident
+            ));
+            uses_default = true;
+        }
+
+        for (idx, oneof) in oneof_decl.iter().enumerate() {
+            let fields = match oneof_fields.get_vec(&(idx as i32)) {
+                Some(fields) => fields,
+                None => continue,
+            };
+            let ident = to_snake(oneof.name());
+            let oneof_path = format!(
+                "{}::{}",
+                to_snake(message_name),
+                to_upper_camel(oneof.name())
+            );
+
+            let mut prune_arms = Vec::new();
+            for (field, _) in fields {
+                let variant = format!("{}::{}", oneof_path, to_upper_camel(field.name()));
+                match self.field_mask_message(field) {
+                    Some(ty) => {
+                        check_arms.push(format!(
+                            "\"{n}\" => {fm}::check_message::<{ty}>(\"{n}\", mask)?,",
+                            n = field.name(),
+                            fm = field_mask,
+                            ty = ty
+                        ));
+                        // The value of the field is taken out of the oneof to be merged, and put
+                        // back if it is still set.
+                        merges.push(format!(
+                            "if let ::core::option::Option::Some(mask) = mask.get(\"{n}\") {{ \
+                             let mut value = match self.{i}.take() {{ \
+                             ::core::option::Option::Some({v}(value)) => ::core::option::Option::Some(value), \
+                             oneof => {{ self.{i} = oneof; ::core::option::Option::None }} }}; \
+                             let src = match &src.{i} {{ \
+                             ::core::option::Option::Some({v}(value)) => ::core::option::Option::Some(value), \
+                             _ => ::core::option::Option::None }}; \
+                             {fm}::merge_message(&mut value, src, mask, options); \
+                             if let ::core::option::Option::Some(value) = value {{ \
+                             self.{i} = ::core::option::Option::Some({v}(value)); }} }}",
+                            n = field.name(),
+                            i = ident,
+                            v = variant,
+                            fm = field_mask
+                        ));
+                        uses_options = true;
+                        prune_arms.push(format!(
+                            "::core::option::Option::Some({}(value)) => {}::prune_message_value(value, mask.get(\"{}\")),",
+                            variant,
+                            field_mask,
+                            field.name()
+                        ));
+                    }
+                    None => {
+                        check_arms.push(format!(
+                            "\"{n}\" => {fm}::check_leaf(\"{n}\", mask)?,",
+                            n = field.name(),
+                            fm = field_mask
+                        ));
+                        merges.push(format!(
+                            "if mask.get(\"{n}\").is_some() {{ match &src.{i} {{ \
+                             ::core::option::Option::Some({v}(_)) => self.{i}.clone_from(&src.{i}), \
+                             _ => if matches!(self.{i}, ::core::option::Option::Some({v}(_))) {{ self.{i} = ::core::option::Option::None; }}, }} }}",
+                            n = field.name(),
+                            i = ident,
+                            v = variant
+                        ));
+                        prune_arms.push(format!(
+                            "::core::option::Option::Some({}(_)) => mask.get(\"{}\").is_some(),",
+                            variant,
+                            field.name()
+                        ));
+                    }
+                }
+            }
+            prunes.push(format!(
+                "let keep = match &mut self.{i} {{ {arms} ::core::option::Option::None => true, }}; \
+                 if !keep {{ self.{i} = ::core::option::Option::None; }}",
+                i = ident,
+                arms = prune_arms.join(" ")
+            ));
+        }
+
+        let uses_mask = !prunes.is_empty();
+        if extension_set {
+            prunes.push("self.extension_set = default.extension_set;".to_string());
+            uses_default = true;
+        }
+        if unknown_fields {
+            prunes.push("self.unknown_fields = default.unknown_fields;".to_string());
+            uses_default = true;
+        }
+
         self.push_indent();
-        self.buf.push_str(&format!(
-            "#[prost(oneof=\"{}\", tags=\"{}\")]\n",
-            name,
-            fields
-                .iter()
-                .map(|&(ref field, _)| field.number())
-                .join(", ")
-        ));
-        self.append_field_attributes(fq_message_name, oneof.name());
+        self.buf.push_str("#[allow(deprecated)]\n");
         self.push_indent();
         self.buf.push_str(&format!(
-            "pub {}: ::core::option::Option<{}>,\n",
-            to_snake(oneof.name()),
+            "impl {}::FieldMasked for {} {{\n",
+            self.config.prost_path.as_deref().unwrap_or("::prost"),
             name
         ));
-    }
+        self.depth += 1;
 
-    fn append_oneof(
-        &mut self,
-        fq_message_name: &str,
-        oneof: OneofDescriptorProto,
-        idx: i32,
-        fields: Vec<(FieldDescriptorProto, usize)>,
-    ) {
-        self.path.push(8);
-        self.path.push(idx);
-        self.append_doc(fq_message_name, None);
-        self.path.pop();
-        self.path.pop();
+        self.push_indent();
+        self.buf.push_str(&format!(
+            "fn check_mask_tree(mask: &{fm}::FieldMaskTree) -> ::core::result::Result<(), {fm}::FieldMaskError> {{\n",
+            fm = field_mask
+        ));
+        self.depth += 1;
+        self.push_indent();
+        if check_arms.is_empty() {
+            self.buf.push_str(&format!(
+                "if let ::core::option::Option::Some((name, _)) = mask.iter().next() {{ \
+                 return ::core::result::Result::Err({}::unknown_field(name)); }}\n",
+                field_mask
+            ));
+        } else {
+            self.buf.push_str("for (name, mask) in mask.iter() {\n");
+            self.depth += 1;
+            self.push_indent();
+            self.buf.push_str("match name {\n");
+            self.depth += 1;
+            for arm in check_arms {
+                self.push_indent();
+                self.buf.push_str(&arm);
+                self.buf.push('\n');
+            }
+            self.push_indent();
+            self.buf.push_str(&format!(
+                "_ => return ::core::result::Result::Err({}::unknown_field(name)),\n",
+                field_mask
+            ));
+            self.depth -= 1;
+            self.push_indent();
+            self.buf.push_str("}\n");
+            self.depth -= 1;
+            self.push_indent();
+            self.buf.push_str("}\n");
+        }
+        self.push_indent();
+        self.buf.push_str("::core::result::Result::Ok(())\n");
+        self.depth -= 1;
+        self.push_indent();
+        self.buf.push_str("}\n");
 
-        let oneof_name = format!("{}.{}", fq_message_name, oneof.name());
-        self.append_type_attributes(&oneof_name);
-        self.append_enum_attributes(&oneof_name);
+        let unused = if merges.is_empty() { "_" } else { "" };
         self.push_indent();
-        self.buf
-            .push_str("#[allow(clippy::derive_partial_eq_without_eq)]\n");
         self.buf.push_str(&format!(
-            "#[derive(Clone, PartialEq, {}::Oneof)]\n",
-            self.config.prost_path.as_deref().unwrap_or("::prost")
+            "fn merge_with_mask_tree(&mut self, {u}src: &Self, {u}mask: &{fm}::FieldMaskTree, {o}options: &{fm}::MergeOptions) {{\n",
+            u = unused,
+            o = if uses_options { "" } else { "_" },
+            fm = field_mask
         ));
-        self.append_skip_debug(&fq_message_name);
+        self.depth += 1;
+        for merge in merges {
+            self.push_indent();
+            self.buf.push_str(&merge);
+            self.buf.push('\n');
+        }
+        self.depth -= 1;
         self.push_indent();
-        self.buf.push_str("pub enum ");
-        self.buf.push_str(&to_upper_camel(oneof.name()));
-        self.buf.push_str(" {\n");
+        self.buf.push_str("}\n");
 
-        self.path.push(2);
+        self.push_indent();
+        self.buf.push_str(&format!(
+            "fn prune_with_mask_tree(&mut self, {}mask: &{}::FieldMaskTree) {{\n",
+            if uses_mask { "" } else { "_" },
+            field_mask
+        ));
         self.depth += 1;
-        for (field, idx) in fields {
-            let type_ = field.r#type();
+        if uses_default {
+            self.push_indent();
+            self.buf.push_str("let default = Self::default();\n");
+        }
+        for prune in prunes {
+            self.push_indent();
+            self.buf.push_str(&prune);
+            self.buf.push('\n');
+        }
+        self.depth -= 1;
+        self.push_indent();
+        self.buf.push_str("}\n");
 
-            self.path.push(idx as i32);
-            self.append_doc(fq_message_name, Some(field.name()));
-            self.path.pop();
+        self.depth -= 1;
+        self.push_indent();
+        self.buf.push_str("}\n");
+    }
 
-            self.push_indent();
-            let ty_tag = self.field_type_tag(&field);
-            self.buf.push_str(&format!(
-                "#[prost({}, tag=\"{}\")]\n",
-                ty_tag,
-                field.number()
-            ));
-            self.append_field_attributes(&oneof_name, field.name());
+    /// Returns the type of a singular message field which field mask paths can descend into, or
+    /// `None` for other fields.
+    fn field_mask_message(&self, field: &FieldDescriptorProto) -> Option<String> {
+        match field.r#type() {
+            Type::Message | Type::Group
+                if field.label() != Label::Repeated && !self.lazy(field) =>
+            {
+                Some(self.resolve_ident(field.type_name()))
+            }
+            _ => None,
+        }
+    }
 
-            self.push_indent();
-            let ty = self.resolve_type(&field, fq_message_name);
+    /// Returns the kind of a single value of the field in JSON.
+    fn json_kind(&self, field: &FieldDescriptorProto) -> JsonKind {
+        if self.lazy(field) {
+            return JsonKind::Lazy;
+        }
+        match field.r#type() {
+            Type::Message | Type::Group if field.type_name() == ".google.protobuf.Value" => {
+                JsonKind::Value
+            }
+            Type::Message | Type::Group => JsonKind::Message,
+            Type::Enum if field.type_name() == ".google.protobuf.NullValue" => JsonKind::NullValue,
+            Type::Enum => JsonKind::Enumeration(self.resolve_ident(field.type_name())),
+            _ => JsonKind::Scalar,
+        }
+    }
 
-            let boxed = ((type_ == Type::Message || type_ == Type::Group)
-                && self
-                    .message_graph
-                    .is_nested(field.type_name(), fq_message_name))
-                || (self
-                    .config
-                    .boxed
-                    .get_first_field(&oneof_name, field.name())
-                    .is_some());
+    /// Returns `true` if the field is boxed in the owned message.
+    fn boxed(
+        &self,
+        field: &FieldDescriptorProto,
+        fq_message_name: &str,
+        config_path: &str,
+    ) -> bool {
+        let type_ = field.r#type();
+        ((type_ == Type::Message || type_ == Type::Group)
+            && self
+                .message_graph
+                .is_nested(field.type_name(), fq_message_name))
+            || self
+                .config
+                .boxed
+                .get_first_field(config_path, field.name())
+                .is_some()
+    }
 
-            debug!(
-                "    oneof: {:?}, type: {:?}, boxed: {}",
-                field.name(),
-                ty,
-                boxed
-            );
+    /// Returns `true` if the field is a message field with the `lazy` option, which is decoded on
+    /// first access.
+    fn lazy(&self, field: &FieldDescriptorProto) -> bool {
+        field.r#type() == Type::Message
+            && field.options.as_ref().is_some_and(|options| options.lazy())
+    }
 
-            if boxed {
-                self.buf.push_str(&format!(
-                    "{}(::prost::alloc::boxed::Box<{}>),\n",
-                    to_upper_camel(field.name()),
-                    ty
-                ));
-            } else {
-                self.buf
-                    .push_str(&format!("{}({}),\n", to_upper_camel(field.name()), ty));
+    fn view_kind(&self, field: &FieldDescriptorProto, fq_message_name: &str) -> ViewKind {
+        if self.lazy(field) {
+            return ViewKind::Lazy;
+        }
+        match field.r#type() {
+            Type::String => ViewKind::Str,
+            Type::Bytes => ViewKind::Bytes(
+                self.config
+                    .bytes_type
+                    .get_first_field(fq_message_name, field.name())
+                    .copied()
+                    .unwrap_or_default(),
+            ),
+            Type::Message | Type::Group => {
+                let type_name = field.type_name();
+                if self.extern_paths.resolve_ident(type_name).is_none()
+                    && self.config.message_views.get_first(type_name).is_some()
+                {
+                    ViewKind::View(format!("{}View", self.resolve_ident(type_name)))
+                } else {
+                    ViewKind::Message
+                }
             }
+            Type::Enum => ViewKind::Scalar(Cow::Borrowed("int32")),
+            _ => ViewKind::Scalar(self.field_type_tag(field)),
         }
-        self.depth -= 1;
-        self.path.pop();
+    }
 
-        self.push_indent();
-        self.buf.push_str("}\n");
+    /// Returns the type of a single value of the field in a message view.
+    fn view_type(
+        &self,
+        kind: &ViewKind,
+        field: &FieldDescriptorProto,
+        fq_message_name: &str,
+    ) -> String {
+        match kind {
+            ViewKind::Scalar(_) | ViewKind::Message => self.resolve_type(field, fq_message_name),
+            ViewKind::Str => "&'a str".to_string(),
+            ViewKind::Bytes(_) => "&'a [u8]".to_string(),
+            ViewKind::View(path) => format!("{}<'a>", path),
+            ViewKind::Lazy => format!(
+                "{}::Lazy<{}>",
+                self.config.prost_path.as_deref().unwrap_or("::prost"),
+                self.resolve_type(field, fq_message_name)
+            ),
+        }
     }
 
     fn location(&self) -> Option<&Location> {
@@ -1021,11 +2830,19 @@ impl<'a> CodeGenerator<'a> {
         }
     }
 
-    fn optional(&self, field: &FieldDescriptorProto) -> bool {
+    fn optional(&self, field: &FieldDescriptorProto, fq_message_name: &str) -> bool {
         if field.proto3_optional.unwrap_or(false) {
             return true;
         }
 
+        if field.label() == Label::Required {
+            return self
+                .config
+                .required_field_presence
+                .get_first_field(fq_message_name, field.name())
+                .is_some();
+        }
+
         if field.label() != Label::Optional {
             return false;
         }
@@ -1254,6 +3071,326 @@ fn build_enum_value_mappings<'a>(
     mappings
 }
 
+/// The kind of a field value in the text format.
+enum TextKind {
+    /// A string, bytes, bool or numeric value.
+    Scalar,
+    /// An enumeration value, with the path of the enumeration type.
+    Enumeration(String),
+    /// A message or group value.
+    Message,
+    /// A lazily decoded message value.
+    Lazy,
+}
+
+impl TextKind {
+    /// Returns a statement printing a value of the field, given a reference to it.
+    fn print(&self, name: &str, number: i32, value: &str) -> String {
+        match self {
+            TextKind::Scalar => format!("printer.scalar(\"{}\", {})", name, value),
+            TextKind::Enumeration(path) => format!(
+                "printer.enumeration(\"{}\", {}, |value| {}::try_from(value).ok().map(|value| value.as_str_name()))",
+                name,
+                value
+                    .strip_prefix('&')
+                    .map_or_else(|| format!("*{}", value), str::to_string),
+                path
+            ),
+            TextKind::Message => format!("printer.message(\"{}\", {})", name, value),
+            TextKind::Lazy => format!("printer.lazy(\"{}\", {}, {})", name, number, value),
+        }
+    }
+
+    /// Returns an expression parsing a value of the field into `target`.
+    fn merge(&self, target: &str) -> String {
+        match self {
+            TextKind::Scalar => format!("parser.scalar({})", target),
+            TextKind::Enumeration(path) => format!(
+                "parser.enumeration({}, |name| {}::from_str_name(name).map(|value| value as i32))",
+                target, path
+            ),
+            TextKind::Message => format!("parser.message({})", target),
+            TextKind::Lazy => format!("parser.lazy({})", target),
+        }
+    }
+
+    /// Returns an expression parsing values of a repeated field into `target`.
+    fn merge_repeated(&self, target: &str) -> String {
+        match self {
+            TextKind::Scalar => format!("parser.repeated_scalar({})", target),
+            TextKind::Enumeration(path) => format!(
+                "parser.repeated_enumeration({}, |name| {}::from_str_name(name).map(|value| value as i32))",
+                target, path
+            ),
+            TextKind::Message => format!("parser.repeated_message({})", target),
+            TextKind::Lazy => format!("parser.repeated_lazy({})", target),
+        }
+    }
+}
+
+/// Returns the name of a field in the text format, which is the name of the message type for
+/// groups.
+fn text_format_name(field: &FieldDescriptorProto) -> String {
+    match field.r#type() {
+        Type::Group => field
+            .type_name()
+            .rsplit('.')
+            .next()
+            .unwrap_or_default()
+            .to_string(),
+        _ => field.name().to_string(),
+    }
+}
+
+/// Returns `true` if the message is a well-known type with a special JSON form, which is
+/// implemented by hand in `prost-types`.
+fn has_json_special_form(fq_message_name: &str) -> bool {
+    matches!(
+        fq_message_name,
+        ".google.protobuf.Any"
+            | ".google.protobuf.Duration"
+            | ".google.protobuf.Timestamp"
+            | ".google.protobuf.FieldMask"
+            | ".google.protobuf.Struct"
+            | ".google.protobuf.Value"
+            | ".google.protobuf.ListValue"
+    )
+}
+
+/// The kind of a field value in JSON.
+enum JsonKind {
+    /// A string, bytes, bool or numeric value.
+    Scalar,
+    /// An enumeration value, with the path of the enumeration type.
+    Enumeration(String),
+    /// A `google.protobuf.NullValue` value, which is `null`.
+    NullValue,
+    /// A message or group value.
+    Message,
+    /// A `google.protobuf.Value` message value, which may be `null`.
+    Value,
+    /// A lazily decoded message value.
+    Lazy,
+}
+
+impl JsonKind {
+    /// Returns an expression writing a value of the field, given a reference to it.
+    fn write(&self, value: &str) -> String {
+        match self {
+            JsonKind::Scalar => format!("writer.scalar({})", value),
+            JsonKind::Enumeration(path) => format!(
+                "writer.enumeration({}, |value| {}::try_from(value).ok().map(|value| value.as_str_name()))",
+                value
+                    .strip_prefix('&')
+                    .map_or_else(|| format!("*{}", value), str::to_string),
+                path
+            ),
+            JsonKind::NullValue => "writer.null()".to_string(),
+            JsonKind::Message | JsonKind::Value => format!("writer.message({})", value),
+            JsonKind::Lazy => format!("writer.lazy({})", value),
+        }
+    }
+
+    /// Returns an expression parsing a value of the field.
+    fn parse(&self) -> String {
+        match self {
+            JsonKind::Scalar => "parser.scalar()".to_string(),
+            JsonKind::Enumeration(path) => format!(
+                "parser.enumeration(|name| {}::from_str_name(name).map(|value| value as i32))",
+                path
+            ),
+            JsonKind::NullValue => "parser.null_value()".to_string(),
+            JsonKind::Message | JsonKind::Value => "parser.message()".to_string(),
+            JsonKind::Lazy => "parser.lazy()".to_string(),
+        }
+    }
+
+    /// Returns `true` if `null` is a value of the field, rather than the absence of one.
+    fn nullable(&self) -> bool {
+        matches!(self, JsonKind::NullValue | JsonKind::Value)
+    }
+}
+
+/// The names of a field in JSON.
+struct JsonNames<'a> {
+    /// The `json_name` of the field, which is the field name in lowerCamelCase by default.
+    json_name: Cow<'a, str>,
+    /// The name of the field in the `.proto` file.
+    proto_name: &'a str,
+}
+
+impl<'a> JsonNames<'a> {
+    fn new(field: &'a FieldDescriptorProto) -> JsonNames<'a> {
+        let json_name = match &field.json_name {
+            Some(json_name) => Cow::Borrowed(json_name.as_str()),
+            None => Cow::Owned(to_json_name(field.name())),
+        };
+        JsonNames {
+            json_name,
+            proto_name: field.name(),
+        }
+    }
+
+    /// Returns a statement writing the field as a member of an object, given a closure writing
+    /// its value.
+    fn write(&self, value: &str) -> String {
+        format!(
+            "writer.field(\"{}\", \"{}\", {})?",
+            self.json_name, self.proto_name, value
+        )
+    }
+
+    /// Returns a match pattern of the names the field is parsed by, which are both the JSON name
+    /// and the `.proto` name.
+    fn pattern(&self) -> String {
+        if self.json_name == self.proto_name {
+            format!("\"{}\"", self.proto_name)
+        } else {
+            format!("\"{}\" | \"{}\"", self.json_name, self.proto_name)
+        }
+    }
+}
+
+/// Returns the default `json_name` of a field, as computed by `protoc`: underscores are removed,
+/// and the letter following each is uppercased.
+fn to_json_name(name: &str) -> String {
+    let mut json_name = String::with_capacity(name.len());
+    let mut capitalize_next = false;
+    for c in name.chars() {
+        if c == '_' {
+            capitalize_next = true;
+        } else if capitalize_next {
+            json_name.push(c.to_ascii_uppercase());
+            capitalize_next = false;
+        } else {
+            json_name.push(c);
+        }
+    }
+    json_name
+}
+
+/// The kind of a field value in a message view.
+enum ViewKind {
+    /// A scalar or enumeration value, decoded by the named `prost::encoding` module.
+    Scalar(Cow<'static, str>),
+    /// A string borrowed from the input.
+    Str,
+    /// Bytes borrowed from the input, converted to the given type in the owned message.
+    Bytes(BytesType),
+    /// A view of a message, with the path of the view type.
+    View(String),
+    /// An owned message, for message types without a view.
+    Message,
+    /// An owned lazily decoded message, for message fields with the `lazy` option.
+    Lazy,
+}
+
+impl ViewKind {
+    /// Returns `true` if the value is borrowed from the input.
+    fn borrows(&self) -> bool {
+        matches!(self, ViewKind::Str | ViewKind::Bytes(_))
+    }
+
+    /// Returns `true` if the type of the value has the lifetime of the view.
+    fn has_lifetime(&self) -> bool {
+        matches!(self, ViewKind::Str | ViewKind::Bytes(_) | ViewKind::View(_))
+    }
+
+    fn is_message(&self) -> bool {
+        matches!(self, ViewKind::View(_) | ViewKind::Message | ViewKind::Lazy)
+    }
+
+    /// Returns the path of the function merging a value of a map entry.
+    fn merge_fn(&self, prost_path: &str) -> String {
+        match self {
+            ViewKind::Scalar(tag) => format!("{}::encoding::{}::merge", prost_path, tag),
+            ViewKind::Str => format!("{}::view::merge_str", prost_path),
+            ViewKind::Bytes(_) => format!("{}::view::merge_bytes", prost_path),
+            ViewKind::View(_) => format!("{}::view::merge_message", prost_path),
+            ViewKind::Message => format!("{}::encoding::message::merge", prost_path),
+            ViewKind::Lazy => format!("{}::encoding::lazy::merge", prost_path),
+        }
+    }
+
+    /// Returns an expression merging a value of the field into `target`.
+    fn merge(&self, prost_path: &str, field: &FieldDescriptorProto, target: &str) -> String {
+        let group = field.r#type() == Type::Group;
+        match self {
+            ViewKind::View(_) if group => format!(
+                "{}::view::merge_group(tag, wire_type, {}, buf, ctx)",
+                prost_path, target
+            ),
+            ViewKind::Message if group => format!(
+                "{}::encoding::group::merge(tag, wire_type, {}, buf, ctx)",
+                prost_path, target
+            ),
+            _ => format!(
+                "{}(wire_type, {}, buf, ctx)",
+                self.merge_fn(prost_path),
+                target
+            ),
+        }
+    }
+
+    /// Returns the path of the function converting a string, bytes or message view value to the
+    /// owned field value.
+    fn to_owned_fn(&self, prost_path: &str) -> String {
+        match self {
+            ViewKind::Str => format!("{}::alloc::string::String::from", prost_path),
+            ViewKind::Bytes(BytesType::Vec) => format!("{}::alloc::vec::Vec::from", prost_path),
+            ViewKind::Bytes(BytesType::Bytes) => {
+                format!("{}::bytes::Bytes::copy_from_slice", prost_path)
+            }
+            ViewKind::View(path) => {
+                format!(
+                    "<{} as {}::view::MessageView>::to_message",
+                    path, prost_path
+                )
+            }
+            ViewKind::Scalar(_) | ViewKind::Message | ViewKind::Lazy => {
+                unreachable!("no conversion function")
+            }
+        }
+    }
+
+    /// Returns an expression converting `value` to the owned field value.
+    ///
+    /// `value` is a reference to the value if `by_ref` is set, and the value itself otherwise. The
+    /// conversion of a message view uses `?`, so it must be in a function or closure returning a
+    /// `Result` with a `DecodeError`.
+    fn to_owned(&self, prost_path: &str, value: &str, by_ref: bool, boxed: bool) -> String {
+        let deref = if by_ref {
+            format!("*{}", value)
+        } else {
+            value.to_string()
+        };
+        let owned = match self {
+            ViewKind::Scalar(_) => deref,
+            ViewKind::Str => format!("{}::alloc::string::String::from({})", prost_path, deref),
+            ViewKind::Bytes(BytesType::Vec) => {
+                format!("{}::alloc::vec::Vec::from({})", prost_path, deref)
+            }
+            ViewKind::Bytes(BytesType::Bytes) => {
+                format!("{}::bytes::Bytes::copy_from_slice({})", prost_path, deref)
+            }
+            ViewKind::View(path) => format!(
+                "<{} as {}::view::MessageView>::to_message({}{})?",
+                path,
+                prost_path,
+                if by_ref { "" } else { "&" },
+                value
+            ),
+            // Owned messages are already boxed if needed.
+            ViewKind::Message | ViewKind::Lazy => return format!("{}.clone()", value),
+        };
+        if boxed {
+            format!("{}::alloc::boxed::Box::new({})", prost_path, owned)
+        } else {
+            owned
+        }
+    }
+}
+
 impl MapType {
     /// The `prost-derive` annotation type corresponding to the map type.
     fn annotation(&self) -> &'static str {
@@ -244,10 +244,19 @@ pub struct Config {
     extern_paths: Vec<(String, String)>,
     default_package_filename: String,
     enable_type_names: bool,
+    enable_extensions: bool,
+    enable_text_format: bool,
+    enable_json: bool,
+    enable_field_masks: bool,
     type_name_domains: PathMap<String>,
     protoc_args: Vec<OsString>,
     disable_comments: PathMap<()>,
     skip_debug: PathMap<()>,
+    unknown_fields: PathMap<()>,
+    cached_size: PathMap<()>,
+    required_field_presence: PathMap<()>,
+    message_views: PathMap<()>,
+    projections: PathMap<(String, Vec<String>)>,
     skip_protoc_run: bool,
     include_file: Option<PathBuf>,
     prost_path: Option<String>,
@@ -635,6 +644,183 @@ impl Config {
         self
     }
 
+    /// Preserve unknown fields in messages matched by `paths`.
+    ///
+    /// Matching messages get an additional `unknown_fields` field of type
+    /// [`UnknownFieldSet`][prost::UnknownFieldSet], which holds any fields encountered while
+    /// decoding that are not part of the message definition. The stored fields are emitted again
+    /// when the message is encoded, so data added by newer versions of a schema survives a
+    /// decode/encode round trip.
+    ///
+    /// For details about matching messages see [`btree_map`](#method.btree_map).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # let mut config = prost_build::Config::new();
+    /// // Preserve unknown fields in all messages.
+    /// config.preserve_unknown_fields(&["."]);
+    /// ```
+    pub fn preserve_unknown_fields<I, S>(&mut self, paths: I) -> &mut Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.unknown_fields.clear();
+        for matcher in paths {
+            self.unknown_fields.insert(matcher.as_ref().to_string(), ());
+        }
+        self
+    }
+
+    /// Cache the encoded lengths of messages matched by `paths` while they are encoded.
+    ///
+    /// Matching messages get an additional `cached_size` field of type
+    /// [`CachedSize`][prost::CachedSize]. When a message containing them is encoded, their
+    /// encoded lengths are recorded in the field while the length of the containing message is
+    /// computed, rather than in a table allocated for the encode, and taken from the field when
+    /// they are encoded. This is most useful for messages which are used in large repeated fields.
+    /// The field is ignored when comparing, hashing and cloning messages.
+    ///
+    /// For details about matching messages see [`btree_map`](#method.btree_map).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # let mut config = prost_build::Config::new();
+    /// // Cache the encoded lengths of all messages.
+    /// config.cached_size(&["."]);
+    /// ```
+    pub fn cached_size<I, S>(&mut self, paths: I) -> &mut Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.cached_size.clear();
+        for matcher in paths {
+            self.cached_size.insert(matcher.as_ref().to_string(), ());
+        }
+        self
+    }
+
+    /// Track the presence of the `proto2` `required` fields matched by `paths`.
+    ///
+    /// Matching required fields are generated as `Option<T>` rather than `T`, and are annotated
+    /// with `#[prost(required, presence)]`. They are `None` until they are set or decoded, so
+    /// [`Message::is_initialized`][prost::Message::is_initialized] and
+    /// [`Message::check_initialized`][prost::Message::check_initialized] report them as missing,
+    /// as do decodes with
+    /// [`DecodeOptions::check_required_fields`][prost::DecodeOptions::check_required_fields].
+    /// Required fields which are not matched can't be missing, as they always hold a value.
+    ///
+    /// For details about matching fields see [`btree_map`](#method.btree_map).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # let mut config = prost_build::Config::new();
+    /// // Track the presence of all required fields.
+    /// config.required_field_presence(&["."]);
+    /// ```
+    pub fn required_field_presence<I, S>(&mut self, paths: I) -> &mut Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.required_field_presence.clear();
+        for matcher in paths {
+            self.required_field_presence
+                .insert(matcher.as_ref().to_string(), ());
+        }
+        self
+    }
+
+    /// Generate zero-copy views of the messages matched by `paths`.
+    ///
+    /// For each matching message `Foo`, an additional `FooView<'a>` struct is generated which
+    /// implements [`MessageView`][prost::view::MessageView]. Decoding a view borrows `string` and
+    /// `bytes` fields from the input instead of copying them, and decodes the elements of
+    /// repeated `string`, `bytes` and message fields on demand. The view's
+    /// [`to_message`][prost::view::MessageView::to_message] method converts it to the owned `Foo`.
+    /// See the [`prost::view`] module for the generated field types.
+    ///
+    /// Message fields use the view of the field type if it is matched by `paths` too, and the
+    /// owned message type otherwise.
+    ///
+    /// For details about matching messages see [`btree_map`](#method.btree_map).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # let mut config = prost_build::Config::new();
+    /// // Generate views of all messages in the `my_messages` package.
+    /// config.message_views(&[".my_messages"]);
+    /// ```
+    pub fn message_views<I, S>(&mut self, paths: I) -> &mut Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.message_views.clear();
+        for matcher in paths {
+            self.message_views.insert(matcher.as_ref().to_string(), ());
+        }
+        self
+    }
+
+    /// Generate a projection selecting fields of the messages matched by `path`.
+    ///
+    /// For each matching message, an associated function `name` is generated which returns a
+    /// [`Projection`][prost::Projection] selecting the fields named by `fields`, to be used with
+    /// `Message::decode_projected`. Fields are named by their name in the `.proto` file, and can
+    /// descend into singular and repeated message fields, such as `header.timestamp`. The names
+    /// are resolved to field numbers when the code is generated, which fails if a name does not
+    /// match a field.
+    ///
+    /// Only the function building the projection is generated, not specialized decoding code:
+    /// `decode_projected` runs the regular merge of the message, which looks up the number of each
+    /// decoded field in the runtime `Projection` and skips the fields it does not select.
+    ///
+    /// The calls to this method are cumulative, and a message matched by several calls gets a
+    /// function for each of them. For details about matching messages see
+    /// [`btree_map`](#method.btree_map).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # let mut config = prost_build::Config::new();
+    /// // Generate `Record::summary_projection()`, selecting the ID and timestamp of records.
+    /// config.projection(".my_messages.Record", "summary_projection", &["id", "header.timestamp"]);
+    /// ```
+    ///
+    /// The generated function is used as:
+    ///
+    /// ```rust,ignore
+    /// let projection = Record::summary_projection();
+    /// for buf in records {
+    ///     let summary = Record::decode_projected(buf, &projection)?;
+    /// }
+    /// ```
+    pub fn projection<P, N, I, S>(&mut self, path: P, name: N, fields: I) -> &mut Self
+    where
+        P: AsRef<str>,
+        N: AsRef<str>,
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.projections.insert(
+            path.as_ref().to_string(),
+            (
+                name.as_ref().to_string(),
+                fields
+                    .into_iter()
+                    .map(|field| field.as_ref().to_string())
+                    .collect(),
+            ),
+        );
+        self
+    }
+
     /// Declare an externally provided Protobuf package or type.
     ///
     /// `extern_path` allows `prost` types in external crates to be referenced in generated code.
@@ -831,6 +1017,28 @@ impl Config {
         self
     }
 
+    /// Configures the code generator to support Protobuf extensions.
+    ///
+    /// Messages declaring extension ranges get an additional `extension_set` field of type
+    /// [`ExtensionSet`][prost::ExtensionSet] storing the extension fields, and implement
+    /// [`Extendable`][prost::Extendable]. Each `extend` declaration is generated as a constant
+    /// [`Extension`][prost::Extension] descriptor, named after the extension field in
+    /// `UPPER_SNAKE_CASE`, which can be used to access the extension value:
+    ///
+    /// ```rust,ignore
+    /// use prost::Extendable;
+    ///
+    /// let mut options = my_package::Options::default();
+    /// options.set_extension(&my_package::MY_OPTION, 42);
+    /// assert_eq!(options.get_extension(&my_package::MY_OPTION)?, Some(42));
+    /// ```
+    ///
+    /// Extensions declared inside a message are generated in the nested module of the message.
+    pub fn enable_extensions(&mut self) -> &mut Self {
+        self.enable_extensions = true;
+        self
+    }
+
     /// Configures the code generator to include type names.
     ///
     /// Message types will implement `Name` trait, which provides type and package name.
@@ -840,6 +1048,77 @@ impl Config {
         self
     }
 
+    /// Configures the code generator to implement the Protobuf text format.
+    ///
+    /// Message types will implement [`TextFormat`][prost::TextFormat], which prints and parses
+    /// messages in the text format used by `.textproto` files:
+    ///
+    /// ```rust,ignore
+    /// use prost::TextFormat;
+    ///
+    /// let config = my_package::Config::parse_text(r#"name: "server" port: 8080"#)?;
+    /// println!("{}", config.to_text_pretty());
+    /// ```
+    ///
+    /// The types of all message fields must implement `TextFormat` as well, so this should be
+    /// enabled for every package a message refers to. The well-known types in `prost-types`
+    /// implement it.
+    pub fn enable_text_format(&mut self) -> &mut Self {
+        self.enable_text_format = true;
+        self
+    }
+
+    /// Configures the code generator to implement the canonical Protobuf JSON mapping.
+    ///
+    /// Message types will implement [`Json`][prost::Json], which writes and parses messages as
+    /// JSON, naming fields by their `json_name`:
+    ///
+    /// ```rust,ignore
+    /// use prost::json::{Json, JsonOptions};
+    ///
+    /// let config = my_package::Config::parse_json(r#"{"name":"server","port":8080}"#)?;
+    /// let options = JsonOptions::new().emit_defaults(true);
+    /// println!("{}", config.to_json_with_options(&options)?);
+    /// ```
+    ///
+    /// Whether fields set to their default value are written is chosen at runtime with
+    /// [`JsonOptions::emit_defaults`][prost::json::JsonOptions::emit_defaults].
+    ///
+    /// The types of all message fields must implement `Json` as well, so this should be enabled
+    /// for every package a message refers to. The well-known types in `prost-types` implement it
+    /// with their special JSON forms. When the well-known types are compiled with
+    /// [`compile_well_known_types`](#method.compile_well_known_types), the types with special
+    /// forms (`Any`, `Duration`, `Timestamp`, `FieldMask`, `Struct`, `Value` and `ListValue`) are
+    /// left to be implemented by hand.
+    pub fn enable_json(&mut self) -> &mut Self {
+        self.enable_json = true;
+        self
+    }
+
+    /// Configures the code generator to implement field mask operations.
+    ///
+    /// Message types will implement [`FieldMasked`][prost::FieldMasked], which copies and clears
+    /// the fields named by the paths of a field mask, such as `google.protobuf.FieldMask`:
+    ///
+    /// ```rust,ignore
+    /// use prost::FieldMasked;
+    ///
+    /// // Copy the fields named by the update mask, such as `display_name` or `address.city`.
+    /// let update_mask = request.update_mask.unwrap_or_default();
+    /// book.merge_with_mask(&request.book.unwrap_or_default(), &update_mask.paths)?;
+    ///
+    /// // Clear every field other than the title and the author's name.
+    /// book.prune(&["title", "author.name"])?;
+    /// ```
+    ///
+    /// Paths name fields by their name in the `.proto` file. The types of all message fields must
+    /// implement `FieldMasked` as well, so this should be enabled for every package a message
+    /// refers to. The well-known types in `prost-types` implement it.
+    pub fn enable_field_masks(&mut self) -> &mut Self {
+        self.enable_field_masks = true;
+        self
+    }
+
     /// Specify domain names to use with message type URLs.
     ///
     /// # Domains
@@ -1305,10 +1584,19 @@ impl default::Default for Config {
             extern_paths: Vec::new(),
             default_package_filename: "_".to_string(),
             enable_type_names: false,
+            enable_extensions: false,
+            enable_text_format: false,
+            enable_json: false,
+            enable_field_masks: false,
             type_name_domains: PathMap::default(),
             protoc_args: Vec::new(),
             disable_comments: PathMap::default(),
             skip_debug: PathMap::default(),
+            unknown_fields: PathMap::default(),
+            cached_size: PathMap::default(),
+            required_field_presence: PathMap::default(),
+            message_views: PathMap::default(),
+            projections: PathMap::default(),
             skip_protoc_run: false,
             include_file: None,
             prost_path: None,
@@ -1332,10 +1620,19 @@ impl fmt::Debug for Config {
             .field("extern_paths", &self.extern_paths)
             .field("default_package_filename", &self.default_package_filename)
             .field("enable_type_names", &self.enable_type_names)
+            .field("enable_extensions", &self.enable_extensions)
+            .field("enable_text_format", &self.enable_text_format)
+            .field("enable_json", &self.enable_json)
+            .field("enable_field_masks", &self.enable_field_masks)
             .field("type_name_domains", &self.type_name_domains)
             .field("protoc_args", &self.protoc_args)
             .field("disable_comments", &self.disable_comments)
             .field("skip_debug", &self.skip_debug)
+            .field("unknown_fields", &self.unknown_fields)
+            .field("cached_size", &self.cached_size)
+            .field("required_field_presence", &self.required_field_presence)
+            .field("message_views", &self.message_views)
+            .field("projections", &self.projections)
             .field("prost_path", &self.prost_path)
             .finish()
     }
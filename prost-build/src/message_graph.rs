@@ -12,6 +12,7 @@ use prost_types::{field_descriptor_proto, DescriptorProto, FileDescriptorProto};
 pub struct MessageGraph {
     index: HashMap<String, NodeIndex>,
     graph: Graph<String, ()>,
+    messages: HashMap<String, DescriptorProto>,
 }
 
 impl MessageGraph {
@@ -21,6 +22,7 @@ impl MessageGraph {
         let mut msg_graph = MessageGraph {
             index: HashMap::new(),
             graph: Graph::new(),
+            messages: HashMap::new(),
         };
 
         for file in files {
@@ -41,6 +43,7 @@ impl MessageGraph {
         let MessageGraph {
             ref mut index,
             ref mut graph,
+            ..
         } = *self;
         assert_eq!(b'.', msg_name.as_bytes()[0]);
         *index
@@ -69,6 +72,8 @@ impl MessageGraph {
         for msg in &msg.nested_type {
             self.add_message(&msg_name, msg);
         }
+
+        self.messages.insert(msg_name, msg.clone());
     }
 
     /// Returns true if message type `inner` is nested in message type `outer`.
@@ -84,4 +89,9 @@ impl MessageGraph {
 
         has_path_connecting(&self.graph, outer, inner, None)
     }
+
+    /// Returns the descriptor of the message type with the fully-qualified name `message`.
+    pub fn get_message(&self, message: &str) -> Option<&DescriptorProto> {
+        self.messages.get(message)
+    }
 }
@@ -3,7 +3,7 @@ use proc_macro2::TokenStream;
 use quote::{quote, ToTokens};
 use syn::Meta;
 
-use crate::field::{set_bool, set_option, tag_attr, word_attr, Label};
+use crate::field::{message, set_bool, set_option, tag_attr, word_attr, Label};
 
 #[derive(Clone)]
 pub struct Field {
@@ -72,20 +72,59 @@ impl Field {
 
     pub fn encode(&self, ident: TokenStream) -> TokenStream {
         let tag = self.tag;
+        self.encode_with(
+            ident,
+            |msg| quote!(::prost::encoding::group::encode(#tag, #msg, buf)),
+        )
+    }
+
+    /// Returns a statement which encodes the field with the map entries of the group in key
+    /// order, and its fields in field number order.
+    pub fn encode_deterministic(&self, ident: TokenStream) -> TokenStream {
+        let tag = self.tag;
+        self.encode_with(
+            ident,
+            |msg| quote!(::prost::encoding::group::encode_deterministic(#tag, #msg, buf)),
+        )
+    }
+
+    /// Returns a statement which encodes the field with the encoded lengths of its nested messages
+    /// taken from the `table`.
+    pub fn encode_with_table(&self, ident: TokenStream) -> TokenStream {
+        let tag = self.tag;
+        self.encode_with(
+            ident,
+            |msg| quote!(::prost::encoding::group::encode_with_table(#tag, #msg, table, buf)),
+        )
+    }
+
+    /// Returns a statement which encodes each group of the field with the given expression.
+    fn encode_with(
+        &self,
+        ident: TokenStream,
+        encode: impl Fn(TokenStream) -> TokenStream,
+    ) -> TokenStream {
         match self.label {
-            Label::Optional => quote! {
-                if let Some(ref msg) = #ident {
-                    ::prost::encoding::group::encode(#tag, msg, buf);
+            Label::Optional => {
+                let encode = encode(quote!(msg));
+                quote! {
+                    if let Some(ref msg) = #ident {
+                        #encode;
+                    }
                 }
-            },
-            Label::Required => quote! {
-                ::prost::encoding::group::encode(#tag, &#ident, buf);
-            },
-            Label::Repeated => quote! {
-                for msg in &#ident {
-                    ::prost::encoding::group::encode(#tag, msg, buf);
+            }
+            Label::Required => {
+                let encode = encode(quote!(&#ident));
+                quote!(#encode;)
+            }
+            Label::Repeated => {
+                let encode = encode(quote!(msg));
+                quote! {
+                    for msg in &#ident {
+                        #encode;
+                    }
                 }
-            },
+            }
         }
     }
 
@@ -109,6 +148,24 @@ impl Field {
         }
     }
 
+    /// Returns a statement which merges the field `other` of another message into the field.
+    pub fn merge_from(&self, ident: TokenStream, other: TokenStream) -> TokenStream {
+        message::merge_from_with(
+            self.label,
+            ident,
+            other,
+            quote!(::prost::Message::merge_from),
+        )
+    }
+
+    /// Returns a statement which records the paths of the missing required fields of the field
+    /// and of its nested messages.
+    pub fn find_missing_fields(&self, ident: &TokenStream) -> Option<TokenStream> {
+        Some(message::find_missing_fields_with(
+            self.label, self.tag, ident,
+        ))
+    }
+
     pub fn encoded_len(&self, ident: TokenStream) -> TokenStream {
         let tag = self.tag;
         match self.label {
@@ -124,6 +181,25 @@ impl Field {
         }
     }
 
+    /// Returns an expression which evaluates to the encoded length of the field, recording the
+    /// encoded lengths of its nested messages in the `table`.
+    pub fn encoded_len_with_table(&self, ident: TokenStream) -> TokenStream {
+        let tag = self.tag;
+        match self.label {
+            Label::Optional => quote! {
+                #ident.as_ref().map_or(0, |msg| {
+                    ::prost::encoding::group::encoded_len_with_table(#tag, msg, table)
+                })
+            },
+            Label::Required => quote! {
+                ::prost::encoding::group::encoded_len_with_table(#tag, &#ident, table)
+            },
+            Label::Repeated => quote! {
+                ::prost::encoding::group::encoded_len_repeated_with_table(#tag, &#ident, table)
+            },
+        }
+    }
+
     pub fn clear(&self, ident: TokenStream) -> TokenStream {
         match self.label {
             Label::Optional => quote!(#ident = ::core::option::Option::None),
@@ -125,16 +125,33 @@ impl Field {
 
     /// Returns a statement which encodes the map field.
     pub fn encode(&self, ident: TokenStream) -> TokenStream {
+        self.encode_with(ident, false)
+    }
+
+    /// Returns a statement which encodes the map field with its entries in key order.
+    pub fn encode_deterministic(&self, ident: TokenStream) -> TokenStream {
+        self.encode_with(ident, true)
+    }
+
+    fn encode_with(&self, ident: TokenStream, deterministic: bool) -> TokenStream {
         let tag = self.tag;
         let key_mod = self.key_ty.module();
         let ke = quote!(::prost::encoding::#key_mod::encode);
         let kl = quote!(::prost::encoding::#key_mod::encoded_len);
         let module = self.map_ty.module();
+        let (encode, encode_with_default) = if deterministic {
+            (
+                quote!(encode_deterministic),
+                quote!(encode_with_default_deterministic),
+            )
+        } else {
+            (quote!(encode), quote!(encode_with_default))
+        };
         match &self.value_ty {
             ValueTy::Scalar(scalar::Ty::Enumeration(ty)) => {
                 let default = quote!(#ty::default() as i32);
                 quote! {
-                    ::prost::encoding::#module::encode_with_default(
+                    ::prost::encoding::#module::#encode_with_default(
                         #ke,
                         #kl,
                         ::prost::encoding::int32::encode,
@@ -151,7 +168,7 @@ impl Field {
                 let ve = quote!(::prost::encoding::#val_mod::encode);
                 let vl = quote!(::prost::encoding::#val_mod::encoded_len);
                 quote! {
-                    ::prost::encoding::#module::encode(
+                    ::prost::encoding::#module::#encode(
                         #ke,
                         #kl,
                         #ve,
@@ -162,17 +179,24 @@ impl Field {
                     );
                 }
             }
-            ValueTy::Message => quote! {
-                ::prost::encoding::#module::encode(
-                    #ke,
-                    #kl,
-                    ::prost::encoding::message::encode,
-                    ::prost::encoding::message::encoded_len,
-                    #tag,
-                    &#ident,
-                    buf,
-                );
-            },
+            ValueTy::Message => {
+                let ve = if deterministic {
+                    quote!(::prost::encoding::message::encode_deterministic)
+                } else {
+                    quote!(::prost::encoding::message::encode)
+                };
+                quote! {
+                    ::prost::encoding::#module::#encode(
+                        #ke,
+                        #kl,
+                        #ve,
+                        ::prost::encoding::message::encoded_len,
+                        #tag,
+                        &#ident,
+                        buf,
+                    );
+                }
+            }
         }
     }
 
@@ -188,7 +212,15 @@ impl Field {
                 quote! {
                     ::prost::encoding::#module::merge_with_default(
                         #km,
-                        ::prost::encoding::int32::merge,
+                        |wire_type, value, buf, ctx| {
+                            ::prost::encoding::enumeration::merge(
+                                wire_type,
+                                value,
+                                #ty::is_valid,
+                                buf,
+                                ctx,
+                            )
+                        },
                         #default,
                         &mut #ident,
                         buf,
@@ -213,6 +245,36 @@ impl Field {
         }
     }
 
+    /// Returns a statement which merges the map `other` of another message into the map, replacing
+    /// the values of existing keys.
+    pub fn merge_from(&self, ident: TokenStream, other: TokenStream) -> TokenStream {
+        let value = match self.value_ty {
+            ValueTy::Scalar(..) => quote!(::core::clone::Clone::clone(value)),
+            ValueTy::Message => quote! {{
+                let mut merged = ::core::default::Default::default();
+                ::prost::Message::merge_from(&mut merged, value);
+                merged
+            }},
+        };
+        quote! {
+            for (key, value) in &#other {
+                #ident.insert(::core::clone::Clone::clone(key), #value);
+            }
+        }
+    }
+
+    /// Returns a statement which records the paths of the missing required fields of the message
+    /// values of the map, or `None` if the values are not messages.
+    pub fn find_missing_fields(&self, ident: &TokenStream) -> Option<TokenStream> {
+        let tag = self.tag;
+        match self.value_ty {
+            ValueTy::Scalar(..) => None,
+            ValueTy::Message => Some(quote! {
+                paths.map(projection, #tag, stringify!(#ident), self.#ident.iter());
+            }),
+        }
+    }
+
     /// Returns an expression which evaluates to the encoded length of the map.
     pub fn encoded_len(&self, ident: TokenStream) -> TokenStream {
         let tag = self.tag;
@@ -248,6 +310,46 @@ impl Field {
         }
     }
 
+    /// Returns a statement which encodes the map field with the encoded lengths of its message
+    /// values taken from the `table`.
+    pub fn encode_with_table(&self, ident: TokenStream) -> TokenStream {
+        if !matches!(self.value_ty, ValueTy::Message) {
+            return self.encode(ident);
+        }
+        let tag = self.tag;
+        let key_mod = self.key_ty.module();
+        let ke = quote!(::prost::encoding::#key_mod::encode);
+        let kl = quote!(::prost::encoding::#key_mod::encoded_len);
+        let module = self.map_ty.module();
+        quote! {
+            ::prost::encoding::#module::encode_with_table(#ke, #kl, #tag, &#ident, table, buf);
+        }
+    }
+
+    /// Returns a statement which encodes the map field with entries in key order, taking the
+    /// encoded lengths of its message values from the `table`.
+    pub fn encode_deterministic_with_table(&self, ident: TokenStream) -> TokenStream {
+        if matches!(self.value_ty, ValueTy::Message) {
+            // The entries are sorted when the table is for a deterministic encode.
+            self.encode_with_table(ident)
+        } else {
+            self.encode_deterministic(ident)
+        }
+    }
+
+    /// Returns an expression which evaluates to the encoded length of the map field, recording the
+    /// encoded lengths of its message values in the `table`.
+    pub fn encoded_len_with_table(&self, ident: TokenStream) -> TokenStream {
+        if !matches!(self.value_ty, ValueTy::Message) {
+            return self.encoded_len(ident);
+        }
+        let tag = self.tag;
+        let key_mod = self.key_ty.module();
+        let kl = quote!(::prost::encoding::#key_mod::encoded_len);
+        let module = self.map_ty.module();
+        quote!(::prost::encoding::#module::encoded_len_with_table(#kl, #tag, &#ident, table))
+    }
+
     pub fn clear(&self, ident: TokenStream) -> TokenStream {
         quote!(#ident.clear())
     }
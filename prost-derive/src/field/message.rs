@@ -9,6 +9,7 @@ use crate::field::{set_bool, set_option, tag_attr, word_attr, Label};
 pub struct Field {
     pub label: Label,
     pub tag: u32,
+    pub lazy: bool,
 }
 
 impl Field {
@@ -17,6 +18,7 @@ impl Field {
         let mut label = None;
         let mut tag = None;
         let mut boxed = false;
+        let mut lazy = false;
 
         let mut unknown_attrs = Vec::new();
 
@@ -25,6 +27,8 @@ impl Field {
                 set_bool(&mut message, "duplicate message attribute")?;
             } else if word_attr("boxed", attr) {
                 set_bool(&mut boxed, "duplicate boxed attribute")?;
+            } else if word_attr("lazy", attr) {
+                set_bool(&mut lazy, "duplicate lazy attribute")?;
             } else if let Some(t) = tag_attr(attr)? {
                 set_option(&mut tag, t, "duplicate tag attributes")?;
             } else if let Some(l) = Label::from_attr(attr) {
@@ -55,6 +59,7 @@ impl Field {
         Ok(Some(Field {
             label: label.unwrap_or(Label::Optional),
             tag,
+            lazy,
         }))
     }
 
@@ -73,53 +78,152 @@ impl Field {
         }
     }
 
+    /// Returns the encoding module for the field's values.
+    fn module(&self) -> TokenStream {
+        if self.lazy {
+            quote!(::prost::encoding::lazy)
+        } else {
+            quote!(::prost::encoding::message)
+        }
+    }
+
     pub fn encode(&self, ident: TokenStream) -> TokenStream {
         let tag = self.tag;
+        let module = self.module();
+        self.encode_with(ident, |msg| quote!(#module::encode(#tag, #msg, buf)))
+    }
+
+    /// Returns a statement which encodes the field with the map entries of the message in key
+    /// order, and its fields in field number order.
+    pub fn encode_deterministic(&self, ident: TokenStream) -> TokenStream {
+        let tag = self.tag;
+        let module = self.module();
+        self.encode_with(
+            ident,
+            |msg| quote!(#module::encode_deterministic(#tag, #msg, buf)),
+        )
+    }
+
+    /// Returns a statement which encodes the field with the encoded lengths of its nested messages
+    /// taken from the `table`.
+    ///
+    /// Lazy fields are encoded without the table.
+    pub fn encode_with_table(&self, ident: TokenStream) -> TokenStream {
+        if self.lazy {
+            return self.encode(ident);
+        }
+        let tag = self.tag;
+        self.encode_with(
+            ident,
+            |msg| quote!(::prost::encoding::message::encode_with_table(#tag, #msg, table, buf)),
+        )
+    }
+
+    /// Returns a statement which encodes each message of the field with the given expression.
+    fn encode_with(
+        &self,
+        ident: TokenStream,
+        encode: impl Fn(TokenStream) -> TokenStream,
+    ) -> TokenStream {
         match self.label {
-            Label::Optional => quote! {
-                if let Some(ref msg) = #ident {
-                    ::prost::encoding::message::encode(#tag, msg, buf);
+            Label::Optional => {
+                let encode = encode(quote!(msg));
+                quote! {
+                    if let Some(ref msg) = #ident {
+                        #encode;
+                    }
                 }
-            },
-            Label::Required => quote! {
-                ::prost::encoding::message::encode(#tag, &#ident, buf);
-            },
-            Label::Repeated => quote! {
-                for msg in &#ident {
-                    ::prost::encoding::message::encode(#tag, msg, buf);
+            }
+            Label::Required => {
+                let encode = encode(quote!(&#ident));
+                quote!(#encode;)
+            }
+            Label::Repeated => {
+                let encode = encode(quote!(msg));
+                quote! {
+                    for msg in &#ident {
+                        #encode;
+                    }
                 }
-            },
+            }
         }
     }
 
     pub fn merge(&self, ident: TokenStream) -> TokenStream {
+        let module = self.module();
         match self.label {
             Label::Optional => quote! {
-                ::prost::encoding::message::merge(wire_type,
+                #module::merge(wire_type,
                                                  #ident.get_or_insert_with(::core::default::Default::default),
                                                  buf,
                                                  ctx)
             },
             Label::Required => quote! {
-                ::prost::encoding::message::merge(wire_type, #ident, buf, ctx)
+                #module::merge(wire_type, #ident, buf, ctx)
             },
             Label::Repeated => quote! {
-                ::prost::encoding::message::merge_repeated(wire_type, #ident, buf, ctx)
+                #module::merge_repeated(wire_type, #ident, buf, ctx)
             },
         }
     }
 
+    /// Returns a statement which merges the field `other` of another message into the field.
+    pub fn merge_from(&self, ident: TokenStream, other: TokenStream) -> TokenStream {
+        let merge_from = if self.lazy {
+            quote!(::prost::encoding::lazy::merge_from)
+        } else {
+            quote!(::prost::Message::merge_from)
+        };
+        merge_from_with(self.label, ident, other, merge_from)
+    }
+
+    /// Returns a statement which records the paths of the missing required fields of the field
+    /// and of its nested messages, or `None` if the field has none.
+    ///
+    /// The fields of lazy messages are not searched.
+    pub fn find_missing_fields(&self, ident: &TokenStream) -> Option<TokenStream> {
+        if self.lazy {
+            return None;
+        }
+        Some(find_missing_fields_with(self.label, self.tag, ident))
+    }
+
     pub fn encoded_len(&self, ident: TokenStream) -> TokenStream {
+        let tag = self.tag;
+        let module = self.module();
+        match self.label {
+            Label::Optional => quote! {
+                #ident.as_ref().map_or(0, |msg| #module::encoded_len(#tag, msg))
+            },
+            Label::Required => quote! {
+                #module::encoded_len(#tag, &#ident)
+            },
+            Label::Repeated => quote! {
+                #module::encoded_len_repeated(#tag, &#ident)
+            },
+        }
+    }
+
+    /// Returns an expression which evaluates to the encoded length of the field, recording the
+    /// encoded lengths of its nested messages in the `table`.
+    ///
+    /// The lengths of lazy fields are not recorded.
+    pub fn encoded_len_with_table(&self, ident: TokenStream) -> TokenStream {
+        if self.lazy {
+            return self.encoded_len(ident);
+        }
         let tag = self.tag;
         match self.label {
             Label::Optional => quote! {
-                #ident.as_ref().map_or(0, |msg| ::prost::encoding::message::encoded_len(#tag, msg))
+                #ident.as_ref().map_or(0, |msg| {
+                    ::prost::encoding::message::encoded_len_with_table(#tag, msg, table)
+                })
             },
             Label::Required => quote! {
-                ::prost::encoding::message::encoded_len(#tag, &#ident)
+                ::prost::encoding::message::encoded_len_with_table(#tag, &#ident, table)
             },
             Label::Repeated => quote! {
-                ::prost::encoding::message::encoded_len_repeated(#tag, &#ident)
+                ::prost::encoding::message::encoded_len_repeated_with_table(#tag, &#ident, table)
             },
         }
     }
@@ -132,3 +236,49 @@ impl Field {
         }
     }
 }
+
+/// Returns a statement which merges the message field `other` into the field with the given merge
+/// function.
+///
+/// Messages are merged into a default message rather than cloned, since messages are not required
+/// to implement `Clone`.
+pub fn merge_from_with(
+    label: Label,
+    ident: TokenStream,
+    other: TokenStream,
+    merge_from: TokenStream,
+) -> TokenStream {
+    match label {
+        Label::Optional => quote! {
+            if let ::core::option::Option::Some(ref msg) = #other {
+                #merge_from(#ident.get_or_insert_with(::core::default::Default::default), msg);
+            }
+        },
+        Label::Required => quote!(#merge_from(&mut #ident, &#other);),
+        Label::Repeated => quote! {
+            #ident.extend(#other.iter().map(|msg| {
+                let mut value = ::core::default::Default::default();
+                #merge_from(&mut value, msg);
+                value
+            }));
+        },
+    }
+}
+
+/// Returns a statement which records the paths of the missing required fields of a message or
+/// group field and of its nested messages.
+pub fn find_missing_fields_with(label: Label, tag: u32, ident: &TokenStream) -> TokenStream {
+    match label {
+        Label::Optional => quote! {
+            if let ::core::option::Option::Some(ref msg) = self.#ident {
+                paths.message(projection, #tag, stringify!(#ident), msg);
+            }
+        },
+        Label::Required => quote! {
+            paths.message(projection, #tag, stringify!(#ident), &self.#ident);
+        },
+        Label::Repeated => quote! {
+            paths.repeated(projection, #tag, stringify!(#ident), &self.#ident);
+        },
+    }
+}
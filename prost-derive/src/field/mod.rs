@@ -11,7 +11,7 @@ use anyhow::{bail, Error};
 use proc_macro2::TokenStream;
 use quote::quote;
 use syn::punctuated::Punctuated;
-use syn::{Attribute, Expr, ExprLit, Lit, LitBool, LitInt, Meta, MetaNameValue, Token};
+use syn::{Attribute, Expr, ExprLit, Lit, LitBool, LitInt, Meta, MetaNameValue, Token, Type};
 
 #[derive(Clone)]
 pub enum Field {
@@ -33,7 +33,23 @@ impl Field {
     /// If the meta items are invalid, an error will be returned.
     /// If the field should be ignored, `None` is returned.
     pub fn new(attrs: Vec<Attribute>, inferred_tag: Option<u32>) -> Result<Option<Field>, Error> {
-        let attrs = prost_attrs(attrs)?;
+        let mut attrs = prost_attrs(attrs)?;
+
+        // A required field whose presence is tracked is stored like an optional field; see
+        // `is_presence`.
+        if attrs.iter().any(|attr| word_attr("presence", attr)) {
+            attrs = attrs
+                .into_iter()
+                .filter(|attr| !word_attr("presence", attr))
+                .map(|attr| {
+                    if word_attr("required", &attr) {
+                        syn::parse_quote!(optional)
+                    } else {
+                        attr
+                    }
+                })
+                .collect();
+        }
 
         // TODO: check for ignore attribute.
 
@@ -88,6 +104,68 @@ impl Field {
         }
     }
 
+    /// Returns `true` if encoding the field may encode nested messages, whose encoded lengths are
+    /// recorded in an `EncodedLenTable`.
+    pub fn has_nested_messages(&self) -> bool {
+        match *self {
+            Field::Message(..) | Field::Group(..) | Field::Oneof(..) => true,
+            Field::Map(ref map) => matches!(map.value_ty, map::ValueTy::Message),
+            Field::Scalar(..) => false,
+        }
+    }
+
+    /// Returns `true` if the field is a message or group field declared `required`, which is not
+    /// lazy.
+    pub fn is_required_message(&self) -> bool {
+        match *self {
+            Field::Message(ref message) => message.label == Label::Required && !message.lazy,
+            Field::Group(ref group) => group.label == Label::Required,
+            Field::Scalar(..) | Field::Map(..) | Field::Oneof(..) => false,
+        }
+    }
+
+    /// Returns a match arm which evaluates to the `SingularMessageField` describing the field for
+    /// the field numbers at which it holds a message or group which is not repeated, or `None` if
+    /// it never does.
+    ///
+    /// `message` and `ident` evaluate to the names of the message and of the field, and `ty` is
+    /// the type of the field, or of the variant for a variant of a oneof.
+    pub fn singular_message_arm(
+        &self,
+        message: &TokenStream,
+        ident: &TokenStream,
+        ty: &Type,
+    ) -> Option<TokenStream> {
+        let (label, lazy, group) = match *self {
+            Field::Message(ref message) => (message.label, message.lazy, false),
+            Field::Group(ref group) => (group.label, false, true),
+            Field::Oneof(ref oneof) => return Some(oneof.singular_message_arm(message, ident)),
+            Field::Scalar(..) | Field::Map(..) => return None,
+        };
+        let field = match (label, lazy) {
+            (Label::Repeated, _) => return None,
+            (_, true) => quote!(::prost::encoding::SingularMessageField::lazy(#message, #ident)),
+            (Label::Optional, false) => quote! {
+                ::prost::encoding::SingularMessageField::optional(
+                    #message,
+                    #ident,
+                    #group,
+                    ::core::marker::PhantomData::<#ty>,
+                )
+            },
+            (Label::Required, false) => quote! {
+                ::prost::encoding::SingularMessageField::new(
+                    #message,
+                    #ident,
+                    #group,
+                    ::core::marker::PhantomData::<#ty>,
+                )
+            },
+        };
+        let tag = self.tags()[0];
+        Some(quote!(#tag => ::core::option::Option::Some(#field),))
+    }
+
     /// Returns a statement which encodes the field.
     pub fn encode(&self, ident: TokenStream) -> TokenStream {
         match *self {
@@ -99,6 +177,27 @@ impl Field {
         }
     }
 
+    /// Returns statements which encode the field with map entries in key order and fields in field
+    /// number order, along with the tag each is encoded at.
+    ///
+    /// A oneof field has a statement for each of its tags, which encodes the oneof if its current
+    /// variant has the tag, so that it is encoded at the position of its variant.
+    pub fn encode_deterministic(&self, ident: TokenStream) -> Vec<(u32, TokenStream)> {
+        match *self {
+            Field::Scalar(ref scalar) => vec![(scalar.tag, scalar.encode(ident))],
+            Field::Message(ref message) => {
+                vec![(message.tag, message.encode_deterministic(ident))]
+            }
+            Field::Map(ref map) => vec![(map.tag, map.encode_deterministic(ident))],
+            Field::Oneof(ref oneof) => oneof
+                .tags
+                .iter()
+                .map(|&tag| (tag, oneof.encode_deterministic(ident.clone(), tag)))
+                .collect(),
+            Field::Group(ref group) => vec![(group.tag, group.encode_deterministic(ident))],
+        }
+    }
+
     /// Returns an expression which evaluates to the result of merging a decoded
     /// value into the field.
     pub fn merge(&self, ident: TokenStream) -> TokenStream {
@@ -111,6 +210,17 @@ impl Field {
         }
     }
 
+    /// Returns a statement which merges the field `other` of another message into the field.
+    pub fn merge_from(&self, ident: TokenStream, other: TokenStream) -> TokenStream {
+        match *self {
+            Field::Scalar(ref scalar) => scalar.merge_from(ident, other),
+            Field::Message(ref message) => message.merge_from(ident, other),
+            Field::Map(ref map) => map.merge_from(ident, other),
+            Field::Oneof(ref oneof) => oneof.merge_from(ident, other),
+            Field::Group(ref group) => group.merge_from(ident, other),
+        }
+    }
+
     /// Returns an expression which evaluates to the encoded length of the field.
     pub fn encoded_len(&self, ident: TokenStream) -> TokenStream {
         match *self {
@@ -122,6 +232,88 @@ impl Field {
         }
     }
 
+    /// Returns a statement which encodes the field, taking the encoded lengths of its nested
+    /// messages from the `table` filled in by `encoded_len_with_table`.
+    pub fn encode_with_table(&self, ident: TokenStream) -> TokenStream {
+        match *self {
+            Field::Scalar(ref scalar) => scalar.encode(ident),
+            Field::Message(ref message) => message.encode_with_table(ident),
+            Field::Map(ref map) => map.encode_with_table(ident),
+            Field::Oneof(ref oneof) => oneof.encode_with_table(ident),
+            Field::Group(ref group) => group.encode_with_table(ident),
+        }
+    }
+
+    /// Returns an expression which evaluates to the encoded length of the field, recording the
+    /// encoded lengths of its nested messages in the `table`, in the order they are encoded.
+    pub fn encoded_len_with_table(&self, ident: TokenStream) -> TokenStream {
+        match *self {
+            Field::Scalar(ref scalar) => scalar.encoded_len(ident),
+            Field::Map(ref map) => map.encoded_len_with_table(ident),
+            Field::Message(ref msg) => msg.encoded_len_with_table(ident),
+            Field::Oneof(ref oneof) => oneof.encoded_len_with_table(ident),
+            Field::Group(ref group) => group.encoded_len_with_table(ident),
+        }
+    }
+
+    /// Returns statements which encode the field like `encode_deterministic`, taking the encoded
+    /// lengths of its nested messages from the `table`, along with the tag each is encoded at.
+    pub fn encode_deterministic_with_table(&self, ident: TokenStream) -> Vec<(u32, TokenStream)> {
+        match *self {
+            Field::Scalar(ref scalar) => vec![(scalar.tag, scalar.encode(ident))],
+            Field::Message(ref message) => vec![(message.tag, message.encode_with_table(ident))],
+            Field::Map(ref map) => vec![(map.tag, map.encode_deterministic_with_table(ident))],
+            Field::Oneof(ref oneof) => oneof
+                .tags
+                .iter()
+                .map(|&tag| {
+                    (
+                        tag,
+                        oneof.encode_deterministic_with_table(ident.clone(), tag),
+                    )
+                })
+                .collect(),
+            Field::Group(ref group) => vec![(group.tag, group.encode_with_table(ident))],
+        }
+    }
+
+    /// Returns expressions which evaluate to the encoded length of the field, recording the
+    /// encoded lengths of its nested messages in the `table` in the order they are encoded by
+    /// `encode_deterministic_with_table`, along with the tag of each.
+    pub fn encoded_len_deterministic_with_table(
+        &self,
+        ident: TokenStream,
+    ) -> Vec<(u32, TokenStream)> {
+        match *self {
+            Field::Oneof(ref oneof) => oneof
+                .tags
+                .iter()
+                .map(|&tag| {
+                    let encoded_len =
+                        oneof.encoded_len_deterministic_with_table(ident.clone(), tag);
+                    (tag, encoded_len)
+                })
+                .collect(),
+            _ => vec![(self.tags()[0], self.encoded_len_with_table(ident))],
+        }
+    }
+
+    /// Returns a statement which records the paths of the missing required fields of the field
+    /// and of its nested messages, or `None` if the field has none.
+    pub fn find_missing_fields(&self, ident: &TokenStream) -> Option<TokenStream> {
+        match *self {
+            Field::Scalar(..) => None,
+            Field::Message(ref message) => message.find_missing_fields(ident),
+            Field::Map(ref map) => map.find_missing_fields(ident),
+            Field::Oneof(..) => Some(quote! {
+                if let ::core::option::Option::Some(ref oneof) = self.#ident {
+                    oneof.find_missing_fields(projection, paths);
+                }
+            }),
+            Field::Group(ref group) => group.find_missing_fields(ident),
+        }
+    }
+
     /// Returns a statement which clears the field.
     pub fn clear(&self, ident: TokenStream) -> TokenStream {
         match *self {
@@ -174,6 +366,121 @@ impl Field {
     }
 }
 
+/// A field holding a set of fields which are not declared as struct fields of the message.
+#[derive(Clone)]
+pub enum FieldSet {
+    /// Fields unknown to the message definition, i.e. `#[prost(unknown_fields)]`.
+    Unknown,
+    /// Extension fields within the given inclusive tag ranges, e.g.
+    /// `#[prost(extensions = "100..200, 1000..536870912")]`.
+    Extensions(Vec<(u32, u32)>),
+}
+
+impl FieldSet {
+    /// Creates a new `FieldSet` from the field attributes.
+    ///
+    /// If the field is not a field set, `None` is returned.
+    pub fn new(attrs: &[Attribute]) -> Result<Option<FieldSet>, Error> {
+        let attrs = prost_attrs(attrs.to_vec())?;
+
+        let mut field_set = None;
+        for attr in &attrs {
+            if word_attr("unknown_fields", attr) {
+                set_option(
+                    &mut field_set,
+                    FieldSet::Unknown,
+                    "duplicate field set attributes",
+                )?;
+            } else if let Some(ranges) = extension_ranges_attr(attr)? {
+                set_option(
+                    &mut field_set,
+                    FieldSet::Extensions(ranges),
+                    "duplicate field set attributes",
+                )?;
+            }
+        }
+
+        if field_set.is_some() && attrs.len() > 1 {
+            bail!("field set attributes can not be combined with other attributes");
+        }
+        Ok(field_set)
+    }
+}
+
+impl fmt::Debug for FieldSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            FieldSet::Unknown => f.write_str("unknown_fields"),
+            FieldSet::Extensions(..) => f.write_str("extensions"),
+        }
+    }
+}
+
+/// Returns `true` if the field caches the encoded length of the message, i.e.
+/// `#[prost(cached_size)]`.
+pub fn is_cached_size(attrs: &[Attribute]) -> Result<bool, Error> {
+    let attrs = prost_attrs(attrs.to_vec())?;
+    if !attrs.iter().any(|attr| word_attr("cached_size", attr)) {
+        return Ok(false);
+    }
+    if attrs.len() > 1 {
+        bail!("cached_size attribute can not be combined with other attributes");
+    }
+    Ok(true)
+}
+
+/// Returns `true` if the field is a required field whose presence is tracked, i.e.
+/// `#[prost(required, presence)]`.
+pub fn is_presence(attrs: &[Attribute]) -> Result<bool, Error> {
+    let attrs = prost_attrs(attrs.to_vec())?;
+    if !attrs.iter().any(|attr| word_attr("presence", attr)) {
+        return Ok(false);
+    }
+    if !attrs.iter().any(|attr| word_attr("required", attr)) {
+        bail!("presence attribute can only be used on required fields");
+    }
+    Ok(true)
+}
+
+/// Parses an `extensions = "100..200, 300"` attribute into a list of inclusive tag ranges.
+fn extension_ranges_attr(attr: &Meta) -> Result<Option<Vec<(u32, u32)>>, Error> {
+    if !attr.path().is_ident("extensions") {
+        return Ok(None);
+    }
+    let ranges = match *attr {
+        Meta::NameValue(MetaNameValue {
+            value:
+                Expr::Lit(ExprLit {
+                    lit: Lit::Str(ref lit),
+                    ..
+                }),
+            ..
+        }) => lit.value(),
+        _ => bail!("invalid extensions attribute: {:?}", attr),
+    };
+    ranges
+        .split(',')
+        .map(|range| {
+            let range = range.trim();
+            match range.split_once("..") {
+                Some((start, end)) => {
+                    let start = start.trim().parse::<u32>()?;
+                    let end = end.trim().parse::<u32>()?;
+                    if end <= start {
+                        bail!("invalid extension range: {}", range);
+                    }
+                    Ok((start, end - 1))
+                }
+                None => {
+                    let tag = range.parse::<u32>()?;
+                    Ok((tag, tag))
+                }
+            }
+        })
+        .collect::<Result<Vec<_>, _>>()
+        .map(Some)
+}
+
 #[derive(Clone, Copy, PartialEq, Eq)]
 pub enum Label {
     /// An optional field.
@@ -1,4 +1,5 @@
 use anyhow::{bail, Error};
+use itertools::Itertools;
 use proc_macro2::TokenStream;
 use quote::quote;
 use syn::{parse_str, Expr, ExprLit, Ident, Lit, Meta, MetaNameValue, Path};
@@ -70,6 +71,17 @@ impl Field {
         }
     }
 
+    /// Returns a statement which encodes the oneof field if its current variant has the given tag.
+    pub fn encode_deterministic(&self, ident: TokenStream, tag: u32) -> TokenStream {
+        quote! {
+            if let Some(ref oneof) = #ident {
+                if oneof.tag() == #tag {
+                    oneof.encode_deterministic(buf)
+                }
+            }
+        }
+    }
+
     /// Returns an expression which evaluates to the result of decoding the oneof field.
     pub fn merge(&self, ident: TokenStream) -> TokenStream {
         let ty = &self.ty;
@@ -78,6 +90,27 @@ impl Field {
         }
     }
 
+    /// Returns a statement which merges the oneof field `other` of another message into the field.
+    pub fn merge_from(&self, ident: TokenStream, other: TokenStream) -> TokenStream {
+        let ty = &self.ty;
+        quote! {
+            if let ::core::option::Option::Some(ref oneof) = #other {
+                #ty::merge_from(&mut #ident, oneof);
+            }
+        }
+    }
+
+    /// Returns a match arm which evaluates to the `SingularMessageField` describing the oneof
+    /// field for the numbers of its variants, if the variant holds a message or group.
+    ///
+    /// `message` and `ident` evaluate to the names of the message and of the field.
+    pub fn singular_message_arm(&self, message: &TokenStream, ident: &TokenStream) -> TokenStream {
+        let ty = &self.ty;
+        let tags = self.tags.iter().map(|tag| quote!(#tag));
+        let tags = Itertools::intersperse(tags, quote!(|));
+        quote!(#(#tags)* => #ty::singular_message_field(#message, #ident, tag),)
+    }
+
     /// Returns an expression which evaluates to the encoded length of the oneof field.
     pub fn encoded_len(&self, ident: TokenStream) -> TokenStream {
         let ty = &self.ty;
@@ -86,6 +119,55 @@ impl Field {
         }
     }
 
+    /// Returns a statement which encodes the oneof field with the encoded lengths of its nested
+    /// messages taken from the `table`.
+    pub fn encode_with_table(&self, ident: TokenStream) -> TokenStream {
+        quote! {
+            if let Some(ref oneof) = #ident {
+                oneof.encode_with_table(table, buf)
+            }
+        }
+    }
+
+    /// Returns an expression which evaluates to the encoded length of the oneof field, recording
+    /// the encoded lengths of its nested messages in the `table`.
+    pub fn encoded_len_with_table(&self, ident: TokenStream) -> TokenStream {
+        quote! {
+            #ident.as_ref().map_or(0, |oneof| oneof.encoded_len_with_table(table))
+        }
+    }
+
+    /// Returns a statement which encodes the oneof field with the encoded lengths of its nested
+    /// messages taken from the `table`, if its current variant has the given tag.
+    pub fn encode_deterministic_with_table(&self, ident: TokenStream, tag: u32) -> TokenStream {
+        quote! {
+            if let Some(ref oneof) = #ident {
+                if oneof.tag() == #tag {
+                    oneof.encode_with_table(table, buf)
+                }
+            }
+        }
+    }
+
+    /// Returns an expression which evaluates to the encoded length of the oneof field if its
+    /// current variant has the given tag, recording the encoded lengths of its nested messages in
+    /// the `table`.
+    pub fn encoded_len_deterministic_with_table(
+        &self,
+        ident: TokenStream,
+        tag: u32,
+    ) -> TokenStream {
+        quote! {
+            #ident.as_ref().map_or(0, |oneof| {
+                if oneof.tag() == #tag {
+                    oneof.encoded_len_with_table(table)
+                } else {
+                    0
+                }
+            })
+        }
+    }
+
     pub fn clear(&self, ident: TokenStream) -> TokenStream {
         quote!(#ident = ::core::option::Option::None)
     }
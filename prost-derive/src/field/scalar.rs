@@ -138,26 +138,64 @@ impl Field {
     /// Returns an expression which evaluates to the result of merging a decoded
     /// scalar value into the field.
     pub fn merge(&self, ident: TokenStream) -> TokenStream {
-        let module = self.ty.module();
         let merge_fn = match self.kind {
             Kind::Plain(..) | Kind::Optional(..) | Kind::Required(..) => quote!(merge),
             Kind::Repeated | Kind::Packed => quote!(merge_repeated),
         };
-        let merge_fn = quote!(::prost::encoding::#module::#merge_fn);
+        // Enumeration values are checked against the variants when decoding strictly.
+        let (merge_fn, is_valid) = match self.ty {
+            Ty::Enumeration(ref ty) => (
+                quote!(::prost::encoding::enumeration::#merge_fn),
+                quote!(#ty::is_valid,),
+            ),
+            _ => {
+                let module = self.ty.module();
+                (quote!(::prost::encoding::#module::#merge_fn), quote!())
+            }
+        };
 
         match self.kind {
             Kind::Plain(..) | Kind::Required(..) | Kind::Repeated | Kind::Packed => quote! {
-                #merge_fn(wire_type, #ident, buf, ctx)
+                #merge_fn(wire_type, #ident, #is_valid buf, ctx)
             },
             Kind::Optional(..) => quote! {
                 #merge_fn(wire_type,
                           #ident.get_or_insert_with(::core::default::Default::default),
+                          #is_valid
                           buf,
                           ctx)
             },
         }
     }
 
+    /// Returns a statement which merges the field `other` of another message into the field.
+    ///
+    /// Like decoding the encoded field, a set value overwrites the field, and repeated values are
+    /// appended.
+    pub fn merge_from(&self, ident: TokenStream, other: TokenStream) -> TokenStream {
+        let value = match self.ty {
+            Ty::String | Ty::Bytes(..) => quote!(::core::clone::Clone::clone(&#other)),
+            _ => quote!(#other),
+        };
+        match self.kind {
+            Kind::Plain(ref default) => {
+                let default = default.typed();
+                quote! {
+                    if #other != #default {
+                        #ident = #value;
+                    }
+                }
+            }
+            Kind::Optional(..) => quote! {
+                if #other.is_some() {
+                    #ident = #value;
+                }
+            },
+            Kind::Required(..) => quote!(#ident = #value;),
+            Kind::Repeated | Kind::Packed => quote!(#ident.extend_from_slice(&#other);),
+        }
+    }
+
     /// Returns an expression which evaluates to the encoded length of the field.
     pub fn encoded_len(&self, ident: TokenStream) -> TokenStream {
         let module = self.ty.module();
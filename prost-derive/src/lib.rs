@@ -6,7 +6,7 @@ extern crate alloc;
 extern crate proc_macro;
 
 use anyhow::{bail, Error};
-use itertools::Itertools;
+use itertools::{Either, Itertools};
 use proc_macro::TokenStream;
 use proc_macro2::Span;
 use quote::quote;
@@ -16,7 +16,7 @@ use syn::{
 };
 
 mod field;
-use crate::field::Field;
+use crate::field::{Field, FieldSet};
 
 fn try_message(input: TokenStream) -> Result<TokenStream, Error> {
     let input: DeriveInput = syn::parse(input)?;
@@ -57,6 +57,17 @@ fn try_message(input: TokenStream) -> Result<TokenStream, Error> {
     };
 
     let mut next_tag: u32 = 1;
+    // Fields holding unknown fields or extensions, along with their position in the declaration.
+    let mut field_sets = Vec::new();
+    // Fields caching the encoded length of the message, along with their position.
+    let mut cached_sizes = Vec::new();
+    // The tags of the required fields whose presence is tracked, i.e. `#[prost(required,
+    // presence)]`, which are missing while they are `None`.
+    let mut presence_tags = Vec::new();
+    // The singular message fields, including the message variants of oneofs, are looked up by
+    // strict decodes, which reject repeated occurrences, and by incremental decodes, which decode
+    // them field by field.
+    let mut singular_message_fields = Vec::new();
     let mut fields = fields
         .into_iter()
         .enumerate()
@@ -68,10 +79,58 @@ fn try_message(input: TokenStream) -> Result<TokenStream, Error> {
                 };
                 quote!(#index)
             });
+            match field::is_cached_size(&field.attrs) {
+                Ok(true) => {
+                    cached_sizes.push((field_ident, i));
+                    return None;
+                }
+                Ok(false) => (),
+                Err(err) => {
+                    return Some(Err(err.context(format!(
+                        "invalid message field {}.{}",
+                        ident, field_ident
+                    ))))
+                }
+            }
+            match FieldSet::new(&field.attrs) {
+                Ok(Some(field_set)) => {
+                    field_sets.push((field_ident, i, field_set));
+                    return None;
+                }
+                Ok(None) => (),
+                Err(err) => {
+                    return Some(Err(err.context(format!(
+                        "invalid message field {}.{}",
+                        ident, field_ident
+                    ))))
+                }
+            }
+            let presence = match field::is_presence(&field.attrs) {
+                Ok(presence) => presence,
+                Err(err) => {
+                    return Some(Err(err.context(format!(
+                        "invalid message field {}.{}",
+                        ident, field_ident
+                    ))))
+                }
+            };
             match Field::new(field.attrs, Some(next_tag)) {
-                Ok(Some(field)) => {
-                    next_tag = field.tags().iter().max().map(|t| t + 1).unwrap_or(next_tag);
-                    Some(Ok((field_ident, field)))
+                Ok(Some(prost_field)) => {
+                    if presence {
+                        presence_tags.push(prost_field.tags()[0]);
+                    }
+                    next_tag = prost_field
+                        .tags()
+                        .iter()
+                        .max()
+                        .map(|t| t + 1)
+                        .unwrap_or(next_tag);
+                    singular_message_fields.extend(prost_field.singular_message_arm(
+                        &quote!(stringify!(#ident)),
+                        &quote!(stringify!(#field_ident)),
+                        &field.ty,
+                    ));
+                    Some(Ok((field_ident, prost_field)))
                 }
                 Ok(None) => None,
                 Err(err) => Some(Err(
@@ -85,8 +144,8 @@ fn try_message(input: TokenStream) -> Result<TokenStream, Error> {
     let unsorted_fields = fields.clone();
 
     // Sort the fields by tag number so that fields will be encoded in tag order.
-    // TODO: This encodes oneof fields in the position of their lowest tag,
-    // regardless of the currently occupied variant, is that consequential?
+    // This encodes oneof fields in the position of their lowest tag, regardless of the currently
+    // occupied variant; `encode_raw_deterministic` encodes them at the position of the variant.
     // See: https://developers.google.com/protocol-buffers/docs/encoding#order
     fields.sort_by_key(|&(_, ref field)| field.tags().into_iter().min().unwrap());
     let fields = fields;
@@ -102,13 +161,172 @@ fn try_message(input: TokenStream) -> Result<TokenStream, Error> {
         bail!("message {} has fields with duplicate tags", ident);
     }
 
+    let unknown_fields = field_sets
+        .iter()
+        .filter(|(_, _, field_set)| matches!(field_set, FieldSet::Unknown))
+        .map(|(field_ident, _, _)| field_ident)
+        .collect::<Vec<_>>();
+    let extension_set = field_sets
+        .iter()
+        .filter_map(|(field_ident, _, field_set)| match field_set {
+            FieldSet::Extensions(ranges) => Some((field_ident, ranges)),
+            FieldSet::Unknown => None,
+        })
+        .collect::<Vec<_>>();
+    if unknown_fields.len() > 1 {
+        bail!("message {} has multiple unknown_fields fields", ident);
+    }
+    if extension_set.len() > 1 {
+        bail!("message {} has multiple extensions fields", ident);
+    }
+    if cached_sizes.len() > 1 {
+        bail!("message {} has multiple cached_size fields", ident);
+    }
+    let unknown_fields = unknown_fields.into_iter().next();
+    let extension_set = extension_set.into_iter().next();
+    let cached_size = cached_sizes.first().map(|(field_ident, _)| field_ident);
+
+    // Extensions are encoded after the regular fields, followed by the unknown fields.
+    let field_set_idents = extension_set
+        .iter()
+        .map(|(field_ident, _)| *field_ident)
+        .chain(unknown_fields)
+        .collect::<Vec<_>>();
+
     let encoded_len = fields
         .iter()
-        .map(|&(ref field_ident, ref field)| field.encoded_len(quote!(self.#field_ident)));
+        .map(|&(ref field_ident, ref field)| field.encoded_len(quote!(self.#field_ident)))
+        .chain(
+            field_set_idents
+                .iter()
+                .map(|field_ident| quote!(::prost::Message::encoded_len(&self.#field_ident))),
+        );
 
     let encode = fields
         .iter()
-        .map(|&(ref field_ident, ref field)| field.encode(quote!(self.#field_ident)));
+        .map(|&(ref field_ident, ref field)| field.encode(quote!(self.#field_ident)))
+        .chain(
+            field_set_idents
+                .iter()
+                .map(|field_ident| quote!(::prost::Message::encode_raw(&self.#field_ident, buf);)),
+        );
+
+    // Messages with nested messages record their encoded lengths in a table while they are sized,
+    // and take them from the table while they are encoded, so that each message is sized once.
+    // Other messages are sized and encoded without the table.
+    let table_methods = if fields.iter().any(|(_, field)| field.has_nested_messages()) {
+        let encoded_len_with_table = fields
+            .iter()
+            .map(|(field_ident, field)| field.encoded_len_with_table(quote!(self.#field_ident)))
+            .chain(field_set_idents.iter().map(|field_ident| {
+                quote!(::prost::Message::encoded_len_with_table(&self.#field_ident, table))
+            }))
+            .collect::<Vec<_>>();
+        let encode_with_table = fields
+            .iter()
+            .map(|(field_ident, field)| field.encode_with_table(quote!(self.#field_ident)))
+            .chain(field_set_idents.iter().map(|field_ident| {
+                quote!(::prost::Message::encode_raw_with_table(&self.#field_ident, table, buf);)
+            }));
+
+        // A deterministic encode encodes oneofs at the position of their variant and map entries
+        // in key order, so the lengths are recorded and taken in that order.
+        let field_sets_with_table = field_set_idents
+            .iter()
+            .map(|field_ident| {
+                quote!(::prost::Message::encode_raw_with_table(&self.#field_ident, table, buf);)
+            })
+            .collect::<Vec<_>>();
+        let encode_with_table = if fields
+            .iter()
+            .any(|(_, field)| matches!(field, Field::Map(..) | Field::Oneof(..)))
+        {
+            let mut encode_deterministic_with_table = fields
+                .iter()
+                .flat_map(|(field_ident, field)| {
+                    field.encode_deterministic_with_table(quote!(self.#field_ident))
+                })
+                .collect::<Vec<_>>();
+            encode_deterministic_with_table.sort_by_key(|&(tag, _)| tag);
+            let encode_deterministic_with_table = encode_deterministic_with_table
+                .into_iter()
+                .map(|(_, encode)| encode);
+            quote! {
+                if table.is_deterministic() {
+                    #(#encode_deterministic_with_table)*
+                    #(#field_sets_with_table)*
+                } else {
+                    #(#encode_with_table)*
+                }
+            }
+        } else {
+            quote!(#(#encode_with_table)*)
+        };
+        let encoded_len_with_table = if fields
+            .iter()
+            .any(|(_, field)| matches!(field, Field::Oneof(..)))
+        {
+            let mut encoded_len_deterministic_with_table = fields
+                .iter()
+                .flat_map(|(field_ident, field)| {
+                    field.encoded_len_deterministic_with_table(quote!(self.#field_ident))
+                })
+                .collect::<Vec<_>>();
+            encoded_len_deterministic_with_table.sort_by_key(|&(tag, _)| tag);
+            let encoded_len_deterministic_with_table = encoded_len_deterministic_with_table
+                .into_iter()
+                .map(|(_, encoded_len)| encoded_len)
+                .chain(field_set_idents.iter().map(|field_ident| {
+                    quote!(::prost::Message::encoded_len_with_table(&self.#field_ident, table))
+                }));
+            quote! {
+                if table.is_deterministic() {
+                    0 #(+ #encoded_len_deterministic_with_table)*
+                } else {
+                    0 #(+ #encoded_len_with_table)*
+                }
+            }
+        } else {
+            quote!(0 #(+ #encoded_len_with_table)*)
+        };
+
+        quote! {
+            #[allow(unused_variables)]
+            fn encode_raw_with_table<B>(
+                &self,
+                table: &mut ::prost::encoding::EncodedLenTable,
+                buf: &mut B,
+            ) where B: ::prost::bytes::BufMut {
+                #encode_with_table
+            }
+
+            #[allow(unused_variables)]
+            fn encoded_len_with_table(&self, table: &mut ::prost::encoding::EncodedLenTable) -> usize {
+                #encoded_len_with_table
+            }
+        }
+    } else {
+        quote!()
+    };
+
+    let mut encode_deterministic = fields
+        .iter()
+        .flat_map(|(field_ident, field)| field.encode_deterministic(quote!(self.#field_ident)))
+        .collect::<Vec<_>>();
+    encode_deterministic.sort_by_key(|&(tag, _)| tag);
+    let encode_deterministic = encode_deterministic
+        .into_iter()
+        .map(|(_, encode)| encode)
+        .chain(
+            extension_set
+                .iter()
+                .map(|(field_ident, _)| quote!(::prost::Message::encode_raw_deterministic(&self.#field_ident, buf);)),
+        )
+        .chain(
+            unknown_fields
+                .iter()
+                .map(|field_ident| quote!(::prost::Message::encode_raw(&self.#field_ident, buf);)),
+        );
 
     let merge = fields.iter().map(|&(ref field_ident, ref field)| {
         let merge = field.merge(quote!(value));
@@ -126,6 +344,85 @@ fn try_message(input: TokenStream) -> Result<TokenStream, Error> {
         }
     });
 
+    let merge_extensions = extension_set.map(|(field_ident, ranges)| {
+        let ranges = ranges.iter().map(|(start, end)| quote!(#start..=#end));
+        let ranges = Itertools::intersperse(ranges, quote!(|));
+        quote! {
+            #(#ranges)* => ::prost::Message::merge_field(
+                &mut self.#field_ident, tag, wire_type, buf, ctx,
+            ),
+        }
+    });
+
+    let merge_unknown = match unknown_fields {
+        Some(field_ident) => quote! {
+            ::prost::Message::merge_field(&mut self.#field_ident, tag, wire_type, buf, ctx)
+        },
+        None => quote!(::prost::encoding::skip_field(wire_type, tag, buf, ctx)),
+    };
+    let merge_unknown = quote! {
+        {
+            ctx.check_unknown_field(tag)?;
+            #merge_unknown
+        }
+    };
+
+    let merge_from = fields
+        .iter()
+        .map(|(field_ident, field)| {
+            field.merge_from(quote!(self.#field_ident), quote!(other.#field_ident))
+        })
+        .chain(field_set_idents.iter().map(|field_ident| {
+            quote!(::prost::Message::merge_from(&mut self.#field_ident, &other.#field_ident);)
+        }));
+
+    let singular_message_fields = if singular_message_fields.is_empty() {
+        quote!()
+    } else {
+        quote! {
+            fn singular_message_field(
+                tag: u32,
+            ) -> ::core::option::Option<::prost::encoding::SingularMessageField> {
+                match tag {
+                    #(#singular_message_fields)*
+                    _ => ::core::option::Option::None,
+                }
+            }
+        }
+    };
+
+    // Required fields whose presence is tracked are missing while they are `None`, and the nested
+    // messages are searched for their own missing fields.
+    let find_missing_fields = fields
+        .iter()
+        .flat_map(|(field_ident, field)| {
+            let tag = field.tags()[0];
+            let missing = presence_tags.contains(&tag).then(|| {
+                quote! {
+                    if self.#field_ident.is_none() {
+                        paths.missing(projection, #tag, stringify!(#field_ident));
+                    }
+                }
+            });
+            missing
+                .into_iter()
+                .chain(field.find_missing_fields(field_ident))
+        })
+        .collect::<Vec<_>>();
+    let find_missing_fields = if find_missing_fields.is_empty() {
+        quote!()
+    } else {
+        quote! {
+            fn find_missing_fields(
+                &self,
+                projection: ::core::option::Option<&::prost::Projection>,
+                paths: &mut ::prost::encoding::MissingFieldPaths,
+            ) {
+                #(#find_missing_fields)*
+            }
+        }
+    };
+
     let struct_name = if fields.is_empty() {
         quote!()
     } else {
@@ -136,20 +433,59 @@ fn try_message(input: TokenStream) -> Result<TokenStream, Error> {
 
     let clear = fields
         .iter()
-        .map(|&(ref field_ident, ref field)| field.clear(quote!(self.#field_ident)));
+        .map(|&(ref field_ident, ref field)| field.clear(quote!(self.#field_ident)))
+        .chain(
+            field_set_idents
+                .iter()
+                .map(|field_ident| quote!(::prost::Message::clear(&mut self.#field_ident))),
+        );
+
+    // A message with a cached size holds its encoded length while it is encoded as a nested
+    // message, rather than the table of encoded lengths.
+    let cached_size_method = cached_size.map(|cached_size| {
+        quote! {
+            fn cached_size(&self) -> ::core::option::Option<&::prost::CachedSize> {
+                ::core::option::Option::Some(&self.#cached_size)
+            }
+        }
+    });
+
+    // Fields in declaration order, with field sets and the cached size on the right.
+    let mut declared_fields = unsorted_fields.iter().map(Either::Left).collect::<Vec<_>>();
+    let mut other_fields = field_sets
+        .iter()
+        .map(|(field_ident, index, _)| (*index, field_ident))
+        .chain(
+            cached_sizes
+                .iter()
+                .map(|(field_ident, index)| (*index, field_ident)),
+        )
+        .collect::<Vec<_>>();
+    other_fields.sort_by_key(|&(index, _)| index);
+    for (index, field_ident) in other_fields {
+        declared_fields.insert(index, Either::Right(field_ident));
+    }
 
     let default = if is_struct {
-        let default = fields.iter().map(|(field_ident, field)| {
-            let value = field.default();
-            quote!(#field_ident: #value,)
+        let default = declared_fields.iter().map(|field| match *field {
+            Either::Left((field_ident, field)) => {
+                let value = field.default();
+                quote!(#field_ident: #value,)
+            }
+            Either::Right(field_ident) => {
+                quote!(#field_ident: ::core::default::Default::default(),)
+            }
         });
         quote! {#ident {
             #(#default)*
         }}
     } else {
-        let default = fields.iter().map(|(_, field)| {
-            let value = field.default();
-            quote!(#value,)
+        let default = declared_fields.iter().map(|field| match *field {
+            Either::Left((_, field)) => {
+                let value = field.default();
+                quote!(#value,)
+            }
+            Either::Right(_) => quote!(::core::default::Default::default(),),
         });
         quote! {#ident (
             #(#default)*
@@ -178,6 +514,13 @@ fn try_message(input: TokenStream) -> Result<TokenStream, Error> {
                 #(#encode)*
             }
 
+            #table_methods
+
+            #[allow(unused_variables)]
+            fn encode_raw_deterministic<B>(&self, buf: &mut B) where B: ::prost::bytes::BufMut {
+                #(#encode_deterministic)*
+            }
+
             #[allow(unused_variables)]
             fn merge_field<B>(
                 &mut self,
@@ -190,7 +533,8 @@ fn try_message(input: TokenStream) -> Result<TokenStream, Error> {
                 #struct_name
                 match tag {
                     #(#merge)*
-                    _ => ::prost::encoding::skip_field(wire_type, tag, buf, ctx),
+                    #merge_extensions
+                    _ => #merge_unknown,
                 }
             }
 
@@ -199,6 +543,17 @@ fn try_message(input: TokenStream) -> Result<TokenStream, Error> {
                 0 #(+ #encoded_len)*
             }
 
+            #find_missing_fields
+
+            #singular_message_fields
+
+            #cached_size_method
+
+            #[allow(unused_variables)]
+            fn merge_from(&mut self, other: &Self) {
+                #(#merge_from)*
+            }
+
             fn clear(&mut self) {
                 #(#clear;)*
             }
@@ -213,8 +568,19 @@ fn try_message(input: TokenStream) -> Result<TokenStream, Error> {
     let expanded = if skip_debug {
         expanded
     } else {
-        let debugs = unsorted_fields.iter().map(|&(ref field_ident, ref field)| {
-            let wrapper = field.debug(quote!(self.#field_ident));
+        // The cached size is not part of the message's value.
+        let cached_size = cached_size.map(ToString::to_string);
+        let debug_fields = declared_fields.iter().filter(|field| match field {
+            Either::Left(_) => true,
+            Either::Right(field_ident) => Some(field_ident.to_string()) != cached_size,
+        });
+        let debugs = debug_fields.map(|field| {
+            let (field_ident, wrapper) = match *field {
+                Either::Left((field_ident, field)) => {
+                    (field_ident, field.debug(quote!(self.#field_ident)))
+                }
+                Either::Right(field_ident) => (field_ident, quote!(&self.#field_ident)),
+            };
             let call = if is_struct {
                 quote!(builder.field(stringify!(#field_ident), &wrapper))
             } else {
@@ -245,6 +611,23 @@ fn try_message(input: TokenStream) -> Result<TokenStream, Error> {
         }
     };
 
+    let expanded = match extension_set {
+        Some((field_ident, _)) => quote! {
+            #expanded
+
+            impl #impl_generics ::prost::Extendable for #ident #ty_generics #where_clause {
+                fn extension_set(&self) -> &::prost::ExtensionSet {
+                    &self.#field_ident
+                }
+
+                fn extension_set_mut(&mut self) -> &mut ::prost::ExtensionSet {
+                    &mut self.#field_ident
+                }
+            }
+        },
+        None => expanded,
+    };
+
     let expanded = quote! {
         #expanded
 
@@ -355,7 +738,10 @@ fn try_enumeration(input: TokenStream) -> Result<TokenStream, Error> {
             fn try_from(value: i32) -> ::core::result::Result<#ident, ::prost::DecodeError> {
                 match value {
                     #(#try_from,)*
-                    _ => ::core::result::Result::Err(::prost::DecodeError::new("invalid enumeration value")),
+                    _ => ::core::result::Result::Err(::prost::DecodeError::with_kind(
+                        ::prost::DecodeErrorKind::InvalidEnumValue,
+                        "invalid enumeration value",
+                    )),
                 }
             }
         }
@@ -391,6 +777,9 @@ fn try_oneof(input: TokenStream) -> Result<TokenStream, Error> {
 
     // Map the variants into 'fields'.
     let mut fields: Vec<(Ident, Field)> = Vec::new();
+    // The message and group variants may occur only once in strict decodes, and are decoded field
+    // by field by incremental decodes.
+    let mut singular_message_fields = Vec::new();
     for Variant {
         attrs,
         ident: variant_ident,
@@ -409,7 +798,14 @@ fn try_oneof(input: TokenStream) -> Result<TokenStream, Error> {
             bail!("Oneof enum variants must have a single field");
         }
         match Field::new_oneof(attrs)? {
-            Some(field) => fields.push((variant_ident, field)),
+            Some(field) => {
+                singular_message_fields.extend(field.singular_message_arm(
+                    &quote!(message),
+                    &quote!(field),
+                    &variant_fields[0].ty,
+                ));
+                fields.push((variant_ident, field))
+            }
             None => bail!("invalid oneof variant: oneof variants may not be ignored"),
         }
     }
@@ -438,6 +834,19 @@ fn try_oneof(input: TokenStream) -> Result<TokenStream, Error> {
         quote!(#ident::#variant_ident(ref value) => { #encode })
     });
 
+    let encode_deterministic = fields.iter().map(|(variant_ident, field)| {
+        let encode = field
+            .encode_deterministic(quote!(*value))
+            .into_iter()
+            .map(|(_, encode)| encode);
+        quote!(#ident::#variant_ident(ref value) => { #(#encode)* })
+    });
+
+    let tag = fields.iter().map(|(variant_ident, field)| {
+        let tag = field.tags()[0];
+        quote!(#ident::#variant_ident(_) => #tag)
+    });
+
     let merge = fields.iter().map(|&(ref variant_ident, ref field)| {
         let tag = field.tags()[0];
         let merge = field.merge(quote!(value));
@@ -457,11 +866,53 @@ fn try_oneof(input: TokenStream) -> Result<TokenStream, Error> {
         }
     });
 
+    let merge_from = fields.iter().map(|(variant_ident, field)| {
+        let merge_from = field.merge_from(quote!(*value), quote!(*other));
+        quote! {
+            #ident::#variant_ident(ref other) => {
+                match field {
+                    ::core::option::Option::Some(#ident::#variant_ident(ref mut value)) => {
+                        #merge_from
+                    },
+                    _ => {
+                        let mut owned_value = ::core::default::Default::default();
+                        let value = &mut owned_value;
+                        #merge_from
+                        *field = ::core::option::Option::Some(#ident::#variant_ident(owned_value));
+                    },
+                }
+            }
+        }
+    });
+
     let encoded_len = fields.iter().map(|&(ref variant_ident, ref field)| {
         let encoded_len = field.encoded_len(quote!(*value));
         quote!(#ident::#variant_ident(ref value) => #encoded_len)
     });
 
+    let encode_with_table = fields.iter().map(|(variant_ident, field)| {
+        let encode = field.encode_with_table(quote!(*value));
+        quote!(#ident::#variant_ident(ref value) => { #encode })
+    });
+
+    let encoded_len_with_table = fields.iter().map(|(variant_ident, field)| {
+        let encoded_len = field.encoded_len_with_table(quote!(*value));
+        quote!(#ident::#variant_ident(ref value) => #encoded_len)
+    });
+
+    // The message variants are searched for missing required fields, except lazy ones.
+    let find_missing_fields = fields
+        .iter()
+        .filter(|(_, field)| field.is_required_message())
+        .map(|(variant_ident, field)| {
+            let tag = field.tags()[0];
+            quote! {
+                #ident::#variant_ident(ref value) => {
+                    paths.message(projection, #tag, stringify!(#variant_ident), value)
+                }
+            }
+        });
+
     let expanded = quote! {
         impl #impl_generics #ident #ty_generics #where_clause {
             /// Encodes the message to a buffer.
@@ -471,6 +922,21 @@ fn try_oneof(input: TokenStream) -> Result<TokenStream, Error> {
                 }
             }
 
+            /// Encodes the message to a buffer, with map entries in key order and fields in field
+            /// number order.
+            pub fn encode_deterministic<B>(&self, buf: &mut B) where B: ::prost::bytes::BufMut {
+                match *self {
+                    #(#encode_deterministic,)*
+                }
+            }
+
+            /// Returns the field number of the current variant.
+            pub fn tag(&self) -> u32 {
+                match *self {
+                    #(#tag,)*
+                }
+            }
+
             /// Decodes an instance of the message from a buffer, and merges it into self.
             pub fn merge<B>(
                 field: &mut ::core::option::Option<#ident #ty_generics>,
@@ -486,6 +952,14 @@ fn try_oneof(input: TokenStream) -> Result<TokenStream, Error> {
                 }
             }
 
+            /// Merges another value of the oneof into the field. The value replaces the field,
+            /// unless both hold the same message variant, in which case the messages are merged.
+            pub fn merge_from(field: &mut ::core::option::Option<#ident #ty_generics>, other: &Self) {
+                match *other {
+                    #(#merge_from,)*
+                }
+            }
+
             /// Returns the encoded length of the message without a length delimiter.
             #[inline]
             pub fn encoded_len(&self) -> usize {
@@ -493,6 +967,63 @@ fn try_oneof(input: TokenStream) -> Result<TokenStream, Error> {
                     #(#encoded_len,)*
                 }
             }
+
+            /// Encodes the message to a buffer, taking the encoded lengths of its nested messages
+            /// from the table filled in by `encoded_len_with_table`.
+            #[doc(hidden)]
+            #[allow(unused_variables)]
+            pub fn encode_with_table<B>(
+                &self,
+                table: &mut ::prost::encoding::EncodedLenTable,
+                buf: &mut B,
+            ) where B: ::prost::bytes::BufMut {
+                match *self {
+                    #(#encode_with_table,)*
+                }
+            }
+
+            /// Returns the encoded length of the message without a length delimiter, recording the
+            /// encoded lengths of its nested messages in the table.
+            #[doc(hidden)]
+            #[allow(unused_variables)]
+            pub fn encoded_len_with_table(
+                &self,
+                table: &mut ::prost::encoding::EncodedLenTable,
+            ) -> usize {
+                match *self {
+                    #(#encoded_len_with_table,)*
+                }
+            }
+
+            /// Returns the variant with the given field number, as the field `field` of the
+            /// message `message`, if the variant holds a message or group.
+            #[doc(hidden)]
+            #[allow(unused_variables)]
+            pub fn singular_message_field(
+                message: &'static str,
+                field: &'static str,
+                tag: u32,
+            ) -> ::core::option::Option<::prost::encoding::SingularMessageField> {
+                match tag {
+                    #(#singular_message_fields)*
+                    _ => ::core::option::Option::None,
+                }
+            }
+
+            /// Records the paths of the missing required fields of the message of the current
+            /// variant, if any.
+            #[doc(hidden)]
+            #[allow(unused_variables, unreachable_patterns)]
+            pub fn find_missing_fields(
+                &self,
+                projection: ::core::option::Option<&::prost::Projection>,
+                paths: &mut ::prost::encoding::MissingFieldPaths,
+            ) {
+                match *self {
+                    #(#find_missing_fields,)*
+                    _ => (),
+                }
+            }
         }
 
     };
@@ -14,6 +14,147 @@ pub struct Version {
     #[prost(string, optional, tag = "4")]
     pub suffix: ::core::option::Option<::prost::alloc::string::String>,
 }
+#[allow(deprecated)]
+impl ::prost::text_format::TextFormat for Version {
+    fn print_fields(&self, printer: &mut ::prost::text_format::Printer<'_>) {
+        if let ::core::option::Option::Some(value) = &self.major {
+            printer.scalar("major", value);
+        }
+        if let ::core::option::Option::Some(value) = &self.minor {
+            printer.scalar("minor", value);
+        }
+        if let ::core::option::Option::Some(value) = &self.patch {
+            printer.scalar("patch", value);
+        }
+        if let ::core::option::Option::Some(value) = &self.suffix {
+            printer.scalar("suffix", value);
+        }
+    }
+    fn merge_text_field(
+        &mut self,
+        name: &str,
+        parser: &mut ::prost::text_format::Parser<'_>,
+    ) -> ::core::result::Result<bool, ::prost::text_format::ParseError> {
+        match name {
+            "major" => {
+                parser
+                    .scalar(
+                        self.major.get_or_insert_with(::core::default::Default::default),
+                    )?
+            }
+            "minor" => {
+                parser
+                    .scalar(
+                        self.minor.get_or_insert_with(::core::default::Default::default),
+                    )?
+            }
+            "patch" => {
+                parser
+                    .scalar(
+                        self.patch.get_or_insert_with(::core::default::Default::default),
+                    )?
+            }
+            "suffix" => {
+                parser
+                    .scalar(
+                        self.suffix.get_or_insert_with(::core::default::Default::default),
+                    )?
+            }
+            _ => return ::core::result::Result::Ok(false),
+        }
+        ::core::result::Result::Ok(true)
+    }
+}
+#[allow(deprecated)]
+impl ::prost::json::Json for Version {
+    fn write_json_fields(
+        &self,
+        writer: &mut ::prost::json::Writer<'_>,
+    ) -> ::core::result::Result<(), ::prost::json::JsonError> {
+        if let ::core::option::Option::Some(value) = &self.major {
+            writer.field("major", "major", |writer| writer.scalar(value))?;
+        }
+        if let ::core::option::Option::Some(value) = &self.minor {
+            writer.field("minor", "minor", |writer| writer.scalar(value))?;
+        }
+        if let ::core::option::Option::Some(value) = &self.patch {
+            writer.field("patch", "patch", |writer| writer.scalar(value))?;
+        }
+        if let ::core::option::Option::Some(value) = &self.suffix {
+            writer.field("suffix", "suffix", |writer| writer.scalar(value))?;
+        }
+        ::core::result::Result::Ok(())
+    }
+    fn merge_json_field(
+        &mut self,
+        name: &str,
+        parser: &mut ::prost::json::Parser<'_>,
+    ) -> ::core::result::Result<bool, ::prost::json::JsonError> {
+        match name {
+            "major" => parser.optional(&mut self.major, |parser| parser.scalar())?,
+            "minor" => parser.optional(&mut self.minor, |parser| parser.scalar())?,
+            "patch" => parser.optional(&mut self.patch, |parser| parser.scalar())?,
+            "suffix" => parser.optional(&mut self.suffix, |parser| parser.scalar())?,
+            _ => return ::core::result::Result::Ok(false),
+        }
+        ::core::result::Result::Ok(true)
+    }
+}
+#[allow(deprecated)]
+impl ::prost::FieldMasked for Version {
+    fn check_mask_tree(
+        mask: &::prost::field_mask::FieldMaskTree,
+    ) -> ::core::result::Result<(), ::prost::field_mask::FieldMaskError> {
+        for (name, mask) in mask.iter() {
+            match name {
+                "major" => ::prost::field_mask::check_leaf("major", mask)?,
+                "minor" => ::prost::field_mask::check_leaf("minor", mask)?,
+                "patch" => ::prost::field_mask::check_leaf("patch", mask)?,
+                "suffix" => ::prost::field_mask::check_leaf("suffix", mask)?,
+                _ => {
+                    return ::core::result::Result::Err(
+                        ::prost::field_mask::unknown_field(name),
+                    );
+                }
+            }
+        }
+        ::core::result::Result::Ok(())
+    }
+    fn merge_with_mask_tree(
+        &mut self,
+        src: &Self,
+        mask: &::prost::field_mask::FieldMaskTree,
+        _options: &::prost::field_mask::MergeOptions,
+    ) {
+        if mask.get("major").is_some() {
+            self.major.clone_from(&src.major);
+        }
+        if mask.get("minor").is_some() {
+            self.minor.clone_from(&src.minor);
+        }
+        if mask.get("patch").is_some() {
+            self.patch.clone_from(&src.patch);
+        }
+        if mask.get("suffix").is_some() {
+            self.suffix.clone_from(&src.suffix);
+        }
+    }
+    fn prune_with_mask_tree(&mut self, mask: &::prost::field_mask::FieldMaskTree) {
+        let default = Self::default();
+        if mask.get("major").is_none() {
+            self.major = default.major;
+        }
+        if mask.get("minor").is_none() {
+            self.minor = default.minor;
+        }
+        if mask.get("patch").is_none() {
+            self.patch = default.patch;
+        }
+        if mask.get("suffix").is_none() {
+            self.suffix = default.suffix;
+        }
+    }
+}
 /// An encoded CodeGeneratorRequest is written to the plugin's stdin.
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -46,6 +187,196 @@ pub struct CodeGeneratorRequest {
     #[prost(message, optional, tag = "3")]
     pub compiler_version: ::core::option::Option<Version>,
 }
+#[allow(deprecated)]
+impl ::prost::text_format::TextFormat for CodeGeneratorRequest {
+    fn print_fields(&self, printer: &mut ::prost::text_format::Printer<'_>) {
+        for value in &self.file_to_generate {
+            printer.scalar("file_to_generate", value);
+        }
+        if let ::core::option::Option::Some(value) = &self.parameter {
+            printer.scalar("parameter", value);
+        }
+        if let ::core::option::Option::Some(value) = &self.compiler_version {
+            printer.message("compiler_version", value);
+        }
+        for value in &self.proto_file {
+            printer.message("proto_file", value);
+        }
+    }
+    fn merge_text_field(
+        &mut self,
+        name: &str,
+        parser: &mut ::prost::text_format::Parser<'_>,
+    ) -> ::core::result::Result<bool, ::prost::text_format::ParseError> {
+        match name {
+            "file_to_generate" => parser.repeated_scalar(&mut self.file_to_generate)?,
+            "parameter" => {
+                parser
+                    .scalar(
+                        self
+                            .parameter
+                            .get_or_insert_with(::core::default::Default::default),
+                    )?
+            }
+            "proto_file" => parser.repeated_message(&mut self.proto_file)?,
+            "compiler_version" => {
+                parser
+                    .message(
+                        self
+                            .compiler_version
+                            .get_or_insert_with(::core::default::Default::default),
+                    )?
+            }
+            _ => return ::core::result::Result::Ok(false),
+        }
+        ::core::result::Result::Ok(true)
+    }
+}
+#[allow(deprecated)]
+impl ::prost::json::Json for CodeGeneratorRequest {
+    fn write_json_fields(
+        &self,
+        writer: &mut ::prost::json::Writer<'_>,
+    ) -> ::core::result::Result<(), ::prost::json::JsonError> {
+        if writer.emit_defaults() || !self.file_to_generate.is_empty() {
+            writer
+                .field(
+                    "fileToGenerate",
+                    "file_to_generate",
+                    |writer| {
+                        writer
+                            .list(
+                                &self.file_to_generate,
+                                |writer, value| writer.scalar(value),
+                            )
+                    },
+                )?;
+        }
+        if let ::core::option::Option::Some(value) = &self.parameter {
+            writer.field("parameter", "parameter", |writer| writer.scalar(value))?;
+        }
+        if let ::core::option::Option::Some(value) = &self.compiler_version {
+            writer
+                .field(
+                    "compilerVersion",
+                    "compiler_version",
+                    |writer| writer.message(value),
+                )?;
+        }
+        if writer.emit_defaults() || !self.proto_file.is_empty() {
+            writer
+                .field(
+                    "protoFile",
+                    "proto_file",
+                    |writer| {
+                        writer
+                            .list(
+                                &self.proto_file,
+                                |writer, value| writer.message(value),
+                            )
+                    },
+                )?;
+        }
+        ::core::result::Result::Ok(())
+    }
+    fn merge_json_field(
+        &mut self,
+        name: &str,
+        parser: &mut ::prost::json::Parser<'_>,
+    ) -> ::core::result::Result<bool, ::prost::json::JsonError> {
+        match name {
+            "fileToGenerate" | "file_to_generate" => {
+                parser.repeated(&mut self.file_to_generate, |parser| parser.scalar())?
+            }
+            "parameter" => {
+                parser.optional(&mut self.parameter, |parser| parser.scalar())?
+            }
+            "protoFile" | "proto_file" => {
+                parser.repeated(&mut self.proto_file, |parser| parser.message())?
+            }
+            "compilerVersion" | "compiler_version" => {
+                parser.optional(&mut self.compiler_version, |parser| parser.message())?
+            }
+            _ => return ::core::result::Result::Ok(false),
+        }
+        ::core::result::Result::Ok(true)
+    }
+}
+#[allow(deprecated)]
+impl ::prost::FieldMasked for CodeGeneratorRequest {
+    fn check_mask_tree(
+        mask: &::prost::field_mask::FieldMaskTree,
+    ) -> ::core::result::Result<(), ::prost::field_mask::FieldMaskError> {
+        for (name, mask) in mask.iter() {
+            match name {
+                "file_to_generate" => {
+                    ::prost::field_mask::check_leaf("file_to_generate", mask)?
+                }
+                "parameter" => ::prost::field_mask::check_leaf("parameter", mask)?,
+                "proto_file" => ::prost::field_mask::check_leaf("proto_file", mask)?,
+                "compiler_version" => {
+                    ::prost::field_mask::check_message::<
+                        Version,
+                    >("compiler_version", mask)?
+                }
+                _ => {
+                    return ::core::result::Result::Err(
+                        ::prost::field_mask::unknown_field(name),
+                    );
+                }
+            }
+        }
+        ::core::result::Result::Ok(())
+    }
+    fn merge_with_mask_tree(
+        &mut self,
+        src: &Self,
+        mask: &::prost::field_mask::FieldMaskTree,
+        options: &::prost::field_mask::MergeOptions,
+    ) {
+        if mask.get("file_to_generate").is_some() {
+            ::prost::field_mask::merge_repeated(
+                &mut self.file_to_generate,
+                &src.file_to_generate,
+                options,
+            );
+        }
+        if mask.get("parameter").is_some() {
+            self.parameter.clone_from(&src.parameter);
+        }
+        if mask.get("proto_file").is_some() {
+            ::prost::field_mask::merge_repeated(
+                &mut self.proto_file,
+                &src.proto_file,
+                options,
+            );
+        }
+        if let ::core::option::Option::Some(mask) = mask.get("compiler_version") {
+            ::prost::field_mask::merge_message(
+                &mut self.compiler_version,
+                src.compiler_version.as_ref(),
+                mask,
+                options,
+            );
+        }
+    }
+    fn prune_with_mask_tree(&mut self, mask: &::prost::field_mask::FieldMaskTree) {
+        let default = Self::default();
+        if mask.get("file_to_generate").is_none() {
+            self.file_to_generate = default.file_to_generate;
+        }
+        if mask.get("parameter").is_none() {
+            self.parameter = default.parameter;
+        }
+        if mask.get("proto_file").is_none() {
+            self.proto_file = default.proto_file;
+        }
+        ::prost::field_mask::prune_message(
+            &mut self.compiler_version,
+            mask.get("compiler_version"),
+        );
+    }
+}
 /// The plugin writes an encoded CodeGeneratorResponse to stdout.
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -67,6 +398,140 @@ pub struct CodeGeneratorResponse {
     #[prost(message, repeated, tag = "15")]
     pub file: ::prost::alloc::vec::Vec<code_generator_response::File>,
 }
+#[allow(deprecated)]
+impl ::prost::text_format::TextFormat for CodeGeneratorResponse {
+    fn print_fields(&self, printer: &mut ::prost::text_format::Printer<'_>) {
+        if let ::core::option::Option::Some(value) = &self.error {
+            printer.scalar("error", value);
+        }
+        if let ::core::option::Option::Some(value) = &self.supported_features {
+            printer.scalar("supported_features", value);
+        }
+        for value in &self.file {
+            printer.message("file", value);
+        }
+    }
+    fn merge_text_field(
+        &mut self,
+        name: &str,
+        parser: &mut ::prost::text_format::Parser<'_>,
+    ) -> ::core::result::Result<bool, ::prost::text_format::ParseError> {
+        match name {
+            "error" => {
+                parser
+                    .scalar(
+                        self.error.get_or_insert_with(::core::default::Default::default),
+                    )?
+            }
+            "supported_features" => {
+                parser
+                    .scalar(
+                        self
+                            .supported_features
+                            .get_or_insert_with(::core::default::Default::default),
+                    )?
+            }
+            "file" => parser.repeated_message(&mut self.file)?,
+            _ => return ::core::result::Result::Ok(false),
+        }
+        ::core::result::Result::Ok(true)
+    }
+}
+#[allow(deprecated)]
+impl ::prost::json::Json for CodeGeneratorResponse {
+    fn write_json_fields(
+        &self,
+        writer: &mut ::prost::json::Writer<'_>,
+    ) -> ::core::result::Result<(), ::prost::json::JsonError> {
+        if let ::core::option::Option::Some(value) = &self.error {
+            writer.field("error", "error", |writer| writer.scalar(value))?;
+        }
+        if let ::core::option::Option::Some(value) = &self.supported_features {
+            writer
+                .field(
+                    "supportedFeatures",
+                    "supported_features",
+                    |writer| writer.scalar(value),
+                )?;
+        }
+        if writer.emit_defaults() || !self.file.is_empty() {
+            writer
+                .field(
+                    "file",
+                    "file",
+                    |writer| {
+                        writer.list(&self.file, |writer, value| writer.message(value))
+                    },
+                )?;
+        }
+        ::core::result::Result::Ok(())
+    }
+    fn merge_json_field(
+        &mut self,
+        name: &str,
+        parser: &mut ::prost::json::Parser<'_>,
+    ) -> ::core::result::Result<bool, ::prost::json::JsonError> {
+        match name {
+            "error" => parser.optional(&mut self.error, |parser| parser.scalar())?,
+            "supportedFeatures" | "supported_features" => {
+                parser.optional(&mut self.supported_features, |parser| parser.scalar())?
+            }
+            "file" => parser.repeated(&mut self.file, |parser| parser.message())?,
+            _ => return ::core::result::Result::Ok(false),
+        }
+        ::core::result::Result::Ok(true)
+    }
+}
+#[allow(deprecated)]
+impl ::prost::FieldMasked for CodeGeneratorResponse {
+    fn check_mask_tree(
+        mask: &::prost::field_mask::FieldMaskTree,
+    ) -> ::core::result::Result<(), ::prost::field_mask::FieldMaskError> {
+        for (name, mask) in mask.iter() {
+            match name {
+                "error" => ::prost::field_mask::check_leaf("error", mask)?,
+                "supported_features" => {
+                    ::prost::field_mask::check_leaf("supported_features", mask)?
+                }
+                "file" => ::prost::field_mask::check_leaf("file", mask)?,
+                _ => {
+                    return ::core::result::Result::Err(
+                        ::prost::field_mask::unknown_field(name),
+                    );
+                }
+            }
+        }
+        ::core::result::Result::Ok(())
+    }
+    fn merge_with_mask_tree(
+        &mut self,
+        src: &Self,
+        mask: &::prost::field_mask::FieldMaskTree,
+        options: &::prost::field_mask::MergeOptions,
+    ) {
+        if mask.get("error").is_some() {
+            self.error.clone_from(&src.error);
+        }
+        if mask.get("supported_features").is_some() {
+            self.supported_features.clone_from(&src.supported_features);
+        }
+        if mask.get("file").is_some() {
+            ::prost::field_mask::merge_repeated(&mut self.file, &src.file, options);
+        }
+    }
+    fn prune_with_mask_tree(&mut self, mask: &::prost::field_mask::FieldMaskTree) {
+        let default = Self::default();
+        if mask.get("error").is_none() {
+            self.error = default.error;
+        }
+        if mask.get("supported_features").is_none() {
+            self.supported_features = default.supported_features;
+        }
+        if mask.get("file").is_none() {
+            self.file = default.file;
+        }
+    }
+}
 /// Nested message and enum types in `CodeGeneratorResponse`.
 pub mod code_generator_response {
     /// Represents a single generated file.
@@ -134,6 +599,187 @@ pub mod code_generator_response {
         #[prost(message, optional, tag = "16")]
         pub generated_code_info: ::core::option::Option<super::super::GeneratedCodeInfo>,
     }
+    #[allow(deprecated)]
+    impl ::prost::text_format::TextFormat for File {
+        fn print_fields(&self, printer: &mut ::prost::text_format::Printer<'_>) {
+            if let ::core::option::Option::Some(value) = &self.name {
+                printer.scalar("name", value);
+            }
+            if let ::core::option::Option::Some(value) = &self.insertion_point {
+                printer.scalar("insertion_point", value);
+            }
+            if let ::core::option::Option::Some(value) = &self.content {
+                printer.scalar("content", value);
+            }
+            if let ::core::option::Option::Some(value) = &self.generated_code_info {
+                printer.message("generated_code_info", value);
+            }
+        }
+        fn merge_text_field(
+            &mut self,
+            name: &str,
+            parser: &mut ::prost::text_format::Parser<'_>,
+        ) -> ::core::result::Result<bool, ::prost::text_format::ParseError> {
+            match name {
+                "name" => {
+                    parser
+                        .scalar(
+                            self
+                                .name
+                                .get_or_insert_with(::core::default::Default::default),
+                        )?
+                }
+                "insertion_point" => {
+                    parser
+                        .scalar(
+                            self
+                                .insertion_point
+                                .get_or_insert_with(::core::default::Default::default),
+                        )?
+                }
+                "content" => {
+                    parser
+                        .scalar(
+                            self
+                                .content
+                                .get_or_insert_with(::core::default::Default::default),
+                        )?
+                }
+                "generated_code_info" => {
+                    parser
+                        .message(
+                            self
+                                .generated_code_info
+                                .get_or_insert_with(::core::default::Default::default),
+                        )?
+                }
+                _ => return ::core::result::Result::Ok(false),
+            }
+            ::core::result::Result::Ok(true)
+        }
+    }
+    #[allow(deprecated)]
+    impl ::prost::json::Json for File {
+        fn write_json_fields(
+            &self,
+            writer: &mut ::prost::json::Writer<'_>,
+        ) -> ::core::result::Result<(), ::prost::json::JsonError> {
+            if let ::core::option::Option::Some(value) = &self.name {
+                writer.field("name", "name", |writer| writer.scalar(value))?;
+            }
+            if let ::core::option::Option::Some(value) = &self.insertion_point {
+                writer
+                    .field(
+                        "insertionPoint",
+                        "insertion_point",
+                        |writer| writer.scalar(value),
+                    )?;
+            }
+            if let ::core::option::Option::Some(value) = &self.content {
+                writer.field("content", "content", |writer| writer.scalar(value))?;
+            }
+            if let ::core::option::Option::Some(value) = &self.generated_code_info {
+                writer
+                    .field(
+                        "generatedCodeInfo",
+                        "generated_code_info",
+                        |writer| writer.message(value),
+                    )?;
+            }
+            ::core::result::Result::Ok(())
+        }
+        fn merge_json_field(
+            &mut self,
+            name: &str,
+            parser: &mut ::prost::json::Parser<'_>,
+        ) -> ::core::result::Result<bool, ::prost::json::JsonError> {
+            match name {
+                "name" => parser.optional(&mut self.name, |parser| parser.scalar())?,
+                "insertionPoint" | "insertion_point" => {
+                    parser.optional(&mut self.insertion_point, |parser| parser.scalar())?
+                }
+                "content" => {
+                    parser.optional(&mut self.content, |parser| parser.scalar())?
+                }
+                "generatedCodeInfo" | "generated_code_info" => {
+                    parser
+                        .optional(
+                            &mut self.generated_code_info,
+                            |parser| parser.message(),
+                        )?
+                }
+                _ => return ::core::result::Result::Ok(false),
+            }
+            ::core::result::Result::Ok(true)
+        }
+    }
+    #[allow(deprecated)]
+    impl ::prost::FieldMasked for File {
+        fn check_mask_tree(
+            mask: &::prost::field_mask::FieldMaskTree,
+        ) -> ::core::result::Result<(), ::prost::field_mask::FieldMaskError> {
+            for (name, mask) in mask.iter() {
+                match name {
+                    "name" => ::prost::field_mask::check_leaf("name", mask)?,
+                    "insertion_point" => {
+                        ::prost::field_mask::check_leaf("insertion_point", mask)?
+                    }
+                    "content" => ::prost::field_mask::check_leaf("content", mask)?,
+                    "generated_code_info" => {
+                        ::prost::field_mask::check_message::<
+                            super::super::GeneratedCodeInfo,
+                        >("generated_code_info", mask)?
+                    }
+                    _ => {
+                        return ::core::result::Result::Err(
+                            ::prost::field_mask::unknown_field(name),
+                        );
+                    }
+                }
+            }
+            ::core::result::Result::Ok(())
+        }
+        fn merge_with_mask_tree(
+            &mut self,
+            src: &Self,
+            mask: &::prost::field_mask::FieldMaskTree,
+            options: &::prost::field_mask::MergeOptions,
+        ) {
+            if mask.get("name").is_some() {
+                self.name.clone_from(&src.name);
+            }
+            if mask.get("insertion_point").is_some() {
+                self.insertion_point.clone_from(&src.insertion_point);
+            }
+            if mask.get("content").is_some() {
+                self.content.clone_from(&src.content);
+            }
+            if let ::core::option::Option::Some(mask) = mask.get("generated_code_info") {
+                ::prost::field_mask::merge_message(
+                    &mut self.generated_code_info,
+                    src.generated_code_info.as_ref(),
+                    mask,
+                    options,
+                );
+            }
+        }
+        fn prune_with_mask_tree(&mut self, mask: &::prost::field_mask::FieldMaskTree) {
+            let default = Self::default();
+            if mask.get("name").is_none() {
+                self.name = default.name;
+            }
+            if mask.get("insertion_point").is_none() {
+                self.insertion_point = default.insertion_point;
+            }
+            if mask.get("content").is_none() {
+                self.content = default.content;
+            }
+            ::prost::field_mask::prune_message(
+                &mut self.generated_code_info,
+                mask.get("generated_code_info"),
+            );
+        }
+    }
     /// Sync with code_generator.h.
     #[derive(
         Clone,
@@ -0,0 +1,1387 @@
+use core::fmt;
+use core::hash::{Hash, Hasher};
+
+use prost::alloc::collections::BTreeMap;
+use prost::alloc::format;
+use prost::alloc::string::{String, ToString};
+use prost::alloc::sync::Arc;
+use prost::alloc::vec::Vec;
+use prost::bytes::Bytes;
+
+use super::{DynamicMessage, Value};
+use crate::field_descriptor_proto::{Label, Type};
+use crate::{DescriptorProto, EnumDescriptorProto, FieldDescriptorProto, FileDescriptorSet};
+
+/// A set of message and enum types, resolved from a [`FileDescriptorSet`].
+///
+/// Cloning a pool is cheap, and the descriptors returned by the pool keep it alive.
+#[derive(Clone)]
+pub struct DescriptorPool {
+    inner: Arc<PoolInner>,
+}
+
+struct PoolInner {
+    messages: Vec<MessageInner>,
+    enums: Vec<EnumInner>,
+    /// The index of each message and enum type, by fully-qualified name without a leading dot.
+    names: BTreeMap<String, Definition>,
+}
+
+#[derive(Clone, Copy)]
+enum Definition {
+    Message(usize),
+    Enum(usize),
+}
+
+pub(super) struct MessageInner {
+    full_name: String,
+    map_entry: bool,
+    pub(super) fields: Vec<FieldInner>,
+    pub(super) oneofs: Vec<OneofInner>,
+    pub(super) field_numbers: BTreeMap<u32, usize>,
+    field_names: BTreeMap<String, usize>,
+    /// The fields in the order they are encoded by generated messages: by field number, except
+    /// that the fields of a oneof are encoded at the position of the oneof's lowest field number.
+    pub(super) encode_order: Vec<usize>,
+}
+
+pub(super) struct FieldInner {
+    name: String,
+    json_name: String,
+    pub(super) number: u32,
+    pub(super) kind: FieldKind,
+    label: Label,
+    pub(super) packed: bool,
+    pub(super) supports_presence: bool,
+    pub(super) oneof: Option<usize>,
+    /// The value of the field when it is not set, or `None` for message fields.
+    pub(super) default: Option<Value>,
+}
+
+/// The type of a field, with message and enum types identified by their index in the pool.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum FieldKind {
+    Double,
+    Float,
+    Int32,
+    Int64,
+    Uint32,
+    Uint64,
+    Sint32,
+    Sint64,
+    Fixed32,
+    Fixed64,
+    Sfixed32,
+    Sfixed64,
+    Bool,
+    String,
+    Bytes,
+    Message(usize),
+    Group(usize),
+    Enum(usize),
+}
+
+pub(super) struct OneofInner {
+    name: String,
+    pub(super) fields: Vec<usize>,
+    synthetic: bool,
+}
+
+struct EnumInner {
+    full_name: String,
+    values: Vec<(String, i32)>,
+}
+
+/// An error resolving the types of a [`FileDescriptorSet`].
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Debug, PartialEq)]
+#[non_exhaustive]
+pub enum DescriptorError {
+    /// A message or enum type is defined more than once.
+    DuplicateName(String),
+    /// The type of a field could not be resolved.
+    UnknownType {
+        /// The fully-qualified name of the field.
+        field: String,
+        /// The type name of the field.
+        type_name: String,
+    },
+    /// A field has no number, a number which is out of range, or the same number as another
+    /// field of the message.
+    InvalidFieldNumber(String),
+    /// The default value of a field could not be parsed.
+    InvalidDefault(String),
+    /// A map entry message does not have valid key and value fields.
+    InvalidMapEntry(String),
+}
+
+impl fmt::Display for DescriptorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DescriptorError::DuplicateName(name) => write!(f, "duplicate type name: {}", name),
+            DescriptorError::UnknownType { field, type_name } => {
+                write!(f, "unknown type {} of field {}", type_name, field)
+            }
+            DescriptorError::InvalidFieldNumber(field) => {
+                write!(f, "invalid number of field {}", field)
+            }
+            DescriptorError::InvalidDefault(field) => {
+                write!(f, "invalid default value of field {}", field)
+            }
+            DescriptorError::InvalidMapEntry(message) => {
+                write!(f, "invalid map entry message {}", message)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DescriptorError {}
+
+impl DescriptorPool {
+    /// Resolves the types defined by a set of files.
+    ///
+    /// All types referenced by fields must be defined in the set, so the set should include the
+    /// dependencies of each file, as generated by `protoc --include_imports`.
+    pub fn new(file_descriptor_set: FileDescriptorSet) -> Result<DescriptorPool, DescriptorError> {
+        let mut builder = Builder::default();
+        for file in &file_descriptor_set.file {
+            let proto3 = file.syntax() == "proto3";
+            for message in &file.message_type {
+                builder.add_message(file.package(), message, proto3)?;
+            }
+            for enum_type in &file.enum_type {
+                builder.add_enum(file.package(), enum_type)?;
+            }
+        }
+        builder.build()
+    }
+
+    /// Returns the message type with the given fully-qualified name, such as
+    /// `google.protobuf.Timestamp`.
+    pub fn get_message_by_name(&self, name: &str) -> Option<MessageDescriptor> {
+        match self.inner.names.get(name.trim_start_matches('.')) {
+            Some(&Definition::Message(index)) => Some(MessageDescriptor {
+                pool: self.clone(),
+                index,
+            }),
+            _ => None,
+        }
+    }
+
+    /// Returns the enum type with the given fully-qualified name.
+    pub fn get_enum_by_name(&self, name: &str) -> Option<EnumDescriptor> {
+        match self.inner.names.get(name.trim_start_matches('.')) {
+            Some(&Definition::Enum(index)) => Some(EnumDescriptor {
+                pool: self.clone(),
+                index,
+            }),
+            _ => None,
+        }
+    }
+
+    /// Returns an iterator over the message types in the pool, including map entry messages.
+    pub fn messages(&self) -> impl ExactSizeIterator<Item = MessageDescriptor> + '_ {
+        (0..self.inner.messages.len()).map(move |index| MessageDescriptor {
+            pool: self.clone(),
+            index,
+        })
+    }
+
+    /// Returns an iterator over the enum types in the pool.
+    pub fn enums(&self) -> impl ExactSizeIterator<Item = EnumDescriptor> + '_ {
+        (0..self.inner.enums.len()).map(move |index| EnumDescriptor {
+            pool: self.clone(),
+            index,
+        })
+    }
+}
+
+impl fmt::Debug for DescriptorPool {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DescriptorPool")
+            .field("messages", &self.messages().collect::<Vec<_>>())
+            .field("enums", &self.enums().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl PartialEq for DescriptorPool {
+    fn eq(&self, other: &DescriptorPool) -> bool {
+        Arc::ptr_eq(&self.inner, &other.inner)
+    }
+}
+
+impl Eq for DescriptorPool {}
+
+/// Collects the definitions of a `FileDescriptorSet` before the field types are resolved.
+#[derive(Default)]
+struct Builder<'a> {
+    /// The message types, along with their descriptors and whether they are defined in a
+    /// `proto3` file.
+    messages: Vec<(String, &'a DescriptorProto, bool)>,
+    enums: Vec<EnumInner>,
+    names: BTreeMap<String, Definition>,
+}
+
+impl<'a> Builder<'a> {
+    fn add_name(&mut self, name: &str, definition: Definition) -> Result<(), DescriptorError> {
+        if self.names.insert(name.to_string(), definition).is_some() {
+            return Err(DescriptorError::DuplicateName(name.to_string()));
+        }
+        Ok(())
+    }
+
+    fn add_message(
+        &mut self,
+        scope: &str,
+        message: &'a DescriptorProto,
+        proto3: bool,
+    ) -> Result<(), DescriptorError> {
+        let full_name = qualify(scope, message.name());
+        self.add_name(&full_name, Definition::Message(self.messages.len()))?;
+        self.messages.push((full_name.clone(), message, proto3));
+        for nested in &message.nested_type {
+            self.add_message(&full_name, nested, proto3)?;
+        }
+        for enum_type in &message.enum_type {
+            self.add_enum(&full_name, enum_type)?;
+        }
+        Ok(())
+    }
+
+    fn add_enum(
+        &mut self,
+        scope: &str,
+        enum_type: &EnumDescriptorProto,
+    ) -> Result<(), DescriptorError> {
+        let full_name = qualify(scope, enum_type.name());
+        self.add_name(&full_name, Definition::Enum(self.enums.len()))?;
+        self.enums.push(EnumInner {
+            full_name,
+            values: enum_type
+                .value
+                .iter()
+                .map(|value| (value.name().to_string(), value.number()))
+                .collect(),
+        });
+        Ok(())
+    }
+
+    fn build(self) -> Result<DescriptorPool, DescriptorError> {
+        let messages = self
+            .messages
+            .iter()
+            .map(|(full_name, message, proto3)| self.resolve_message(full_name, message, *proto3))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        for message in &messages {
+            for field in &message.fields {
+                if let FieldKind::Message(index) = field.kind {
+                    if messages[index].map_entry && !valid_map_entry(&messages[index]) {
+                        return Err(DescriptorError::InvalidMapEntry(
+                            messages[index].full_name.clone(),
+                        ));
+                    }
+                }
+            }
+        }
+
+        Ok(DescriptorPool {
+            inner: Arc::new(PoolInner {
+                messages,
+                enums: self.enums,
+                names: self.names,
+            }),
+        })
+    }
+
+    fn resolve_message(
+        &self,
+        full_name: &str,
+        message: &DescriptorProto,
+        proto3: bool,
+    ) -> Result<MessageInner, DescriptorError> {
+        let mut oneofs = message
+            .oneof_decl
+            .iter()
+            .map(|oneof| OneofInner {
+                name: oneof.name().to_string(),
+                fields: Vec::new(),
+                synthetic: false,
+            })
+            .collect::<Vec<_>>();
+        let mut fields = Vec::with_capacity(message.field.len());
+        let mut field_numbers = BTreeMap::new();
+        let mut field_names = BTreeMap::new();
+
+        for (index, field) in message.field.iter().enumerate() {
+            let field_name = format!("{}.{}", full_name, field.name());
+            let number = match u32::try_from(field.number()) {
+                Ok(number @ 1..=0x1FFF_FFFF) => number,
+                _ => return Err(DescriptorError::InvalidFieldNumber(field_name)),
+            };
+            if field_numbers.insert(number, index).is_some() {
+                return Err(DescriptorError::InvalidFieldNumber(field_name));
+            }
+            field_names.insert(field.name().to_string(), index);
+
+            let kind = self.resolve_kind(full_name, &field_name, field)?;
+            let repeated = field.label() == Label::Repeated;
+            let oneof = match field.oneof_index {
+                Some(oneof_index) => {
+                    let oneof = oneofs
+                        .get_mut(oneof_index as usize)
+                        .ok_or_else(|| DescriptorError::InvalidFieldNumber(field_name.clone()))?;
+                    oneof.fields.push(index);
+                    oneof.synthetic = field.proto3_optional();
+                    Some(oneof_index as usize)
+                }
+                None => None,
+            };
+            let packable = !matches!(
+                kind,
+                FieldKind::String | FieldKind::Bytes | FieldKind::Message(_) | FieldKind::Group(_)
+            );
+            let packed = repeated
+                && packable
+                && field
+                    .options
+                    .as_ref()
+                    .and_then(|options| options.packed)
+                    .unwrap_or(proto3);
+            let supports_presence = !repeated
+                && (!proto3
+                    || oneof.is_some()
+                    || matches!(kind, FieldKind::Message(_) | FieldKind::Group(_)));
+            let default = match field.default_value {
+                Some(ref default) => Some(
+                    self.parse_default(kind, default)
+                        .ok_or_else(|| DescriptorError::InvalidDefault(field_name.clone()))?,
+                ),
+                None => self.zero_value(kind),
+            };
+
+            fields.push(FieldInner {
+                name: field.name().to_string(),
+                json_name: field
+                    .json_name
+                    .clone()
+                    .unwrap_or_else(|| to_json_name(field.name())),
+                number,
+                kind,
+                label: field.label(),
+                packed,
+                supports_presence,
+                oneof,
+                default,
+            });
+        }
+
+        // Fields are encoded in the order of their numbers, with the fields of a oneof at the
+        // position of the oneof's lowest field number, as `prost-derive` does.
+        let mut encode_order = (0..fields.len()).collect::<Vec<_>>();
+        encode_order.sort_by_key(|&index| {
+            let field: &FieldInner = &fields[index];
+            let position = match field.oneof {
+                Some(oneof) if !oneofs[oneof].synthetic => oneofs[oneof]
+                    .fields
+                    .iter()
+                    .map(|&index| fields[index].number)
+                    .min()
+                    .unwrap_or(field.number),
+                _ => field.number,
+            };
+            (position, field.number)
+        });
+
+        Ok(MessageInner {
+            full_name: full_name.to_string(),
+            map_entry: message
+                .options
+                .as_ref()
+                .is_some_and(|options| options.map_entry()),
+            fields,
+            oneofs,
+            field_numbers,
+            field_names,
+            encode_order,
+        })
+    }
+
+    fn resolve_kind(
+        &self,
+        scope: &str,
+        field_name: &str,
+        field: &FieldDescriptorProto,
+    ) -> Result<FieldKind, DescriptorError> {
+        let kind = match field.r#type {
+            Some(_) => match field.r#type() {
+                Type::Double => FieldKind::Double,
+                Type::Float => FieldKind::Float,
+                Type::Int64 => FieldKind::Int64,
+                Type::Uint64 => FieldKind::Uint64,
+                Type::Int32 => FieldKind::Int32,
+                Type::Fixed64 => FieldKind::Fixed64,
+                Type::Fixed32 => FieldKind::Fixed32,
+                Type::Bool => FieldKind::Bool,
+                Type::String => FieldKind::String,
+                Type::Bytes => FieldKind::Bytes,
+                Type::Uint32 => FieldKind::Uint32,
+                Type::Sfixed32 => FieldKind::Sfixed32,
+                Type::Sfixed64 => FieldKind::Sfixed64,
+                Type::Sint32 => FieldKind::Sint32,
+                Type::Sint64 => FieldKind::Sint64,
+                Type::Group | Type::Message | Type::Enum => {
+                    return self.resolve_type_name(scope, field_name, field)
+                }
+            },
+            // The type may be left unset for message and enum fields, if the type name
+            // has not been resolved by the compiler.
+            None => return self.resolve_type_name(scope, field_name, field),
+        };
+        Ok(kind)
+    }
+
+    fn resolve_type_name(
+        &self,
+        scope: &str,
+        field_name: &str,
+        field: &FieldDescriptorProto,
+    ) -> Result<FieldKind, DescriptorError> {
+        let type_name = field.type_name();
+        let definition = if let Some(name) = type_name.strip_prefix('.') {
+            self.names.get(name)
+        } else {
+            // Relative names are resolved from the innermost scope outwards, as in C++.
+            let mut scope = scope;
+            loop {
+                if let Some(definition) = self.names.get(&qualify(scope, type_name)) {
+                    break Some(definition);
+                }
+                if scope.is_empty() {
+                    break None;
+                }
+                scope = scope.rfind('.').map_or("", |index| &scope[..index]);
+            }
+        };
+        let type_ = field.r#type.and_then(|type_| Type::try_from(type_).ok());
+        let kind = match (definition, type_) {
+            (Some(&Definition::Message(index)), Some(Type::Group)) => FieldKind::Group(index),
+            (Some(&Definition::Message(index)), None | Some(Type::Message)) => {
+                FieldKind::Message(index)
+            }
+            (Some(&Definition::Enum(index)), None | Some(Type::Enum)) => FieldKind::Enum(index),
+            _ => {
+                return Err(DescriptorError::UnknownType {
+                    field: field_name.to_string(),
+                    type_name: type_name.to_string(),
+                })
+            }
+        };
+        Ok(kind)
+    }
+
+    /// Returns the value of a field of the given type without a declared default value, or `None`
+    /// for message fields.
+    fn zero_value(&self, kind: FieldKind) -> Option<Value> {
+        let value = match kind {
+            FieldKind::Double => Value::F64(0.0),
+            FieldKind::Float => Value::F32(0.0),
+            FieldKind::Int32 | FieldKind::Sint32 | FieldKind::Sfixed32 => Value::I32(0),
+            FieldKind::Int64 | FieldKind::Sint64 | FieldKind::Sfixed64 => Value::I64(0),
+            FieldKind::Uint32 | FieldKind::Fixed32 => Value::U32(0),
+            FieldKind::Uint64 | FieldKind::Fixed64 => Value::U64(0),
+            FieldKind::Bool => Value::Bool(false),
+            FieldKind::String => Value::String(String::new()),
+            FieldKind::Bytes => Value::Bytes(Bytes::new()),
+            FieldKind::Enum(index) => {
+                Value::EnumNumber(self.enums[index].values.first().map_or(0, |value| value.1))
+            }
+            FieldKind::Message(_) | FieldKind::Group(_) => return None,
+        };
+        Some(value)
+    }
+
+    /// Parses the default value of a field, in the format used by `protoc`.
+    fn parse_default(&self, kind: FieldKind, default: &str) -> Option<Value> {
+        let value = match kind {
+            FieldKind::Double => Value::F64(parse_float(default)?),
+            FieldKind::Float => Value::F32(parse_float(default)? as f32),
+            FieldKind::Int32 | FieldKind::Sint32 | FieldKind::Sfixed32 => {
+                Value::I32(default.parse().ok()?)
+            }
+            FieldKind::Int64 | FieldKind::Sint64 | FieldKind::Sfixed64 => {
+                Value::I64(default.parse().ok()?)
+            }
+            FieldKind::Uint32 | FieldKind::Fixed32 => Value::U32(default.parse().ok()?),
+            FieldKind::Uint64 | FieldKind::Fixed64 => Value::U64(default.parse().ok()?),
+            FieldKind::Bool => Value::Bool(default.parse().ok()?),
+            FieldKind::String => Value::String(default.to_string()),
+            FieldKind::Bytes => Value::Bytes(Bytes::from(unescape_c_escape_string(default)?)),
+            FieldKind::Enum(index) => Value::EnumNumber(
+                self.enums[index]
+                    .values
+                    .iter()
+                    .find(|(name, _)| name == default)?
+                    .1,
+            ),
+            FieldKind::Message(_) | FieldKind::Group(_) => return None,
+        };
+        Some(value)
+    }
+}
+
+fn qualify(scope: &str, name: &str) -> String {
+    if scope.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}.{}", scope, name)
+    }
+}
+
+/// Returns `true` if a map entry message has a key field with a valid map key type, and a value
+/// field.
+fn valid_map_entry(message: &MessageInner) -> bool {
+    let field = |number| {
+        message
+            .field_numbers
+            .get(&number)
+            .map(|&index| &message.fields[index])
+    };
+    match (field(1), field(2)) {
+        (Some(key), Some(value)) => {
+            key.label != Label::Repeated
+                && value.label != Label::Repeated
+                && !matches!(
+                    key.kind,
+                    FieldKind::Double
+                        | FieldKind::Float
+                        | FieldKind::Bytes
+                        | FieldKind::Message(_)
+                        | FieldKind::Group(_)
+                        | FieldKind::Enum(_)
+                )
+        }
+        _ => false,
+    }
+}
+
+fn parse_float(value: &str) -> Option<f64> {
+    match value {
+        "inf" => Some(f64::INFINITY),
+        "-inf" => Some(f64::NEG_INFINITY),
+        "nan" => Some(f64::NAN),
+        _ => value.parse().ok(),
+    }
+}
+
+/// Computes the JSON name of a field, for descriptors which don't include it.
+fn to_json_name(name: &str) -> String {
+    let mut json_name = String::with_capacity(name.len());
+    let mut capitalize = false;
+    for c in name.chars() {
+        if c == '_' {
+            capitalize = true;
+        } else if capitalize {
+            json_name.push(c.to_ascii_uppercase());
+            capitalize = false;
+        } else {
+            json_name.push(c);
+        }
+    }
+    json_name
+}
+
+/// Unescapes a C-escaped bytes default value, as produced by `protoc`.
+fn unescape_c_escape_string(s: &str) -> Option<Vec<u8>> {
+    let src = s.as_bytes();
+    let mut dst = Vec::with_capacity(src.len());
+    let mut p = 0;
+    while p < src.len() {
+        if src[p] != b'\\' {
+            dst.push(src[p]);
+            p += 1;
+            continue;
+        }
+        p += 1;
+        let c = *src.get(p)?;
+        p += 1;
+        let byte = match c {
+            b'a' => 0x07,
+            b'b' => 0x08,
+            b'f' => 0x0C,
+            b'n' => 0x0A,
+            b'r' => 0x0D,
+            b't' => 0x09,
+            b'v' => 0x0B,
+            b'\\' | b'?' | b'\'' | b'"' => c,
+            b'0'..=b'7' => {
+                let mut octal = u32::from(c - b'0');
+                for _ in 0..2 {
+                    match src.get(p) {
+                        Some(&c @ b'0'..=b'7') => {
+                            octal = octal * 8 + u32::from(c - b'0');
+                            p += 1;
+                        }
+                        _ => break,
+                    }
+                }
+                u8::try_from(octal).ok()?
+            }
+            b'x' => {
+                let mut hex = 0;
+                let start = p;
+                while p < src.len() && p < start + 2 && src[p].is_ascii_hexdigit() {
+                    hex = hex * 16 + (src[p] as char).to_digit(16)?;
+                    p += 1;
+                }
+                if p == start {
+                    return None;
+                }
+                hex as u8
+            }
+            _ => return None,
+        };
+        dst.push(byte);
+    }
+    Some(dst)
+}
+
+/// A message type.
+#[derive(Clone, PartialEq, Eq)]
+pub struct MessageDescriptor {
+    pool: DescriptorPool,
+    index: usize,
+}
+
+impl MessageDescriptor {
+    pub(super) fn inner(&self) -> &MessageInner {
+        &self.pool.inner.messages[self.index]
+    }
+
+    /// Returns the pool which defines the message type.
+    pub fn pool(&self) -> &DescriptorPool {
+        &self.pool
+    }
+
+    /// Returns the name of the message type, without its package or parent message.
+    pub fn name(&self) -> &str {
+        let full_name = self.full_name();
+        full_name.rsplit('.').next().unwrap_or(full_name)
+    }
+
+    /// Returns the fully-qualified name of the message type, such as `google.protobuf.Any`.
+    pub fn full_name(&self) -> &str {
+        &self.inner().full_name
+    }
+
+    /// Returns `true` if the message type is the entry type of a map field.
+    pub fn is_map_entry(&self) -> bool {
+        self.inner().map_entry
+    }
+
+    /// Returns an iterator over the fields of the message, in declaration order.
+    pub fn fields(&self) -> impl ExactSizeIterator<Item = FieldDescriptor> + '_ {
+        (0..self.inner().fields.len()).map(move |index| self.field(index))
+    }
+
+    /// Returns the field with the given number.
+    pub fn get_field(&self, number: u32) -> Option<FieldDescriptor> {
+        let index = *self.inner().field_numbers.get(&number)?;
+        Some(self.field(index))
+    }
+
+    /// Returns the field with the given name.
+    pub fn get_field_by_name(&self, name: &str) -> Option<FieldDescriptor> {
+        let index = *self.inner().field_names.get(name)?;
+        Some(self.field(index))
+    }
+
+    /// Returns an iterator over the oneofs of the message, excluding the synthetic oneofs of
+    /// `proto3` optional fields.
+    pub fn oneofs(&self) -> impl Iterator<Item = OneofDescriptor> + '_ {
+        self.inner()
+            .oneofs
+            .iter()
+            .enumerate()
+            .filter(|(_, oneof)| !oneof.synthetic)
+            .map(move |(index, _)| OneofDescriptor {
+                message: self.clone(),
+                index,
+            })
+    }
+
+    /// Returns the key field of a map entry message.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the message is not a map entry.
+    pub fn map_entry_key_field(&self) -> FieldDescriptor {
+        assert!(
+            self.is_map_entry(),
+            "{} is not a map entry",
+            self.full_name()
+        );
+        self.get_field(1).expect("map entry key field")
+    }
+
+    /// Returns the value field of a map entry message.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the message is not a map entry.
+    pub fn map_entry_value_field(&self) -> FieldDescriptor {
+        assert!(
+            self.is_map_entry(),
+            "{} is not a map entry",
+            self.full_name()
+        );
+        self.get_field(2).expect("map entry value field")
+    }
+
+    /// Returns the message type with the given index in the same pool.
+    pub(super) fn with_index(&self, index: usize) -> MessageDescriptor {
+        MessageDescriptor {
+            pool: self.pool.clone(),
+            index,
+        }
+    }
+
+    /// Returns the key and value fields of the map entry message with the given index.
+    pub(super) fn map_entry_fields(&self, index: usize) -> (&FieldInner, &FieldInner) {
+        let entry = &self.pool.inner.messages[index];
+        (
+            &entry.fields[entry.field_numbers[&1]],
+            &entry.fields[entry.field_numbers[&2]],
+        )
+    }
+
+    /// Returns the value of a singular field of this pool when it is not set.
+    pub(super) fn default_value(&self, field: &FieldInner) -> Value {
+        match (&field.default, field.kind) {
+            (Some(default), _) => default.clone(),
+            (None, FieldKind::Message(index) | FieldKind::Group(index)) => {
+                Value::Message(DynamicMessage::new(self.with_index(index)))
+            }
+            (None, _) => unreachable!("field without a default value"),
+        }
+    }
+
+    pub(super) fn field(&self, index: usize) -> FieldDescriptor {
+        FieldDescriptor {
+            message: self.clone(),
+            index,
+        }
+    }
+}
+
+impl fmt::Debug for MessageDescriptor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("MessageDescriptor")
+            .field(&self.full_name())
+            .finish()
+    }
+}
+
+impl Hash for MessageDescriptor {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.full_name().hash(state);
+    }
+}
+
+/// A field of a message type.
+#[derive(Clone, PartialEq, Eq)]
+pub struct FieldDescriptor {
+    message: MessageDescriptor,
+    index: usize,
+}
+
+/// The type of a field.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Kind {
+    Double,
+    Float,
+    Int32,
+    Int64,
+    Uint32,
+    Uint64,
+    Sint32,
+    Sint64,
+    Fixed32,
+    Fixed64,
+    Sfixed32,
+    Sfixed64,
+    Bool,
+    String,
+    Bytes,
+    /// A message or group field.
+    Message(MessageDescriptor),
+    Enum(EnumDescriptor),
+}
+
+impl FieldDescriptor {
+    pub(super) fn inner(&self) -> &FieldInner {
+        &self.message.inner().fields[self.index]
+    }
+
+    /// Returns the message type which contains the field.
+    pub fn parent_message(&self) -> &MessageDescriptor {
+        &self.message
+    }
+
+    /// Returns the name of the field.
+    pub fn name(&self) -> &str {
+        &self.inner().name
+    }
+
+    /// Returns the JSON name of the field.
+    pub fn json_name(&self) -> &str {
+        &self.inner().json_name
+    }
+
+    /// Returns the number of the field.
+    pub fn number(&self) -> u32 {
+        self.inner().number
+    }
+
+    /// Returns the type of the field. Map fields have the type of their entry message.
+    pub fn kind(&self) -> Kind {
+        let pool = &self.message.pool;
+        match self.inner().kind {
+            FieldKind::Double => Kind::Double,
+            FieldKind::Float => Kind::Float,
+            FieldKind::Int32 => Kind::Int32,
+            FieldKind::Int64 => Kind::Int64,
+            FieldKind::Uint32 => Kind::Uint32,
+            FieldKind::Uint64 => Kind::Uint64,
+            FieldKind::Sint32 => Kind::Sint32,
+            FieldKind::Sint64 => Kind::Sint64,
+            FieldKind::Fixed32 => Kind::Fixed32,
+            FieldKind::Fixed64 => Kind::Fixed64,
+            FieldKind::Sfixed32 => Kind::Sfixed32,
+            FieldKind::Sfixed64 => Kind::Sfixed64,
+            FieldKind::Bool => Kind::Bool,
+            FieldKind::String => Kind::String,
+            FieldKind::Bytes => Kind::Bytes,
+            FieldKind::Message(index) | FieldKind::Group(index) => {
+                Kind::Message(MessageDescriptor {
+                    pool: pool.clone(),
+                    index,
+                })
+            }
+            FieldKind::Enum(index) => Kind::Enum(EnumDescriptor {
+                pool: pool.clone(),
+                index,
+            }),
+        }
+    }
+
+    /// Returns `true` if the field is a repeated field, but not a map field.
+    pub fn is_list(&self) -> bool {
+        self.inner().label == Label::Repeated && !self.is_map()
+    }
+
+    /// Returns `true` if the field is a map field.
+    pub fn is_map(&self) -> bool {
+        self.inner().label == Label::Repeated
+            && matches!(self.inner().kind, FieldKind::Message(index)
+                if self.message.pool.inner.messages[index].map_entry)
+    }
+
+    /// Returns `true` if the field is a `proto2` required field.
+    pub fn is_required(&self) -> bool {
+        self.inner().label == Label::Required
+    }
+
+    /// Returns `true` if the field is a repeated field which is encoded in packed format.
+    pub fn is_packed(&self) -> bool {
+        self.inner().packed
+    }
+
+    /// Returns `true` if the field is a group field.
+    pub fn is_group(&self) -> bool {
+        matches!(self.inner().kind, FieldKind::Group(_))
+    }
+
+    /// Returns `true` if the field tracks whether it is set, rather than being unset when it has
+    /// its default value.
+    ///
+    /// This is the case for message fields, fields of a oneof, `proto3` optional fields, and
+    /// all singular `proto2` fields.
+    pub fn supports_presence(&self) -> bool {
+        self.inner().supports_presence
+    }
+
+    /// Returns the oneof containing the field, unless the field is a `proto3` optional field.
+    pub fn containing_oneof(&self) -> Option<OneofDescriptor> {
+        let index = self.inner().oneof?;
+        if self.message.inner().oneofs[index].synthetic {
+            return None;
+        }
+        Some(OneofDescriptor {
+            message: self.message.clone(),
+            index,
+        })
+    }
+
+    /// Returns the value of the field in a message where it is not set.
+    ///
+    /// This is the field's default value if one is declared, and otherwise the default value of
+    /// its type: an empty list or map, or an empty message.
+    pub fn default_value(&self) -> Value {
+        if self.is_list() {
+            return Value::List(Vec::new());
+        }
+        if self.is_map() {
+            return Value::Map(BTreeMap::new());
+        }
+        self.message.default_value(self.inner())
+    }
+}
+
+impl fmt::Debug for FieldDescriptor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FieldDescriptor")
+            .field("message", &self.message.full_name())
+            .field("name", &self.name())
+            .field("number", &self.number())
+            .finish()
+    }
+}
+
+/// A oneof of a message type.
+#[derive(Clone, PartialEq, Eq)]
+pub struct OneofDescriptor {
+    message: MessageDescriptor,
+    index: usize,
+}
+
+impl OneofDescriptor {
+    /// Returns the message type which contains the oneof.
+    pub fn parent_message(&self) -> &MessageDescriptor {
+        &self.message
+    }
+
+    /// Returns the name of the oneof.
+    pub fn name(&self) -> &str {
+        &self.message.inner().oneofs[self.index].name
+    }
+
+    /// Returns an iterator over the fields of the oneof.
+    pub fn fields(&self) -> impl ExactSizeIterator<Item = FieldDescriptor> + '_ {
+        self.message.inner().oneofs[self.index]
+            .fields
+            .iter()
+            .map(move |&index| self.message.field(index))
+    }
+}
+
+impl fmt::Debug for OneofDescriptor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OneofDescriptor")
+            .field("message", &self.message.full_name())
+            .field("name", &self.name())
+            .finish()
+    }
+}
+
+/// An enum type.
+#[derive(Clone, PartialEq, Eq)]
+pub struct EnumDescriptor {
+    pool: DescriptorPool,
+    index: usize,
+}
+
+impl EnumDescriptor {
+    fn inner(&self) -> &EnumInner {
+        &self.pool.inner.enums[self.index]
+    }
+
+    /// Returns the name of the enum type, without its package or parent message.
+    pub fn name(&self) -> &str {
+        let full_name = self.full_name();
+        full_name.rsplit('.').next().unwrap_or(full_name)
+    }
+
+    /// Returns the fully-qualified name of the enum type.
+    pub fn full_name(&self) -> &str {
+        &self.inner().full_name
+    }
+
+    /// Returns an iterator over the names and numbers of the values of the enum, in declaration
+    /// order.
+    pub fn values(&self) -> impl ExactSizeIterator<Item = (&str, i32)> + '_ {
+        self.inner()
+            .values
+            .iter()
+            .map(|(name, number)| (name.as_str(), *number))
+    }
+
+    /// Returns the name of the first value with the given number.
+    pub fn get_value(&self, number: i32) -> Option<&str> {
+        self.values()
+            .find(|&(_, value)| value == number)
+            .map(|(name, _)| name)
+    }
+
+    /// Returns the number of the value with the given name.
+    pub fn get_value_by_name(&self, name: &str) -> Option<i32> {
+        self.values()
+            .find(|&(value, _)| value == name)
+            .map(|(_, number)| number)
+    }
+
+    /// Returns the default value of the enum, which is its first value.
+    pub fn default_value(&self) -> i32 {
+        self.values().next().map_or(0, |(_, number)| number)
+    }
+}
+
+impl fmt::Debug for EnumDescriptor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("EnumDescriptor")
+            .field(&self.full_name())
+            .finish()
+    }
+}
+
+impl Hash for EnumDescriptor {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.full_name().hash(state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use prost::alloc::vec;
+
+    use crate::dynamic::tests::{field, map_entry, message, pool, typed_field};
+    use crate::{EnumValueDescriptorProto, FileDescriptorProto};
+
+    fn enum_type(name: &str, values: &[(&str, i32)]) -> EnumDescriptorProto {
+        EnumDescriptorProto {
+            name: Some(name.to_string()),
+            value: values
+                .iter()
+                .map(|&(name, number)| EnumValueDescriptorProto {
+                    name: Some(name.to_string()),
+                    number: Some(number),
+                    ..Default::default()
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    fn file(package: &str, message_type: Vec<DescriptorProto>) -> FileDescriptorSet {
+        FileDescriptorSet {
+            file: vec![FileDescriptorProto {
+                package: Some(package.to_string()),
+                message_type,
+                ..Default::default()
+            }],
+        }
+    }
+
+    #[test]
+    fn nested_types() {
+        let outer = DescriptorProto {
+            nested_type: vec![DescriptorProto {
+                enum_type: vec![enum_type("Kind", &[("FIRST", 5), ("SECOND", 6)])],
+                ..message(
+                    "Inner",
+                    vec![
+                        // Relative names are resolved from the innermost scope.
+                        typed_field("kind", 1, Label::Optional, Type::Enum, "Kind"),
+                        typed_field("outer", 2, Label::Optional, Type::Message, "Outer"),
+                    ],
+                )
+            }],
+            ..message(
+                "Outer",
+                vec![
+                    typed_field("inner", 1, Label::Optional, Type::Message, "Inner"),
+                    typed_field("top", 2, Label::Optional, Type::Message, "a.Inner"),
+                    // The type may be unset if the type name has not been resolved.
+                    FieldDescriptorProto {
+                        r#type: None,
+                        ..typed_field("kind", 3, Label::Optional, Type::Enum, "Inner.Kind")
+                    },
+                ],
+            )
+        };
+        let pool = DescriptorPool::new(file("a", vec![outer, message("Inner", vec![])])).unwrap();
+        assert_eq!(pool.messages().len(), 3);
+        assert_eq!(pool.enums().len(), 1);
+
+        let outer = pool.get_message_by_name("a.Outer").unwrap();
+        let inner = pool.get_message_by_name(".a.Outer.Inner").unwrap();
+        let top = pool.get_message_by_name("a.Inner").unwrap();
+        let kind = pool.get_enum_by_name("a.Outer.Inner.Kind").unwrap();
+        assert_eq!(inner.name(), "Inner");
+        assert_eq!(inner.full_name(), "a.Outer.Inner");
+        assert_eq!(kind.name(), "Kind");
+        assert!(pool.get_enum_by_name("a.Outer").is_none());
+        assert!(pool.get_message_by_name("a.Outer.Inner.Kind").is_none());
+        assert!(pool.get_message_by_name("Outer").is_none());
+
+        let field_kind =
+            |message: &MessageDescriptor, name| message.get_field_by_name(name).unwrap().kind();
+        assert_eq!(field_kind(&outer, "inner"), Kind::Message(inner.clone()));
+        assert_eq!(field_kind(&outer, "top"), Kind::Message(top));
+        assert_eq!(field_kind(&outer, "kind"), Kind::Enum(kind.clone()));
+        assert_eq!(field_kind(&inner, "kind"), Kind::Enum(kind.clone()));
+        assert_eq!(field_kind(&inner, "outer"), Kind::Message(outer.clone()));
+        assert_eq!(inner.get_field(2).unwrap().parent_message(), &inner);
+
+        assert_eq!(
+            kind.values().collect::<Vec<_>>(),
+            [("FIRST", 5), ("SECOND", 6)]
+        );
+        assert_eq!(kind.get_value(6), Some("SECOND"));
+        assert_eq!(kind.get_value(0), None);
+        assert_eq!(kind.get_value_by_name("FIRST"), Some(5));
+        assert_eq!(kind.default_value(), 5);
+        // An enum field without a default value defaults to the first value of the enum.
+        assert_eq!(
+            inner.get_field(1).unwrap().default_value(),
+            Value::EnumNumber(5)
+        );
+        assert_eq!(
+            outer.get_field(1).unwrap().default_value(),
+            Value::Message(DynamicMessage::new(inner))
+        );
+    }
+
+    #[test]
+    fn map_entries() {
+        let pool = pool();
+        let maps = pool.get_message_by_name("test.Maps").unwrap();
+        let items = maps.get_field_by_name("items").unwrap();
+        assert!(items.is_map());
+        assert!(!items.is_list());
+        let entry = match items.kind() {
+            Kind::Message(entry) => entry,
+            kind => panic!("unexpected kind: {:?}", kind),
+        };
+        assert!(entry.is_map_entry());
+        assert_eq!(entry.full_name(), "test.Maps.ItemsEntry");
+        assert_eq!(entry.map_entry_key_field().kind(), Kind::Int32);
+        assert_eq!(
+            entry.map_entry_value_field().kind(),
+            Kind::Message(pool.get_message_by_name("test.Scalars").unwrap())
+        );
+        assert_eq!(items.default_value(), Value::Map(BTreeMap::new()));
+
+        let messages = pool
+            .get_message_by_name("test.Repeated")
+            .unwrap()
+            .get_field_by_name("messages")
+            .unwrap();
+        assert!(messages.is_list());
+        assert!(!messages.is_map());
+        assert_eq!(messages.default_value(), Value::List(Vec::new()));
+
+        let invalid = |key| {
+            DescriptorPool::new(file(
+                "a",
+                vec![DescriptorProto {
+                    nested_type: vec![map_entry(
+                        "Entry",
+                        key,
+                        field("", 0, Label::Optional, Type::Int32),
+                    )],
+                    ..message(
+                        "Map",
+                        vec![typed_field(
+                            "map",
+                            1,
+                            Label::Repeated,
+                            Type::Message,
+                            "Entry",
+                        )],
+                    )
+                }],
+            ))
+        };
+        assert!(invalid(Type::Sint64).is_ok());
+        assert_eq!(
+            invalid(Type::Bytes),
+            Err(DescriptorError::InvalidMapEntry("a.Map.Entry".to_string()))
+        );
+        assert_eq!(
+            invalid(Type::Double),
+            Err(DescriptorError::InvalidMapEntry("a.Map.Entry".to_string()))
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "test.Scalars is not a map entry")]
+    fn map_entry_key_field_of_message() {
+        pool()
+            .get_message_by_name("test.Scalars")
+            .unwrap()
+            .map_entry_key_field();
+    }
+
+    #[test]
+    fn oneofs_and_presence() {
+        let pool = pool();
+        let scalars = pool.get_message_by_name("test.Scalars").unwrap();
+        // The synthetic oneof of a proto3 optional field is not a oneof of the message.
+        assert_eq!(scalars.oneofs().count(), 0);
+        let optional = scalars.get_field_by_name("optional").unwrap();
+        assert_eq!(optional.containing_oneof(), None);
+        assert!(optional.supports_presence());
+        assert!(!scalars
+            .get_field_by_name("int")
+            .unwrap()
+            .supports_presence());
+
+        let oneofs = pool.get_message_by_name("test.Oneofs").unwrap();
+        let choice = oneofs.oneofs().next().unwrap();
+        assert_eq!(choice.parent_message(), &oneofs);
+        let text = oneofs.get_field_by_name("text").unwrap();
+        assert_eq!(text.containing_oneof(), Some(choice));
+        assert!(text.supports_presence());
+        assert_eq!(
+            oneofs
+                .get_field_by_name("first")
+                .unwrap()
+                .containing_oneof(),
+            None
+        );
+
+        let proto2 = pool.get_message_by_name("test2.Proto2").unwrap();
+        assert!(proto2
+            .fields()
+            .all(|field| field.is_list() || field.supports_presence()));
+        assert!(!proto2
+            .get_field_by_name("ints")
+            .unwrap()
+            .supports_presence());
+    }
+
+    #[test]
+    fn default_values() {
+        let defaults = [
+            (Type::Double, "inf", Value::F64(f64::INFINITY)),
+            (Type::Double, "-inf", Value::F64(f64::NEG_INFINITY)),
+            (Type::Float, "1.5", Value::F32(1.5)),
+            (Type::Int64, "-3", Value::I64(-3)),
+            (Type::Uint32, "4", Value::U32(4)),
+            (Type::Bool, "true", Value::Bool(true)),
+            (Type::String, "a\\nb", Value::from("a\\nb")),
+            (
+                Type::Bytes,
+                "\\a\\0\\101\\x42\\\\\\'",
+                Value::from(b"\x07\x00AB\\'".to_vec()),
+            ),
+        ];
+        let fields = defaults
+            .iter()
+            .enumerate()
+            .map(|(index, (type_, default, _))| FieldDescriptorProto {
+                default_value: Some(default.to_string()),
+                ..field("f", index as i32 + 1, Label::Optional, *type_)
+            })
+            .collect::<Vec<_>>();
+        let pool = DescriptorPool::new(file("a", vec![message("Defaults", fields)])).unwrap();
+        let defaults_message = pool.get_message_by_name("a.Defaults").unwrap();
+        for (field, (_, _, expected)) in defaults_message.fields().zip(&defaults) {
+            assert_eq!(field.default_value(), *expected, "{:?}", field);
+        }
+
+        let nan = FieldDescriptorProto {
+            default_value: Some("nan".to_string()),
+            ..field("nan", 1, Label::Optional, Type::Double)
+        };
+        let pool = DescriptorPool::new(file("a", vec![message("Nan", vec![nan])])).unwrap();
+        let nan = pool
+            .get_message_by_name("a.Nan")
+            .unwrap()
+            .get_field(1)
+            .unwrap();
+        assert!(nan.default_value().as_f64().unwrap().is_nan());
+
+        for (type_, default) in [
+            (Type::Uint32, "-1"),
+            (Type::Int32, "1.5"),
+            (Type::Bool, "yes"),
+            (Type::Bytes, "\\400"),
+            (Type::Bytes, "\\x"),
+            (Type::Bytes, "\\q"),
+        ] {
+            let invalid = FieldDescriptorProto {
+                default_value: Some(default.to_string()),
+                ..field("f", 1, Label::Optional, type_)
+            };
+            assert_eq!(
+                DescriptorPool::new(file("a", vec![message("A", vec![invalid])])),
+                Err(DescriptorError::InvalidDefault("a.A.f".to_string())),
+                "{:?} {:?}",
+                type_,
+                default
+            );
+        }
+    }
+
+    #[test]
+    fn json_names() {
+        let fields = vec![
+            field("snake_case_name", 1, Label::Optional, Type::Int32),
+            FieldDescriptorProto {
+                json_name: Some("custom".to_string()),
+                ..field("renamed", 2, Label::Optional, Type::Int32)
+            },
+        ];
+        let pool = DescriptorPool::new(file("a", vec![message("A", fields)])).unwrap();
+        let descriptor = pool.get_message_by_name("a.A").unwrap();
+        assert_eq!(
+            descriptor.get_field(1).unwrap().json_name(),
+            "snakeCaseName"
+        );
+        assert_eq!(descriptor.get_field(2).unwrap().json_name(), "custom");
+    }
+
+    #[test]
+    fn field_numbers() {
+        for number in [0, -1, 0x2000_0000] {
+            assert_eq!(
+                DescriptorPool::new(file(
+                    "a",
+                    vec![message(
+                        "A",
+                        vec![field("f", number, Label::Optional, Type::Int32)]
+                    )]
+                )),
+                Err(DescriptorError::InvalidFieldNumber("a.A.f".to_string()))
+            );
+        }
+        let pool = DescriptorPool::new(file(
+            "a",
+            vec![message(
+                "A",
+                vec![field("f", 0x1FFF_FFFF, Label::Optional, Type::Int32)],
+            )],
+        ))
+        .unwrap();
+        let descriptor = pool.get_message_by_name("a.A").unwrap();
+        assert_eq!(descriptor.get_field(0x1FFF_FFFF).unwrap().name(), "f");
+        assert!(descriptor.get_field(1).is_none());
+    }
+
+    #[test]
+    fn pool_equality() {
+        let pool = pool();
+        assert_eq!(pool, pool.clone());
+        assert_ne!(pool, super::super::tests::pool());
+        assert_eq!(
+            pool.get_message_by_name("test.Scalars").unwrap().pool(),
+            &pool
+        );
+    }
+}
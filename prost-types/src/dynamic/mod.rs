@@ -0,0 +1,1461 @@
+//! Messages whose types are resolved at runtime.
+//!
+//! A [`DescriptorPool`] resolves the message and enum types of a [`FileDescriptorSet`], such as
+//! one produced by `protoc --descriptor_set_out --include_imports` or fetched from a schema
+//! registry. A [`DynamicMessage`] is a message of one of these types, with field values stored
+//! as [`Value`]s. Dynamic messages are encoded and decoded with the same `prost::encoding`
+//! functions as generated messages, so the two are wire-compatible: a generated message and a
+//! dynamic message with the same field values encode to the same bytes.
+//!
+//! ```rust
+//! use prost::Message;
+//! use prost_types::dynamic::{DescriptorPool, DynamicMessage, Value};
+//! use prost_types::FileDescriptorSet;
+//!
+//! # fn example(encoded_descriptors: &[u8], payload: &[u8]) -> Result<(), Box<dyn std::error::Error>> {
+//! let pool = DescriptorPool::new(FileDescriptorSet::decode(encoded_descriptors)?)?;
+//! let descriptor = pool.get_message_by_name("example.Person").unwrap();
+//!
+//! let mut person = DynamicMessage::decode(descriptor, payload)?;
+//! println!("name: {:?}", person.get_field_by_name("name"));
+//! person.set_field_by_name("id", Value::I32(42))?;
+//! let encoded = person.encode_to_vec();
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Unknown fields are preserved, and encoded after the known fields. Extensions are treated as
+//! unknown fields.
+//!
+//! [`FileDescriptorSet`]: crate::FileDescriptorSet
+
+mod descriptor;
+mod value;
+
+pub use descriptor::{
+    DescriptorError, DescriptorPool, EnumDescriptor, FieldDescriptor, Kind, MessageDescriptor,
+    OneofDescriptor,
+};
+pub use value::{MapKey, Value};
+
+use core::fmt;
+
+use prost::alloc::borrow::Cow;
+use prost::alloc::collections::BTreeMap;
+use prost::alloc::vec::Vec;
+use prost::bytes::{Buf, BufMut};
+use prost::encoding::{
+    self, check_wire_type, decode_key, encode_key, encode_varint, encoded_len_varint, key_len,
+    merge_loop, skip_field, DecodeContext, WireType,
+};
+use prost::{DecodeError, Message, UnknownFieldSet};
+
+use descriptor::{FieldInner, FieldKind};
+
+/// A message whose type is described by a [`MessageDescriptor`].
+///
+/// Fields are identified by their [`FieldDescriptor`], or by their name or number. A field which
+/// is not set has its default value, as returned by [`FieldDescriptor::default_value`].
+#[derive(Clone)]
+pub struct DynamicMessage {
+    descriptor: MessageDescriptor,
+    /// The values of the fields which are set, by field number.
+    fields: BTreeMap<u32, Value>,
+    unknown_fields: UnknownFieldSet,
+}
+
+/// An error setting the value of a field of a [`DynamicMessage`].
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Debug, PartialEq)]
+#[non_exhaustive]
+pub enum SetFieldError {
+    /// The message has no field with the given name or number.
+    NotFound,
+    /// The value does not have the type of the field.
+    InvalidType {
+        /// The field.
+        field: FieldDescriptor,
+        /// The value which could not be stored in the field.
+        value: Value,
+    },
+}
+
+impl fmt::Display for SetFieldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SetFieldError::NotFound => write!(f, "field not found"),
+            SetFieldError::InvalidType { field, value } => write!(
+                f,
+                "invalid value for field {}.{}: {:?}",
+                field.parent_message().full_name(),
+                field.name(),
+                value
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for SetFieldError {}
+
+impl DynamicMessage {
+    /// Creates a message of the given type, with no fields set.
+    pub fn new(descriptor: MessageDescriptor) -> DynamicMessage {
+        DynamicMessage {
+            descriptor,
+            fields: BTreeMap::new(),
+            unknown_fields: UnknownFieldSet::new(),
+        }
+    }
+
+    /// Decodes a message of the given type from a buffer.
+    pub fn decode<B>(descriptor: MessageDescriptor, buf: B) -> Result<DynamicMessage, DecodeError>
+    where
+        B: Buf,
+    {
+        let mut message = DynamicMessage::new(descriptor);
+        message.merge(buf)?;
+        Ok(message)
+    }
+
+    /// Returns the type of the message.
+    pub fn descriptor(&self) -> &MessageDescriptor {
+        &self.descriptor
+    }
+
+    /// Returns `true` if the field is set.
+    ///
+    /// Fields without presence, such as repeated fields and `proto3` fields which are not
+    /// `optional`, are only set if they don't have their default value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the field is not a field of the message's type.
+    pub fn has_field(&self, field: &FieldDescriptor) -> bool {
+        self.check_field(field);
+        match self.fields.get(&field.number()) {
+            Some(value) => field.supports_presence() || !is_default(field.inner(), value),
+            None => false,
+        }
+    }
+
+    /// Returns the value of the field, or its default value if it is not set.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the field is not a field of the message's type.
+    pub fn get_field(&self, field: &FieldDescriptor) -> Cow<'_, Value> {
+        self.check_field(field);
+        match self.fields.get(&field.number()) {
+            Some(value) => Cow::Borrowed(value),
+            None => Cow::Owned(field.default_value()),
+        }
+    }
+
+    /// Returns a mutable reference to the value of the field, setting it to its default value if
+    /// it is not set.
+    ///
+    /// If the field is part of a oneof, the other fields of the oneof are cleared. The value must
+    /// not be replaced with a value which is not valid for the field, or encoding the message
+    /// will panic.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the field is not a field of the message's type.
+    pub fn get_field_mut(&mut self, field: &FieldDescriptor) -> &mut Value {
+        self.check_field(field);
+        self.clear_oneof(field);
+        self.fields
+            .entry(field.number())
+            .or_insert_with(|| field.default_value())
+    }
+
+    /// Sets the value of the field.
+    ///
+    /// If the field is part of a oneof, the other fields of the oneof are cleared.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the field is not a field of the message's type, or if the value is not valid
+    /// for the field.
+    pub fn set_field(&mut self, field: &FieldDescriptor, value: Value) {
+        if let Err(error) = self.try_set_field(field, value) {
+            panic!("{}", error);
+        }
+    }
+
+    /// Sets the value of the field, failing if the value is not valid for the field.
+    ///
+    /// If the field is part of a oneof, the other fields of the oneof are cleared.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the field is not a field of the message's type.
+    pub fn try_set_field(
+        &mut self,
+        field: &FieldDescriptor,
+        value: Value,
+    ) -> Result<(), SetFieldError> {
+        self.check_field(field);
+        if !value.is_valid_for_field(field) {
+            return Err(SetFieldError::InvalidType {
+                field: field.clone(),
+                value,
+            });
+        }
+        self.clear_oneof(field);
+        self.fields.insert(field.number(), value);
+        Ok(())
+    }
+
+    /// Clears the field, resetting it to its default value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the field is not a field of the message's type.
+    pub fn clear_field(&mut self, field: &FieldDescriptor) {
+        self.check_field(field);
+        self.fields.remove(&field.number());
+    }
+
+    /// Returns the value of the field with the given name, or `None` if the message has no such
+    /// field.
+    pub fn get_field_by_name(&self, name: &str) -> Option<Cow<'_, Value>> {
+        let field = self.descriptor.get_field_by_name(name)?;
+        Some(self.get_field(&field))
+    }
+
+    /// Returns the value of the field with the given number, or `None` if the message has no
+    /// such field.
+    pub fn get_field_by_number(&self, number: u32) -> Option<Cow<'_, Value>> {
+        let field = self.descriptor.get_field(number)?;
+        Some(self.get_field(&field))
+    }
+
+    /// Sets the value of the field with the given name.
+    pub fn set_field_by_name(&mut self, name: &str, value: Value) -> Result<(), SetFieldError> {
+        let field = self
+            .descriptor
+            .get_field_by_name(name)
+            .ok_or(SetFieldError::NotFound)?;
+        self.try_set_field(&field, value)
+    }
+
+    /// Sets the value of the field with the given number.
+    pub fn set_field_by_number(&mut self, number: u32, value: Value) -> Result<(), SetFieldError> {
+        let field = self
+            .descriptor
+            .get_field(number)
+            .ok_or(SetFieldError::NotFound)?;
+        self.try_set_field(&field, value)
+    }
+
+    /// Returns an iterator over the fields which are set, in order of their numbers.
+    pub fn fields(&self) -> impl Iterator<Item = (FieldDescriptor, &Value)> + '_ {
+        self.fields.iter().filter_map(move |(&number, value)| {
+            let field = self.descriptor.get_field(number)?;
+            if field.supports_presence() || !is_default(field.inner(), value) {
+                Some((field, value))
+            } else {
+                None
+            }
+        })
+    }
+
+    /// Returns the fields of the message which are not fields of its type.
+    pub fn unknown_fields(&self) -> &UnknownFieldSet {
+        &self.unknown_fields
+    }
+
+    fn check_field(&self, field: &FieldDescriptor) {
+        assert!(
+            field.parent_message() == &self.descriptor,
+            "{} is not a field of {}",
+            field.name(),
+            self.descriptor.full_name()
+        );
+    }
+
+    /// Clears the fields of the oneof containing `field`, other than `field` itself.
+    fn clear_oneof(&mut self, field: &FieldDescriptor) {
+        if let Some(oneof) = field.containing_oneof() {
+            for other in oneof.fields() {
+                if other.number() != field.number() {
+                    self.fields.remove(&other.number());
+                }
+            }
+        }
+    }
+}
+
+impl Message for DynamicMessage {
+    fn encode_raw<B>(&self, buf: &mut B)
+    where
+        B: BufMut,
+    {
+        let message = self.descriptor.inner();
+        for &index in &message.encode_order {
+            let field = &message.fields[index];
+            if let Some(value) = self.fields.get(&field.number) {
+                encode_field(&self.descriptor, field, value, false, buf);
+            }
+        }
+        self.unknown_fields.encode_raw(buf);
+    }
+
+    fn encode_raw_deterministic<B>(&self, buf: &mut B)
+    where
+        B: BufMut,
+    {
+        // The set fields are held in field number order, and map entries in key order.
+        let message = self.descriptor.inner();
+        for (number, value) in &self.fields {
+            let field = &message.fields[message.field_numbers[number]];
+            encode_field(&self.descriptor, field, value, true, buf);
+        }
+        self.unknown_fields.encode_raw(buf);
+    }
+
+    fn merge_field<B>(
+        &mut self,
+        tag: u32,
+        wire_type: WireType,
+        buf: &mut B,
+        ctx: DecodeContext,
+    ) -> Result<(), DecodeError>
+    where
+        B: Buf,
+    {
+        let DynamicMessage {
+            ref descriptor,
+            ref mut fields,
+            ref mut unknown_fields,
+        } = *self;
+        let message = descriptor.inner();
+        let index = match message.field_numbers.get(&tag) {
+            Some(&index) => index,
+            None => return unknown_fields.merge_field(tag, wire_type, buf, ctx),
+        };
+        let field = &message.fields[index];
+
+        if let Some(oneof) = field.oneof {
+            for &other in &message.oneofs[oneof].fields {
+                if other != index {
+                    fields.remove(&message.fields[other].number);
+                }
+            }
+        }
+
+        let value = fields
+            .entry(tag)
+            .or_insert_with(|| descriptor.field(index).default_value());
+        match value {
+            Value::List(values) => merge_list(descriptor, field, values, wire_type, buf, ctx),
+            Value::Map(entries) => merge_map(descriptor, field, entries, wire_type, buf, ctx),
+            value => merge_value(field.kind, tag, wire_type, value, buf, ctx),
+        }
+    }
+
+    fn encoded_len(&self) -> usize {
+        let message = self.descriptor.inner();
+        self.fields
+            .iter()
+            .map(|(number, value)| {
+                let field = &message.fields[message.field_numbers[number]];
+                field_encoded_len(&self.descriptor, field, value)
+            })
+            .sum::<usize>()
+            + self.unknown_fields.encoded_len()
+    }
+
+    fn clear(&mut self) {
+        self.fields.clear();
+        self.unknown_fields.clear();
+    }
+}
+
+/// Messages are equal if they have the same type, and the same fields are set to equal values.
+impl PartialEq for DynamicMessage {
+    fn eq(&self, other: &DynamicMessage) -> bool {
+        self.descriptor == other.descriptor
+            && self.fields().eq(other.fields())
+            && self.unknown_fields == other.unknown_fields
+    }
+}
+
+impl fmt::Debug for DynamicMessage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut builder = f.debug_struct(self.descriptor.name());
+        for (field, value) in self.fields() {
+            builder.field(field.name(), value);
+        }
+        if !self.unknown_fields.is_empty() {
+            builder.field("unknown_fields", &self.unknown_fields);
+        }
+        builder.finish()
+    }
+}
+
+/// Returns `true` if the value is the default value of a field, which is not encoded for fields
+/// without presence and for the keys and values of map entries.
+fn is_default(field: &FieldInner, value: &Value) -> bool {
+    match value {
+        Value::List(values) => values.is_empty(),
+        Value::Map(entries) => entries.is_empty(),
+        Value::Message(message) => message.encoded_len() == 0,
+        value => field.default.as_ref() == Some(value),
+    }
+}
+
+#[cold]
+fn invalid_value(kind: FieldKind, value: &Value) -> ! {
+    panic!("invalid value for field of type {:?}: {:?}", kind, value)
+}
+
+/// Generates the functions encoding and decoding single values, dispatching to the
+/// `prost::encoding` module of each field type.
+macro_rules! value_encoding {
+    (
+        packed: { $($packed_kind:pat => $packed_module:ident, $packed_variant:ident, $wire_type:ident;)* }
+        length_delimited: { $($kind:pat => $module:ident, $variant:ident;)* }
+        map_keys: { $($key_kind:pat => $key_module:ident, $key_variant:ident;)* }
+    ) => {
+        fn encode_value<B>(kind: FieldKind, tag: u32, value: &Value, deterministic: bool, buf: &mut B)
+        where
+            B: BufMut,
+        {
+            match (kind, value) {
+                $(($packed_kind, Value::$packed_variant(value)) => {
+                    encoding::$packed_module::encode(tag, value, buf)
+                })*
+                $(($kind, Value::$variant(value)) => encoding::$module::encode(tag, value, buf),)*
+                (FieldKind::Message(_), Value::Message(message)) if deterministic => {
+                    encoding::message::encode_deterministic(tag, message, buf)
+                }
+                (FieldKind::Message(_), Value::Message(message)) => {
+                    encoding::message::encode(tag, message, buf)
+                }
+                (FieldKind::Group(_), Value::Message(message)) if deterministic => {
+                    encoding::group::encode_deterministic(tag, message, buf)
+                }
+                (FieldKind::Group(_), Value::Message(message)) => {
+                    encoding::group::encode(tag, message, buf)
+                }
+                (kind, value) => invalid_value(kind, value),
+            }
+        }
+
+        fn value_encoded_len(kind: FieldKind, tag: u32, value: &Value) -> usize {
+            match (kind, value) {
+                $(($packed_kind, Value::$packed_variant(value)) => {
+                    encoding::$packed_module::encoded_len(tag, value)
+                })*
+                $(($kind, Value::$variant(value)) => encoding::$module::encoded_len(tag, value),)*
+                (FieldKind::Message(_), Value::Message(message)) => {
+                    encoding::message::encoded_len(tag, message)
+                }
+                (FieldKind::Group(_), Value::Message(message)) => {
+                    encoding::group::encoded_len(tag, message)
+                }
+                (kind, value) => invalid_value(kind, value),
+            }
+        }
+
+        fn merge_value<B>(
+            kind: FieldKind,
+            tag: u32,
+            wire_type: WireType,
+            value: &mut Value,
+            buf: &mut B,
+            ctx: DecodeContext,
+        ) -> Result<(), DecodeError>
+        where
+            B: Buf,
+        {
+            match (kind, value) {
+                $(($packed_kind, Value::$packed_variant(value)) => {
+                    encoding::$packed_module::merge(wire_type, value, buf, ctx)
+                })*
+                $(($kind, Value::$variant(value)) => {
+                    encoding::$module::merge(wire_type, value, buf, ctx)
+                })*
+                (FieldKind::Message(_), Value::Message(message)) => {
+                    encoding::message::merge(wire_type, message, buf, ctx)
+                }
+                (FieldKind::Group(_), Value::Message(message)) => {
+                    encoding::group::merge(tag, wire_type, message, buf, ctx)
+                }
+                _ => Err(DecodeError::new("field value does not match the field type")),
+            }
+        }
+
+        fn encode_packed<B>(kind: FieldKind, tag: u32, values: &[Value], buf: &mut B)
+        where
+            B: BufMut,
+        {
+            match kind {
+                $($packed_kind => {
+                    let values = values
+                        .iter()
+                        .map(|value| match *value {
+                            Value::$packed_variant(value) => value,
+                            ref value => invalid_value(kind, value),
+                        })
+                        .collect::<Vec<_>>();
+                    encoding::$packed_module::encode_packed(tag, &values, buf)
+                })*
+                _ => unreachable!("{:?} fields can't be packed", kind),
+            }
+        }
+
+        fn packed_encoded_len(kind: FieldKind, tag: u32, values: &[Value]) -> usize {
+            match kind {
+                $($packed_kind => {
+                    let values = values
+                        .iter()
+                        .map(|value| match *value {
+                            Value::$packed_variant(value) => value,
+                            ref value => invalid_value(kind, value),
+                        })
+                        .collect::<Vec<_>>();
+                    encoding::$packed_module::encoded_len_packed(tag, &values)
+                })*
+                _ => unreachable!("{:?} fields can't be packed", kind),
+            }
+        }
+
+        /// Merges an element of a repeated scalar field, which may be packed or unpacked.
+        fn merge_repeated_scalar<B>(
+            kind: FieldKind,
+            wire_type: WireType,
+            values: &mut Vec<Value>,
+            buf: &mut B,
+            ctx: DecodeContext,
+        ) -> Result<(), DecodeError>
+        where
+            B: Buf,
+        {
+            match kind {
+                $($packed_kind => {
+                    let merge = |values: &mut Vec<Value>,
+                                 wire_type: WireType,
+                                 buf: &mut B,
+                                 ctx: DecodeContext|
+                     -> Result<(), DecodeError> {
+                        ctx.charge_element(values)?;
+                        let mut value = Default::default();
+                        encoding::$packed_module::merge(wire_type, &mut value, buf, ctx)?;
+                        values.push(Value::$packed_variant(value));
+                        Ok(())
+                    };
+                    if wire_type == WireType::LengthDelimited {
+                        merge_loop(values, buf, ctx, |values, buf, ctx| {
+                            merge(values, WireType::$wire_type, buf, ctx)
+                        })
+                    } else {
+                        merge(values, wire_type, buf, ctx)
+                    }
+                })*
+                _ => unreachable!("{:?} fields can't be packed", kind),
+            }
+        }
+
+        fn encode_map_key<B>(kind: FieldKind, tag: u32, key: &MapKey, buf: &mut B)
+        where
+            B: BufMut,
+        {
+            match (kind, key) {
+                $(($key_kind, MapKey::$key_variant(key)) => {
+                    encoding::$key_module::encode(tag, key, buf)
+                })*
+                (kind, key) => invalid_value(kind, &Value::from(key.clone())),
+            }
+        }
+
+        fn map_key_encoded_len(kind: FieldKind, tag: u32, key: &MapKey) -> usize {
+            match (kind, key) {
+                $(($key_kind, MapKey::$key_variant(key)) => {
+                    encoding::$key_module::encoded_len(tag, key)
+                })*
+                (kind, key) => invalid_value(kind, &Value::from(key.clone())),
+            }
+        }
+
+        fn merge_map_key<B>(
+            kind: FieldKind,
+            wire_type: WireType,
+            key: &mut MapKey,
+            buf: &mut B,
+            ctx: DecodeContext,
+        ) -> Result<(), DecodeError>
+        where
+            B: Buf,
+        {
+            match (kind, key) {
+                $(($key_kind, MapKey::$key_variant(key)) => {
+                    encoding::$key_module::merge(wire_type, key, buf, ctx)
+                })*
+                _ => Err(DecodeError::new("map key does not match the key type")),
+            }
+        }
+    };
+}
+
+value_encoding! {
+    packed: {
+        FieldKind::Double => double, F64, SixtyFourBit;
+        FieldKind::Float => float, F32, ThirtyTwoBit;
+        FieldKind::Int32 => int32, I32, Varint;
+        FieldKind::Int64 => int64, I64, Varint;
+        FieldKind::Uint32 => uint32, U32, Varint;
+        FieldKind::Uint64 => uint64, U64, Varint;
+        FieldKind::Sint32 => sint32, I32, Varint;
+        FieldKind::Sint64 => sint64, I64, Varint;
+        FieldKind::Fixed32 => fixed32, U32, ThirtyTwoBit;
+        FieldKind::Fixed64 => fixed64, U64, SixtyFourBit;
+        FieldKind::Sfixed32 => sfixed32, I32, ThirtyTwoBit;
+        FieldKind::Sfixed64 => sfixed64, I64, SixtyFourBit;
+        FieldKind::Bool => bool, Bool, Varint;
+        FieldKind::Enum(_) => int32, EnumNumber, Varint;
+    }
+    length_delimited: {
+        FieldKind::String => string, String;
+        FieldKind::Bytes => bytes, Bytes;
+    }
+    map_keys: {
+        FieldKind::Int32 => int32, I32;
+        FieldKind::Int64 => int64, I64;
+        FieldKind::Uint32 => uint32, U32;
+        FieldKind::Uint64 => uint64, U64;
+        FieldKind::Sint32 => sint32, I32;
+        FieldKind::Sint64 => sint64, I64;
+        FieldKind::Fixed32 => fixed32, U32;
+        FieldKind::Fixed64 => fixed64, U64;
+        FieldKind::Sfixed32 => sfixed32, I32;
+        FieldKind::Sfixed64 => sfixed64, I64;
+        FieldKind::Bool => bool, Bool;
+        FieldKind::String => string, String;
+    }
+}
+
+fn encode_field<B>(
+    descriptor: &MessageDescriptor,
+    field: &FieldInner,
+    value: &Value,
+    deterministic: bool,
+    buf: &mut B,
+) where
+    B: BufMut,
+{
+    match value {
+        Value::List(values) if field.packed => {
+            if !values.is_empty() {
+                encode_packed(field.kind, field.number, values, buf);
+            }
+        }
+        Value::List(values) => {
+            for value in values {
+                encode_value(field.kind, field.number, value, deterministic, buf);
+            }
+        }
+        Value::Map(entries) => {
+            let (key_field, value_field) = match field.kind {
+                FieldKind::Message(index) => descriptor.map_entry_fields(index),
+                kind => invalid_value(kind, value),
+            };
+            for (key, value) in entries {
+                let skip_key = key.is_default();
+                let skip_value = is_default(value_field, value);
+                let len = (if skip_key {
+                    0
+                } else {
+                    map_key_encoded_len(key_field.kind, 1, key)
+                }) + (if skip_value {
+                    0
+                } else {
+                    value_encoded_len(value_field.kind, 2, value)
+                });
+
+                encode_key(field.number, WireType::LengthDelimited, buf);
+                encode_varint(len as u64, buf);
+                if !skip_key {
+                    encode_map_key(key_field.kind, 1, key, buf);
+                }
+                if !skip_value {
+                    encode_value(value_field.kind, 2, value, deterministic, buf);
+                }
+            }
+        }
+        value => {
+            if field.supports_presence || !is_default(field, value) {
+                encode_value(field.kind, field.number, value, deterministic, buf);
+            }
+        }
+    }
+}
+
+fn field_encoded_len(descriptor: &MessageDescriptor, field: &FieldInner, value: &Value) -> usize {
+    match value {
+        Value::List(values) if field.packed => {
+            if values.is_empty() {
+                0
+            } else {
+                packed_encoded_len(field.kind, field.number, values)
+            }
+        }
+        Value::List(values) => values
+            .iter()
+            .map(|value| value_encoded_len(field.kind, field.number, value))
+            .sum(),
+        Value::Map(entries) => {
+            let (key_field, value_field) = match field.kind {
+                FieldKind::Message(index) => descriptor.map_entry_fields(index),
+                kind => invalid_value(kind, value),
+            };
+            key_len(field.number) * entries.len()
+                + entries
+                    .iter()
+                    .map(|(key, value)| {
+                        let len = (if key.is_default() {
+                            0
+                        } else {
+                            map_key_encoded_len(key_field.kind, 1, key)
+                        }) + (if is_default(value_field, value) {
+                            0
+                        } else {
+                            value_encoded_len(value_field.kind, 2, value)
+                        });
+                        encoded_len_varint(len as u64) + len
+                    })
+                    .sum::<usize>()
+        }
+        value => {
+            if field.supports_presence || !is_default(field, value) {
+                value_encoded_len(field.kind, field.number, value)
+            } else {
+                0
+            }
+        }
+    }
+}
+
+fn merge_list<B>(
+    descriptor: &MessageDescriptor,
+    field: &FieldInner,
+    values: &mut Vec<Value>,
+    wire_type: WireType,
+    buf: &mut B,
+    ctx: DecodeContext,
+) -> Result<(), DecodeError>
+where
+    B: Buf,
+{
+    match field.kind {
+        FieldKind::Message(index) | FieldKind::Group(index) => {
+            ctx.charge_element(values)?;
+            let mut value = Value::Message(DynamicMessage::new(descriptor.with_index(index)));
+            merge_value(field.kind, field.number, wire_type, &mut value, buf, ctx)?;
+            values.push(value);
+            Ok(())
+        }
+        FieldKind::String | FieldKind::Bytes => {
+            check_wire_type(WireType::LengthDelimited, wire_type)?;
+            ctx.charge_element(values)?;
+            let mut value = descriptor.default_value(field);
+            merge_value(field.kind, field.number, wire_type, &mut value, buf, ctx)?;
+            values.push(value);
+            Ok(())
+        }
+        kind => merge_repeated_scalar(kind, wire_type, values, buf, ctx),
+    }
+}
+
+fn merge_map<B>(
+    descriptor: &MessageDescriptor,
+    field: &FieldInner,
+    entries: &mut BTreeMap<MapKey, Value>,
+    wire_type: WireType,
+    buf: &mut B,
+    ctx: DecodeContext,
+) -> Result<(), DecodeError>
+where
+    B: Buf,
+{
+    let (key_field, value_field) = match field.kind {
+        FieldKind::Message(index) => descriptor.map_entry_fields(index),
+        _ => {
+            return Err(DecodeError::new(
+                "field value does not match the field type",
+            ))
+        }
+    };
+
+    check_wire_type(WireType::LengthDelimited, wire_type)?;
+    ctx.charge_map_entry::<MapKey, Value>(entries.len())?;
+    let mut key = MapKey::default_for(key_field.kind);
+    let mut value = descriptor.default_value(value_field);
+    merge_loop(
+        &mut (&mut key, &mut value),
+        buf,
+        ctx,
+        |&mut (ref mut key, ref mut value), buf, ctx| {
+            let (tag, wire_type) = decode_key(buf)?;
+            match tag {
+                1 => merge_map_key(key_field.kind, wire_type, key, buf, ctx),
+                2 => merge_value(value_field.kind, tag, wire_type, value, buf, ctx),
+                _ => skip_field(wire_type, tag, buf, ctx),
+            }
+        },
+    )?;
+    entries.insert(key, value);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use prost::alloc::collections::BTreeMap;
+    use prost::alloc::string::{String, ToString};
+    use prost::alloc::vec;
+    use prost::bytes::Bytes;
+
+    use crate::field_descriptor_proto::{Label, Type};
+    use crate::{
+        DescriptorProto, EnumDescriptorProto, EnumValueDescriptorProto, FieldDescriptorProto,
+        FieldOptions, FileDescriptorProto, FileDescriptorSet, MessageOptions, OneofDescriptorProto,
+    };
+
+    pub(super) fn field(
+        name: &str,
+        number: i32,
+        label: Label,
+        type_: Type,
+    ) -> FieldDescriptorProto {
+        FieldDescriptorProto {
+            name: Some(name.to_string()),
+            number: Some(number),
+            label: Some(label as i32),
+            r#type: Some(type_ as i32),
+            ..Default::default()
+        }
+    }
+
+    pub(super) fn typed_field(
+        name: &str,
+        number: i32,
+        label: Label,
+        type_: Type,
+        type_name: &str,
+    ) -> FieldDescriptorProto {
+        FieldDescriptorProto {
+            type_name: Some(type_name.to_string()),
+            ..field(name, number, label, type_)
+        }
+    }
+
+    pub(super) fn message(name: &str, field: Vec<FieldDescriptorProto>) -> DescriptorProto {
+        DescriptorProto {
+            name: Some(name.to_string()),
+            field,
+            ..Default::default()
+        }
+    }
+
+    pub(super) fn map_entry(name: &str, key: Type, value: FieldDescriptorProto) -> DescriptorProto {
+        DescriptorProto {
+            options: Some(MessageOptions {
+                map_entry: Some(true),
+                ..Default::default()
+            }),
+            ..message(
+                name,
+                vec![
+                    field("key", 1, Label::Optional, key),
+                    FieldDescriptorProto {
+                        name: Some("value".to_string()),
+                        number: Some(2),
+                        ..value
+                    },
+                ],
+            )
+        }
+    }
+
+    pub(super) fn pool() -> DescriptorPool {
+        let proto3 = FileDescriptorProto {
+            name: Some("test.proto".to_string()),
+            package: Some("test".to_string()),
+            syntax: Some("proto3".to_string()),
+            message_type: vec![
+                DescriptorProto {
+                    oneof_decl: vec![OneofDescriptorProto {
+                        name: Some("_optional".to_string()),
+                        ..Default::default()
+                    }],
+                    ..message(
+                        "Scalars",
+                        vec![
+                            field("int", 1, Label::Optional, Type::Int32),
+                            field("sint", 2, Label::Optional, Type::Sint64),
+                            field("fixed", 3, Label::Optional, Type::Fixed32),
+                            field("double", 4, Label::Optional, Type::Double),
+                            field("string", 5, Label::Optional, Type::String),
+                            field("bytes", 6, Label::Optional, Type::Bytes),
+                            field("flag", 7, Label::Optional, Type::Bool),
+                            typed_field("color", 8, Label::Optional, Type::Enum, ".test.Color"),
+                            FieldDescriptorProto {
+                                oneof_index: Some(0),
+                                proto3_optional: Some(true),
+                                ..field("optional", 9, Label::Optional, Type::Int32)
+                            },
+                        ],
+                    )
+                },
+                message(
+                    "Repeated",
+                    vec![
+                        field("packed", 1, Label::Repeated, Type::Int32),
+                        FieldDescriptorProto {
+                            options: Some(FieldOptions {
+                                packed: Some(false),
+                                ..Default::default()
+                            }),
+                            ..field("unpacked", 2, Label::Repeated, Type::Sint32)
+                        },
+                        field("strings", 3, Label::Repeated, Type::String),
+                        typed_field("messages", 4, Label::Repeated, Type::Message, "Scalars"),
+                    ],
+                ),
+                DescriptorProto {
+                    nested_type: vec![
+                        map_entry(
+                            "CountsEntry",
+                            Type::String,
+                            field("", 0, Label::Optional, Type::Int32),
+                        ),
+                        map_entry(
+                            "ItemsEntry",
+                            Type::Int32,
+                            typed_field("", 0, Label::Optional, Type::Message, ".test.Scalars"),
+                        ),
+                    ],
+                    ..message(
+                        "Maps",
+                        vec![
+                            typed_field("counts", 1, Label::Repeated, Type::Message, "CountsEntry"),
+                            typed_field("items", 2, Label::Repeated, Type::Message, "ItemsEntry"),
+                        ],
+                    )
+                },
+                DescriptorProto {
+                    oneof_decl: vec![OneofDescriptorProto {
+                        name: Some("choice".to_string()),
+                        ..Default::default()
+                    }],
+                    ..message(
+                        "Oneofs",
+                        vec![
+                            field("first", 1, Label::Optional, Type::Int32),
+                            FieldDescriptorProto {
+                                oneof_index: Some(0),
+                                ..field("text", 3, Label::Optional, Type::String)
+                            },
+                            field("middle", 4, Label::Optional, Type::Int32),
+                            FieldDescriptorProto {
+                                oneof_index: Some(0),
+                                ..typed_field(
+                                    "scalars",
+                                    5,
+                                    Label::Optional,
+                                    Type::Message,
+                                    "Scalars",
+                                )
+                            },
+                        ],
+                    )
+                },
+            ],
+            enum_type: vec![EnumDescriptorProto {
+                name: Some("Color".to_string()),
+                value: ["RED", "BLUE"]
+                    .iter()
+                    .enumerate()
+                    .map(|(number, name)| EnumValueDescriptorProto {
+                        name: Some(name.to_string()),
+                        number: Some(number as i32),
+                        ..Default::default()
+                    })
+                    .collect(),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let proto2 = FileDescriptorProto {
+            name: Some("test2.proto".to_string()),
+            package: Some("test2".to_string()),
+            message_type: vec![DescriptorProto {
+                nested_type: vec![message(
+                    "Group",
+                    vec![field("value", 3, Label::Optional, Type::Int32)],
+                )],
+                ..message(
+                    "Proto2",
+                    vec![
+                        FieldDescriptorProto {
+                            default_value: Some("7".to_string()),
+                            ..field("int", 1, Label::Optional, Type::Int32)
+                        },
+                        typed_field(
+                            "group",
+                            2,
+                            Label::Optional,
+                            Type::Group,
+                            ".test2.Proto2.Group",
+                        ),
+                        field("name", 4, Label::Required, Type::String),
+                        FieldDescriptorProto {
+                            default_value: Some("a\\001\\x02".to_string()),
+                            ..field("bytes", 5, Label::Optional, Type::Bytes)
+                        },
+                        typed_field("color", 6, Label::Optional, Type::Enum, ".test.Color"),
+                        field("ints", 7, Label::Repeated, Type::Int32),
+                    ],
+                )
+            }],
+            ..Default::default()
+        };
+        DescriptorPool::new(FileDescriptorSet {
+            file: vec![proto3, proto2],
+        })
+        .unwrap()
+    }
+
+    #[derive(Clone, PartialEq, Message)]
+    struct Scalars {
+        #[prost(int32, tag = "1")]
+        int: i32,
+        #[prost(sint64, tag = "2")]
+        sint: i64,
+        #[prost(fixed32, tag = "3")]
+        fixed: u32,
+        #[prost(double, tag = "4")]
+        double: f64,
+        #[prost(string, tag = "5")]
+        string: String,
+        #[prost(bytes = "vec", tag = "6")]
+        bytes: Vec<u8>,
+        #[prost(bool, tag = "7")]
+        flag: bool,
+        #[prost(int32, tag = "8")]
+        color: i32,
+        #[prost(int32, optional, tag = "9")]
+        optional: Option<i32>,
+    }
+
+    #[derive(Clone, PartialEq, Message)]
+    struct Repeated {
+        #[prost(int32, repeated, tag = "1")]
+        packed: Vec<i32>,
+        #[prost(sint32, repeated, packed = "false", tag = "2")]
+        unpacked: Vec<i32>,
+        #[prost(string, repeated, tag = "3")]
+        strings: Vec<String>,
+        #[prost(message, repeated, tag = "4")]
+        messages: Vec<Scalars>,
+    }
+
+    #[derive(Clone, PartialEq, Message)]
+    struct Maps {
+        #[prost(btree_map = "string, int32", tag = "1")]
+        counts: BTreeMap<String, i32>,
+        #[prost(btree_map = "int32, message", tag = "2")]
+        items: BTreeMap<i32, Scalars>,
+    }
+
+    #[derive(Clone, PartialEq, Message)]
+    struct Oneofs {
+        #[prost(int32, tag = "1")]
+        first: i32,
+        #[prost(oneof = "Choice", tags = "3, 5")]
+        choice: Option<Choice>,
+        #[prost(int32, tag = "4")]
+        middle: i32,
+    }
+
+    #[derive(Clone, PartialEq, prost::Oneof)]
+    enum Choice {
+        #[prost(string, tag = "3")]
+        Text(String),
+        #[prost(message, tag = "5")]
+        Scalars(Scalars),
+    }
+
+    #[derive(Clone, PartialEq, Message)]
+    struct Proto2 {
+        #[prost(int32, optional, tag = "1", default = "7")]
+        int: Option<i32>,
+        #[prost(group, optional, tag = "2")]
+        group: Option<Group>,
+        #[prost(string, required, tag = "4")]
+        name: String,
+        #[prost(bytes = "vec", optional, tag = "5")]
+        bytes: Option<Vec<u8>>,
+        #[prost(int32, optional, tag = "6")]
+        color: Option<i32>,
+        #[prost(int32, repeated, packed = "false", tag = "7")]
+        ints: Vec<i32>,
+    }
+
+    #[derive(Clone, PartialEq, Message)]
+    struct Group {
+        #[prost(int32, optional, tag = "3")]
+        value: Option<i32>,
+    }
+
+    fn scalars() -> Scalars {
+        Scalars {
+            int: -5,
+            sint: -300,
+            fixed: 7,
+            double: 1.5,
+            string: "string".to_string(),
+            bytes: vec![1, 2, 3],
+            flag: true,
+            color: 1,
+            optional: Some(0),
+        }
+    }
+
+    /// Checks that a message decodes to a dynamic message which encodes to the same bytes.
+    fn roundtrip<M>(name: &str, message: &M) -> DynamicMessage
+    where
+        M: Message,
+    {
+        let encoded = message.encode_to_vec();
+        let descriptor = pool().get_message_by_name(name).unwrap();
+        let dynamic = DynamicMessage::decode(descriptor, encoded.as_slice()).unwrap();
+        assert_eq!(dynamic.encoded_len(), encoded.len());
+        assert_eq!(dynamic.encode_to_vec(), encoded);
+        dynamic
+    }
+
+    #[test]
+    fn scalars_roundtrip() {
+        let dynamic = roundtrip("test.Scalars", &scalars());
+        let get = |name| dynamic.get_field_by_name(name).unwrap().into_owned();
+        assert_eq!(get("int"), Value::I32(-5));
+        assert_eq!(get("sint"), Value::I64(-300));
+        assert_eq!(get("fixed"), Value::U32(7));
+        assert_eq!(get("double"), Value::F64(1.5));
+        assert_eq!(get("string"), Value::from("string"));
+        assert_eq!(get("bytes"), Value::Bytes(Bytes::from_static(&[1, 2, 3])));
+        assert_eq!(get("flag"), Value::Bool(true));
+        assert_eq!(get("color"), Value::EnumNumber(1));
+        assert_eq!(get("optional"), Value::I32(0));
+        assert_eq!(dynamic.fields().count(), 9);
+
+        roundtrip("test.Scalars", &Scalars::default());
+    }
+
+    #[test]
+    fn set_fields() {
+        let descriptor = pool().get_message_by_name("test.Scalars").unwrap();
+        let mut dynamic = DynamicMessage::new(descriptor.clone());
+        for field in descriptor.fields() {
+            assert!(!dynamic.has_field(&field));
+        }
+        assert_eq!(
+            dynamic.get_field_by_number(8),
+            Some(Cow::Owned(Value::EnumNumber(0)))
+        );
+
+        dynamic.set_field_by_name("int", Value::I32(-5)).unwrap();
+        dynamic.set_field_by_name("sint", Value::I64(-300)).unwrap();
+        dynamic.set_field_by_name("fixed", Value::U32(7)).unwrap();
+        dynamic
+            .set_field_by_name("double", Value::F64(1.5))
+            .unwrap();
+        dynamic
+            .set_field_by_name("string", "string".into())
+            .unwrap();
+        dynamic
+            .set_field_by_name("bytes", vec![1, 2, 3].into())
+            .unwrap();
+        dynamic.set_field_by_name("flag", true.into()).unwrap();
+        dynamic
+            .set_field_by_number(8, Value::EnumNumber(1))
+            .unwrap();
+        dynamic.set_field_by_number(9, Value::I32(0)).unwrap();
+        assert_eq!(dynamic.encode_to_vec(), scalars().encode_to_vec());
+
+        // Fields without presence are not set when they have their default value.
+        dynamic.set_field_by_name("int", Value::I32(0)).unwrap();
+        assert!(!dynamic.has_field(&descriptor.get_field(1).unwrap()));
+        assert!(dynamic.has_field(&descriptor.get_field(9).unwrap()));
+        let expected = Scalars {
+            int: 0,
+            ..scalars()
+        };
+        assert_eq!(dynamic.encode_to_vec(), expected.encode_to_vec());
+
+        assert_eq!(
+            dynamic.set_field_by_name("missing", Value::I32(0)),
+            Err(SetFieldError::NotFound)
+        );
+        assert_eq!(
+            dynamic.set_field_by_name("int", Value::I64(0)),
+            Err(SetFieldError::InvalidType {
+                field: descriptor.get_field(1).unwrap(),
+                value: Value::I64(0),
+            })
+        );
+        assert!(dynamic.set_field_by_name("color", Value::I32(0)).is_err());
+    }
+
+    #[test]
+    fn repeated_fields() {
+        let repeated = Repeated {
+            packed: vec![1, -2, 300],
+            unpacked: vec![-1, 2],
+            strings: vec!["a".to_string(), String::new()],
+            messages: vec![scalars(), Scalars::default()],
+        };
+        let dynamic = roundtrip("test.Repeated", &repeated);
+        assert_eq!(
+            *dynamic.get_field_by_name("unpacked").unwrap(),
+            Value::List(vec![Value::I32(-1), Value::I32(2)])
+        );
+        let messages = dynamic.get_field_by_name("messages").unwrap();
+        let messages = messages.as_list().unwrap();
+        assert_eq!(messages.len(), 2);
+        assert_eq!(
+            messages[0].as_message().unwrap().encode_to_vec(),
+            scalars().encode_to_vec()
+        );
+
+        // Packed fields are decoded from unpacked encodings, and vice versa.
+        let mut unpacked = Vec::new();
+        encoding::int32::encode_repeated(1, &[1, 2], &mut unpacked);
+        encoding::sint32::encode_packed(2, &[3, 4], &mut unpacked);
+        let descriptor = pool().get_message_by_name("test.Repeated").unwrap();
+        let dynamic = DynamicMessage::decode(descriptor, unpacked.as_slice()).unwrap();
+        let expected = Repeated {
+            packed: vec![1, 2],
+            unpacked: vec![3, 4],
+            ..Repeated::default()
+        };
+        assert_eq!(dynamic.encode_to_vec(), expected.encode_to_vec());
+    }
+
+    #[test]
+    fn map_fields() {
+        let maps = Maps {
+            counts: [("a", 1), ("", 2), ("c", 0)]
+                .iter()
+                .map(|&(key, value)| (key.to_string(), value))
+                .collect(),
+            items: [(0, Scalars::default()), (-1, scalars())]
+                .iter()
+                .cloned()
+                .collect(),
+        };
+        let dynamic = roundtrip("test.Maps", &maps);
+        let counts = dynamic.get_field_by_name("counts").unwrap();
+        let counts = counts.as_map().unwrap();
+        assert_eq!(counts.len(), 3);
+        assert_eq!(counts[&MapKey::String("a".to_string())], Value::I32(1));
+        assert_eq!(counts[&MapKey::String(String::new())], Value::I32(2));
+
+        let descriptor = dynamic.descriptor().clone();
+        let mut dynamic = DynamicMessage::new(descriptor.clone());
+        let field = descriptor.get_field_by_name("counts").unwrap();
+        assert!(field.is_map());
+        dynamic
+            .get_field_mut(&field)
+            .as_map_mut()
+            .unwrap()
+            .insert(MapKey::String("b".to_string()), Value::I32(3));
+        let expected = Maps {
+            counts: [("b".to_string(), 3)].iter().cloned().collect(),
+            ..Maps::default()
+        };
+        assert_eq!(dynamic.encode_to_vec(), expected.encode_to_vec());
+        assert!(dynamic
+            .set_field_by_name("counts", Value::List(Vec::new()))
+            .is_err());
+    }
+
+    #[test]
+    fn oneof_fields() {
+        let oneofs = Oneofs {
+            first: 1,
+            choice: Some(Choice::Scalars(scalars())),
+            middle: 4,
+        };
+        // The oneof is encoded at the position of its lowest field number.
+        let dynamic = roundtrip("test.Oneofs", &oneofs);
+        assert!(dynamic.get_field_by_name("text").unwrap().as_str() == Some(""));
+
+        let descriptor = dynamic.descriptor().clone();
+        let oneof = descriptor.oneofs().next().unwrap();
+        assert_eq!(oneof.name(), "choice");
+        assert_eq!(
+            oneof
+                .fields()
+                .map(|field| field.number())
+                .collect::<Vec<_>>(),
+            [3, 5]
+        );
+
+        let mut dynamic = dynamic;
+        dynamic.set_field_by_name("text", "text".into()).unwrap();
+        assert!(!dynamic.has_field(&descriptor.get_field(5).unwrap()));
+        let expected = Oneofs {
+            choice: Some(Choice::Text("text".to_string())),
+            ..oneofs
+        };
+        assert_eq!(dynamic.encode_to_vec(), expected.encode_to_vec());
+
+        // Decoding another field of the oneof replaces the current one.
+        dynamic.merge(oneofs.encode_to_vec().as_slice()).unwrap();
+        assert!(!dynamic.has_field(&descriptor.get_field(3).unwrap()));
+        assert_eq!(dynamic.encode_to_vec(), oneofs.encode_to_vec());
+
+        // Fields of a oneof have presence, even in proto3.
+        let expected = Oneofs {
+            choice: Some(Choice::Text(String::new())),
+            ..Oneofs::default()
+        };
+        roundtrip("test.Oneofs", &expected);
+    }
+
+    #[test]
+    fn deterministic_encoding() {
+        let oneofs = Oneofs {
+            first: 1,
+            choice: Some(Choice::Scalars(scalars())),
+            middle: 4,
+        };
+        // The oneof is encoded at the position of its current field number.
+        let mut expected = Vec::new();
+        prost::encoding::int32::encode(1, &1, &mut expected);
+        prost::encoding::int32::encode(4, &4, &mut expected);
+        prost::encoding::message::encode(5, &scalars(), &mut expected);
+        assert_eq!(oneofs.encode_to_vec_deterministic(), expected);
+
+        let dynamic = roundtrip("test.Oneofs", &oneofs);
+        assert_eq!(dynamic.encode_to_vec_deterministic(), expected);
+
+        let maps = Maps {
+            counts: [("b".to_string(), 1), ("a".to_string(), 2)].into(),
+            items: [(1, scalars()), (-1, scalars())].into(),
+        };
+        let dynamic = roundtrip("test.Maps", &maps);
+        assert_eq!(
+            dynamic.encode_to_vec_deterministic(),
+            maps.encode_to_vec_deterministic()
+        );
+    }
+
+    #[test]
+    fn proto2_fields() {
+        let descriptor = pool().get_message_by_name("test2.Proto2").unwrap();
+        let dynamic = DynamicMessage::new(descriptor.clone());
+        assert_eq!(*dynamic.get_field_by_name("int").unwrap(), Value::I32(7));
+        assert_eq!(
+            *dynamic.get_field_by_name("bytes").unwrap(),
+            Value::Bytes(Bytes::from_static(b"a\x01\x02"))
+        );
+        assert!(descriptor.get_field_by_name("name").unwrap().is_required());
+        assert!(descriptor.get_field_by_name("group").unwrap().is_group());
+        assert!(!descriptor.get_field_by_name("ints").unwrap().is_packed());
+
+        let proto2 = Proto2 {
+            int: Some(0),
+            group: Some(Group { value: Some(3) }),
+            name: "name".to_string(),
+            bytes: None,
+            color: Some(0),
+            ints: vec![1, 2],
+        };
+        let dynamic = roundtrip("test2.Proto2", &proto2);
+        let group = dynamic.get_field_by_name("group").unwrap();
+        assert_eq!(
+            *group
+                .as_message()
+                .unwrap()
+                .get_field_by_name("value")
+                .unwrap(),
+            Value::I32(3)
+        );
+        let descriptor = dynamic.descriptor();
+        assert!(dynamic.has_field(&descriptor.get_field_by_name("int").unwrap()));
+        assert!(!dynamic.has_field(&descriptor.get_field_by_name("bytes").unwrap()));
+    }
+
+    #[test]
+    fn unknown_fields() {
+        let mut buf = Oneofs {
+            first: 1,
+            ..Oneofs::default()
+        }
+        .encode_to_vec();
+        encoding::string::encode(10, &"unknown".to_string(), &mut buf);
+
+        let descriptor = pool().get_message_by_name("test.Oneofs").unwrap();
+        let dynamic = DynamicMessage::decode(descriptor, buf.as_slice()).unwrap();
+        assert_eq!(dynamic.unknown_fields().len(), 1);
+        assert_eq!(dynamic.encode_to_vec(), buf);
+    }
+
+    #[test]
+    fn invalid_input() {
+        let descriptor = pool().get_message_by_name("test.Scalars").unwrap();
+        let mut buf = Vec::new();
+        encoding::int32::encode(5, &1, &mut buf);
+        assert!(DynamicMessage::decode(descriptor.clone(), buf.as_slice()).is_err());
+        assert!(DynamicMessage::decode(descriptor, &b"\x0a\x05"[..]).is_err());
+    }
+
+    #[test]
+    fn descriptor_errors() {
+        let file = |message_type| FileDescriptorSet {
+            file: vec![FileDescriptorProto {
+                package: Some("test".to_string()),
+                message_type,
+                ..Default::default()
+            }],
+        };
+        assert_eq!(
+            DescriptorPool::new(file(vec![message("A", vec![]), message("A", vec![])])),
+            Err(DescriptorError::DuplicateName("test.A".to_string()))
+        );
+        assert_eq!(
+            DescriptorPool::new(file(vec![message(
+                "A",
+                vec![typed_field(
+                    "b",
+                    1,
+                    Label::Optional,
+                    Type::Message,
+                    ".test.B"
+                )]
+            )])),
+            Err(DescriptorError::UnknownType {
+                field: "test.A.b".to_string(),
+                type_name: ".test.B".to_string(),
+            })
+        );
+        assert_eq!(
+            DescriptorPool::new(file(vec![message(
+                "A",
+                vec![
+                    field("a", 1, Label::Optional, Type::Int32),
+                    field("b", 1, Label::Optional, Type::Int32),
+                ]
+            )])),
+            Err(DescriptorError::InvalidFieldNumber("test.A.b".to_string()))
+        );
+    }
+}
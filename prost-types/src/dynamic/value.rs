@@ -0,0 +1,496 @@
+use prost::alloc::collections::BTreeMap;
+use prost::alloc::string::String;
+use prost::alloc::vec::Vec;
+use prost::bytes::Bytes;
+
+use super::descriptor::FieldKind;
+use super::{DynamicMessage, FieldDescriptor, Kind};
+
+/// The value of a field of a [`DynamicMessage`].
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+    /// A `bool` value.
+    Bool(bool),
+    /// An `int32`, `sint32` or `sfixed32` value.
+    I32(i32),
+    /// An `int64`, `sint64` or `sfixed64` value.
+    I64(i64),
+    /// A `uint32` or `fixed32` value.
+    U32(u32),
+    /// A `uint64` or `fixed64` value.
+    U64(u64),
+    /// A `float` value.
+    F32(f32),
+    /// A `double` value.
+    F64(f64),
+    /// A `string` value.
+    String(String),
+    /// A `bytes` value.
+    Bytes(Bytes),
+    /// The number of an enum value, which may not be a value defined by the enum type.
+    EnumNumber(i32),
+    /// A message or group value.
+    Message(DynamicMessage),
+    /// The values of a repeated field.
+    List(Vec<Value>),
+    /// The entries of a map field.
+    Map(BTreeMap<MapKey, Value>),
+}
+
+/// The key of an entry of a map field.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum MapKey {
+    /// A `bool` key.
+    Bool(bool),
+    /// An `int32`, `sint32` or `sfixed32` key.
+    I32(i32),
+    /// An `int64`, `sint64` or `sfixed64` key.
+    I64(i64),
+    /// A `uint32` or `fixed32` key.
+    U32(u32),
+    /// A `uint64` or `fixed64` key.
+    U64(u64),
+    /// A `string` key.
+    String(String),
+}
+
+impl Value {
+    /// Returns `true` if the value can be stored in the given field.
+    ///
+    /// The elements of lists and the entries of maps are checked as well, as are the types of
+    /// message values, but not the fields of message values.
+    pub fn is_valid_for_field(&self, field: &FieldDescriptor) -> bool {
+        match self {
+            Value::List(values) if field.is_list() => {
+                let kind = field.kind();
+                values.iter().all(|value| value.is_valid(&kind))
+            }
+            Value::Map(entries) if field.is_map() => {
+                let entry = match field.kind() {
+                    Kind::Message(entry) => entry,
+                    _ => return false,
+                };
+                let key_kind = entry.map_entry_key_field().kind();
+                let value_kind = entry.map_entry_value_field().kind();
+                entries
+                    .iter()
+                    .all(|(key, value)| key.is_valid(&key_kind) && value.is_valid(&value_kind))
+            }
+            Value::List(_) | Value::Map(_) => false,
+            _ if field.is_list() || field.is_map() => false,
+            value => value.is_valid(&field.kind()),
+        }
+    }
+
+    /// Returns `true` if the value is a single value of the given type.
+    fn is_valid(&self, kind: &Kind) -> bool {
+        match (self, kind) {
+            (Value::Bool(_), Kind::Bool)
+            | (Value::I32(_), Kind::Int32 | Kind::Sint32 | Kind::Sfixed32)
+            | (Value::I64(_), Kind::Int64 | Kind::Sint64 | Kind::Sfixed64)
+            | (Value::U32(_), Kind::Uint32 | Kind::Fixed32)
+            | (Value::U64(_), Kind::Uint64 | Kind::Fixed64)
+            | (Value::F32(_), Kind::Float)
+            | (Value::F64(_), Kind::Double)
+            | (Value::String(_), Kind::String)
+            | (Value::Bytes(_), Kind::Bytes)
+            | (Value::EnumNumber(_), Kind::Enum(_)) => true,
+            (Value::Message(message), Kind::Message(descriptor)) => {
+                message.descriptor() == descriptor
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns the value if it is a `Bool`.
+    pub fn as_bool(&self) -> Option<bool> {
+        match *self {
+            Value::Bool(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns the value if it is an `I32`.
+    pub fn as_i32(&self) -> Option<i32> {
+        match *self {
+            Value::I32(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns the value if it is an `I64`.
+    pub fn as_i64(&self) -> Option<i64> {
+        match *self {
+            Value::I64(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns the value if it is a `U32`.
+    pub fn as_u32(&self) -> Option<u32> {
+        match *self {
+            Value::U32(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns the value if it is a `U64`.
+    pub fn as_u64(&self) -> Option<u64> {
+        match *self {
+            Value::U64(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns the value if it is an `F32`.
+    pub fn as_f32(&self) -> Option<f32> {
+        match *self {
+            Value::F32(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns the value if it is an `F64`.
+    pub fn as_f64(&self) -> Option<f64> {
+        match *self {
+            Value::F64(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns the value if it is a `String`.
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns the value if it is `Bytes`.
+    pub fn as_bytes(&self) -> Option<&Bytes> {
+        match self {
+            Value::Bytes(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns the value if it is an `EnumNumber`.
+    pub fn as_enum_number(&self) -> Option<i32> {
+        match *self {
+            Value::EnumNumber(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns the value if it is a `Message`.
+    pub fn as_message(&self) -> Option<&DynamicMessage> {
+        match self {
+            Value::Message(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns a mutable reference to the value if it is a `Message`.
+    pub fn as_message_mut(&mut self) -> Option<&mut DynamicMessage> {
+        match self {
+            Value::Message(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns the values if the value is a `List`.
+    pub fn as_list(&self) -> Option<&[Value]> {
+        match self {
+            Value::List(values) => Some(values),
+            _ => None,
+        }
+    }
+
+    /// Returns a mutable reference to the values if the value is a `List`.
+    pub fn as_list_mut(&mut self) -> Option<&mut Vec<Value>> {
+        match self {
+            Value::List(values) => Some(values),
+            _ => None,
+        }
+    }
+
+    /// Returns the entries if the value is a `Map`.
+    pub fn as_map(&self) -> Option<&BTreeMap<MapKey, Value>> {
+        match self {
+            Value::Map(entries) => Some(entries),
+            _ => None,
+        }
+    }
+
+    /// Returns a mutable reference to the entries if the value is a `Map`.
+    pub fn as_map_mut(&mut self) -> Option<&mut BTreeMap<MapKey, Value>> {
+        match self {
+            Value::Map(entries) => Some(entries),
+            _ => None,
+        }
+    }
+}
+
+impl MapKey {
+    /// Returns the default key of a map key type.
+    pub(super) fn default_for(kind: FieldKind) -> MapKey {
+        match kind {
+            FieldKind::Int32 | FieldKind::Sint32 | FieldKind::Sfixed32 => MapKey::I32(0),
+            FieldKind::Int64 | FieldKind::Sint64 | FieldKind::Sfixed64 => MapKey::I64(0),
+            FieldKind::Uint32 | FieldKind::Fixed32 => MapKey::U32(0),
+            FieldKind::Uint64 | FieldKind::Fixed64 => MapKey::U64(0),
+            FieldKind::Bool => MapKey::Bool(false),
+            _ => MapKey::String(String::new()),
+        }
+    }
+
+    /// Returns `true` if the key is the default key of its type.
+    pub(super) fn is_default(&self) -> bool {
+        match *self {
+            MapKey::Bool(key) => !key,
+            MapKey::I32(key) => key == 0,
+            MapKey::I64(key) => key == 0,
+            MapKey::U32(key) => key == 0,
+            MapKey::U64(key) => key == 0,
+            MapKey::String(ref key) => key.is_empty(),
+        }
+    }
+
+    /// Returns `true` if the key is a value of the given type.
+    fn is_valid(&self, kind: &Kind) -> bool {
+        matches!(
+            (self, kind),
+            (MapKey::Bool(_), Kind::Bool)
+                | (MapKey::I32(_), Kind::Int32 | Kind::Sint32 | Kind::Sfixed32)
+                | (MapKey::I64(_), Kind::Int64 | Kind::Sint64 | Kind::Sfixed64)
+                | (MapKey::U32(_), Kind::Uint32 | Kind::Fixed32)
+                | (MapKey::U64(_), Kind::Uint64 | Kind::Fixed64)
+                | (MapKey::String(_), Kind::String)
+        )
+    }
+}
+
+impl From<MapKey> for Value {
+    fn from(key: MapKey) -> Value {
+        match key {
+            MapKey::Bool(key) => Value::Bool(key),
+            MapKey::I32(key) => Value::I32(key),
+            MapKey::I64(key) => Value::I64(key),
+            MapKey::U32(key) => Value::U32(key),
+            MapKey::U64(key) => Value::U64(key),
+            MapKey::String(key) => Value::String(key),
+        }
+    }
+}
+
+macro_rules! from {
+    ($($ty:ty => $variant:ident,)*) => {
+        $(
+            impl From<$ty> for Value {
+                fn from(value: $ty) -> Value {
+                    Value::$variant(value.into())
+                }
+            }
+        )*
+    };
+}
+
+from! {
+    bool => Bool,
+    i32 => I32,
+    i64 => I64,
+    u32 => U32,
+    u64 => U64,
+    f32 => F32,
+    f64 => F64,
+    String => String,
+    &str => String,
+    Bytes => Bytes,
+    Vec<u8> => Bytes,
+    DynamicMessage => Message,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use prost::alloc::string::ToString;
+    use prost::alloc::vec;
+
+    use crate::dynamic::tests::pool;
+
+    fn field(message: &str, name: &str) -> FieldDescriptor {
+        pool()
+            .get_message_by_name(message)
+            .unwrap()
+            .get_field_by_name(name)
+            .unwrap()
+    }
+
+    #[test]
+    fn valid_scalars() {
+        let valid = |name, value: Value| value.is_valid_for_field(&field("test.Scalars", name));
+        assert!(valid("int", Value::I32(1)));
+        assert!(valid("sint", Value::I64(1)));
+        assert!(valid("fixed", Value::U32(1)));
+        assert!(valid("double", Value::F64(1.0)));
+        assert!(valid("string", "".into()));
+        assert!(valid("bytes", Value::Bytes(Bytes::new())));
+        assert!(valid("flag", false.into()));
+        assert!(valid("color", Value::EnumNumber(7)));
+
+        assert!(!valid("int", Value::I64(1)));
+        assert!(!valid("int", Value::U32(1)));
+        assert!(!valid("double", Value::F32(1.0)));
+        assert!(!valid("string", Value::Bytes(Bytes::new())));
+        assert!(!valid("color", Value::I32(1)));
+        assert!(!valid("int", Value::List(vec![Value::I32(1)])));
+    }
+
+    #[test]
+    fn valid_lists() {
+        let packed = field("test.Repeated", "packed");
+        assert!(Value::List(Vec::new()).is_valid_for_field(&packed));
+        assert!(Value::List(vec![Value::I32(1), Value::I32(2)]).is_valid_for_field(&packed));
+        assert!(!Value::List(vec![Value::I32(1), Value::I64(2)]).is_valid_for_field(&packed));
+        assert!(!Value::I32(1).is_valid_for_field(&packed));
+        assert!(!Value::Map(BTreeMap::new()).is_valid_for_field(&packed));
+
+        // Message values must have the type of the field, but their fields are not checked.
+        let messages = field("test.Repeated", "messages");
+        let pool = messages.parent_message().pool();
+        let scalars = pool.get_message_by_name("test.Scalars").unwrap();
+        let oneofs = pool.get_message_by_name("test.Oneofs").unwrap();
+        let message = Value::Message(DynamicMessage::new(scalars));
+        assert!(Value::List(vec![message.clone()]).is_valid_for_field(&messages));
+        let other = Value::Message(DynamicMessage::new(oneofs));
+        assert!(!Value::List(vec![message, other]).is_valid_for_field(&messages));
+    }
+
+    #[test]
+    fn valid_maps() {
+        let counts = field("test.Maps", "counts");
+        let entries = |key: MapKey, value: Value| Value::Map([(key, value)].into_iter().collect());
+        assert!(Value::Map(BTreeMap::new()).is_valid_for_field(&counts));
+        assert!(entries(MapKey::String("a".to_string()), Value::I32(1)).is_valid_for_field(&counts));
+        assert!(!entries(MapKey::I32(1), Value::I32(1)).is_valid_for_field(&counts));
+        assert!(
+            !entries(MapKey::String("a".to_string()), Value::I64(1)).is_valid_for_field(&counts)
+        );
+        assert!(!Value::List(Vec::new()).is_valid_for_field(&counts));
+
+        let items = field("test.Maps", "items");
+        let scalars = items
+            .parent_message()
+            .pool()
+            .get_message_by_name("test.Scalars")
+            .unwrap();
+        let message = Value::Message(DynamicMessage::new(scalars));
+        assert!(entries(MapKey::I32(1), message.clone()).is_valid_for_field(&items));
+        assert!(!entries(MapKey::U32(1), message).is_valid_for_field(&items));
+        assert!(!entries(MapKey::I32(1), Value::I32(1)).is_valid_for_field(&items));
+    }
+
+    #[test]
+    fn accessors() {
+        assert_eq!(Value::Bool(true).as_bool(), Some(true));
+        assert_eq!(Value::I32(-1).as_i32(), Some(-1));
+        assert_eq!(Value::I64(-2).as_i64(), Some(-2));
+        assert_eq!(Value::U32(3).as_u32(), Some(3));
+        assert_eq!(Value::U64(4).as_u64(), Some(4));
+        assert_eq!(Value::F32(0.5).as_f32(), Some(0.5));
+        assert_eq!(Value::F64(0.25).as_f64(), Some(0.25));
+        assert_eq!(Value::from("a").as_str(), Some("a"));
+        assert_eq!(
+            Value::from(vec![1u8]).as_bytes(),
+            Some(&Bytes::from_static(&[1]))
+        );
+        assert_eq!(Value::EnumNumber(5).as_enum_number(), Some(5));
+
+        // Accessors of other types return `None`, and integer types are not converted.
+        let value = Value::I32(1);
+        assert_eq!(value.as_bool(), None);
+        assert_eq!(value.as_i64(), None);
+        assert_eq!(value.as_u32(), None);
+        assert_eq!(value.as_enum_number(), None);
+        assert_eq!(value.as_str(), None);
+        assert_eq!(value.as_list(), None);
+        assert_eq!(value.as_map(), None);
+        assert!(value.as_message().is_none());
+
+        let mut list = Value::List(vec![Value::I32(1)]);
+        list.as_list_mut().unwrap().push(Value::I32(2));
+        assert_eq!(list.as_list(), Some(&[Value::I32(1), Value::I32(2)][..]));
+
+        let mut map = Value::Map(BTreeMap::new());
+        map.as_map_mut()
+            .unwrap()
+            .insert(MapKey::Bool(true), Value::Bool(false));
+        assert_eq!(
+            map.as_map().unwrap()[&MapKey::Bool(true)],
+            Value::Bool(false)
+        );
+
+        let scalars = pool().get_message_by_name("test.Scalars").unwrap();
+        let mut message = Value::Message(DynamicMessage::new(scalars));
+        message
+            .as_message_mut()
+            .unwrap()
+            .set_field_by_name("int", Value::I32(3))
+            .unwrap();
+        assert_eq!(
+            *message
+                .as_message()
+                .unwrap()
+                .get_field_by_name("int")
+                .unwrap(),
+            Value::I32(3)
+        );
+    }
+
+    #[test]
+    fn conversions() {
+        assert_eq!(Value::from(true), Value::Bool(true));
+        assert_eq!(Value::from(-1i32), Value::I32(-1));
+        assert_eq!(Value::from(-1i64), Value::I64(-1));
+        assert_eq!(Value::from(1u32), Value::U32(1));
+        assert_eq!(Value::from(1u64), Value::U64(1));
+        assert_eq!(Value::from(1.5f32), Value::F32(1.5));
+        assert_eq!(Value::from(1.5f64), Value::F64(1.5));
+        assert_eq!(Value::from("a"), Value::String("a".to_string()));
+        assert_eq!(Value::from("a".to_string()), Value::String("a".to_string()));
+        assert_eq!(
+            Value::from(vec![1u8, 2]),
+            Value::Bytes(Bytes::from_static(&[1, 2]))
+        );
+
+        assert_eq!(Value::from(MapKey::Bool(true)), Value::Bool(true));
+        assert_eq!(Value::from(MapKey::I32(-1)), Value::I32(-1));
+        assert_eq!(Value::from(MapKey::I64(-1)), Value::I64(-1));
+        assert_eq!(Value::from(MapKey::U32(1)), Value::U32(1));
+        assert_eq!(Value::from(MapKey::U64(1)), Value::U64(1));
+        assert_eq!(
+            Value::from(MapKey::String("k".to_string())),
+            Value::String("k".to_string())
+        );
+    }
+
+    #[test]
+    fn default_map_keys() {
+        for (kind, key) in [
+            (FieldKind::Int32, MapKey::I32(0)),
+            (FieldKind::Sfixed64, MapKey::I64(0)),
+            (FieldKind::Fixed32, MapKey::U32(0)),
+            (FieldKind::Uint64, MapKey::U64(0)),
+            (FieldKind::Bool, MapKey::Bool(false)),
+            (FieldKind::String, MapKey::String(String::new())),
+        ] {
+            assert_eq!(MapKey::default_for(kind), key);
+            assert!(key.is_default());
+        }
+        assert!(!MapKey::I32(1).is_default());
+        assert!(!MapKey::Bool(true).is_default());
+        assert!(!MapKey::String("a".to_string()).is_default());
+    }
+}
@@ -0,0 +1,448 @@
+use super::*;
+
+use prost::alloc::collections::BTreeMap;
+use prost::alloc::string::ToString;
+
+use crate::dynamic::{Kind, MessageDescriptor};
+
+impl FieldMask {
+    /// Creates a field mask from paths.
+    pub fn new<I, S>(paths: I) -> FieldMask
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        FieldMask {
+            paths: paths.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    /// Parses a field mask from its JSON form: a comma-separated list of paths, with the field
+    /// names in lowerCamelCase.
+    ///
+    /// Based on [`google::protobuf::util::FieldMaskUtil::FromJsonString`][1].
+    ///
+    /// [1]: https://github.com/protocolbuffers/protobuf/blob/v25.1/src/google/protobuf/util/field_mask_util.h#L67-L70
+    pub fn from_json_string(value: &str) -> Result<FieldMask, FieldMaskError> {
+        value
+            .split(',')
+            .filter(|path| !path.is_empty())
+            .map(|path| {
+                if path.contains('_') {
+                    return Err(FieldMaskError::InvalidJsonPath(path.to_string()));
+                }
+                let mut snake = String::with_capacity(path.len());
+                for c in path.chars() {
+                    if c.is_ascii_uppercase() {
+                        snake.push('_');
+                        snake.push(c.to_ascii_lowercase());
+                    } else {
+                        snake.push(c);
+                    }
+                }
+                Ok(snake)
+            })
+            .collect::<Result<_, _>>()
+            .map(|paths| FieldMask { paths })
+    }
+
+    /// Formats the field mask in its JSON form: a comma-separated list of paths, with the field
+    /// names in lowerCamelCase.
+    ///
+    /// Fails if a path can not be converted to lowerCamelCase and back unchanged, which is the
+    /// case if it contains an uppercase letter, or an underscore which is not followed by a
+    /// lowercase letter.
+    ///
+    /// Based on [`google::protobuf::util::FieldMaskUtil::ToJsonString`][1].
+    ///
+    /// [1]: https://github.com/protocolbuffers/protobuf/blob/v25.1/src/google/protobuf/util/field_mask_util.h#L63-L66
+    pub fn to_json_string(&self) -> Result<String, FieldMaskError> {
+        let mut value = String::new();
+        for (index, path) in self.paths.iter().enumerate() {
+            if index > 0 {
+                value.push(',');
+            }
+            let mut chars = path.chars();
+            while let Some(c) = chars.next() {
+                match c {
+                    '_' => match chars.next() {
+                        Some(next @ 'a'..='z') => value.push(next.to_ascii_uppercase()),
+                        _ => return Err(FieldMaskError::UnrepresentablePath(path.clone())),
+                    },
+                    'A'..='Z' => return Err(FieldMaskError::UnrepresentablePath(path.clone())),
+                    c => value.push(c),
+                }
+            }
+        }
+        Ok(value)
+    }
+
+    /// Converts the field mask to its canonical form: the paths are sorted, and duplicate paths
+    /// and paths covered by another path of the mask are removed.
+    ///
+    /// Based on [`google::protobuf::util::FieldMaskUtil::ToCanonicalForm`][1].
+    ///
+    /// [1]: https://github.com/protocolbuffers/protobuf/blob/v25.1/src/google/protobuf/util/field_mask_util.h#L109-L115
+    pub fn canonicalize(&mut self) {
+        self.paths = Tree::new(&self.paths).paths();
+    }
+
+    /// Returns the union of two field masks, in canonical form.
+    pub fn union(&self, other: &FieldMask) -> FieldMask {
+        let mut tree = Tree::new(&self.paths);
+        for path in &other.paths {
+            tree.insert(path);
+        }
+        FieldMask {
+            paths: tree.paths(),
+        }
+    }
+
+    /// Returns the intersection of two field masks, in canonical form.
+    ///
+    /// A path is in the intersection if it is covered by both masks.
+    pub fn intersection(&self, other: &FieldMask) -> FieldMask {
+        FieldMask {
+            paths: Tree::new(&self.paths)
+                .intersection(&Tree::new(&other.paths))
+                .paths(),
+        }
+    }
+
+    /// Returns the paths of the field mask which are not covered by `other`, in canonical form.
+    ///
+    /// When `other` covers only part of a singular message field named by a path of `self`, the
+    /// path is replaced by paths naming the remaining fields of the message, which are looked up
+    /// in the descriptor of the message type the masks apply to. Such a path is kept unchanged if
+    /// it does not name a singular message field in the descriptor.
+    ///
+    /// Based on [`google::protobuf::util::FieldMaskUtil::Subtract`][1].
+    ///
+    /// [1]: https://github.com/protocolbuffers/protobuf/blob/v25.1/src/google/protobuf/util/field_mask_util.h#L129-L137
+    pub fn subtract(&self, other: &FieldMask, descriptor: &MessageDescriptor) -> FieldMask {
+        FieldMask {
+            paths: Tree::new(&self.paths)
+                .subtract(&Tree::new(&other.paths), Some(descriptor))
+                .paths(),
+        }
+    }
+
+    /// Returns `true` if the path is covered by the field mask, which is the case if it is one of
+    /// the paths of the mask, or descends into a field named by one of them.
+    ///
+    /// Based on [`google::protobuf::util::FieldMaskUtil::IsPathInFieldMask`][1].
+    ///
+    /// [1]: https://github.com/protocolbuffers/protobuf/blob/v25.1/src/google/protobuf/util/field_mask_util.h#L147-L150
+    pub fn contains(&self, path: &str) -> bool {
+        self.paths.iter().any(|mask_path| {
+            path.strip_prefix(mask_path.as_str())
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with('.'))
+        })
+    }
+}
+
+impl FromStr for FieldMask {
+    type Err = FieldMaskError;
+
+    /// Parses a field mask from its JSON form, as [`FieldMask::from_json_string`] does.
+    fn from_str(s: &str) -> Result<FieldMask, FieldMaskError> {
+        FieldMask::from_json_string(s)
+    }
+}
+
+/// A field mask handling error.
+#[derive(Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum FieldMaskError {
+    /// A path of the JSON form of a field mask contains an underscore.
+    InvalidJsonPath(String),
+
+    /// A path can not be converted to the JSON form of a field mask.
+    UnrepresentablePath(String),
+}
+
+impl fmt::Display for FieldMaskError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FieldMaskError::InvalidJsonPath(path) => {
+                write!(f, "invalid field mask path '{}'", path)
+            }
+            FieldMaskError::UnrepresentablePath(path) => {
+                write!(f, "field mask path '{}' can not be written as JSON", path)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for FieldMaskError {}
+
+/// The paths of a field mask, arranged as a tree of field names.
+///
+/// The tree of a field selects the whole field if it has no children.
+#[derive(Clone, Default)]
+struct Tree {
+    children: BTreeMap<String, Tree>,
+}
+
+impl Tree {
+    fn new(paths: &[String]) -> Tree {
+        let mut tree = Tree::default();
+        for path in paths {
+            tree.insert(path);
+        }
+        tree
+    }
+
+    /// Adds a path to the tree, unless it is covered by a path already in the tree.
+    fn insert(&mut self, path: &str) {
+        let mut node = self;
+        let mut names = path.split('.').peekable();
+        while let Some(name) = names.next() {
+            let exists = node.children.contains_key(name);
+            node = node.children.entry(name.to_string()).or_default();
+            if exists && node.children.is_empty() {
+                return;
+            }
+            if names.peek().is_none() {
+                node.children.clear();
+            }
+        }
+    }
+
+    /// Returns the paths of the tree, in sorted order.
+    fn paths(&self) -> Vec<String> {
+        let mut paths = Vec::new();
+        for (name, tree) in &self.children {
+            if tree.children.is_empty() {
+                paths.push(name.clone());
+            } else {
+                paths.extend(
+                    tree.paths()
+                        .into_iter()
+                        .map(|path| format!("{}.{}", name, path)),
+                );
+            }
+        }
+        paths
+    }
+
+    fn intersection(&self, other: &Tree) -> Tree {
+        let mut children = BTreeMap::new();
+        for (name, tree) in &self.children {
+            let other = match other.children.get(name) {
+                Some(other) => other,
+                None => continue,
+            };
+            let intersection = if tree.children.is_empty() {
+                other.clone()
+            } else if other.children.is_empty() {
+                tree.clone()
+            } else {
+                let intersection = tree.intersection(other);
+                if intersection.children.is_empty() {
+                    continue;
+                }
+                intersection
+            };
+            children.insert(name.clone(), intersection);
+        }
+        Tree { children }
+    }
+
+    /// Returns the paths of `self` not covered by `other`, where `descriptor` is the type of the
+    /// message the paths name fields of, if known.
+    fn subtract(&self, other: &Tree, descriptor: core::option::Option<&MessageDescriptor>) -> Tree {
+        let mut children = BTreeMap::new();
+        for (name, tree) in &self.children {
+            let other = match other.children.get(name) {
+                Some(other) if other.children.is_empty() => continue,
+                Some(other) => other,
+                None => {
+                    children.insert(name.clone(), tree.clone());
+                    continue;
+                }
+            };
+            let message = descriptor
+                .and_then(|descriptor| descriptor.get_field_by_name(name))
+                .filter(|field| !field.is_list() && !field.is_map())
+                .and_then(|field| match field.kind() {
+                    Kind::Message(message) => Some(message),
+                    _ => None,
+                });
+            let difference = match (&message, tree.children.is_empty()) {
+                // Expand the path to the fields of the message, to subtract paths from them.
+                (Some(message), true) => Tree {
+                    children: message
+                        .fields()
+                        .map(|field| (field.name().to_string(), Tree::default()))
+                        .collect(),
+                }
+                .subtract(other, Some(message)),
+                (None, true) => {
+                    children.insert(name.clone(), tree.clone());
+                    continue;
+                }
+                (message, false) => tree.subtract(other, message.as_ref()),
+            };
+            if !difference.children.is_empty() {
+                children.insert(name.clone(), difference);
+            }
+        }
+        Tree { children }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use prost::alloc::vec;
+
+    fn mask(paths: &[&str]) -> FieldMask {
+        FieldMask::new(paths.iter().copied())
+    }
+
+    #[test]
+    fn json_string() {
+        let field_mask = mask(&["foo_bar", "baz.qux_quux", "x"]);
+        assert_eq!(field_mask.to_json_string().unwrap(), "fooBar,baz.quxQuux,x");
+        assert_eq!(
+            FieldMask::from_json_string("fooBar,baz.quxQuux,x").unwrap(),
+            field_mask
+        );
+        assert_eq!("fooBar,baz.quxQuux,x".parse(), Ok(field_mask));
+
+        assert_eq!(
+            FieldMask::from_json_string("").unwrap(),
+            FieldMask::default()
+        );
+        assert_eq!(FieldMask::default().to_json_string().unwrap(), "");
+        assert_eq!(
+            FieldMask::from_json_string("foo,bar_baz"),
+            Err(FieldMaskError::InvalidJsonPath("bar_baz".to_string()))
+        );
+        for path in ["fooBar", "foo__bar", "foo_", "foo_1"] {
+            assert_eq!(
+                mask(&[path]).to_json_string(),
+                Err(FieldMaskError::UnrepresentablePath(path.to_string()))
+            );
+        }
+    }
+
+    #[test]
+    fn canonicalize() {
+        let mut field_mask = mask(&[
+            "foo.bar", "baz", "foo", "baz", "qux.a.b", "qux.a.c", "qux.b",
+        ]);
+        field_mask.canonicalize();
+        assert_eq!(
+            field_mask,
+            mask(&["baz", "foo", "qux.a.b", "qux.a.c", "qux.b"])
+        );
+
+        let mut field_mask = FieldMask::default();
+        field_mask.canonicalize();
+        assert_eq!(field_mask, FieldMask::default());
+    }
+
+    #[test]
+    fn union() {
+        assert_eq!(
+            mask(&["foo", "bar.baz", "bar.quz"]).union(&mask(&["foo.bar", "bar"])),
+            mask(&["bar", "foo"])
+        );
+        assert_eq!(
+            mask(&["foo.bar"]).union(&mask(&["foo.baz", "qux"])),
+            mask(&["foo.bar", "foo.baz", "qux"])
+        );
+    }
+
+    #[test]
+    fn intersection() {
+        assert_eq!(
+            mask(&["foo", "bar.baz", "bar.quz"]).intersection(&mask(&["foo.bar", "bar"])),
+            mask(&["bar.baz", "bar.quz", "foo.bar"])
+        );
+        assert_eq!(
+            mask(&["foo.bar", "baz"]).intersection(&mask(&["foo.baz", "qux"])),
+            FieldMask::default()
+        );
+    }
+
+    #[test]
+    fn subtract() {
+        let file_descriptor_set = FileDescriptorSet {
+            file: vec![FileDescriptorProto {
+                name: Some("test.proto".to_string()),
+                package: Some("test".to_string()),
+                message_type: vec![
+                    DescriptorProto {
+                        name: Some("Outer".to_string()),
+                        field: vec![
+                            field("a", 1, ".test.Inner"),
+                            field("b", 2, ""),
+                            field("c", 3, ".test.Inner"),
+                        ],
+                        ..Default::default()
+                    },
+                    DescriptorProto {
+                        name: Some("Inner".to_string()),
+                        field: vec![field("x", 1, ""), field("y", 2, ""), field("z", 3, "")],
+                        ..Default::default()
+                    },
+                ],
+                ..Default::default()
+            }],
+        };
+        let pool = crate::dynamic::DescriptorPool::new(file_descriptor_set).unwrap();
+        let outer = pool.get_message_by_name("test.Outer").unwrap();
+
+        assert_eq!(
+            mask(&["a", "b", "c.x"]).subtract(&mask(&["b", "c"]), &outer),
+            mask(&["a"])
+        );
+        assert_eq!(
+            mask(&["a", "b"]).subtract(&mask(&["a.y"]), &outer),
+            mask(&["a.x", "a.z", "b"])
+        );
+        assert_eq!(
+            mask(&["a.x", "a.y", "c"]).subtract(&mask(&["a.x", "a.y", "c.z"]), &outer),
+            mask(&["c.x", "c.y"])
+        );
+        // A path which does not name a message field can not be expanded, and is kept.
+        assert_eq!(mask(&["b"]).subtract(&mask(&["b.x"]), &outer), mask(&["b"]));
+    }
+
+    fn field(name: &str, number: i32, type_name: &str) -> FieldDescriptorProto {
+        FieldDescriptorProto {
+            name: Some(name.to_string()),
+            number: Some(number),
+            label: Some(field_descriptor_proto::Label::Optional as i32),
+            r#type: Some(if type_name.is_empty() {
+                field_descriptor_proto::Type::Int32
+            } else {
+                field_descriptor_proto::Type::Message
+            } as i32),
+            type_name: if type_name.is_empty() {
+                None
+            } else {
+                Some(type_name.to_string())
+            },
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn contains() {
+        let field_mask = mask(&["foo", "bar.baz"]);
+        assert!(field_mask.contains("foo"));
+        assert!(field_mask.contains("foo.qux"));
+        assert!(field_mask.contains("bar.baz"));
+        assert!(field_mask.contains("bar.baz.qux"));
+        assert!(!field_mask.contains("bar"));
+        assert!(!field_mask.contains("foobar"));
+        assert!(!field_mask.contains("bar.bazqux"));
+        assert!(!field_mask.contains(""));
+    }
+}
@@ -0,0 +1,394 @@
+//! The special JSON forms of the well-known types.
+
+use prost::alloc::borrow::Cow;
+use prost::alloc::collections::BTreeMap;
+use prost::alloc::string::ToString;
+use prost::json::{Json, JsonError, Parser, TypeRegistry, ValueKind, Writer};
+
+use super::*;
+
+/// The range of seconds of a `Timestamp` in JSON, from 0001-01-01T00:00:00Z to
+/// 9999-12-31T23:59:59Z.
+const TIMESTAMP_SECONDS: core::ops::RangeInclusive<i64> = -62_135_596_800..=253_402_300_799;
+
+/// The maximum magnitude of the seconds of a `Duration`, which is about 10,000 years.
+const DURATION_SECONDS_MAX: i64 = 315_576_000_000;
+
+/// Adds the well-known types to a registry of the message types which can be packed in `Any`
+/// messages.
+pub fn register_well_known_types(registry: &mut TypeRegistry) -> &mut TypeRegistry {
+    registry
+        .register::<Any>()
+        .register::<Duration>()
+        .register::<Timestamp>()
+        .register_full_name::<FieldMask>("google.protobuf.FieldMask")
+        .register_full_name::<Struct>("google.protobuf.Struct")
+        .register_full_name::<Value>("google.protobuf.Value")
+        .register_full_name::<ListValue>("google.protobuf.ListValue")
+        .register_full_name::<()>("google.protobuf.Empty")
+        .register_full_name::<bool>("google.protobuf.BoolValue")
+        .register_full_name::<i32>("google.protobuf.Int32Value")
+        .register_full_name::<i64>("google.protobuf.Int64Value")
+        .register_full_name::<u32>("google.protobuf.UInt32Value")
+        .register_full_name::<u64>("google.protobuf.UInt64Value")
+        .register_full_name::<f32>("google.protobuf.FloatValue")
+        .register_full_name::<f64>("google.protobuf.DoubleValue")
+        .register_full_name::<String>("google.protobuf.StringValue")
+        .register_full_name::<Vec<u8>>("google.protobuf.BytesValue")
+}
+
+/// Implements the fields of a well-known type with a special form, which are written as a single
+/// `value` member when packed in an `Any` message.
+macro_rules! special_form {
+    ($ty:ty) => {
+        impl Json for $ty {
+            fn write_json_fields(&self, writer: &mut Writer<'_>) -> Result<(), JsonError> {
+                writer.field("value", "value", |writer| self.write_json_value(writer))
+            }
+
+            fn merge_json_field(
+                &mut self,
+                name: &str,
+                parser: &mut Parser<'_>,
+            ) -> Result<bool, JsonError> {
+                if name != "value" {
+                    return Ok(false);
+                }
+                *self = parser.message()?;
+                Ok(true)
+            }
+
+            fn write_json_value(&self, writer: &mut Writer<'_>) -> Result<(), JsonError> {
+                SpecialForm::write(self, writer)
+            }
+
+            fn merge_json_value(&mut self, parser: &mut Parser<'_>) -> Result<(), JsonError> {
+                SpecialForm::merge(self, parser)
+            }
+        }
+    };
+}
+
+trait SpecialForm {
+    fn write(&self, writer: &mut Writer<'_>) -> Result<(), JsonError>;
+
+    fn merge(&mut self, parser: &mut Parser<'_>) -> Result<(), JsonError>;
+}
+
+special_form!(Any);
+special_form!(Duration);
+special_form!(Timestamp);
+special_form!(FieldMask);
+special_form!(Struct);
+special_form!(Value);
+special_form!(ListValue);
+
+/// An `Any` message is an object with the type URL as its `@type` member, and either the fields of
+/// the packed message, or its special form as the `value` member.
+impl SpecialForm for Any {
+    fn write(&self, writer: &mut Writer<'_>) -> Result<(), JsonError> {
+        writer.any(&self.type_url, &self.value)
+    }
+
+    fn merge(&mut self, parser: &mut Parser<'_>) -> Result<(), JsonError> {
+        let (type_url, value) = parser.any()?;
+        self.type_url = type_url;
+        self.value = value;
+        Ok(())
+    }
+}
+
+/// A `Duration` is a string of decimal seconds with an `s` suffix, such as `"1.5s"`.
+impl SpecialForm for Duration {
+    fn write(&self, writer: &mut Writer<'_>) -> Result<(), JsonError> {
+        let mut duration = self.clone();
+        duration.normalize();
+        if duration.seconds.abs() > DURATION_SECONDS_MAX {
+            return Err(writer.error("duration out of range"));
+        }
+
+        let mut value = String::new();
+        if duration.seconds < 0 || duration.nanos < 0 {
+            value.push('-');
+        }
+        value.push_str(&duration.seconds.abs().to_string());
+        push_nanos(&mut value, duration.nanos.unsigned_abs());
+        value.push('s');
+        writer.string(&value)
+    }
+
+    fn merge(&mut self, parser: &mut Parser<'_>) -> Result<(), JsonError> {
+        let value: String = parser.scalar()?;
+        *self = datetime::parse_duration(&value)
+            .filter(|duration| duration.seconds.abs() <= DURATION_SECONDS_MAX)
+            .ok_or_else(|| parser.error(format!("invalid duration '{}'", value)))?;
+        Ok(())
+    }
+}
+
+/// A `Timestamp` is an RFC 3339 string in UTC, such as `"1972-01-01T10:00:20.021Z"`. Offsets from
+/// UTC are accepted when parsing.
+impl SpecialForm for Timestamp {
+    fn write(&self, writer: &mut Writer<'_>) -> Result<(), JsonError> {
+        let mut timestamp = self.clone();
+        timestamp.normalize();
+        if !TIMESTAMP_SECONDS.contains(&timestamp.seconds) {
+            return Err(writer.error("timestamp out of range"));
+        }
+        writer.string(&timestamp.to_string())
+    }
+
+    fn merge(&mut self, parser: &mut Parser<'_>) -> Result<(), JsonError> {
+        let value: String = parser.scalar()?;
+        *self = Some(&value)
+            .filter(|value| is_rfc3339_date_time(value))
+            .and_then(|value| datetime::parse_timestamp(value))
+            .filter(|timestamp| TIMESTAMP_SECONDS.contains(&timestamp.seconds))
+            .ok_or_else(|| parser.error(format!("invalid timestamp '{}'", value)))?;
+        Ok(())
+    }
+}
+
+/// A `FieldMask` is a string of comma-separated paths, with the path segments in lowerCamelCase.
+impl SpecialForm for FieldMask {
+    fn write(&self, writer: &mut Writer<'_>) -> Result<(), JsonError> {
+        let value = self
+            .to_json_string()
+            .map_err(|error| writer.error(error.to_string()))?;
+        writer.string(&value)
+    }
+
+    fn merge(&mut self, parser: &mut Parser<'_>) -> Result<(), JsonError> {
+        let value: String = parser.scalar()?;
+        *self =
+            FieldMask::from_json_string(&value).map_err(|error| parser.error(error.to_string()))?;
+        Ok(())
+    }
+}
+
+/// A `Struct` is an object of arbitrary values.
+impl SpecialForm for Struct {
+    fn write(&self, writer: &mut Writer<'_>) -> Result<(), JsonError> {
+        writer.object(|writer| {
+            for (name, value) in &self.fields {
+                writer.field(name, name, |writer| writer.message(value))?;
+            }
+            Ok(())
+        })
+    }
+
+    fn merge(&mut self, parser: &mut Parser<'_>) -> Result<(), JsonError> {
+        let fields: &mut BTreeMap<String, Value> = &mut self.fields;
+        parser.object(|parser, name| {
+            fields.insert(name.to_string(), parser.message()?);
+            Ok(true)
+        })
+    }
+}
+
+/// A `Value` is any JSON value.
+impl SpecialForm for Value {
+    fn write(&self, writer: &mut Writer<'_>) -> Result<(), JsonError> {
+        match &self.kind {
+            None => Err(writer.error("value has no kind")),
+            Some(value::Kind::NullValue(_)) => writer.null(),
+            Some(value::Kind::NumberValue(value)) if !value.is_finite() => {
+                Err(writer.error("number value is not finite"))
+            }
+            Some(value::Kind::NumberValue(value)) => writer.scalar(value),
+            Some(value::Kind::StringValue(value)) => writer.scalar(value),
+            Some(value::Kind::BoolValue(value)) => writer.scalar(value),
+            Some(value::Kind::StructValue(value)) => writer.message(value),
+            Some(value::Kind::ListValue(value)) => writer.message(value),
+        }
+    }
+
+    fn merge(&mut self, parser: &mut Parser<'_>) -> Result<(), JsonError> {
+        let kind = match parser.peek_kind()? {
+            ValueKind::Null => value::Kind::NullValue(parser.null_value()?),
+            ValueKind::Bool => value::Kind::BoolValue(parser.scalar()?),
+            ValueKind::Number => value::Kind::NumberValue(parser.scalar()?),
+            ValueKind::String => value::Kind::StringValue(parser.scalar()?),
+            ValueKind::Object => value::Kind::StructValue(parser.message()?),
+            ValueKind::Array => value::Kind::ListValue(parser.message()?),
+        };
+        self.kind = Some(kind);
+        Ok(())
+    }
+}
+
+/// A `ListValue` is an array of arbitrary values.
+impl SpecialForm for ListValue {
+    fn write(&self, writer: &mut Writer<'_>) -> Result<(), JsonError> {
+        writer.list(&self.values, |writer, value| writer.message(value))
+    }
+
+    fn merge(&mut self, parser: &mut Parser<'_>) -> Result<(), JsonError> {
+        let values = &mut self.values;
+        parser.array(|parser| {
+            values.push(parser.message()?);
+            Ok(())
+        })
+    }
+}
+
+/// Appends the fraction of a second with 0, 3, 6 or 9 digits.
+fn push_nanos(value: &mut String, nanos: u32) {
+    let fraction: Cow<'_, str> = if nanos == 0 {
+        Cow::Borrowed("")
+    } else if nanos % 1_000_000 == 0 {
+        Cow::Owned(format!(".{:03}", nanos / 1_000_000))
+    } else if nanos % 1_000 == 0 {
+        Cow::Owned(format!(".{:06}", nanos / 1_000))
+    } else {
+        Cow::Owned(format!(".{:09}", nanos))
+    };
+    value.push_str(&fraction);
+}
+
+/// Returns `true` if the string has the form of an RFC 3339 date and time, with an uppercase `T`
+/// separator and either a `Z` suffix or an offset.
+fn is_rfc3339_date_time(value: &str) -> bool {
+    let bytes = value.as_bytes();
+    let digits = |range: core::ops::Range<usize>| {
+        bytes
+            .get(range)
+            .is_some_and(|digits| digits.iter().all(u8::is_ascii_digit))
+    };
+    let offset = match bytes.last() {
+        Some(b'Z') => 1,
+        _ => 6,
+    };
+    digits(0..4)
+        && bytes.get(4) == Some(&b'-')
+        && bytes.get(10) == Some(&b'T')
+        && bytes.len() >= 19 + offset
+        && (offset == 1
+            || (matches!(bytes[bytes.len() - 6], b'+' | b'-')
+                && digits(bytes.len() - 5..bytes.len() - 3)
+                && bytes[bytes.len() - 3] == b':'
+                && digits(bytes.len() - 2..bytes.len())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use prost::json::JsonOptions;
+
+    #[test]
+    fn duration() {
+        let duration = |seconds, nanos| Duration { seconds, nanos };
+        assert_eq!(duration(1, 500_000_000).to_json().unwrap(), "\"1.500s\"");
+        assert_eq!(duration(0, -1_000).to_json().unwrap(), "\"-0.000001s\"");
+        assert_eq!(duration(-3, 0).to_json().unwrap(), "\"-3s\"");
+        assert!(duration(315_576_000_001, 0).to_json().is_err());
+
+        assert_eq!(
+            Duration::parse_json("\"-1.5s\"").unwrap(),
+            duration(-1, -500_000_000)
+        );
+        assert!(Duration::parse_json("\"1.5\"").is_err());
+        assert!(Duration::parse_json("\"315576000001s\"").is_err());
+    }
+
+    #[test]
+    fn timestamp() {
+        let timestamp = Timestamp {
+            seconds: 63_108_020,
+            nanos: 21_000_000,
+        };
+        assert_eq!(timestamp.to_json().unwrap(), "\"1972-01-01T10:00:20.021Z\"");
+        assert_eq!(
+            Timestamp::parse_json("\"1972-01-01T10:00:20.021Z\"").unwrap(),
+            timestamp
+        );
+        assert_eq!(
+            Timestamp::parse_json("\"1972-01-01T11:00:20.021+01:00\"").unwrap(),
+            timestamp
+        );
+        assert!(Timestamp::parse_json("\"1972-01-01t10:00:20Z\"").is_err());
+        assert!(Timestamp::parse_json("\"1972-01-01T10:00:20z\"").is_err());
+        assert!(Timestamp::parse_json("\"1972-01-01T10:00:20\"").is_err());
+        assert!(Timestamp::parse_json("\"0000-01-01T00:00:00Z\"").is_err());
+        assert!(Timestamp {
+            seconds: 253_402_300_800,
+            nanos: 0
+        }
+        .to_json()
+        .is_err());
+    }
+
+    #[test]
+    fn field_mask() {
+        let mask = FieldMask {
+            paths: vec!["foo_bar".to_string(), "baz.qux_quux".to_string()],
+        };
+        assert_eq!(mask.to_json().unwrap(), "\"fooBar,baz.quxQuux\"");
+        assert_eq!(
+            FieldMask::parse_json("\"fooBar,baz.quxQuux\"").unwrap(),
+            mask
+        );
+        assert_eq!(FieldMask::parse_json("\"\"").unwrap(), FieldMask::default());
+        assert!(FieldMask::parse_json("\"foo_bar\"").is_err());
+        for path in ["fooBar", "foo_3_bar", "foo__bar"] {
+            let mask = FieldMask {
+                paths: vec![path.to_string()],
+            };
+            assert!(mask.to_json().is_err(), "{}", path);
+        }
+    }
+
+    #[test]
+    fn struct_value() {
+        let json = r#"{"a":null,"b":[1.5,"c",true,{}],"d":{"e":false}}"#;
+        let value = Value::parse_json(json).unwrap();
+        assert_eq!(value.to_json().unwrap(), json);
+        assert!(matches!(value.kind, Some(value::Kind::StructValue(_))));
+        assert_eq!(
+            Struct::parse_json(json).unwrap().fields["a"],
+            Value {
+                kind: Some(value::Kind::NullValue(0))
+            }
+        );
+        assert!(Value::default().to_json().is_err());
+        assert!(Value {
+            kind: Some(value::Kind::NumberValue(f64::NAN))
+        }
+        .to_json()
+        .is_err());
+        assert!(Struct::parse_json("[]").is_err());
+        assert_eq!(ListValue::parse_json("[]").unwrap(), ListValue::default());
+    }
+
+    #[test]
+    fn any() {
+        let mut registry = TypeRegistry::new();
+        register_well_known_types(&mut registry);
+        let options = JsonOptions::new().type_registry(&registry);
+
+        let any = Any::from_msg(&Duration {
+            seconds: 1,
+            nanos: 0,
+        })
+        .unwrap();
+        let json = any.to_json_with_options(&options).unwrap();
+        assert_eq!(
+            json,
+            r#"{"@type":"type.googleapis.com/google.protobuf.Duration","value":"1s"}"#
+        );
+        assert_eq!(Any::parse_json_with_options(&json, &options).unwrap(), any);
+
+        let nested = Any::from_msg(&any).unwrap();
+        let json = nested.to_json_with_options(&options).unwrap();
+        assert_eq!(
+            Any::parse_json_with_options(&json, &options).unwrap(),
+            nested
+        );
+
+        assert_eq!(Any::default().to_json().unwrap(), "{}");
+        assert_eq!(Any::parse_json("{}").unwrap(), Any::default());
+        assert!(any.to_json().is_err());
+        assert!(Any::parse_json(r#"{"value":"1s"}"#).is_err());
+    }
+}
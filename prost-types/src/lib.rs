@@ -14,6 +14,7 @@
 #[rustfmt::skip]
 pub mod compiler;
 mod datetime;
+pub mod dynamic;
 #[rustfmt::skip]
 mod protobuf;
 
@@ -45,8 +46,14 @@ mod any;
 mod duration;
 pub use duration::DurationError;
 
+mod field_mask;
+pub use field_mask::FieldMaskError;
+
 mod timestamp;
 pub use timestamp::TimestampError;
 
 mod type_url;
 pub(crate) use type_url::{type_url_for, TypeUrl};
+
+mod json;
+pub use json::register_well_known_types;
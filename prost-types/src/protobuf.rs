@@ -7,6 +7,89 @@ pub struct FileDescriptorSet {
     #[prost(message, repeated, tag = "1")]
     pub file: ::prost::alloc::vec::Vec<FileDescriptorProto>,
 }
+#[allow(deprecated)]
+impl ::prost::text_format::TextFormat for FileDescriptorSet {
+    fn print_fields(&self, printer: &mut ::prost::text_format::Printer<'_>) {
+        for value in &self.file {
+            printer.message("file", value);
+        }
+    }
+    fn merge_text_field(
+        &mut self,
+        name: &str,
+        parser: &mut ::prost::text_format::Parser<'_>,
+    ) -> ::core::result::Result<bool, ::prost::text_format::ParseError> {
+        match name {
+            "file" => parser.repeated_message(&mut self.file)?,
+            _ => return ::core::result::Result::Ok(false),
+        }
+        ::core::result::Result::Ok(true)
+    }
+}
+#[allow(deprecated)]
+impl ::prost::json::Json for FileDescriptorSet {
+    fn write_json_fields(
+        &self,
+        writer: &mut ::prost::json::Writer<'_>,
+    ) -> ::core::result::Result<(), ::prost::json::JsonError> {
+        if writer.emit_defaults() || !self.file.is_empty() {
+            writer
+                .field(
+                    "file",
+                    "file",
+                    |writer| {
+                        writer.list(&self.file, |writer, value| writer.message(value))
+                    },
+                )?;
+        }
+        ::core::result::Result::Ok(())
+    }
+    fn merge_json_field(
+        &mut self,
+        name: &str,
+        parser: &mut ::prost::json::Parser<'_>,
+    ) -> ::core::result::Result<bool, ::prost::json::JsonError> {
+        match name {
+            "file" => parser.repeated(&mut self.file, |parser| parser.message())?,
+            _ => return ::core::result::Result::Ok(false),
+        }
+        ::core::result::Result::Ok(true)
+    }
+}
+#[allow(deprecated)]
+impl ::prost::FieldMasked for FileDescriptorSet {
+    fn check_mask_tree(
+        mask: &::prost::field_mask::FieldMaskTree,
+    ) -> ::core::result::Result<(), ::prost::field_mask::FieldMaskError> {
+        for (name, mask) in mask.iter() {
+            match name {
+                "file" => ::prost::field_mask::check_leaf("file", mask)?,
+                _ => {
+                    return ::core::result::Result::Err(
+                        ::prost::field_mask::unknown_field(name),
+                    );
+                }
+            }
+        }
+        ::core::result::Result::Ok(())
+    }
+    fn merge_with_mask_tree(
+        &mut self,
+        src: &Self,
+        mask: &::prost::field_mask::FieldMaskTree,
+        options: &::prost::field_mask::MergeOptions,
+    ) {
+        if mask.get("file").is_some() {
+            ::prost::field_mask::merge_repeated(&mut self.file, &src.file, options);
+        }
+    }
+    fn prune_with_mask_tree(&mut self, mask: &::prost::field_mask::FieldMaskTree) {
+        let default = Self::default();
+        if mask.get("file").is_none() {
+            self.file = default.file;
+        }
+    }
+}
 /// Describes a complete .proto file.
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -49,6 +132,408 @@ pub struct FileDescriptorProto {
     #[prost(string, optional, tag = "12")]
     pub syntax: ::core::option::Option<::prost::alloc::string::String>,
 }
+#[allow(deprecated)]
+impl ::prost::text_format::TextFormat for FileDescriptorProto {
+    fn print_fields(&self, printer: &mut ::prost::text_format::Printer<'_>) {
+        if let ::core::option::Option::Some(value) = &self.name {
+            printer.scalar("name", value);
+        }
+        if let ::core::option::Option::Some(value) = &self.package {
+            printer.scalar("package", value);
+        }
+        for value in &self.dependency {
+            printer.scalar("dependency", value);
+        }
+        for value in &self.message_type {
+            printer.message("message_type", value);
+        }
+        for value in &self.enum_type {
+            printer.message("enum_type", value);
+        }
+        for value in &self.service {
+            printer.message("service", value);
+        }
+        for value in &self.extension {
+            printer.message("extension", value);
+        }
+        if let ::core::option::Option::Some(value) = &self.options {
+            printer.message("options", value);
+        }
+        if let ::core::option::Option::Some(value) = &self.source_code_info {
+            printer.message("source_code_info", value);
+        }
+        for value in &self.public_dependency {
+            printer.scalar("public_dependency", value);
+        }
+        for value in &self.weak_dependency {
+            printer.scalar("weak_dependency", value);
+        }
+        if let ::core::option::Option::Some(value) = &self.syntax {
+            printer.scalar("syntax", value);
+        }
+    }
+    fn merge_text_field(
+        &mut self,
+        name: &str,
+        parser: &mut ::prost::text_format::Parser<'_>,
+    ) -> ::core::result::Result<bool, ::prost::text_format::ParseError> {
+        match name {
+            "name" => {
+                parser
+                    .scalar(
+                        self.name.get_or_insert_with(::core::default::Default::default),
+                    )?
+            }
+            "package" => {
+                parser
+                    .scalar(
+                        self
+                            .package
+                            .get_or_insert_with(::core::default::Default::default),
+                    )?
+            }
+            "dependency" => parser.repeated_scalar(&mut self.dependency)?,
+            "public_dependency" => parser.repeated_scalar(&mut self.public_dependency)?,
+            "weak_dependency" => parser.repeated_scalar(&mut self.weak_dependency)?,
+            "message_type" => parser.repeated_message(&mut self.message_type)?,
+            "enum_type" => parser.repeated_message(&mut self.enum_type)?,
+            "service" => parser.repeated_message(&mut self.service)?,
+            "extension" => parser.repeated_message(&mut self.extension)?,
+            "options" => {
+                parser
+                    .message(
+                        self
+                            .options
+                            .get_or_insert_with(::core::default::Default::default),
+                    )?
+            }
+            "source_code_info" => {
+                parser
+                    .message(
+                        self
+                            .source_code_info
+                            .get_or_insert_with(::core::default::Default::default),
+                    )?
+            }
+            "syntax" => {
+                parser
+                    .scalar(
+                        self.syntax.get_or_insert_with(::core::default::Default::default),
+                    )?
+            }
+            _ => return ::core::result::Result::Ok(false),
+        }
+        ::core::result::Result::Ok(true)
+    }
+}
+#[allow(deprecated)]
+impl ::prost::json::Json for FileDescriptorProto {
+    fn write_json_fields(
+        &self,
+        writer: &mut ::prost::json::Writer<'_>,
+    ) -> ::core::result::Result<(), ::prost::json::JsonError> {
+        if let ::core::option::Option::Some(value) = &self.name {
+            writer.field("name", "name", |writer| writer.scalar(value))?;
+        }
+        if let ::core::option::Option::Some(value) = &self.package {
+            writer.field("package", "package", |writer| writer.scalar(value))?;
+        }
+        if writer.emit_defaults() || !self.dependency.is_empty() {
+            writer
+                .field(
+                    "dependency",
+                    "dependency",
+                    |writer| {
+                        writer
+                            .list(&self.dependency, |writer, value| writer.scalar(value))
+                    },
+                )?;
+        }
+        if writer.emit_defaults() || !self.message_type.is_empty() {
+            writer
+                .field(
+                    "messageType",
+                    "message_type",
+                    |writer| {
+                        writer
+                            .list(
+                                &self.message_type,
+                                |writer, value| writer.message(value),
+                            )
+                    },
+                )?;
+        }
+        if writer.emit_defaults() || !self.enum_type.is_empty() {
+            writer
+                .field(
+                    "enumType",
+                    "enum_type",
+                    |writer| {
+                        writer
+                            .list(&self.enum_type, |writer, value| writer.message(value))
+                    },
+                )?;
+        }
+        if writer.emit_defaults() || !self.service.is_empty() {
+            writer
+                .field(
+                    "service",
+                    "service",
+                    |writer| {
+                        writer.list(&self.service, |writer, value| writer.message(value))
+                    },
+                )?;
+        }
+        if writer.emit_defaults() || !self.extension.is_empty() {
+            writer
+                .field(
+                    "extension",
+                    "extension",
+                    |writer| {
+                        writer
+                            .list(&self.extension, |writer, value| writer.message(value))
+                    },
+                )?;
+        }
+        if let ::core::option::Option::Some(value) = &self.options {
+            writer.field("options", "options", |writer| writer.message(value))?;
+        }
+        if let ::core::option::Option::Some(value) = &self.source_code_info {
+            writer
+                .field(
+                    "sourceCodeInfo",
+                    "source_code_info",
+                    |writer| writer.message(value),
+                )?;
+        }
+        if writer.emit_defaults() || !self.public_dependency.is_empty() {
+            writer
+                .field(
+                    "publicDependency",
+                    "public_dependency",
+                    |writer| {
+                        writer
+                            .list(
+                                &self.public_dependency,
+                                |writer, value| writer.scalar(value),
+                            )
+                    },
+                )?;
+        }
+        if writer.emit_defaults() || !self.weak_dependency.is_empty() {
+            writer
+                .field(
+                    "weakDependency",
+                    "weak_dependency",
+                    |writer| {
+                        writer
+                            .list(
+                                &self.weak_dependency,
+                                |writer, value| writer.scalar(value),
+                            )
+                    },
+                )?;
+        }
+        if let ::core::option::Option::Some(value) = &self.syntax {
+            writer.field("syntax", "syntax", |writer| writer.scalar(value))?;
+        }
+        ::core::result::Result::Ok(())
+    }
+    fn merge_json_field(
+        &mut self,
+        name: &str,
+        parser: &mut ::prost::json::Parser<'_>,
+    ) -> ::core::result::Result<bool, ::prost::json::JsonError> {
+        match name {
+            "name" => parser.optional(&mut self.name, |parser| parser.scalar())?,
+            "package" => parser.optional(&mut self.package, |parser| parser.scalar())?,
+            "dependency" => {
+                parser.repeated(&mut self.dependency, |parser| parser.scalar())?
+            }
+            "publicDependency" | "public_dependency" => {
+                parser.repeated(&mut self.public_dependency, |parser| parser.scalar())?
+            }
+            "weakDependency" | "weak_dependency" => {
+                parser.repeated(&mut self.weak_dependency, |parser| parser.scalar())?
+            }
+            "messageType" | "message_type" => {
+                parser.repeated(&mut self.message_type, |parser| parser.message())?
+            }
+            "enumType" | "enum_type" => {
+                parser.repeated(&mut self.enum_type, |parser| parser.message())?
+            }
+            "service" => parser.repeated(&mut self.service, |parser| parser.message())?,
+            "extension" => {
+                parser.repeated(&mut self.extension, |parser| parser.message())?
+            }
+            "options" => parser.optional(&mut self.options, |parser| parser.message())?,
+            "sourceCodeInfo" | "source_code_info" => {
+                parser.optional(&mut self.source_code_info, |parser| parser.message())?
+            }
+            "syntax" => parser.optional(&mut self.syntax, |parser| parser.scalar())?,
+            _ => return ::core::result::Result::Ok(false),
+        }
+        ::core::result::Result::Ok(true)
+    }
+}
+#[allow(deprecated)]
+impl ::prost::FieldMasked for FileDescriptorProto {
+    fn check_mask_tree(
+        mask: &::prost::field_mask::FieldMaskTree,
+    ) -> ::core::result::Result<(), ::prost::field_mask::FieldMaskError> {
+        for (name, mask) in mask.iter() {
+            match name {
+                "name" => ::prost::field_mask::check_leaf("name", mask)?,
+                "package" => ::prost::field_mask::check_leaf("package", mask)?,
+                "dependency" => ::prost::field_mask::check_leaf("dependency", mask)?,
+                "public_dependency" => {
+                    ::prost::field_mask::check_leaf("public_dependency", mask)?
+                }
+                "weak_dependency" => {
+                    ::prost::field_mask::check_leaf("weak_dependency", mask)?
+                }
+                "message_type" => ::prost::field_mask::check_leaf("message_type", mask)?,
+                "enum_type" => ::prost::field_mask::check_leaf("enum_type", mask)?,
+                "service" => ::prost::field_mask::check_leaf("service", mask)?,
+                "extension" => ::prost::field_mask::check_leaf("extension", mask)?,
+                "options" => {
+                    ::prost::field_mask::check_message::<FileOptions>("options", mask)?
+                }
+                "source_code_info" => {
+                    ::prost::field_mask::check_message::<
+                        SourceCodeInfo,
+                    >("source_code_info", mask)?
+                }
+                "syntax" => ::prost::field_mask::check_leaf("syntax", mask)?,
+                _ => {
+                    return ::core::result::Result::Err(
+                        ::prost::field_mask::unknown_field(name),
+                    );
+                }
+            }
+        }
+        ::core::result::Result::Ok(())
+    }
+    fn merge_with_mask_tree(
+        &mut self,
+        src: &Self,
+        mask: &::prost::field_mask::FieldMaskTree,
+        options: &::prost::field_mask::MergeOptions,
+    ) {
+        if mask.get("name").is_some() {
+            self.name.clone_from(&src.name);
+        }
+        if mask.get("package").is_some() {
+            self.package.clone_from(&src.package);
+        }
+        if mask.get("dependency").is_some() {
+            ::prost::field_mask::merge_repeated(
+                &mut self.dependency,
+                &src.dependency,
+                options,
+            );
+        }
+        if mask.get("public_dependency").is_some() {
+            ::prost::field_mask::merge_repeated(
+                &mut self.public_dependency,
+                &src.public_dependency,
+                options,
+            );
+        }
+        if mask.get("weak_dependency").is_some() {
+            ::prost::field_mask::merge_repeated(
+                &mut self.weak_dependency,
+                &src.weak_dependency,
+                options,
+            );
+        }
+        if mask.get("message_type").is_some() {
+            ::prost::field_mask::merge_repeated(
+                &mut self.message_type,
+                &src.message_type,
+                options,
+            );
+        }
+        if mask.get("enum_type").is_some() {
+            ::prost::field_mask::merge_repeated(
+                &mut self.enum_type,
+                &src.enum_type,
+                options,
+            );
+        }
+        if mask.get("service").is_some() {
+            ::prost::field_mask::merge_repeated(
+                &mut self.service,
+                &src.service,
+                options,
+            );
+        }
+        if mask.get("extension").is_some() {
+            ::prost::field_mask::merge_repeated(
+                &mut self.extension,
+                &src.extension,
+                options,
+            );
+        }
+        if let ::core::option::Option::Some(mask) = mask.get("options") {
+            ::prost::field_mask::merge_message(
+                &mut self.options,
+                src.options.as_ref(),
+                mask,
+                options,
+            );
+        }
+        if let ::core::option::Option::Some(mask) = mask.get("source_code_info") {
+            ::prost::field_mask::merge_message(
+                &mut self.source_code_info,
+                src.source_code_info.as_ref(),
+                mask,
+                options,
+            );
+        }
+        if mask.get("syntax").is_some() {
+            self.syntax.clone_from(&src.syntax);
+        }
+    }
+    fn prune_with_mask_tree(&mut self, mask: &::prost::field_mask::FieldMaskTree) {
+        let default = Self::default();
+        if mask.get("name").is_none() {
+            self.name = default.name;
+        }
+        if mask.get("package").is_none() {
+            self.package = default.package;
+        }
+        if mask.get("dependency").is_none() {
+            self.dependency = default.dependency;
+        }
+        if mask.get("public_dependency").is_none() {
+            self.public_dependency = default.public_dependency;
+        }
+        if mask.get("weak_dependency").is_none() {
+            self.weak_dependency = default.weak_dependency;
+        }
+        if mask.get("message_type").is_none() {
+            self.message_type = default.message_type;
+        }
+        if mask.get("enum_type").is_none() {
+            self.enum_type = default.enum_type;
+        }
+        if mask.get("service").is_none() {
+            self.service = default.service;
+        }
+        if mask.get("extension").is_none() {
+            self.extension = default.extension;
+        }
+        ::prost::field_mask::prune_message(&mut self.options, mask.get("options"));
+        ::prost::field_mask::prune_message(
+            &mut self.source_code_info,
+            mask.get("source_code_info"),
+        );
+        if mask.get("syntax").is_none() {
+            self.syntax = default.syntax;
+        }
+    }
+}
 /// Describes a message type.
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -76,6 +561,362 @@ pub struct DescriptorProto {
     #[prost(string, repeated, tag = "10")]
     pub reserved_name: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
 }
+#[allow(deprecated)]
+impl ::prost::text_format::TextFormat for DescriptorProto {
+    fn print_fields(&self, printer: &mut ::prost::text_format::Printer<'_>) {
+        if let ::core::option::Option::Some(value) = &self.name {
+            printer.scalar("name", value);
+        }
+        for value in &self.field {
+            printer.message("field", value);
+        }
+        for value in &self.nested_type {
+            printer.message("nested_type", value);
+        }
+        for value in &self.enum_type {
+            printer.message("enum_type", value);
+        }
+        for value in &self.extension_range {
+            printer.message("extension_range", value);
+        }
+        for value in &self.extension {
+            printer.message("extension", value);
+        }
+        if let ::core::option::Option::Some(value) = &self.options {
+            printer.message("options", value);
+        }
+        for value in &self.oneof_decl {
+            printer.message("oneof_decl", value);
+        }
+        for value in &self.reserved_range {
+            printer.message("reserved_range", value);
+        }
+        for value in &self.reserved_name {
+            printer.scalar("reserved_name", value);
+        }
+    }
+    fn merge_text_field(
+        &mut self,
+        name: &str,
+        parser: &mut ::prost::text_format::Parser<'_>,
+    ) -> ::core::result::Result<bool, ::prost::text_format::ParseError> {
+        match name {
+            "name" => {
+                parser
+                    .scalar(
+                        self.name.get_or_insert_with(::core::default::Default::default),
+                    )?
+            }
+            "field" => parser.repeated_message(&mut self.field)?,
+            "extension" => parser.repeated_message(&mut self.extension)?,
+            "nested_type" => parser.repeated_message(&mut self.nested_type)?,
+            "enum_type" => parser.repeated_message(&mut self.enum_type)?,
+            "extension_range" => parser.repeated_message(&mut self.extension_range)?,
+            "oneof_decl" => parser.repeated_message(&mut self.oneof_decl)?,
+            "options" => {
+                parser
+                    .message(
+                        self
+                            .options
+                            .get_or_insert_with(::core::default::Default::default),
+                    )?
+            }
+            "reserved_range" => parser.repeated_message(&mut self.reserved_range)?,
+            "reserved_name" => parser.repeated_scalar(&mut self.reserved_name)?,
+            _ => return ::core::result::Result::Ok(false),
+        }
+        ::core::result::Result::Ok(true)
+    }
+}
+#[allow(deprecated)]
+impl ::prost::json::Json for DescriptorProto {
+    fn write_json_fields(
+        &self,
+        writer: &mut ::prost::json::Writer<'_>,
+    ) -> ::core::result::Result<(), ::prost::json::JsonError> {
+        if let ::core::option::Option::Some(value) = &self.name {
+            writer.field("name", "name", |writer| writer.scalar(value))?;
+        }
+        if writer.emit_defaults() || !self.field.is_empty() {
+            writer
+                .field(
+                    "field",
+                    "field",
+                    |writer| {
+                        writer.list(&self.field, |writer, value| writer.message(value))
+                    },
+                )?;
+        }
+        if writer.emit_defaults() || !self.nested_type.is_empty() {
+            writer
+                .field(
+                    "nestedType",
+                    "nested_type",
+                    |writer| {
+                        writer
+                            .list(
+                                &self.nested_type,
+                                |writer, value| writer.message(value),
+                            )
+                    },
+                )?;
+        }
+        if writer.emit_defaults() || !self.enum_type.is_empty() {
+            writer
+                .field(
+                    "enumType",
+                    "enum_type",
+                    |writer| {
+                        writer
+                            .list(&self.enum_type, |writer, value| writer.message(value))
+                    },
+                )?;
+        }
+        if writer.emit_defaults() || !self.extension_range.is_empty() {
+            writer
+                .field(
+                    "extensionRange",
+                    "extension_range",
+                    |writer| {
+                        writer
+                            .list(
+                                &self.extension_range,
+                                |writer, value| writer.message(value),
+                            )
+                    },
+                )?;
+        }
+        if writer.emit_defaults() || !self.extension.is_empty() {
+            writer
+                .field(
+                    "extension",
+                    "extension",
+                    |writer| {
+                        writer
+                            .list(&self.extension, |writer, value| writer.message(value))
+                    },
+                )?;
+        }
+        if let ::core::option::Option::Some(value) = &self.options {
+            writer.field("options", "options", |writer| writer.message(value))?;
+        }
+        if writer.emit_defaults() || !self.oneof_decl.is_empty() {
+            writer
+                .field(
+                    "oneofDecl",
+                    "oneof_decl",
+                    |writer| {
+                        writer
+                            .list(
+                                &self.oneof_decl,
+                                |writer, value| writer.message(value),
+                            )
+                    },
+                )?;
+        }
+        if writer.emit_defaults() || !self.reserved_range.is_empty() {
+            writer
+                .field(
+                    "reservedRange",
+                    "reserved_range",
+                    |writer| {
+                        writer
+                            .list(
+                                &self.reserved_range,
+                                |writer, value| writer.message(value),
+                            )
+                    },
+                )?;
+        }
+        if writer.emit_defaults() || !self.reserved_name.is_empty() {
+            writer
+                .field(
+                    "reservedName",
+                    "reserved_name",
+                    |writer| {
+                        writer
+                            .list(
+                                &self.reserved_name,
+                                |writer, value| writer.scalar(value),
+                            )
+                    },
+                )?;
+        }
+        ::core::result::Result::Ok(())
+    }
+    fn merge_json_field(
+        &mut self,
+        name: &str,
+        parser: &mut ::prost::json::Parser<'_>,
+    ) -> ::core::result::Result<bool, ::prost::json::JsonError> {
+        match name {
+            "name" => parser.optional(&mut self.name, |parser| parser.scalar())?,
+            "field" => parser.repeated(&mut self.field, |parser| parser.message())?,
+            "extension" => {
+                parser.repeated(&mut self.extension, |parser| parser.message())?
+            }
+            "nestedType" | "nested_type" => {
+                parser.repeated(&mut self.nested_type, |parser| parser.message())?
+            }
+            "enumType" | "enum_type" => {
+                parser.repeated(&mut self.enum_type, |parser| parser.message())?
+            }
+            "extensionRange" | "extension_range" => {
+                parser.repeated(&mut self.extension_range, |parser| parser.message())?
+            }
+            "oneofDecl" | "oneof_decl" => {
+                parser.repeated(&mut self.oneof_decl, |parser| parser.message())?
+            }
+            "options" => parser.optional(&mut self.options, |parser| parser.message())?,
+            "reservedRange" | "reserved_range" => {
+                parser.repeated(&mut self.reserved_range, |parser| parser.message())?
+            }
+            "reservedName" | "reserved_name" => {
+                parser.repeated(&mut self.reserved_name, |parser| parser.scalar())?
+            }
+            _ => return ::core::result::Result::Ok(false),
+        }
+        ::core::result::Result::Ok(true)
+    }
+}
+#[allow(deprecated)]
+impl ::prost::FieldMasked for DescriptorProto {
+    fn check_mask_tree(
+        mask: &::prost::field_mask::FieldMaskTree,
+    ) -> ::core::result::Result<(), ::prost::field_mask::FieldMaskError> {
+        for (name, mask) in mask.iter() {
+            match name {
+                "name" => ::prost::field_mask::check_leaf("name", mask)?,
+                "field" => ::prost::field_mask::check_leaf("field", mask)?,
+                "extension" => ::prost::field_mask::check_leaf("extension", mask)?,
+                "nested_type" => ::prost::field_mask::check_leaf("nested_type", mask)?,
+                "enum_type" => ::prost::field_mask::check_leaf("enum_type", mask)?,
+                "extension_range" => {
+                    ::prost::field_mask::check_leaf("extension_range", mask)?
+                }
+                "oneof_decl" => ::prost::field_mask::check_leaf("oneof_decl", mask)?,
+                "options" => {
+                    ::prost::field_mask::check_message::<
+                        MessageOptions,
+                    >("options", mask)?
+                }
+                "reserved_range" => {
+                    ::prost::field_mask::check_leaf("reserved_range", mask)?
+                }
+                "reserved_name" => {
+                    ::prost::field_mask::check_leaf("reserved_name", mask)?
+                }
+                _ => {
+                    return ::core::result::Result::Err(
+                        ::prost::field_mask::unknown_field(name),
+                    );
+                }
+            }
+        }
+        ::core::result::Result::Ok(())
+    }
+    fn merge_with_mask_tree(
+        &mut self,
+        src: &Self,
+        mask: &::prost::field_mask::FieldMaskTree,
+        options: &::prost::field_mask::MergeOptions,
+    ) {
+        if mask.get("name").is_some() {
+            self.name.clone_from(&src.name);
+        }
+        if mask.get("field").is_some() {
+            ::prost::field_mask::merge_repeated(&mut self.field, &src.field, options);
+        }
+        if mask.get("extension").is_some() {
+            ::prost::field_mask::merge_repeated(
+                &mut self.extension,
+                &src.extension,
+                options,
+            );
+        }
+        if mask.get("nested_type").is_some() {
+            ::prost::field_mask::merge_repeated(
+                &mut self.nested_type,
+                &src.nested_type,
+                options,
+            );
+        }
+        if mask.get("enum_type").is_some() {
+            ::prost::field_mask::merge_repeated(
+                &mut self.enum_type,
+                &src.enum_type,
+                options,
+            );
+        }
+        if mask.get("extension_range").is_some() {
+            ::prost::field_mask::merge_repeated(
+                &mut self.extension_range,
+                &src.extension_range,
+                options,
+            );
+        }
+        if mask.get("oneof_decl").is_some() {
+            ::prost::field_mask::merge_repeated(
+                &mut self.oneof_decl,
+                &src.oneof_decl,
+                options,
+            );
+        }
+        if let ::core::option::Option::Some(mask) = mask.get("options") {
+            ::prost::field_mask::merge_message(
+                &mut self.options,
+                src.options.as_ref(),
+                mask,
+                options,
+            );
+        }
+        if mask.get("reserved_range").is_some() {
+            ::prost::field_mask::merge_repeated(
+                &mut self.reserved_range,
+                &src.reserved_range,
+                options,
+            );
+        }
+        if mask.get("reserved_name").is_some() {
+            ::prost::field_mask::merge_repeated(
+                &mut self.reserved_name,
+                &src.reserved_name,
+                options,
+            );
+        }
+    }
+    fn prune_with_mask_tree(&mut self, mask: &::prost::field_mask::FieldMaskTree) {
+        let default = Self::default();
+        if mask.get("name").is_none() {
+            self.name = default.name;
+        }
+        if mask.get("field").is_none() {
+            self.field = default.field;
+        }
+        if mask.get("extension").is_none() {
+            self.extension = default.extension;
+        }
+        if mask.get("nested_type").is_none() {
+            self.nested_type = default.nested_type;
+        }
+        if mask.get("enum_type").is_none() {
+            self.enum_type = default.enum_type;
+        }
+        if mask.get("extension_range").is_none() {
+            self.extension_range = default.extension_range;
+        }
+        if mask.get("oneof_decl").is_none() {
+            self.oneof_decl = default.oneof_decl;
+        }
+        ::prost::field_mask::prune_message(&mut self.options, mask.get("options"));
+        if mask.get("reserved_range").is_none() {
+            self.reserved_range = default.reserved_range;
+        }
+        if mask.get("reserved_name").is_none() {
+            self.reserved_name = default.reserved_name;
+        }
+    }
+}
 /// Nested message and enum types in `DescriptorProto`.
 pub mod descriptor_proto {
     #[allow(clippy::derive_partial_eq_without_eq)]
@@ -90,6 +931,142 @@ pub mod descriptor_proto {
         #[prost(message, optional, tag = "3")]
         pub options: ::core::option::Option<super::ExtensionRangeOptions>,
     }
+    #[allow(deprecated)]
+    impl ::prost::text_format::TextFormat for ExtensionRange {
+        fn print_fields(&self, printer: &mut ::prost::text_format::Printer<'_>) {
+            if let ::core::option::Option::Some(value) = &self.start {
+                printer.scalar("start", value);
+            }
+            if let ::core::option::Option::Some(value) = &self.end {
+                printer.scalar("end", value);
+            }
+            if let ::core::option::Option::Some(value) = &self.options {
+                printer.message("options", value);
+            }
+        }
+        fn merge_text_field(
+            &mut self,
+            name: &str,
+            parser: &mut ::prost::text_format::Parser<'_>,
+        ) -> ::core::result::Result<bool, ::prost::text_format::ParseError> {
+            match name {
+                "start" => {
+                    parser
+                        .scalar(
+                            self
+                                .start
+                                .get_or_insert_with(::core::default::Default::default),
+                        )?
+                }
+                "end" => {
+                    parser
+                        .scalar(
+                            self
+                                .end
+                                .get_or_insert_with(::core::default::Default::default),
+                        )?
+                }
+                "options" => {
+                    parser
+                        .message(
+                            self
+                                .options
+                                .get_or_insert_with(::core::default::Default::default),
+                        )?
+                }
+                _ => return ::core::result::Result::Ok(false),
+            }
+            ::core::result::Result::Ok(true)
+        }
+    }
+    #[allow(deprecated)]
+    impl ::prost::json::Json for ExtensionRange {
+        fn write_json_fields(
+            &self,
+            writer: &mut ::prost::json::Writer<'_>,
+        ) -> ::core::result::Result<(), ::prost::json::JsonError> {
+            if let ::core::option::Option::Some(value) = &self.start {
+                writer.field("start", "start", |writer| writer.scalar(value))?;
+            }
+            if let ::core::option::Option::Some(value) = &self.end {
+                writer.field("end", "end", |writer| writer.scalar(value))?;
+            }
+            if let ::core::option::Option::Some(value) = &self.options {
+                writer.field("options", "options", |writer| writer.message(value))?;
+            }
+            ::core::result::Result::Ok(())
+        }
+        fn merge_json_field(
+            &mut self,
+            name: &str,
+            parser: &mut ::prost::json::Parser<'_>,
+        ) -> ::core::result::Result<bool, ::prost::json::JsonError> {
+            match name {
+                "start" => parser.optional(&mut self.start, |parser| parser.scalar())?,
+                "end" => parser.optional(&mut self.end, |parser| parser.scalar())?,
+                "options" => {
+                    parser.optional(&mut self.options, |parser| parser.message())?
+                }
+                _ => return ::core::result::Result::Ok(false),
+            }
+            ::core::result::Result::Ok(true)
+        }
+    }
+    #[allow(deprecated)]
+    impl ::prost::FieldMasked for ExtensionRange {
+        fn check_mask_tree(
+            mask: &::prost::field_mask::FieldMaskTree,
+        ) -> ::core::result::Result<(), ::prost::field_mask::FieldMaskError> {
+            for (name, mask) in mask.iter() {
+                match name {
+                    "start" => ::prost::field_mask::check_leaf("start", mask)?,
+                    "end" => ::prost::field_mask::check_leaf("end", mask)?,
+                    "options" => {
+                        ::prost::field_mask::check_message::<
+                            super::ExtensionRangeOptions,
+                        >("options", mask)?
+                    }
+                    _ => {
+                        return ::core::result::Result::Err(
+                            ::prost::field_mask::unknown_field(name),
+                        );
+                    }
+                }
+            }
+            ::core::result::Result::Ok(())
+        }
+        fn merge_with_mask_tree(
+            &mut self,
+            src: &Self,
+            mask: &::prost::field_mask::FieldMaskTree,
+            options: &::prost::field_mask::MergeOptions,
+        ) {
+            if mask.get("start").is_some() {
+                self.start.clone_from(&src.start);
+            }
+            if mask.get("end").is_some() {
+                self.end.clone_from(&src.end);
+            }
+            if let ::core::option::Option::Some(mask) = mask.get("options") {
+                ::prost::field_mask::merge_message(
+                    &mut self.options,
+                    src.options.as_ref(),
+                    mask,
+                    options,
+                );
+            }
+        }
+        fn prune_with_mask_tree(&mut self, mask: &::prost::field_mask::FieldMaskTree) {
+            let default = Self::default();
+            if mask.get("start").is_none() {
+                self.start = default.start;
+            }
+            if mask.get("end").is_none() {
+                self.end = default.end;
+            }
+            ::prost::field_mask::prune_message(&mut self.options, mask.get("options"));
+        }
+    }
     /// Range of reserved tag numbers. Reserved tag numbers may not be used by
     /// fields or extension ranges in the same message. Reserved ranges may
     /// not overlap.
@@ -103,6 +1080,111 @@ pub mod descriptor_proto {
         #[prost(int32, optional, tag = "2")]
         pub end: ::core::option::Option<i32>,
     }
+    #[allow(deprecated)]
+    impl ::prost::text_format::TextFormat for ReservedRange {
+        fn print_fields(&self, printer: &mut ::prost::text_format::Printer<'_>) {
+            if let ::core::option::Option::Some(value) = &self.start {
+                printer.scalar("start", value);
+            }
+            if let ::core::option::Option::Some(value) = &self.end {
+                printer.scalar("end", value);
+            }
+        }
+        fn merge_text_field(
+            &mut self,
+            name: &str,
+            parser: &mut ::prost::text_format::Parser<'_>,
+        ) -> ::core::result::Result<bool, ::prost::text_format::ParseError> {
+            match name {
+                "start" => {
+                    parser
+                        .scalar(
+                            self
+                                .start
+                                .get_or_insert_with(::core::default::Default::default),
+                        )?
+                }
+                "end" => {
+                    parser
+                        .scalar(
+                            self
+                                .end
+                                .get_or_insert_with(::core::default::Default::default),
+                        )?
+                }
+                _ => return ::core::result::Result::Ok(false),
+            }
+            ::core::result::Result::Ok(true)
+        }
+    }
+    #[allow(deprecated)]
+    impl ::prost::json::Json for ReservedRange {
+        fn write_json_fields(
+            &self,
+            writer: &mut ::prost::json::Writer<'_>,
+        ) -> ::core::result::Result<(), ::prost::json::JsonError> {
+            if let ::core::option::Option::Some(value) = &self.start {
+                writer.field("start", "start", |writer| writer.scalar(value))?;
+            }
+            if let ::core::option::Option::Some(value) = &self.end {
+                writer.field("end", "end", |writer| writer.scalar(value))?;
+            }
+            ::core::result::Result::Ok(())
+        }
+        fn merge_json_field(
+            &mut self,
+            name: &str,
+            parser: &mut ::prost::json::Parser<'_>,
+        ) -> ::core::result::Result<bool, ::prost::json::JsonError> {
+            match name {
+                "start" => parser.optional(&mut self.start, |parser| parser.scalar())?,
+                "end" => parser.optional(&mut self.end, |parser| parser.scalar())?,
+                _ => return ::core::result::Result::Ok(false),
+            }
+            ::core::result::Result::Ok(true)
+        }
+    }
+    #[allow(deprecated)]
+    impl ::prost::FieldMasked for ReservedRange {
+        fn check_mask_tree(
+            mask: &::prost::field_mask::FieldMaskTree,
+        ) -> ::core::result::Result<(), ::prost::field_mask::FieldMaskError> {
+            for (name, mask) in mask.iter() {
+                match name {
+                    "start" => ::prost::field_mask::check_leaf("start", mask)?,
+                    "end" => ::prost::field_mask::check_leaf("end", mask)?,
+                    _ => {
+                        return ::core::result::Result::Err(
+                            ::prost::field_mask::unknown_field(name),
+                        );
+                    }
+                }
+            }
+            ::core::result::Result::Ok(())
+        }
+        fn merge_with_mask_tree(
+            &mut self,
+            src: &Self,
+            mask: &::prost::field_mask::FieldMaskTree,
+            _options: &::prost::field_mask::MergeOptions,
+        ) {
+            if mask.get("start").is_some() {
+                self.start.clone_from(&src.start);
+            }
+            if mask.get("end").is_some() {
+                self.end.clone_from(&src.end);
+            }
+        }
+        fn prune_with_mask_tree(&mut self, mask: &::prost::field_mask::FieldMaskTree) {
+            let default = Self::default();
+            if mask.get("start").is_none() {
+                self.start = default.start;
+            }
+            if mask.get("end").is_none() {
+                self.end = default.end;
+            }
+        }
+    }
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -111,6 +1193,104 @@ pub struct ExtensionRangeOptions {
     #[prost(message, repeated, tag = "999")]
     pub uninterpreted_option: ::prost::alloc::vec::Vec<UninterpretedOption>,
 }
+#[allow(deprecated)]
+impl ::prost::text_format::TextFormat for ExtensionRangeOptions {
+    fn print_fields(&self, printer: &mut ::prost::text_format::Printer<'_>) {
+        for value in &self.uninterpreted_option {
+            printer.message("uninterpreted_option", value);
+        }
+    }
+    fn merge_text_field(
+        &mut self,
+        name: &str,
+        parser: &mut ::prost::text_format::Parser<'_>,
+    ) -> ::core::result::Result<bool, ::prost::text_format::ParseError> {
+        match name {
+            "uninterpreted_option" => {
+                parser.repeated_message(&mut self.uninterpreted_option)?
+            }
+            _ => return ::core::result::Result::Ok(false),
+        }
+        ::core::result::Result::Ok(true)
+    }
+}
+#[allow(deprecated)]
+impl ::prost::json::Json for ExtensionRangeOptions {
+    fn write_json_fields(
+        &self,
+        writer: &mut ::prost::json::Writer<'_>,
+    ) -> ::core::result::Result<(), ::prost::json::JsonError> {
+        if writer.emit_defaults() || !self.uninterpreted_option.is_empty() {
+            writer
+                .field(
+                    "uninterpretedOption",
+                    "uninterpreted_option",
+                    |writer| {
+                        writer
+                            .list(
+                                &self.uninterpreted_option,
+                                |writer, value| writer.message(value),
+                            )
+                    },
+                )?;
+        }
+        ::core::result::Result::Ok(())
+    }
+    fn merge_json_field(
+        &mut self,
+        name: &str,
+        parser: &mut ::prost::json::Parser<'_>,
+    ) -> ::core::result::Result<bool, ::prost::json::JsonError> {
+        match name {
+            "uninterpretedOption" | "uninterpreted_option" => {
+                parser
+                    .repeated(&mut self.uninterpreted_option, |parser| parser.message())?
+            }
+            _ => return ::core::result::Result::Ok(false),
+        }
+        ::core::result::Result::Ok(true)
+    }
+}
+#[allow(deprecated)]
+impl ::prost::FieldMasked for ExtensionRangeOptions {
+    fn check_mask_tree(
+        mask: &::prost::field_mask::FieldMaskTree,
+    ) -> ::core::result::Result<(), ::prost::field_mask::FieldMaskError> {
+        for (name, mask) in mask.iter() {
+            match name {
+                "uninterpreted_option" => {
+                    ::prost::field_mask::check_leaf("uninterpreted_option", mask)?
+                }
+                _ => {
+                    return ::core::result::Result::Err(
+                        ::prost::field_mask::unknown_field(name),
+                    );
+                }
+            }
+        }
+        ::core::result::Result::Ok(())
+    }
+    fn merge_with_mask_tree(
+        &mut self,
+        src: &Self,
+        mask: &::prost::field_mask::FieldMaskTree,
+        options: &::prost::field_mask::MergeOptions,
+    ) {
+        if mask.get("uninterpreted_option").is_some() {
+            ::prost::field_mask::merge_repeated(
+                &mut self.uninterpreted_option,
+                &src.uninterpreted_option,
+                options,
+            );
+        }
+    }
+    fn prune_with_mask_tree(&mut self, mask: &::prost::field_mask::FieldMaskTree) {
+        let default = Self::default();
+        if mask.get("uninterpreted_option").is_none() {
+            self.uninterpreted_option = default.uninterpreted_option;
+        }
+    }
+}
 /// Describes a field within a message.
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -179,6 +1359,408 @@ pub struct FieldDescriptorProto {
     #[prost(bool, optional, tag = "17")]
     pub proto3_optional: ::core::option::Option<bool>,
 }
+#[allow(deprecated)]
+impl ::prost::text_format::TextFormat for FieldDescriptorProto {
+    fn print_fields(&self, printer: &mut ::prost::text_format::Printer<'_>) {
+        if let ::core::option::Option::Some(value) = &self.name {
+            printer.scalar("name", value);
+        }
+        if let ::core::option::Option::Some(value) = &self.extendee {
+            printer.scalar("extendee", value);
+        }
+        if let ::core::option::Option::Some(value) = &self.number {
+            printer.scalar("number", value);
+        }
+        if let ::core::option::Option::Some(value) = &self.label {
+            printer
+                .enumeration(
+                    "label",
+                    *value,
+                    |value| {
+                        field_descriptor_proto::Label::try_from(value)
+                            .ok()
+                            .map(|value| value.as_str_name())
+                    },
+                );
+        }
+        if let ::core::option::Option::Some(value) = &self.r#type {
+            printer
+                .enumeration(
+                    "type",
+                    *value,
+                    |value| {
+                        field_descriptor_proto::Type::try_from(value)
+                            .ok()
+                            .map(|value| value.as_str_name())
+                    },
+                );
+        }
+        if let ::core::option::Option::Some(value) = &self.type_name {
+            printer.scalar("type_name", value);
+        }
+        if let ::core::option::Option::Some(value) = &self.default_value {
+            printer.scalar("default_value", value);
+        }
+        if let ::core::option::Option::Some(value) = &self.options {
+            printer.message("options", value);
+        }
+        if let ::core::option::Option::Some(value) = &self.oneof_index {
+            printer.scalar("oneof_index", value);
+        }
+        if let ::core::option::Option::Some(value) = &self.json_name {
+            printer.scalar("json_name", value);
+        }
+        if let ::core::option::Option::Some(value) = &self.proto3_optional {
+            printer.scalar("proto3_optional", value);
+        }
+    }
+    fn merge_text_field(
+        &mut self,
+        name: &str,
+        parser: &mut ::prost::text_format::Parser<'_>,
+    ) -> ::core::result::Result<bool, ::prost::text_format::ParseError> {
+        match name {
+            "name" => {
+                parser
+                    .scalar(
+                        self.name.get_or_insert_with(::core::default::Default::default),
+                    )?
+            }
+            "number" => {
+                parser
+                    .scalar(
+                        self.number.get_or_insert_with(::core::default::Default::default),
+                    )?
+            }
+            "label" => {
+                parser
+                    .enumeration(
+                        self.label.get_or_insert_with(::core::default::Default::default),
+                        |name| {
+                            field_descriptor_proto::Label::from_str_name(name)
+                                .map(|value| value as i32)
+                        },
+                    )?
+            }
+            "type" => {
+                parser
+                    .enumeration(
+                        self
+                            .r#type
+                            .get_or_insert_with(::core::default::Default::default),
+                        |name| {
+                            field_descriptor_proto::Type::from_str_name(name)
+                                .map(|value| value as i32)
+                        },
+                    )?
+            }
+            "type_name" => {
+                parser
+                    .scalar(
+                        self
+                            .type_name
+                            .get_or_insert_with(::core::default::Default::default),
+                    )?
+            }
+            "extendee" => {
+                parser
+                    .scalar(
+                        self
+                            .extendee
+                            .get_or_insert_with(::core::default::Default::default),
+                    )?
+            }
+            "default_value" => {
+                parser
+                    .scalar(
+                        self
+                            .default_value
+                            .get_or_insert_with(::core::default::Default::default),
+                    )?
+            }
+            "oneof_index" => {
+                parser
+                    .scalar(
+                        self
+                            .oneof_index
+                            .get_or_insert_with(::core::default::Default::default),
+                    )?
+            }
+            "json_name" => {
+                parser
+                    .scalar(
+                        self
+                            .json_name
+                            .get_or_insert_with(::core::default::Default::default),
+                    )?
+            }
+            "options" => {
+                parser
+                    .message(
+                        self
+                            .options
+                            .get_or_insert_with(::core::default::Default::default),
+                    )?
+            }
+            "proto3_optional" => {
+                parser
+                    .scalar(
+                        self
+                            .proto3_optional
+                            .get_or_insert_with(::core::default::Default::default),
+                    )?
+            }
+            _ => return ::core::result::Result::Ok(false),
+        }
+        ::core::result::Result::Ok(true)
+    }
+}
+#[allow(deprecated)]
+impl ::prost::json::Json for FieldDescriptorProto {
+    fn write_json_fields(
+        &self,
+        writer: &mut ::prost::json::Writer<'_>,
+    ) -> ::core::result::Result<(), ::prost::json::JsonError> {
+        if let ::core::option::Option::Some(value) = &self.name {
+            writer.field("name", "name", |writer| writer.scalar(value))?;
+        }
+        if let ::core::option::Option::Some(value) = &self.extendee {
+            writer.field("extendee", "extendee", |writer| writer.scalar(value))?;
+        }
+        if let ::core::option::Option::Some(value) = &self.number {
+            writer.field("number", "number", |writer| writer.scalar(value))?;
+        }
+        if let ::core::option::Option::Some(value) = &self.label {
+            writer
+                .field(
+                    "label",
+                    "label",
+                    |writer| {
+                        writer
+                            .enumeration(
+                                *value,
+                                |value| {
+                                    field_descriptor_proto::Label::try_from(value)
+                                        .ok()
+                                        .map(|value| value.as_str_name())
+                                },
+                            )
+                    },
+                )?;
+        }
+        if let ::core::option::Option::Some(value) = &self.r#type {
+            writer
+                .field(
+                    "type",
+                    "type",
+                    |writer| {
+                        writer
+                            .enumeration(
+                                *value,
+                                |value| {
+                                    field_descriptor_proto::Type::try_from(value)
+                                        .ok()
+                                        .map(|value| value.as_str_name())
+                                },
+                            )
+                    },
+                )?;
+        }
+        if let ::core::option::Option::Some(value) = &self.type_name {
+            writer.field("typeName", "type_name", |writer| writer.scalar(value))?;
+        }
+        if let ::core::option::Option::Some(value) = &self.default_value {
+            writer
+                .field("defaultValue", "default_value", |writer| writer.scalar(value))?;
+        }
+        if let ::core::option::Option::Some(value) = &self.options {
+            writer.field("options", "options", |writer| writer.message(value))?;
+        }
+        if let ::core::option::Option::Some(value) = &self.oneof_index {
+            writer.field("oneofIndex", "oneof_index", |writer| writer.scalar(value))?;
+        }
+        if let ::core::option::Option::Some(value) = &self.json_name {
+            writer.field("jsonName", "json_name", |writer| writer.scalar(value))?;
+        }
+        if let ::core::option::Option::Some(value) = &self.proto3_optional {
+            writer
+                .field(
+                    "proto3Optional",
+                    "proto3_optional",
+                    |writer| writer.scalar(value),
+                )?;
+        }
+        ::core::result::Result::Ok(())
+    }
+    fn merge_json_field(
+        &mut self,
+        name: &str,
+        parser: &mut ::prost::json::Parser<'_>,
+    ) -> ::core::result::Result<bool, ::prost::json::JsonError> {
+        match name {
+            "name" => parser.optional(&mut self.name, |parser| parser.scalar())?,
+            "number" => parser.optional(&mut self.number, |parser| parser.scalar())?,
+            "label" => {
+                parser
+                    .optional(
+                        &mut self.label,
+                        |parser| {
+                            parser
+                                .enumeration(|name| {
+                                    field_descriptor_proto::Label::from_str_name(name)
+                                        .map(|value| value as i32)
+                                })
+                        },
+                    )?
+            }
+            "type" => {
+                parser
+                    .optional(
+                        &mut self.r#type,
+                        |parser| {
+                            parser
+                                .enumeration(|name| {
+                                    field_descriptor_proto::Type::from_str_name(name)
+                                        .map(|value| value as i32)
+                                })
+                        },
+                    )?
+            }
+            "typeName" | "type_name" => {
+                parser.optional(&mut self.type_name, |parser| parser.scalar())?
+            }
+            "extendee" => parser.optional(&mut self.extendee, |parser| parser.scalar())?,
+            "defaultValue" | "default_value" => {
+                parser.optional(&mut self.default_value, |parser| parser.scalar())?
+            }
+            "oneofIndex" | "oneof_index" => {
+                parser.optional(&mut self.oneof_index, |parser| parser.scalar())?
+            }
+            "jsonName" | "json_name" => {
+                parser.optional(&mut self.json_name, |parser| parser.scalar())?
+            }
+            "options" => parser.optional(&mut self.options, |parser| parser.message())?,
+            "proto3Optional" | "proto3_optional" => {
+                parser.optional(&mut self.proto3_optional, |parser| parser.scalar())?
+            }
+            _ => return ::core::result::Result::Ok(false),
+        }
+        ::core::result::Result::Ok(true)
+    }
+}
+#[allow(deprecated)]
+impl ::prost::FieldMasked for FieldDescriptorProto {
+    fn check_mask_tree(
+        mask: &::prost::field_mask::FieldMaskTree,
+    ) -> ::core::result::Result<(), ::prost::field_mask::FieldMaskError> {
+        for (name, mask) in mask.iter() {
+            match name {
+                "name" => ::prost::field_mask::check_leaf("name", mask)?,
+                "number" => ::prost::field_mask::check_leaf("number", mask)?,
+                "label" => ::prost::field_mask::check_leaf("label", mask)?,
+                "type" => ::prost::field_mask::check_leaf("type", mask)?,
+                "type_name" => ::prost::field_mask::check_leaf("type_name", mask)?,
+                "extendee" => ::prost::field_mask::check_leaf("extendee", mask)?,
+                "default_value" => {
+                    ::prost::field_mask::check_leaf("default_value", mask)?
+                }
+                "oneof_index" => ::prost::field_mask::check_leaf("oneof_index", mask)?,
+                "json_name" => ::prost::field_mask::check_leaf("json_name", mask)?,
+                "options" => {
+                    ::prost::field_mask::check_message::<FieldOptions>("options", mask)?
+                }
+                "proto3_optional" => {
+                    ::prost::field_mask::check_leaf("proto3_optional", mask)?
+                }
+                _ => {
+                    return ::core::result::Result::Err(
+                        ::prost::field_mask::unknown_field(name),
+                    );
+                }
+            }
+        }
+        ::core::result::Result::Ok(())
+    }
+    fn merge_with_mask_tree(
+        &mut self,
+        src: &Self,
+        mask: &::prost::field_mask::FieldMaskTree,
+        options: &::prost::field_mask::MergeOptions,
+    ) {
+        if mask.get("name").is_some() {
+            self.name.clone_from(&src.name);
+        }
+        if mask.get("number").is_some() {
+            self.number.clone_from(&src.number);
+        }
+        if mask.get("label").is_some() {
+            self.label.clone_from(&src.label);
+        }
+        if mask.get("type").is_some() {
+            self.r#type.clone_from(&src.r#type);
+        }
+        if mask.get("type_name").is_some() {
+            self.type_name.clone_from(&src.type_name);
+        }
+        if mask.get("extendee").is_some() {
+            self.extendee.clone_from(&src.extendee);
+        }
+        if mask.get("default_value").is_some() {
+            self.default_value.clone_from(&src.default_value);
+        }
+        if mask.get("oneof_index").is_some() {
+            self.oneof_index.clone_from(&src.oneof_index);
+        }
+        if mask.get("json_name").is_some() {
+            self.json_name.clone_from(&src.json_name);
+        }
+        if let ::core::option::Option::Some(mask) = mask.get("options") {
+            ::prost::field_mask::merge_message(
+                &mut self.options,
+                src.options.as_ref(),
+                mask,
+                options,
+            );
+        }
+        if mask.get("proto3_optional").is_some() {
+            self.proto3_optional.clone_from(&src.proto3_optional);
+        }
+    }
+    fn prune_with_mask_tree(&mut self, mask: &::prost::field_mask::FieldMaskTree) {
+        let default = Self::default();
+        if mask.get("name").is_none() {
+            self.name = default.name;
+        }
+        if mask.get("number").is_none() {
+            self.number = default.number;
+        }
+        if mask.get("label").is_none() {
+            self.label = default.label;
+        }
+        if mask.get("type").is_none() {
+            self.r#type = default.r#type;
+        }
+        if mask.get("type_name").is_none() {
+            self.type_name = default.type_name;
+        }
+        if mask.get("extendee").is_none() {
+            self.extendee = default.extendee;
+        }
+        if mask.get("default_value").is_none() {
+            self.default_value = default.default_value;
+        }
+        if mask.get("oneof_index").is_none() {
+            self.oneof_index = default.oneof_index;
+        }
+        if mask.get("json_name").is_none() {
+            self.json_name = default.json_name;
+        }
+        ::prost::field_mask::prune_message(&mut self.options, mask.get("options"));
+        if mask.get("proto3_optional").is_none() {
+            self.proto3_optional = default.proto3_optional;
+        }
+    }
+}
 /// Nested message and enum types in `FieldDescriptorProto`.
 pub mod field_descriptor_proto {
     #[derive(
@@ -329,6 +1911,114 @@ pub struct OneofDescriptorProto {
     #[prost(message, optional, tag = "2")]
     pub options: ::core::option::Option<OneofOptions>,
 }
+#[allow(deprecated)]
+impl ::prost::text_format::TextFormat for OneofDescriptorProto {
+    fn print_fields(&self, printer: &mut ::prost::text_format::Printer<'_>) {
+        if let ::core::option::Option::Some(value) = &self.name {
+            printer.scalar("name", value);
+        }
+        if let ::core::option::Option::Some(value) = &self.options {
+            printer.message("options", value);
+        }
+    }
+    fn merge_text_field(
+        &mut self,
+        name: &str,
+        parser: &mut ::prost::text_format::Parser<'_>,
+    ) -> ::core::result::Result<bool, ::prost::text_format::ParseError> {
+        match name {
+            "name" => {
+                parser
+                    .scalar(
+                        self.name.get_or_insert_with(::core::default::Default::default),
+                    )?
+            }
+            "options" => {
+                parser
+                    .message(
+                        self
+                            .options
+                            .get_or_insert_with(::core::default::Default::default),
+                    )?
+            }
+            _ => return ::core::result::Result::Ok(false),
+        }
+        ::core::result::Result::Ok(true)
+    }
+}
+#[allow(deprecated)]
+impl ::prost::json::Json for OneofDescriptorProto {
+    fn write_json_fields(
+        &self,
+        writer: &mut ::prost::json::Writer<'_>,
+    ) -> ::core::result::Result<(), ::prost::json::JsonError> {
+        if let ::core::option::Option::Some(value) = &self.name {
+            writer.field("name", "name", |writer| writer.scalar(value))?;
+        }
+        if let ::core::option::Option::Some(value) = &self.options {
+            writer.field("options", "options", |writer| writer.message(value))?;
+        }
+        ::core::result::Result::Ok(())
+    }
+    fn merge_json_field(
+        &mut self,
+        name: &str,
+        parser: &mut ::prost::json::Parser<'_>,
+    ) -> ::core::result::Result<bool, ::prost::json::JsonError> {
+        match name {
+            "name" => parser.optional(&mut self.name, |parser| parser.scalar())?,
+            "options" => parser.optional(&mut self.options, |parser| parser.message())?,
+            _ => return ::core::result::Result::Ok(false),
+        }
+        ::core::result::Result::Ok(true)
+    }
+}
+#[allow(deprecated)]
+impl ::prost::FieldMasked for OneofDescriptorProto {
+    fn check_mask_tree(
+        mask: &::prost::field_mask::FieldMaskTree,
+    ) -> ::core::result::Result<(), ::prost::field_mask::FieldMaskError> {
+        for (name, mask) in mask.iter() {
+            match name {
+                "name" => ::prost::field_mask::check_leaf("name", mask)?,
+                "options" => {
+                    ::prost::field_mask::check_message::<OneofOptions>("options", mask)?
+                }
+                _ => {
+                    return ::core::result::Result::Err(
+                        ::prost::field_mask::unknown_field(name),
+                    );
+                }
+            }
+        }
+        ::core::result::Result::Ok(())
+    }
+    fn merge_with_mask_tree(
+        &mut self,
+        src: &Self,
+        mask: &::prost::field_mask::FieldMaskTree,
+        options: &::prost::field_mask::MergeOptions,
+    ) {
+        if mask.get("name").is_some() {
+            self.name.clone_from(&src.name);
+        }
+        if let ::core::option::Option::Some(mask) = mask.get("options") {
+            ::prost::field_mask::merge_message(
+                &mut self.options,
+                src.options.as_ref(),
+                mask,
+                options,
+            );
+        }
+    }
+    fn prune_with_mask_tree(&mut self, mask: &::prost::field_mask::FieldMaskTree) {
+        let default = Self::default();
+        if mask.get("name").is_none() {
+            self.name = default.name;
+        }
+        ::prost::field_mask::prune_message(&mut self.options, mask.get("options"));
+    }
+}
 /// Describes an enum type.
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -351,6 +2041,204 @@ pub struct EnumDescriptorProto {
     #[prost(string, repeated, tag = "5")]
     pub reserved_name: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
 }
+#[allow(deprecated)]
+impl ::prost::text_format::TextFormat for EnumDescriptorProto {
+    fn print_fields(&self, printer: &mut ::prost::text_format::Printer<'_>) {
+        if let ::core::option::Option::Some(value) = &self.name {
+            printer.scalar("name", value);
+        }
+        for value in &self.value {
+            printer.message("value", value);
+        }
+        if let ::core::option::Option::Some(value) = &self.options {
+            printer.message("options", value);
+        }
+        for value in &self.reserved_range {
+            printer.message("reserved_range", value);
+        }
+        for value in &self.reserved_name {
+            printer.scalar("reserved_name", value);
+        }
+    }
+    fn merge_text_field(
+        &mut self,
+        name: &str,
+        parser: &mut ::prost::text_format::Parser<'_>,
+    ) -> ::core::result::Result<bool, ::prost::text_format::ParseError> {
+        match name {
+            "name" => {
+                parser
+                    .scalar(
+                        self.name.get_or_insert_with(::core::default::Default::default),
+                    )?
+            }
+            "value" => parser.repeated_message(&mut self.value)?,
+            "options" => {
+                parser
+                    .message(
+                        self
+                            .options
+                            .get_or_insert_with(::core::default::Default::default),
+                    )?
+            }
+            "reserved_range" => parser.repeated_message(&mut self.reserved_range)?,
+            "reserved_name" => parser.repeated_scalar(&mut self.reserved_name)?,
+            _ => return ::core::result::Result::Ok(false),
+        }
+        ::core::result::Result::Ok(true)
+    }
+}
+#[allow(deprecated)]
+impl ::prost::json::Json for EnumDescriptorProto {
+    fn write_json_fields(
+        &self,
+        writer: &mut ::prost::json::Writer<'_>,
+    ) -> ::core::result::Result<(), ::prost::json::JsonError> {
+        if let ::core::option::Option::Some(value) = &self.name {
+            writer.field("name", "name", |writer| writer.scalar(value))?;
+        }
+        if writer.emit_defaults() || !self.value.is_empty() {
+            writer
+                .field(
+                    "value",
+                    "value",
+                    |writer| {
+                        writer.list(&self.value, |writer, value| writer.message(value))
+                    },
+                )?;
+        }
+        if let ::core::option::Option::Some(value) = &self.options {
+            writer.field("options", "options", |writer| writer.message(value))?;
+        }
+        if writer.emit_defaults() || !self.reserved_range.is_empty() {
+            writer
+                .field(
+                    "reservedRange",
+                    "reserved_range",
+                    |writer| {
+                        writer
+                            .list(
+                                &self.reserved_range,
+                                |writer, value| writer.message(value),
+                            )
+                    },
+                )?;
+        }
+        if writer.emit_defaults() || !self.reserved_name.is_empty() {
+            writer
+                .field(
+                    "reservedName",
+                    "reserved_name",
+                    |writer| {
+                        writer
+                            .list(
+                                &self.reserved_name,
+                                |writer, value| writer.scalar(value),
+                            )
+                    },
+                )?;
+        }
+        ::core::result::Result::Ok(())
+    }
+    fn merge_json_field(
+        &mut self,
+        name: &str,
+        parser: &mut ::prost::json::Parser<'_>,
+    ) -> ::core::result::Result<bool, ::prost::json::JsonError> {
+        match name {
+            "name" => parser.optional(&mut self.name, |parser| parser.scalar())?,
+            "value" => parser.repeated(&mut self.value, |parser| parser.message())?,
+            "options" => parser.optional(&mut self.options, |parser| parser.message())?,
+            "reservedRange" | "reserved_range" => {
+                parser.repeated(&mut self.reserved_range, |parser| parser.message())?
+            }
+            "reservedName" | "reserved_name" => {
+                parser.repeated(&mut self.reserved_name, |parser| parser.scalar())?
+            }
+            _ => return ::core::result::Result::Ok(false),
+        }
+        ::core::result::Result::Ok(true)
+    }
+}
+#[allow(deprecated)]
+impl ::prost::FieldMasked for EnumDescriptorProto {
+    fn check_mask_tree(
+        mask: &::prost::field_mask::FieldMaskTree,
+    ) -> ::core::result::Result<(), ::prost::field_mask::FieldMaskError> {
+        for (name, mask) in mask.iter() {
+            match name {
+                "name" => ::prost::field_mask::check_leaf("name", mask)?,
+                "value" => ::prost::field_mask::check_leaf("value", mask)?,
+                "options" => {
+                    ::prost::field_mask::check_message::<EnumOptions>("options", mask)?
+                }
+                "reserved_range" => {
+                    ::prost::field_mask::check_leaf("reserved_range", mask)?
+                }
+                "reserved_name" => {
+                    ::prost::field_mask::check_leaf("reserved_name", mask)?
+                }
+                _ => {
+                    return ::core::result::Result::Err(
+                        ::prost::field_mask::unknown_field(name),
+                    );
+                }
+            }
+        }
+        ::core::result::Result::Ok(())
+    }
+    fn merge_with_mask_tree(
+        &mut self,
+        src: &Self,
+        mask: &::prost::field_mask::FieldMaskTree,
+        options: &::prost::field_mask::MergeOptions,
+    ) {
+        if mask.get("name").is_some() {
+            self.name.clone_from(&src.name);
+        }
+        if mask.get("value").is_some() {
+            ::prost::field_mask::merge_repeated(&mut self.value, &src.value, options);
+        }
+        if let ::core::option::Option::Some(mask) = mask.get("options") {
+            ::prost::field_mask::merge_message(
+                &mut self.options,
+                src.options.as_ref(),
+                mask,
+                options,
+            );
+        }
+        if mask.get("reserved_range").is_some() {
+            ::prost::field_mask::merge_repeated(
+                &mut self.reserved_range,
+                &src.reserved_range,
+                options,
+            );
+        }
+        if mask.get("reserved_name").is_some() {
+            ::prost::field_mask::merge_repeated(
+                &mut self.reserved_name,
+                &src.reserved_name,
+                options,
+            );
+        }
+    }
+    fn prune_with_mask_tree(&mut self, mask: &::prost::field_mask::FieldMaskTree) {
+        let default = Self::default();
+        if mask.get("name").is_none() {
+            self.name = default.name;
+        }
+        if mask.get("value").is_none() {
+            self.value = default.value;
+        }
+        ::prost::field_mask::prune_message(&mut self.options, mask.get("options"));
+        if mask.get("reserved_range").is_none() {
+            self.reserved_range = default.reserved_range;
+        }
+        if mask.get("reserved_name").is_none() {
+            self.reserved_name = default.reserved_name;
+        }
+    }
+}
 /// Nested message and enum types in `EnumDescriptorProto`.
 pub mod enum_descriptor_proto {
     /// Range of reserved numeric values. Reserved values may not be used by
@@ -369,6 +2257,111 @@ pub mod enum_descriptor_proto {
         #[prost(int32, optional, tag = "2")]
         pub end: ::core::option::Option<i32>,
     }
+    #[allow(deprecated)]
+    impl ::prost::text_format::TextFormat for EnumReservedRange {
+        fn print_fields(&self, printer: &mut ::prost::text_format::Printer<'_>) {
+            if let ::core::option::Option::Some(value) = &self.start {
+                printer.scalar("start", value);
+            }
+            if let ::core::option::Option::Some(value) = &self.end {
+                printer.scalar("end", value);
+            }
+        }
+        fn merge_text_field(
+            &mut self,
+            name: &str,
+            parser: &mut ::prost::text_format::Parser<'_>,
+        ) -> ::core::result::Result<bool, ::prost::text_format::ParseError> {
+            match name {
+                "start" => {
+                    parser
+                        .scalar(
+                            self
+                                .start
+                                .get_or_insert_with(::core::default::Default::default),
+                        )?
+                }
+                "end" => {
+                    parser
+                        .scalar(
+                            self
+                                .end
+                                .get_or_insert_with(::core::default::Default::default),
+                        )?
+                }
+                _ => return ::core::result::Result::Ok(false),
+            }
+            ::core::result::Result::Ok(true)
+        }
+    }
+    #[allow(deprecated)]
+    impl ::prost::json::Json for EnumReservedRange {
+        fn write_json_fields(
+            &self,
+            writer: &mut ::prost::json::Writer<'_>,
+        ) -> ::core::result::Result<(), ::prost::json::JsonError> {
+            if let ::core::option::Option::Some(value) = &self.start {
+                writer.field("start", "start", |writer| writer.scalar(value))?;
+            }
+            if let ::core::option::Option::Some(value) = &self.end {
+                writer.field("end", "end", |writer| writer.scalar(value))?;
+            }
+            ::core::result::Result::Ok(())
+        }
+        fn merge_json_field(
+            &mut self,
+            name: &str,
+            parser: &mut ::prost::json::Parser<'_>,
+        ) -> ::core::result::Result<bool, ::prost::json::JsonError> {
+            match name {
+                "start" => parser.optional(&mut self.start, |parser| parser.scalar())?,
+                "end" => parser.optional(&mut self.end, |parser| parser.scalar())?,
+                _ => return ::core::result::Result::Ok(false),
+            }
+            ::core::result::Result::Ok(true)
+        }
+    }
+    #[allow(deprecated)]
+    impl ::prost::FieldMasked for EnumReservedRange {
+        fn check_mask_tree(
+            mask: &::prost::field_mask::FieldMaskTree,
+        ) -> ::core::result::Result<(), ::prost::field_mask::FieldMaskError> {
+            for (name, mask) in mask.iter() {
+                match name {
+                    "start" => ::prost::field_mask::check_leaf("start", mask)?,
+                    "end" => ::prost::field_mask::check_leaf("end", mask)?,
+                    _ => {
+                        return ::core::result::Result::Err(
+                            ::prost::field_mask::unknown_field(name),
+                        );
+                    }
+                }
+            }
+            ::core::result::Result::Ok(())
+        }
+        fn merge_with_mask_tree(
+            &mut self,
+            src: &Self,
+            mask: &::prost::field_mask::FieldMaskTree,
+            _options: &::prost::field_mask::MergeOptions,
+        ) {
+            if mask.get("start").is_some() {
+                self.start.clone_from(&src.start);
+            }
+            if mask.get("end").is_some() {
+                self.end.clone_from(&src.end);
+            }
+        }
+        fn prune_with_mask_tree(&mut self, mask: &::prost::field_mask::FieldMaskTree) {
+            let default = Self::default();
+            if mask.get("start").is_none() {
+                self.start = default.start;
+            }
+            if mask.get("end").is_none() {
+                self.end = default.end;
+            }
+        }
+    }
 }
 /// Describes a value within an enum.
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -381,6 +2374,136 @@ pub struct EnumValueDescriptorProto {
     #[prost(message, optional, tag = "3")]
     pub options: ::core::option::Option<EnumValueOptions>,
 }
+#[allow(deprecated)]
+impl ::prost::text_format::TextFormat for EnumValueDescriptorProto {
+    fn print_fields(&self, printer: &mut ::prost::text_format::Printer<'_>) {
+        if let ::core::option::Option::Some(value) = &self.name {
+            printer.scalar("name", value);
+        }
+        if let ::core::option::Option::Some(value) = &self.number {
+            printer.scalar("number", value);
+        }
+        if let ::core::option::Option::Some(value) = &self.options {
+            printer.message("options", value);
+        }
+    }
+    fn merge_text_field(
+        &mut self,
+        name: &str,
+        parser: &mut ::prost::text_format::Parser<'_>,
+    ) -> ::core::result::Result<bool, ::prost::text_format::ParseError> {
+        match name {
+            "name" => {
+                parser
+                    .scalar(
+                        self.name.get_or_insert_with(::core::default::Default::default),
+                    )?
+            }
+            "number" => {
+                parser
+                    .scalar(
+                        self.number.get_or_insert_with(::core::default::Default::default),
+                    )?
+            }
+            "options" => {
+                parser
+                    .message(
+                        self
+                            .options
+                            .get_or_insert_with(::core::default::Default::default),
+                    )?
+            }
+            _ => return ::core::result::Result::Ok(false),
+        }
+        ::core::result::Result::Ok(true)
+    }
+}
+#[allow(deprecated)]
+impl ::prost::json::Json for EnumValueDescriptorProto {
+    fn write_json_fields(
+        &self,
+        writer: &mut ::prost::json::Writer<'_>,
+    ) -> ::core::result::Result<(), ::prost::json::JsonError> {
+        if let ::core::option::Option::Some(value) = &self.name {
+            writer.field("name", "name", |writer| writer.scalar(value))?;
+        }
+        if let ::core::option::Option::Some(value) = &self.number {
+            writer.field("number", "number", |writer| writer.scalar(value))?;
+        }
+        if let ::core::option::Option::Some(value) = &self.options {
+            writer.field("options", "options", |writer| writer.message(value))?;
+        }
+        ::core::result::Result::Ok(())
+    }
+    fn merge_json_field(
+        &mut self,
+        name: &str,
+        parser: &mut ::prost::json::Parser<'_>,
+    ) -> ::core::result::Result<bool, ::prost::json::JsonError> {
+        match name {
+            "name" => parser.optional(&mut self.name, |parser| parser.scalar())?,
+            "number" => parser.optional(&mut self.number, |parser| parser.scalar())?,
+            "options" => parser.optional(&mut self.options, |parser| parser.message())?,
+            _ => return ::core::result::Result::Ok(false),
+        }
+        ::core::result::Result::Ok(true)
+    }
+}
+#[allow(deprecated)]
+impl ::prost::FieldMasked for EnumValueDescriptorProto {
+    fn check_mask_tree(
+        mask: &::prost::field_mask::FieldMaskTree,
+    ) -> ::core::result::Result<(), ::prost::field_mask::FieldMaskError> {
+        for (name, mask) in mask.iter() {
+            match name {
+                "name" => ::prost::field_mask::check_leaf("name", mask)?,
+                "number" => ::prost::field_mask::check_leaf("number", mask)?,
+                "options" => {
+                    ::prost::field_mask::check_message::<
+                        EnumValueOptions,
+                    >("options", mask)?
+                }
+                _ => {
+                    return ::core::result::Result::Err(
+                        ::prost::field_mask::unknown_field(name),
+                    );
+                }
+            }
+        }
+        ::core::result::Result::Ok(())
+    }
+    fn merge_with_mask_tree(
+        &mut self,
+        src: &Self,
+        mask: &::prost::field_mask::FieldMaskTree,
+        options: &::prost::field_mask::MergeOptions,
+    ) {
+        if mask.get("name").is_some() {
+            self.name.clone_from(&src.name);
+        }
+        if mask.get("number").is_some() {
+            self.number.clone_from(&src.number);
+        }
+        if let ::core::option::Option::Some(mask) = mask.get("options") {
+            ::prost::field_mask::merge_message(
+                &mut self.options,
+                src.options.as_ref(),
+                mask,
+                options,
+            );
+        }
+    }
+    fn prune_with_mask_tree(&mut self, mask: &::prost::field_mask::FieldMaskTree) {
+        let default = Self::default();
+        if mask.get("name").is_none() {
+            self.name = default.name;
+        }
+        if mask.get("number").is_none() {
+            self.number = default.number;
+        }
+        ::prost::field_mask::prune_message(&mut self.options, mask.get("options"));
+    }
+}
 /// Describes a service.
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -392,6 +2515,138 @@ pub struct ServiceDescriptorProto {
     #[prost(message, optional, tag = "3")]
     pub options: ::core::option::Option<ServiceOptions>,
 }
+#[allow(deprecated)]
+impl ::prost::text_format::TextFormat for ServiceDescriptorProto {
+    fn print_fields(&self, printer: &mut ::prost::text_format::Printer<'_>) {
+        if let ::core::option::Option::Some(value) = &self.name {
+            printer.scalar("name", value);
+        }
+        for value in &self.method {
+            printer.message("method", value);
+        }
+        if let ::core::option::Option::Some(value) = &self.options {
+            printer.message("options", value);
+        }
+    }
+    fn merge_text_field(
+        &mut self,
+        name: &str,
+        parser: &mut ::prost::text_format::Parser<'_>,
+    ) -> ::core::result::Result<bool, ::prost::text_format::ParseError> {
+        match name {
+            "name" => {
+                parser
+                    .scalar(
+                        self.name.get_or_insert_with(::core::default::Default::default),
+                    )?
+            }
+            "method" => parser.repeated_message(&mut self.method)?,
+            "options" => {
+                parser
+                    .message(
+                        self
+                            .options
+                            .get_or_insert_with(::core::default::Default::default),
+                    )?
+            }
+            _ => return ::core::result::Result::Ok(false),
+        }
+        ::core::result::Result::Ok(true)
+    }
+}
+#[allow(deprecated)]
+impl ::prost::json::Json for ServiceDescriptorProto {
+    fn write_json_fields(
+        &self,
+        writer: &mut ::prost::json::Writer<'_>,
+    ) -> ::core::result::Result<(), ::prost::json::JsonError> {
+        if let ::core::option::Option::Some(value) = &self.name {
+            writer.field("name", "name", |writer| writer.scalar(value))?;
+        }
+        if writer.emit_defaults() || !self.method.is_empty() {
+            writer
+                .field(
+                    "method",
+                    "method",
+                    |writer| {
+                        writer.list(&self.method, |writer, value| writer.message(value))
+                    },
+                )?;
+        }
+        if let ::core::option::Option::Some(value) = &self.options {
+            writer.field("options", "options", |writer| writer.message(value))?;
+        }
+        ::core::result::Result::Ok(())
+    }
+    fn merge_json_field(
+        &mut self,
+        name: &str,
+        parser: &mut ::prost::json::Parser<'_>,
+    ) -> ::core::result::Result<bool, ::prost::json::JsonError> {
+        match name {
+            "name" => parser.optional(&mut self.name, |parser| parser.scalar())?,
+            "method" => parser.repeated(&mut self.method, |parser| parser.message())?,
+            "options" => parser.optional(&mut self.options, |parser| parser.message())?,
+            _ => return ::core::result::Result::Ok(false),
+        }
+        ::core::result::Result::Ok(true)
+    }
+}
+#[allow(deprecated)]
+impl ::prost::FieldMasked for ServiceDescriptorProto {
+    fn check_mask_tree(
+        mask: &::prost::field_mask::FieldMaskTree,
+    ) -> ::core::result::Result<(), ::prost::field_mask::FieldMaskError> {
+        for (name, mask) in mask.iter() {
+            match name {
+                "name" => ::prost::field_mask::check_leaf("name", mask)?,
+                "method" => ::prost::field_mask::check_leaf("method", mask)?,
+                "options" => {
+                    ::prost::field_mask::check_message::<
+                        ServiceOptions,
+                    >("options", mask)?
+                }
+                _ => {
+                    return ::core::result::Result::Err(
+                        ::prost::field_mask::unknown_field(name),
+                    );
+                }
+            }
+        }
+        ::core::result::Result::Ok(())
+    }
+    fn merge_with_mask_tree(
+        &mut self,
+        src: &Self,
+        mask: &::prost::field_mask::FieldMaskTree,
+        options: &::prost::field_mask::MergeOptions,
+    ) {
+        if mask.get("name").is_some() {
+            self.name.clone_from(&src.name);
+        }
+        if mask.get("method").is_some() {
+            ::prost::field_mask::merge_repeated(&mut self.method, &src.method, options);
+        }
+        if let ::core::option::Option::Some(mask) = mask.get("options") {
+            ::prost::field_mask::merge_message(
+                &mut self.options,
+                src.options.as_ref(),
+                mask,
+                options,
+            );
+        }
+    }
+    fn prune_with_mask_tree(&mut self, mask: &::prost::field_mask::FieldMaskTree) {
+        let default = Self::default();
+        if mask.get("name").is_none() {
+            self.name = default.name;
+        }
+        if mask.get("method").is_none() {
+            self.method = default.method;
+        }
+        ::prost::field_mask::prune_message(&mut self.options, mask.get("options"));
+    }
+}
 /// Describes a method of a service.
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -413,6 +2668,224 @@ pub struct MethodDescriptorProto {
     #[prost(bool, optional, tag = "6", default = "false")]
     pub server_streaming: ::core::option::Option<bool>,
 }
+#[allow(deprecated)]
+impl ::prost::text_format::TextFormat for MethodDescriptorProto {
+    fn print_fields(&self, printer: &mut ::prost::text_format::Printer<'_>) {
+        if let ::core::option::Option::Some(value) = &self.name {
+            printer.scalar("name", value);
+        }
+        if let ::core::option::Option::Some(value) = &self.input_type {
+            printer.scalar("input_type", value);
+        }
+        if let ::core::option::Option::Some(value) = &self.output_type {
+            printer.scalar("output_type", value);
+        }
+        if let ::core::option::Option::Some(value) = &self.options {
+            printer.message("options", value);
+        }
+        if let ::core::option::Option::Some(value) = &self.client_streaming {
+            printer.scalar("client_streaming", value);
+        }
+        if let ::core::option::Option::Some(value) = &self.server_streaming {
+            printer.scalar("server_streaming", value);
+        }
+    }
+    fn merge_text_field(
+        &mut self,
+        name: &str,
+        parser: &mut ::prost::text_format::Parser<'_>,
+    ) -> ::core::result::Result<bool, ::prost::text_format::ParseError> {
+        match name {
+            "name" => {
+                parser
+                    .scalar(
+                        self.name.get_or_insert_with(::core::default::Default::default),
+                    )?
+            }
+            "input_type" => {
+                parser
+                    .scalar(
+                        self
+                            .input_type
+                            .get_or_insert_with(::core::default::Default::default),
+                    )?
+            }
+            "output_type" => {
+                parser
+                    .scalar(
+                        self
+                            .output_type
+                            .get_or_insert_with(::core::default::Default::default),
+                    )?
+            }
+            "options" => {
+                parser
+                    .message(
+                        self
+                            .options
+                            .get_or_insert_with(::core::default::Default::default),
+                    )?
+            }
+            "client_streaming" => {
+                parser
+                    .scalar(
+                        self
+                            .client_streaming
+                            .get_or_insert_with(::core::default::Default::default),
+                    )?
+            }
+            "server_streaming" => {
+                parser
+                    .scalar(
+                        self
+                            .server_streaming
+                            .get_or_insert_with(::core::default::Default::default),
+                    )?
+            }
+            _ => return ::core::result::Result::Ok(false),
+        }
+        ::core::result::Result::Ok(true)
+    }
+}
+#[allow(deprecated)]
+impl ::prost::json::Json for MethodDescriptorProto {
+    fn write_json_fields(
+        &self,
+        writer: &mut ::prost::json::Writer<'_>,
+    ) -> ::core::result::Result<(), ::prost::json::JsonError> {
+        if let ::core::option::Option::Some(value) = &self.name {
+            writer.field("name", "name", |writer| writer.scalar(value))?;
+        }
+        if let ::core::option::Option::Some(value) = &self.input_type {
+            writer.field("inputType", "input_type", |writer| writer.scalar(value))?;
+        }
+        if let ::core::option::Option::Some(value) = &self.output_type {
+            writer.field("outputType", "output_type", |writer| writer.scalar(value))?;
+        }
+        if let ::core::option::Option::Some(value) = &self.options {
+            writer.field("options", "options", |writer| writer.message(value))?;
+        }
+        if let ::core::option::Option::Some(value) = &self.client_streaming {
+            writer
+                .field(
+                    "clientStreaming",
+                    "client_streaming",
+                    |writer| writer.scalar(value),
+                )?;
+        }
+        if let ::core::option::Option::Some(value) = &self.server_streaming {
+            writer
+                .field(
+                    "serverStreaming",
+                    "server_streaming",
+                    |writer| writer.scalar(value),
+                )?;
+        }
+        ::core::result::Result::Ok(())
+    }
+    fn merge_json_field(
+        &mut self,
+        name: &str,
+        parser: &mut ::prost::json::Parser<'_>,
+    ) -> ::core::result::Result<bool, ::prost::json::JsonError> {
+        match name {
+            "name" => parser.optional(&mut self.name, |parser| parser.scalar())?,
+            "inputType" | "input_type" => {
+                parser.optional(&mut self.input_type, |parser| parser.scalar())?
+            }
+            "outputType" | "output_type" => {
+                parser.optional(&mut self.output_type, |parser| parser.scalar())?
+            }
+            "options" => parser.optional(&mut self.options, |parser| parser.message())?,
+            "clientStreaming" | "client_streaming" => {
+                parser.optional(&mut self.client_streaming, |parser| parser.scalar())?
+            }
+            "serverStreaming" | "server_streaming" => {
+                parser.optional(&mut self.server_streaming, |parser| parser.scalar())?
+            }
+            _ => return ::core::result::Result::Ok(false),
+        }
+        ::core::result::Result::Ok(true)
+    }
+}
+#[allow(deprecated)]
+impl ::prost::FieldMasked for MethodDescriptorProto {
+    fn check_mask_tree(
+        mask: &::prost::field_mask::FieldMaskTree,
+    ) -> ::core::result::Result<(), ::prost::field_mask::FieldMaskError> {
+        for (name, mask) in mask.iter() {
+            match name {
+                "name" => ::prost::field_mask::check_leaf("name", mask)?,
+                "input_type" => ::prost::field_mask::check_leaf("input_type", mask)?,
+                "output_type" => ::prost::field_mask::check_leaf("output_type", mask)?,
+                "options" => {
+                    ::prost::field_mask::check_message::<MethodOptions>("options", mask)?
+                }
+                "client_streaming" => {
+                    ::prost::field_mask::check_leaf("client_streaming", mask)?
+                }
+                "server_streaming" => {
+                    ::prost::field_mask::check_leaf("server_streaming", mask)?
+                }
+                _ => {
+                    return ::core::result::Result::Err(
+                        ::prost::field_mask::unknown_field(name),
+                    );
+                }
+            }
+        }
+        ::core::result::Result::Ok(())
+    }
+    fn merge_with_mask_tree(
+        &mut self,
+        src: &Self,
+        mask: &::prost::field_mask::FieldMaskTree,
+        options: &::prost::field_mask::MergeOptions,
+    ) {
+        if mask.get("name").is_some() {
+            self.name.clone_from(&src.name);
+        }
+        if mask.get("input_type").is_some() {
+            self.input_type.clone_from(&src.input_type);
+        }
+        if mask.get("output_type").is_some() {
+            self.output_type.clone_from(&src.output_type);
+        }
+        if let ::core::option::Option::Some(mask) = mask.get("options") {
+            ::prost::field_mask::merge_message(
+                &mut self.options,
+                src.options.as_ref(),
+                mask,
+                options,
+            );
+        }
+        if mask.get("client_streaming").is_some() {
+            self.client_streaming.clone_from(&src.client_streaming);
+        }
+        if mask.get("server_streaming").is_some() {
+            self.server_streaming.clone_from(&src.server_streaming);
+        }
+    }
+    fn prune_with_mask_tree(&mut self, mask: &::prost::field_mask::FieldMaskTree) {
+        let default = Self::default();
+        if mask.get("name").is_none() {
+            self.name = default.name;
+        }
+        if mask.get("input_type").is_none() {
+            self.input_type = default.input_type;
+        }
+        if mask.get("output_type").is_none() {
+            self.output_type = default.output_type;
+        }
+        ::prost::field_mask::prune_message(&mut self.options, mask.get("options"));
+        if mask.get("client_streaming").is_none() {
+            self.client_streaming = default.client_streaming;
+        }
+        if mask.get("server_streaming").is_none() {
+            self.server_streaming = default.server_streaming;
+        }
+    }
+}
 /// Each of the definitions above may have "options" attached.  These are
 /// just annotations which may cause code to be generated slightly differently
 /// or may contain hints for code that manipulates protocol messages.
@@ -558,6 +3031,737 @@ pub struct FileOptions {
     #[prost(message, repeated, tag = "999")]
     pub uninterpreted_option: ::prost::alloc::vec::Vec<UninterpretedOption>,
 }
+#[allow(deprecated)]
+impl ::prost::text_format::TextFormat for FileOptions {
+    fn print_fields(&self, printer: &mut ::prost::text_format::Printer<'_>) {
+        if let ::core::option::Option::Some(value) = &self.java_package {
+            printer.scalar("java_package", value);
+        }
+        if let ::core::option::Option::Some(value) = &self.java_outer_classname {
+            printer.scalar("java_outer_classname", value);
+        }
+        if let ::core::option::Option::Some(value) = &self.optimize_for {
+            printer
+                .enumeration(
+                    "optimize_for",
+                    *value,
+                    |value| {
+                        file_options::OptimizeMode::try_from(value)
+                            .ok()
+                            .map(|value| value.as_str_name())
+                    },
+                );
+        }
+        if let ::core::option::Option::Some(value) = &self.java_multiple_files {
+            printer.scalar("java_multiple_files", value);
+        }
+        if let ::core::option::Option::Some(value) = &self.go_package {
+            printer.scalar("go_package", value);
+        }
+        if let ::core::option::Option::Some(value) = &self.cc_generic_services {
+            printer.scalar("cc_generic_services", value);
+        }
+        if let ::core::option::Option::Some(value) = &self.java_generic_services {
+            printer.scalar("java_generic_services", value);
+        }
+        if let ::core::option::Option::Some(value) = &self.py_generic_services {
+            printer.scalar("py_generic_services", value);
+        }
+        if let ::core::option::Option::Some(value) = &self.java_generate_equals_and_hash
+        {
+            printer.scalar("java_generate_equals_and_hash", value);
+        }
+        if let ::core::option::Option::Some(value) = &self.deprecated {
+            printer.scalar("deprecated", value);
+        }
+        if let ::core::option::Option::Some(value) = &self.java_string_check_utf8 {
+            printer.scalar("java_string_check_utf8", value);
+        }
+        if let ::core::option::Option::Some(value) = &self.cc_enable_arenas {
+            printer.scalar("cc_enable_arenas", value);
+        }
+        if let ::core::option::Option::Some(value) = &self.objc_class_prefix {
+            printer.scalar("objc_class_prefix", value);
+        }
+        if let ::core::option::Option::Some(value) = &self.csharp_namespace {
+            printer.scalar("csharp_namespace", value);
+        }
+        if let ::core::option::Option::Some(value) = &self.swift_prefix {
+            printer.scalar("swift_prefix", value);
+        }
+        if let ::core::option::Option::Some(value) = &self.php_class_prefix {
+            printer.scalar("php_class_prefix", value);
+        }
+        if let ::core::option::Option::Some(value) = &self.php_namespace {
+            printer.scalar("php_namespace", value);
+        }
+        if let ::core::option::Option::Some(value) = &self.php_generic_services {
+            printer.scalar("php_generic_services", value);
+        }
+        if let ::core::option::Option::Some(value) = &self.php_metadata_namespace {
+            printer.scalar("php_metadata_namespace", value);
+        }
+        if let ::core::option::Option::Some(value) = &self.ruby_package {
+            printer.scalar("ruby_package", value);
+        }
+        for value in &self.uninterpreted_option {
+            printer.message("uninterpreted_option", value);
+        }
+    }
+    fn merge_text_field(
+        &mut self,
+        name: &str,
+        parser: &mut ::prost::text_format::Parser<'_>,
+    ) -> ::core::result::Result<bool, ::prost::text_format::ParseError> {
+        match name {
+            "java_package" => {
+                parser
+                    .scalar(
+                        self
+                            .java_package
+                            .get_or_insert_with(::core::default::Default::default),
+                    )?
+            }
+            "java_outer_classname" => {
+                parser
+                    .scalar(
+                        self
+                            .java_outer_classname
+                            .get_or_insert_with(::core::default::Default::default),
+                    )?
+            }
+            "java_multiple_files" => {
+                parser
+                    .scalar(
+                        self
+                            .java_multiple_files
+                            .get_or_insert_with(::core::default::Default::default),
+                    )?
+            }
+            "java_generate_equals_and_hash" => {
+                parser
+                    .scalar(
+                        self
+                            .java_generate_equals_and_hash
+                            .get_or_insert_with(::core::default::Default::default),
+                    )?
+            }
+            "java_string_check_utf8" => {
+                parser
+                    .scalar(
+                        self
+                            .java_string_check_utf8
+                            .get_or_insert_with(::core::default::Default::default),
+                    )?
+            }
+            "optimize_for" => {
+                parser
+                    .enumeration(
+                        self
+                            .optimize_for
+                            .get_or_insert_with(::core::default::Default::default),
+                        |name| {
+                            file_options::OptimizeMode::from_str_name(name)
+                                .map(|value| value as i32)
+                        },
+                    )?
+            }
+            "go_package" => {
+                parser
+                    .scalar(
+                        self
+                            .go_package
+                            .get_or_insert_with(::core::default::Default::default),
+                    )?
+            }
+            "cc_generic_services" => {
+                parser
+                    .scalar(
+                        self
+                            .cc_generic_services
+                            .get_or_insert_with(::core::default::Default::default),
+                    )?
+            }
+            "java_generic_services" => {
+                parser
+                    .scalar(
+                        self
+                            .java_generic_services
+                            .get_or_insert_with(::core::default::Default::default),
+                    )?
+            }
+            "py_generic_services" => {
+                parser
+                    .scalar(
+                        self
+                            .py_generic_services
+                            .get_or_insert_with(::core::default::Default::default),
+                    )?
+            }
+            "php_generic_services" => {
+                parser
+                    .scalar(
+                        self
+                            .php_generic_services
+                            .get_or_insert_with(::core::default::Default::default),
+                    )?
+            }
+            "deprecated" => {
+                parser
+                    .scalar(
+                        self
+                            .deprecated
+                            .get_or_insert_with(::core::default::Default::default),
+                    )?
+            }
+            "cc_enable_arenas" => {
+                parser
+                    .scalar(
+                        self
+                            .cc_enable_arenas
+                            .get_or_insert_with(::core::default::Default::default),
+                    )?
+            }
+            "objc_class_prefix" => {
+                parser
+                    .scalar(
+                        self
+                            .objc_class_prefix
+                            .get_or_insert_with(::core::default::Default::default),
+                    )?
+            }
+            "csharp_namespace" => {
+                parser
+                    .scalar(
+                        self
+                            .csharp_namespace
+                            .get_or_insert_with(::core::default::Default::default),
+                    )?
+            }
+            "swift_prefix" => {
+                parser
+                    .scalar(
+                        self
+                            .swift_prefix
+                            .get_or_insert_with(::core::default::Default::default),
+                    )?
+            }
+            "php_class_prefix" => {
+                parser
+                    .scalar(
+                        self
+                            .php_class_prefix
+                            .get_or_insert_with(::core::default::Default::default),
+                    )?
+            }
+            "php_namespace" => {
+                parser
+                    .scalar(
+                        self
+                            .php_namespace
+                            .get_or_insert_with(::core::default::Default::default),
+                    )?
+            }
+            "php_metadata_namespace" => {
+                parser
+                    .scalar(
+                        self
+                            .php_metadata_namespace
+                            .get_or_insert_with(::core::default::Default::default),
+                    )?
+            }
+            "ruby_package" => {
+                parser
+                    .scalar(
+                        self
+                            .ruby_package
+                            .get_or_insert_with(::core::default::Default::default),
+                    )?
+            }
+            "uninterpreted_option" => {
+                parser.repeated_message(&mut self.uninterpreted_option)?
+            }
+            _ => return ::core::result::Result::Ok(false),
+        }
+        ::core::result::Result::Ok(true)
+    }
+}
+#[allow(deprecated)]
+impl ::prost::json::Json for FileOptions {
+    fn write_json_fields(
+        &self,
+        writer: &mut ::prost::json::Writer<'_>,
+    ) -> ::core::result::Result<(), ::prost::json::JsonError> {
+        if let ::core::option::Option::Some(value) = &self.java_package {
+            writer.field("javaPackage", "java_package", |writer| writer.scalar(value))?;
+        }
+        if let ::core::option::Option::Some(value) = &self.java_outer_classname {
+            writer
+                .field(
+                    "javaOuterClassname",
+                    "java_outer_classname",
+                    |writer| writer.scalar(value),
+                )?;
+        }
+        if let ::core::option::Option::Some(value) = &self.optimize_for {
+            writer
+                .field(
+                    "optimizeFor",
+                    "optimize_for",
+                    |writer| {
+                        writer
+                            .enumeration(
+                                *value,
+                                |value| {
+                                    file_options::OptimizeMode::try_from(value)
+                                        .ok()
+                                        .map(|value| value.as_str_name())
+                                },
+                            )
+                    },
+                )?;
+        }
+        if let ::core::option::Option::Some(value) = &self.java_multiple_files {
+            writer
+                .field(
+                    "javaMultipleFiles",
+                    "java_multiple_files",
+                    |writer| writer.scalar(value),
+                )?;
+        }
+        if let ::core::option::Option::Some(value) = &self.go_package {
+            writer.field("goPackage", "go_package", |writer| writer.scalar(value))?;
+        }
+        if let ::core::option::Option::Some(value) = &self.cc_generic_services {
+            writer
+                .field(
+                    "ccGenericServices",
+                    "cc_generic_services",
+                    |writer| writer.scalar(value),
+                )?;
+        }
+        if let ::core::option::Option::Some(value) = &self.java_generic_services {
+            writer
+                .field(
+                    "javaGenericServices",
+                    "java_generic_services",
+                    |writer| writer.scalar(value),
+                )?;
+        }
+        if let ::core::option::Option::Some(value) = &self.py_generic_services {
+            writer
+                .field(
+                    "pyGenericServices",
+                    "py_generic_services",
+                    |writer| writer.scalar(value),
+                )?;
+        }
+        if let ::core::option::Option::Some(value) = &self.java_generate_equals_and_hash
+        {
+            writer
+                .field(
+                    "javaGenerateEqualsAndHash",
+                    "java_generate_equals_and_hash",
+                    |writer| writer.scalar(value),
+                )?;
+        }
+        if let ::core::option::Option::Some(value) = &self.deprecated {
+            writer.field("deprecated", "deprecated", |writer| writer.scalar(value))?;
+        }
+        if let ::core::option::Option::Some(value) = &self.java_string_check_utf8 {
+            writer
+                .field(
+                    "javaStringCheckUtf8",
+                    "java_string_check_utf8",
+                    |writer| writer.scalar(value),
+                )?;
+        }
+        if let ::core::option::Option::Some(value) = &self.cc_enable_arenas {
+            writer
+                .field(
+                    "ccEnableArenas",
+                    "cc_enable_arenas",
+                    |writer| writer.scalar(value),
+                )?;
+        }
+        if let ::core::option::Option::Some(value) = &self.objc_class_prefix {
+            writer
+                .field(
+                    "objcClassPrefix",
+                    "objc_class_prefix",
+                    |writer| writer.scalar(value),
+                )?;
+        }
+        if let ::core::option::Option::Some(value) = &self.csharp_namespace {
+            writer
+                .field(
+                    "csharpNamespace",
+                    "csharp_namespace",
+                    |writer| writer.scalar(value),
+                )?;
+        }
+        if let ::core::option::Option::Some(value) = &self.swift_prefix {
+            writer.field("swiftPrefix", "swift_prefix", |writer| writer.scalar(value))?;
+        }
+        if let ::core::option::Option::Some(value) = &self.php_class_prefix {
+            writer
+                .field(
+                    "phpClassPrefix",
+                    "php_class_prefix",
+                    |writer| writer.scalar(value),
+                )?;
+        }
+        if let ::core::option::Option::Some(value) = &self.php_namespace {
+            writer
+                .field("phpNamespace", "php_namespace", |writer| writer.scalar(value))?;
+        }
+        if let ::core::option::Option::Some(value) = &self.php_generic_services {
+            writer
+                .field(
+                    "phpGenericServices",
+                    "php_generic_services",
+                    |writer| writer.scalar(value),
+                )?;
+        }
+        if let ::core::option::Option::Some(value) = &self.php_metadata_namespace {
+            writer
+                .field(
+                    "phpMetadataNamespace",
+                    "php_metadata_namespace",
+                    |writer| writer.scalar(value),
+                )?;
+        }
+        if let ::core::option::Option::Some(value) = &self.ruby_package {
+            writer.field("rubyPackage", "ruby_package", |writer| writer.scalar(value))?;
+        }
+        if writer.emit_defaults() || !self.uninterpreted_option.is_empty() {
+            writer
+                .field(
+                    "uninterpretedOption",
+                    "uninterpreted_option",
+                    |writer| {
+                        writer
+                            .list(
+                                &self.uninterpreted_option,
+                                |writer, value| writer.message(value),
+                            )
+                    },
+                )?;
+        }
+        ::core::result::Result::Ok(())
+    }
+    fn merge_json_field(
+        &mut self,
+        name: &str,
+        parser: &mut ::prost::json::Parser<'_>,
+    ) -> ::core::result::Result<bool, ::prost::json::JsonError> {
+        match name {
+            "javaPackage" | "java_package" => {
+                parser.optional(&mut self.java_package, |parser| parser.scalar())?
+            }
+            "javaOuterClassname" | "java_outer_classname" => {
+                parser
+                    .optional(&mut self.java_outer_classname, |parser| parser.scalar())?
+            }
+            "javaMultipleFiles" | "java_multiple_files" => {
+                parser.optional(&mut self.java_multiple_files, |parser| parser.scalar())?
+            }
+            "javaGenerateEqualsAndHash" | "java_generate_equals_and_hash" => {
+                parser
+                    .optional(
+                        &mut self.java_generate_equals_and_hash,
+                        |parser| parser.scalar(),
+                    )?
+            }
+            "javaStringCheckUtf8" | "java_string_check_utf8" => {
+                parser
+                    .optional(
+                        &mut self.java_string_check_utf8,
+                        |parser| parser.scalar(),
+                    )?
+            }
+            "optimizeFor" | "optimize_for" => {
+                parser
+                    .optional(
+                        &mut self.optimize_for,
+                        |parser| {
+                            parser
+                                .enumeration(|name| {
+                                    file_options::OptimizeMode::from_str_name(name)
+                                        .map(|value| value as i32)
+                                })
+                        },
+                    )?
+            }
+            "goPackage" | "go_package" => {
+                parser.optional(&mut self.go_package, |parser| parser.scalar())?
+            }
+            "ccGenericServices" | "cc_generic_services" => {
+                parser.optional(&mut self.cc_generic_services, |parser| parser.scalar())?
+            }
+            "javaGenericServices" | "java_generic_services" => {
+                parser
+                    .optional(&mut self.java_generic_services, |parser| parser.scalar())?
+            }
+            "pyGenericServices" | "py_generic_services" => {
+                parser.optional(&mut self.py_generic_services, |parser| parser.scalar())?
+            }
+            "phpGenericServices" | "php_generic_services" => {
+                parser
+                    .optional(&mut self.php_generic_services, |parser| parser.scalar())?
+            }
+            "deprecated" => {
+                parser.optional(&mut self.deprecated, |parser| parser.scalar())?
+            }
+            "ccEnableArenas" | "cc_enable_arenas" => {
+                parser.optional(&mut self.cc_enable_arenas, |parser| parser.scalar())?
+            }
+            "objcClassPrefix" | "objc_class_prefix" => {
+                parser.optional(&mut self.objc_class_prefix, |parser| parser.scalar())?
+            }
+            "csharpNamespace" | "csharp_namespace" => {
+                parser.optional(&mut self.csharp_namespace, |parser| parser.scalar())?
+            }
+            "swiftPrefix" | "swift_prefix" => {
+                parser.optional(&mut self.swift_prefix, |parser| parser.scalar())?
+            }
+            "phpClassPrefix" | "php_class_prefix" => {
+                parser.optional(&mut self.php_class_prefix, |parser| parser.scalar())?
+            }
+            "phpNamespace" | "php_namespace" => {
+                parser.optional(&mut self.php_namespace, |parser| parser.scalar())?
+            }
+            "phpMetadataNamespace" | "php_metadata_namespace" => {
+                parser
+                    .optional(
+                        &mut self.php_metadata_namespace,
+                        |parser| parser.scalar(),
+                    )?
+            }
+            "rubyPackage" | "ruby_package" => {
+                parser.optional(&mut self.ruby_package, |parser| parser.scalar())?
+            }
+            "uninterpretedOption" | "uninterpreted_option" => {
+                parser
+                    .repeated(&mut self.uninterpreted_option, |parser| parser.message())?
+            }
+            _ => return ::core::result::Result::Ok(false),
+        }
+        ::core::result::Result::Ok(true)
+    }
+}
+#[allow(deprecated)]
+impl ::prost::FieldMasked for FileOptions {
+    fn check_mask_tree(
+        mask: &::prost::field_mask::FieldMaskTree,
+    ) -> ::core::result::Result<(), ::prost::field_mask::FieldMaskError> {
+        for (name, mask) in mask.iter() {
+            match name {
+                "java_package" => ::prost::field_mask::check_leaf("java_package", mask)?,
+                "java_outer_classname" => {
+                    ::prost::field_mask::check_leaf("java_outer_classname", mask)?
+                }
+                "java_multiple_files" => {
+                    ::prost::field_mask::check_leaf("java_multiple_files", mask)?
+                }
+                "java_generate_equals_and_hash" => {
+                    ::prost::field_mask::check_leaf(
+                        "java_generate_equals_and_hash",
+                        mask,
+                    )?
+                }
+                "java_string_check_utf8" => {
+                    ::prost::field_mask::check_leaf("java_string_check_utf8", mask)?
+                }
+                "optimize_for" => ::prost::field_mask::check_leaf("optimize_for", mask)?,
+                "go_package" => ::prost::field_mask::check_leaf("go_package", mask)?,
+                "cc_generic_services" => {
+                    ::prost::field_mask::check_leaf("cc_generic_services", mask)?
+                }
+                "java_generic_services" => {
+                    ::prost::field_mask::check_leaf("java_generic_services", mask)?
+                }
+                "py_generic_services" => {
+                    ::prost::field_mask::check_leaf("py_generic_services", mask)?
+                }
+                "php_generic_services" => {
+                    ::prost::field_mask::check_leaf("php_generic_services", mask)?
+                }
+                "deprecated" => ::prost::field_mask::check_leaf("deprecated", mask)?,
+                "cc_enable_arenas" => {
+                    ::prost::field_mask::check_leaf("cc_enable_arenas", mask)?
+                }
+                "objc_class_prefix" => {
+                    ::prost::field_mask::check_leaf("objc_class_prefix", mask)?
+                }
+                "csharp_namespace" => {
+                    ::prost::field_mask::check_leaf("csharp_namespace", mask)?
+                }
+                "swift_prefix" => ::prost::field_mask::check_leaf("swift_prefix", mask)?,
+                "php_class_prefix" => {
+                    ::prost::field_mask::check_leaf("php_class_prefix", mask)?
+                }
+                "php_namespace" => {
+                    ::prost::field_mask::check_leaf("php_namespace", mask)?
+                }
+                "php_metadata_namespace" => {
+                    ::prost::field_mask::check_leaf("php_metadata_namespace", mask)?
+                }
+                "ruby_package" => ::prost::field_mask::check_leaf("ruby_package", mask)?,
+                "uninterpreted_option" => {
+                    ::prost::field_mask::check_leaf("uninterpreted_option", mask)?
+                }
+                _ => {
+                    return ::core::result::Result::Err(
+                        ::prost::field_mask::unknown_field(name),
+                    );
+                }
+            }
+        }
+        ::core::result::Result::Ok(())
+    }
+    fn merge_with_mask_tree(
+        &mut self,
+        src: &Self,
+        mask: &::prost::field_mask::FieldMaskTree,
+        options: &::prost::field_mask::MergeOptions,
+    ) {
+        if mask.get("java_package").is_some() {
+            self.java_package.clone_from(&src.java_package);
+        }
+        if mask.get("java_outer_classname").is_some() {
+            self.java_outer_classname.clone_from(&src.java_outer_classname);
+        }
+        if mask.get("java_multiple_files").is_some() {
+            self.java_multiple_files.clone_from(&src.java_multiple_files);
+        }
+        if mask.get("java_generate_equals_and_hash").is_some() {
+            self.java_generate_equals_and_hash
+                .clone_from(&src.java_generate_equals_and_hash);
+        }
+        if mask.get("java_string_check_utf8").is_some() {
+            self.java_string_check_utf8.clone_from(&src.java_string_check_utf8);
+        }
+        if mask.get("optimize_for").is_some() {
+            self.optimize_for.clone_from(&src.optimize_for);
+        }
+        if mask.get("go_package").is_some() {
+            self.go_package.clone_from(&src.go_package);
+        }
+        if mask.get("cc_generic_services").is_some() {
+            self.cc_generic_services.clone_from(&src.cc_generic_services);
+        }
+        if mask.get("java_generic_services").is_some() {
+            self.java_generic_services.clone_from(&src.java_generic_services);
+        }
+        if mask.get("py_generic_services").is_some() {
+            self.py_generic_services.clone_from(&src.py_generic_services);
+        }
+        if mask.get("php_generic_services").is_some() {
+            self.php_generic_services.clone_from(&src.php_generic_services);
+        }
+        if mask.get("deprecated").is_some() {
+            self.deprecated.clone_from(&src.deprecated);
+        }
+        if mask.get("cc_enable_arenas").is_some() {
+            self.cc_enable_arenas.clone_from(&src.cc_enable_arenas);
+        }
+        if mask.get("objc_class_prefix").is_some() {
+            self.objc_class_prefix.clone_from(&src.objc_class_prefix);
+        }
+        if mask.get("csharp_namespace").is_some() {
+            self.csharp_namespace.clone_from(&src.csharp_namespace);
+        }
+        if mask.get("swift_prefix").is_some() {
+            self.swift_prefix.clone_from(&src.swift_prefix);
+        }
+        if mask.get("php_class_prefix").is_some() {
+            self.php_class_prefix.clone_from(&src.php_class_prefix);
+        }
+        if mask.get("php_namespace").is_some() {
+            self.php_namespace.clone_from(&src.php_namespace);
+        }
+        if mask.get("php_metadata_namespace").is_some() {
+            self.php_metadata_namespace.clone_from(&src.php_metadata_namespace);
+        }
+        if mask.get("ruby_package").is_some() {
+            self.ruby_package.clone_from(&src.ruby_package);
+        }
+        if mask.get("uninterpreted_option").is_some() {
+            ::prost::field_mask::merge_repeated(
+                &mut self.uninterpreted_option,
+                &src.uninterpreted_option,
+                options,
+            );
+        }
+    }
+    fn prune_with_mask_tree(&mut self, mask: &::prost::field_mask::FieldMaskTree) {
+        let default = Self::default();
+        if mask.get("java_package").is_none() {
+            self.java_package = default.java_package;
+        }
+        if mask.get("java_outer_classname").is_none() {
+            self.java_outer_classname = default.java_outer_classname;
+        }
+        if mask.get("java_multiple_files").is_none() {
+            self.java_multiple_files = default.java_multiple_files;
+        }
+        if mask.get("java_generate_equals_and_hash").is_none() {
+            self.java_generate_equals_and_hash = default.java_generate_equals_and_hash;
+        }
+        if mask.get("java_string_check_utf8").is_none() {
+            self.java_string_check_utf8 = default.java_string_check_utf8;
+        }
+        if mask.get("optimize_for").is_none() {
+            self.optimize_for = default.optimize_for;
+        }
+        if mask.get("go_package").is_none() {
+            self.go_package = default.go_package;
+        }
+        if mask.get("cc_generic_services").is_none() {
+            self.cc_generic_services = default.cc_generic_services;
+        }
+        if mask.get("java_generic_services").is_none() {
+            self.java_generic_services = default.java_generic_services;
+        }
+        if mask.get("py_generic_services").is_none() {
+            self.py_generic_services = default.py_generic_services;
+        }
+        if mask.get("php_generic_services").is_none() {
+            self.php_generic_services = default.php_generic_services;
+        }
+        if mask.get("deprecated").is_none() {
+            self.deprecated = default.deprecated;
+        }
+        if mask.get("cc_enable_arenas").is_none() {
+            self.cc_enable_arenas = default.cc_enable_arenas;
+        }
+        if mask.get("objc_class_prefix").is_none() {
+            self.objc_class_prefix = default.objc_class_prefix;
+        }
+        if mask.get("csharp_namespace").is_none() {
+            self.csharp_namespace = default.csharp_namespace;
+        }
+        if mask.get("swift_prefix").is_none() {
+            self.swift_prefix = default.swift_prefix;
+        }
+        if mask.get("php_class_prefix").is_none() {
+            self.php_class_prefix = default.php_class_prefix;
+        }
+        if mask.get("php_namespace").is_none() {
+            self.php_namespace = default.php_namespace;
+        }
+        if mask.get("php_metadata_namespace").is_none() {
+            self.php_metadata_namespace = default.php_metadata_namespace;
+        }
+        if mask.get("ruby_package").is_none() {
+            self.ruby_package = default.ruby_package;
+        }
+        if mask.get("uninterpreted_option").is_none() {
+            self.uninterpreted_option = default.uninterpreted_option;
+        }
+    }
+}
 /// Nested message and enum types in `FileOptions`.
 pub mod file_options {
     /// Generated classes can be optimized for speed or code size.
@@ -667,6 +3871,231 @@ pub struct MessageOptions {
     #[prost(message, repeated, tag = "999")]
     pub uninterpreted_option: ::prost::alloc::vec::Vec<UninterpretedOption>,
 }
+#[allow(deprecated)]
+impl ::prost::text_format::TextFormat for MessageOptions {
+    fn print_fields(&self, printer: &mut ::prost::text_format::Printer<'_>) {
+        if let ::core::option::Option::Some(value) = &self.message_set_wire_format {
+            printer.scalar("message_set_wire_format", value);
+        }
+        if let ::core::option::Option::Some(value) = &self
+            .no_standard_descriptor_accessor
+        {
+            printer.scalar("no_standard_descriptor_accessor", value);
+        }
+        if let ::core::option::Option::Some(value) = &self.deprecated {
+            printer.scalar("deprecated", value);
+        }
+        if let ::core::option::Option::Some(value) = &self.map_entry {
+            printer.scalar("map_entry", value);
+        }
+        for value in &self.uninterpreted_option {
+            printer.message("uninterpreted_option", value);
+        }
+    }
+    fn merge_text_field(
+        &mut self,
+        name: &str,
+        parser: &mut ::prost::text_format::Parser<'_>,
+    ) -> ::core::result::Result<bool, ::prost::text_format::ParseError> {
+        match name {
+            "message_set_wire_format" => {
+                parser
+                    .scalar(
+                        self
+                            .message_set_wire_format
+                            .get_or_insert_with(::core::default::Default::default),
+                    )?
+            }
+            "no_standard_descriptor_accessor" => {
+                parser
+                    .scalar(
+                        self
+                            .no_standard_descriptor_accessor
+                            .get_or_insert_with(::core::default::Default::default),
+                    )?
+            }
+            "deprecated" => {
+                parser
+                    .scalar(
+                        self
+                            .deprecated
+                            .get_or_insert_with(::core::default::Default::default),
+                    )?
+            }
+            "map_entry" => {
+                parser
+                    .scalar(
+                        self
+                            .map_entry
+                            .get_or_insert_with(::core::default::Default::default),
+                    )?
+            }
+            "uninterpreted_option" => {
+                parser.repeated_message(&mut self.uninterpreted_option)?
+            }
+            _ => return ::core::result::Result::Ok(false),
+        }
+        ::core::result::Result::Ok(true)
+    }
+}
+#[allow(deprecated)]
+impl ::prost::json::Json for MessageOptions {
+    fn write_json_fields(
+        &self,
+        writer: &mut ::prost::json::Writer<'_>,
+    ) -> ::core::result::Result<(), ::prost::json::JsonError> {
+        if let ::core::option::Option::Some(value) = &self.message_set_wire_format {
+            writer
+                .field(
+                    "messageSetWireFormat",
+                    "message_set_wire_format",
+                    |writer| writer.scalar(value),
+                )?;
+        }
+        if let ::core::option::Option::Some(value) = &self
+            .no_standard_descriptor_accessor
+        {
+            writer
+                .field(
+                    "noStandardDescriptorAccessor",
+                    "no_standard_descriptor_accessor",
+                    |writer| writer.scalar(value),
+                )?;
+        }
+        if let ::core::option::Option::Some(value) = &self.deprecated {
+            writer.field("deprecated", "deprecated", |writer| writer.scalar(value))?;
+        }
+        if let ::core::option::Option::Some(value) = &self.map_entry {
+            writer.field("mapEntry", "map_entry", |writer| writer.scalar(value))?;
+        }
+        if writer.emit_defaults() || !self.uninterpreted_option.is_empty() {
+            writer
+                .field(
+                    "uninterpretedOption",
+                    "uninterpreted_option",
+                    |writer| {
+                        writer
+                            .list(
+                                &self.uninterpreted_option,
+                                |writer, value| writer.message(value),
+                            )
+                    },
+                )?;
+        }
+        ::core::result::Result::Ok(())
+    }
+    fn merge_json_field(
+        &mut self,
+        name: &str,
+        parser: &mut ::prost::json::Parser<'_>,
+    ) -> ::core::result::Result<bool, ::prost::json::JsonError> {
+        match name {
+            "messageSetWireFormat" | "message_set_wire_format" => {
+                parser
+                    .optional(
+                        &mut self.message_set_wire_format,
+                        |parser| parser.scalar(),
+                    )?
+            }
+            "noStandardDescriptorAccessor" | "no_standard_descriptor_accessor" => {
+                parser
+                    .optional(
+                        &mut self.no_standard_descriptor_accessor,
+                        |parser| parser.scalar(),
+                    )?
+            }
+            "deprecated" => {
+                parser.optional(&mut self.deprecated, |parser| parser.scalar())?
+            }
+            "mapEntry" | "map_entry" => {
+                parser.optional(&mut self.map_entry, |parser| parser.scalar())?
+            }
+            "uninterpretedOption" | "uninterpreted_option" => {
+                parser
+                    .repeated(&mut self.uninterpreted_option, |parser| parser.message())?
+            }
+            _ => return ::core::result::Result::Ok(false),
+        }
+        ::core::result::Result::Ok(true)
+    }
+}
+#[allow(deprecated)]
+impl ::prost::FieldMasked for MessageOptions {
+    fn check_mask_tree(
+        mask: &::prost::field_mask::FieldMaskTree,
+    ) -> ::core::result::Result<(), ::prost::field_mask::FieldMaskError> {
+        for (name, mask) in mask.iter() {
+            match name {
+                "message_set_wire_format" => {
+                    ::prost::field_mask::check_leaf("message_set_wire_format", mask)?
+                }
+                "no_standard_descriptor_accessor" => {
+                    ::prost::field_mask::check_leaf(
+                        "no_standard_descriptor_accessor",
+                        mask,
+                    )?
+                }
+                "deprecated" => ::prost::field_mask::check_leaf("deprecated", mask)?,
+                "map_entry" => ::prost::field_mask::check_leaf("map_entry", mask)?,
+                "uninterpreted_option" => {
+                    ::prost::field_mask::check_leaf("uninterpreted_option", mask)?
+                }
+                _ => {
+                    return ::core::result::Result::Err(
+                        ::prost::field_mask::unknown_field(name),
+                    );
+                }
+            }
+        }
+        ::core::result::Result::Ok(())
+    }
+    fn merge_with_mask_tree(
+        &mut self,
+        src: &Self,
+        mask: &::prost::field_mask::FieldMaskTree,
+        options: &::prost::field_mask::MergeOptions,
+    ) {
+        if mask.get("message_set_wire_format").is_some() {
+            self.message_set_wire_format.clone_from(&src.message_set_wire_format);
+        }
+        if mask.get("no_standard_descriptor_accessor").is_some() {
+            self.no_standard_descriptor_accessor
+                .clone_from(&src.no_standard_descriptor_accessor);
+        }
+        if mask.get("deprecated").is_some() {
+            self.deprecated.clone_from(&src.deprecated);
+        }
+        if mask.get("map_entry").is_some() {
+            self.map_entry.clone_from(&src.map_entry);
+        }
+        if mask.get("uninterpreted_option").is_some() {
+            ::prost::field_mask::merge_repeated(
+                &mut self.uninterpreted_option,
+                &src.uninterpreted_option,
+                options,
+            );
+        }
+    }
+    fn prune_with_mask_tree(&mut self, mask: &::prost::field_mask::FieldMaskTree) {
+        let default = Self::default();
+        if mask.get("message_set_wire_format").is_none() {
+            self.message_set_wire_format = default.message_set_wire_format;
+        }
+        if mask.get("no_standard_descriptor_accessor").is_none() {
+            self.no_standard_descriptor_accessor = default
+                .no_standard_descriptor_accessor;
+        }
+        if mask.get("deprecated").is_none() {
+            self.deprecated = default.deprecated;
+        }
+        if mask.get("map_entry").is_none() {
+            self.map_entry = default.map_entry;
+        }
+        if mask.get("uninterpreted_option").is_none() {
+            self.uninterpreted_option = default.uninterpreted_option;
+        }
+    }
+}
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct FieldOptions {
@@ -748,6 +4177,310 @@ pub struct FieldOptions {
     #[prost(message, repeated, tag = "999")]
     pub uninterpreted_option: ::prost::alloc::vec::Vec<UninterpretedOption>,
 }
+#[allow(deprecated)]
+impl ::prost::text_format::TextFormat for FieldOptions {
+    fn print_fields(&self, printer: &mut ::prost::text_format::Printer<'_>) {
+        if let ::core::option::Option::Some(value) = &self.ctype {
+            printer
+                .enumeration(
+                    "ctype",
+                    *value,
+                    |value| {
+                        field_options::CType::try_from(value)
+                            .ok()
+                            .map(|value| value.as_str_name())
+                    },
+                );
+        }
+        if let ::core::option::Option::Some(value) = &self.packed {
+            printer.scalar("packed", value);
+        }
+        if let ::core::option::Option::Some(value) = &self.deprecated {
+            printer.scalar("deprecated", value);
+        }
+        if let ::core::option::Option::Some(value) = &self.lazy {
+            printer.scalar("lazy", value);
+        }
+        if let ::core::option::Option::Some(value) = &self.jstype {
+            printer
+                .enumeration(
+                    "jstype",
+                    *value,
+                    |value| {
+                        field_options::JsType::try_from(value)
+                            .ok()
+                            .map(|value| value.as_str_name())
+                    },
+                );
+        }
+        if let ::core::option::Option::Some(value) = &self.weak {
+            printer.scalar("weak", value);
+        }
+        for value in &self.uninterpreted_option {
+            printer.message("uninterpreted_option", value);
+        }
+    }
+    fn merge_text_field(
+        &mut self,
+        name: &str,
+        parser: &mut ::prost::text_format::Parser<'_>,
+    ) -> ::core::result::Result<bool, ::prost::text_format::ParseError> {
+        match name {
+            "ctype" => {
+                parser
+                    .enumeration(
+                        self.ctype.get_or_insert_with(::core::default::Default::default),
+                        |name| {
+                            field_options::CType::from_str_name(name)
+                                .map(|value| value as i32)
+                        },
+                    )?
+            }
+            "packed" => {
+                parser
+                    .scalar(
+                        self.packed.get_or_insert_with(::core::default::Default::default),
+                    )?
+            }
+            "jstype" => {
+                parser
+                    .enumeration(
+                        self
+                            .jstype
+                            .get_or_insert_with(::core::default::Default::default),
+                        |name| {
+                            field_options::JsType::from_str_name(name)
+                                .map(|value| value as i32)
+                        },
+                    )?
+            }
+            "lazy" => {
+                parser
+                    .scalar(
+                        self.lazy.get_or_insert_with(::core::default::Default::default),
+                    )?
+            }
+            "deprecated" => {
+                parser
+                    .scalar(
+                        self
+                            .deprecated
+                            .get_or_insert_with(::core::default::Default::default),
+                    )?
+            }
+            "weak" => {
+                parser
+                    .scalar(
+                        self.weak.get_or_insert_with(::core::default::Default::default),
+                    )?
+            }
+            "uninterpreted_option" => {
+                parser.repeated_message(&mut self.uninterpreted_option)?
+            }
+            _ => return ::core::result::Result::Ok(false),
+        }
+        ::core::result::Result::Ok(true)
+    }
+}
+#[allow(deprecated)]
+impl ::prost::json::Json for FieldOptions {
+    fn write_json_fields(
+        &self,
+        writer: &mut ::prost::json::Writer<'_>,
+    ) -> ::core::result::Result<(), ::prost::json::JsonError> {
+        if let ::core::option::Option::Some(value) = &self.ctype {
+            writer
+                .field(
+                    "ctype",
+                    "ctype",
+                    |writer| {
+                        writer
+                            .enumeration(
+                                *value,
+                                |value| {
+                                    field_options::CType::try_from(value)
+                                        .ok()
+                                        .map(|value| value.as_str_name())
+                                },
+                            )
+                    },
+                )?;
+        }
+        if let ::core::option::Option::Some(value) = &self.packed {
+            writer.field("packed", "packed", |writer| writer.scalar(value))?;
+        }
+        if let ::core::option::Option::Some(value) = &self.deprecated {
+            writer.field("deprecated", "deprecated", |writer| writer.scalar(value))?;
+        }
+        if let ::core::option::Option::Some(value) = &self.lazy {
+            writer.field("lazy", "lazy", |writer| writer.scalar(value))?;
+        }
+        if let ::core::option::Option::Some(value) = &self.jstype {
+            writer
+                .field(
+                    "jstype",
+                    "jstype",
+                    |writer| {
+                        writer
+                            .enumeration(
+                                *value,
+                                |value| {
+                                    field_options::JsType::try_from(value)
+                                        .ok()
+                                        .map(|value| value.as_str_name())
+                                },
+                            )
+                    },
+                )?;
+        }
+        if let ::core::option::Option::Some(value) = &self.weak {
+            writer.field("weak", "weak", |writer| writer.scalar(value))?;
+        }
+        if writer.emit_defaults() || !self.uninterpreted_option.is_empty() {
+            writer
+                .field(
+                    "uninterpretedOption",
+                    "uninterpreted_option",
+                    |writer| {
+                        writer
+                            .list(
+                                &self.uninterpreted_option,
+                                |writer, value| writer.message(value),
+                            )
+                    },
+                )?;
+        }
+        ::core::result::Result::Ok(())
+    }
+    fn merge_json_field(
+        &mut self,
+        name: &str,
+        parser: &mut ::prost::json::Parser<'_>,
+    ) -> ::core::result::Result<bool, ::prost::json::JsonError> {
+        match name {
+            "ctype" => {
+                parser
+                    .optional(
+                        &mut self.ctype,
+                        |parser| {
+                            parser
+                                .enumeration(|name| {
+                                    field_options::CType::from_str_name(name)
+                                        .map(|value| value as i32)
+                                })
+                        },
+                    )?
+            }
+            "packed" => parser.optional(&mut self.packed, |parser| parser.scalar())?,
+            "jstype" => {
+                parser
+                    .optional(
+                        &mut self.jstype,
+                        |parser| {
+                            parser
+                                .enumeration(|name| {
+                                    field_options::JsType::from_str_name(name)
+                                        .map(|value| value as i32)
+                                })
+                        },
+                    )?
+            }
+            "lazy" => parser.optional(&mut self.lazy, |parser| parser.scalar())?,
+            "deprecated" => {
+                parser.optional(&mut self.deprecated, |parser| parser.scalar())?
+            }
+            "weak" => parser.optional(&mut self.weak, |parser| parser.scalar())?,
+            "uninterpretedOption" | "uninterpreted_option" => {
+                parser
+                    .repeated(&mut self.uninterpreted_option, |parser| parser.message())?
+            }
+            _ => return ::core::result::Result::Ok(false),
+        }
+        ::core::result::Result::Ok(true)
+    }
+}
+#[allow(deprecated)]
+impl ::prost::FieldMasked for FieldOptions {
+    fn check_mask_tree(
+        mask: &::prost::field_mask::FieldMaskTree,
+    ) -> ::core::result::Result<(), ::prost::field_mask::FieldMaskError> {
+        for (name, mask) in mask.iter() {
+            match name {
+                "ctype" => ::prost::field_mask::check_leaf("ctype", mask)?,
+                "packed" => ::prost::field_mask::check_leaf("packed", mask)?,
+                "jstype" => ::prost::field_mask::check_leaf("jstype", mask)?,
+                "lazy" => ::prost::field_mask::check_leaf("lazy", mask)?,
+                "deprecated" => ::prost::field_mask::check_leaf("deprecated", mask)?,
+                "weak" => ::prost::field_mask::check_leaf("weak", mask)?,
+                "uninterpreted_option" => {
+                    ::prost::field_mask::check_leaf("uninterpreted_option", mask)?
+                }
+                _ => {
+                    return ::core::result::Result::Err(
+                        ::prost::field_mask::unknown_field(name),
+                    );
+                }
+            }
+        }
+        ::core::result::Result::Ok(())
+    }
+    fn merge_with_mask_tree(
+        &mut self,
+        src: &Self,
+        mask: &::prost::field_mask::FieldMaskTree,
+        options: &::prost::field_mask::MergeOptions,
+    ) {
+        if mask.get("ctype").is_some() {
+            self.ctype.clone_from(&src.ctype);
+        }
+        if mask.get("packed").is_some() {
+            self.packed.clone_from(&src.packed);
+        }
+        if mask.get("jstype").is_some() {
+            self.jstype.clone_from(&src.jstype);
+        }
+        if mask.get("lazy").is_some() {
+            self.lazy.clone_from(&src.lazy);
+        }
+        if mask.get("deprecated").is_some() {
+            self.deprecated.clone_from(&src.deprecated);
+        }
+        if mask.get("weak").is_some() {
+            self.weak.clone_from(&src.weak);
+        }
+        if mask.get("uninterpreted_option").is_some() {
+            ::prost::field_mask::merge_repeated(
+                &mut self.uninterpreted_option,
+                &src.uninterpreted_option,
+                options,
+            );
+        }
+    }
+    fn prune_with_mask_tree(&mut self, mask: &::prost::field_mask::FieldMaskTree) {
+        let default = Self::default();
+        if mask.get("ctype").is_none() {
+            self.ctype = default.ctype;
+        }
+        if mask.get("packed").is_none() {
+            self.packed = default.packed;
+        }
+        if mask.get("jstype").is_none() {
+            self.jstype = default.jstype;
+        }
+        if mask.get("lazy").is_none() {
+            self.lazy = default.lazy;
+        }
+        if mask.get("deprecated").is_none() {
+            self.deprecated = default.deprecated;
+        }
+        if mask.get("weak").is_none() {
+            self.weak = default.weak;
+        }
+        if mask.get("uninterpreted_option").is_none() {
+            self.uninterpreted_option = default.uninterpreted_option;
+        }
+    }
+}
 /// Nested message and enum types in `FieldOptions`.
 pub mod field_options {
     #[derive(
@@ -840,6 +4573,104 @@ pub struct OneofOptions {
     #[prost(message, repeated, tag = "999")]
     pub uninterpreted_option: ::prost::alloc::vec::Vec<UninterpretedOption>,
 }
+#[allow(deprecated)]
+impl ::prost::text_format::TextFormat for OneofOptions {
+    fn print_fields(&self, printer: &mut ::prost::text_format::Printer<'_>) {
+        for value in &self.uninterpreted_option {
+            printer.message("uninterpreted_option", value);
+        }
+    }
+    fn merge_text_field(
+        &mut self,
+        name: &str,
+        parser: &mut ::prost::text_format::Parser<'_>,
+    ) -> ::core::result::Result<bool, ::prost::text_format::ParseError> {
+        match name {
+            "uninterpreted_option" => {
+                parser.repeated_message(&mut self.uninterpreted_option)?
+            }
+            _ => return ::core::result::Result::Ok(false),
+        }
+        ::core::result::Result::Ok(true)
+    }
+}
+#[allow(deprecated)]
+impl ::prost::json::Json for OneofOptions {
+    fn write_json_fields(
+        &self,
+        writer: &mut ::prost::json::Writer<'_>,
+    ) -> ::core::result::Result<(), ::prost::json::JsonError> {
+        if writer.emit_defaults() || !self.uninterpreted_option.is_empty() {
+            writer
+                .field(
+                    "uninterpretedOption",
+                    "uninterpreted_option",
+                    |writer| {
+                        writer
+                            .list(
+                                &self.uninterpreted_option,
+                                |writer, value| writer.message(value),
+                            )
+                    },
+                )?;
+        }
+        ::core::result::Result::Ok(())
+    }
+    fn merge_json_field(
+        &mut self,
+        name: &str,
+        parser: &mut ::prost::json::Parser<'_>,
+    ) -> ::core::result::Result<bool, ::prost::json::JsonError> {
+        match name {
+            "uninterpretedOption" | "uninterpreted_option" => {
+                parser
+                    .repeated(&mut self.uninterpreted_option, |parser| parser.message())?
+            }
+            _ => return ::core::result::Result::Ok(false),
+        }
+        ::core::result::Result::Ok(true)
+    }
+}
+#[allow(deprecated)]
+impl ::prost::FieldMasked for OneofOptions {
+    fn check_mask_tree(
+        mask: &::prost::field_mask::FieldMaskTree,
+    ) -> ::core::result::Result<(), ::prost::field_mask::FieldMaskError> {
+        for (name, mask) in mask.iter() {
+            match name {
+                "uninterpreted_option" => {
+                    ::prost::field_mask::check_leaf("uninterpreted_option", mask)?
+                }
+                _ => {
+                    return ::core::result::Result::Err(
+                        ::prost::field_mask::unknown_field(name),
+                    );
+                }
+            }
+        }
+        ::core::result::Result::Ok(())
+    }
+    fn merge_with_mask_tree(
+        &mut self,
+        src: &Self,
+        mask: &::prost::field_mask::FieldMaskTree,
+        options: &::prost::field_mask::MergeOptions,
+    ) {
+        if mask.get("uninterpreted_option").is_some() {
+            ::prost::field_mask::merge_repeated(
+                &mut self.uninterpreted_option,
+                &src.uninterpreted_option,
+                options,
+            );
+        }
+    }
+    fn prune_with_mask_tree(&mut self, mask: &::prost::field_mask::FieldMaskTree) {
+        let default = Self::default();
+        if mask.get("uninterpreted_option").is_none() {
+            self.uninterpreted_option = default.uninterpreted_option;
+        }
+    }
+}
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct EnumOptions {
@@ -857,6 +4688,152 @@ pub struct EnumOptions {
     #[prost(message, repeated, tag = "999")]
     pub uninterpreted_option: ::prost::alloc::vec::Vec<UninterpretedOption>,
 }
+#[allow(deprecated)]
+impl ::prost::text_format::TextFormat for EnumOptions {
+    fn print_fields(&self, printer: &mut ::prost::text_format::Printer<'_>) {
+        if let ::core::option::Option::Some(value) = &self.allow_alias {
+            printer.scalar("allow_alias", value);
+        }
+        if let ::core::option::Option::Some(value) = &self.deprecated {
+            printer.scalar("deprecated", value);
+        }
+        for value in &self.uninterpreted_option {
+            printer.message("uninterpreted_option", value);
+        }
+    }
+    fn merge_text_field(
+        &mut self,
+        name: &str,
+        parser: &mut ::prost::text_format::Parser<'_>,
+    ) -> ::core::result::Result<bool, ::prost::text_format::ParseError> {
+        match name {
+            "allow_alias" => {
+                parser
+                    .scalar(
+                        self
+                            .allow_alias
+                            .get_or_insert_with(::core::default::Default::default),
+                    )?
+            }
+            "deprecated" => {
+                parser
+                    .scalar(
+                        self
+                            .deprecated
+                            .get_or_insert_with(::core::default::Default::default),
+                    )?
+            }
+            "uninterpreted_option" => {
+                parser.repeated_message(&mut self.uninterpreted_option)?
+            }
+            _ => return ::core::result::Result::Ok(false),
+        }
+        ::core::result::Result::Ok(true)
+    }
+}
+#[allow(deprecated)]
+impl ::prost::json::Json for EnumOptions {
+    fn write_json_fields(
+        &self,
+        writer: &mut ::prost::json::Writer<'_>,
+    ) -> ::core::result::Result<(), ::prost::json::JsonError> {
+        if let ::core::option::Option::Some(value) = &self.allow_alias {
+            writer.field("allowAlias", "allow_alias", |writer| writer.scalar(value))?;
+        }
+        if let ::core::option::Option::Some(value) = &self.deprecated {
+            writer.field("deprecated", "deprecated", |writer| writer.scalar(value))?;
+        }
+        if writer.emit_defaults() || !self.uninterpreted_option.is_empty() {
+            writer
+                .field(
+                    "uninterpretedOption",
+                    "uninterpreted_option",
+                    |writer| {
+                        writer
+                            .list(
+                                &self.uninterpreted_option,
+                                |writer, value| writer.message(value),
+                            )
+                    },
+                )?;
+        }
+        ::core::result::Result::Ok(())
+    }
+    fn merge_json_field(
+        &mut self,
+        name: &str,
+        parser: &mut ::prost::json::Parser<'_>,
+    ) -> ::core::result::Result<bool, ::prost::json::JsonError> {
+        match name {
+            "allowAlias" | "allow_alias" => {
+                parser.optional(&mut self.allow_alias, |parser| parser.scalar())?
+            }
+            "deprecated" => {
+                parser.optional(&mut self.deprecated, |parser| parser.scalar())?
+            }
+            "uninterpretedOption" | "uninterpreted_option" => {
+                parser
+                    .repeated(&mut self.uninterpreted_option, |parser| parser.message())?
+            }
+            _ => return ::core::result::Result::Ok(false),
+        }
+        ::core::result::Result::Ok(true)
+    }
+}
+#[allow(deprecated)]
+impl ::prost::FieldMasked for EnumOptions {
+    fn check_mask_tree(
+        mask: &::prost::field_mask::FieldMaskTree,
+    ) -> ::core::result::Result<(), ::prost::field_mask::FieldMaskError> {
+        for (name, mask) in mask.iter() {
+            match name {
+                "allow_alias" => ::prost::field_mask::check_leaf("allow_alias", mask)?,
+                "deprecated" => ::prost::field_mask::check_leaf("deprecated", mask)?,
+                "uninterpreted_option" => {
+                    ::prost::field_mask::check_leaf("uninterpreted_option", mask)?
+                }
+                _ => {
+                    return ::core::result::Result::Err(
+                        ::prost::field_mask::unknown_field(name),
+                    );
+                }
+            }
+        }
+        ::core::result::Result::Ok(())
+    }
+    fn merge_with_mask_tree(
+        &mut self,
+        src: &Self,
+        mask: &::prost::field_mask::FieldMaskTree,
+        options: &::prost::field_mask::MergeOptions,
+    ) {
+        if mask.get("allow_alias").is_some() {
+            self.allow_alias.clone_from(&src.allow_alias);
+        }
+        if mask.get("deprecated").is_some() {
+            self.deprecated.clone_from(&src.deprecated);
+        }
+        if mask.get("uninterpreted_option").is_some() {
+            ::prost::field_mask::merge_repeated(
+                &mut self.uninterpreted_option,
+                &src.uninterpreted_option,
+                options,
+            );
+        }
+    }
+    fn prune_with_mask_tree(&mut self, mask: &::prost::field_mask::FieldMaskTree) {
+        let default = Self::default();
+        if mask.get("allow_alias").is_none() {
+            self.allow_alias = default.allow_alias;
+        }
+        if mask.get("deprecated").is_none() {
+            self.deprecated = default.deprecated;
+        }
+        if mask.get("uninterpreted_option").is_none() {
+            self.uninterpreted_option = default.uninterpreted_option;
+        }
+    }
+}
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct EnumValueOptions {
@@ -870,6 +4847,128 @@ pub struct EnumValueOptions {
     #[prost(message, repeated, tag = "999")]
     pub uninterpreted_option: ::prost::alloc::vec::Vec<UninterpretedOption>,
 }
+#[allow(deprecated)]
+impl ::prost::text_format::TextFormat for EnumValueOptions {
+    fn print_fields(&self, printer: &mut ::prost::text_format::Printer<'_>) {
+        if let ::core::option::Option::Some(value) = &self.deprecated {
+            printer.scalar("deprecated", value);
+        }
+        for value in &self.uninterpreted_option {
+            printer.message("uninterpreted_option", value);
+        }
+    }
+    fn merge_text_field(
+        &mut self,
+        name: &str,
+        parser: &mut ::prost::text_format::Parser<'_>,
+    ) -> ::core::result::Result<bool, ::prost::text_format::ParseError> {
+        match name {
+            "deprecated" => {
+                parser
+                    .scalar(
+                        self
+                            .deprecated
+                            .get_or_insert_with(::core::default::Default::default),
+                    )?
+            }
+            "uninterpreted_option" => {
+                parser.repeated_message(&mut self.uninterpreted_option)?
+            }
+            _ => return ::core::result::Result::Ok(false),
+        }
+        ::core::result::Result::Ok(true)
+    }
+}
+#[allow(deprecated)]
+impl ::prost::json::Json for EnumValueOptions {
+    fn write_json_fields(
+        &self,
+        writer: &mut ::prost::json::Writer<'_>,
+    ) -> ::core::result::Result<(), ::prost::json::JsonError> {
+        if let ::core::option::Option::Some(value) = &self.deprecated {
+            writer.field("deprecated", "deprecated", |writer| writer.scalar(value))?;
+        }
+        if writer.emit_defaults() || !self.uninterpreted_option.is_empty() {
+            writer
+                .field(
+                    "uninterpretedOption",
+                    "uninterpreted_option",
+                    |writer| {
+                        writer
+                            .list(
+                                &self.uninterpreted_option,
+                                |writer, value| writer.message(value),
+                            )
+                    },
+                )?;
+        }
+        ::core::result::Result::Ok(())
+    }
+    fn merge_json_field(
+        &mut self,
+        name: &str,
+        parser: &mut ::prost::json::Parser<'_>,
+    ) -> ::core::result::Result<bool, ::prost::json::JsonError> {
+        match name {
+            "deprecated" => {
+                parser.optional(&mut self.deprecated, |parser| parser.scalar())?
+            }
+            "uninterpretedOption" | "uninterpreted_option" => {
+                parser
+                    .repeated(&mut self.uninterpreted_option, |parser| parser.message())?
+            }
+            _ => return ::core::result::Result::Ok(false),
+        }
+        ::core::result::Result::Ok(true)
+    }
+}
+#[allow(deprecated)]
+impl ::prost::FieldMasked for EnumValueOptions {
+    fn check_mask_tree(
+        mask: &::prost::field_mask::FieldMaskTree,
+    ) -> ::core::result::Result<(), ::prost::field_mask::FieldMaskError> {
+        for (name, mask) in mask.iter() {
+            match name {
+                "deprecated" => ::prost::field_mask::check_leaf("deprecated", mask)?,
+                "uninterpreted_option" => {
+                    ::prost::field_mask::check_leaf("uninterpreted_option", mask)?
+                }
+                _ => {
+                    return ::core::result::Result::Err(
+                        ::prost::field_mask::unknown_field(name),
+                    );
+                }
+            }
+        }
+        ::core::result::Result::Ok(())
+    }
+    fn merge_with_mask_tree(
+        &mut self,
+        src: &Self,
+        mask: &::prost::field_mask::FieldMaskTree,
+        options: &::prost::field_mask::MergeOptions,
+    ) {
+        if mask.get("deprecated").is_some() {
+            self.deprecated.clone_from(&src.deprecated);
+        }
+        if mask.get("uninterpreted_option").is_some() {
+            ::prost::field_mask::merge_repeated(
+                &mut self.uninterpreted_option,
+                &src.uninterpreted_option,
+                options,
+            );
+        }
+    }
+    fn prune_with_mask_tree(&mut self, mask: &::prost::field_mask::FieldMaskTree) {
+        let default = Self::default();
+        if mask.get("deprecated").is_none() {
+            self.deprecated = default.deprecated;
+        }
+        if mask.get("uninterpreted_option").is_none() {
+            self.uninterpreted_option = default.uninterpreted_option;
+        }
+    }
+}
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct ServiceOptions {
@@ -883,6 +4982,128 @@ pub struct ServiceOptions {
     #[prost(message, repeated, tag = "999")]
     pub uninterpreted_option: ::prost::alloc::vec::Vec<UninterpretedOption>,
 }
+#[allow(deprecated)]
+impl ::prost::text_format::TextFormat for ServiceOptions {
+    fn print_fields(&self, printer: &mut ::prost::text_format::Printer<'_>) {
+        if let ::core::option::Option::Some(value) = &self.deprecated {
+            printer.scalar("deprecated", value);
+        }
+        for value in &self.uninterpreted_option {
+            printer.message("uninterpreted_option", value);
+        }
+    }
+    fn merge_text_field(
+        &mut self,
+        name: &str,
+        parser: &mut ::prost::text_format::Parser<'_>,
+    ) -> ::core::result::Result<bool, ::prost::text_format::ParseError> {
+        match name {
+            "deprecated" => {
+                parser
+                    .scalar(
+                        self
+                            .deprecated
+                            .get_or_insert_with(::core::default::Default::default),
+                    )?
+            }
+            "uninterpreted_option" => {
+                parser.repeated_message(&mut self.uninterpreted_option)?
+            }
+            _ => return ::core::result::Result::Ok(false),
+        }
+        ::core::result::Result::Ok(true)
+    }
+}
+#[allow(deprecated)]
+impl ::prost::json::Json for ServiceOptions {
+    fn write_json_fields(
+        &self,
+        writer: &mut ::prost::json::Writer<'_>,
+    ) -> ::core::result::Result<(), ::prost::json::JsonError> {
+        if let ::core::option::Option::Some(value) = &self.deprecated {
+            writer.field("deprecated", "deprecated", |writer| writer.scalar(value))?;
+        }
+        if writer.emit_defaults() || !self.uninterpreted_option.is_empty() {
+            writer
+                .field(
+                    "uninterpretedOption",
+                    "uninterpreted_option",
+                    |writer| {
+                        writer
+                            .list(
+                                &self.uninterpreted_option,
+                                |writer, value| writer.message(value),
+                            )
+                    },
+                )?;
+        }
+        ::core::result::Result::Ok(())
+    }
+    fn merge_json_field(
+        &mut self,
+        name: &str,
+        parser: &mut ::prost::json::Parser<'_>,
+    ) -> ::core::result::Result<bool, ::prost::json::JsonError> {
+        match name {
+            "deprecated" => {
+                parser.optional(&mut self.deprecated, |parser| parser.scalar())?
+            }
+            "uninterpretedOption" | "uninterpreted_option" => {
+                parser
+                    .repeated(&mut self.uninterpreted_option, |parser| parser.message())?
+            }
+            _ => return ::core::result::Result::Ok(false),
+        }
+        ::core::result::Result::Ok(true)
+    }
+}
+#[allow(deprecated)]
+impl ::prost::FieldMasked for ServiceOptions {
+    fn check_mask_tree(
+        mask: &::prost::field_mask::FieldMaskTree,
+    ) -> ::core::result::Result<(), ::prost::field_mask::FieldMaskError> {
+        for (name, mask) in mask.iter() {
+            match name {
+                "deprecated" => ::prost::field_mask::check_leaf("deprecated", mask)?,
+                "uninterpreted_option" => {
+                    ::prost::field_mask::check_leaf("uninterpreted_option", mask)?
+                }
+                _ => {
+                    return ::core::result::Result::Err(
+                        ::prost::field_mask::unknown_field(name),
+                    );
+                }
+            }
+        }
+        ::core::result::Result::Ok(())
+    }
+    fn merge_with_mask_tree(
+        &mut self,
+        src: &Self,
+        mask: &::prost::field_mask::FieldMaskTree,
+        options: &::prost::field_mask::MergeOptions,
+    ) {
+        if mask.get("deprecated").is_some() {
+            self.deprecated.clone_from(&src.deprecated);
+        }
+        if mask.get("uninterpreted_option").is_some() {
+            ::prost::field_mask::merge_repeated(
+                &mut self.uninterpreted_option,
+                &src.uninterpreted_option,
+                options,
+            );
+        }
+    }
+    fn prune_with_mask_tree(&mut self, mask: &::prost::field_mask::FieldMaskTree) {
+        let default = Self::default();
+        if mask.get("deprecated").is_none() {
+            self.deprecated = default.deprecated;
+        }
+        if mask.get("uninterpreted_option").is_none() {
+            self.uninterpreted_option = default.uninterpreted_option;
+        }
+    }
+}
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct MethodOptions {
@@ -903,6 +5124,192 @@ pub struct MethodOptions {
     #[prost(message, repeated, tag = "999")]
     pub uninterpreted_option: ::prost::alloc::vec::Vec<UninterpretedOption>,
 }
+#[allow(deprecated)]
+impl ::prost::text_format::TextFormat for MethodOptions {
+    fn print_fields(&self, printer: &mut ::prost::text_format::Printer<'_>) {
+        if let ::core::option::Option::Some(value) = &self.deprecated {
+            printer.scalar("deprecated", value);
+        }
+        if let ::core::option::Option::Some(value) = &self.idempotency_level {
+            printer
+                .enumeration(
+                    "idempotency_level",
+                    *value,
+                    |value| {
+                        method_options::IdempotencyLevel::try_from(value)
+                            .ok()
+                            .map(|value| value.as_str_name())
+                    },
+                );
+        }
+        for value in &self.uninterpreted_option {
+            printer.message("uninterpreted_option", value);
+        }
+    }
+    fn merge_text_field(
+        &mut self,
+        name: &str,
+        parser: &mut ::prost::text_format::Parser<'_>,
+    ) -> ::core::result::Result<bool, ::prost::text_format::ParseError> {
+        match name {
+            "deprecated" => {
+                parser
+                    .scalar(
+                        self
+                            .deprecated
+                            .get_or_insert_with(::core::default::Default::default),
+                    )?
+            }
+            "idempotency_level" => {
+                parser
+                    .enumeration(
+                        self
+                            .idempotency_level
+                            .get_or_insert_with(::core::default::Default::default),
+                        |name| {
+                            method_options::IdempotencyLevel::from_str_name(name)
+                                .map(|value| value as i32)
+                        },
+                    )?
+            }
+            "uninterpreted_option" => {
+                parser.repeated_message(&mut self.uninterpreted_option)?
+            }
+            _ => return ::core::result::Result::Ok(false),
+        }
+        ::core::result::Result::Ok(true)
+    }
+}
+#[allow(deprecated)]
+impl ::prost::json::Json for MethodOptions {
+    fn write_json_fields(
+        &self,
+        writer: &mut ::prost::json::Writer<'_>,
+    ) -> ::core::result::Result<(), ::prost::json::JsonError> {
+        if let ::core::option::Option::Some(value) = &self.deprecated {
+            writer.field("deprecated", "deprecated", |writer| writer.scalar(value))?;
+        }
+        if let ::core::option::Option::Some(value) = &self.idempotency_level {
+            writer
+                .field(
+                    "idempotencyLevel",
+                    "idempotency_level",
+                    |writer| {
+                        writer
+                            .enumeration(
+                                *value,
+                                |value| {
+                                    method_options::IdempotencyLevel::try_from(value)
+                                        .ok()
+                                        .map(|value| value.as_str_name())
+                                },
+                            )
+                    },
+                )?;
+        }
+        if writer.emit_defaults() || !self.uninterpreted_option.is_empty() {
+            writer
+                .field(
+                    "uninterpretedOption",
+                    "uninterpreted_option",
+                    |writer| {
+                        writer
+                            .list(
+                                &self.uninterpreted_option,
+                                |writer, value| writer.message(value),
+                            )
+                    },
+                )?;
+        }
+        ::core::result::Result::Ok(())
+    }
+    fn merge_json_field(
+        &mut self,
+        name: &str,
+        parser: &mut ::prost::json::Parser<'_>,
+    ) -> ::core::result::Result<bool, ::prost::json::JsonError> {
+        match name {
+            "deprecated" => {
+                parser.optional(&mut self.deprecated, |parser| parser.scalar())?
+            }
+            "idempotencyLevel" | "idempotency_level" => {
+                parser
+                    .optional(
+                        &mut self.idempotency_level,
+                        |parser| {
+                            parser
+                                .enumeration(|name| {
+                                    method_options::IdempotencyLevel::from_str_name(name)
+                                        .map(|value| value as i32)
+                                })
+                        },
+                    )?
+            }
+            "uninterpretedOption" | "uninterpreted_option" => {
+                parser
+                    .repeated(&mut self.uninterpreted_option, |parser| parser.message())?
+            }
+            _ => return ::core::result::Result::Ok(false),
+        }
+        ::core::result::Result::Ok(true)
+    }
+}
+#[allow(deprecated)]
+impl ::prost::FieldMasked for MethodOptions {
+    fn check_mask_tree(
+        mask: &::prost::field_mask::FieldMaskTree,
+    ) -> ::core::result::Result<(), ::prost::field_mask::FieldMaskError> {
+        for (name, mask) in mask.iter() {
+            match name {
+                "deprecated" => ::prost::field_mask::check_leaf("deprecated", mask)?,
+                "idempotency_level" => {
+                    ::prost::field_mask::check_leaf("idempotency_level", mask)?
+                }
+                "uninterpreted_option" => {
+                    ::prost::field_mask::check_leaf("uninterpreted_option", mask)?
+                }
+                _ => {
+                    return ::core::result::Result::Err(
+                        ::prost::field_mask::unknown_field(name),
+                    );
+                }
+            }
+        }
+        ::core::result::Result::Ok(())
+    }
+    fn merge_with_mask_tree(
+        &mut self,
+        src: &Self,
+        mask: &::prost::field_mask::FieldMaskTree,
+        options: &::prost::field_mask::MergeOptions,
+    ) {
+        if mask.get("deprecated").is_some() {
+            self.deprecated.clone_from(&src.deprecated);
+        }
+        if mask.get("idempotency_level").is_some() {
+            self.idempotency_level.clone_from(&src.idempotency_level);
+        }
+        if mask.get("uninterpreted_option").is_some() {
+            ::prost::field_mask::merge_repeated(
+                &mut self.uninterpreted_option,
+                &src.uninterpreted_option,
+                options,
+            );
+        }
+    }
+    fn prune_with_mask_tree(&mut self, mask: &::prost::field_mask::FieldMaskTree) {
+        let default = Self::default();
+        if mask.get("deprecated").is_none() {
+            self.deprecated = default.deprecated;
+        }
+        if mask.get("idempotency_level").is_none() {
+            self.idempotency_level = default.idempotency_level;
+        }
+        if mask.get("uninterpreted_option").is_none() {
+            self.uninterpreted_option = default.uninterpreted_option;
+        }
+    }
+}
 /// Nested message and enum types in `MethodOptions`.
 pub mod method_options {
     /// Is this method side-effect-free (or safe in HTTP parlance), or idempotent,
@@ -976,6 +5383,261 @@ pub struct UninterpretedOption {
     #[prost(string, optional, tag = "8")]
     pub aggregate_value: ::core::option::Option<::prost::alloc::string::String>,
 }
+#[allow(deprecated)]
+impl ::prost::text_format::TextFormat for UninterpretedOption {
+    fn print_fields(&self, printer: &mut ::prost::text_format::Printer<'_>) {
+        for value in &self.name {
+            printer.message("name", value);
+        }
+        if let ::core::option::Option::Some(value) = &self.identifier_value {
+            printer.scalar("identifier_value", value);
+        }
+        if let ::core::option::Option::Some(value) = &self.positive_int_value {
+            printer.scalar("positive_int_value", value);
+        }
+        if let ::core::option::Option::Some(value) = &self.negative_int_value {
+            printer.scalar("negative_int_value", value);
+        }
+        if let ::core::option::Option::Some(value) = &self.double_value {
+            printer.scalar("double_value", value);
+        }
+        if let ::core::option::Option::Some(value) = &self.string_value {
+            printer.scalar("string_value", value);
+        }
+        if let ::core::option::Option::Some(value) = &self.aggregate_value {
+            printer.scalar("aggregate_value", value);
+        }
+    }
+    fn merge_text_field(
+        &mut self,
+        name: &str,
+        parser: &mut ::prost::text_format::Parser<'_>,
+    ) -> ::core::result::Result<bool, ::prost::text_format::ParseError> {
+        match name {
+            "name" => parser.repeated_message(&mut self.name)?,
+            "identifier_value" => {
+                parser
+                    .scalar(
+                        self
+                            .identifier_value
+                            .get_or_insert_with(::core::default::Default::default),
+                    )?
+            }
+            "positive_int_value" => {
+                parser
+                    .scalar(
+                        self
+                            .positive_int_value
+                            .get_or_insert_with(::core::default::Default::default),
+                    )?
+            }
+            "negative_int_value" => {
+                parser
+                    .scalar(
+                        self
+                            .negative_int_value
+                            .get_or_insert_with(::core::default::Default::default),
+                    )?
+            }
+            "double_value" => {
+                parser
+                    .scalar(
+                        self
+                            .double_value
+                            .get_or_insert_with(::core::default::Default::default),
+                    )?
+            }
+            "string_value" => {
+                parser
+                    .scalar(
+                        self
+                            .string_value
+                            .get_or_insert_with(::core::default::Default::default),
+                    )?
+            }
+            "aggregate_value" => {
+                parser
+                    .scalar(
+                        self
+                            .aggregate_value
+                            .get_or_insert_with(::core::default::Default::default),
+                    )?
+            }
+            _ => return ::core::result::Result::Ok(false),
+        }
+        ::core::result::Result::Ok(true)
+    }
+}
+#[allow(deprecated)]
+impl ::prost::json::Json for UninterpretedOption {
+    fn write_json_fields(
+        &self,
+        writer: &mut ::prost::json::Writer<'_>,
+    ) -> ::core::result::Result<(), ::prost::json::JsonError> {
+        if writer.emit_defaults() || !self.name.is_empty() {
+            writer
+                .field(
+                    "name",
+                    "name",
+                    |writer| {
+                        writer.list(&self.name, |writer, value| writer.message(value))
+                    },
+                )?;
+        }
+        if let ::core::option::Option::Some(value) = &self.identifier_value {
+            writer
+                .field(
+                    "identifierValue",
+                    "identifier_value",
+                    |writer| writer.scalar(value),
+                )?;
+        }
+        if let ::core::option::Option::Some(value) = &self.positive_int_value {
+            writer
+                .field(
+                    "positiveIntValue",
+                    "positive_int_value",
+                    |writer| writer.scalar(value),
+                )?;
+        }
+        if let ::core::option::Option::Some(value) = &self.negative_int_value {
+            writer
+                .field(
+                    "negativeIntValue",
+                    "negative_int_value",
+                    |writer| writer.scalar(value),
+                )?;
+        }
+        if let ::core::option::Option::Some(value) = &self.double_value {
+            writer.field("doubleValue", "double_value", |writer| writer.scalar(value))?;
+        }
+        if let ::core::option::Option::Some(value) = &self.string_value {
+            writer.field("stringValue", "string_value", |writer| writer.scalar(value))?;
+        }
+        if let ::core::option::Option::Some(value) = &self.aggregate_value {
+            writer
+                .field(
+                    "aggregateValue",
+                    "aggregate_value",
+                    |writer| writer.scalar(value),
+                )?;
+        }
+        ::core::result::Result::Ok(())
+    }
+    fn merge_json_field(
+        &mut self,
+        name: &str,
+        parser: &mut ::prost::json::Parser<'_>,
+    ) -> ::core::result::Result<bool, ::prost::json::JsonError> {
+        match name {
+            "name" => parser.repeated(&mut self.name, |parser| parser.message())?,
+            "identifierValue" | "identifier_value" => {
+                parser.optional(&mut self.identifier_value, |parser| parser.scalar())?
+            }
+            "positiveIntValue" | "positive_int_value" => {
+                parser.optional(&mut self.positive_int_value, |parser| parser.scalar())?
+            }
+            "negativeIntValue" | "negative_int_value" => {
+                parser.optional(&mut self.negative_int_value, |parser| parser.scalar())?
+            }
+            "doubleValue" | "double_value" => {
+                parser.optional(&mut self.double_value, |parser| parser.scalar())?
+            }
+            "stringValue" | "string_value" => {
+                parser.optional(&mut self.string_value, |parser| parser.scalar())?
+            }
+            "aggregateValue" | "aggregate_value" => {
+                parser.optional(&mut self.aggregate_value, |parser| parser.scalar())?
+            }
+            _ => return ::core::result::Result::Ok(false),
+        }
+        ::core::result::Result::Ok(true)
+    }
+}
+#[allow(deprecated)]
+impl ::prost::FieldMasked for UninterpretedOption {
+    fn check_mask_tree(
+        mask: &::prost::field_mask::FieldMaskTree,
+    ) -> ::core::result::Result<(), ::prost::field_mask::FieldMaskError> {
+        for (name, mask) in mask.iter() {
+            match name {
+                "name" => ::prost::field_mask::check_leaf("name", mask)?,
+                "identifier_value" => {
+                    ::prost::field_mask::check_leaf("identifier_value", mask)?
+                }
+                "positive_int_value" => {
+                    ::prost::field_mask::check_leaf("positive_int_value", mask)?
+                }
+                "negative_int_value" => {
+                    ::prost::field_mask::check_leaf("negative_int_value", mask)?
+                }
+                "double_value" => ::prost::field_mask::check_leaf("double_value", mask)?,
+                "string_value" => ::prost::field_mask::check_leaf("string_value", mask)?,
+                "aggregate_value" => {
+                    ::prost::field_mask::check_leaf("aggregate_value", mask)?
+                }
+                _ => {
+                    return ::core::result::Result::Err(
+                        ::prost::field_mask::unknown_field(name),
+                    );
+                }
+            }
+        }
+        ::core::result::Result::Ok(())
+    }
+    fn merge_with_mask_tree(
+        &mut self,
+        src: &Self,
+        mask: &::prost::field_mask::FieldMaskTree,
+        options: &::prost::field_mask::MergeOptions,
+    ) {
+        if mask.get("name").is_some() {
+            ::prost::field_mask::merge_repeated(&mut self.name, &src.name, options);
+        }
+        if mask.get("identifier_value").is_some() {
+            self.identifier_value.clone_from(&src.identifier_value);
+        }
+        if mask.get("positive_int_value").is_some() {
+            self.positive_int_value.clone_from(&src.positive_int_value);
+        }
+        if mask.get("negative_int_value").is_some() {
+            self.negative_int_value.clone_from(&src.negative_int_value);
+        }
+        if mask.get("double_value").is_some() {
+            self.double_value.clone_from(&src.double_value);
+        }
+        if mask.get("string_value").is_some() {
+            self.string_value.clone_from(&src.string_value);
+        }
+        if mask.get("aggregate_value").is_some() {
+            self.aggregate_value.clone_from(&src.aggregate_value);
+        }
+    }
+    fn prune_with_mask_tree(&mut self, mask: &::prost::field_mask::FieldMaskTree) {
+        let default = Self::default();
+        if mask.get("name").is_none() {
+            self.name = default.name;
+        }
+        if mask.get("identifier_value").is_none() {
+            self.identifier_value = default.identifier_value;
+        }
+        if mask.get("positive_int_value").is_none() {
+            self.positive_int_value = default.positive_int_value;
+        }
+        if mask.get("negative_int_value").is_none() {
+            self.negative_int_value = default.negative_int_value;
+        }
+        if mask.get("double_value").is_none() {
+            self.double_value = default.double_value;
+        }
+        if mask.get("string_value").is_none() {
+            self.string_value = default.string_value;
+        }
+        if mask.get("aggregate_value").is_none() {
+            self.aggregate_value = default.aggregate_value;
+        }
+    }
+}
 /// Nested message and enum types in `UninterpretedOption`.
 pub mod uninterpreted_option {
     /// The name of the uninterpreted option.  Each string represents a segment in
@@ -991,6 +5653,105 @@ pub mod uninterpreted_option {
         #[prost(bool, required, tag = "2")]
         pub is_extension: bool,
     }
+    #[allow(deprecated)]
+    impl ::prost::text_format::TextFormat for NamePart {
+        fn print_fields(&self, printer: &mut ::prost::text_format::Printer<'_>) {
+            printer.scalar("name_part", &self.name_part);
+            printer.scalar("is_extension", &self.is_extension);
+        }
+        fn merge_text_field(
+            &mut self,
+            name: &str,
+            parser: &mut ::prost::text_format::Parser<'_>,
+        ) -> ::core::result::Result<bool, ::prost::text_format::ParseError> {
+            match name {
+                "name_part" => parser.scalar(&mut self.name_part)?,
+                "is_extension" => parser.scalar(&mut self.is_extension)?,
+                _ => return ::core::result::Result::Ok(false),
+            }
+            ::core::result::Result::Ok(true)
+        }
+    }
+    #[allow(deprecated)]
+    impl ::prost::json::Json for NamePart {
+        fn write_json_fields(
+            &self,
+            writer: &mut ::prost::json::Writer<'_>,
+        ) -> ::core::result::Result<(), ::prost::json::JsonError> {
+            writer
+                .field(
+                    "namePart",
+                    "name_part",
+                    |writer| writer.scalar(&self.name_part),
+                )?;
+            writer
+                .field(
+                    "isExtension",
+                    "is_extension",
+                    |writer| writer.scalar(&self.is_extension),
+                )?;
+            ::core::result::Result::Ok(())
+        }
+        fn merge_json_field(
+            &mut self,
+            name: &str,
+            parser: &mut ::prost::json::Parser<'_>,
+        ) -> ::core::result::Result<bool, ::prost::json::JsonError> {
+            match name {
+                "namePart" | "name_part" => {
+                    parser.field(&mut self.name_part, |parser| parser.scalar())?
+                }
+                "isExtension" | "is_extension" => {
+                    parser.field(&mut self.is_extension, |parser| parser.scalar())?
+                }
+                _ => return ::core::result::Result::Ok(false),
+            }
+            ::core::result::Result::Ok(true)
+        }
+    }
+    #[allow(deprecated)]
+    impl ::prost::FieldMasked for NamePart {
+        fn check_mask_tree(
+            mask: &::prost::field_mask::FieldMaskTree,
+        ) -> ::core::result::Result<(), ::prost::field_mask::FieldMaskError> {
+            for (name, mask) in mask.iter() {
+                match name {
+                    "name_part" => ::prost::field_mask::check_leaf("name_part", mask)?,
+                    "is_extension" => {
+                        ::prost::field_mask::check_leaf("is_extension", mask)?
+                    }
+                    _ => {
+                        return ::core::result::Result::Err(
+                            ::prost::field_mask::unknown_field(name),
+                        );
+                    }
+                }
+            }
+            ::core::result::Result::Ok(())
+        }
+        fn merge_with_mask_tree(
+            &mut self,
+            src: &Self,
+            mask: &::prost::field_mask::FieldMaskTree,
+            _options: &::prost::field_mask::MergeOptions,
+        ) {
+            if mask.get("name_part").is_some() {
+                self.name_part.clone_from(&src.name_part);
+            }
+            if mask.get("is_extension").is_some() {
+                self.is_extension.clone_from(&src.is_extension);
+            }
+        }
+        fn prune_with_mask_tree(&mut self, mask: &::prost::field_mask::FieldMaskTree) {
+            let default = Self::default();
+            if mask.get("name_part").is_none() {
+                self.name_part = default.name_part;
+            }
+            if mask.get("is_extension").is_none() {
+                self.is_extension = default.is_extension;
+            }
+        }
+    }
 }
 /// Encapsulates information about the original source file from which a
 /// FileDescriptorProto was generated.
@@ -1044,6 +5805,94 @@ pub struct SourceCodeInfo {
     #[prost(message, repeated, tag = "1")]
     pub location: ::prost::alloc::vec::Vec<source_code_info::Location>,
 }
+#[allow(deprecated)]
+impl ::prost::text_format::TextFormat for SourceCodeInfo {
+    fn print_fields(&self, printer: &mut ::prost::text_format::Printer<'_>) {
+        for value in &self.location {
+            printer.message("location", value);
+        }
+    }
+    fn merge_text_field(
+        &mut self,
+        name: &str,
+        parser: &mut ::prost::text_format::Parser<'_>,
+    ) -> ::core::result::Result<bool, ::prost::text_format::ParseError> {
+        match name {
+            "location" => parser.repeated_message(&mut self.location)?,
+            _ => return ::core::result::Result::Ok(false),
+        }
+        ::core::result::Result::Ok(true)
+    }
+}
+#[allow(deprecated)]
+impl ::prost::json::Json for SourceCodeInfo {
+    fn write_json_fields(
+        &self,
+        writer: &mut ::prost::json::Writer<'_>,
+    ) -> ::core::result::Result<(), ::prost::json::JsonError> {
+        if writer.emit_defaults() || !self.location.is_empty() {
+            writer
+                .field(
+                    "location",
+                    "location",
+                    |writer| {
+                        writer
+                            .list(&self.location, |writer, value| writer.message(value))
+                    },
+                )?;
+        }
+        ::core::result::Result::Ok(())
+    }
+    fn merge_json_field(
+        &mut self,
+        name: &str,
+        parser: &mut ::prost::json::Parser<'_>,
+    ) -> ::core::result::Result<bool, ::prost::json::JsonError> {
+        match name {
+            "location" => parser.repeated(&mut self.location, |parser| parser.message())?,
+            _ => return ::core::result::Result::Ok(false),
+        }
+        ::core::result::Result::Ok(true)
+    }
+}
+#[allow(deprecated)]
+impl ::prost::FieldMasked for SourceCodeInfo {
+    fn check_mask_tree(
+        mask: &::prost::field_mask::FieldMaskTree,
+    ) -> ::core::result::Result<(), ::prost::field_mask::FieldMaskError> {
+        for (name, mask) in mask.iter() {
+            match name {
+                "location" => ::prost::field_mask::check_leaf("location", mask)?,
+                _ => {
+                    return ::core::result::Result::Err(
+                        ::prost::field_mask::unknown_field(name),
+                    );
+                }
+            }
+        }
+        ::core::result::Result::Ok(())
+    }
+    fn merge_with_mask_tree(
+        &mut self,
+        src: &Self,
+        mask: &::prost::field_mask::FieldMaskTree,
+        options: &::prost::field_mask::MergeOptions,
+    ) {
+        if mask.get("location").is_some() {
+            ::prost::field_mask::merge_repeated(
+                &mut self.location,
+                &src.location,
+                options,
+            );
+        }
+    }
+    fn prune_with_mask_tree(&mut self, mask: &::prost::field_mask::FieldMaskTree) {
+        let default = Self::default();
+        if mask.get("location").is_none() {
+            self.location = default.location;
+        }
+    }
+}
 /// Nested message and enum types in `SourceCodeInfo`.
 pub mod source_code_info {
     #[allow(clippy::derive_partial_eq_without_eq)]
@@ -1137,6 +5986,218 @@ pub mod source_code_info {
             ::prost::alloc::string::String,
         >,
     }
+    #[allow(deprecated)]
+    impl ::prost::text_format::TextFormat for Location {
+        fn print_fields(&self, printer: &mut ::prost::text_format::Printer<'_>) {
+            for value in &self.path {
+                printer.scalar("path", value);
+            }
+            for value in &self.span {
+                printer.scalar("span", value);
+            }
+            if let ::core::option::Option::Some(value) = &self.leading_comments {
+                printer.scalar("leading_comments", value);
+            }
+            if let ::core::option::Option::Some(value) = &self.trailing_comments {
+                printer.scalar("trailing_comments", value);
+            }
+            for value in &self.leading_detached_comments {
+                printer.scalar("leading_detached_comments", value);
+            }
+        }
+        fn merge_text_field(
+            &mut self,
+            name: &str,
+            parser: &mut ::prost::text_format::Parser<'_>,
+        ) -> ::core::result::Result<bool, ::prost::text_format::ParseError> {
+            match name {
+                "path" => parser.repeated_scalar(&mut self.path)?,
+                "span" => parser.repeated_scalar(&mut self.span)?,
+                "leading_comments" => {
+                    parser
+                        .scalar(
+                            self
+                                .leading_comments
+                                .get_or_insert_with(::core::default::Default::default),
+                        )?
+                }
+                "trailing_comments" => {
+                    parser
+                        .scalar(
+                            self
+                                .trailing_comments
+                                .get_or_insert_with(::core::default::Default::default),
+                        )?
+                }
+                "leading_detached_comments" => {
+                    parser.repeated_scalar(&mut self.leading_detached_comments)?
+                }
+                _ => return ::core::result::Result::Ok(false),
+            }
+            ::core::result::Result::Ok(true)
+        }
+    }
+    #[allow(deprecated)]
+    impl ::prost::json::Json for Location {
+        fn write_json_fields(
+            &self,
+            writer: &mut ::prost::json::Writer<'_>,
+        ) -> ::core::result::Result<(), ::prost::json::JsonError> {
+            if writer.emit_defaults() || !self.path.is_empty() {
+                writer
+                    .field(
+                        "path",
+                        "path",
+                        |writer| {
+                            writer.list(&self.path, |writer, value| writer.scalar(value))
+                        },
+                    )?;
+            }
+            if writer.emit_defaults() || !self.span.is_empty() {
+                writer
+                    .field(
+                        "span",
+                        "span",
+                        |writer| {
+                            writer.list(&self.span, |writer, value| writer.scalar(value))
+                        },
+                    )?;
+            }
+            if let ::core::option::Option::Some(value) = &self.leading_comments {
+                writer
+                    .field(
+                        "leadingComments",
+                        "leading_comments",
+                        |writer| writer.scalar(value),
+                    )?;
+            }
+            if let ::core::option::Option::Some(value) = &self.trailing_comments {
+                writer
+                    .field(
+                        "trailingComments",
+                        "trailing_comments",
+                        |writer| writer.scalar(value),
+                    )?;
+            }
+            if writer.emit_defaults() || !self.leading_detached_comments.is_empty() {
+                writer
+                    .field(
+                        "leadingDetachedComments",
+                        "leading_detached_comments",
+                        |writer| {
+                            writer
+                                .list(
+                                    &self.leading_detached_comments,
+                                    |writer, value| writer.scalar(value),
+                                )
+                        },
+                    )?;
+            }
+            ::core::result::Result::Ok(())
+        }
+        fn merge_json_field(
+            &mut self,
+            name: &str,
+            parser: &mut ::prost::json::Parser<'_>,
+        ) -> ::core::result::Result<bool, ::prost::json::JsonError> {
+            match name {
+                "path" => parser.repeated(&mut self.path, |parser| parser.scalar())?,
+                "span" => parser.repeated(&mut self.span, |parser| parser.scalar())?,
+                "leadingComments" | "leading_comments" => {
+                    parser
+                        .optional(&mut self.leading_comments, |parser| parser.scalar())?
+                }
+                "trailingComments" | "trailing_comments" => {
+                    parser
+                        .optional(&mut self.trailing_comments, |parser| parser.scalar())?
+                }
+                "leadingDetachedComments" | "leading_detached_comments" => {
+                    parser
+                        .repeated(
+                            &mut self.leading_detached_comments,
+                            |parser| parser.scalar(),
+                        )?
+                }
+                _ => return ::core::result::Result::Ok(false),
+            }
+            ::core::result::Result::Ok(true)
+        }
+    }
+    #[allow(deprecated)]
+    impl ::prost::FieldMasked for Location {
+        fn check_mask_tree(
+            mask: &::prost::field_mask::FieldMaskTree,
+        ) -> ::core::result::Result<(), ::prost::field_mask::FieldMaskError> {
+            for (name, mask) in mask.iter() {
+                match name {
+                    "path" => ::prost::field_mask::check_leaf("path", mask)?,
+                    "span" => ::prost::field_mask::check_leaf("span", mask)?,
+                    "leading_comments" => {
+                        ::prost::field_mask::check_leaf("leading_comments", mask)?
+                    }
+                    "trailing_comments" => {
+                        ::prost::field_mask::check_leaf("trailing_comments", mask)?
+                    }
+                    "leading_detached_comments" => {
+                        ::prost::field_mask::check_leaf(
+                            "leading_detached_comments",
+                            mask,
+                        )?
+                    }
+                    _ => {
+                        return ::core::result::Result::Err(
+                            ::prost::field_mask::unknown_field(name),
+                        );
+                    }
+                }
+            }
+            ::core::result::Result::Ok(())
+        }
+        fn merge_with_mask_tree(
+            &mut self,
+            src: &Self,
+            mask: &::prost::field_mask::FieldMaskTree,
+            options: &::prost::field_mask::MergeOptions,
+        ) {
+            if mask.get("path").is_some() {
+                ::prost::field_mask::merge_repeated(&mut self.path, &src.path, options);
+            }
+            if mask.get("span").is_some() {
+                ::prost::field_mask::merge_repeated(&mut self.span, &src.span, options);
+            }
+            if mask.get("leading_comments").is_some() {
+                self.leading_comments.clone_from(&src.leading_comments);
+            }
+            if mask.get("trailing_comments").is_some() {
+                self.trailing_comments.clone_from(&src.trailing_comments);
+            }
+            if mask.get("leading_detached_comments").is_some() {
+                ::prost::field_mask::merge_repeated(
+                    &mut self.leading_detached_comments,
+                    &src.leading_detached_comments,
+                    options,
+                );
+            }
+        }
+        fn prune_with_mask_tree(&mut self, mask: &::prost::field_mask::FieldMaskTree) {
+            let default = Self::default();
+            if mask.get("path").is_none() {
+                self.path = default.path;
+            }
+            if mask.get("span").is_none() {
+                self.span = default.span;
+            }
+            if mask.get("leading_comments").is_none() {
+                self.leading_comments = default.leading_comments;
+            }
+            if mask.get("trailing_comments").is_none() {
+                self.trailing_comments = default.trailing_comments;
+            }
+            if mask.get("leading_detached_comments").is_none() {
+                self.leading_detached_comments = default.leading_detached_comments;
+            }
+        }
+    }
 }
 /// Describes the relationship between generated code and its original source
 /// file. A GeneratedCodeInfo message is associated with only one generated
@@ -1149,6 +6210,99 @@ pub struct GeneratedCodeInfo {
     #[prost(message, repeated, tag = "1")]
     pub annotation: ::prost::alloc::vec::Vec<generated_code_info::Annotation>,
 }
+#[allow(deprecated)]
+impl ::prost::text_format::TextFormat for GeneratedCodeInfo {
+    fn print_fields(&self, printer: &mut ::prost::text_format::Printer<'_>) {
+        for value in &self.annotation {
+            printer.message("annotation", value);
+        }
+    }
+    fn merge_text_field(
+        &mut self,
+        name: &str,
+        parser: &mut ::prost::text_format::Parser<'_>,
+    ) -> ::core::result::Result<bool, ::prost::text_format::ParseError> {
+        match name {
+            "annotation" => parser.repeated_message(&mut self.annotation)?,
+            _ => return ::core::result::Result::Ok(false),
+        }
+        ::core::result::Result::Ok(true)
+    }
+}
+#[allow(deprecated)]
+impl ::prost::json::Json for GeneratedCodeInfo {
+    fn write_json_fields(
+        &self,
+        writer: &mut ::prost::json::Writer<'_>,
+    ) -> ::core::result::Result<(), ::prost::json::JsonError> {
+        if writer.emit_defaults() || !self.annotation.is_empty() {
+            writer
+                .field(
+                    "annotation",
+                    "annotation",
+                    |writer| {
+                        writer
+                            .list(
+                                &self.annotation,
+                                |writer, value| writer.message(value),
+                            )
+                    },
+                )?;
+        }
+        ::core::result::Result::Ok(())
+    }
+    fn merge_json_field(
+        &mut self,
+        name: &str,
+        parser: &mut ::prost::json::Parser<'_>,
+    ) -> ::core::result::Result<bool, ::prost::json::JsonError> {
+        match name {
+            "annotation" => {
+                parser.repeated(&mut self.annotation, |parser| parser.message())?
+            }
+            _ => return ::core::result::Result::Ok(false),
+        }
+        ::core::result::Result::Ok(true)
+    }
+}
+#[allow(deprecated)]
+impl ::prost::FieldMasked for GeneratedCodeInfo {
+    fn check_mask_tree(
+        mask: &::prost::field_mask::FieldMaskTree,
+    ) -> ::core::result::Result<(), ::prost::field_mask::FieldMaskError> {
+        for (name, mask) in mask.iter() {
+            match name {
+                "annotation" => ::prost::field_mask::check_leaf("annotation", mask)?,
+                _ => {
+                    return ::core::result::Result::Err(
+                        ::prost::field_mask::unknown_field(name),
+                    );
+                }
+            }
+        }
+        ::core::result::Result::Ok(())
+    }
+    fn merge_with_mask_tree(
+        &mut self,
+        src: &Self,
+        mask: &::prost::field_mask::FieldMaskTree,
+        options: &::prost::field_mask::MergeOptions,
+    ) {
+        if mask.get("annotation").is_some() {
+            ::prost::field_mask::merge_repeated(
+                &mut self.annotation,
+                &src.annotation,
+                options,
+            );
+        }
+    }
+    fn prune_with_mask_tree(&mut self, mask: &::prost::field_mask::FieldMaskTree) {
+        let default = Self::default();
+        if mask.get("annotation").is_none() {
+            self.annotation = default.annotation;
+        }
+    }
+}
 /// Nested message and enum types in `GeneratedCodeInfo`.
 pub mod generated_code_info {
     #[allow(clippy::derive_partial_eq_without_eq)]
@@ -1171,6 +6325,160 @@ pub mod generated_code_info {
         #[prost(int32, optional, tag = "4")]
         pub end: ::core::option::Option<i32>,
     }
+    #[allow(deprecated)]
+    impl ::prost::text_format::TextFormat for Annotation {
+        fn print_fields(&self, printer: &mut ::prost::text_format::Printer<'_>) {
+            for value in &self.path {
+                printer.scalar("path", value);
+            }
+            if let ::core::option::Option::Some(value) = &self.source_file {
+                printer.scalar("source_file", value);
+            }
+            if let ::core::option::Option::Some(value) = &self.begin {
+                printer.scalar("begin", value);
+            }
+            if let ::core::option::Option::Some(value) = &self.end {
+                printer.scalar("end", value);
+            }
+        }
+        fn merge_text_field(
+            &mut self,
+            name: &str,
+            parser: &mut ::prost::text_format::Parser<'_>,
+        ) -> ::core::result::Result<bool, ::prost::text_format::ParseError> {
+            match name {
+                "path" => parser.repeated_scalar(&mut self.path)?,
+                "source_file" => {
+                    parser
+                        .scalar(
+                            self
+                                .source_file
+                                .get_or_insert_with(::core::default::Default::default),
+                        )?
+                }
+                "begin" => {
+                    parser
+                        .scalar(
+                            self
+                                .begin
+                                .get_or_insert_with(::core::default::Default::default),
+                        )?
+                }
+                "end" => {
+                    parser
+                        .scalar(
+                            self
+                                .end
+                                .get_or_insert_with(::core::default::Default::default),
+                        )?
+                }
+                _ => return ::core::result::Result::Ok(false),
+            }
+            ::core::result::Result::Ok(true)
+        }
+    }
+    #[allow(deprecated)]
+    impl ::prost::json::Json for Annotation {
+        fn write_json_fields(
+            &self,
+            writer: &mut ::prost::json::Writer<'_>,
+        ) -> ::core::result::Result<(), ::prost::json::JsonError> {
+            if writer.emit_defaults() || !self.path.is_empty() {
+                writer
+                    .field(
+                        "path",
+                        "path",
+                        |writer| {
+                            writer.list(&self.path, |writer, value| writer.scalar(value))
+                        },
+                    )?;
+            }
+            if let ::core::option::Option::Some(value) = &self.source_file {
+                writer
+                    .field("sourceFile", "source_file", |writer| writer.scalar(value))?;
+            }
+            if let ::core::option::Option::Some(value) = &self.begin {
+                writer.field("begin", "begin", |writer| writer.scalar(value))?;
+            }
+            if let ::core::option::Option::Some(value) = &self.end {
+                writer.field("end", "end", |writer| writer.scalar(value))?;
+            }
+            ::core::result::Result::Ok(())
+        }
+        fn merge_json_field(
+            &mut self,
+            name: &str,
+            parser: &mut ::prost::json::Parser<'_>,
+        ) -> ::core::result::Result<bool, ::prost::json::JsonError> {
+            match name {
+                "path" => parser.repeated(&mut self.path, |parser| parser.scalar())?,
+                "sourceFile" | "source_file" => {
+                    parser.optional(&mut self.source_file, |parser| parser.scalar())?
+                }
+                "begin" => parser.optional(&mut self.begin, |parser| parser.scalar())?,
+                "end" => parser.optional(&mut self.end, |parser| parser.scalar())?,
+                _ => return ::core::result::Result::Ok(false),
+            }
+            ::core::result::Result::Ok(true)
+        }
+    }
+    #[allow(deprecated)]
+    impl ::prost::FieldMasked for Annotation {
+        fn check_mask_tree(
+            mask: &::prost::field_mask::FieldMaskTree,
+        ) -> ::core::result::Result<(), ::prost::field_mask::FieldMaskError> {
+            for (name, mask) in mask.iter() {
+                match name {
+                    "path" => ::prost::field_mask::check_leaf("path", mask)?,
+                    "source_file" => {
+                        ::prost::field_mask::check_leaf("source_file", mask)?
+                    }
+                    "begin" => ::prost::field_mask::check_leaf("begin", mask)?,
+                    "end" => ::prost::field_mask::check_leaf("end", mask)?,
+                    _ => {
+                        return ::core::result::Result::Err(
+                            ::prost::field_mask::unknown_field(name),
+                        );
+                    }
+                }
+            }
+            ::core::result::Result::Ok(())
+        }
+        fn merge_with_mask_tree(
+            &mut self,
+            src: &Self,
+            mask: &::prost::field_mask::FieldMaskTree,
+            options: &::prost::field_mask::MergeOptions,
+        ) {
+            if mask.get("path").is_some() {
+                ::prost::field_mask::merge_repeated(&mut self.path, &src.path, options);
+            }
+            if mask.get("source_file").is_some() {
+                self.source_file.clone_from(&src.source_file);
+            }
+            if mask.get("begin").is_some() {
+                self.begin.clone_from(&src.begin);
+            }
+            if mask.get("end").is_some() {
+                self.end.clone_from(&src.end);
+            }
+        }
+        fn prune_with_mask_tree(&mut self, mask: &::prost::field_mask::FieldMaskTree) {
+            let default = Self::default();
+            if mask.get("path").is_none() {
+                self.path = default.path;
+            }
+            if mask.get("source_file").is_none() {
+                self.source_file = default.source_file;
+            }
+            if mask.get("begin").is_none() {
+                self.begin = default.begin;
+            }
+            if mask.get("end").is_none() {
+                self.end = default.end;
+            }
+        }
+    }
 }
 /// `Any` contains an arbitrary serialized protocol buffer message along with a
 /// URL that describes the type of the serialized message.
@@ -1301,6 +6609,70 @@ pub struct Any {
     #[prost(bytes = "vec", tag = "2")]
     pub value: ::prost::alloc::vec::Vec<u8>,
 }
+#[allow(deprecated)]
+impl ::prost::text_format::TextFormat for Any {
+    fn print_fields(&self, printer: &mut ::prost::text_format::Printer<'_>) {
+        if !::prost::text_format::is_default(&self.type_url) {
+            printer.scalar("type_url", &self.type_url);
+        }
+        if !::prost::text_format::is_default(&self.value) {
+            printer.scalar("value", &self.value);
+        }
+    }
+    fn merge_text_field(
+        &mut self,
+        name: &str,
+        parser: &mut ::prost::text_format::Parser<'_>,
+    ) -> ::core::result::Result<bool, ::prost::text_format::ParseError> {
+        match name {
+            "type_url" => parser.scalar(&mut self.type_url)?,
+            "value" => parser.scalar(&mut self.value)?,
+            _ => return ::core::result::Result::Ok(false),
+        }
+        ::core::result::Result::Ok(true)
+    }
+}
+#[allow(deprecated)]
+impl ::prost::FieldMasked for Any {
+    fn check_mask_tree(
+        mask: &::prost::field_mask::FieldMaskTree,
+    ) -> ::core::result::Result<(), ::prost::field_mask::FieldMaskError> {
+        for (name, mask) in mask.iter() {
+            match name {
+                "type_url" => ::prost::field_mask::check_leaf("type_url", mask)?,
+                "value" => ::prost::field_mask::check_leaf("value", mask)?,
+                _ => {
+                    return ::core::result::Result::Err(
+                        ::prost::field_mask::unknown_field(name),
+                    );
+                }
+            }
+        }
+        ::core::result::Result::Ok(())
+    }
+    fn merge_with_mask_tree(
+        &mut self,
+        src: &Self,
+        mask: &::prost::field_mask::FieldMaskTree,
+        _options: &::prost::field_mask::MergeOptions,
+    ) {
+        if mask.get("type_url").is_some() {
+            self.type_url.clone_from(&src.type_url);
+        }
+        if mask.get("value").is_some() {
+            self.value.clone_from(&src.value);
+        }
+    }
+    fn prune_with_mask_tree(&mut self, mask: &::prost::field_mask::FieldMaskTree) {
+        let default = Self::default();
+        if mask.get("type_url").is_none() {
+            self.type_url = default.type_url;
+        }
+        if mask.get("value").is_none() {
+            self.value = default.value;
+        }
+    }
+}
 /// `SourceContext` represents information about the source of a
 /// protobuf element, like the file in which it is defined.
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -1311,6 +6683,89 @@ pub struct SourceContext {
     #[prost(string, tag = "1")]
     pub file_name: ::prost::alloc::string::String,
 }
+#[allow(deprecated)]
+impl ::prost::text_format::TextFormat for SourceContext {
+    fn print_fields(&self, printer: &mut ::prost::text_format::Printer<'_>) {
+        if !::prost::text_format::is_default(&self.file_name) {
+            printer.scalar("file_name", &self.file_name);
+        }
+    }
+    fn merge_text_field(
+        &mut self,
+        name: &str,
+        parser: &mut ::prost::text_format::Parser<'_>,
+    ) -> ::core::result::Result<bool, ::prost::text_format::ParseError> {
+        match name {
+            "file_name" => parser.scalar(&mut self.file_name)?,
+            _ => return ::core::result::Result::Ok(false),
+        }
+        ::core::result::Result::Ok(true)
+    }
+}
+#[allow(deprecated)]
+impl ::prost::json::Json for SourceContext {
+    fn write_json_fields(
+        &self,
+        writer: &mut ::prost::json::Writer<'_>,
+    ) -> ::core::result::Result<(), ::prost::json::JsonError> {
+        if writer.emit_defaults() || !::prost::json::is_default(&self.file_name) {
+            writer
+                .field(
+                    "fileName",
+                    "file_name",
+                    |writer| writer.scalar(&self.file_name),
+                )?;
+        }
+        ::core::result::Result::Ok(())
+    }
+    fn merge_json_field(
+        &mut self,
+        name: &str,
+        parser: &mut ::prost::json::Parser<'_>,
+    ) -> ::core::result::Result<bool, ::prost::json::JsonError> {
+        match name {
+            "fileName" | "file_name" => {
+                parser.field(&mut self.file_name, |parser| parser.scalar())?
+            }
+            _ => return ::core::result::Result::Ok(false),
+        }
+        ::core::result::Result::Ok(true)
+    }
+}
+#[allow(deprecated)]
+impl ::prost::FieldMasked for SourceContext {
+    fn check_mask_tree(
+        mask: &::prost::field_mask::FieldMaskTree,
+    ) -> ::core::result::Result<(), ::prost::field_mask::FieldMaskError> {
+        for (name, mask) in mask.iter() {
+            match name {
+                "file_name" => ::prost::field_mask::check_leaf("file_name", mask)?,
+                _ => {
+                    return ::core::result::Result::Err(
+                        ::prost::field_mask::unknown_field(name),
+                    );
+                }
+            }
+        }
+        ::core::result::Result::Ok(())
+    }
+    fn merge_with_mask_tree(
+        &mut self,
+        src: &Self,
+        mask: &::prost::field_mask::FieldMaskTree,
+        _options: &::prost::field_mask::MergeOptions,
+    ) {
+        if mask.get("file_name").is_some() {
+            self.file_name.clone_from(&src.file_name);
+        }
+    }
+    fn prune_with_mask_tree(&mut self, mask: &::prost::field_mask::FieldMaskTree) {
+        let default = Self::default();
+        if mask.get("file_name").is_none() {
+            self.file_name = default.file_name;
+        }
+    }
+}
 /// A protocol buffer message type.
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -1334,6 +6789,243 @@ pub struct Type {
     #[prost(enumeration = "Syntax", tag = "6")]
     pub syntax: i32,
 }
+#[allow(deprecated)]
+impl ::prost::text_format::TextFormat for Type {
+    fn print_fields(&self, printer: &mut ::prost::text_format::Printer<'_>) {
+        if !::prost::text_format::is_default(&self.name) {
+            printer.scalar("name", &self.name);
+        }
+        for value in &self.fields {
+            printer.message("fields", value);
+        }
+        for value in &self.oneofs {
+            printer.scalar("oneofs", value);
+        }
+        for value in &self.options {
+            printer.message("options", value);
+        }
+        if let ::core::option::Option::Some(value) = &self.source_context {
+            printer.message("source_context", value);
+        }
+        if !::prost::text_format::is_default(&self.syntax) {
+            printer
+                .enumeration(
+                    "syntax",
+                    self.syntax,
+                    |value| Syntax::try_from(value).ok().map(|value| value.as_str_name()),
+                );
+        }
+    }
+    fn merge_text_field(
+        &mut self,
+        name: &str,
+        parser: &mut ::prost::text_format::Parser<'_>,
+    ) -> ::core::result::Result<bool, ::prost::text_format::ParseError> {
+        match name {
+            "name" => parser.scalar(&mut self.name)?,
+            "fields" => parser.repeated_message(&mut self.fields)?,
+            "oneofs" => parser.repeated_scalar(&mut self.oneofs)?,
+            "options" => parser.repeated_message(&mut self.options)?,
+            "source_context" => {
+                parser
+                    .message(
+                        self
+                            .source_context
+                            .get_or_insert_with(::core::default::Default::default),
+                    )?
+            }
+            "syntax" => {
+                parser
+                    .enumeration(
+                        &mut self.syntax,
+                        |name| Syntax::from_str_name(name).map(|value| value as i32),
+                    )?
+            }
+            _ => return ::core::result::Result::Ok(false),
+        }
+        ::core::result::Result::Ok(true)
+    }
+}
+#[allow(deprecated)]
+impl ::prost::json::Json for Type {
+    fn write_json_fields(
+        &self,
+        writer: &mut ::prost::json::Writer<'_>,
+    ) -> ::core::result::Result<(), ::prost::json::JsonError> {
+        if writer.emit_defaults() || !::prost::json::is_default(&self.name) {
+            writer.field("name", "name", |writer| writer.scalar(&self.name))?;
+        }
+        if writer.emit_defaults() || !self.fields.is_empty() {
+            writer
+                .field(
+                    "fields",
+                    "fields",
+                    |writer| {
+                        writer.list(&self.fields, |writer, value| writer.message(value))
+                    },
+                )?;
+        }
+        if writer.emit_defaults() || !self.oneofs.is_empty() {
+            writer
+                .field(
+                    "oneofs",
+                    "oneofs",
+                    |writer| {
+                        writer.list(&self.oneofs, |writer, value| writer.scalar(value))
+                    },
+                )?;
+        }
+        if writer.emit_defaults() || !self.options.is_empty() {
+            writer
+                .field(
+                    "options",
+                    "options",
+                    |writer| {
+                        writer.list(&self.options, |writer, value| writer.message(value))
+                    },
+                )?;
+        }
+        if let ::core::option::Option::Some(value) = &self.source_context {
+            writer
+                .field(
+                    "sourceContext",
+                    "source_context",
+                    |writer| writer.message(value),
+                )?;
+        }
+        if writer.emit_defaults() || !::prost::json::is_default(&self.syntax) {
+            writer
+                .field(
+                    "syntax",
+                    "syntax",
+                    |writer| {
+                        writer
+                            .enumeration(
+                                self.syntax,
+                                |value| {
+                                    Syntax::try_from(value)
+                                        .ok()
+                                        .map(|value| value.as_str_name())
+                                },
+                            )
+                    },
+                )?;
+        }
+        ::core::result::Result::Ok(())
+    }
+    fn merge_json_field(
+        &mut self,
+        name: &str,
+        parser: &mut ::prost::json::Parser<'_>,
+    ) -> ::core::result::Result<bool, ::prost::json::JsonError> {
+        match name {
+            "name" => parser.field(&mut self.name, |parser| parser.scalar())?,
+            "fields" => parser.repeated(&mut self.fields, |parser| parser.message())?,
+            "oneofs" => parser.repeated(&mut self.oneofs, |parser| parser.scalar())?,
+            "options" => parser.repeated(&mut self.options, |parser| parser.message())?,
+            "sourceContext" | "source_context" => {
+                parser.optional(&mut self.source_context, |parser| parser.message())?
+            }
+            "syntax" => {
+                parser
+                    .field(
+                        &mut self.syntax,
+                        |parser| {
+                            parser
+                                .enumeration(|name| {
+                                    Syntax::from_str_name(name).map(|value| value as i32)
+                                })
+                        },
+                    )?
+            }
+            _ => return ::core::result::Result::Ok(false),
+        }
+        ::core::result::Result::Ok(true)
+    }
+}
+#[allow(deprecated)]
+impl ::prost::FieldMasked for Type {
+    fn check_mask_tree(
+        mask: &::prost::field_mask::FieldMaskTree,
+    ) -> ::core::result::Result<(), ::prost::field_mask::FieldMaskError> {
+        for (name, mask) in mask.iter() {
+            match name {
+                "name" => ::prost::field_mask::check_leaf("name", mask)?,
+                "fields" => ::prost::field_mask::check_leaf("fields", mask)?,
+                "oneofs" => ::prost::field_mask::check_leaf("oneofs", mask)?,
+                "options" => ::prost::field_mask::check_leaf("options", mask)?,
+                "source_context" => {
+                    ::prost::field_mask::check_message::<
+                        SourceContext,
+                    >("source_context", mask)?
+                }
+                "syntax" => ::prost::field_mask::check_leaf("syntax", mask)?,
+                _ => {
+                    return ::core::result::Result::Err(
+                        ::prost::field_mask::unknown_field(name),
+                    );
+                }
+            }
+        }
+        ::core::result::Result::Ok(())
+    }
+    fn merge_with_mask_tree(
+        &mut self,
+        src: &Self,
+        mask: &::prost::field_mask::FieldMaskTree,
+        options: &::prost::field_mask::MergeOptions,
+    ) {
+        if mask.get("name").is_some() {
+            self.name.clone_from(&src.name);
+        }
+        if mask.get("fields").is_some() {
+            ::prost::field_mask::merge_repeated(&mut self.fields, &src.fields, options);
+        }
+        if mask.get("oneofs").is_some() {
+            ::prost::field_mask::merge_repeated(&mut self.oneofs, &src.oneofs, options);
+        }
+        if mask.get("options").is_some() {
+            ::prost::field_mask::merge_repeated(
+                &mut self.options,
+                &src.options,
+                options,
+            );
+        }
+        if let ::core::option::Option::Some(mask) = mask.get("source_context") {
+            ::prost::field_mask::merge_message(
+                &mut self.source_context,
+                src.source_context.as_ref(),
+                mask,
+                options,
+            );
+        }
+        if mask.get("syntax").is_some() {
+            self.syntax.clone_from(&src.syntax);
+        }
+    }
+    fn prune_with_mask_tree(&mut self, mask: &::prost::field_mask::FieldMaskTree) {
+        let default = Self::default();
+        if mask.get("name").is_none() {
+            self.name = default.name;
+        }
+        if mask.get("fields").is_none() {
+            self.fields = default.fields;
+        }
+        if mask.get("oneofs").is_none() {
+            self.oneofs = default.oneofs;
+        }
+        if mask.get("options").is_none() {
+            self.options = default.options;
+        }
+        ::prost::field_mask::prune_message(
+            &mut self.source_context,
+            mask.get("source_context"),
+        );
+        if mask.get("syntax").is_none() {
+            self.syntax = default.syntax;
+        }
+    }
+}
 /// A single field of a message type.
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -1371,6 +7063,339 @@ pub struct Field {
     #[prost(string, tag = "11")]
     pub default_value: ::prost::alloc::string::String,
 }
+#[allow(deprecated)]
+impl ::prost::text_format::TextFormat for Field {
+    fn print_fields(&self, printer: &mut ::prost::text_format::Printer<'_>) {
+        if !::prost::text_format::is_default(&self.kind) {
+            printer
+                .enumeration(
+                    "kind",
+                    self.kind,
+                    |value| {
+                        field::Kind::try_from(value)
+                            .ok()
+                            .map(|value| value.as_str_name())
+                    },
+                );
+        }
+        if !::prost::text_format::is_default(&self.cardinality) {
+            printer
+                .enumeration(
+                    "cardinality",
+                    self.cardinality,
+                    |value| {
+                        field::Cardinality::try_from(value)
+                            .ok()
+                            .map(|value| value.as_str_name())
+                    },
+                );
+        }
+        if !::prost::text_format::is_default(&self.number) {
+            printer.scalar("number", &self.number);
+        }
+        if !::prost::text_format::is_default(&self.name) {
+            printer.scalar("name", &self.name);
+        }
+        if !::prost::text_format::is_default(&self.type_url) {
+            printer.scalar("type_url", &self.type_url);
+        }
+        if !::prost::text_format::is_default(&self.oneof_index) {
+            printer.scalar("oneof_index", &self.oneof_index);
+        }
+        if !::prost::text_format::is_default(&self.packed) {
+            printer.scalar("packed", &self.packed);
+        }
+        for value in &self.options {
+            printer.message("options", value);
+        }
+        if !::prost::text_format::is_default(&self.json_name) {
+            printer.scalar("json_name", &self.json_name);
+        }
+        if !::prost::text_format::is_default(&self.default_value) {
+            printer.scalar("default_value", &self.default_value);
+        }
+    }
+    fn merge_text_field(
+        &mut self,
+        name: &str,
+        parser: &mut ::prost::text_format::Parser<'_>,
+    ) -> ::core::result::Result<bool, ::prost::text_format::ParseError> {
+        match name {
+            "kind" => {
+                parser
+                    .enumeration(
+                        &mut self.kind,
+                        |name| field::Kind::from_str_name(name).map(|value| value as i32),
+                    )?
+            }
+            "cardinality" => {
+                parser
+                    .enumeration(
+                        &mut self.cardinality,
+                        |name| {
+                            field::Cardinality::from_str_name(name)
+                                .map(|value| value as i32)
+                        },
+                    )?
+            }
+            "number" => parser.scalar(&mut self.number)?,
+            "name" => parser.scalar(&mut self.name)?,
+            "type_url" => parser.scalar(&mut self.type_url)?,
+            "oneof_index" => parser.scalar(&mut self.oneof_index)?,
+            "packed" => parser.scalar(&mut self.packed)?,
+            "options" => parser.repeated_message(&mut self.options)?,
+            "json_name" => parser.scalar(&mut self.json_name)?,
+            "default_value" => parser.scalar(&mut self.default_value)?,
+            _ => return ::core::result::Result::Ok(false),
+        }
+        ::core::result::Result::Ok(true)
+    }
+}
+#[allow(deprecated)]
+impl ::prost::json::Json for Field {
+    fn write_json_fields(
+        &self,
+        writer: &mut ::prost::json::Writer<'_>,
+    ) -> ::core::result::Result<(), ::prost::json::JsonError> {
+        if writer.emit_defaults() || !::prost::json::is_default(&self.kind) {
+            writer
+                .field(
+                    "kind",
+                    "kind",
+                    |writer| {
+                        writer
+                            .enumeration(
+                                self.kind,
+                                |value| {
+                                    field::Kind::try_from(value)
+                                        .ok()
+                                        .map(|value| value.as_str_name())
+                                },
+                            )
+                    },
+                )?;
+        }
+        if writer.emit_defaults() || !::prost::json::is_default(&self.cardinality) {
+            writer
+                .field(
+                    "cardinality",
+                    "cardinality",
+                    |writer| {
+                        writer
+                            .enumeration(
+                                self.cardinality,
+                                |value| {
+                                    field::Cardinality::try_from(value)
+                                        .ok()
+                                        .map(|value| value.as_str_name())
+                                },
+                            )
+                    },
+                )?;
+        }
+        if writer.emit_defaults() || !::prost::json::is_default(&self.number) {
+            writer.field("number", "number", |writer| writer.scalar(&self.number))?;
+        }
+        if writer.emit_defaults() || !::prost::json::is_default(&self.name) {
+            writer.field("name", "name", |writer| writer.scalar(&self.name))?;
+        }
+        if writer.emit_defaults() || !::prost::json::is_default(&self.type_url) {
+            writer.field("typeUrl", "type_url", |writer| writer.scalar(&self.type_url))?;
+        }
+        if writer.emit_defaults() || !::prost::json::is_default(&self.oneof_index) {
+            writer
+                .field(
+                    "oneofIndex",
+                    "oneof_index",
+                    |writer| writer.scalar(&self.oneof_index),
+                )?;
+        }
+        if writer.emit_defaults() || !::prost::json::is_default(&self.packed) {
+            writer.field("packed", "packed", |writer| writer.scalar(&self.packed))?;
+        }
+        if writer.emit_defaults() || !self.options.is_empty() {
+            writer
+                .field(
+                    "options",
+                    "options",
+                    |writer| {
+                        writer.list(&self.options, |writer, value| writer.message(value))
+                    },
+                )?;
+        }
+        if writer.emit_defaults() || !::prost::json::is_default(&self.json_name) {
+            writer
+                .field(
+                    "jsonName",
+                    "json_name",
+                    |writer| writer.scalar(&self.json_name),
+                )?;
+        }
+        if writer.emit_defaults() || !::prost::json::is_default(&self.default_value) {
+            writer
+                .field(
+                    "defaultValue",
+                    "default_value",
+                    |writer| writer.scalar(&self.default_value),
+                )?;
+        }
+        ::core::result::Result::Ok(())
+    }
+    fn merge_json_field(
+        &mut self,
+        name: &str,
+        parser: &mut ::prost::json::Parser<'_>,
+    ) -> ::core::result::Result<bool, ::prost::json::JsonError> {
+        match name {
+            "kind" => {
+                parser
+                    .field(
+                        &mut self.kind,
+                        |parser| {
+                            parser
+                                .enumeration(|name| {
+                                    field::Kind::from_str_name(name).map(|value| value as i32)
+                                })
+                        },
+                    )?
+            }
+            "cardinality" => {
+                parser
+                    .field(
+                        &mut self.cardinality,
+                        |parser| {
+                            parser
+                                .enumeration(|name| {
+                                    field::Cardinality::from_str_name(name)
+                                        .map(|value| value as i32)
+                                })
+                        },
+                    )?
+            }
+            "number" => parser.field(&mut self.number, |parser| parser.scalar())?,
+            "name" => parser.field(&mut self.name, |parser| parser.scalar())?,
+            "typeUrl" | "type_url" => {
+                parser.field(&mut self.type_url, |parser| parser.scalar())?
+            }
+            "oneofIndex" | "oneof_index" => {
+                parser.field(&mut self.oneof_index, |parser| parser.scalar())?
+            }
+            "packed" => parser.field(&mut self.packed, |parser| parser.scalar())?,
+            "options" => parser.repeated(&mut self.options, |parser| parser.message())?,
+            "jsonName" | "json_name" => {
+                parser.field(&mut self.json_name, |parser| parser.scalar())?
+            }
+            "defaultValue" | "default_value" => {
+                parser.field(&mut self.default_value, |parser| parser.scalar())?
+            }
+            _ => return ::core::result::Result::Ok(false),
+        }
+        ::core::result::Result::Ok(true)
+    }
+}
+#[allow(deprecated)]
+impl ::prost::FieldMasked for Field {
+    fn check_mask_tree(
+        mask: &::prost::field_mask::FieldMaskTree,
+    ) -> ::core::result::Result<(), ::prost::field_mask::FieldMaskError> {
+        for (name, mask) in mask.iter() {
+            match name {
+                "kind" => ::prost::field_mask::check_leaf("kind", mask)?,
+                "cardinality" => ::prost::field_mask::check_leaf("cardinality", mask)?,
+                "number" => ::prost::field_mask::check_leaf("number", mask)?,
+                "name" => ::prost::field_mask::check_leaf("name", mask)?,
+                "type_url" => ::prost::field_mask::check_leaf("type_url", mask)?,
+                "oneof_index" => ::prost::field_mask::check_leaf("oneof_index", mask)?,
+                "packed" => ::prost::field_mask::check_leaf("packed", mask)?,
+                "options" => ::prost::field_mask::check_leaf("options", mask)?,
+                "json_name" => ::prost::field_mask::check_leaf("json_name", mask)?,
+                "default_value" => {
+                    ::prost::field_mask::check_leaf("default_value", mask)?
+                }
+                _ => {
+                    return ::core::result::Result::Err(
+                        ::prost::field_mask::unknown_field(name),
+                    );
+                }
+            }
+        }
+        ::core::result::Result::Ok(())
+    }
+    fn merge_with_mask_tree(
+        &mut self,
+        src: &Self,
+        mask: &::prost::field_mask::FieldMaskTree,
+        options: &::prost::field_mask::MergeOptions,
+    ) {
+        if mask.get("kind").is_some() {
+            self.kind.clone_from(&src.kind);
+        }
+        if mask.get("cardinality").is_some() {
+            self.cardinality.clone_from(&src.cardinality);
+        }
+        if mask.get("number").is_some() {
+            self.number.clone_from(&src.number);
+        }
+        if mask.get("name").is_some() {
+            self.name.clone_from(&src.name);
+        }
+        if mask.get("type_url").is_some() {
+            self.type_url.clone_from(&src.type_url);
+        }
+        if mask.get("oneof_index").is_some() {
+            self.oneof_index.clone_from(&src.oneof_index);
+        }
+        if mask.get("packed").is_some() {
+            self.packed.clone_from(&src.packed);
+        }
+        if mask.get("options").is_some() {
+            ::prost::field_mask::merge_repeated(
+                &mut self.options,
+                &src.options,
+                options,
+            );
+        }
+        if mask.get("json_name").is_some() {
+            self.json_name.clone_from(&src.json_name);
+        }
+        if mask.get("default_value").is_some() {
+            self.default_value.clone_from(&src.default_value);
+        }
+    }
+    fn prune_with_mask_tree(&mut self, mask: &::prost::field_mask::FieldMaskTree) {
+        let default = Self::default();
+        if mask.get("kind").is_none() {
+            self.kind = default.kind;
+        }
+        if mask.get("cardinality").is_none() {
+            self.cardinality = default.cardinality;
+        }
+        if mask.get("number").is_none() {
+            self.number = default.number;
+        }
+        if mask.get("name").is_none() {
+            self.name = default.name;
+        }
+        if mask.get("type_url").is_none() {
+            self.type_url = default.type_url;
+        }
+        if mask.get("oneof_index").is_none() {
+            self.oneof_index = default.oneof_index;
+        }
+        if mask.get("packed").is_none() {
+            self.packed = default.packed;
+        }
+        if mask.get("options").is_none() {
+            self.options = default.options;
+        }
+        if mask.get("json_name").is_none() {
+            self.json_name = default.json_name;
+        }
+        if mask.get("default_value").is_none() {
+            self.default_value = default.default_value;
+        }
+    }
+}
 /// Nested message and enum types in `Field`.
 pub mod field {
     /// Basic field types.
@@ -1548,6 +7573,228 @@ pub struct Enum {
     #[prost(enumeration = "Syntax", tag = "5")]
     pub syntax: i32,
 }
+#[allow(deprecated)]
+impl ::prost::text_format::TextFormat for Enum {
+    fn print_fields(&self, printer: &mut ::prost::text_format::Printer<'_>) {
+        if !::prost::text_format::is_default(&self.name) {
+            printer.scalar("name", &self.name);
+        }
+        for value in &self.enumvalue {
+            printer.message("enumvalue", value);
+        }
+        for value in &self.options {
+            printer.message("options", value);
+        }
+        if let ::core::option::Option::Some(value) = &self.source_context {
+            printer.message("source_context", value);
+        }
+        if !::prost::text_format::is_default(&self.syntax) {
+            printer
+                .enumeration(
+                    "syntax",
+                    self.syntax,
+                    |value| Syntax::try_from(value).ok().map(|value| value.as_str_name()),
+                );
+        }
+    }
+    fn merge_text_field(
+        &mut self,
+        name: &str,
+        parser: &mut ::prost::text_format::Parser<'_>,
+    ) -> ::core::result::Result<bool, ::prost::text_format::ParseError> {
+        match name {
+            "name" => parser.scalar(&mut self.name)?,
+            "enumvalue" => parser.repeated_message(&mut self.enumvalue)?,
+            "options" => parser.repeated_message(&mut self.options)?,
+            "source_context" => {
+                parser
+                    .message(
+                        self
+                            .source_context
+                            .get_or_insert_with(::core::default::Default::default),
+                    )?
+            }
+            "syntax" => {
+                parser
+                    .enumeration(
+                        &mut self.syntax,
+                        |name| Syntax::from_str_name(name).map(|value| value as i32),
+                    )?
+            }
+            _ => return ::core::result::Result::Ok(false),
+        }
+        ::core::result::Result::Ok(true)
+    }
+}
+#[allow(deprecated)]
+impl ::prost::json::Json for Enum {
+    fn write_json_fields(
+        &self,
+        writer: &mut ::prost::json::Writer<'_>,
+    ) -> ::core::result::Result<(), ::prost::json::JsonError> {
+        if writer.emit_defaults() || !::prost::json::is_default(&self.name) {
+            writer.field("name", "name", |writer| writer.scalar(&self.name))?;
+        }
+        if writer.emit_defaults() || !self.enumvalue.is_empty() {
+            writer
+                .field(
+                    "enumvalue",
+                    "enumvalue",
+                    |writer| {
+                        writer
+                            .list(&self.enumvalue, |writer, value| writer.message(value))
+                    },
+                )?;
+        }
+        if writer.emit_defaults() || !self.options.is_empty() {
+            writer
+                .field(
+                    "options",
+                    "options",
+                    |writer| {
+                        writer.list(&self.options, |writer, value| writer.message(value))
+                    },
+                )?;
+        }
+        if let ::core::option::Option::Some(value) = &self.source_context {
+            writer
+                .field(
+                    "sourceContext",
+                    "source_context",
+                    |writer| writer.message(value),
+                )?;
+        }
+        if writer.emit_defaults() || !::prost::json::is_default(&self.syntax) {
+            writer
+                .field(
+                    "syntax",
+                    "syntax",
+                    |writer| {
+                        writer
+                            .enumeration(
+                                self.syntax,
+                                |value| {
+                                    Syntax::try_from(value)
+                                        .ok()
+                                        .map(|value| value.as_str_name())
+                                },
+                            )
+                    },
+                )?;
+        }
+        ::core::result::Result::Ok(())
+    }
+    fn merge_json_field(
+        &mut self,
+        name: &str,
+        parser: &mut ::prost::json::Parser<'_>,
+    ) -> ::core::result::Result<bool, ::prost::json::JsonError> {
+        match name {
+            "name" => parser.field(&mut self.name, |parser| parser.scalar())?,
+            "enumvalue" => {
+                parser.repeated(&mut self.enumvalue, |parser| parser.message())?
+            }
+            "options" => parser.repeated(&mut self.options, |parser| parser.message())?,
+            "sourceContext" | "source_context" => {
+                parser.optional(&mut self.source_context, |parser| parser.message())?
+            }
+            "syntax" => {
+                parser
+                    .field(
+                        &mut self.syntax,
+                        |parser| {
+                            parser
+                                .enumeration(|name| {
+                                    Syntax::from_str_name(name).map(|value| value as i32)
+                                })
+                        },
+                    )?
+            }
+            _ => return ::core::result::Result::Ok(false),
+        }
+        ::core::result::Result::Ok(true)
+    }
+}
+#[allow(deprecated)]
+impl ::prost::FieldMasked for Enum {
+    fn check_mask_tree(
+        mask: &::prost::field_mask::FieldMaskTree,
+    ) -> ::core::result::Result<(), ::prost::field_mask::FieldMaskError> {
+        for (name, mask) in mask.iter() {
+            match name {
+                "name" => ::prost::field_mask::check_leaf("name", mask)?,
+                "enumvalue" => ::prost::field_mask::check_leaf("enumvalue", mask)?,
+                "options" => ::prost::field_mask::check_leaf("options", mask)?,
+                "source_context" => {
+                    ::prost::field_mask::check_message::<
+                        SourceContext,
+                    >("source_context", mask)?
+                }
+                "syntax" => ::prost::field_mask::check_leaf("syntax", mask)?,
+                _ => {
+                    return ::core::result::Result::Err(
+                        ::prost::field_mask::unknown_field(name),
+                    );
+                }
+            }
+        }
+        ::core::result::Result::Ok(())
+    }
+    fn merge_with_mask_tree(
+        &mut self,
+        src: &Self,
+        mask: &::prost::field_mask::FieldMaskTree,
+        options: &::prost::field_mask::MergeOptions,
+    ) {
+        if mask.get("name").is_some() {
+            self.name.clone_from(&src.name);
+        }
+        if mask.get("enumvalue").is_some() {
+            ::prost::field_mask::merge_repeated(
+                &mut self.enumvalue,
+                &src.enumvalue,
+                options,
+            );
+        }
+        if mask.get("options").is_some() {
+            ::prost::field_mask::merge_repeated(
+                &mut self.options,
+                &src.options,
+                options,
+            );
+        }
+        if let ::core::option::Option::Some(mask) = mask.get("source_context") {
+            ::prost::field_mask::merge_message(
+                &mut self.source_context,
+                src.source_context.as_ref(),
+                mask,
+                options,
+            );
+        }
+        if mask.get("syntax").is_some() {
+            self.syntax.clone_from(&src.syntax);
+        }
+    }
+    fn prune_with_mask_tree(&mut self, mask: &::prost::field_mask::FieldMaskTree) {
+        let default = Self::default();
+        if mask.get("name").is_none() {
+            self.name = default.name;
+        }
+        if mask.get("enumvalue").is_none() {
+            self.enumvalue = default.enumvalue;
+        }
+        if mask.get("options").is_none() {
+            self.options = default.options;
+        }
+        ::prost::field_mask::prune_message(
+            &mut self.source_context,
+            mask.get("source_context"),
+        );
+        if mask.get("syntax").is_none() {
+            self.syntax = default.syntax;
+        }
+    }
+}
 /// Enum value definition.
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -1562,6 +7809,123 @@ pub struct EnumValue {
     #[prost(message, repeated, tag = "3")]
     pub options: ::prost::alloc::vec::Vec<Option>,
 }
+#[allow(deprecated)]
+impl ::prost::text_format::TextFormat for EnumValue {
+    fn print_fields(&self, printer: &mut ::prost::text_format::Printer<'_>) {
+        if !::prost::text_format::is_default(&self.name) {
+            printer.scalar("name", &self.name);
+        }
+        if !::prost::text_format::is_default(&self.number) {
+            printer.scalar("number", &self.number);
+        }
+        for value in &self.options {
+            printer.message("options", value);
+        }
+    }
+    fn merge_text_field(
+        &mut self,
+        name: &str,
+        parser: &mut ::prost::text_format::Parser<'_>,
+    ) -> ::core::result::Result<bool, ::prost::text_format::ParseError> {
+        match name {
+            "name" => parser.scalar(&mut self.name)?,
+            "number" => parser.scalar(&mut self.number)?,
+            "options" => parser.repeated_message(&mut self.options)?,
+            _ => return ::core::result::Result::Ok(false),
+        }
+        ::core::result::Result::Ok(true)
+    }
+}
+#[allow(deprecated)]
+impl ::prost::json::Json for EnumValue {
+    fn write_json_fields(
+        &self,
+        writer: &mut ::prost::json::Writer<'_>,
+    ) -> ::core::result::Result<(), ::prost::json::JsonError> {
+        if writer.emit_defaults() || !::prost::json::is_default(&self.name) {
+            writer.field("name", "name", |writer| writer.scalar(&self.name))?;
+        }
+        if writer.emit_defaults() || !::prost::json::is_default(&self.number) {
+            writer.field("number", "number", |writer| writer.scalar(&self.number))?;
+        }
+        if writer.emit_defaults() || !self.options.is_empty() {
+            writer
+                .field(
+                    "options",
+                    "options",
+                    |writer| {
+                        writer.list(&self.options, |writer, value| writer.message(value))
+                    },
+                )?;
+        }
+        ::core::result::Result::Ok(())
+    }
+    fn merge_json_field(
+        &mut self,
+        name: &str,
+        parser: &mut ::prost::json::Parser<'_>,
+    ) -> ::core::result::Result<bool, ::prost::json::JsonError> {
+        match name {
+            "name" => parser.field(&mut self.name, |parser| parser.scalar())?,
+            "number" => parser.field(&mut self.number, |parser| parser.scalar())?,
+            "options" => parser.repeated(&mut self.options, |parser| parser.message())?,
+            _ => return ::core::result::Result::Ok(false),
+        }
+        ::core::result::Result::Ok(true)
+    }
+}
+#[allow(deprecated)]
+impl ::prost::FieldMasked for EnumValue {
+    fn check_mask_tree(
+        mask: &::prost::field_mask::FieldMaskTree,
+    ) -> ::core::result::Result<(), ::prost::field_mask::FieldMaskError> {
+        for (name, mask) in mask.iter() {
+            match name {
+                "name" => ::prost::field_mask::check_leaf("name", mask)?,
+                "number" => ::prost::field_mask::check_leaf("number", mask)?,
+                "options" => ::prost::field_mask::check_leaf("options", mask)?,
+                _ => {
+                    return ::core::result::Result::Err(
+                        ::prost::field_mask::unknown_field(name),
+                    );
+                }
+            }
+        }
+        ::core::result::Result::Ok(())
+    }
+    fn merge_with_mask_tree(
+        &mut self,
+        src: &Self,
+        mask: &::prost::field_mask::FieldMaskTree,
+        options: &::prost::field_mask::MergeOptions,
+    ) {
+        if mask.get("name").is_some() {
+            self.name.clone_from(&src.name);
+        }
+        if mask.get("number").is_some() {
+            self.number.clone_from(&src.number);
+        }
+        if mask.get("options").is_some() {
+            ::prost::field_mask::merge_repeated(
+                &mut self.options,
+                &src.options,
+                options,
+            );
+        }
+    }
+    fn prune_with_mask_tree(&mut self, mask: &::prost::field_mask::FieldMaskTree) {
+        let default = Self::default();
+        if mask.get("name").is_none() {
+            self.name = default.name;
+        }
+        if mask.get("number").is_none() {
+            self.number = default.number;
+        }
+        if mask.get("options").is_none() {
+            self.options = default.options;
+        }
+    }
+}
 /// A protocol buffer option, which can be attached to a message, field,
 /// enumeration, etc.
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -1580,6 +7944,105 @@ pub struct Option {
     #[prost(message, optional, tag = "2")]
     pub value: ::core::option::Option<Any>,
 }
+#[allow(deprecated)]
+impl ::prost::text_format::TextFormat for Option {
+    fn print_fields(&self, printer: &mut ::prost::text_format::Printer<'_>) {
+        if !::prost::text_format::is_default(&self.name) {
+            printer.scalar("name", &self.name);
+        }
+        if let ::core::option::Option::Some(value) = &self.value {
+            printer.message("value", value);
+        }
+    }
+    fn merge_text_field(
+        &mut self,
+        name: &str,
+        parser: &mut ::prost::text_format::Parser<'_>,
+    ) -> ::core::result::Result<bool, ::prost::text_format::ParseError> {
+        match name {
+            "name" => parser.scalar(&mut self.name)?,
+            "value" => {
+                parser
+                    .message(
+                        self.value.get_or_insert_with(::core::default::Default::default),
+                    )?
+            }
+            _ => return ::core::result::Result::Ok(false),
+        }
+        ::core::result::Result::Ok(true)
+    }
+}
+#[allow(deprecated)]
+impl ::prost::json::Json for Option {
+    fn write_json_fields(
+        &self,
+        writer: &mut ::prost::json::Writer<'_>,
+    ) -> ::core::result::Result<(), ::prost::json::JsonError> {
+        if writer.emit_defaults() || !::prost::json::is_default(&self.name) {
+            writer.field("name", "name", |writer| writer.scalar(&self.name))?;
+        }
+        if let ::core::option::Option::Some(value) = &self.value {
+            writer.field("value", "value", |writer| writer.message(value))?;
+        }
+        ::core::result::Result::Ok(())
+    }
+    fn merge_json_field(
+        &mut self,
+        name: &str,
+        parser: &mut ::prost::json::Parser<'_>,
+    ) -> ::core::result::Result<bool, ::prost::json::JsonError> {
+        match name {
+            "name" => parser.field(&mut self.name, |parser| parser.scalar())?,
+            "value" => parser.optional(&mut self.value, |parser| parser.message())?,
+            _ => return ::core::result::Result::Ok(false),
+        }
+        ::core::result::Result::Ok(true)
+    }
+}
+#[allow(deprecated)]
+impl ::prost::FieldMasked for Option {
+    fn check_mask_tree(
+        mask: &::prost::field_mask::FieldMaskTree,
+    ) -> ::core::result::Result<(), ::prost::field_mask::FieldMaskError> {
+        for (name, mask) in mask.iter() {
+            match name {
+                "name" => ::prost::field_mask::check_leaf("name", mask)?,
+                "value" => ::prost::field_mask::check_message::<Any>("value", mask)?,
+                _ => {
+                    return ::core::result::Result::Err(
+                        ::prost::field_mask::unknown_field(name),
+                    );
+                }
+            }
+        }
+        ::core::result::Result::Ok(())
+    }
+    fn merge_with_mask_tree(
+        &mut self,
+        src: &Self,
+        mask: &::prost::field_mask::FieldMaskTree,
+        options: &::prost::field_mask::MergeOptions,
+    ) {
+        if mask.get("name").is_some() {
+            self.name.clone_from(&src.name);
+        }
+        if let ::core::option::Option::Some(mask) = mask.get("value") {
+            ::prost::field_mask::merge_message(
+                &mut self.value,
+                src.value.as_ref(),
+                mask,
+                options,
+            );
+        }
+    }
+    fn prune_with_mask_tree(&mut self, mask: &::prost::field_mask::FieldMaskTree) {
+        let default = Self::default();
+        if mask.get("name").is_none() {
+            self.name = default.name;
+        }
+        ::prost::field_mask::prune_message(&mut self.value, mask.get("value"));
+    }
+}
 /// The syntax in which a protocol buffer element is defined.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
 #[repr(i32)]
@@ -1663,6 +8126,262 @@ pub struct Api {
     #[prost(enumeration = "Syntax", tag = "7")]
     pub syntax: i32,
 }
+#[allow(deprecated)]
+impl ::prost::text_format::TextFormat for Api {
+    fn print_fields(&self, printer: &mut ::prost::text_format::Printer<'_>) {
+        if !::prost::text_format::is_default(&self.name) {
+            printer.scalar("name", &self.name);
+        }
+        for value in &self.methods {
+            printer.message("methods", value);
+        }
+        for value in &self.options {
+            printer.message("options", value);
+        }
+        if !::prost::text_format::is_default(&self.version) {
+            printer.scalar("version", &self.version);
+        }
+        if let ::core::option::Option::Some(value) = &self.source_context {
+            printer.message("source_context", value);
+        }
+        for value in &self.mixins {
+            printer.message("mixins", value);
+        }
+        if !::prost::text_format::is_default(&self.syntax) {
+            printer
+                .enumeration(
+                    "syntax",
+                    self.syntax,
+                    |value| Syntax::try_from(value).ok().map(|value| value.as_str_name()),
+                );
+        }
+    }
+    fn merge_text_field(
+        &mut self,
+        name: &str,
+        parser: &mut ::prost::text_format::Parser<'_>,
+    ) -> ::core::result::Result<bool, ::prost::text_format::ParseError> {
+        match name {
+            "name" => parser.scalar(&mut self.name)?,
+            "methods" => parser.repeated_message(&mut self.methods)?,
+            "options" => parser.repeated_message(&mut self.options)?,
+            "version" => parser.scalar(&mut self.version)?,
+            "source_context" => {
+                parser
+                    .message(
+                        self
+                            .source_context
+                            .get_or_insert_with(::core::default::Default::default),
+                    )?
+            }
+            "mixins" => parser.repeated_message(&mut self.mixins)?,
+            "syntax" => {
+                parser
+                    .enumeration(
+                        &mut self.syntax,
+                        |name| Syntax::from_str_name(name).map(|value| value as i32),
+                    )?
+            }
+            _ => return ::core::result::Result::Ok(false),
+        }
+        ::core::result::Result::Ok(true)
+    }
+}
+#[allow(deprecated)]
+impl ::prost::json::Json for Api {
+    fn write_json_fields(
+        &self,
+        writer: &mut ::prost::json::Writer<'_>,
+    ) -> ::core::result::Result<(), ::prost::json::JsonError> {
+        if writer.emit_defaults() || !::prost::json::is_default(&self.name) {
+            writer.field("name", "name", |writer| writer.scalar(&self.name))?;
+        }
+        if writer.emit_defaults() || !self.methods.is_empty() {
+            writer
+                .field(
+                    "methods",
+                    "methods",
+                    |writer| {
+                        writer.list(&self.methods, |writer, value| writer.message(value))
+                    },
+                )?;
+        }
+        if writer.emit_defaults() || !self.options.is_empty() {
+            writer
+                .field(
+                    "options",
+                    "options",
+                    |writer| {
+                        writer.list(&self.options, |writer, value| writer.message(value))
+                    },
+                )?;
+        }
+        if writer.emit_defaults() || !::prost::json::is_default(&self.version) {
+            writer.field("version", "version", |writer| writer.scalar(&self.version))?;
+        }
+        if let ::core::option::Option::Some(value) = &self.source_context {
+            writer
+                .field(
+                    "sourceContext",
+                    "source_context",
+                    |writer| writer.message(value),
+                )?;
+        }
+        if writer.emit_defaults() || !self.mixins.is_empty() {
+            writer
+                .field(
+                    "mixins",
+                    "mixins",
+                    |writer| {
+                        writer.list(&self.mixins, |writer, value| writer.message(value))
+                    },
+                )?;
+        }
+        if writer.emit_defaults() || !::prost::json::is_default(&self.syntax) {
+            writer
+                .field(
+                    "syntax",
+                    "syntax",
+                    |writer| {
+                        writer
+                            .enumeration(
+                                self.syntax,
+                                |value| {
+                                    Syntax::try_from(value)
+                                        .ok()
+                                        .map(|value| value.as_str_name())
+                                },
+                            )
+                    },
+                )?;
+        }
+        ::core::result::Result::Ok(())
+    }
+    fn merge_json_field(
+        &mut self,
+        name: &str,
+        parser: &mut ::prost::json::Parser<'_>,
+    ) -> ::core::result::Result<bool, ::prost::json::JsonError> {
+        match name {
+            "name" => parser.field(&mut self.name, |parser| parser.scalar())?,
+            "methods" => parser.repeated(&mut self.methods, |parser| parser.message())?,
+            "options" => parser.repeated(&mut self.options, |parser| parser.message())?,
+            "version" => parser.field(&mut self.version, |parser| parser.scalar())?,
+            "sourceContext" | "source_context" => {
+                parser.optional(&mut self.source_context, |parser| parser.message())?
+            }
+            "mixins" => parser.repeated(&mut self.mixins, |parser| parser.message())?,
+            "syntax" => {
+                parser
+                    .field(
+                        &mut self.syntax,
+                        |parser| {
+                            parser
+                                .enumeration(|name| {
+                                    Syntax::from_str_name(name).map(|value| value as i32)
+                                })
+                        },
+                    )?
+            }
+            _ => return ::core::result::Result::Ok(false),
+        }
+        ::core::result::Result::Ok(true)
+    }
+}
+#[allow(deprecated)]
+impl ::prost::FieldMasked for Api {
+    fn check_mask_tree(
+        mask: &::prost::field_mask::FieldMaskTree,
+    ) -> ::core::result::Result<(), ::prost::field_mask::FieldMaskError> {
+        for (name, mask) in mask.iter() {
+            match name {
+                "name" => ::prost::field_mask::check_leaf("name", mask)?,
+                "methods" => ::prost::field_mask::check_leaf("methods", mask)?,
+                "options" => ::prost::field_mask::check_leaf("options", mask)?,
+                "version" => ::prost::field_mask::check_leaf("version", mask)?,
+                "source_context" => {
+                    ::prost::field_mask::check_message::<
+                        SourceContext,
+                    >("source_context", mask)?
+                }
+                "mixins" => ::prost::field_mask::check_leaf("mixins", mask)?,
+                "syntax" => ::prost::field_mask::check_leaf("syntax", mask)?,
+                _ => {
+                    return ::core::result::Result::Err(
+                        ::prost::field_mask::unknown_field(name),
+                    );
+                }
+            }
+        }
+        ::core::result::Result::Ok(())
+    }
+    fn merge_with_mask_tree(
+        &mut self,
+        src: &Self,
+        mask: &::prost::field_mask::FieldMaskTree,
+        options: &::prost::field_mask::MergeOptions,
+    ) {
+        if mask.get("name").is_some() {
+            self.name.clone_from(&src.name);
+        }
+        if mask.get("methods").is_some() {
+            ::prost::field_mask::merge_repeated(
+                &mut self.methods,
+                &src.methods,
+                options,
+            );
+        }
+        if mask.get("options").is_some() {
+            ::prost::field_mask::merge_repeated(
+                &mut self.options,
+                &src.options,
+                options,
+            );
+        }
+        if mask.get("version").is_some() {
+            self.version.clone_from(&src.version);
+        }
+        if let ::core::option::Option::Some(mask) = mask.get("source_context") {
+            ::prost::field_mask::merge_message(
+                &mut self.source_context,
+                src.source_context.as_ref(),
+                mask,
+                options,
+            );
+        }
+        if mask.get("mixins").is_some() {
+            ::prost::field_mask::merge_repeated(&mut self.mixins, &src.mixins, options);
+        }
+        if mask.get("syntax").is_some() {
+            self.syntax.clone_from(&src.syntax);
+        }
+    }
+    fn prune_with_mask_tree(&mut self, mask: &::prost::field_mask::FieldMaskTree) {
+        let default = Self::default();
+        if mask.get("name").is_none() {
+            self.name = default.name;
+        }
+        if mask.get("methods").is_none() {
+            self.methods = default.methods;
+        }
+        if mask.get("options").is_none() {
+            self.options = default.options;
+        }
+        if mask.get("version").is_none() {
+            self.version = default.version;
+        }
+        ::prost::field_mask::prune_message(
+            &mut self.source_context,
+            mask.get("source_context"),
+        );
+        if mask.get("mixins").is_none() {
+            self.mixins = default.mixins;
+        }
+        if mask.get("syntax").is_none() {
+            self.syntax = default.syntax;
+        }
+    }
+}
 /// Method represents a method of an API interface.
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -1689,6 +8408,259 @@ pub struct Method {
     #[prost(enumeration = "Syntax", tag = "7")]
     pub syntax: i32,
 }
+#[allow(deprecated)]
+impl ::prost::text_format::TextFormat for Method {
+    fn print_fields(&self, printer: &mut ::prost::text_format::Printer<'_>) {
+        if !::prost::text_format::is_default(&self.name) {
+            printer.scalar("name", &self.name);
+        }
+        if !::prost::text_format::is_default(&self.request_type_url) {
+            printer.scalar("request_type_url", &self.request_type_url);
+        }
+        if !::prost::text_format::is_default(&self.request_streaming) {
+            printer.scalar("request_streaming", &self.request_streaming);
+        }
+        if !::prost::text_format::is_default(&self.response_type_url) {
+            printer.scalar("response_type_url", &self.response_type_url);
+        }
+        if !::prost::text_format::is_default(&self.response_streaming) {
+            printer.scalar("response_streaming", &self.response_streaming);
+        }
+        for value in &self.options {
+            printer.message("options", value);
+        }
+        if !::prost::text_format::is_default(&self.syntax) {
+            printer
+                .enumeration(
+                    "syntax",
+                    self.syntax,
+                    |value| Syntax::try_from(value).ok().map(|value| value.as_str_name()),
+                );
+        }
+    }
+    fn merge_text_field(
+        &mut self,
+        name: &str,
+        parser: &mut ::prost::text_format::Parser<'_>,
+    ) -> ::core::result::Result<bool, ::prost::text_format::ParseError> {
+        match name {
+            "name" => parser.scalar(&mut self.name)?,
+            "request_type_url" => parser.scalar(&mut self.request_type_url)?,
+            "request_streaming" => parser.scalar(&mut self.request_streaming)?,
+            "response_type_url" => parser.scalar(&mut self.response_type_url)?,
+            "response_streaming" => parser.scalar(&mut self.response_streaming)?,
+            "options" => parser.repeated_message(&mut self.options)?,
+            "syntax" => {
+                parser
+                    .enumeration(
+                        &mut self.syntax,
+                        |name| Syntax::from_str_name(name).map(|value| value as i32),
+                    )?
+            }
+            _ => return ::core::result::Result::Ok(false),
+        }
+        ::core::result::Result::Ok(true)
+    }
+}
+#[allow(deprecated)]
+impl ::prost::json::Json for Method {
+    fn write_json_fields(
+        &self,
+        writer: &mut ::prost::json::Writer<'_>,
+    ) -> ::core::result::Result<(), ::prost::json::JsonError> {
+        if writer.emit_defaults() || !::prost::json::is_default(&self.name) {
+            writer.field("name", "name", |writer| writer.scalar(&self.name))?;
+        }
+        if writer.emit_defaults() || !::prost::json::is_default(&self.request_type_url) {
+            writer
+                .field(
+                    "requestTypeUrl",
+                    "request_type_url",
+                    |writer| writer.scalar(&self.request_type_url),
+                )?;
+        }
+        if writer.emit_defaults() || !::prost::json::is_default(&self.request_streaming)
+        {
+            writer
+                .field(
+                    "requestStreaming",
+                    "request_streaming",
+                    |writer| writer.scalar(&self.request_streaming),
+                )?;
+        }
+        if writer.emit_defaults() || !::prost::json::is_default(&self.response_type_url)
+        {
+            writer
+                .field(
+                    "responseTypeUrl",
+                    "response_type_url",
+                    |writer| writer.scalar(&self.response_type_url),
+                )?;
+        }
+        if writer.emit_defaults() || !::prost::json::is_default(&self.response_streaming)
+        {
+            writer
+                .field(
+                    "responseStreaming",
+                    "response_streaming",
+                    |writer| writer.scalar(&self.response_streaming),
+                )?;
+        }
+        if writer.emit_defaults() || !self.options.is_empty() {
+            writer
+                .field(
+                    "options",
+                    "options",
+                    |writer| {
+                        writer.list(&self.options, |writer, value| writer.message(value))
+                    },
+                )?;
+        }
+        if writer.emit_defaults() || !::prost::json::is_default(&self.syntax) {
+            writer
+                .field(
+                    "syntax",
+                    "syntax",
+                    |writer| {
+                        writer
+                            .enumeration(
+                                self.syntax,
+                                |value| {
+                                    Syntax::try_from(value)
+                                        .ok()
+                                        .map(|value| value.as_str_name())
+                                },
+                            )
+                    },
+                )?;
+        }
+        ::core::result::Result::Ok(())
+    }
+    fn merge_json_field(
+        &mut self,
+        name: &str,
+        parser: &mut ::prost::json::Parser<'_>,
+    ) -> ::core::result::Result<bool, ::prost::json::JsonError> {
+        match name {
+            "name" => parser.field(&mut self.name, |parser| parser.scalar())?,
+            "requestTypeUrl" | "request_type_url" => {
+                parser.field(&mut self.request_type_url, |parser| parser.scalar())?
+            }
+            "requestStreaming" | "request_streaming" => {
+                parser.field(&mut self.request_streaming, |parser| parser.scalar())?
+            }
+            "responseTypeUrl" | "response_type_url" => {
+                parser.field(&mut self.response_type_url, |parser| parser.scalar())?
+            }
+            "responseStreaming" | "response_streaming" => {
+                parser.field(&mut self.response_streaming, |parser| parser.scalar())?
+            }
+            "options" => parser.repeated(&mut self.options, |parser| parser.message())?,
+            "syntax" => {
+                parser
+                    .field(
+                        &mut self.syntax,
+                        |parser| {
+                            parser
+                                .enumeration(|name| {
+                                    Syntax::from_str_name(name).map(|value| value as i32)
+                                })
+                        },
+                    )?
+            }
+            _ => return ::core::result::Result::Ok(false),
+        }
+        ::core::result::Result::Ok(true)
+    }
+}
+#[allow(deprecated)]
+impl ::prost::FieldMasked for Method {
+    fn check_mask_tree(
+        mask: &::prost::field_mask::FieldMaskTree,
+    ) -> ::core::result::Result<(), ::prost::field_mask::FieldMaskError> {
+        for (name, mask) in mask.iter() {
+            match name {
+                "name" => ::prost::field_mask::check_leaf("name", mask)?,
+                "request_type_url" => {
+                    ::prost::field_mask::check_leaf("request_type_url", mask)?
+                }
+                "request_streaming" => {
+                    ::prost::field_mask::check_leaf("request_streaming", mask)?
+                }
+                "response_type_url" => {
+                    ::prost::field_mask::check_leaf("response_type_url", mask)?
+                }
+                "response_streaming" => {
+                    ::prost::field_mask::check_leaf("response_streaming", mask)?
+                }
+                "options" => ::prost::field_mask::check_leaf("options", mask)?,
+                "syntax" => ::prost::field_mask::check_leaf("syntax", mask)?,
+                _ => {
+                    return ::core::result::Result::Err(
+                        ::prost::field_mask::unknown_field(name),
+                    );
+                }
+            }
+        }
+        ::core::result::Result::Ok(())
+    }
+    fn merge_with_mask_tree(
+        &mut self,
+        src: &Self,
+        mask: &::prost::field_mask::FieldMaskTree,
+        options: &::prost::field_mask::MergeOptions,
+    ) {
+        if mask.get("name").is_some() {
+            self.name.clone_from(&src.name);
+        }
+        if mask.get("request_type_url").is_some() {
+            self.request_type_url.clone_from(&src.request_type_url);
+        }
+        if mask.get("request_streaming").is_some() {
+            self.request_streaming.clone_from(&src.request_streaming);
+        }
+        if mask.get("response_type_url").is_some() {
+            self.response_type_url.clone_from(&src.response_type_url);
+        }
+        if mask.get("response_streaming").is_some() {
+            self.response_streaming.clone_from(&src.response_streaming);
+        }
+        if mask.get("options").is_some() {
+            ::prost::field_mask::merge_repeated(
+                &mut self.options,
+                &src.options,
+                options,
+            );
+        }
+        if mask.get("syntax").is_some() {
+            self.syntax.clone_from(&src.syntax);
+        }
+    }
+    fn prune_with_mask_tree(&mut self, mask: &::prost::field_mask::FieldMaskTree) {
+        let default = Self::default();
+        if mask.get("name").is_none() {
+            self.name = default.name;
+        }
+        if mask.get("request_type_url").is_none() {
+            self.request_type_url = default.request_type_url;
+        }
+        if mask.get("request_streaming").is_none() {
+            self.request_streaming = default.request_streaming;
+        }
+        if mask.get("response_type_url").is_none() {
+            self.response_type_url = default.response_type_url;
+        }
+        if mask.get("response_streaming").is_none() {
+            self.response_streaming = default.response_streaming;
+        }
+        if mask.get("options").is_none() {
+            self.options = default.options;
+        }
+        if mask.get("syntax").is_none() {
+            self.syntax = default.syntax;
+        }
+    }
+}
 /// Declares an API Interface to be included in this interface. The including
 /// interface must redeclare all the methods from the included interface, but
 /// documentation and options are inherited as follows:
@@ -1788,6 +8760,97 @@ pub struct Mixin {
     #[prost(string, tag = "2")]
     pub root: ::prost::alloc::string::String,
 }
+#[allow(deprecated)]
+impl ::prost::text_format::TextFormat for Mixin {
+    fn print_fields(&self, printer: &mut ::prost::text_format::Printer<'_>) {
+        if !::prost::text_format::is_default(&self.name) {
+            printer.scalar("name", &self.name);
+        }
+        if !::prost::text_format::is_default(&self.root) {
+            printer.scalar("root", &self.root);
+        }
+    }
+    fn merge_text_field(
+        &mut self,
+        name: &str,
+        parser: &mut ::prost::text_format::Parser<'_>,
+    ) -> ::core::result::Result<bool, ::prost::text_format::ParseError> {
+        match name {
+            "name" => parser.scalar(&mut self.name)?,
+            "root" => parser.scalar(&mut self.root)?,
+            _ => return ::core::result::Result::Ok(false),
+        }
+        ::core::result::Result::Ok(true)
+    }
+}
+#[allow(deprecated)]
+impl ::prost::json::Json for Mixin {
+    fn write_json_fields(
+        &self,
+        writer: &mut ::prost::json::Writer<'_>,
+    ) -> ::core::result::Result<(), ::prost::json::JsonError> {
+        if writer.emit_defaults() || !::prost::json::is_default(&self.name) {
+            writer.field("name", "name", |writer| writer.scalar(&self.name))?;
+        }
+        if writer.emit_defaults() || !::prost::json::is_default(&self.root) {
+            writer.field("root", "root", |writer| writer.scalar(&self.root))?;
+        }
+        ::core::result::Result::Ok(())
+    }
+    fn merge_json_field(
+        &mut self,
+        name: &str,
+        parser: &mut ::prost::json::Parser<'_>,
+    ) -> ::core::result::Result<bool, ::prost::json::JsonError> {
+        match name {
+            "name" => parser.field(&mut self.name, |parser| parser.scalar())?,
+            "root" => parser.field(&mut self.root, |parser| parser.scalar())?,
+            _ => return ::core::result::Result::Ok(false),
+        }
+        ::core::result::Result::Ok(true)
+    }
+}
+#[allow(deprecated)]
+impl ::prost::FieldMasked for Mixin {
+    fn check_mask_tree(
+        mask: &::prost::field_mask::FieldMaskTree,
+    ) -> ::core::result::Result<(), ::prost::field_mask::FieldMaskError> {
+        for (name, mask) in mask.iter() {
+            match name {
+                "name" => ::prost::field_mask::check_leaf("name", mask)?,
+                "root" => ::prost::field_mask::check_leaf("root", mask)?,
+                _ => {
+                    return ::core::result::Result::Err(
+                        ::prost::field_mask::unknown_field(name),
+                    );
+                }
+            }
+        }
+        ::core::result::Result::Ok(())
+    }
+    fn merge_with_mask_tree(
+        &mut self,
+        src: &Self,
+        mask: &::prost::field_mask::FieldMaskTree,
+        _options: &::prost::field_mask::MergeOptions,
+    ) {
+        if mask.get("name").is_some() {
+            self.name.clone_from(&src.name);
+        }
+        if mask.get("root").is_some() {
+            self.root.clone_from(&src.root);
+        }
+    }
+    fn prune_with_mask_tree(&mut self, mask: &::prost::field_mask::FieldMaskTree) {
+        let default = Self::default();
+        if mask.get("name").is_none() {
+            self.name = default.name;
+        }
+        if mask.get("root").is_none() {
+            self.root = default.root;
+        }
+    }
+}
 /// A Duration represents a signed, fixed-length span of time represented
 /// as a count of seconds and fractions of seconds at nanosecond
 /// resolution. It is independent of any calendar and concepts like "day"
@@ -1869,6 +8932,70 @@ pub struct Duration {
     #[prost(int32, tag = "2")]
     pub nanos: i32,
 }
+#[allow(deprecated)]
+impl ::prost::text_format::TextFormat for Duration {
+    fn print_fields(&self, printer: &mut ::prost::text_format::Printer<'_>) {
+        if !::prost::text_format::is_default(&self.seconds) {
+            printer.scalar("seconds", &self.seconds);
+        }
+        if !::prost::text_format::is_default(&self.nanos) {
+            printer.scalar("nanos", &self.nanos);
+        }
+    }
+    fn merge_text_field(
+        &mut self,
+        name: &str,
+        parser: &mut ::prost::text_format::Parser<'_>,
+    ) -> ::core::result::Result<bool, ::prost::text_format::ParseError> {
+        match name {
+            "seconds" => parser.scalar(&mut self.seconds)?,
+            "nanos" => parser.scalar(&mut self.nanos)?,
+            _ => return ::core::result::Result::Ok(false),
+        }
+        ::core::result::Result::Ok(true)
+    }
+}
+#[allow(deprecated)]
+impl ::prost::FieldMasked for Duration {
+    fn check_mask_tree(
+        mask: &::prost::field_mask::FieldMaskTree,
+    ) -> ::core::result::Result<(), ::prost::field_mask::FieldMaskError> {
+        for (name, mask) in mask.iter() {
+            match name {
+                "seconds" => ::prost::field_mask::check_leaf("seconds", mask)?,
+                "nanos" => ::prost::field_mask::check_leaf("nanos", mask)?,
+                _ => {
+                    return ::core::result::Result::Err(
+                        ::prost::field_mask::unknown_field(name),
+                    );
+                }
+            }
+        }
+        ::core::result::Result::Ok(())
+    }
+    fn merge_with_mask_tree(
+        &mut self,
+        src: &Self,
+        mask: &::prost::field_mask::FieldMaskTree,
+        _options: &::prost::field_mask::MergeOptions,
+    ) {
+        if mask.get("seconds").is_some() {
+            self.seconds.clone_from(&src.seconds);
+        }
+        if mask.get("nanos").is_some() {
+            self.nanos.clone_from(&src.nanos);
+        }
+    }
+    fn prune_with_mask_tree(&mut self, mask: &::prost::field_mask::FieldMaskTree) {
+        let default = Self::default();
+        if mask.get("seconds").is_none() {
+            self.seconds = default.seconds;
+        }
+        if mask.get("nanos").is_none() {
+            self.nanos = default.nanos;
+        }
+    }
+}
 /// `FieldMask` represents a set of symbolic field paths, for example:
 ///
 /// ```text
@@ -2098,6 +9225,59 @@ pub struct FieldMask {
     #[prost(string, repeated, tag = "1")]
     pub paths: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
 }
+#[allow(deprecated)]
+impl ::prost::text_format::TextFormat for FieldMask {
+    fn print_fields(&self, printer: &mut ::prost::text_format::Printer<'_>) {
+        for value in &self.paths {
+            printer.scalar("paths", value);
+        }
+    }
+    fn merge_text_field(
+        &mut self,
+        name: &str,
+        parser: &mut ::prost::text_format::Parser<'_>,
+    ) -> ::core::result::Result<bool, ::prost::text_format::ParseError> {
+        match name {
+            "paths" => parser.repeated_scalar(&mut self.paths)?,
+            _ => return ::core::result::Result::Ok(false),
+        }
+        ::core::result::Result::Ok(true)
+    }
+}
+#[allow(deprecated)]
+impl ::prost::FieldMasked for FieldMask {
+    fn check_mask_tree(
+        mask: &::prost::field_mask::FieldMaskTree,
+    ) -> ::core::result::Result<(), ::prost::field_mask::FieldMaskError> {
+        for (name, mask) in mask.iter() {
+            match name {
+                "paths" => ::prost::field_mask::check_leaf("paths", mask)?,
+                _ => {
+                    return ::core::result::Result::Err(
+                        ::prost::field_mask::unknown_field(name),
+                    );
+                }
+            }
+        }
+        ::core::result::Result::Ok(())
+    }
+    fn merge_with_mask_tree(
+        &mut self,
+        src: &Self,
+        mask: &::prost::field_mask::FieldMaskTree,
+        options: &::prost::field_mask::MergeOptions,
+    ) {
+        if mask.get("paths").is_some() {
+            ::prost::field_mask::merge_repeated(&mut self.paths, &src.paths, options);
+        }
+    }
+    fn prune_with_mask_tree(&mut self, mask: &::prost::field_mask::FieldMaskTree) {
+        let default = Self::default();
+        if mask.get("paths").is_none() {
+            self.paths = default.paths;
+        }
+    }
+}
 /// `Struct` represents a structured data value, consisting of fields
 /// which map to dynamically typed values. In some languages, `Struct`
 /// might be supported by a native representation. For example, in
@@ -2116,6 +9296,86 @@ pub struct Struct {
         Value,
     >,
 }
+#[allow(deprecated)]
+impl ::prost::text_format::TextFormat for Struct {
+    fn print_fields(&self, printer: &mut ::prost::text_format::Printer<'_>) {
+        for (key, value) in &self.fields {
+            printer
+                .block(
+                    "fields",
+                    |printer| {
+                        printer.scalar("key", key);
+                        printer.message("value", value);
+                    },
+                );
+        }
+    }
+    fn merge_text_field(
+        &mut self,
+        name: &str,
+        parser: &mut ::prost::text_format::Parser<'_>,
+    ) -> ::core::result::Result<bool, ::prost::text_format::ParseError> {
+        match name {
+            "fields" => {
+                parser
+                    .repeated(
+                        true,
+                        |parser| {
+                            let mut key = ::core::default::Default::default();
+                            let mut value = ::core::default::Default::default();
+                            parser
+                                .block(|parser, name| {
+                                    match name {
+                                        "key" => parser.scalar(&mut key)?,
+                                        "value" => parser.message(&mut value)?,
+                                        _ => return ::core::result::Result::Ok(false),
+                                    }
+                                    ::core::result::Result::Ok(true)
+                                })?;
+                            self.fields.insert(key, value);
+                            ::core::result::Result::Ok(())
+                        },
+                    )?
+            }
+            _ => return ::core::result::Result::Ok(false),
+        }
+        ::core::result::Result::Ok(true)
+    }
+}
+#[allow(deprecated)]
+impl ::prost::FieldMasked for Struct {
+    fn check_mask_tree(
+        mask: &::prost::field_mask::FieldMaskTree,
+    ) -> ::core::result::Result<(), ::prost::field_mask::FieldMaskError> {
+        for (name, mask) in mask.iter() {
+            match name {
+                "fields" => ::prost::field_mask::check_leaf("fields", mask)?,
+                _ => {
+                    return ::core::result::Result::Err(
+                        ::prost::field_mask::unknown_field(name),
+                    );
+                }
+            }
+        }
+        ::core::result::Result::Ok(())
+    }
+    fn merge_with_mask_tree(
+        &mut self,
+        src: &Self,
+        mask: &::prost::field_mask::FieldMaskTree,
+        options: &::prost::field_mask::MergeOptions,
+    ) {
+        if mask.get("fields").is_some() {
+            ::prost::field_mask::merge_map(&mut self.fields, &src.fields, options);
+        }
+    }
+    fn prune_with_mask_tree(&mut self, mask: &::prost::field_mask::FieldMaskTree) {
+        let default = Self::default();
+        if mask.get("fields").is_none() {
+            self.fields = default.fields;
+        }
+    }
+}
 /// `Value` represents a dynamically typed value which can be either
 /// null, a number, a string, a boolean, a recursive struct value, or a
 /// list of values. A producer of value is expected to set one of these
@@ -2129,6 +9389,246 @@ pub struct Value {
     #[prost(oneof = "value::Kind", tags = "1, 2, 3, 4, 5, 6")]
     pub kind: ::core::option::Option<value::Kind>,
 }
+#[allow(deprecated)]
+impl ::prost::text_format::TextFormat for Value {
+    fn print_fields(&self, printer: &mut ::prost::text_format::Printer<'_>) {
+        if let ::core::option::Option::Some(oneof) = &self.kind {
+            match oneof {
+                value::Kind::NullValue(value) => {
+                    printer
+                        .enumeration(
+                            "null_value",
+                            *value,
+                            |value| {
+                                NullValue::try_from(value)
+                                    .ok()
+                                    .map(|value| value.as_str_name())
+                            },
+                        )
+                }
+                value::Kind::NumberValue(value) => printer.scalar("number_value", value),
+                value::Kind::StringValue(value) => printer.scalar("string_value", value),
+                value::Kind::BoolValue(value) => printer.scalar("bool_value", value),
+                value::Kind::StructValue(value) => printer.message("struct_value", value),
+                value::Kind::ListValue(value) => printer.message("list_value", value),
+            }
+        }
+    }
+    fn merge_text_field(
+        &mut self,
+        name: &str,
+        parser: &mut ::prost::text_format::Parser<'_>,
+    ) -> ::core::result::Result<bool, ::prost::text_format::ParseError> {
+        match name {
+            "null_value" => {
+                let mut value = ::core::default::Default::default();
+                parser
+                    .enumeration(
+                        &mut value,
+                        |name| NullValue::from_str_name(name).map(|value| value as i32),
+                    )?;
+                self.kind = ::core::option::Option::Some(value::Kind::NullValue(value));
+            }
+            "number_value" => {
+                let mut value = ::core::default::Default::default();
+                parser.scalar(&mut value)?;
+                self.kind = ::core::option::Option::Some(
+                    value::Kind::NumberValue(value),
+                );
+            }
+            "string_value" => {
+                let mut value = ::core::default::Default::default();
+                parser.scalar(&mut value)?;
+                self.kind = ::core::option::Option::Some(
+                    value::Kind::StringValue(value),
+                );
+            }
+            "bool_value" => {
+                let mut value = ::core::default::Default::default();
+                parser.scalar(&mut value)?;
+                self.kind = ::core::option::Option::Some(value::Kind::BoolValue(value));
+            }
+            "struct_value" => {
+                let mut value = ::core::default::Default::default();
+                parser.message(&mut value)?;
+                self.kind = ::core::option::Option::Some(
+                    value::Kind::StructValue(value),
+                );
+            }
+            "list_value" => {
+                let mut value = ::core::default::Default::default();
+                parser.message(&mut value)?;
+                self.kind = ::core::option::Option::Some(value::Kind::ListValue(value));
+            }
+            _ => return ::core::result::Result::Ok(false),
+        }
+        ::core::result::Result::Ok(true)
+    }
+}
+#[allow(deprecated)]
+impl ::prost::FieldMasked for Value {
+    fn check_mask_tree(
+        mask: &::prost::field_mask::FieldMaskTree,
+    ) -> ::core::result::Result<(), ::prost::field_mask::FieldMaskError> {
+        for (name, mask) in mask.iter() {
+            match name {
+                "null_value" => ::prost::field_mask::check_leaf("null_value", mask)?,
+                "number_value" => ::prost::field_mask::check_leaf("number_value", mask)?,
+                "string_value" => ::prost::field_mask::check_leaf("string_value", mask)?,
+                "bool_value" => ::prost::field_mask::check_leaf("bool_value", mask)?,
+                "struct_value" => {
+                    ::prost::field_mask::check_message::<Struct>("struct_value", mask)?
+                }
+                "list_value" => {
+                    ::prost::field_mask::check_message::<ListValue>("list_value", mask)?
+                }
+                _ => {
+                    return ::core::result::Result::Err(
+                        ::prost::field_mask::unknown_field(name),
+                    );
+                }
+            }
+        }
+        ::core::result::Result::Ok(())
+    }
+    fn merge_with_mask_tree(
+        &mut self,
+        src: &Self,
+        mask: &::prost::field_mask::FieldMaskTree,
+        options: &::prost::field_mask::MergeOptions,
+    ) {
+        if mask.get("null_value").is_some() {
+            match &src.kind {
+                ::core::option::Option::Some(value::Kind::NullValue(_)) => {
+                    self.kind.clone_from(&src.kind)
+                }
+                _ => {
+                    if matches!(
+                        self.kind,
+                        ::core::option::Option::Some(value::Kind::NullValue(_))
+                    ) {
+                        self.kind = ::core::option::Option::None;
+                    }
+                }
+            }
+        }
+        if mask.get("number_value").is_some() {
+            match &src.kind {
+                ::core::option::Option::Some(value::Kind::NumberValue(_)) => {
+                    self.kind.clone_from(&src.kind)
+                }
+                _ => {
+                    if matches!(
+                        self.kind,
+                        ::core::option::Option::Some(value::Kind::NumberValue(_))
+                    ) {
+                        self.kind = ::core::option::Option::None;
+                    }
+                }
+            }
+        }
+        if mask.get("string_value").is_some() {
+            match &src.kind {
+                ::core::option::Option::Some(value::Kind::StringValue(_)) => {
+                    self.kind.clone_from(&src.kind)
+                }
+                _ => {
+                    if matches!(
+                        self.kind,
+                        ::core::option::Option::Some(value::Kind::StringValue(_))
+                    ) {
+                        self.kind = ::core::option::Option::None;
+                    }
+                }
+            }
+        }
+        if mask.get("bool_value").is_some() {
+            match &src.kind {
+                ::core::option::Option::Some(value::Kind::BoolValue(_)) => {
+                    self.kind.clone_from(&src.kind)
+                }
+                _ => {
+                    if matches!(
+                        self.kind,
+                        ::core::option::Option::Some(value::Kind::BoolValue(_))
+                    ) {
+                        self.kind = ::core::option::Option::None;
+                    }
+                }
+            }
+        }
+        if let ::core::option::Option::Some(mask) = mask.get("struct_value") {
+            let mut value = match self.kind.take() {
+                ::core::option::Option::Some(value::Kind::StructValue(value)) => {
+                    ::core::option::Option::Some(value)
+                }
+                oneof => {
+                    self.kind = oneof;
+                    ::core::option::Option::None
+                }
+            };
+            let src = match &src.kind {
+                ::core::option::Option::Some(value::Kind::StructValue(value)) => {
+                    ::core::option::Option::Some(value)
+                }
+                _ => ::core::option::Option::None,
+            };
+            ::prost::field_mask::merge_message(&mut value, src, mask, options);
+            if let ::core::option::Option::Some(value) = value {
+                self.kind = ::core::option::Option::Some(
+                    value::Kind::StructValue(value),
+                );
+            }
+        }
+        if let ::core::option::Option::Some(mask) = mask.get("list_value") {
+            let mut value = match self.kind.take() {
+                ::core::option::Option::Some(value::Kind::ListValue(value)) => {
+                    ::core::option::Option::Some(value)
+                }
+                oneof => {
+                    self.kind = oneof;
+                    ::core::option::Option::None
+                }
+            };
+            let src = match &src.kind {
+                ::core::option::Option::Some(value::Kind::ListValue(value)) => {
+                    ::core::option::Option::Some(value)
+                }
+                _ => ::core::option::Option::None,
+            };
+            ::prost::field_mask::merge_message(&mut value, src, mask, options);
+            if let ::core::option::Option::Some(value) = value {
+                self.kind = ::core::option::Option::Some(value::Kind::ListValue(value));
+            }
+        }
+    }
+    fn prune_with_mask_tree(&mut self, mask: &::prost::field_mask::FieldMaskTree) {
+        let keep = match &mut self.kind {
+            ::core::option::Option::Some(value::Kind::NullValue(_)) => {
+                mask.get("null_value").is_some()
+            }
+            ::core::option::Option::Some(value::Kind::NumberValue(_)) => {
+                mask.get("number_value").is_some()
+            }
+            ::core::option::Option::Some(value::Kind::StringValue(_)) => {
+                mask.get("string_value").is_some()
+            }
+            ::core::option::Option::Some(value::Kind::BoolValue(_)) => {
+                mask.get("bool_value").is_some()
+            }
+            ::core::option::Option::Some(value::Kind::StructValue(value)) => {
+                ::prost::field_mask::prune_message_value(value, mask.get("struct_value"))
+            }
+            ::core::option::Option::Some(value::Kind::ListValue(value)) => {
+                ::prost::field_mask::prune_message_value(value, mask.get("list_value"))
+            }
+            ::core::option::Option::None => true,
+        };
+        if !keep {
+            self.kind = ::core::option::Option::None;
+        }
+    }
+}
 /// Nested message and enum types in `Value`.
 pub mod value {
     /// The kind of value.
@@ -2165,6 +9665,59 @@ pub struct ListValue {
     #[prost(message, repeated, tag = "1")]
     pub values: ::prost::alloc::vec::Vec<Value>,
 }
+#[allow(deprecated)]
+impl ::prost::text_format::TextFormat for ListValue {
+    fn print_fields(&self, printer: &mut ::prost::text_format::Printer<'_>) {
+        for value in &self.values {
+            printer.message("values", value);
+        }
+    }
+    fn merge_text_field(
+        &mut self,
+        name: &str,
+        parser: &mut ::prost::text_format::Parser<'_>,
+    ) -> ::core::result::Result<bool, ::prost::text_format::ParseError> {
+        match name {
+            "values" => parser.repeated_message(&mut self.values)?,
+            _ => return ::core::result::Result::Ok(false),
+        }
+        ::core::result::Result::Ok(true)
+    }
+}
+#[allow(deprecated)]
+impl ::prost::FieldMasked for ListValue {
+    fn check_mask_tree(
+        mask: &::prost::field_mask::FieldMaskTree,
+    ) -> ::core::result::Result<(), ::prost::field_mask::FieldMaskError> {
+        for (name, mask) in mask.iter() {
+            match name {
+                "values" => ::prost::field_mask::check_leaf("values", mask)?,
+                _ => {
+                    return ::core::result::Result::Err(
+                        ::prost::field_mask::unknown_field(name),
+                    );
+                }
+            }
+        }
+        ::core::result::Result::Ok(())
+    }
+    fn merge_with_mask_tree(
+        &mut self,
+        src: &Self,
+        mask: &::prost::field_mask::FieldMaskTree,
+        options: &::prost::field_mask::MergeOptions,
+    ) {
+        if mask.get("values").is_some() {
+            ::prost::field_mask::merge_repeated(&mut self.values, &src.values, options);
+        }
+    }
+    fn prune_with_mask_tree(&mut self, mask: &::prost::field_mask::FieldMaskTree) {
+        let default = Self::default();
+        if mask.get("values").is_none() {
+            self.values = default.values;
+        }
+    }
+}
 /// `NullValue` is a singleton enumeration to represent the null value for the
 /// `Value` type union.
 ///
@@ -2307,3 +9860,67 @@ pub struct Timestamp {
     #[prost(int32, tag = "2")]
     pub nanos: i32,
 }
+#[allow(deprecated)]
+impl ::prost::text_format::TextFormat for Timestamp {
+    fn print_fields(&self, printer: &mut ::prost::text_format::Printer<'_>) {
+        if !::prost::text_format::is_default(&self.seconds) {
+            printer.scalar("seconds", &self.seconds);
+        }
+        if !::prost::text_format::is_default(&self.nanos) {
+            printer.scalar("nanos", &self.nanos);
+        }
+    }
+    fn merge_text_field(
+        &mut self,
+        name: &str,
+        parser: &mut ::prost::text_format::Parser<'_>,
+    ) -> ::core::result::Result<bool, ::prost::text_format::ParseError> {
+        match name {
+            "seconds" => parser.scalar(&mut self.seconds)?,
+            "nanos" => parser.scalar(&mut self.nanos)?,
+            _ => return ::core::result::Result::Ok(false),
+        }
+        ::core::result::Result::Ok(true)
+    }
+}
+#[allow(deprecated)]
+impl ::prost::FieldMasked for Timestamp {
+    fn check_mask_tree(
+        mask: &::prost::field_mask::FieldMaskTree,
+    ) -> ::core::result::Result<(), ::prost::field_mask::FieldMaskError> {
+        for (name, mask) in mask.iter() {
+            match name {
+                "seconds" => ::prost::field_mask::check_leaf("seconds", mask)?,
+                "nanos" => ::prost::field_mask::check_leaf("nanos", mask)?,
+                _ => {
+                    return ::core::result::Result::Err(
+                        ::prost::field_mask::unknown_field(name),
+                    );
+                }
+            }
+        }
+        ::core::result::Result::Ok(())
+    }
+    fn merge_with_mask_tree(
+        &mut self,
+        src: &Self,
+        mask: &::prost::field_mask::FieldMaskTree,
+        _options: &::prost::field_mask::MergeOptions,
+    ) {
+        if mask.get("seconds").is_some() {
+            self.seconds.clone_from(&src.seconds);
+        }
+        if mask.get("nanos").is_some() {
+            self.nanos.clone_from(&src.nanos);
+        }
+    }
+    fn prune_with_mask_tree(&mut self, mask: &::prost::field_mask::FieldMaskTree) {
+        let default = Self::default();
+        if mask.get("seconds").is_none() {
+            self.seconds = default.seconds;
+        }
+        if mask.get("nanos").is_none() {
+            self.nanos = default.nanos;
+        }
+    }
+}
@@ -81,9 +81,12 @@ fn main() -> Result<()> {
     // Generate BTreeMap fields for all messages. This forces encoded output to be consistent, so
     // that encode/decode roundtrips can use encoded output for comparison. Otherwise trying to
     // compare based on the Rust PartialEq implementations is difficult, due to presence of NaN
-    // values.
+    // values. Unknown fields are preserved in all messages, as the conformance tests expect.
     prost_build::Config::new()
         .btree_map(["."])
+        .preserve_unknown_fields(["."])
+        .enable_text_format()
+        .enable_json()
         .compile_protos(
             &[
                 test_includes.join("test_messages_proto2.proto"),
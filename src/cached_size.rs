@@ -0,0 +1,112 @@
+//! A cell holding the encoded length of a message while it is encoded.
+
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+/// The encoded length of a message, cached while the message is encoded.
+///
+/// Encoding a nested message requires its length up front. `Message::encode` and the other encode
+/// methods compute the lengths of all nested messages before encoding, and record them in a table
+/// which grows with the number of nested messages. A message type opts into holding its own length
+/// instead by declaring a field of this type with the `#[prost(cached_size)]` attribute:
+///
+/// ```rust
+/// # use prost::{CachedSize, Message};
+/// #[derive(Clone, PartialEq, Message)]
+/// struct Person {
+///     #[prost(string, tag = "1")]
+///     name: String,
+///     #[prost(cached_size)]
+///     cached_size: CachedSize,
+/// }
+/// ```
+///
+/// When a message containing `Person` values is encoded, the length of each `Person` is recorded
+/// in its cell while the length of the containing message is computed, and taken from the cell
+/// when the `Person` is encoded. Every encode records the lengths before it reads them, so a length
+/// left in a cell by an encode which was aborted, e.g. by a panic, is never used.
+///
+/// The cell is ignored when comparing and hashing messages, and is not cloned.
+#[derive(Default)]
+pub struct CachedSize {
+    /// The cached length plus one, or zero if no length is cached.
+    len: AtomicUsize,
+}
+
+impl CachedSize {
+    /// Creates an empty cell.
+    pub const fn new() -> CachedSize {
+        CachedSize {
+            len: AtomicUsize::new(0),
+        }
+    }
+
+    /// Returns the cached length, if any.
+    #[inline]
+    pub fn get(&self) -> Option<usize> {
+        match self.len.load(Ordering::Relaxed) {
+            0 => None,
+            len => Some(len - 1),
+        }
+    }
+
+    /// Caches a length.
+    #[inline]
+    pub(crate) fn set(&self, len: usize) {
+        self.len.store(len + 1, Ordering::Relaxed);
+    }
+
+    /// Takes the cached length, if any, leaving the cell empty.
+    #[inline]
+    pub(crate) fn take(&self) -> Option<usize> {
+        match self.len.swap(0, Ordering::Relaxed) {
+            0 => None,
+            len => Some(len - 1),
+        }
+    }
+}
+
+impl Clone for CachedSize {
+    fn clone(&self) -> CachedSize {
+        CachedSize::new()
+    }
+}
+
+impl PartialEq for CachedSize {
+    fn eq(&self, _: &CachedSize) -> bool {
+        true
+    }
+}
+
+impl Eq for CachedSize {}
+
+impl Hash for CachedSize {
+    fn hash<H: Hasher>(&self, _: &mut H) {}
+}
+
+impl fmt::Debug for CachedSize {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("CachedSize").field(&self.get()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn get_set_take() {
+        let cached_size = CachedSize::new();
+        assert_eq!(cached_size.get(), None);
+        cached_size.set(0);
+        assert_eq!(cached_size.get(), Some(0));
+        cached_size.set(300);
+        assert_eq!(cached_size.get(), Some(300));
+        assert_eq!(cached_size.clone().get(), None);
+        assert_eq!(cached_size, CachedSize::new());
+        assert_eq!(cached_size.take(), Some(300));
+        assert_eq!(cached_size.get(), None);
+        assert_eq!(cached_size.take(), None);
+    }
+}
@@ -0,0 +1,349 @@
+//! A [`tokio_util::codec`] implementation for framing messages over byte streams.
+//!
+//! [`ProstCodec`] can be used with [`Framed`], [`FramedRead`] and [`FramedWrite`] to send and
+//! receive messages over sockets, pipes and other asynchronous byte streams:
+//!
+//! ```rust
+//! # use prost::codec::ProstCodec;
+//! # use prost::Message;
+//! use tokio_util::codec::Framed;
+//!
+//! #[derive(Clone, PartialEq, Message)]
+//! struct Request {
+//!     #[prost(string, tag = "1")]
+//!     query: String,
+//! }
+//!
+//! #[derive(Clone, PartialEq, Message)]
+//! struct Response {
+//!     #[prost(string, repeated, tag = "1")]
+//!     results: Vec<String>,
+//! }
+//!
+//! # fn frame(stream: tokio::io::DuplexStream) {
+//! // A client sends requests and receives responses.
+//! let client = Framed::new(stream, ProstCodec::<Request, Response>::new());
+//! # }
+//! ```
+//!
+//! This module requires the `tokio-util` feature.
+//!
+//! [`Framed`]: tokio_util::codec::Framed
+//! [`FramedRead`]: tokio_util::codec::FramedRead
+//! [`FramedWrite`]: tokio_util::codec::FramedWrite
+
+use core::fmt;
+use core::marker::PhantomData;
+use std::io;
+
+use bytes::{Buf, BufMut, BytesMut};
+use tokio_util::codec::{Decoder, Encoder};
+
+use crate::encoding::{decode_partial_varint, encode_varint, encoded_len_varint, EncodedLenTable};
+use crate::{Message, DEFAULT_MAX_FRAME_LEN};
+
+/// The encoding of the length prefix of each frame.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum LengthPrefix {
+    /// A varint, as written by [`Message::encode_length_delimited`].
+    #[default]
+    Varint,
+    /// A 32-bit little-endian integer.
+    Fixed32Le,
+    /// A 32-bit big-endian integer.
+    Fixed32Be,
+}
+
+impl LengthPrefix {
+    /// Returns the encoded length of the prefix for a frame of `len` bytes.
+    fn encoded_len(self, len: usize) -> usize {
+        match self {
+            LengthPrefix::Varint => encoded_len_varint(len as u64),
+            LengthPrefix::Fixed32Le | LengthPrefix::Fixed32Be => 4,
+        }
+    }
+
+    /// Returns the largest frame length the prefix can represent.
+    fn max_len(self) -> usize {
+        match self {
+            LengthPrefix::Varint => usize::MAX,
+            LengthPrefix::Fixed32Le | LengthPrefix::Fixed32Be => {
+                u32::MAX.try_into().unwrap_or(usize::MAX)
+            }
+        }
+    }
+
+    fn encode(self, len: usize, dst: &mut BytesMut) {
+        match self {
+            LengthPrefix::Varint => encode_varint(len as u64, dst),
+            LengthPrefix::Fixed32Le => dst.put_u32_le(len as u32),
+            LengthPrefix::Fixed32Be => dst.put_u32(len as u32),
+        }
+    }
+
+    /// Decodes a prefix from the start of `src` without consuming it, returning the length of the
+    /// frame and of the prefix, or `None` if more data is needed.
+    fn decode(self, src: &[u8]) -> io::Result<Option<(u64, usize)>> {
+        match self {
+            LengthPrefix::Varint => decode_partial_varint(src).map_err(|_| {
+                io::Error::new(io::ErrorKind::InvalidData, "invalid length delimiter")
+            }),
+            LengthPrefix::Fixed32Le | LengthPrefix::Fixed32Be => {
+                let prefix: [u8; 4] = match src.get(..4) {
+                    Some(prefix) => prefix.try_into().unwrap(),
+                    None => return Ok(None),
+                };
+                let len = if self == LengthPrefix::Fixed32Le {
+                    u32::from_le_bytes(prefix)
+                } else {
+                    u32::from_be_bytes(prefix)
+                };
+                Ok(Some((u64::from(len), 4)))
+            }
+        }
+    }
+}
+
+/// A codec which encodes messages of type `Enc` and decodes messages of type `Dec`, each framed
+/// with a length prefix.
+///
+/// Frames longer than the maximum frame length are rejected in both directions, with an
+/// [`io::ErrorKind::InvalidInput`] error when encoding and an [`io::ErrorKind::InvalidData`]
+/// error when decoding.
+pub struct ProstCodec<Enc, Dec> {
+    length_prefix: LengthPrefix,
+    max_frame_len: usize,
+    _marker: PhantomData<fn(Enc) -> Dec>,
+}
+
+impl<Enc, Dec> ProstCodec<Enc, Dec> {
+    /// Creates a codec using varint length prefixes and a maximum frame length of
+    /// [`DEFAULT_MAX_FRAME_LEN`].
+    pub fn new() -> ProstCodec<Enc, Dec> {
+        ProstCodec {
+            length_prefix: LengthPrefix::Varint,
+            max_frame_len: DEFAULT_MAX_FRAME_LEN,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Sets the encoding of the length prefix of each frame.
+    pub fn length_prefix(mut self, length_prefix: LengthPrefix) -> ProstCodec<Enc, Dec> {
+        self.length_prefix = length_prefix;
+        self
+    }
+
+    /// Sets the maximum length of a message, excluding its length prefix.
+    pub fn max_frame_len(mut self, max_frame_len: usize) -> ProstCodec<Enc, Dec> {
+        self.max_frame_len = max_frame_len;
+        self
+    }
+
+    fn frame_too_long(&self, len: u64, kind: io::ErrorKind) -> io::Error {
+        io::Error::new(
+            kind,
+            format!(
+                "message of {} bytes exceeds the maximum frame length of {} bytes",
+                len,
+                self.max_frame_len.min(self.length_prefix.max_len())
+            ),
+        )
+    }
+}
+
+impl<Enc, Dec> Default for ProstCodec<Enc, Dec> {
+    fn default() -> ProstCodec<Enc, Dec> {
+        ProstCodec::new()
+    }
+}
+
+impl<Enc, Dec> Clone for ProstCodec<Enc, Dec> {
+    fn clone(&self) -> ProstCodec<Enc, Dec> {
+        ProstCodec {
+            length_prefix: self.length_prefix,
+            max_frame_len: self.max_frame_len,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<Enc, Dec> fmt::Debug for ProstCodec<Enc, Dec> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ProstCodec")
+            .field("length_prefix", &self.length_prefix)
+            .field("max_frame_len", &self.max_frame_len)
+            .finish()
+    }
+}
+
+impl<Enc, Dec> Encoder<Enc> for ProstCodec<Enc, Dec>
+where
+    Enc: Message,
+{
+    type Error = io::Error;
+
+    fn encode(&mut self, item: Enc, dst: &mut BytesMut) -> io::Result<()> {
+        let mut table = EncodedLenTable::new();
+        let len = item.encoded_len_with_table(&mut table);
+        if len > self.max_frame_len || len > self.length_prefix.max_len() {
+            return Err(self.frame_too_long(len as u64, io::ErrorKind::InvalidInput));
+        }
+
+        dst.reserve(self.length_prefix.encoded_len(len) + len);
+        self.length_prefix.encode(len, dst);
+        item.encode_raw_with_table(&mut table, dst);
+        Ok(())
+    }
+}
+
+impl<Enc, Dec> Decoder for ProstCodec<Enc, Dec>
+where
+    Dec: Message + Default,
+{
+    type Item = Dec;
+    type Error = io::Error;
+
+    fn decode(&mut self, src: &mut BytesMut) -> io::Result<Option<Dec>> {
+        let (len, prefix_len) = match self.length_prefix.decode(src)? {
+            Some(prefix) => prefix,
+            None => return Ok(None),
+        };
+        if len > self.max_frame_len as u64 {
+            return Err(self.frame_too_long(len, io::ErrorKind::InvalidData));
+        }
+
+        let frame_len = prefix_len + len as usize;
+        if src.len() < frame_len {
+            src.reserve(frame_len - src.len());
+            return Ok(None);
+        }
+
+        src.advance(prefix_len);
+        let frame = src.split_to(len as usize).freeze();
+        Ok(Some(Dec::decode(frame)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use alloc::string::{String, ToString};
+    use alloc::vec::Vec;
+
+    use futures_util::{SinkExt, StreamExt};
+    use tokio::io::{duplex, AsyncWriteExt};
+    use tokio_util::codec::{Framed, FramedRead, FramedWrite};
+
+    fn messages() -> Vec<String> {
+        ["hello", "", "world"]
+            .iter()
+            .map(|s| s.to_string())
+            .chain(Some("x".repeat(1000)))
+            .collect()
+    }
+
+    async fn roundtrip(length_prefix: LengthPrefix) {
+        // A small duplex buffer forces frames to be split across reads.
+        let (client, server) = duplex(16);
+        let mut client = Framed::new(
+            client,
+            ProstCodec::<String, u64>::new().length_prefix(length_prefix),
+        );
+        let mut server = Framed::new(
+            server,
+            ProstCodec::<u64, String>::new().length_prefix(length_prefix),
+        );
+
+        let writer = tokio::spawn(async move {
+            for msg in messages() {
+                client.send(msg).await.unwrap();
+            }
+            assert_eq!(client.next().await.unwrap().unwrap(), 4);
+        });
+
+        let mut received = Vec::new();
+        for _ in 0..4 {
+            received.push(server.next().await.unwrap().unwrap());
+        }
+        assert_eq!(received, messages());
+        server.send(received.len() as u64).await.unwrap();
+        writer.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn varint() {
+        roundtrip(LengthPrefix::Varint).await;
+    }
+
+    #[tokio::test]
+    async fn fixed32_le() {
+        roundtrip(LengthPrefix::Fixed32Le).await;
+    }
+
+    #[tokio::test]
+    async fn fixed32_be() {
+        roundtrip(LengthPrefix::Fixed32Be).await;
+    }
+
+    #[test]
+    fn prefix_encoding() {
+        let msg = "hello".to_string();
+        let len = msg.encoded_len();
+
+        let mut codec = ProstCodec::<String, String>::new();
+        let mut buf = BytesMut::new();
+        codec.encode(msg.clone(), &mut buf).unwrap();
+        assert_eq!(&buf[..], &msg.encode_length_delimited_to_vec()[..]);
+
+        let mut codec = codec.length_prefix(LengthPrefix::Fixed32Le);
+        let mut buf = BytesMut::new();
+        codec.encode(msg.clone(), &mut buf).unwrap();
+        assert_eq!(&buf[..4], &(len as u32).to_le_bytes()[..]);
+
+        let mut codec = codec.length_prefix(LengthPrefix::Fixed32Be);
+        let mut buf = BytesMut::new();
+        codec.encode(msg.clone(), &mut buf).unwrap();
+        assert_eq!(&buf[..4], &(len as u32).to_be_bytes()[..]);
+        assert_eq!(codec.decode(&mut buf).unwrap().unwrap(), msg);
+        assert!(buf.is_empty());
+    }
+
+    #[tokio::test]
+    async fn max_frame_len() {
+        let mut codec = ProstCodec::<String, String>::new().max_frame_len(100);
+        let error = codec
+            .encode("x".repeat(101), &mut BytesMut::new())
+            .unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+
+        // The frame is rejected as soon as its prefix arrives.
+        let (mut client, server) = duplex(64);
+        let mut server = FramedRead::new(server, codec);
+        client.write_all(&[0xc8, 0x01, 0x0a]).await.unwrap();
+        let error = server.next().await.unwrap().unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[tokio::test]
+    async fn truncated_stream() {
+        let (client, server) = duplex(64);
+        let mut client = FramedWrite::new(client, ProstCodec::<String, String>::new());
+        client.send("hello".to_string()).await.unwrap();
+        let mut client = client.into_inner();
+        client.write_all(&[0x07, 0x0a]).await.unwrap();
+        drop(client);
+
+        let mut server = FramedRead::new(server, ProstCodec::<String, String>::new());
+        assert_eq!(server.next().await.unwrap().unwrap(), "hello");
+        assert!(server.next().await.unwrap().is_err());
+    }
+
+    #[test]
+    fn invalid_message() {
+        let mut codec = ProstCodec::<String, String>::new();
+        let mut buf = BytesMut::from(&[0x03, 0x0a, 0x05, b'x'][..]);
+        let error = codec.decode(&mut buf).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    }
+}
@@ -0,0 +1,444 @@
+//! Reading and writing streams of length-delimited messages.
+
+use alloc::vec::Vec;
+use core::fmt;
+use core::marker::PhantomData;
+use std::io::{self, Read, Write};
+
+use crate::encoding::{decode_partial_varint, encode_varint, encoded_len_varint, EncodedLenTable};
+use crate::Message;
+
+/// The default maximum frame length of [`DelimitedReader`] and [`DelimitedWriter`], in bytes.
+pub const DEFAULT_MAX_FRAME_LEN: usize = 64 * 1024 * 1024;
+
+/// An iterator over a stream of length-delimited messages read from an [`io::Read`].
+///
+/// Each message is expected to be prefixed with its length encoded as a varint, as written by
+/// [`Message::encode_length_delimited`] or [`DelimitedWriter`]. The stream may only end between
+/// messages; an end of stream within a length delimiter or a message is reported as an
+/// [`io::ErrorKind::UnexpectedEof`] error.
+///
+/// The length delimiter is read one byte at a time, so unbuffered readers such as files and
+/// sockets should be wrapped in an [`io::BufReader`].
+///
+/// ```rust
+/// # use prost::{DelimitedReader, DelimitedWriter, Message};
+/// #[derive(Clone, PartialEq, Message)]
+/// struct Entry {
+///     #[prost(string, tag = "1")]
+///     text: String,
+/// }
+///
+/// let mut writer = DelimitedWriter::new(Vec::new());
+/// writer.write_message(&Entry { text: "first".into() }).unwrap();
+/// writer.write_message(&Entry { text: "second".into() }).unwrap();
+/// let log = writer.into_inner();
+///
+/// let entries = DelimitedReader::<Entry, _>::new(log.as_slice())
+///     .collect::<Result<Vec<_>, _>>()
+///     .unwrap();
+/// assert_eq!(entries.len(), 2);
+/// assert_eq!(entries[1].text, "second");
+/// ```
+pub struct DelimitedReader<M, R> {
+    reader: R,
+    buf: Vec<u8>,
+    max_frame_len: usize,
+    done: bool,
+    _marker: PhantomData<fn() -> M>,
+}
+
+impl<M, R> DelimitedReader<M, R>
+where
+    M: Message + Default,
+    R: Read,
+{
+    /// Creates a reader of length-delimited messages from `reader`.
+    pub fn new(reader: R) -> DelimitedReader<M, R> {
+        DelimitedReader {
+            reader,
+            buf: Vec::new(),
+            max_frame_len: DEFAULT_MAX_FRAME_LEN,
+            done: false,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Sets the maximum length of a message, excluding its length delimiter.
+    ///
+    /// A longer message is reported as an [`io::ErrorKind::InvalidData`] error. Defaults to
+    /// [`DEFAULT_MAX_FRAME_LEN`].
+    pub fn max_frame_len(mut self, max_frame_len: usize) -> DelimitedReader<M, R> {
+        self.max_frame_len = max_frame_len;
+        self
+    }
+
+    /// Reads the next message from the stream.
+    ///
+    /// Returns `Ok(None)` if the stream ended cleanly before the next message.
+    ///
+    /// If a message can not be decoded, the error is returned and the reader skips to the next
+    /// message. Any other error leaves the stream at an unknown position, so the reader returns
+    /// no further messages afterwards.
+    pub fn read_message(&mut self) -> io::Result<Option<M>> {
+        if self.done {
+            return Ok(None);
+        }
+        match self.read_frame() {
+            Ok(true) => Ok(Some(M::decode(self.buf.as_slice())?)),
+            Ok(false) => {
+                self.done = true;
+                Ok(None)
+            }
+            Err(error) => {
+                self.done = true;
+                Err(error)
+            }
+        }
+    }
+
+    /// Reads the next frame into `self.buf`, returning `false` at a clean end of stream.
+    fn read_frame(&mut self) -> io::Result<bool> {
+        let len = match read_length_delimiter(&mut self.reader)? {
+            Some(len) => len,
+            None => return Ok(false),
+        };
+        if len > self.max_frame_len as u64 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "length-delimited message of {} bytes exceeds the maximum frame length of {} bytes",
+                    len, self.max_frame_len
+                ),
+            ));
+        }
+
+        self.buf.clear();
+        read_frame(&mut self.reader, len, &mut self.buf)?;
+        Ok(true)
+    }
+
+    /// Returns a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.reader
+    }
+
+    /// Returns a mutable reference to the underlying reader.
+    ///
+    /// Reading from the underlying reader directly may corrupt the stream of messages.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.reader
+    }
+
+    /// Consumes the `DelimitedReader`, returning the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+impl<M, R> Iterator for DelimitedReader<M, R>
+where
+    M: Message + Default,
+    R: Read,
+{
+    type Item = io::Result<M>;
+
+    fn next(&mut self) -> Option<io::Result<M>> {
+        self.read_message().transpose()
+    }
+}
+
+impl<M, R> fmt::Debug for DelimitedReader<M, R>
+where
+    R: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DelimitedReader")
+            .field("reader", &self.reader)
+            .field("max_frame_len", &self.max_frame_len)
+            .field("done", &self.done)
+            .finish()
+    }
+}
+
+/// A writer of length-delimited messages to an [`io::Write`].
+///
+/// Each message is written with its length encoded as a varint prefix, as read by
+/// [`DelimitedReader`] and [`Message::decode_length_delimited`]. Every message is written with a
+/// single call to [`Write::write_all`], so unbuffered writers may be used directly.
+pub struct DelimitedWriter<W> {
+    writer: W,
+    buf: Vec<u8>,
+    max_frame_len: usize,
+}
+
+impl<W> DelimitedWriter<W>
+where
+    W: Write,
+{
+    /// Creates a writer of length-delimited messages to `writer`.
+    pub fn new(writer: W) -> DelimitedWriter<W> {
+        DelimitedWriter {
+            writer,
+            buf: Vec::new(),
+            max_frame_len: DEFAULT_MAX_FRAME_LEN,
+        }
+    }
+
+    /// Sets the maximum length of a message, excluding its length delimiter.
+    ///
+    /// Writing a longer message fails with an [`io::ErrorKind::InvalidInput`] error, and nothing
+    /// is written. Defaults to [`DEFAULT_MAX_FRAME_LEN`].
+    pub fn max_frame_len(mut self, max_frame_len: usize) -> DelimitedWriter<W> {
+        self.max_frame_len = max_frame_len;
+        self
+    }
+
+    /// Writes a length-delimited message to the stream.
+    pub fn write_message<M>(&mut self, msg: &M) -> io::Result<()>
+    where
+        M: Message,
+    {
+        let mut table = EncodedLenTable::new();
+        let len = msg.encoded_len_with_table(&mut table);
+        if len > self.max_frame_len {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "length-delimited message of {} bytes exceeds the maximum frame length of {} bytes",
+                    len, self.max_frame_len
+                ),
+            ));
+        }
+
+        self.buf.clear();
+        self.buf.reserve(encoded_len_varint(len as u64) + len);
+        encode_varint(len as u64, &mut self.buf);
+        msg.encode_raw_with_table(&mut table, &mut self.buf);
+        self.writer.write_all(&self.buf)
+    }
+
+    /// Flushes the underlying writer.
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+
+    /// Returns a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.writer
+    }
+
+    /// Returns a mutable reference to the underlying writer.
+    ///
+    /// Writing to the underlying writer directly may corrupt the stream of messages.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.writer
+    }
+
+    /// Consumes the `DelimitedWriter`, returning the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+impl<W> fmt::Debug for DelimitedWriter<W>
+where
+    W: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DelimitedWriter")
+            .field("writer", &self.writer)
+            .field("max_frame_len", &self.max_frame_len)
+            .finish()
+    }
+}
+
+/// Reads a varint length delimiter, returning `None` at a clean end of stream.
+pub(crate) fn read_length_delimiter<R>(reader: &mut R) -> io::Result<Option<u64>>
+where
+    R: Read,
+{
+    let mut bytes = [0; 10];
+    for count in 0..bytes.len() {
+        loop {
+            match reader.read(&mut bytes[count..=count]) {
+                Ok(0) if count == 0 => return Ok(None),
+                Ok(0) => return Err(unexpected_eof("length delimiter")),
+                Ok(_) => break,
+                Err(error) if error.kind() == io::ErrorKind::Interrupted => continue,
+                Err(error) => return Err(error),
+            }
+        }
+        match decode_partial_varint(&bytes[..=count]) {
+            Ok(Some((len, _))) => return Ok(Some(len)),
+            Ok(None) => {}
+            Err(_) => break,
+        }
+    }
+    Err(io::Error::new(
+        io::ErrorKind::InvalidData,
+        "invalid length delimiter",
+    ))
+}
+
+/// Reads a message of `len` bytes, appending it to `buf`.
+pub(crate) fn read_frame<R>(reader: &mut R, len: u64, buf: &mut Vec<u8>) -> io::Result<()>
+where
+    R: Read,
+{
+    // Only part of the buffer is reserved up front, so that a corrupt or malicious delimiter does
+    // not cause a large allocation before any of the message has arrived.
+    const MAX_RESERVE: u64 = 64 * 1024;
+    buf.reserve(len.min(MAX_RESERVE) as usize);
+
+    let start = buf.len();
+    reader.take(len).read_to_end(buf)?;
+    if ((buf.len() - start) as u64) < len {
+        return Err(unexpected_eof("message"));
+    }
+    Ok(())
+}
+
+fn unexpected_eof(what: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::UnexpectedEof,
+        format!("stream ended within a {}", what),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use alloc::string::{String, ToString};
+    use alloc::vec;
+
+    /// A reader which returns at most one byte per call, interrupting every other call.
+    struct Trickle<'a> {
+        data: &'a [u8],
+        interrupt: bool,
+    }
+
+    impl Read for Trickle<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            self.interrupt = !self.interrupt;
+            if self.interrupt {
+                return Err(io::ErrorKind::Interrupted.into());
+            }
+            let n = self.data.len().min(buf.len()).min(1);
+            buf[..n].copy_from_slice(&self.data[..n]);
+            self.data = &self.data[n..];
+            Ok(n)
+        }
+    }
+
+    fn messages() -> Vec<String> {
+        vec![
+            "hello".to_string(),
+            String::new(),
+            "x".repeat(300),
+            "world".to_string(),
+        ]
+    }
+
+    fn encoded() -> Vec<u8> {
+        let mut writer = DelimitedWriter::new(Vec::new());
+        for msg in messages() {
+            writer.write_message(&msg).unwrap();
+        }
+        writer.into_inner()
+    }
+
+    #[test]
+    fn roundtrip() {
+        let buf = encoded();
+        let mut expected = Vec::new();
+        for msg in messages() {
+            msg.encode_length_delimited(&mut expected).unwrap();
+        }
+        assert_eq!(buf, expected);
+
+        let decoded = DelimitedReader::<String, _>::new(buf.as_slice())
+            .collect::<io::Result<Vec<_>>>()
+            .unwrap();
+        assert_eq!(decoded, messages());
+    }
+
+    #[test]
+    fn partial_reads() {
+        let buf = encoded();
+        let reader = Trickle {
+            data: &buf,
+            interrupt: false,
+        };
+        let decoded = DelimitedReader::<String, _>::new(reader)
+            .collect::<io::Result<Vec<_>>>()
+            .unwrap();
+        assert_eq!(decoded, messages());
+    }
+
+    #[test]
+    fn empty_stream() {
+        let mut reader = DelimitedReader::<String, _>::new(&[][..]);
+        assert!(reader.read_message().unwrap().is_none());
+        assert!(reader.next().is_none());
+    }
+
+    #[test]
+    fn truncated_stream() {
+        let buf = encoded();
+        // Truncate within the delimiter of the third message, and within its contents.
+        let third = 7 + 1 + 1;
+        let fourth = third + 2 + 303;
+        for end in [third + 1, third + 100, buf.len() - 1] {
+            let mut reader = DelimitedReader::<String, _>::new(&buf[..end]);
+            assert_eq!(reader.next().unwrap().unwrap(), "hello");
+            assert_eq!(reader.next().unwrap().unwrap(), "");
+            if end < fourth {
+                let error = reader.next().unwrap().unwrap_err();
+                assert_eq!(error.kind(), io::ErrorKind::UnexpectedEof);
+            } else {
+                reader.next().unwrap().unwrap();
+                let error = reader.next().unwrap().unwrap_err();
+                assert_eq!(error.kind(), io::ErrorKind::UnexpectedEof);
+            }
+            assert!(reader.next().is_none());
+        }
+    }
+
+    #[test]
+    fn max_frame_len() {
+        let buf = encoded();
+        let mut reader = DelimitedReader::<String, _>::new(buf.as_slice()).max_frame_len(100);
+        assert_eq!(reader.next().unwrap().unwrap(), "hello");
+        assert_eq!(reader.next().unwrap().unwrap(), "");
+        let error = reader.next().unwrap().unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        assert!(reader.next().is_none());
+
+        let mut writer = DelimitedWriter::new(Vec::new()).max_frame_len(100);
+        let error = writer.write_message(&"x".repeat(300)).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+        assert!(writer.get_ref().is_empty());
+    }
+
+    #[test]
+    fn invalid_message() {
+        let mut buf = vec![0x03, 0x0a, 0x05, b'x'];
+        "ok".to_string().encode_length_delimited(&mut buf).unwrap();
+        let mut reader = DelimitedReader::<String, _>::new(buf.as_slice());
+        let error = reader.next().unwrap().unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        // The invalid message is skipped.
+        assert_eq!(reader.next().unwrap().unwrap(), "ok");
+        assert!(reader.next().is_none());
+    }
+
+    #[test]
+    fn invalid_delimiter() {
+        let buf = [0xff; 11];
+        let mut reader = DelimitedReader::<String, _>::new(&buf[..]);
+        let error = reader.next().unwrap().unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    }
+}
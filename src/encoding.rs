@@ -17,8 +17,15 @@ use core::usize;
 
 use ::bytes::{Buf, BufMut, Bytes};
 
-use crate::DecodeError;
+use crate::options::Budget;
+use crate::projection::{self, Projection};
+use crate::strict::FieldChecks;
+use crate::DecodeOptions;
 use crate::Message;
+use crate::{DecodeError, DecodeErrorKind};
+
+pub use crate::required::MissingFieldPaths;
+pub use crate::strict::SingularMessageField;
 
 /// Encodes an integer value into LEB128 variable length format, and writes it to the buffer.
 /// The buffer must have enough remaining space (maximum 10 bytes).
@@ -48,7 +55,10 @@ where
     let bytes = buf.chunk();
     let len = bytes.len();
     if len == 0 {
-        return Err(DecodeError::new("invalid varint"));
+        return Err(DecodeError::with_kind(
+            DecodeErrorKind::InvalidVarint,
+            "invalid varint",
+        ));
     }
 
     let byte = bytes[0];
@@ -153,7 +163,10 @@ fn decode_varint_slice(bytes: &[u8]) -> Result<(u64, usize), DecodeError> {
 
     // We have overrun the maximum size of a varint (10 bytes) or the final byte caused an overflow.
     // Assume the data is corrupt.
-    Err(DecodeError::new("invalid varint"))
+    Err(DecodeError::with_kind(
+        DecodeErrorKind::InvalidVarint,
+        "invalid varint",
+    ))
 }
 
 /// Decodes a LEB128-encoded variable length integer from the buffer, advancing the buffer as
@@ -176,14 +189,34 @@ where
             // Check for u64::MAX overflow. See [`ConsumeVarint`][1] for details.
             // [1]: https://github.com/protocolbuffers/protobuf-go/blob/v1.27.1/encoding/protowire/wire.go#L358
             if count == 9 && byte >= 0x02 {
-                return Err(DecodeError::new("invalid varint"));
+                return Err(DecodeError::with_kind(
+                    DecodeErrorKind::InvalidVarint,
+                    "invalid varint",
+                ));
             } else {
                 return Ok(value);
             }
         }
     }
 
-    Err(DecodeError::new("invalid varint"))
+    Err(DecodeError::with_kind(
+        DecodeErrorKind::InvalidVarint,
+        "invalid varint",
+    ))
+}
+
+/// Decodes a varint from the start of bytes which may end before the varint does, such as a
+/// length prefix which has been partially received, returning the value and the number of bytes
+/// read, or `None` if more bytes are needed.
+pub(crate) fn decode_partial_varint(bytes: &[u8]) -> Result<Option<(u64, usize)>, DecodeError> {
+    match bytes.iter().take(10).position(|&byte| byte < 0x80) {
+        Some(end) => decode_varint_slow(&mut &bytes[..=end]).map(|value| Some((value, end + 1))),
+        None if bytes.len() < 10 => Ok(None),
+        None => Err(DecodeError::with_kind(
+            DecodeErrorKind::InvalidVarint,
+            "invalid varint",
+        )),
+    }
 }
 
 /// Additional information passed to every decode/merge function.
@@ -191,46 +224,136 @@ where
 /// The context should be passed by value and can be freely cloned. When passing
 /// to a function which is decoding a nested object, then use `enter_recursion`.
 #[derive(Clone, Debug)]
-#[cfg_attr(feature = "no-recursion-limit", derive(Default))]
-pub struct DecodeContext {
+pub struct DecodeContext<'a> {
     /// How many times we can recurse in the current decode stack before we hit
     /// the recursion limit.
     ///
-    /// The recursion limit is defined by `RECURSION_LIMIT` and cannot be
-    /// customized. The recursion limit can be ignored by building the Prost
-    /// crate with the `no-recursion-limit` feature.
-    #[cfg(not(feature = "no-recursion-limit"))]
+    /// The recursion limit is configured by [`DecodeOptions::recursion_limit`], and
+    /// defaults to `RECURSION_LIMIT`. The default limit can be removed by building the
+    /// Prost crate with the `no-recursion-limit` feature.
     recurse_count: u32,
+    /// The resource budgets shared by all levels of the current decode, if any are
+    /// configured.
+    budget: Option<&'a Budget>,
+    /// The fields to decode at the current level, or `None` to decode all fields.
+    projection: Option<&'a Projection>,
+    /// Whether to reject fields which do not match the schema of the messages.
+    strict: bool,
 }
 
-#[cfg(not(feature = "no-recursion-limit"))]
-impl Default for DecodeContext {
+impl Default for DecodeContext<'_> {
     #[inline]
-    fn default() -> DecodeContext {
+    fn default() -> Self {
         DecodeContext {
             recurse_count: crate::RECURSION_LIMIT,
+            budget: None,
+            projection: None,
+            strict: false,
         }
     }
 }
 
-impl DecodeContext {
+impl<'a> DecodeContext<'a> {
+    /// Creates a context for a top-level decode with the given options.
+    ///
+    /// The budget should be created from the same options with `Budget::new`.
+    #[inline]
+    pub(crate) fn new(options: &DecodeOptions, budget: Option<&'a Budget>) -> DecodeContext<'a> {
+        DecodeContext {
+            recurse_count: options.recursion_limit,
+            budget,
+            projection: None,
+            strict: options.strict,
+        }
+    }
+
+    /// Returns the context with the fields to decode at the current level replaced.
+    #[inline]
+    pub(crate) fn with_projection(self, projection: Option<&'a Projection>) -> DecodeContext<'a> {
+        DecodeContext { projection, ..self }
+    }
+
+    /// Returns the fields to decode at the current level, or `None` if all fields are decoded.
+    #[inline]
+    pub(crate) fn projection(&self) -> Option<&'a Projection> {
+        self.projection
+    }
+
     /// Call this function before recursively decoding.
     ///
     /// There is no `exit` function since this function creates a new `DecodeContext`
     /// to be used at the next level of recursion. Continue to use the old context
     // at the previous level of recursion.
-    #[cfg(not(feature = "no-recursion-limit"))]
     #[inline]
-    pub(crate) fn enter_recursion(&self) -> DecodeContext {
+    pub(crate) fn enter_recursion(&self) -> DecodeContext<'a> {
         DecodeContext {
             recurse_count: self.recurse_count - 1,
+            budget: self.budget,
+            projection: self.projection,
+            strict: self.strict,
+        }
+    }
+
+    /// Returns `true` if fields which do not match the schema of the messages are rejected.
+    #[inline]
+    pub(crate) fn strict(&self) -> bool {
+        self.strict
+    }
+
+    /// Checks a field whose number is not known to the message being decoded, which fails when
+    /// decoding strictly.
+    ///
+    /// Meant to be used only by `Message` implementations.
+    #[doc(hidden)]
+    #[inline]
+    pub fn check_unknown_field(&self, tag: u32) -> Result<(), DecodeError> {
+        if self.strict {
+            Err(DecodeError::with_kind(
+                DecodeErrorKind::UnknownField,
+                format!("unknown field number {}", tag),
+            ))
+        } else {
+            Ok(())
         }
     }
 
-    #[cfg(feature = "no-recursion-limit")]
+    /// Charges a string or bytes value of `len` bytes against the decode budget.
     #[inline]
-    pub(crate) fn enter_recursion(&self) -> DecodeContext {
-        DecodeContext {}
+    pub(crate) fn charge_bytes(&self, len: usize) -> Result<(), DecodeError> {
+        match self.budget {
+            Some(budget) => budget.charge_bytes(len),
+            None => Ok(()),
+        }
+    }
+
+    /// Charges a new element against the decode budget, before it is added to the values of a
+    /// repeated field.
+    #[inline]
+    pub fn charge_element<T>(&self, values: &[T]) -> Result<(), DecodeError> {
+        match self.budget {
+            Some(budget) => budget.charge_element(values.len(), mem::size_of::<T>()),
+            None => Ok(()),
+        }
+    }
+
+    /// Charges a new unknown field against the decode budget, before it is added to an unknown
+    /// field set which currently holds `len` fields.
+    #[inline]
+    pub(crate) fn charge_unknown_field<T>(&self, len: usize) -> Result<(), DecodeError> {
+        match self.budget {
+            Some(budget) => budget.charge_unknown_field(len, mem::size_of::<T>()),
+            None => Ok(()),
+        }
+    }
+
+    /// Charges a new entry against the decode budget, before it is added to a map field which
+    /// currently holds `len` entries.
+    #[inline]
+    pub fn charge_map_entry<K, V>(&self, len: usize) -> Result<(), DecodeError> {
+        match self.budget {
+            Some(budget) => budget.charge_element(len, mem::size_of::<(K, V)>()),
+            None => Ok(()),
+        }
     }
 
     /// Checks whether the recursion limit has been reached in the stack of
@@ -238,21 +361,90 @@ impl DecodeContext {
     ///
     /// Returns `Ok<()>` if it is ok to continue recursing.
     /// Returns `Err<DecodeError>` if the recursion limit has been reached.
-    #[cfg(not(feature = "no-recursion-limit"))]
     #[inline]
     pub(crate) fn limit_reached(&self) -> Result<(), DecodeError> {
         if self.recurse_count == 0 {
-            Err(DecodeError::new("recursion limit reached"))
+            Err(DecodeError::with_kind(
+                DecodeErrorKind::RecursionLimit,
+                "recursion limit reached",
+            ))
         } else {
             Ok(())
         }
     }
+}
+
+/// The encoded lengths of the nested messages of a message, in the order they are encoded.
+///
+/// Encoding a nested message requires its length up front, which is found by traversing the
+/// message. To avoid traversing each nested message again at every level of nesting,
+/// `Message::encode` first records the lengths of all nested messages in a single traversal with
+/// `Message::encoded_len_with_table`, and then takes them from the table while encoding with
+/// `Message::encode_raw_with_table`. A table for a deterministic encode records the lengths in the
+/// deterministic order.
+///
+/// Meant to be used only by `Message` implementations.
+#[doc(hidden)]
+#[derive(Debug, Default)]
+pub struct EncodedLenTable {
+    lens: Vec<usize>,
+    position: usize,
+    deterministic: bool,
+}
+
+impl EncodedLenTable {
+    /// Creates an empty table.
+    #[inline]
+    pub fn new() -> EncodedLenTable {
+        EncodedLenTable::default()
+    }
 
-    #[cfg(feature = "no-recursion-limit")]
+    /// Creates an empty table for a deterministic encode.
     #[inline]
-    #[allow(clippy::unnecessary_wraps)] // needed in other features
-    pub(crate) fn limit_reached(&self) -> Result<(), DecodeError> {
-        Ok(())
+    pub(crate) fn deterministic() -> EncodedLenTable {
+        EncodedLenTable {
+            deterministic: true,
+            ..EncodedLenTable::default()
+        }
+    }
+
+    /// Returns `true` if the table is for a deterministic encode, which encodes map entries in key
+    /// order and fields in field number order.
+    ///
+    /// Meant to be used only by `Message` implementations.
+    #[inline]
+    pub fn is_deterministic(&self) -> bool {
+        self.deterministic
+    }
+
+    /// Reserves an entry for the length of a nested message, before its own nested messages are
+    /// recorded. Returns the index of the entry, to be set with `set`.
+    #[inline]
+    pub(crate) fn reserve(&mut self) -> usize {
+        self.lens.push(0);
+        self.lens.len() - 1
+    }
+
+    /// Sets the length of a reserved entry.
+    #[inline]
+    pub(crate) fn set(&mut self, index: usize, len: usize) {
+        self.lens[index] = len;
+    }
+
+    /// Returns the length of the next nested message to be encoded, without taking it, or `None`
+    /// if the table has no more lengths.
+    #[inline]
+    pub(crate) fn peek(&self) -> Option<usize> {
+        self.lens.get(self.position).copied()
+    }
+
+    /// Takes the length of the next nested message to be encoded, or returns `None` if the table
+    /// has no more lengths.
+    #[inline]
+    pub(crate) fn take(&mut self) -> Option<usize> {
+        let len = self.peek()?;
+        self.position += 1;
+        Some(len)
     }
 }
 
@@ -265,14 +457,21 @@ pub fn encoded_len_varint(value: u64) -> usize {
     ((((value | 1).leading_zeros() ^ 63) * 9 + 73) / 64) as usize
 }
 
+/// The wire type of an encoded field, which determines how its value is encoded.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 #[repr(u8)]
 pub enum WireType {
+    /// A varint-encoded integer, bool or enum value.
     Varint = 0,
+    /// A `fixed64`, `sfixed64` or `double` value.
     SixtyFourBit = 1,
+    /// A length-delimited string, bytes, message or packed repeated value.
     LengthDelimited = 2,
+    /// The start of a group.
     StartGroup = 3,
+    /// The end of a group.
     EndGroup = 4,
+    /// A `fixed32`, `sfixed32` or `float` value.
     ThirtyTwoBit = 5,
 }
 
@@ -291,10 +490,10 @@ impl TryFrom<u64> for WireType {
             3 => Ok(WireType::StartGroup),
             4 => Ok(WireType::EndGroup),
             5 => Ok(WireType::ThirtyTwoBit),
-            _ => Err(DecodeError::new(format!(
-                "invalid wire type value: {}",
-                value
-            ))),
+            _ => Err(DecodeError::with_kind(
+                DecodeErrorKind::InvalidWireType,
+                format!("invalid wire type value: {}", value),
+            )),
         }
     }
 }
@@ -320,13 +519,19 @@ where
 {
     let key = decode_varint(buf)?;
     if key > u64::from(u32::MAX) {
-        return Err(DecodeError::new(format!("invalid key value: {}", key)));
+        return Err(DecodeError::with_kind(
+            DecodeErrorKind::InvalidTag,
+            format!("invalid key value: {}", key),
+        ));
     }
     let wire_type = WireType::try_from(key & 0x07)?;
     let tag = key as u32 >> 3;
 
     if tag < MIN_TAG {
-        return Err(DecodeError::new("invalid tag value: 0"));
+        return Err(DecodeError::with_kind(
+            DecodeErrorKind::InvalidTag,
+            "invalid tag value: 0",
+        ));
     }
 
     Ok((tag, wire_type))
@@ -344,10 +549,10 @@ pub fn key_len(tag: u32) -> usize {
 #[inline]
 pub fn check_wire_type(expected: WireType, actual: WireType) -> Result<(), DecodeError> {
     if expected != actual {
-        return Err(DecodeError::new(format!(
-            "invalid wire type: {:?} (expected {:?})",
-            actual, expected
-        )));
+        return Err(DecodeError::with_kind(
+            DecodeErrorKind::WireTypeMismatch,
+            format!("invalid wire type: {:?} (expected {:?})", actual, expected),
+        ));
     }
     Ok(())
 }
@@ -367,7 +572,10 @@ where
     let len = decode_varint(buf)?;
     let remaining = buf.remaining();
     if len > remaining as u64 {
-        return Err(DecodeError::new("buffer underflow"));
+        return Err(DecodeError::with_kind(
+            DecodeErrorKind::UnexpectedEof,
+            "buffer underflow",
+        ));
     }
 
     let limit = remaining - len as usize;
@@ -376,7 +584,10 @@ where
     }
 
     if buf.remaining() != limit {
-        return Err(DecodeError::new("delimited length exceeded"));
+        return Err(DecodeError::with_kind(
+            DecodeErrorKind::InvalidLength,
+            "delimited length exceeded",
+        ));
     }
     Ok(())
 }
@@ -401,18 +612,29 @@ where
             match inner_wire_type {
                 WireType::EndGroup => {
                     if inner_tag != tag {
-                        return Err(DecodeError::new("unexpected end group tag"));
+                        return Err(DecodeError::with_kind(
+                            DecodeErrorKind::UnexpectedEndGroup,
+                            "unexpected end group tag",
+                        ));
                     }
                     break 0;
                 }
                 _ => skip_field(inner_wire_type, inner_tag, buf, ctx.enter_recursion())?,
             }
         },
-        WireType::EndGroup => return Err(DecodeError::new("unexpected end group tag")),
+        WireType::EndGroup => {
+            return Err(DecodeError::with_kind(
+                DecodeErrorKind::UnexpectedEndGroup,
+                "unexpected end group tag",
+            ))
+        }
     };
 
     if len > buf.remaining() as u64 {
-        return Err(DecodeError::new("buffer underflow"));
+        return Err(DecodeError::with_kind(
+            DecodeErrorKind::UnexpectedEof,
+            "buffer underflow",
+        ));
     }
 
     buf.advance(len as usize);
@@ -451,6 +673,7 @@ macro_rules! merge_repeated_numeric {
             if wire_type == WireType::LengthDelimited {
                 // Packed.
                 merge_loop(values, buf, ctx, |values, buf, ctx| {
+                    ctx.charge_element(values)?;
                     let mut value = Default::default();
                     $merge($wire_type, &mut value, buf, ctx)?;
                     values.push(value);
@@ -459,6 +682,7 @@ macro_rules! merge_repeated_numeric {
             } else {
                 // Unpacked.
                 check_wire_type($wire_type, wire_type)?;
+                ctx.charge_element(values)?;
                 let mut value = Default::default();
                 $merge(wire_type, &mut value, buf, ctx)?;
                 values.push(value);
@@ -629,7 +853,10 @@ macro_rules! fixed_width {
             {
                 check_wire_type($wire_type, wire_type)?;
                 if buf.remaining() < $width {
-                    return Err(DecodeError::new("buffer underflow"));
+                    return Err(DecodeError::with_kind(
+                        DecodeErrorKind::UnexpectedEof,
+                        "buffer underflow",
+                    ));
                 }
                 *value = buf.$get();
                 Ok(())
@@ -755,6 +982,62 @@ fixed_width!(
     get_i64_le
 );
 
+/// Decoding functions for enumeration fields, which are encoded like `int32` fields, and whose
+/// values are checked against the variants of the enumeration when decoding strictly.
+pub mod enumeration {
+    use crate::encoding::*;
+
+    pub fn merge<B>(
+        wire_type: WireType,
+        value: &mut i32,
+        is_valid: fn(i32) -> bool,
+        buf: &mut B,
+        ctx: DecodeContext,
+    ) -> Result<(), DecodeError>
+    where
+        B: Buf,
+    {
+        let strict = ctx.strict();
+        int32::merge(wire_type, value, buf, ctx)?;
+        if strict {
+            check(*value, is_valid)?;
+        }
+        Ok(())
+    }
+
+    pub fn merge_repeated<B>(
+        wire_type: WireType,
+        values: &mut Vec<i32>,
+        is_valid: fn(i32) -> bool,
+        buf: &mut B,
+        ctx: DecodeContext,
+    ) -> Result<(), DecodeError>
+    where
+        B: Buf,
+    {
+        let strict = ctx.strict();
+        let len = values.len();
+        int32::merge_repeated(wire_type, values, buf, ctx)?;
+        if strict {
+            for &value in &values[len..] {
+                check(value, is_valid)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn check(value: i32, is_valid: fn(i32) -> bool) -> Result<(), DecodeError> {
+        if is_valid(value) {
+            Ok(())
+        } else {
+            Err(DecodeError::with_kind(
+                DecodeErrorKind::InvalidEnumValue,
+                format!("invalid enumeration value {}", value),
+            ))
+        }
+    }
+}
+
 /// Macro which emits encoding functions for a length-delimited type.
 macro_rules! length_delimited {
     ($ty:ty) => {
@@ -770,6 +1053,7 @@ macro_rules! length_delimited {
             B: Buf,
         {
             check_wire_type(WireType::LengthDelimited, wire_type)?;
+            ctx.charge_element(values)?;
             let mut value = Default::default();
             merge(wire_type, &mut value, buf, ctx)?;
             values.push(value);
@@ -842,7 +1126,8 @@ pub mod string {
                     mem::forget(drop_guard);
                     Ok(())
                 }
-                Err(_) => Err(DecodeError::new(
+                Err(_) => Err(DecodeError::with_kind(
+                    DecodeErrorKind::InvalidUtf8,
                     "invalid string value: data is not UTF-8 encoded",
                 )),
             }
@@ -961,7 +1246,7 @@ pub mod bytes {
         wire_type: WireType,
         value: &mut A,
         buf: &mut B,
-        _ctx: DecodeContext,
+        ctx: DecodeContext,
     ) -> Result<(), DecodeError>
     where
         A: BytesAdapter,
@@ -970,9 +1255,13 @@ pub mod bytes {
         check_wire_type(WireType::LengthDelimited, wire_type)?;
         let len = decode_varint(buf)?;
         if len > buf.remaining() as u64 {
-            return Err(DecodeError::new("buffer underflow"));
+            return Err(DecodeError::with_kind(
+                DecodeErrorKind::UnexpectedEof,
+                "buffer underflow",
+            ));
         }
         let len = len as usize;
+        ctx.charge_bytes(len)?;
 
         // Clear the existing value. This follows from the following rule in the encoding guide[1]:
         //
@@ -994,7 +1283,7 @@ pub mod bytes {
         wire_type: WireType,
         value: &mut A,
         buf: &mut B,
-        _ctx: DecodeContext,
+        ctx: DecodeContext,
     ) -> Result<(), DecodeError>
     where
         A: BytesAdapter,
@@ -1003,9 +1292,13 @@ pub mod bytes {
         check_wire_type(WireType::LengthDelimited, wire_type)?;
         let len = decode_varint(buf)?;
         if len > buf.remaining() as u64 {
-            return Err(DecodeError::new("buffer underflow"));
+            return Err(DecodeError::with_kind(
+                DecodeErrorKind::UnexpectedEof,
+                "buffer underflow",
+            ));
         }
         let len = len as usize;
+        ctx.charge_bytes(len)?;
 
         // If we must copy, make sure to copy only once.
         value.replace_with(buf.take(len));
@@ -1066,6 +1359,17 @@ pub mod message {
         msg.encode_raw(buf);
     }
 
+    /// Encodes a message with its map entries in key order, and its fields in field number order.
+    pub fn encode_deterministic<M, B>(tag: u32, msg: &M, buf: &mut B)
+    where
+        M: Message,
+        B: BufMut,
+    {
+        encode_key(tag, WireType::LengthDelimited, buf);
+        encode_varint(msg.encoded_len() as u64, buf);
+        msg.encode_raw_deterministic(buf);
+    }
+
     pub fn merge<M, B>(
         wire_type: WireType,
         msg: &mut M,
@@ -1078,15 +1382,12 @@ pub mod message {
     {
         check_wire_type(WireType::LengthDelimited, wire_type)?;
         ctx.limit_reached()?;
-        merge_loop(
-            msg,
-            buf,
-            ctx.enter_recursion(),
-            |msg: &mut M, buf: &mut B, ctx| {
-                let (tag, wire_type) = decode_key(buf)?;
-                msg.merge_field(tag, wire_type, buf, ctx)
-            },
-        )
+        let ctx = ctx.enter_recursion();
+        let mut checks = FieldChecks::new::<M>(&ctx);
+        merge_loop(msg, buf, ctx, |msg: &mut M, buf: &mut B, ctx| {
+            let (tag, wire_type) = decode_key(buf)?;
+            projection::merge_field(msg, tag, wire_type, buf, ctx, &mut checks)
+        })
     }
 
     pub fn encode_repeated<M, B>(tag: u32, messages: &[M], buf: &mut B)
@@ -1110,6 +1411,7 @@ pub mod message {
         B: Buf,
     {
         check_wire_type(WireType::LengthDelimited, wire_type)?;
+        ctx.charge_element(messages)?;
         let mut msg = M::default();
         merge(WireType::LengthDelimited, &mut msg, buf, ctx)?;
         messages.push(msg);
@@ -1137,6 +1439,79 @@ pub mod message {
                 .map(|len| len + encoded_len_varint(len as u64))
                 .sum::<usize>()
     }
+
+    /// Encodes a message with the encoded lengths of it and its nested messages taken from the
+    /// table filled in by `encoded_len_with_table`.
+    pub fn encode_with_table<M, B>(tag: u32, msg: &M, table: &mut EncodedLenTable, buf: &mut B)
+    where
+        M: Message,
+        B: BufMut,
+    {
+        // A message whose length was not recorded, by an implementation which doesn't record the
+        // lengths of its nested messages, is measured again.
+        let len = match msg.cached_size() {
+            Some(cached_size) => cached_size.take(),
+            None => table.take(),
+        }
+        .unwrap_or_else(|| msg.encoded_len());
+        encode_key(tag, WireType::LengthDelimited, buf);
+        encode_varint(len as u64, buf);
+        msg.encode_raw_with_table(table, buf);
+    }
+
+    /// Returns the encoded length of a message, recording the encoded lengths of it and its nested
+    /// messages in the table.
+    ///
+    /// The encoded length of a message with a cached size is recorded in its cached size instead.
+    #[inline]
+    pub fn encoded_len_with_table<M>(tag: u32, msg: &M, table: &mut EncodedLenTable) -> usize
+    where
+        M: Message,
+    {
+        let len = match msg.cached_size() {
+            Some(cached_size) => {
+                let len = msg.encoded_len_with_table(table);
+                cached_size.set(len);
+                len
+            }
+            None => {
+                let index = table.reserve();
+                let len = msg.encoded_len_with_table(table);
+                table.set(index, len);
+                len
+            }
+        };
+        key_len(tag) + encoded_len_varint(len as u64) + len
+    }
+
+    /// Returns the encoded length of a message recorded by `encoded_len_with_table`, without
+    /// taking it, or measures the message again if its length was not recorded.
+    #[inline]
+    pub(crate) fn recorded_len<M>(msg: &M, table: &EncodedLenTable) -> usize
+    where
+        M: Message,
+    {
+        match msg.cached_size() {
+            Some(cached_size) => cached_size.get(),
+            None => table.peek(),
+        }
+        .unwrap_or_else(|| msg.encoded_len())
+    }
+
+    #[inline]
+    pub fn encoded_len_repeated_with_table<M>(
+        tag: u32,
+        messages: &[M],
+        table: &mut EncodedLenTable,
+    ) -> usize
+    where
+        M: Message,
+    {
+        messages
+            .iter()
+            .map(|msg| encoded_len_with_table(tag, msg, table))
+            .sum::<usize>()
+    }
 }
 
 pub mod group {
@@ -1152,6 +1527,17 @@ pub mod group {
         encode_key(tag, WireType::EndGroup, buf);
     }
 
+    /// Encodes a group with its map entries in key order, and its fields in field number order.
+    pub fn encode_deterministic<M, B>(tag: u32, msg: &M, buf: &mut B)
+    where
+        M: Message,
+        B: BufMut,
+    {
+        encode_key(tag, WireType::StartGroup, buf);
+        msg.encode_raw_deterministic(buf);
+        encode_key(tag, WireType::EndGroup, buf);
+    }
+
     pub fn merge<M, B>(
         tag: u32,
         wire_type: WireType,
@@ -1166,16 +1552,27 @@ pub mod group {
         check_wire_type(WireType::StartGroup, wire_type)?;
 
         ctx.limit_reached()?;
+        let mut checks = FieldChecks::new::<M>(&ctx);
         loop {
             let (field_tag, field_wire_type) = decode_key(buf)?;
             if field_wire_type == WireType::EndGroup {
                 if field_tag != tag {
-                    return Err(DecodeError::new("unexpected end group tag"));
+                    return Err(DecodeError::with_kind(
+                        DecodeErrorKind::UnexpectedEndGroup,
+                        "unexpected end group tag",
+                    ));
                 }
                 return Ok(());
             }
 
-            M::merge_field(msg, field_tag, field_wire_type, buf, ctx.enter_recursion())?;
+            projection::merge_field(
+                msg,
+                field_tag,
+                field_wire_type,
+                buf,
+                ctx.enter_recursion(),
+                &mut checks,
+            )?;
         }
     }
 
@@ -1201,6 +1598,7 @@ pub mod group {
         B: Buf,
     {
         check_wire_type(WireType::StartGroup, wire_type)?;
+        ctx.charge_element(messages)?;
         let mut msg = M::default();
         merge(tag, WireType::StartGroup, &mut msg, buf, ctx)?;
         messages.push(msg);
@@ -1222,6 +1620,156 @@ pub mod group {
     {
         2 * key_len(tag) * messages.len() + messages.iter().map(Message::encoded_len).sum::<usize>()
     }
+
+    /// Encodes a group with the encoded lengths of its nested messages taken from the table filled
+    /// in by `encoded_len_with_table`.
+    pub fn encode_with_table<M, B>(tag: u32, msg: &M, table: &mut EncodedLenTable, buf: &mut B)
+    where
+        M: Message,
+        B: BufMut,
+    {
+        encode_key(tag, WireType::StartGroup, buf);
+        msg.encode_raw_with_table(table, buf);
+        encode_key(tag, WireType::EndGroup, buf);
+    }
+
+    /// Returns the encoded length of a group, recording the encoded lengths of its nested messages
+    /// in the table.
+    #[inline]
+    pub fn encoded_len_with_table<M>(tag: u32, msg: &M, table: &mut EncodedLenTable) -> usize
+    where
+        M: Message,
+    {
+        2 * key_len(tag) + msg.encoded_len_with_table(table)
+    }
+
+    #[inline]
+    pub fn encoded_len_repeated_with_table<M>(
+        tag: u32,
+        messages: &[M],
+        table: &mut EncodedLenTable,
+    ) -> usize
+    where
+        M: Message,
+    {
+        2 * key_len(tag) * messages.len()
+            + messages
+                .iter()
+                .map(|msg| msg.encoded_len_with_table(table))
+                .sum::<usize>()
+    }
+}
+
+/// Message fields wrapped in [`Lazy`], which keep the encoded message when decoded.
+pub mod lazy {
+    use super::*;
+
+    use crate::Lazy;
+
+    pub fn encode<M, B>(tag: u32, value: &Lazy<M>, buf: &mut B)
+    where
+        M: Message + Default,
+        B: BufMut,
+    {
+        encode_key(tag, WireType::LengthDelimited, buf);
+        encode_varint(value.encoded_len() as u64, buf);
+        value.encode_raw(buf);
+    }
+
+    /// Encodes a lazily decoded message, with its map entries in key order and its fields in field
+    /// number order if it has been modified since it was decoded.
+    pub fn encode_deterministic<M, B>(tag: u32, value: &Lazy<M>, buf: &mut B)
+    where
+        M: Message + Default,
+        B: BufMut,
+    {
+        encode_key(tag, WireType::LengthDelimited, buf);
+        encode_varint(value.encoded_len() as u64, buf);
+        value.encode_raw_deterministic(buf);
+    }
+
+    pub fn merge<M, B>(
+        wire_type: WireType,
+        value: &mut Lazy<M>,
+        buf: &mut B,
+        ctx: DecodeContext,
+    ) -> Result<(), DecodeError>
+    where
+        M: Message + Default,
+        B: Buf,
+    {
+        check_wire_type(WireType::LengthDelimited, wire_type)?;
+        if value.is_decoded() {
+            // A message which is already decoded is merged right away, within the limits of the
+            // decode.
+            return message::merge(wire_type, value.get_mut()?, buf, ctx);
+        }
+        ctx.limit_reached()?;
+        let len = decode_varint(buf)?;
+        if len > buf.remaining() as u64 {
+            return Err(DecodeError::with_kind(
+                DecodeErrorKind::UnexpectedEof,
+                "buffer underflow",
+            ));
+        }
+        let len = len as usize;
+        ctx.charge_bytes(len)?;
+        value.merge_encoded(buf.copy_to_bytes(len))
+    }
+
+    pub fn encode_repeated<M, B>(tag: u32, values: &[Lazy<M>], buf: &mut B)
+    where
+        M: Message + Default,
+        B: BufMut,
+    {
+        for value in values {
+            encode(tag, value, buf);
+        }
+    }
+
+    pub fn merge_repeated<M, B>(
+        wire_type: WireType,
+        values: &mut Vec<Lazy<M>>,
+        buf: &mut B,
+        ctx: DecodeContext,
+    ) -> Result<(), DecodeError>
+    where
+        M: Message + Default,
+        B: Buf,
+    {
+        check_wire_type(WireType::LengthDelimited, wire_type)?;
+        ctx.charge_element(values)?;
+        let mut value = Lazy::default();
+        merge(WireType::LengthDelimited, &mut value, buf, ctx)?;
+        values.push(value);
+        Ok(())
+    }
+
+    /// Merges another lazily decoded message into `value`, without decoding either message unless
+    /// both have been decoded.
+    pub fn merge_from<M>(value: &mut Lazy<M>, other: &Lazy<M>)
+    where
+        M: Message + Default,
+    {
+        value.merge_from(other)
+    }
+
+    #[inline]
+    pub fn encoded_len<M>(tag: u32, value: &Lazy<M>) -> usize
+    where
+        M: Message + Default,
+    {
+        let len = value.encoded_len();
+        key_len(tag) + encoded_len_varint(len as u64) + len
+    }
+
+    #[inline]
+    pub fn encoded_len_repeated<M>(tag: u32, values: &[Lazy<M>]) -> usize
+    where
+        M: Message + Default,
+    {
+        values.iter().map(|value| encoded_len(tag, value)).sum()
+    }
 }
 
 /// Rust doesn't have a `Map` trait, so macros are currently the best way to be
@@ -1317,7 +1865,102 @@ macro_rules! map {
             VE: Fn(u32, &V, &mut B),
             VL: Fn(u32, &V) -> usize,
         {
-            for (key, val) in values.iter() {
+            encode_entries(
+                key_encode,
+                key_encoded_len,
+                val_encode,
+                val_encoded_len,
+                val_default,
+                tag,
+                values.iter(),
+                buf,
+            )
+        }
+
+        /// Generic protobuf map encode function, encoding the entries in key order.
+        pub fn encode_deterministic<K, V, B, KE, KL, VE, VL>(
+            key_encode: KE,
+            key_encoded_len: KL,
+            val_encode: VE,
+            val_encoded_len: VL,
+            tag: u32,
+            values: &$map_ty<K, V>,
+            buf: &mut B,
+        ) where
+            K: Default + Eq + Hash + Ord,
+            V: Default + PartialEq,
+            B: BufMut,
+            KE: Fn(u32, &K, &mut B),
+            KL: Fn(u32, &K) -> usize,
+            VE: Fn(u32, &V, &mut B),
+            VL: Fn(u32, &V) -> usize,
+        {
+            encode_with_default_deterministic(
+                key_encode,
+                key_encoded_len,
+                val_encode,
+                val_encoded_len,
+                &V::default(),
+                tag,
+                values,
+                buf,
+            )
+        }
+
+        /// Generic protobuf map encode function with an overridden value default, encoding the
+        /// entries in key order.
+        pub fn encode_with_default_deterministic<K, V, B, KE, KL, VE, VL>(
+            key_encode: KE,
+            key_encoded_len: KL,
+            val_encode: VE,
+            val_encoded_len: VL,
+            val_default: &V,
+            tag: u32,
+            values: &$map_ty<K, V>,
+            buf: &mut B,
+        ) where
+            K: Default + Eq + Hash + Ord,
+            V: PartialEq,
+            B: BufMut,
+            KE: Fn(u32, &K, &mut B),
+            KL: Fn(u32, &K) -> usize,
+            VE: Fn(u32, &V, &mut B),
+            VL: Fn(u32, &V) -> usize,
+        {
+            let mut entries = values.iter().collect::<Vec<_>>();
+            entries.sort_unstable_by(|a, b| a.0.cmp(b.0));
+            encode_entries(
+                key_encode,
+                key_encoded_len,
+                val_encode,
+                val_encoded_len,
+                val_default,
+                tag,
+                entries.into_iter(),
+                buf,
+            )
+        }
+
+        #[allow(clippy::too_many_arguments)]
+        fn encode_entries<'a, K, V, B, KE, KL, VE, VL>(
+            key_encode: KE,
+            key_encoded_len: KL,
+            val_encode: VE,
+            val_encoded_len: VL,
+            val_default: &V,
+            tag: u32,
+            entries: impl Iterator<Item = (&'a K, &'a V)>,
+            buf: &mut B,
+        ) where
+            K: Default + Eq + 'a,
+            V: PartialEq + 'a,
+            B: BufMut,
+            KE: Fn(u32, &K, &mut B),
+            KL: Fn(u32, &K) -> usize,
+            VE: Fn(u32, &V, &mut B),
+            VL: Fn(u32, &V) -> usize,
+        {
+            for (key, val) in entries {
                 let skip_key = key == &K::default();
                 let skip_val = val == val_default;
 
@@ -1353,6 +1996,7 @@ macro_rules! map {
             KM: Fn(WireType, &mut K, &mut B, DecodeContext) -> Result<(), DecodeError>,
             VM: Fn(WireType, &mut V, &mut B, DecodeContext) -> Result<(), DecodeError>,
         {
+            ctx.charge_map_entry::<K, V>(values.len())?;
             let mut key = Default::default();
             let mut val = val_default;
             ctx.limit_reached()?;
@@ -1365,7 +2009,10 @@ macro_rules! map {
                     match tag {
                         1 => key_merge(wire_type, key, buf, ctx),
                         2 => val_merge(wire_type, val, buf, ctx),
-                        _ => skip_field(wire_type, tag, buf, ctx),
+                        _ => {
+                            ctx.check_unknown_field(tag)?;
+                            skip_field(wire_type, tag, buf, ctx)
+                        }
                     }
                 },
             )?;
@@ -1408,6 +2055,103 @@ macro_rules! map {
                     })
                     .sum::<usize>()
         }
+
+        /// Protobuf map encode function for message values, with the encoded lengths of the values
+        /// and their nested messages taken from the table filled in by `encoded_len_with_table`.
+        ///
+        /// The entries are encoded in key order if the table is for a deterministic encode.
+        pub fn encode_with_table<K, V, B, KE, KL>(
+            key_encode: KE,
+            key_encoded_len: KL,
+            tag: u32,
+            values: &$map_ty<K, V>,
+            table: &mut EncodedLenTable,
+            buf: &mut B,
+        ) where
+            K: Default + Eq + Hash + Ord,
+            V: Message + Default + PartialEq,
+            B: BufMut,
+            KE: Fn(u32, &K, &mut B),
+            KL: Fn(u32, &K) -> usize,
+        {
+            let val_default = V::default();
+            for (key, val) in entries_with_table(values, table) {
+                let skip_key = key == &K::default();
+                let skip_val = val == &val_default;
+
+                let len = (if skip_key { 0 } else { key_encoded_len(1, key) })
+                    + (if skip_val {
+                        0
+                    } else {
+                        let val_len = message::recorded_len(val, table);
+                        key_len(2) + encoded_len_varint(val_len as u64) + val_len
+                    });
+
+                encode_key(tag, WireType::LengthDelimited, buf);
+                encode_varint(len as u64, buf);
+                if !skip_key {
+                    key_encode(1, key, buf);
+                }
+                if !skip_val {
+                    message::encode_with_table(2, val, table, buf);
+                }
+            }
+        }
+
+        /// Protobuf map encoded length function for message values, recording the encoded lengths
+        /// of the values and their nested messages in the table.
+        pub fn encoded_len_with_table<K, V, KL>(
+            key_encoded_len: KL,
+            tag: u32,
+            values: &$map_ty<K, V>,
+            table: &mut EncodedLenTable,
+        ) -> usize
+        where
+            K: Default + Eq + Hash + Ord,
+            V: Message + Default + PartialEq,
+            KL: Fn(u32, &K) -> usize,
+        {
+            let val_default = V::default();
+            key_len(tag) * values.len()
+                + entries_with_table(values, table)
+                    .map(|(key, val)| {
+                        let len = (if key == &K::default() {
+                            0
+                        } else {
+                            key_encoded_len(1, key)
+                        }) + (if val == &val_default {
+                            0
+                        } else {
+                            message::encoded_len_with_table(2, val, table)
+                        });
+                        encoded_len_varint(len as u64) + len
+                    })
+                    .sum::<usize>()
+        }
+
+        /// Returns the entries of the map in the order they are encoded with the table.
+        ///
+        /// Only a deterministic encode collects the entries to sort them; otherwise they are
+        /// iterated in place.
+        fn entries_with_table<'a, K, V>(
+            values: &'a $map_ty<K, V>,
+            table: &EncodedLenTable,
+        ) -> impl Iterator<Item = (&'a K, &'a V)>
+        where
+            K: Ord,
+        {
+            let (sorted, unsorted) = if table.is_deterministic() {
+                let mut entries = values.iter().collect::<Vec<_>>();
+                entries.sort_unstable_by(|a, b| a.0.cmp(b.0));
+                (Some(entries), None)
+            } else {
+                (None, Some(values.iter()))
+            };
+            sorted
+                .into_iter()
+                .flatten()
+                .chain(unsorted.into_iter().flatten())
+        }
     };
 }
 
@@ -1686,6 +2430,55 @@ mod test {
         decode_varint(&mut u64_max_plus_one.clone()).expect_err("decoding u64::MAX + 1 succeeded");
         decode_varint_slow(&mut u64_max_plus_one.clone())
             .expect_err("slow decoding u64::MAX + 1 succeeded");
+        decode_partial_varint(u64_max_plus_one)
+            .expect_err("partial decoding u64::MAX + 1 succeeded");
+    }
+
+    #[test]
+    fn partial_varint() {
+        assert_eq!(decode_partial_varint(&[]), Ok(None));
+        assert_eq!(decode_partial_varint(&[0xAC]), Ok(None));
+        assert_eq!(decode_partial_varint(&[0xAC, 0x02]), Ok(Some((300, 2))));
+        assert_eq!(
+            decode_partial_varint(&[0xAC, 0x02, 0x01]),
+            Ok(Some((300, 2)))
+        );
+        assert_eq!(decode_partial_varint(&[0xFF; 9]), Ok(None));
+        assert!(decode_partial_varint(&[0xFF; 10]).is_err());
+        assert_eq!(
+            decode_partial_varint(&[0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x01]),
+            Ok(Some((u64::MAX, 10)))
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn hash_map_encode_deterministic() {
+        let entries = (0..100).map(|i| (i.to_string(), i));
+        let hash_map = entries.clone().collect::<std::collections::HashMap<_, _>>();
+        let btree_map = entries.collect::<alloc::collections::BTreeMap<_, _>>();
+
+        let mut expected = Vec::new();
+        crate::encoding::btree_map::encode(
+            string::encode,
+            string::encoded_len,
+            int32::encode,
+            int32::encoded_len,
+            1,
+            &btree_map,
+            &mut expected,
+        );
+        let mut buf = Vec::new();
+        crate::encoding::hash_map::encode_deterministic(
+            string::encode,
+            string::encoded_len,
+            int32::encode,
+            int32::encoded_len,
+            1,
+            &hash_map,
+            &mut buf,
+        );
+        assert_eq!(buf, expected);
     }
 
     /// This big bowl o' macro soup generates an encoding property test for each combination of map
@@ -2,21 +2,27 @@
 
 use alloc::borrow::Cow;
 use alloc::boxed::Box;
+use alloc::string::String;
 use alloc::vec::Vec;
 
 use core::fmt;
 
+use crate::required;
+
 /// A Protobuf message decoding error.
 ///
 /// `DecodeError` indicates that the input buffer does not contain a valid
 /// Protobuf message. The error details should be considered 'best effort': in
 /// general it is not possible to exactly pinpoint why data is malformed.
-#[derive(Clone, PartialEq, Eq)]
+///
+/// Two errors compare equal if they have the same description and field path, see the
+/// `PartialEq` implementation.
+#[derive(Clone)]
 pub struct DecodeError {
     inner: Box<Inner>,
 }
 
-#[derive(Clone, PartialEq, Eq)]
+#[derive(Clone)]
 struct Inner {
     /// A 'best effort' root cause description.
     description: Cow<'static, str>,
@@ -24,6 +30,83 @@ struct Inner {
     /// message type and field where decoding failed. The stack contains an
     /// entry per level of nesting.
     stack: Vec<(&'static str, &'static str)>,
+    /// The kind of error.
+    kind: DecodeErrorKind,
+    /// The decode budget which was exceeded, if that is the cause of the error.
+    budget: Option<DecodeBudget>,
+    /// The offset in the input buffer at which decoding failed.
+    offset: Option<usize>,
+    /// The paths of the missing required fields, if that is the cause of the error.
+    missing_fields: Vec<String>,
+}
+
+/// The kind of a [`DecodeError`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum DecodeErrorKind {
+    /// A varint was longer than ten bytes, overflowed 64 bits, or was truncated.
+    InvalidVarint,
+    /// The input ended in the middle of a field value.
+    UnexpectedEof,
+    /// A field key contained a wire type value which does not exist.
+    InvalidWireType,
+    /// A field was encoded with a wire type which does not match the field's type.
+    WireTypeMismatch,
+    /// A string field contained data which is not valid UTF-8.
+    InvalidUtf8,
+    /// Messages were nested more deeply than the recursion limit.
+    RecursionLimit,
+    /// A field key contained a tag which is zero or out of range.
+    InvalidTag,
+    /// A closed enumeration field contained a value which is not a variant of the enumeration,
+    /// or any enumeration field did when decoding with [`DecodeOptions::strict`].
+    ///
+    /// [`DecodeOptions::strict`]: crate::DecodeOptions::strict
+    InvalidEnumValue,
+    /// A group end tag appeared without a matching start tag, or did not match the start tag.
+    UnexpectedEndGroup,
+    /// A value extended past the end of its enclosing length-delimited field, or a length was
+    /// out of range.
+    InvalidLength,
+    /// One of the budgets configured in [`DecodeOptions`] was exceeded.
+    ///
+    /// [`DecodeOptions`]: crate::DecodeOptions
+    BudgetExceeded,
+    /// Fields declared `required` were missing from a message or its nested messages, when
+    /// decoding with [`DecodeOptions::check_required_fields`] or checking a message with
+    /// [`Message::check_initialized`].
+    ///
+    /// [`DecodeOptions::check_required_fields`]: crate::DecodeOptions::check_required_fields
+    /// [`Message::check_initialized`]: crate::Message::check_initialized
+    MissingRequiredField,
+    /// A message contained a field whose number is not part of its schema, when decoding with
+    /// [`DecodeOptions::strict`].
+    ///
+    /// [`DecodeOptions::strict`]: crate::DecodeOptions::strict
+    UnknownField,
+    /// A message or group field which is not repeated occurred more than once in a message, when
+    /// decoding with [`DecodeOptions::strict`].
+    ///
+    /// [`DecodeOptions::strict`]: crate::DecodeOptions::strict
+    DuplicateField,
+    /// Any other error, such as one returned by a hand-written `Message` implementation.
+    Other,
+}
+
+/// A resource budget which can be configured in [`DecodeOptions`].
+///
+/// [`DecodeOptions`]: crate::DecodeOptions
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum DecodeBudget {
+    /// The total number of bytes of decoded string, bytes, repeated and map field data.
+    TotalBytes,
+    /// The number of elements in a single repeated or map field.
+    RepeatedElements,
+    /// The length of a single string or bytes field.
+    FieldBytes,
+    /// The number of unknown fields kept by a single message.
+    UnknownFields,
 }
 
 impl DecodeError {
@@ -33,14 +116,108 @@ impl DecodeError {
     #[doc(hidden)]
     #[cold]
     pub fn new(description: impl Into<Cow<'static, str>>) -> DecodeError {
+        DecodeError::with_kind(DecodeErrorKind::Other, description)
+    }
+
+    /// Creates a new `DecodeError` of the given kind, with a 'best effort' root cause
+    /// description.
+    ///
+    /// Meant to be used only by `Message` implementations.
+    #[doc(hidden)]
+    #[cold]
+    pub fn with_kind(
+        kind: DecodeErrorKind,
+        description: impl Into<Cow<'static, str>>,
+    ) -> DecodeError {
         DecodeError {
             inner: Box::new(Inner {
                 description: description.into(),
                 stack: Vec::new(),
+                kind,
+                budget: None,
+                offset: None,
+                missing_fields: Vec::new(),
             }),
         }
     }
 
+    /// Creates a new `DecodeError` for an exceeded decode budget.
+    #[cold]
+    pub(crate) fn budget_exceeded(budget: DecodeBudget) -> DecodeError {
+        let mut error = DecodeError::with_kind(
+            DecodeErrorKind::BudgetExceeded,
+            match budget {
+                DecodeBudget::TotalBytes => "total decoded data exceeds budget",
+                DecodeBudget::RepeatedElements => "repeated field element count exceeds budget",
+                DecodeBudget::FieldBytes => "string or bytes field length exceeds budget",
+                DecodeBudget::UnknownFields => "unknown field count exceeds budget",
+            },
+        );
+        error.inner.budget = Some(budget);
+        error
+    }
+
+    /// Returns the kind of error.
+    pub fn kind(&self) -> DecodeErrorKind {
+        self.inner.kind
+    }
+
+    /// Returns the offset in the input buffer at which decoding failed, if known.
+    ///
+    /// The offset is the position at which the error was detected. For example, an invalid string
+    /// field fails at the end of the string, after its bytes have been read. Errors in nested
+    /// messages are reported at their offset in the outermost message. The offset is counted from:
+    ///
+    ///  * the start of the buffer passed to [`Message::decode`], [`Message::merge`], a view's
+    ///    [`MessageView::decode`], or one of the related methods;
+    ///  * the start of the length delimiter, for [`Message::decode_length_delimited`] and
+    ///    [`Message::merge_length_delimited`];
+    ///  * the end of the length delimiter, for the methods which read length-delimited messages
+    ///    from a reader or a stream of frames, since those decode each frame on its own;
+    ///  * the start of the message being received, for an [`IncrementalDecoder`];
+    ///  * the start of the field's encoded contents, for errors returned when accessing a lazily
+    ///    decoded field or an element of a repeated view field.
+    ///
+    /// The offset is `None` for errors which are not returned by decoding a buffer, such as those
+    /// created by [`DecodeError::new`] or returned by `TryFrom<i32>` for an enumeration.
+    ///
+    /// [`Message::decode`]: crate::Message::decode
+    /// [`Message::merge`]: crate::Message::merge
+    /// [`Message::decode_length_delimited`]: crate::Message::decode_length_delimited
+    /// [`Message::merge_length_delimited`]: crate::Message::merge_length_delimited
+    /// [`MessageView::decode`]: crate::view::MessageView::decode
+    /// [`IncrementalDecoder`]: crate::IncrementalDecoder
+    pub fn offset(&self) -> Option<usize> {
+        self.inner.offset
+    }
+
+    /// Returns the path of (message, field) name pairs leading to the field where decoding
+    /// failed, starting from the outermost message.
+    ///
+    /// The path is empty if decoding failed outside of a known field, for example while decoding
+    /// a field key.
+    pub fn path(&self) -> impl DoubleEndedIterator<Item = (&'static str, &'static str)> + '_ {
+        self.inner.stack.iter().rev().copied()
+    }
+
+    /// Returns the decode budget which was exceeded, if the error was caused by exceeding one of
+    /// the budgets configured in [`DecodeOptions`].
+    ///
+    /// [`DecodeOptions`]: crate::DecodeOptions
+    pub fn exceeded_budget(&self) -> Option<DecodeBudget> {
+        self.inner.budget
+    }
+
+    /// Returns the paths of the missing required fields, for an error of kind
+    /// [`DecodeErrorKind::MissingRequiredField`].
+    ///
+    /// A path names the fields leading to the missing field, starting from a field of the
+    /// outermost message, with the index of repeated elements and the key of map entries, e.g.
+    /// `members[1].email`.
+    pub fn missing_fields(&self) -> impl Iterator<Item = &str> + '_ {
+        self.inner.missing_fields.iter().map(String::as_str)
+    }
+
     /// Pushes a (message, field) name location pair on to the location stack.
     ///
     /// Meant to be used only by `Message` implementations.
@@ -48,13 +225,41 @@ impl DecodeError {
     pub fn push(&mut self, message: &'static str, field: &'static str) {
         self.inner.stack.push((message, field));
     }
+
+    /// Records the offset in the input buffer at which decoding failed, unless it is already
+    /// known.
+    pub(crate) fn set_offset(&mut self, offset: usize) {
+        self.inner.offset.get_or_insert(offset);
+    }
+
+    /// Records the paths of the missing required fields which caused the error.
+    pub(crate) fn set_missing_fields(&mut self, missing_fields: Vec<String>) {
+        self.inner.missing_fields = missing_fields;
+    }
+}
+
+/// Compares the description and field path of the errors only.
+///
+/// The [`kind`](DecodeError::kind), [`offset`](DecodeError::offset),
+/// [`exceeded_budget`](DecodeError::exceeded_budget) and
+/// [`missing_fields`](DecodeError::missing_fields) are ignored, so that an error created with
+/// [`DecodeError::new`] is equal to an error of any kind returned by a decode with the same
+/// description and path. Compare `kind()` as well to tell such errors apart.
+impl PartialEq for DecodeError {
+    fn eq(&self, other: &DecodeError) -> bool {
+        self.inner.description == other.inner.description && self.inner.stack == other.inner.stack
+    }
 }
 
+impl Eq for DecodeError {}
+
 impl fmt::Debug for DecodeError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("DecodeError")
             .field("description", &self.inner.description)
             .field("stack", &self.inner.stack)
+            .field("kind", &self.inner.kind)
+            .field("offset", &self.inner.offset)
             .finish()
     }
 }
@@ -81,13 +286,32 @@ impl From<DecodeError> for std::io::Error {
 
 /// A Protobuf message encoding error.
 ///
-/// `EncodeError` always indicates that a message failed to encode because the
-/// provided buffer had insufficient capacity. Message encoding is otherwise
-/// infallible.
-#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+/// `EncodeError` indicates that a message failed to encode because the
+/// provided buffer had insufficient capacity, or that
+/// [`Message::check_initialized`] found missing required fields. Message
+/// encoding is otherwise infallible.
+///
+/// [`Message::check_initialized`]: crate::Message::check_initialized
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct EncodeError {
     required: usize,
     remaining: usize,
+    kind: EncodeErrorKind,
+    /// The paths of the missing required fields, if that is the cause of the error.
+    missing_fields: Vec<String>,
+}
+
+/// The kind of an [`EncodeError`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum EncodeErrorKind {
+    /// The buffer had insufficient capacity for the encoded message.
+    InsufficientCapacity,
+    /// The message or one of its nested messages is missing fields declared `required`, found by
+    /// [`Message::check_initialized`].
+    ///
+    /// [`Message::check_initialized`]: crate::Message::check_initialized
+    MissingRequiredField,
 }
 
 impl EncodeError {
@@ -96,27 +320,61 @@ impl EncodeError {
         EncodeError {
             required,
             remaining,
+            kind: EncodeErrorKind::InsufficientCapacity,
+            missing_fields: Vec::new(),
         }
     }
 
-    /// Returns the required buffer capacity to encode the message.
+    /// Creates a new `EncodeError` for a message which is missing the required fields with the
+    /// given paths.
+    pub(crate) fn missing_required_fields(missing_fields: Vec<String>) -> EncodeError {
+        EncodeError {
+            required: 0,
+            remaining: 0,
+            kind: EncodeErrorKind::MissingRequiredField,
+            missing_fields,
+        }
+    }
+
+    /// Returns the kind of error.
+    pub fn kind(&self) -> EncodeErrorKind {
+        self.kind
+    }
+
+    /// Returns the required buffer capacity to encode the message, or zero if the error is not
+    /// caused by insufficient capacity.
     pub fn required_capacity(&self) -> usize {
         self.required
     }
 
-    /// Returns the remaining length in the provided buffer at the time of encoding.
+    /// Returns the remaining length in the provided buffer at the time of encoding, or zero if
+    /// the error is not caused by insufficient capacity.
     pub fn remaining(&self) -> usize {
         self.remaining
     }
+
+    /// Returns the paths of the missing required fields, for an error of kind
+    /// [`EncodeErrorKind::MissingRequiredField`], in the form described by
+    /// [`DecodeError::missing_fields`].
+    pub fn missing_fields(&self) -> impl Iterator<Item = &str> + '_ {
+        self.missing_fields.iter().map(String::as_str)
+    }
 }
 
 impl fmt::Display for EncodeError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(
-            f,
-            "failed to encode Protobuf message; insufficient buffer capacity (required: {}, remaining: {})",
-            self.required, self.remaining
-        )
+        match self.kind {
+            EncodeErrorKind::InsufficientCapacity => write!(
+                f,
+                "failed to encode Protobuf message; insufficient buffer capacity (required: {}, remaining: {})",
+                self.required, self.remaining
+            ),
+            EncodeErrorKind::MissingRequiredField => write!(
+                f,
+                "failed to encode Protobuf message; {}",
+                required::describe(&self.missing_fields)
+            ),
+        }
     }
 }
 
@@ -0,0 +1,688 @@
+//! Support for Protobuf extensions.
+//!
+//! Messages declaring extension ranges store the values of extension fields in an
+//! [`ExtensionSet`], and implement [`Extendable`] to provide typed access to them through
+//! [`Extension`] descriptors. The values are kept in their encoded form until they are accessed,
+//! so extensions which are not known to the application are preserved when the message is
+//! re-encoded.
+//!
+//! The types in this module implementing [`Codec`] describe how the value of an extension field is
+//! encoded, and are used as the second type parameter of [`Extension`].
+
+use alloc::vec::Vec;
+use core::fmt;
+use core::marker::PhantomData;
+use core::slice;
+
+use ::bytes::{Buf, BufMut};
+
+use crate::encoding::{self, decode_key, DecodeContext, WireType};
+use crate::{DecodeError, UnknownField, UnknownFieldSet};
+
+/// Describes the encoding of the value of an extension field.
+pub trait Codec {
+    /// The Rust type of the extension value.
+    type Value: Default;
+
+    /// The wire type used to encode the extension value.
+    const WIRE_TYPE: WireType;
+
+    /// Encodes the value as a field with the given number.
+    fn encode<B>(number: u32, value: &Self::Value, buf: &mut B)
+    where
+        B: BufMut;
+
+    /// Merges a field with the given number into the value.
+    fn merge<B>(
+        number: u32,
+        wire_type: WireType,
+        value: &mut Self::Value,
+        buf: &mut B,
+        ctx: DecodeContext,
+    ) -> Result<(), DecodeError>
+    where
+        B: Buf;
+}
+
+/// A [`Codec`] which can be used for the elements of a [`Repeated`] extension.
+pub trait RepeatedCodec: Codec {
+    /// Encodes the values as unpacked repeated fields with the given number.
+    fn encode_repeated<B>(number: u32, values: &[Self::Value], buf: &mut B)
+    where
+        B: BufMut;
+
+    /// Merges a repeated field with the given number into the values.
+    fn merge_repeated<B>(
+        number: u32,
+        wire_type: WireType,
+        values: &mut Vec<Self::Value>,
+        buf: &mut B,
+        ctx: DecodeContext,
+    ) -> Result<(), DecodeError>
+    where
+        B: Buf;
+}
+
+/// A [`Codec`] which can be used for the elements of a [`Packed`] extension.
+pub trait PackedCodec: RepeatedCodec {
+    /// Encodes the values as a packed repeated field with the given number.
+    fn encode_packed<B>(number: u32, values: &[Self::Value], buf: &mut B)
+    where
+        B: BufMut;
+}
+
+macro_rules! scalar_codec {
+    ($name:ident, $proto_ty:ident, $ty:ty, $wire_type:expr) => {
+        #[doc = concat!("Codec for `", stringify!($proto_ty), "` extension fields.")]
+        #[derive(Debug)]
+        pub enum $name {}
+
+        impl Codec for $name {
+            type Value = $ty;
+
+            const WIRE_TYPE: WireType = $wire_type;
+
+            fn encode<B>(number: u32, value: &$ty, buf: &mut B)
+            where
+                B: BufMut,
+            {
+                encoding::$proto_ty::encode(number, value, buf)
+            }
+
+            fn merge<B>(
+                _number: u32,
+                wire_type: WireType,
+                value: &mut $ty,
+                buf: &mut B,
+                ctx: DecodeContext,
+            ) -> Result<(), DecodeError>
+            where
+                B: Buf,
+            {
+                encoding::$proto_ty::merge(wire_type, value, buf, ctx)
+            }
+        }
+
+        impl RepeatedCodec for $name {
+            fn encode_repeated<B>(number: u32, values: &[$ty], buf: &mut B)
+            where
+                B: BufMut,
+            {
+                encoding::$proto_ty::encode_repeated(number, values, buf)
+            }
+
+            fn merge_repeated<B>(
+                _number: u32,
+                wire_type: WireType,
+                values: &mut Vec<$ty>,
+                buf: &mut B,
+                ctx: DecodeContext,
+            ) -> Result<(), DecodeError>
+            where
+                B: Buf,
+            {
+                encoding::$proto_ty::merge_repeated(wire_type, values, buf, ctx)
+            }
+        }
+    };
+    ($name:ident, $proto_ty:ident, $ty:ty, $wire_type:expr, packed) => {
+        scalar_codec!($name, $proto_ty, $ty, $wire_type);
+
+        impl PackedCodec for $name {
+            fn encode_packed<B>(number: u32, values: &[$ty], buf: &mut B)
+            where
+                B: BufMut,
+            {
+                encoding::$proto_ty::encode_packed(number, values, buf)
+            }
+        }
+    };
+}
+
+scalar_codec!(Bool, bool, bool, WireType::Varint, packed);
+scalar_codec!(Int32, int32, i32, WireType::Varint, packed);
+scalar_codec!(Int64, int64, i64, WireType::Varint, packed);
+scalar_codec!(Uint32, uint32, u32, WireType::Varint, packed);
+scalar_codec!(Uint64, uint64, u64, WireType::Varint, packed);
+scalar_codec!(Sint32, sint32, i32, WireType::Varint, packed);
+scalar_codec!(Sint64, sint64, i64, WireType::Varint, packed);
+scalar_codec!(Fixed32, fixed32, u32, WireType::ThirtyTwoBit, packed);
+scalar_codec!(Fixed64, fixed64, u64, WireType::SixtyFourBit, packed);
+scalar_codec!(Sfixed32, sfixed32, i32, WireType::ThirtyTwoBit, packed);
+scalar_codec!(Sfixed64, sfixed64, i64, WireType::SixtyFourBit, packed);
+scalar_codec!(Float, float, f32, WireType::ThirtyTwoBit, packed);
+scalar_codec!(Double, double, f64, WireType::SixtyFourBit, packed);
+// Enumeration values are represented as `i32`, like enumeration fields of messages.
+scalar_codec!(Enumeration, int32, i32, WireType::Varint, packed);
+scalar_codec!(
+    String,
+    string,
+    alloc::string::String,
+    WireType::LengthDelimited
+);
+scalar_codec!(Bytes, bytes, Vec<u8>, WireType::LengthDelimited);
+
+/// Codec for `message` extension fields.
+pub struct Message<M>(PhantomData<fn() -> M>);
+
+impl<M> Codec for Message<M>
+where
+    M: crate::Message + Default,
+{
+    type Value = M;
+
+    const WIRE_TYPE: WireType = WireType::LengthDelimited;
+
+    fn encode<B>(number: u32, value: &M, buf: &mut B)
+    where
+        B: BufMut,
+    {
+        encoding::message::encode(number, value, buf)
+    }
+
+    fn merge<B>(
+        _number: u32,
+        wire_type: WireType,
+        value: &mut M,
+        buf: &mut B,
+        ctx: DecodeContext,
+    ) -> Result<(), DecodeError>
+    where
+        B: Buf,
+    {
+        encoding::message::merge(wire_type, value, buf, ctx)
+    }
+}
+
+impl<M> RepeatedCodec for Message<M>
+where
+    M: crate::Message + Default,
+{
+    fn encode_repeated<B>(number: u32, values: &[M], buf: &mut B)
+    where
+        B: BufMut,
+    {
+        encoding::message::encode_repeated(number, values, buf)
+    }
+
+    fn merge_repeated<B>(
+        _number: u32,
+        wire_type: WireType,
+        values: &mut Vec<M>,
+        buf: &mut B,
+        ctx: DecodeContext,
+    ) -> Result<(), DecodeError>
+    where
+        B: Buf,
+    {
+        encoding::message::merge_repeated(wire_type, values, buf, ctx)
+    }
+}
+
+/// Codec for `group` extension fields.
+pub struct Group<M>(PhantomData<fn() -> M>);
+
+impl<M> Codec for Group<M>
+where
+    M: crate::Message + Default,
+{
+    type Value = M;
+
+    const WIRE_TYPE: WireType = WireType::StartGroup;
+
+    fn encode<B>(number: u32, value: &M, buf: &mut B)
+    where
+        B: BufMut,
+    {
+        encoding::group::encode(number, value, buf)
+    }
+
+    fn merge<B>(
+        number: u32,
+        wire_type: WireType,
+        value: &mut M,
+        buf: &mut B,
+        ctx: DecodeContext,
+    ) -> Result<(), DecodeError>
+    where
+        B: Buf,
+    {
+        encoding::group::merge(number, wire_type, value, buf, ctx)
+    }
+}
+
+impl<M> RepeatedCodec for Group<M>
+where
+    M: crate::Message + Default,
+{
+    fn encode_repeated<B>(number: u32, values: &[M], buf: &mut B)
+    where
+        B: BufMut,
+    {
+        encoding::group::encode_repeated(number, values, buf)
+    }
+
+    fn merge_repeated<B>(
+        number: u32,
+        wire_type: WireType,
+        values: &mut Vec<M>,
+        buf: &mut B,
+        ctx: DecodeContext,
+    ) -> Result<(), DecodeError>
+    where
+        B: Buf,
+    {
+        encoding::group::merge_repeated(number, wire_type, values, buf, ctx)
+    }
+}
+
+/// Codec for unpacked `repeated` extension fields.
+pub struct Repeated<C>(PhantomData<fn() -> C>);
+
+impl<C> Codec for Repeated<C>
+where
+    C: RepeatedCodec,
+{
+    type Value = Vec<C::Value>;
+
+    const WIRE_TYPE: WireType = C::WIRE_TYPE;
+
+    fn encode<B>(number: u32, values: &Vec<C::Value>, buf: &mut B)
+    where
+        B: BufMut,
+    {
+        C::encode_repeated(number, values, buf)
+    }
+
+    fn merge<B>(
+        number: u32,
+        wire_type: WireType,
+        values: &mut Vec<C::Value>,
+        buf: &mut B,
+        ctx: DecodeContext,
+    ) -> Result<(), DecodeError>
+    where
+        B: Buf,
+    {
+        C::merge_repeated(number, wire_type, values, buf, ctx)
+    }
+}
+
+/// Codec for packed `repeated` extension fields.
+pub struct Packed<C>(PhantomData<fn() -> C>);
+
+impl<C> Codec for Packed<C>
+where
+    C: PackedCodec,
+{
+    type Value = Vec<C::Value>;
+
+    const WIRE_TYPE: WireType = WireType::LengthDelimited;
+
+    fn encode<B>(number: u32, values: &Vec<C::Value>, buf: &mut B)
+    where
+        B: BufMut,
+    {
+        C::encode_packed(number, values, buf)
+    }
+
+    fn merge<B>(
+        number: u32,
+        wire_type: WireType,
+        values: &mut Vec<C::Value>,
+        buf: &mut B,
+        ctx: DecodeContext,
+    ) -> Result<(), DecodeError>
+    where
+        B: Buf,
+    {
+        C::merge_repeated(number, wire_type, values, buf, ctx)
+    }
+}
+
+/// A typed descriptor of an extension field of message type `M`, encoded with the [`Codec`] `T`.
+///
+/// Extension descriptors are usually generated by `prost-build` as constants for the `extend`
+/// declarations of a `.proto` file, and are passed to the methods of [`Extendable`].
+pub struct Extension<M, T> {
+    number: u32,
+    name: &'static str,
+    _marker: PhantomData<fn() -> (M, T)>,
+}
+
+impl<M, T> Extension<M, T> {
+    /// Creates a descriptor of the extension field with the given number and fully-qualified
+    /// Protobuf name.
+    pub const fn new(number: u32, name: &'static str) -> Extension<M, T> {
+        Extension {
+            number,
+            name,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns the field number of the extension.
+    pub const fn number(&self) -> u32 {
+        self.number
+    }
+
+    /// Returns the fully-qualified Protobuf name of the extension, e.g. `my.package.my_option`.
+    pub const fn name(&self) -> &'static str {
+        self.name
+    }
+}
+
+impl<M, T> Extension<M, T>
+where
+    T: Codec,
+{
+    /// Returns the wire type used to encode the extension.
+    pub const fn wire_type(&self) -> WireType {
+        T::WIRE_TYPE
+    }
+}
+
+impl<M, T> Clone for Extension<M, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<M, T> Copy for Extension<M, T> {}
+
+impl<M, T> fmt::Debug for Extension<M, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Extension")
+            .field("number", &self.number)
+            .field("name", &self.name)
+            .finish()
+    }
+}
+
+/// Storage for the extension fields of a message.
+///
+/// Extension fields are kept in their encoded form and in wire order. Typed values are decoded
+/// from the stored fields on access, so fields of extensions which are unknown to the application
+/// are preserved.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ExtensionSet {
+    fields: UnknownFieldSet,
+}
+
+impl ExtensionSet {
+    /// Creates an empty `ExtensionSet`.
+    pub fn new() -> ExtensionSet {
+        ExtensionSet::default()
+    }
+
+    /// Returns `true` if no extension fields are stored.
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty()
+    }
+
+    /// Returns an iterator over the stored extension fields in their encoded form, in wire order.
+    pub fn iter(&self) -> slice::Iter<'_, UnknownField> {
+        self.fields.iter()
+    }
+
+    /// Returns `true` if a value is stored for the extension.
+    pub fn has<M, T>(&self, extension: &Extension<M, T>) -> bool {
+        self.fields.get(extension.number).next().is_some()
+    }
+
+    /// Decodes the value of the extension.
+    ///
+    /// Returns `None` if no value is stored for the extension, and an error if the stored fields
+    /// can not be decoded with the extension's codec.
+    pub fn get<M, T>(&self, extension: &Extension<M, T>) -> Result<Option<T::Value>, DecodeError>
+    where
+        T: Codec,
+    {
+        let mut buf = Vec::new();
+        for field in self.fields.iter() {
+            if field.number == extension.number {
+                field.encode(&mut buf);
+            }
+        }
+        if buf.is_empty() {
+            return Ok(None);
+        }
+
+        let mut value = T::Value::default();
+        let mut buf = buf.as_slice();
+        while buf.has_remaining() {
+            let (number, wire_type) = decode_key(&mut buf)?;
+            T::merge(
+                number,
+                wire_type,
+                &mut value,
+                &mut buf,
+                DecodeContext::default(),
+            )?;
+        }
+        Ok(Some(value))
+    }
+
+    /// Sets the value of the extension, replacing any previously stored value.
+    pub fn set<M, T>(&mut self, extension: &Extension<M, T>, value: &T::Value)
+    where
+        T: Codec,
+    {
+        self.fields.remove(extension.number);
+
+        let mut buf = Vec::new();
+        T::encode(extension.number, value, &mut buf);
+        let mut buf = buf.as_slice();
+        while buf.has_remaining() {
+            let (number, wire_type) =
+                decode_key(&mut buf).expect("extension value is validly encoded");
+            crate::Message::merge_field(
+                &mut self.fields,
+                number,
+                wire_type,
+                &mut buf,
+                DecodeContext::default(),
+            )
+            .expect("extension value is validly encoded");
+        }
+    }
+
+    /// Removes any stored value of the extension.
+    pub fn clear<M, T>(&mut self, extension: &Extension<M, T>) {
+        self.fields.remove(extension.number);
+    }
+}
+
+impl crate::Message for ExtensionSet {
+    fn encode_raw<B>(&self, buf: &mut B)
+    where
+        B: BufMut,
+    {
+        self.fields.encode_raw(buf)
+    }
+
+    fn encode_raw_deterministic<B>(&self, buf: &mut B)
+    where
+        B: BufMut,
+    {
+        // Extensions are stored in the order they were set or decoded, so they are sorted by field
+        // number. The sort is stable, keeping the order of the values of repeated extensions.
+        let mut fields = self.fields.iter().collect::<Vec<_>>();
+        fields.sort_by_key(|field| field.number);
+        for field in fields {
+            field.encode(buf);
+        }
+    }
+
+    fn merge_field<B>(
+        &mut self,
+        tag: u32,
+        wire_type: WireType,
+        buf: &mut B,
+        ctx: DecodeContext,
+    ) -> Result<(), DecodeError>
+    where
+        B: Buf,
+    {
+        self.fields.merge_field(tag, wire_type, buf, ctx)
+    }
+
+    fn encoded_len(&self) -> usize {
+        self.fields.encoded_len()
+    }
+
+    fn merge_from(&mut self, other: &ExtensionSet) {
+        crate::Message::merge_from(&mut self.fields, &other.fields)
+    }
+
+    fn clear(&mut self) {
+        self.fields.clear()
+    }
+}
+
+/// A message with extension ranges, which stores its extension fields in an [`ExtensionSet`].
+///
+/// This trait is implemented by `#[derive(Message)]` for messages with a field declared with the
+/// `#[prost(extensions = "...")]` attribute, listing the extension ranges of the message:
+///
+/// ```rust
+/// # use prost::{Extendable, Extension, ExtensionSet, Message};
+/// #[derive(Clone, PartialEq, Message)]
+/// struct Options {
+///     #[prost(string, optional, tag = "1")]
+///     name: Option<String>,
+///     #[prost(extensions = "100..536870912")]
+///     extensions: ExtensionSet,
+/// }
+///
+/// const WEIGHT: Extension<Options, prost::extension::Int32> =
+///     Extension::new(100, "my.package.weight");
+///
+/// let mut options = Options::default();
+/// options.set_extension(&WEIGHT, 42);
+///
+/// let options = Options::decode(options.encode_to_vec().as_slice()).unwrap();
+/// assert_eq!(options.get_extension(&WEIGHT).unwrap(), Some(42));
+/// ```
+pub trait Extendable: crate::Message {
+    /// Returns the storage of the extension fields.
+    fn extension_set(&self) -> &ExtensionSet;
+
+    /// Returns the mutable storage of the extension fields.
+    fn extension_set_mut(&mut self) -> &mut ExtensionSet;
+
+    /// Returns `true` if a value is set for the extension.
+    fn has_extension<T>(&self, extension: &Extension<Self, T>) -> bool
+    where
+        Self: Sized,
+    {
+        self.extension_set().has(extension)
+    }
+
+    /// Decodes the value of the extension.
+    ///
+    /// Returns `None` if the extension is not set, and an error if the stored value can not be
+    /// decoded as the extension's type.
+    fn get_extension<T>(
+        &self,
+        extension: &Extension<Self, T>,
+    ) -> Result<Option<T::Value>, DecodeError>
+    where
+        T: Codec,
+        Self: Sized,
+    {
+        self.extension_set().get(extension)
+    }
+
+    /// Sets the value of the extension.
+    fn set_extension<T>(&mut self, extension: &Extension<Self, T>, value: T::Value)
+    where
+        T: Codec,
+        Self: Sized,
+    {
+        self.extension_set_mut().set(extension, &value)
+    }
+
+    /// Clears the value of the extension.
+    fn clear_extension<T>(&mut self, extension: &Extension<Self, T>)
+    where
+        Self: Sized,
+    {
+        self.extension_set_mut().clear(extension)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use alloc::string::ToString;
+    use alloc::vec;
+
+    use crate::Message as _;
+
+    const NUMBER: Extension<(), Int32> = Extension::new(100, "test.number");
+    const NAME: Extension<(), String> = Extension::new(101, "test.name");
+    const VALUES: Extension<(), Packed<Sint64>> = Extension::new(102, "test.values");
+    const UNPACKED: Extension<(), Repeated<Fixed32>> = Extension::new(103, "test.unpacked");
+    const NESTED: Extension<(), Message<ExtensionSet>> = Extension::new(104, "test.nested");
+    const GROUP: Extension<(), Group<ExtensionSet>> = Extension::new(105, "test.group");
+
+    #[test]
+    fn set_get_clear() {
+        let mut set = ExtensionSet::new();
+        assert!(!set.has(&NUMBER));
+        assert_eq!(set.get(&NUMBER), Ok(None));
+
+        set.set(&NUMBER, &-5);
+        set.set(&NAME, &"hello".to_string());
+        set.set(&VALUES, &vec![1, -2, 3]);
+        set.set(&UNPACKED, &vec![4, 5]);
+        assert!(set.has(&NUMBER));
+        assert_eq!(set.get(&NUMBER), Ok(Some(-5)));
+        assert_eq!(set.get(&NAME), Ok(Some("hello".to_string())));
+        assert_eq!(set.get(&VALUES), Ok(Some(vec![1, -2, 3])));
+        assert_eq!(set.get(&UNPACKED), Ok(Some(vec![4, 5])));
+        assert_eq!(set.iter().filter(|field| field.number == 103).count(), 2);
+
+        set.set(&NUMBER, &7);
+        assert_eq!(set.get(&NUMBER), Ok(Some(7)));
+
+        set.clear(&NUMBER);
+        assert!(!set.has(&NUMBER));
+        assert!(set.has(&NAME));
+    }
+
+    #[test]
+    fn nested_messages() {
+        let mut inner = ExtensionSet::new();
+        inner.set(&NUMBER, &1);
+
+        let mut set = ExtensionSet::new();
+        set.set(&NESTED, &inner);
+        set.set(&GROUP, &inner);
+        assert_eq!(set.get(&NESTED), Ok(Some(inner.clone())));
+        assert_eq!(set.get(&GROUP), Ok(Some(inner)));
+    }
+
+    #[test]
+    fn roundtrip_preserves_fields() {
+        let mut set = ExtensionSet::new();
+        set.set(&NAME, &"hello".to_string());
+        set.set(&VALUES, &vec![1, 2]);
+
+        let encoded = set.encode_to_vec();
+        assert_eq!(set.encoded_len(), encoded.len());
+        let decoded = ExtensionSet::decode(encoded.as_slice()).unwrap();
+        assert_eq!(decoded, set);
+        assert_eq!(decoded.encode_to_vec(), encoded);
+    }
+
+    #[test]
+    fn mismatched_codec() {
+        let mut set = ExtensionSet::new();
+        set.set(&NAME, &"hello".to_string());
+        let number: Extension<(), Int32> = Extension::new(NAME.number(), "test.wrong");
+        assert!(set.get(&number).is_err());
+    }
+}
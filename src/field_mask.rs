@@ -0,0 +1,468 @@
+//! Field mask operations on messages.
+//!
+//! A field mask is a set of paths, each naming a field of a message by its name in the `.proto`
+//! file. A path can descend into a singular message field to name one of its fields, such as
+//! `address.city`. Field masks are usually carried in a `google.protobuf.FieldMask` message, for
+//! example to name the fields changed by an update method following [AIP-134][1].
+//!
+//! Implementations of [`FieldMasked`] are generated by `prost-build` when enabled with
+//! `Config::enable_field_masks`, and support three operations:
+//!
+//!  * [`merge_with_mask`](FieldMasked::merge_with_mask) copies the masked fields of another
+//!    message into `self`.
+//!  * [`prune`](FieldMasked::prune) clears every field of `self` which is not masked.
+//!  * [`validate_mask`](FieldMasked::validate_mask) checks that every path names a field.
+//!
+//! Paths can not descend into repeated, map or lazy fields, and extension fields can not be
+//! named.
+//!
+//! [1]: https://google.aip.dev/134
+
+use alloc::borrow::Cow;
+use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::{DecodeOptions, Message};
+
+/// A message whose fields can be selected by a field mask.
+pub trait FieldMasked: Message {
+    /// Checks that the paths of the mask name fields of the message.
+    ///
+    /// Meant to be used only by `FieldMasked` implementations.
+    #[doc(hidden)]
+    fn check_mask_tree(mask: &FieldMaskTree) -> Result<(), FieldMaskError>
+    where
+        Self: Sized;
+
+    /// Copies the fields of `src` selected by a valid mask into `self`.
+    ///
+    /// Meant to be used only by `FieldMasked` implementations.
+    #[doc(hidden)]
+    fn merge_with_mask_tree(&mut self, src: &Self, mask: &FieldMaskTree, options: &MergeOptions)
+    where
+        Self: Sized;
+
+    /// Clears the fields of `self` not selected by a valid mask.
+    ///
+    /// Meant to be used only by `FieldMasked` implementations.
+    #[doc(hidden)]
+    fn prune_with_mask_tree(&mut self, mask: &FieldMaskTree);
+
+    /// Checks that each path of the mask names a field of the message.
+    fn validate_mask<S>(paths: &[S]) -> Result<(), FieldMaskError>
+    where
+        S: AsRef<str>,
+        Self: Sized,
+    {
+        Self::check_mask_tree(&FieldMaskTree::new(paths)?)
+    }
+
+    /// Copies the fields of `src` named by the mask into `self`, replacing the values of masked
+    /// repeated, map and message fields.
+    ///
+    /// The mask is validated first, and `self` is left unchanged if it is invalid.
+    fn merge_with_mask<S>(&mut self, src: &Self, paths: &[S]) -> Result<(), FieldMaskError>
+    where
+        S: AsRef<str>,
+        Self: Sized,
+    {
+        self.merge_with_mask_options(src, paths, &MergeOptions::new())
+    }
+
+    /// Copies the fields of `src` named by the mask into `self`, using the given options.
+    ///
+    /// The mask is validated first, and `self` is left unchanged if it is invalid.
+    fn merge_with_mask_options<S>(
+        &mut self,
+        src: &Self,
+        paths: &[S],
+        options: &MergeOptions,
+    ) -> Result<(), FieldMaskError>
+    where
+        S: AsRef<str>,
+        Self: Sized,
+    {
+        let mask = FieldMaskTree::new(paths)?;
+        Self::check_mask_tree(&mask)?;
+        self.merge_with_mask_tree(src, &mask, options);
+        Ok(())
+    }
+
+    /// Clears every field of `self` which is not named by the mask, along with any extension and
+    /// unknown fields.
+    ///
+    /// The mask is validated first, and `self` is left unchanged if it is invalid.
+    fn prune<S>(&mut self, paths: &[S]) -> Result<(), FieldMaskError>
+    where
+        S: AsRef<str>,
+        Self: Sized,
+    {
+        let mask = FieldMaskTree::new(paths)?;
+        Self::check_mask_tree(&mask)?;
+        self.prune_with_mask_tree(&mask);
+        Ok(())
+    }
+}
+
+impl<M> FieldMasked for Box<M>
+where
+    M: FieldMasked,
+{
+    fn check_mask_tree(mask: &FieldMaskTree) -> Result<(), FieldMaskError> {
+        M::check_mask_tree(mask)
+    }
+
+    fn merge_with_mask_tree(&mut self, src: &Self, mask: &FieldMaskTree, options: &MergeOptions) {
+        (**self).merge_with_mask_tree(src, mask, options)
+    }
+
+    fn prune_with_mask_tree(&mut self, mask: &FieldMaskTree) {
+        (**self).prune_with_mask_tree(mask)
+    }
+}
+
+/// Options for [`FieldMasked::merge_with_mask_options`].
+///
+/// By default, masked repeated, map and message fields are replaced by the values in the source
+/// message, following the update semantics of [AIP-134][1]. Both can instead be merged, matching
+/// the defaults of `FieldMaskUtil` in the C++ and Java Protobuf libraries.
+///
+/// [1]: https://google.aip.dev/134
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MergeOptions {
+    replace_repeated_fields: bool,
+    replace_message_fields: bool,
+}
+
+impl MergeOptions {
+    /// Creates the default options.
+    pub fn new() -> MergeOptions {
+        MergeOptions::default()
+    }
+
+    /// Sets whether masked repeated and map fields are replaced by the values in the source
+    /// message. If not, the values in the source message are appended, and map entries are
+    /// inserted. Enabled by default.
+    pub fn replace_repeated_fields(mut self, replace: bool) -> MergeOptions {
+        self.replace_repeated_fields = replace;
+        self
+    }
+
+    /// Sets whether masked message fields are replaced by the values in the source message. If
+    /// not, the source message is merged into the message in `self`, as if by [`Message::merge`],
+    /// and a message field which is not set in the source message is left unchanged. Enabled by
+    /// default.
+    ///
+    /// Lazy message fields are always replaced.
+    pub fn replace_message_fields(mut self, replace: bool) -> MergeOptions {
+        self.replace_message_fields = replace;
+        self
+    }
+}
+
+impl Default for MergeOptions {
+    fn default() -> MergeOptions {
+        MergeOptions {
+            replace_repeated_fields: true,
+            replace_message_fields: true,
+        }
+    }
+}
+
+/// The paths of a field mask, arranged as a tree of field names.
+///
+/// Redundant paths are removed: a path which descends into a field named by another path of the
+/// mask is covered by that path.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct FieldMaskTree {
+    children: BTreeMap<String, FieldMaskTree>,
+}
+
+impl FieldMaskTree {
+    /// Creates a tree from the paths of a field mask.
+    ///
+    /// Returns an error if a path is empty, or has an empty field name.
+    pub fn new<S>(paths: &[S]) -> Result<FieldMaskTree, FieldMaskError>
+    where
+        S: AsRef<str>,
+    {
+        let mut tree = FieldMaskTree::default();
+        for path in paths {
+            tree.insert(path.as_ref())?;
+        }
+        Ok(tree)
+    }
+
+    fn insert(&mut self, path: &str) -> Result<(), FieldMaskError> {
+        if path.split('.').any(str::is_empty) {
+            return Err(FieldMaskError::new(path, "empty field name"));
+        }
+        let mut node = self;
+        let mut names = path.split('.').peekable();
+        while let Some(name) = names.next() {
+            let exists = node.children.contains_key(name);
+            node = node.children.entry(name.to_string()).or_default();
+            if exists && node.is_leaf() {
+                // The path is covered by a shorter path.
+                break;
+            }
+            if names.peek().is_none() {
+                node.children.clear();
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns `true` if the tree has no paths.
+    ///
+    /// The tree of a field selects the whole field if it is empty.
+    pub fn is_leaf(&self) -> bool {
+        self.children.is_empty()
+    }
+
+    /// Returns the tree of the field with the given name, if the field is selected.
+    pub fn get(&self, name: &str) -> Option<&FieldMaskTree> {
+        self.children.get(name)
+    }
+
+    /// Returns an iterator over the selected fields and their trees, in name order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &FieldMaskTree)> {
+        self.children
+            .iter()
+            .map(|(name, tree)| (name.as_str(), tree))
+    }
+
+    /// Returns the paths of the tree, in sorted order.
+    pub fn paths(&self) -> Vec<String> {
+        let mut paths = Vec::new();
+        for (name, tree) in self.iter() {
+            if tree.is_leaf() {
+                paths.push(name.to_string());
+            } else {
+                paths.extend(
+                    tree.paths()
+                        .into_iter()
+                        .map(|path| [name, ".", &path].concat()),
+                );
+            }
+        }
+        paths
+    }
+}
+
+/// An invalid field mask path.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FieldMaskError {
+    path: String,
+    description: Cow<'static, str>,
+}
+
+impl FieldMaskError {
+    fn new(path: impl Into<String>, description: impl Into<Cow<'static, str>>) -> FieldMaskError {
+        FieldMaskError {
+            path: path.into(),
+            description: description.into(),
+        }
+    }
+
+    /// Returns the invalid path, or its prefix up to the invalid field name.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+}
+
+impl fmt::Display for FieldMaskError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid field mask path \"{}\": {}",
+            self.path, self.description
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for FieldMaskError {}
+
+/// Returns the error for a path naming a field the message does not have.
+///
+/// Meant to be used only by `FieldMasked` implementations.
+#[doc(hidden)]
+#[cold]
+pub fn unknown_field(name: &str) -> FieldMaskError {
+    FieldMaskError::new(name, "no such field")
+}
+
+/// Checks that a mask selects the whole of a field which paths can not descend into.
+///
+/// Meant to be used only by `FieldMasked` implementations.
+#[doc(hidden)]
+pub fn check_leaf(name: &str, mask: &FieldMaskTree) -> Result<(), FieldMaskError> {
+    match mask.iter().next() {
+        None => Ok(()),
+        Some((child, _)) => Err(FieldMaskError::new(
+            [name, ".", child].concat(),
+            "paths can only descend into singular message fields",
+        )),
+    }
+}
+
+/// Checks a mask of a singular message field.
+///
+/// Meant to be used only by `FieldMasked` implementations.
+#[doc(hidden)]
+pub fn check_message<M>(name: &str, mask: &FieldMaskTree) -> Result<(), FieldMaskError>
+where
+    M: FieldMasked,
+{
+    M::check_mask_tree(mask).map_err(|mut error| {
+        error.path = [name, ".", &error.path].concat();
+        error
+    })
+}
+
+/// Copies a masked repeated field.
+///
+/// Meant to be used only by `FieldMasked` implementations.
+#[doc(hidden)]
+pub fn merge_repeated<T>(dst: &mut Vec<T>, src: &[T], options: &MergeOptions)
+where
+    T: Clone,
+{
+    if options.replace_repeated_fields {
+        dst.clear();
+    }
+    dst.extend_from_slice(src);
+}
+
+/// Copies a masked map field.
+///
+/// Meant to be used only by `FieldMasked` implementations.
+#[doc(hidden)]
+pub fn merge_map<'a, M, K, V>(dst: &mut M, src: &'a M, options: &MergeOptions)
+where
+    M: Clone + Extend<(K, V)>,
+    &'a M: IntoIterator<Item = (&'a K, &'a V)>,
+    K: Clone + 'a,
+    V: Clone + 'a,
+{
+    if options.replace_repeated_fields {
+        dst.clone_from(src);
+    } else {
+        dst.extend(
+            src.into_iter()
+                .map(|(key, value)| (key.clone(), value.clone())),
+        );
+    }
+}
+
+/// Copies a masked optional message field.
+///
+/// Meant to be used only by `FieldMasked` implementations.
+#[doc(hidden)]
+pub fn merge_message<M>(
+    dst: &mut Option<M>,
+    src: Option<&M>,
+    mask: &FieldMaskTree,
+    options: &MergeOptions,
+) where
+    M: FieldMasked + Clone + Default,
+{
+    match (dst, src) {
+        (Some(dst), Some(src)) => merge_message_value(dst, src, mask, options),
+        (dst, src) if mask.is_leaf() => {
+            if options.replace_message_fields || src.is_some() {
+                *dst = src.cloned();
+            }
+        }
+        // A path descends into a field which is set in only one of the messages, so the field is
+        // merged as if it were set to the default value in the other.
+        (dst @ None, Some(src)) => {
+            merge_message_value(dst.insert(M::default()), src, mask, options)
+        }
+        (Some(dst), None) => merge_message_value(dst, &M::default(), mask, options),
+        (None, None) => (),
+    }
+}
+
+/// Copies a masked message value.
+///
+/// Meant to be used only by `FieldMasked` implementations.
+#[doc(hidden)]
+pub fn merge_message_value<M>(dst: &mut M, src: &M, mask: &FieldMaskTree, options: &MergeOptions)
+where
+    M: FieldMasked + Clone,
+{
+    if !mask.is_leaf() {
+        dst.merge_with_mask_tree(src, mask, options);
+    } else if options.replace_message_fields {
+        dst.clone_from(src);
+    } else {
+        // The message was just encoded, so merging it can only fail by exceeding the recursion
+        // limit, which is lifted.
+        let options = DecodeOptions::new().recursion_limit(u32::MAX);
+        dst.merge_with_options(src.encode_to_vec().as_slice(), &options)
+            .expect("failed to merge an encoded message");
+    }
+}
+
+/// Prunes an optional message field, clearing it if it is not masked.
+///
+/// Meant to be used only by `FieldMasked` implementations.
+#[doc(hidden)]
+pub fn prune_message<M>(value: &mut Option<M>, mask: Option<&FieldMaskTree>)
+where
+    M: FieldMasked,
+{
+    if let Some(message) = value {
+        if !prune_message_value(message, mask) {
+            *value = None;
+        }
+    }
+}
+
+/// Prunes a message value, returning `false` if it is not masked and should be cleared.
+///
+/// Meant to be used only by `FieldMasked` implementations.
+#[doc(hidden)]
+pub fn prune_message_value<M>(value: &mut M, mask: Option<&FieldMaskTree>) -> bool
+where
+    M: FieldMasked,
+{
+    match mask {
+        Some(mask) => {
+            if !mask.is_leaf() {
+                value.prune_with_mask_tree(mask);
+            }
+            true
+        }
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use alloc::vec;
+
+    #[test]
+    fn tree() {
+        let tree = FieldMaskTree::new(&["b.c", "a", "b.d.e", "a.x", "b.d"]).unwrap();
+        assert_eq!(tree.paths(), vec!["a", "b.c", "b.d"]);
+        assert!(tree.get("a").unwrap().is_leaf());
+        assert!(!tree.get("b").unwrap().is_leaf());
+        assert_eq!(tree.get("c"), None);
+
+        let tree = FieldMaskTree::new::<&str>(&[]).unwrap();
+        assert!(tree.is_leaf());
+        assert!(tree.paths().is_empty());
+
+        for path in ["", "a.", ".a", "a..b"] {
+            assert_eq!(FieldMaskTree::new(&[path]).unwrap_err().path(), path);
+        }
+    }
+}
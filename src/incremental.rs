@@ -0,0 +1,610 @@
+//! Decoding messages from input which arrives in chunks.
+
+use alloc::vec::Vec;
+use core::mem;
+
+use bytes::Buf;
+
+use crate::encoding::{
+    decode_key, decode_partial_varint, decode_varint, encode_key, encode_varint, key_len,
+    DecodeContext, WireType,
+};
+use crate::options::Budget;
+use crate::required;
+use crate::strict::{FieldChecks, MessageFields, SingularMessageField};
+use crate::{DecodeError, DecodeErrorKind, DecodeOptions, Message};
+
+/// The result of pushing a chunk of input to an [`IncrementalDecoder`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DecodeStatus<M> {
+    /// The message is not complete yet, and more input is needed.
+    NeedMoreData,
+    /// The message is complete.
+    Done(M),
+}
+
+/// A push-style decoder, which decodes a message from input which arrives in chunks.
+///
+/// Each chunk is passed to [`decode`](IncrementalDecoder::decode) as it arrives. The decoder
+/// scans the chunk and keeps its state between calls, including partially received varints and
+/// the stack of nested messages and groups being received, and merges each field of the message as
+/// soon as the field is complete.
+///
+/// Message and group fields which are not repeated, including those of oneofs, are not buffered:
+/// the decoder descends into them, at any depth, and merges each of their fields on its own. Other
+/// fields, such as strings, packed fields, and the elements of repeated message fields, are
+/// buffered until they are complete, so the memory used beyond the message itself is bounded by
+/// the size of the largest such field, rather than by the size of the whole message. The length of
+/// a buffered field is checked as soon as it is received: decoding fails if it exceeds the
+/// enclosing message, or, with [`with_options`](IncrementalDecoder::with_options), if it exceeds
+/// [`max_field_bytes`] or what is left of [`max_total_bytes`].
+///
+/// The end of the message is found in one of two ways:
+///
+///  * A decoder created with [`length_delimited`](IncrementalDecoder::length_delimited) reads a
+///    length delimiter first, as written by `Message::encode_length_delimited`, and returns
+///    [`DecodeStatus::Done`] once the message is complete. Input following the message is left in
+///    the chunk, so a stream of length-delimited messages can be decoded by pushing the same chunk
+///    again.
+///  * A decoder created with [`new`](IncrementalDecoder::new) reads until the end of the input,
+///    which is signaled by calling [`finish`](IncrementalDecoder::finish).
+///
+/// ```rust
+/// use prost::{DecodeStatus, IncrementalDecoder, Message};
+///
+/// #[derive(Clone, PartialEq, Message)]
+/// struct Chunked {
+///     #[prost(string, tag = "1")]
+///     name: String,
+///     #[prost(uint64, repeated, tag = "2")]
+///     values: Vec<u64>,
+/// }
+///
+/// let message = Chunked { name: "chunked".to_string(), values: vec![1, 300, 70000] };
+/// let buf = message.encode_length_delimited_to_vec();
+///
+/// let mut decoder = IncrementalDecoder::<Chunked>::length_delimited();
+/// let mut chunks = buf.chunks(3);
+/// let decoded = loop {
+///     let mut chunk = chunks.next().unwrap();
+///     if let DecodeStatus::Done(decoded) = decoder.decode(&mut chunk).unwrap() {
+///         break decoded;
+///     }
+/// };
+/// assert_eq!(decoded, message);
+/// ```
+///
+/// After a message is complete, or if decoding fails, the decoder is reset and can be used to
+/// decode another message.
+///
+/// [`max_field_bytes`]: DecodeOptions::max_field_bytes
+/// [`max_total_bytes`]: DecodeOptions::max_total_bytes
+#[derive(Debug)]
+pub struct IncrementalDecoder<M> {
+    message: M,
+    options: DecodeOptions,
+    budget: Option<Budget>,
+    length_delimited: bool,
+    /// The number of bytes of the message which have not been read yet, once its length delimiter
+    /// has been read.
+    remaining: Option<usize>,
+    /// The bytes of the field being received, or of the length delimiter.
+    pending: Vec<u8>,
+    state: State,
+    /// The field numbers of the groups started but not ended in the field being received.
+    groups: Vec<u32>,
+    /// The message and group fields being received field by field, outermost first.
+    frames: Vec<Frame>,
+    /// The offset in the message of the field being received.
+    offset: usize,
+    /// The fields of the message received so far, for the checks made by the options.
+    checks: FieldChecks,
+}
+
+/// A message or group field which is not repeated, whose fields are merged into the message one
+/// at a time as they are received.
+#[derive(Debug)]
+struct Frame {
+    tag: u32,
+    field: SingularMessageField,
+    /// The offset in the message of the end of a message field, or `None` for a group field,
+    /// which ends with an end group key.
+    end: Option<usize>,
+    /// The singular message fields of the message type of the field.
+    fields: MessageFields,
+    /// The fields received so far, for the checks made by the options.
+    checks: FieldChecks,
+}
+
+/// The part of the field being received which is read next.
+///
+/// Varints are kept in `pending` until they are complete, from the offset given by `start`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum State {
+    /// The length delimiter of the message.
+    Delimiter,
+    /// The key of a field, or of a field nested in a group.
+    Key { start: usize },
+    /// A varint value.
+    Varint { start: usize },
+    /// The length of a length-delimited value.
+    Length { start: usize },
+    /// The bytes of a fixed-width or length-delimited value.
+    Bytes { remaining: usize },
+}
+
+impl State {
+    /// Returns the offset in the pending bytes of the varint being read.
+    fn varint_start(self) -> usize {
+        match self {
+            State::Delimiter => 0,
+            State::Key { start } | State::Varint { start } | State::Length { start } => start,
+            State::Bytes { .. } => unreachable!(),
+        }
+    }
+}
+
+impl<M> IncrementalDecoder<M>
+where
+    M: Message + Default,
+{
+    /// Creates a decoder for a message which extends to the end of the input.
+    pub fn new() -> IncrementalDecoder<M> {
+        IncrementalDecoder::create(false)
+    }
+
+    /// Creates a decoder for a message preceded by a length delimiter.
+    pub fn length_delimited() -> IncrementalDecoder<M> {
+        IncrementalDecoder::create(true)
+    }
+
+    fn create(length_delimited: bool) -> IncrementalDecoder<M> {
+        IncrementalDecoder {
+            message: M::default(),
+            options: DecodeOptions::default(),
+            budget: None,
+            length_delimited,
+            remaining: None,
+            pending: Vec::new(),
+            state: IncrementalDecoder::<M>::initial_state(length_delimited),
+            groups: Vec::new(),
+            frames: Vec::new(),
+            offset: 0,
+            checks: FieldChecks::new::<M>(&DecodeContext::default()),
+        }
+    }
+
+    /// Sets the options used to decode each message.
+    pub fn with_options(mut self, options: &DecodeOptions) -> IncrementalDecoder<M> {
+        self.options = options.clone();
+        self.take();
+        self
+    }
+
+    /// Decodes a chunk of input.
+    ///
+    /// Returns [`DecodeStatus::Done`] with the message once it is complete, which can only happen
+    /// for a length-delimited message. The chunk is consumed up to the end of the message, and
+    /// entirely if the message is not complete yet.
+    pub fn decode<B>(&mut self, buf: &mut B) -> Result<DecodeStatus<M>, DecodeError>
+    where
+        B: Buf,
+    {
+        self.decode_chunk(buf).map_err(|error| self.fail(error))
+    }
+
+    /// Signals the end of the input, and returns the decoded message.
+    ///
+    /// Fails if the input ends within a field, or, for a length-delimited message, before the end
+    /// of the message.
+    pub fn finish(&mut self) -> Result<M, DecodeError> {
+        let complete = match self.remaining {
+            Some(remaining) => remaining == 0,
+            None => !self.length_delimited && self.frames.is_empty() && self.is_between_fields(),
+        };
+        if !complete {
+            let error = DecodeError::with_kind(DecodeErrorKind::UnexpectedEof, "buffer underflow");
+            return Err(self.fail(error));
+        }
+        if let Err(error) = self.check_required_fields() {
+            return Err(self.fail(error));
+        }
+        Ok(self.take())
+    }
+
+    /// Returns `true` if no part of the message has been decoded since it was started.
+    pub fn is_empty(&self) -> bool {
+        self.offset == 0 && self.pending.is_empty() && self.remaining.is_none()
+    }
+
+    fn decode_chunk<B>(&mut self, buf: &mut B) -> Result<DecodeStatus<M>, DecodeError>
+    where
+        B: Buf,
+    {
+        loop {
+            if self.limit() == Some(0) {
+                // The innermost message field being received, or the message, ends here.
+                if !self.is_between_fields()
+                    || self.frames.last().is_some_and(|frame| frame.end.is_none())
+                {
+                    return Err(self.nested(DecodeError::with_kind(
+                        DecodeErrorKind::InvalidLength,
+                        "delimited length exceeded",
+                    )));
+                }
+                if self.frames.pop().is_some() {
+                    continue;
+                }
+                self.check_required_fields()?;
+                return Ok(DecodeStatus::Done(self.take()));
+            }
+            if !buf.has_remaining() {
+                return Ok(DecodeStatus::NeedMoreData);
+            }
+
+            let chunk = buf.chunk();
+            let limit = match self.limit() {
+                Some(limit) => chunk.len().min(limit),
+                None => chunk.len(),
+            };
+            match self.state {
+                State::Bytes { remaining } => {
+                    let len = limit.min(remaining);
+                    self.consume(buf, len);
+                    if len == remaining {
+                        self.value_complete()?;
+                    } else {
+                        self.state = State::Bytes {
+                            remaining: remaining - len,
+                        };
+                    }
+                }
+                state => {
+                    // The received part of the varint is followed by as much of the chunk as the
+                    // longest varint could need.
+                    let start = state.varint_start();
+                    let received = self.pending.len() - start;
+                    let mut bytes = [0; 10];
+                    let len = limit.min(bytes.len() - received);
+                    bytes[..received].copy_from_slice(&self.pending[start..]);
+                    bytes[received..received + len].copy_from_slice(&chunk[..len]);
+                    match decode_partial_varint(&bytes[..received + len]) {
+                        Ok(Some((_, varint_len))) => {
+                            self.consume(buf, varint_len - received);
+                            self.varint_complete(start)?;
+                        }
+                        Ok(None) => self.consume(buf, len),
+                        Err(error) => {
+                            // The error is reported at the end of the invalid varint.
+                            self.consume(buf, len);
+                            return Err(self.nested(error));
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Moves `len` bytes of the chunk to the pending bytes.
+    fn consume<B>(&mut self, buf: &mut B, len: usize)
+    where
+        B: Buf,
+    {
+        self.pending.extend_from_slice(&buf.chunk()[..len]);
+        buf.advance(len);
+        if let Some(remaining) = &mut self.remaining {
+            *remaining -= len;
+        }
+    }
+
+    fn varint_complete(&mut self, start: usize) -> Result<(), DecodeError> {
+        let mut varint = &self.pending[start..];
+        match self.state {
+            State::Delimiter => {
+                let len = decode_varint(&mut varint)?;
+                if len > usize::MAX as u64 {
+                    return Err(DecodeError::with_kind(
+                        DecodeErrorKind::InvalidLength,
+                        "length delimiter exceeds maximum usize value",
+                    ));
+                }
+                self.remaining = Some(len as usize);
+                self.pending.clear();
+                self.state = State::Key { start: 0 };
+            }
+            State::Key { .. } => {
+                let (tag, wire_type) =
+                    decode_key(&mut varint).map_err(|error| self.nested(error))?;
+                let start = self.pending.len();
+                self.state = match wire_type {
+                    WireType::Varint => State::Varint { start },
+                    WireType::SixtyFourBit => State::Bytes { remaining: 8 },
+                    WireType::LengthDelimited => State::Length { start },
+                    WireType::StartGroup => {
+                        let depth = self.frames.len() + self.groups.len();
+                        if depth >= self.options.recursion_limit as usize {
+                            return Err(self.nested(DecodeError::with_kind(
+                                DecodeErrorKind::RecursionLimit,
+                                "recursion limit reached",
+                            )));
+                        }
+                        if self.groups.is_empty() {
+                            if let Some(field) = self.singular_message_field(tag, wire_type) {
+                                return self.enter(tag, field, None);
+                            }
+                        }
+                        self.groups.push(tag);
+                        State::Key { start }
+                    }
+                    WireType::EndGroup => {
+                        if self.groups.is_empty() {
+                            return self.end_group(tag);
+                        }
+                        if self.groups.pop() != Some(tag) {
+                            return Err(self.nested(DecodeError::with_kind(
+                                DecodeErrorKind::UnexpectedEndGroup,
+                                "unexpected end group tag",
+                            )));
+                        }
+                        return self.value_complete();
+                    }
+                    WireType::ThirtyTwoBit => State::Bytes { remaining: 4 },
+                };
+            }
+            State::Varint { .. } => {
+                decode_varint(&mut varint).map_err(|error| self.nested(error))?;
+                self.value_complete()?;
+            }
+            State::Length { .. } => {
+                let len = decode_varint(&mut varint).map_err(|error| self.nested(error))?;
+                if self.limit().is_some_and(|limit| len > limit as u64) || len > usize::MAX as u64 {
+                    return Err(self.nested(DecodeError::with_kind(
+                        DecodeErrorKind::InvalidLength,
+                        "delimited length exceeded",
+                    )));
+                }
+                let len = len as usize;
+                if self.groups.is_empty() {
+                    let (tag, _) = decode_key(&mut self.pending.as_slice())?;
+                    if let Some(field) = self.singular_message_field(tag, WireType::LengthDelimited)
+                    {
+                        let end = self.offset + self.pending.len() + len;
+                        return self.enter(tag, field, Some(end));
+                    }
+                }
+                // The value is buffered until it is complete.
+                if let Some(budget) = &self.budget {
+                    budget
+                        .check_buffered(len)
+                        .map_err(|error| self.nested(error))?;
+                }
+                match len {
+                    0 => self.value_complete()?,
+                    len => self.state = State::Bytes { remaining: len },
+                }
+            }
+            State::Bytes { .. } => unreachable!(),
+        }
+        Ok(())
+    }
+
+    /// Merges the field being received into the message once its value is complete, unless the
+    /// value is nested in a group of the field.
+    fn value_complete(&mut self) -> Result<(), DecodeError> {
+        if self.groups.is_empty() {
+            let (tag, _) = decode_key(&mut self.pending.as_slice())?;
+            let checked = self.checks().decoded(tag);
+            checked.map_err(|error| self.nested(error))?;
+            let pending = mem::take(&mut self.pending);
+            let merged = self.merge(&pending);
+            self.pending = pending;
+            merged?;
+            self.offset += self.pending.len();
+            self.pending.clear();
+        }
+        self.state = State::Key {
+            start: self.pending.len(),
+        };
+        Ok(())
+    }
+
+    /// Starts receiving a message or group field which is not repeated field by field, once its
+    /// key, and its length for a message field, have been received.
+    fn enter(
+        &mut self,
+        tag: u32,
+        field: SingularMessageField,
+        end: Option<usize>,
+    ) -> Result<(), DecodeError> {
+        let checked = self.checks().decoded(tag);
+        checked.map_err(|error| self.nested(error))?;
+
+        // The field is merged empty first, so that it is set even if it has no fields.
+        let mut empty = Vec::new();
+        encode_key(tag, field.wire_type, &mut empty);
+        match end {
+            Some(_) => empty.push(0),
+            None => encode_key(tag, WireType::EndGroup, &mut empty),
+        }
+        self.merge(&empty)?;
+
+        let fields = field.nested.expect("field is not decoded lazily");
+        let ctx = DecodeContext::new(&self.options, self.budget.as_ref());
+        let checks = FieldChecks::of(fields, &ctx);
+        self.frames.push(Frame {
+            tag,
+            field,
+            end,
+            fields,
+            checks,
+        });
+        self.offset += self.pending.len();
+        self.pending.clear();
+        self.state = State::Key { start: 0 };
+        Ok(())
+    }
+
+    /// Ends the innermost group field being received, once its end group key has been received.
+    fn end_group(&mut self, tag: u32) -> Result<(), DecodeError> {
+        match self.frames.last() {
+            Some(frame) if frame.end.is_none() && frame.tag == tag => {
+                self.frames.pop();
+                self.offset += self.pending.len();
+                self.pending.clear();
+                self.state = State::Key { start: 0 };
+                Ok(())
+            }
+            _ => Err(self.nested(DecodeError::with_kind(
+                DecodeErrorKind::UnexpectedEndGroup,
+                "unexpected end group tag",
+            ))),
+        }
+    }
+
+    /// Returns the field of the innermost message being received with the given number and wire
+    /// type, if it is a message or group field which is not repeated, which is received field by
+    /// field unless it is decoded lazily.
+    fn singular_message_field(
+        &self,
+        tag: u32,
+        wire_type: WireType,
+    ) -> Option<SingularMessageField> {
+        let fields = match self.frames.last() {
+            Some(frame) => frame.fields,
+            None => M::singular_message_field,
+        };
+        fields(tag).filter(|field| field.wire_type == wire_type && field.nested.is_some())
+    }
+
+    /// Merges a complete field of the innermost message being received into the message, nested
+    /// in the fields being received which enclose it.
+    fn merge(&mut self, field: &[u8]) -> Result<(), DecodeError> {
+        let nested;
+        let mut buf = if self.frames.is_empty() {
+            field
+        } else {
+            nested = self.wrap(field);
+            nested.as_slice()
+        };
+        let (tag, wire_type) = decode_key(&mut buf)?;
+        let ctx = DecodeContext::new(&self.options, self.budget.as_ref());
+        if let Err(mut error) = self.message.merge_field(tag, wire_type, &mut buf, ctx) {
+            // The bytes left after the error end with the end group keys of the enclosing groups.
+            let end_group_keys: usize = self
+                .frames
+                .iter()
+                .filter(|frame| frame.end.is_none())
+                .map(|frame| key_len(frame.tag))
+                .sum();
+            let left = buf.len().saturating_sub(end_group_keys).min(field.len());
+            error.set_offset(self.offset + field.len() - left);
+            return Err(error);
+        }
+        Ok(())
+    }
+
+    /// Returns a field of the innermost message being received, nested in the fields being
+    /// received which enclose it.
+    fn wrap(&self, field: &[u8]) -> Vec<u8> {
+        let mut nested = field.to_vec();
+        for frame in self.frames.iter().rev() {
+            let mut outer = Vec::with_capacity(nested.len() + 2 * key_len(frame.tag) + 10);
+            encode_key(frame.tag, frame.field.wire_type, &mut outer);
+            match frame.end {
+                Some(_) => {
+                    encode_varint(nested.len() as u64, &mut outer);
+                    outer.extend_from_slice(&nested);
+                }
+                None => {
+                    outer.extend_from_slice(&nested);
+                    encode_key(frame.tag, WireType::EndGroup, &mut outer);
+                }
+            }
+            nested = outer;
+        }
+        nested
+    }
+
+    /// Returns the checks of the fields of the innermost message being received.
+    fn checks(&mut self) -> &mut FieldChecks {
+        match self.frames.last_mut() {
+            Some(frame) => &mut frame.checks,
+            None => &mut self.checks,
+        }
+    }
+
+    /// Adds the fields being received to the path of an error found in the innermost of them.
+    fn nested(&self, mut error: DecodeError) -> DecodeError {
+        for frame in self.frames.iter().rev() {
+            error.push(frame.field.message, frame.field.field);
+        }
+        error
+    }
+
+    /// Returns the number of bytes left in the innermost message field being received, or in the
+    /// message, if its end is known.
+    fn limit(&self) -> Option<usize> {
+        let position = self.offset + self.pending.len();
+        let frame = self
+            .frames
+            .iter()
+            .rev()
+            .find_map(|frame| frame.end)
+            .map(|end| end - position);
+        match (self.remaining, frame) {
+            (Some(remaining), Some(frame)) => Some(remaining.min(frame)),
+            (remaining, frame) => remaining.or(frame),
+        }
+    }
+
+    fn is_between_fields(&self) -> bool {
+        self.pending.is_empty() && self.state == State::Key { start: 0 }
+    }
+
+    /// Returns the decoded message, and resets the decoder for the next message.
+    fn take(&mut self) -> M {
+        self.budget = Budget::new(&self.options);
+        self.remaining = None;
+        self.pending.clear();
+        self.state = IncrementalDecoder::<M>::initial_state(self.length_delimited);
+        self.groups.clear();
+        self.frames.clear();
+        self.offset = 0;
+        self.checks =
+            FieldChecks::new::<M>(&DecodeContext::new(&self.options, self.budget.as_ref()));
+        mem::take(&mut self.message)
+    }
+
+    /// Checks that the required fields of the message are present, if the options check them.
+    fn check_required_fields(&self) -> Result<(), DecodeError> {
+        if self.options.check_required_fields {
+            required::check(&self.message, None)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Resets the decoder after a decode error.
+    fn fail(&mut self, mut error: DecodeError) -> DecodeError {
+        error.set_offset(self.offset + self.pending.len());
+        self.take();
+        error
+    }
+
+    fn initial_state(length_delimited: bool) -> State {
+        if length_delimited {
+            State::Delimiter
+        } else {
+            State::Key { start: 0 }
+        }
+    }
+}
+
+impl<M> Default for IncrementalDecoder<M>
+where
+    M: Message + Default,
+{
+    fn default() -> IncrementalDecoder<M> {
+        IncrementalDecoder::new()
+    }
+}
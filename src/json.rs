@@ -0,0 +1,1548 @@
+//! The canonical Protobuf JSON mapping.
+//!
+//! Messages are mapped to JSON objects, with fields named by their `json_name`, which defaults to
+//! the field name in lowerCamelCase:
+//!
+//! ```text
+//! {"name":"prost","version":{"major":0,"minor":12},"kind":"LIBRARY","downloads":"1000000"}
+//! ```
+//!
+//! Implementations of [`Json`] are generated by `prost-build` when enabled with
+//! `Config::enable_json`, and follow the [JSON mapping specification][1]:
+//!
+//!  * 64-bit integers are written as strings, since they can not be represented exactly by JSON
+//!    numbers, and other numbers are written as numbers. Both forms are accepted when parsing, as
+//!    are the special values `"NaN"`, `"Infinity"` and `"-Infinity"` for floating point numbers.
+//!  * `bytes` values are written in standard base64 with padding. Both the standard and the URL-safe
+//!    alphabets are accepted when parsing, with or without padding.
+//!  * Enumeration values are written by their names, falling back to the number of values which
+//!    are not known to the enumeration.
+//!  * Fields without presence are not written if they are set to the default value, unless enabled
+//!    with [`JsonOptions::emit_defaults`]. A `null` value parses as the default value of a field.
+//!  * The well-known types in `prost-types` have their special forms, such as RFC 3339 strings for
+//!    `Timestamp`. Writing and parsing `Any` messages requires a [`TypeRegistry`] containing the
+//!    type of the packed message.
+//!
+//! Extension fields and unknown fields are not written, since the JSON mapping has no
+//! representation for them.
+//!
+//! [1]: https://protobuf.dev/programming-guides/proto3/#json
+
+use alloc::borrow::Cow;
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+
+use bytes::Bytes;
+
+use crate::{Lazy, Message, Name};
+
+#[doc(hidden)]
+pub use crate::text_format::is_default;
+
+/// A message which can be written and parsed in the canonical Protobuf JSON mapping.
+pub trait Json: Message {
+    /// Writes the fields of the message as the members of a JSON object.
+    ///
+    /// Meant to be used only by `Json` implementations.
+    #[doc(hidden)]
+    fn write_json_fields(&self, writer: &mut Writer<'_>) -> Result<(), JsonError>;
+
+    /// Parses the value of the field with the given name, and merges it into `self`.
+    ///
+    /// Returns `false` if the message has no field with the name. Meant to be used only by `Json`
+    /// implementations.
+    #[doc(hidden)]
+    fn merge_json_field(&mut self, name: &str, parser: &mut Parser<'_>) -> Result<bool, JsonError>;
+
+    /// Writes the message as a JSON value, which is an object of its fields unless the message
+    /// has a special form.
+    ///
+    /// Meant to be used only by `Json` implementations.
+    #[doc(hidden)]
+    fn write_json_value(&self, writer: &mut Writer<'_>) -> Result<(), JsonError> {
+        writer.object(|writer| self.write_json_fields(writer))
+    }
+
+    /// Parses a JSON value, and merges it into `self`.
+    ///
+    /// Meant to be used only by `Json` implementations.
+    #[doc(hidden)]
+    fn merge_json_value(&mut self, parser: &mut Parser<'_>) -> Result<(), JsonError> {
+        parser.object(|parser, name| self.merge_json_field(name, parser))
+    }
+
+    /// Writes the message as JSON.
+    fn to_json(&self) -> Result<String, JsonError>
+    where
+        Self: Sized,
+    {
+        self.to_json_with_options(&JsonOptions::new())
+    }
+
+    /// Writes the message as JSON, using the given options.
+    fn to_json_with_options(&self, options: &JsonOptions<'_>) -> Result<String, JsonError>
+    where
+        Self: Sized,
+    {
+        let mut buf = String::new();
+        self.write_json_value(&mut Writer {
+            buf: &mut buf,
+            options,
+            first: true,
+        })?;
+        Ok(buf)
+    }
+
+    /// Parses a message from JSON, and merges it into `self`.
+    ///
+    /// Singular fields present in the JSON replace the values in `self`, and the values of repeated
+    /// and map fields are added to the values in `self`.
+    fn merge_json(&mut self, json: &str) -> Result<(), JsonError>
+    where
+        Self: Sized,
+    {
+        self.merge_json_with_options(json, &JsonOptions::new())
+    }
+
+    /// Parses a message from JSON, and merges it into `self`, using the given options.
+    fn merge_json_with_options(
+        &mut self,
+        json: &str,
+        options: &JsonOptions<'_>,
+    ) -> Result<(), JsonError>
+    where
+        Self: Sized,
+    {
+        let mut parser = Parser {
+            input: json,
+            pos: 0,
+            options,
+            recurse_count: crate::RECURSION_LIMIT,
+        };
+        self.merge_json_value(&mut parser)?;
+        match parser.peek() {
+            None => Ok(()),
+            Some(_) => Err(parser.error("unexpected trailing input")),
+        }
+    }
+
+    /// Parses a message from JSON.
+    fn parse_json(json: &str) -> Result<Self, JsonError>
+    where
+        Self: Default,
+    {
+        Self::parse_json_with_options(json, &JsonOptions::new())
+    }
+
+    /// Parses a message from JSON, using the given options.
+    fn parse_json_with_options(json: &str, options: &JsonOptions<'_>) -> Result<Self, JsonError>
+    where
+        Self: Default,
+    {
+        let mut message = Self::default();
+        message.merge_json_with_options(json, options)?;
+        Ok(message)
+    }
+}
+
+/// Options for writing and parsing JSON, used with [`Json::to_json_with_options`] and
+/// [`Json::merge_json_with_options`].
+#[derive(Clone, Debug, Default)]
+pub struct JsonOptions<'a> {
+    emit_defaults: bool,
+    proto_field_names: bool,
+    ignore_unknown_fields: bool,
+    type_registry: Option<&'a TypeRegistry>,
+}
+
+impl<'a> JsonOptions<'a> {
+    /// Creates the default JSON options.
+    pub fn new() -> JsonOptions<'a> {
+        JsonOptions::default()
+    }
+
+    /// Sets whether fields without presence are written when set to their default value, including
+    /// empty repeated and map fields.
+    ///
+    /// Defaults to `false`.
+    pub fn emit_defaults(mut self, emit_defaults: bool) -> JsonOptions<'a> {
+        self.emit_defaults = emit_defaults;
+        self
+    }
+
+    /// Sets whether fields are written by their names in the `.proto` definition, rather than by
+    /// their JSON names. Both names are always accepted when parsing.
+    ///
+    /// Defaults to `false`.
+    pub fn proto_field_names(mut self, proto_field_names: bool) -> JsonOptions<'a> {
+        self.proto_field_names = proto_field_names;
+        self
+    }
+
+    /// Sets whether fields which are not known to the message are skipped when parsing, rather than
+    /// causing an error.
+    ///
+    /// Defaults to `false`.
+    pub fn ignore_unknown_fields(mut self, ignore_unknown_fields: bool) -> JsonOptions<'a> {
+        self.ignore_unknown_fields = ignore_unknown_fields;
+        self
+    }
+
+    /// Sets the registry of the message types which can be packed in `Any` messages.
+    ///
+    /// Without a registry, writing or parsing a non-empty `Any` message fails.
+    pub fn type_registry(mut self, type_registry: &'a TypeRegistry) -> JsonOptions<'a> {
+        self.type_registry = Some(type_registry);
+        self
+    }
+}
+
+/// A registry of the message types which can be packed in `Any` messages, by their full names.
+///
+/// The type URL of a packed message is resolved by its final path segment, so the registry applies
+/// to type URLs of any domain.
+#[derive(Clone, Debug, Default)]
+pub struct TypeRegistry {
+    types: BTreeMap<String, AnyType>,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct AnyType {
+    write_fields: fn(&[u8], &mut Writer<'_>) -> Result<(), JsonError>,
+    merge_fields: fn(&mut Parser<'_>) -> Result<Vec<u8>, JsonError>,
+}
+
+impl TypeRegistry {
+    /// Creates an empty registry.
+    pub fn new() -> TypeRegistry {
+        TypeRegistry::default()
+    }
+
+    /// Adds a message type to the registry.
+    pub fn register<M>(&mut self) -> &mut TypeRegistry
+    where
+        M: Json + Name + Default,
+    {
+        self.register_full_name::<M>(M::full_name())
+    }
+
+    /// Adds a message type to the registry by the given full name, such as
+    /// `google.protobuf.Int32Value` for `i32`.
+    pub fn register_full_name<M>(&mut self, full_name: impl Into<String>) -> &mut TypeRegistry
+    where
+        M: Json + Default,
+    {
+        self.types.insert(
+            full_name.into(),
+            AnyType {
+                write_fields: write_any_fields::<M>,
+                merge_fields: merge_any_fields::<M>,
+            },
+        );
+        self
+    }
+
+    /// Returns `true` if the registry contains the message type of the given type URL.
+    pub fn contains(&self, type_url: &str) -> bool {
+        self.get(type_url).is_some()
+    }
+
+    fn get(&self, type_url: &str) -> Option<AnyType> {
+        let (_, full_name) = type_url.rsplit_once('/')?;
+        self.types.get(full_name).copied()
+    }
+}
+
+fn write_any_fields<M>(value: &[u8], writer: &mut Writer<'_>) -> Result<(), JsonError>
+where
+    M: Json + Default,
+{
+    let message = M::decode(value).map_err(|error| writer.error(error.to_string()))?;
+    message.write_json_fields(writer)
+}
+
+fn merge_any_fields<M>(parser: &mut Parser<'_>) -> Result<Vec<u8>, JsonError>
+where
+    M: Json + Default,
+{
+    let mut message = M::default();
+    parser.object(|parser, name| {
+        if name == "@type" {
+            parser.skip_value()?;
+            Ok(true)
+        } else {
+            message.merge_json_field(name, parser)
+        }
+    })?;
+    Ok(message.encode_to_vec())
+}
+
+/// A JSON error.
+///
+/// Errors which occur when parsing have the line and column of the input where they occurred,
+/// counted from 1, with columns counted in characters.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct JsonError {
+    description: Cow<'static, str>,
+    position: Option<(usize, usize)>,
+}
+
+impl JsonError {
+    /// Returns the line of the input where the error occurred, if it occurred when parsing.
+    pub fn line(&self) -> Option<usize> {
+        self.position.map(|(line, _)| line)
+    }
+
+    /// Returns the column of the input where the error occurred, if it occurred when parsing.
+    pub fn column(&self) -> Option<usize> {
+        self.position.map(|(_, column)| column)
+    }
+}
+
+impl fmt::Display for JsonError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.position {
+            Some((line, column)) => write!(
+                f,
+                "failed to parse JSON: {} at line {}, column {}",
+                self.description, line, column
+            ),
+            None => write!(f, "failed to write JSON: {}", self.description),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for JsonError {}
+
+/// Writes the fields of a message as JSON.
+///
+/// Meant to be used only by `Json` implementations.
+#[doc(hidden)]
+pub struct Writer<'a> {
+    buf: &'a mut String,
+    options: &'a JsonOptions<'a>,
+    /// Whether the next member is the first of the current object.
+    first: bool,
+}
+
+impl<'a> Writer<'a> {
+    /// Returns `true` if fields without presence are written when set to their default value.
+    pub fn emit_defaults(&self) -> bool {
+        self.options.emit_defaults
+    }
+
+    /// Writes a member of the current object, named by either the JSON name or the `.proto` name
+    /// of the field.
+    pub fn field(
+        &mut self,
+        json_name: &str,
+        proto_name: &str,
+        value: impl FnOnce(&mut Writer<'a>) -> Result<(), JsonError>,
+    ) -> Result<(), JsonError> {
+        if !self.first {
+            self.buf.push(',');
+        }
+        self.first = false;
+        let name = if self.options.proto_field_names {
+            proto_name
+        } else {
+            json_name
+        };
+        write_string(name, self.buf);
+        self.buf.push(':');
+        value(self)
+    }
+
+    /// Writes an object, with the members written by `fields`.
+    pub fn object(
+        &mut self,
+        fields: impl FnOnce(&mut Writer<'a>) -> Result<(), JsonError>,
+    ) -> Result<(), JsonError> {
+        self.buf.push('{');
+        let first = core::mem::replace(&mut self.first, true);
+        fields(self)?;
+        self.first = first;
+        self.buf.push('}');
+        Ok(())
+    }
+
+    /// Writes an array of values.
+    pub fn list<'v, T>(
+        &mut self,
+        values: impl IntoIterator<Item = &'v T>,
+        mut value: impl FnMut(&mut Writer<'a>, &'v T) -> Result<(), JsonError>,
+    ) -> Result<(), JsonError>
+    where
+        T: 'v,
+    {
+        self.buf.push('[');
+        for (index, item) in values.into_iter().enumerate() {
+            if index > 0 {
+                self.buf.push(',');
+            }
+            value(self, item)?;
+        }
+        self.buf.push(']');
+        Ok(())
+    }
+
+    /// Writes the entries of a map field as an object, with the keys as member names.
+    pub fn map<'v, K, V>(
+        &mut self,
+        entries: impl IntoIterator<Item = (&'v K, &'v V)>,
+        mut value: impl FnMut(&mut Writer<'a>, &'v V) -> Result<(), JsonError>,
+    ) -> Result<(), JsonError>
+    where
+        K: MapKey + 'v,
+        V: 'v,
+    {
+        self.buf.push('{');
+        for (index, (key, item)) in entries.into_iter().enumerate() {
+            if index > 0 {
+                self.buf.push(',');
+            }
+            write_string(&key.to_key(), self.buf);
+            self.buf.push(':');
+            value(self, item)?;
+        }
+        self.buf.push('}');
+        Ok(())
+    }
+
+    /// Writes a string, bytes, bool or numeric value.
+    pub fn scalar<T>(&mut self, value: &T) -> Result<(), JsonError>
+    where
+        T: Scalar,
+    {
+        value.write(self.buf);
+        Ok(())
+    }
+
+    /// Writes a string value.
+    pub fn string(&mut self, value: &str) -> Result<(), JsonError> {
+        write_string(value, self.buf);
+        Ok(())
+    }
+
+    /// Writes an enumeration value by its name, or by its number if the value is not known.
+    pub fn enumeration(
+        &mut self,
+        value: i32,
+        as_str_name: fn(i32) -> Option<&'static str>,
+    ) -> Result<(), JsonError> {
+        match as_str_name(value) {
+            Some(name) => write_string(name, self.buf),
+            None => self.buf.push_str(&value.to_string()),
+        }
+        Ok(())
+    }
+
+    /// Writes a `null` value, which is the only value of `google.protobuf.NullValue`.
+    pub fn null(&mut self) -> Result<(), JsonError> {
+        self.buf.push_str("null");
+        Ok(())
+    }
+
+    /// Writes a message value.
+    pub fn message<M>(&mut self, message: &M) -> Result<(), JsonError>
+    where
+        M: Json,
+    {
+        message.write_json_value(self)
+    }
+
+    /// Writes a lazily decoded message value.
+    pub fn lazy<M>(&mut self, lazy: &Lazy<M>) -> Result<(), JsonError>
+    where
+        M: Json + Default,
+    {
+        let message = lazy.get().map_err(|error| self.error(error.to_string()))?;
+        message.write_json_value(self)
+    }
+
+    /// Writes the message packed in an `Any` message, which must be contained in the type registry.
+    pub fn any(&mut self, type_url: &str, value: &[u8]) -> Result<(), JsonError> {
+        if type_url.is_empty() && value.is_empty() {
+            return self.object(|_| Ok(()));
+        }
+        let any_type = self
+            .options
+            .type_registry
+            .and_then(|registry| registry.get(type_url))
+            .ok_or_else(|| self.error(format!("type URL '{}' is not registered", type_url)))?;
+        self.object(|writer| {
+            writer.field("@type", "@type", |writer| writer.string(type_url))?;
+            (any_type.write_fields)(value, writer)
+        })
+    }
+
+    /// Returns an error for a value which can not be written.
+    pub fn error(&self, description: impl Into<Cow<'static, str>>) -> JsonError {
+        JsonError {
+            description: description.into(),
+            position: None,
+        }
+    }
+}
+
+/// The kind of a JSON value.
+///
+/// Meant to be used only by `Json` implementations.
+#[doc(hidden)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ValueKind {
+    Null,
+    Bool,
+    Number,
+    String,
+    Object,
+    Array,
+}
+
+/// Parses the fields of a message from JSON.
+///
+/// Meant to be used only by `Json` implementations.
+#[doc(hidden)]
+pub struct Parser<'a> {
+    input: &'a str,
+    pos: usize,
+    options: &'a JsonOptions<'a>,
+    /// The remaining number of nested objects and arrays which may be parsed.
+    recurse_count: u32,
+}
+
+impl<'a> Parser<'a> {
+    /// Parses the value of a field without presence. A `null` value sets the field to its default
+    /// value.
+    pub fn field<T>(
+        &mut self,
+        target: &mut T,
+        value: impl FnOnce(&mut Parser<'a>) -> Result<T, JsonError>,
+    ) -> Result<(), JsonError>
+    where
+        T: Default,
+    {
+        *target = if self.null() {
+            T::default()
+        } else {
+            value(self)?
+        };
+        Ok(())
+    }
+
+    /// Parses the value of a field with presence. A `null` value clears the field.
+    pub fn optional<T>(
+        &mut self,
+        target: &mut Option<T>,
+        value: impl FnOnce(&mut Parser<'a>) -> Result<T, JsonError>,
+    ) -> Result<(), JsonError> {
+        *target = if self.null() {
+            None
+        } else {
+            Some(value(self)?)
+        };
+        Ok(())
+    }
+
+    /// Parses the array of values of a repeated field, and adds them to the field. A `null` value
+    /// adds no values.
+    pub fn repeated<T>(
+        &mut self,
+        target: &mut Vec<T>,
+        mut value: impl FnMut(&mut Parser<'a>) -> Result<T, JsonError>,
+    ) -> Result<(), JsonError> {
+        if self.null() {
+            return Ok(());
+        }
+        self.array(|parser| {
+            target.push(value(parser)?);
+            Ok(())
+        })
+    }
+
+    /// Parses the object of entries of a map field, calling `entry` with the key of each. A `null`
+    /// value has no entries.
+    pub fn map<K>(
+        &mut self,
+        mut entry: impl FnMut(&mut Parser<'a>, K) -> Result<(), JsonError>,
+    ) -> Result<(), JsonError>
+    where
+        K: MapKey,
+    {
+        if self.null() {
+            return Ok(());
+        }
+        self.members(|parser, name| {
+            let key = K::from_key(name)
+                .ok_or_else(|| parser.error(format!("invalid map key '{}'", name)))?;
+            entry(parser, key)?;
+            Ok(true)
+        })
+    }
+
+    /// Parses the value of a member of a oneof, returning `None` if the value is `null`, unless
+    /// `null` is a value of the field.
+    ///
+    /// At most one member of a oneof may be set, so it is an error if `is_set` is `true`.
+    pub fn oneof<T>(
+        &mut self,
+        is_set: bool,
+        nullable: bool,
+        value: impl FnOnce(&mut Parser<'a>) -> Result<T, JsonError>,
+    ) -> Result<Option<T>, JsonError> {
+        if !nullable && self.null() {
+            return Ok(None);
+        }
+        if is_set {
+            return Err(self.error("multiple values for a oneof"));
+        }
+        value(self).map(Some)
+    }
+
+    /// Parses a string, bytes, bool or numeric value.
+    pub fn scalar<T>(&mut self) -> Result<T, JsonError>
+    where
+        T: Scalar,
+    {
+        T::parse(self)
+    }
+
+    /// Parses an enumeration value, given by its name or number.
+    pub fn enumeration(
+        &mut self,
+        from_str_name: fn(&str) -> Option<i32>,
+    ) -> Result<i32, JsonError> {
+        self.skip_whitespace();
+        let start = self.pos;
+        if self.peek() != Some(b'"') {
+            return i32::parse(self);
+        }
+        let name = self.string()?;
+        from_str_name(&name)
+            .ok_or_else(|| self.error_at(start, format!("unknown enumeration value '{}'", name)))
+    }
+
+    /// Parses a `google.protobuf.NullValue` value, which is given by `null`.
+    pub fn null_value(&mut self) -> Result<i32, JsonError> {
+        if self.null() {
+            Ok(0)
+        } else {
+            self.enumeration(|name| (name == "NULL_VALUE").then_some(0))
+        }
+    }
+
+    /// Parses a message value.
+    pub fn message<M>(&mut self) -> Result<M, JsonError>
+    where
+        M: Json + Default,
+    {
+        let mut message = M::default();
+        message.merge_json_value(self)?;
+        Ok(message)
+    }
+
+    /// Parses a lazily decoded message value.
+    pub fn lazy<M>(&mut self) -> Result<Lazy<M>, JsonError>
+    where
+        M: Json + Default,
+    {
+        self.message().map(Lazy::new)
+    }
+
+    /// Parses an object, calling `merge_field` with the name of each member.
+    ///
+    /// `merge_field` returns `false` if the name is not known, in which case the member is skipped
+    /// if unknown fields are ignored, or is an error otherwise.
+    pub fn object(
+        &mut self,
+        mut merge_field: impl FnMut(&mut Parser<'a>, &str) -> Result<bool, JsonError>,
+    ) -> Result<(), JsonError> {
+        self.members(|parser, name| {
+            if merge_field(parser, name)? {
+                Ok(true)
+            } else if parser.options.ignore_unknown_fields {
+                parser.skip_value()?;
+                Ok(true)
+            } else {
+                Ok(false)
+            }
+        })
+    }
+
+    /// Parses an array, calling `value` for each element.
+    pub fn array(
+        &mut self,
+        mut value: impl FnMut(&mut Parser<'a>) -> Result<(), JsonError>,
+    ) -> Result<(), JsonError> {
+        self.expect(b'[')?;
+        self.enter()?;
+        if !self.try_consume(b']') {
+            loop {
+                value(self)?;
+                if self.try_consume(b']') {
+                    break;
+                }
+                self.expect(b',')?;
+            }
+        }
+        self.recurse_count += 1;
+        Ok(())
+    }
+
+    /// Parses the message packed in an `Any` message, which must be contained in the type
+    /// registry, returning its type URL and encoded value.
+    pub fn any(&mut self) -> Result<(String, Vec<u8>), JsonError> {
+        self.skip_whitespace();
+        let start = self.pos;
+
+        // The type URL may be given by any member of the object, so the object is first skipped
+        // over to find it.
+        let mut type_url = None;
+        let mut empty = true;
+        self.members(|parser, name| {
+            empty = false;
+            if name == "@type" {
+                type_url = Some(parser.string()?.into_owned());
+            } else {
+                parser.skip_value()?;
+            }
+            Ok(true)
+        })?;
+        let end = self.pos;
+        let type_url = match type_url {
+            Some(type_url) => type_url,
+            None if empty => return Ok((String::new(), Vec::new())),
+            None => return Err(self.error_at(start, "missing '@type' member of Any")),
+        };
+        let any_type = self
+            .options
+            .type_registry
+            .and_then(|registry| registry.get(&type_url))
+            .ok_or_else(|| {
+                self.error_at(start, format!("type URL '{}' is not registered", type_url))
+            })?;
+
+        self.pos = start;
+        let value = (any_type.merge_fields)(self)?;
+        debug_assert_eq!(self.pos, end);
+        Ok((type_url, value))
+    }
+
+    /// Returns the kind of the next value.
+    pub fn peek_kind(&mut self) -> Result<ValueKind, JsonError> {
+        match self.peek() {
+            Some(b'n') => Ok(ValueKind::Null),
+            Some(b't' | b'f') => Ok(ValueKind::Bool),
+            Some(b'-' | b'0'..=b'9') => Ok(ValueKind::Number),
+            Some(b'"') => Ok(ValueKind::String),
+            Some(b'{') => Ok(ValueKind::Object),
+            Some(b'[') => Ok(ValueKind::Array),
+            _ => Err(self.error("expected a value")),
+        }
+    }
+
+    /// Consumes a `null` value, if the next value is one.
+    pub fn null(&mut self) -> bool {
+        self.literal("null")
+    }
+
+    /// Skips a value of any kind.
+    pub fn skip_value(&mut self) -> Result<(), JsonError> {
+        match self.peek_kind()? {
+            ValueKind::Null => {
+                self.null();
+            }
+            ValueKind::Bool => {
+                bool::parse(self)?;
+            }
+            ValueKind::Number => {
+                let number = self.number()?;
+                if !is_number(&number) {
+                    return Err(self.error(format!("invalid number '{}'", number)));
+                }
+            }
+            ValueKind::String => {
+                self.string()?;
+            }
+            ValueKind::Object => self.members(|parser, _| {
+                parser.skip_value()?;
+                Ok(true)
+            })?,
+            ValueKind::Array => self.array(|parser| parser.skip_value())?,
+        }
+        Ok(())
+    }
+
+    /// Returns an error at the current position of the input.
+    pub fn error(&self, description: impl Into<Cow<'static, str>>) -> JsonError {
+        self.error_at(self.pos, description)
+    }
+
+    /// Parses the members of an object, calling `member` with the name of each.
+    fn members(
+        &mut self,
+        mut member: impl FnMut(&mut Parser<'a>, &str) -> Result<bool, JsonError>,
+    ) -> Result<(), JsonError> {
+        self.expect(b'{')?;
+        self.enter()?;
+        if !self.try_consume(b'}') {
+            loop {
+                self.skip_whitespace();
+                let start = self.pos;
+                let name = self.string()?;
+                self.expect(b':')?;
+                if !member(self, &name)? {
+                    return Err(self.error_at(start, format!("unknown field '{}'", name)));
+                }
+                if self.try_consume(b'}') {
+                    break;
+                }
+                self.expect(b',')?;
+            }
+        }
+        self.recurse_count += 1;
+        Ok(())
+    }
+
+    fn enter(&mut self) -> Result<(), JsonError> {
+        if self.recurse_count == 0 {
+            return Err(self.error("recursion limit reached"));
+        }
+        self.recurse_count -= 1;
+        Ok(())
+    }
+
+    /// Skips whitespace, and returns the next byte of the input.
+    fn peek(&mut self) -> Option<u8> {
+        self.skip_whitespace();
+        self.input.as_bytes().get(self.pos).copied()
+    }
+
+    fn skip_whitespace(&mut self) {
+        let bytes = self.input.as_bytes();
+        while matches!(bytes.get(self.pos), Some(b' ' | b'\t' | b'\n' | b'\r')) {
+            self.pos += 1;
+        }
+    }
+
+    fn try_consume(&mut self, byte: u8) -> bool {
+        if self.peek() == Some(byte) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, byte: u8) -> Result<(), JsonError> {
+        if self.try_consume(byte) {
+            Ok(())
+        } else {
+            Err(self.error(format!("expected '{}'", byte as char)))
+        }
+    }
+
+    /// Consumes a literal such as `true`, if the next token is the literal.
+    fn literal(&mut self, literal: &str) -> bool {
+        self.skip_whitespace();
+        let rest = &self.input.as_bytes()[self.pos..];
+        if rest.starts_with(literal.as_bytes())
+            && !rest
+                .get(literal.len())
+                .is_some_and(|byte| byte.is_ascii_alphanumeric())
+        {
+            self.pos += literal.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Parses a number, which is either a JSON number or a string containing one, and returns its
+    /// text.
+    fn number(&mut self) -> Result<Cow<'a, str>, JsonError> {
+        if self.peek() == Some(b'"') {
+            return self.string();
+        }
+        let start = self.pos;
+        let bytes = self.input.as_bytes();
+        while bytes
+            .get(self.pos)
+            .is_some_and(|&byte| byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'+' | b'.'))
+        {
+            self.pos += 1;
+        }
+        if self.pos == start {
+            return Err(self.error("expected a number"));
+        }
+        Ok(Cow::Borrowed(&self.input[start..self.pos]))
+    }
+
+    /// Parses an integer, checking that it is within the range of `T`.
+    fn integer<T>(&mut self) -> Result<T, JsonError>
+    where
+        T: TryFrom<i128>,
+    {
+        self.skip_whitespace();
+        let start = self.pos;
+        let number = self.number()?;
+        parse_integer(&number)
+            .and_then(|value| T::try_from(value).ok())
+            .ok_or_else(|| self.error_at(start, format!("invalid integer '{}'", number)))
+    }
+
+    /// Parses a floating point number, or one of the strings `NaN`, `Infinity` and `-Infinity`.
+    fn float<T>(&mut self) -> Result<T, JsonError>
+    where
+        T: core::str::FromStr + Float,
+    {
+        self.skip_whitespace();
+        let start = self.pos;
+        let quoted = self.peek() == Some(b'"');
+        let number = self.number()?;
+        let value = match &*number {
+            "NaN" if quoted => Some(T::NAN),
+            "Infinity" if quoted => Some(T::INFINITY),
+            "-Infinity" if quoted => Some(T::NEG_INFINITY),
+            number if is_number(number) => {
+                number.parse::<T>().ok().filter(|value| value.is_finite())
+            }
+            _ => None,
+        };
+        value.ok_or_else(|| self.error_at(start, format!("invalid number '{}'", number)))
+    }
+
+    /// Parses a string, unescaping it if necessary.
+    fn string(&mut self) -> Result<Cow<'a, str>, JsonError> {
+        self.expect(b'"')?;
+        let bytes = self.input.as_bytes();
+        let start = self.pos;
+        let mut value: Option<String> = None;
+        loop {
+            let byte = match bytes.get(self.pos) {
+                Some(&byte) => byte,
+                None => return Err(self.error("unterminated string")),
+            };
+            match byte {
+                b'"' => {
+                    let value = match value {
+                        Some(value) => Cow::Owned(value),
+                        None => Cow::Borrowed(&self.input[start..self.pos]),
+                    };
+                    self.pos += 1;
+                    return Ok(value);
+                }
+                b'\\' => {
+                    let value =
+                        value.get_or_insert_with(|| self.input[start..self.pos].to_string());
+                    let escape_start = self.pos;
+                    self.pos += 1;
+                    let escape = bytes.get(self.pos).copied().unwrap_or_default();
+                    self.pos += 1;
+                    match escape {
+                        b'"' => value.push('"'),
+                        b'\\' => value.push('\\'),
+                        b'/' => value.push('/'),
+                        b'b' => value.push('\x08'),
+                        b'f' => value.push('\x0c'),
+                        b'n' => value.push('\n'),
+                        b'r' => value.push('\r'),
+                        b't' => value.push('\t'),
+                        b'u' => {
+                            let c = self.unicode_escape().ok_or_else(|| {
+                                self.error_at(escape_start, "invalid unicode escape")
+                            })?;
+                            value.push(c);
+                        }
+                        _ => return Err(self.error_at(escape_start, "invalid escape sequence")),
+                    }
+                }
+                0..=0x1f => return Err(self.error("control character in string")),
+                _ => {
+                    // Copy the whole character, which is valid UTF-8 since the input is a `str`.
+                    let len = self.input[self.pos..]
+                        .chars()
+                        .next()
+                        .map_or(1, char::len_utf8);
+                    if let Some(value) = &mut value {
+                        value.push_str(&self.input[self.pos..self.pos + len]);
+                    }
+                    self.pos += len;
+                }
+            }
+        }
+    }
+
+    /// Parses the digits of a `\u` escape, combining surrogate pairs.
+    fn unicode_escape(&mut self) -> Option<char> {
+        let high = self.hex4()?;
+        if !(0xd800..0xdc00).contains(&high) {
+            return char::from_u32(high);
+        }
+        let bytes = self.input.as_bytes();
+        if bytes.get(self.pos) != Some(&b'\\') || bytes.get(self.pos + 1) != Some(&b'u') {
+            return None;
+        }
+        self.pos += 2;
+        let low = self.hex4()?;
+        if !(0xdc00..0xe000).contains(&low) {
+            return None;
+        }
+        char::from_u32(0x10000 + ((high - 0xd800) << 10) + (low - 0xdc00))
+    }
+
+    fn hex4(&mut self) -> Option<u32> {
+        let digits = self.input.get(self.pos..self.pos + 4)?;
+        if !digits.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+            return None;
+        }
+        self.pos += 4;
+        u32::from_str_radix(digits, 16).ok()
+    }
+
+    #[cold]
+    fn error_at(&self, pos: usize, description: impl Into<Cow<'static, str>>) -> JsonError {
+        let before = &self.input[..pos];
+        let line_start = before.rfind('\n').map_or(0, |index| index + 1);
+        JsonError {
+            description: description.into(),
+            position: Some((
+                before.matches('\n').count() + 1,
+                before[line_start..].chars().count() + 1,
+            )),
+        }
+    }
+}
+
+/// Returns `true` if the text is a number in the JSON grammar.
+fn is_number(text: &str) -> bool {
+    let mut bytes = text.as_bytes();
+    let digits = |bytes: &mut &[u8]| {
+        let count = bytes
+            .iter()
+            .take_while(|byte| byte.is_ascii_digit())
+            .count();
+        *bytes = &bytes[count..];
+        count
+    };
+    if let Some(rest) = bytes.strip_prefix(b"-") {
+        bytes = rest;
+    }
+    match digits(&mut bytes) {
+        0 => return false,
+        1 => (),
+        _ if text.trim_start_matches('-').starts_with('0') => return false,
+        _ => (),
+    }
+    if let Some(rest) = bytes.strip_prefix(b".") {
+        bytes = rest;
+        if digits(&mut bytes) == 0 {
+            return false;
+        }
+    }
+    if let Some(rest) = bytes
+        .strip_prefix(b"e")
+        .or_else(|| bytes.strip_prefix(b"E"))
+    {
+        bytes = rest
+            .strip_prefix(b"+")
+            .or_else(|| rest.strip_prefix(b"-"))
+            .unwrap_or(rest);
+        if digits(&mut bytes) == 0 {
+            return false;
+        }
+    }
+    bytes.is_empty()
+}
+
+/// Parses an integer, which may be given in exponent or fractional notation if its value is
+/// integral.
+fn parse_integer(text: &str) -> Option<i128> {
+    if !is_number(text) {
+        return None;
+    }
+    if let Ok(value) = text.parse::<i128>() {
+        return Some(value);
+    }
+    let value = text.parse::<f64>().ok()?;
+    let max = u64::MAX as f64;
+    if !(-max..=max).contains(&value) {
+        return None;
+    }
+    // The conversion truncates, so the value is integral only if it converts back exactly.
+    let integer = value as i128;
+    (integer as f64 == value).then_some(integer)
+}
+
+/// Appends a string in quotes, escaping quotes, backslashes and control characters.
+fn write_string(value: &str, buf: &mut String) {
+    buf.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => buf.push_str("\\\""),
+            '\\' => buf.push_str("\\\\"),
+            '\n' => buf.push_str("\\n"),
+            '\r' => buf.push_str("\\r"),
+            '\t' => buf.push_str("\\t"),
+            '\x08' => buf.push_str("\\b"),
+            '\x0c' => buf.push_str("\\f"),
+            '\0'..='\x1f' => buf.push_str(&format!("\\u{:04x}", c as u32)),
+            c => buf.push(c),
+        }
+    }
+    buf.push('"');
+}
+
+const BASE64: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Appends bytes in quotes, encoded in standard base64 with padding.
+fn write_base64(value: &[u8], buf: &mut String) {
+    buf.push('"');
+    for chunk in value.chunks(3) {
+        let bits = chunk.iter().enumerate().fold(0u32, |bits, (index, &byte)| {
+            bits | (u32::from(byte) << (16 - 8 * index))
+        });
+        for index in 0..4 {
+            if index <= chunk.len() {
+                buf.push(BASE64[((bits >> (18 - 6 * index)) & 0x3f) as usize] as char);
+            } else {
+                buf.push('=');
+            }
+        }
+    }
+    buf.push('"');
+}
+
+/// Decodes base64 in either the standard or the URL-safe alphabet, with or without padding.
+fn decode_base64(text: &str) -> Option<Vec<u8>> {
+    let text = text.as_bytes();
+    let unpadded = match text {
+        [rest @ .., b'=', b'='] | [rest @ .., b'='] if text.len() % 4 == 0 => rest,
+        _ => text,
+    };
+    if unpadded.len() % 4 == 1 {
+        return None;
+    }
+    let mut value = Vec::with_capacity(unpadded.len() * 3 / 4);
+    for chunk in unpadded.chunks(4) {
+        let mut bits = 0u32;
+        for (index, &byte) in chunk.iter().enumerate() {
+            let digit = match byte {
+                b'A'..=b'Z' => byte - b'A',
+                b'a'..=b'z' => byte - b'a' + 26,
+                b'0'..=b'9' => byte - b'0' + 52,
+                b'+' | b'-' => 62,
+                b'/' | b'_' => 63,
+                _ => return None,
+            };
+            bits |= u32::from(digit) << (18 - 6 * index);
+        }
+        for index in 0..chunk.len() - 1 {
+            value.push((bits >> (16 - 8 * index)) as u8);
+        }
+    }
+    Some(value)
+}
+
+/// A floating point type.
+trait Float: Copy {
+    const NAN: Self;
+    const INFINITY: Self;
+    const NEG_INFINITY: Self;
+
+    fn is_finite(self) -> bool;
+}
+
+macro_rules! float {
+    ($ty:ident) => {
+        impl Float for $ty {
+            const NAN: $ty = $ty::NAN;
+            const INFINITY: $ty = $ty::INFINITY;
+            const NEG_INFINITY: $ty = $ty::NEG_INFINITY;
+
+            fn is_finite(self) -> bool {
+                $ty::is_finite(self)
+            }
+        }
+    };
+}
+
+float!(f32);
+float!(f64);
+
+/// A value of a string, bytes, bool or numeric field.
+///
+/// Meant to be used only by `Json` implementations.
+#[doc(hidden)]
+pub trait Scalar: Sized {
+    /// Appends the value to the buffer.
+    fn write(&self, buf: &mut String);
+
+    /// Parses a value.
+    fn parse(parser: &mut Parser<'_>) -> Result<Self, JsonError>;
+}
+
+impl Scalar for bool {
+    fn write(&self, buf: &mut String) {
+        buf.push_str(if *self { "true" } else { "false" });
+    }
+
+    fn parse(parser: &mut Parser<'_>) -> Result<bool, JsonError> {
+        if parser.literal("true") {
+            Ok(true)
+        } else if parser.literal("false") {
+            Ok(false)
+        } else {
+            Err(parser.error("expected a bool"))
+        }
+    }
+}
+
+macro_rules! integer_scalar {
+    ($ty:ty, $quoted:expr) => {
+        impl Scalar for $ty {
+            fn write(&self, buf: &mut String) {
+                if $quoted {
+                    buf.push('"');
+                    buf.push_str(&self.to_string());
+                    buf.push('"');
+                } else {
+                    buf.push_str(&self.to_string());
+                }
+            }
+
+            fn parse(parser: &mut Parser<'_>) -> Result<$ty, JsonError> {
+                parser.integer()
+            }
+        }
+    };
+}
+
+integer_scalar!(i32, false);
+integer_scalar!(u32, false);
+integer_scalar!(i64, true);
+integer_scalar!(u64, true);
+
+macro_rules! float_scalar {
+    ($ty:ty) => {
+        impl Scalar for $ty {
+            fn write(&self, buf: &mut String) {
+                if self.is_nan() {
+                    buf.push_str("\"NaN\"");
+                } else if self.is_infinite() {
+                    buf.push_str(if *self > 0.0 {
+                        "\"Infinity\""
+                    } else {
+                        "\"-Infinity\""
+                    });
+                } else {
+                    buf.push_str(&format!("{:?}", self));
+                }
+            }
+
+            fn parse(parser: &mut Parser<'_>) -> Result<$ty, JsonError> {
+                parser.float()
+            }
+        }
+    };
+}
+
+float_scalar!(f32);
+float_scalar!(f64);
+
+impl Scalar for String {
+    fn write(&self, buf: &mut String) {
+        write_string(self, buf);
+    }
+
+    fn parse(parser: &mut Parser<'_>) -> Result<String, JsonError> {
+        parser.string().map(Cow::into_owned)
+    }
+}
+
+impl Scalar for Vec<u8> {
+    fn write(&self, buf: &mut String) {
+        write_base64(self, buf);
+    }
+
+    fn parse(parser: &mut Parser<'_>) -> Result<Vec<u8>, JsonError> {
+        parser.skip_whitespace();
+        let start = parser.pos;
+        let text = parser.string()?;
+        decode_base64(&text).ok_or_else(|| parser.error_at(start, "invalid base64"))
+    }
+}
+
+impl Scalar for Bytes {
+    fn write(&self, buf: &mut String) {
+        write_base64(self, buf);
+    }
+
+    fn parse(parser: &mut Parser<'_>) -> Result<Bytes, JsonError> {
+        Vec::<u8>::parse(parser).map(Bytes::from)
+    }
+}
+
+/// A key of a map field, which is written as the name of an object member.
+///
+/// Meant to be used only by `Json` implementations.
+#[doc(hidden)]
+pub trait MapKey: Sized {
+    /// Returns the key as a member name.
+    fn to_key(&self) -> Cow<'_, str>;
+
+    /// Parses a key from a member name.
+    fn from_key(key: &str) -> Option<Self>;
+}
+
+impl MapKey for String {
+    fn to_key(&self) -> Cow<'_, str> {
+        Cow::Borrowed(self)
+    }
+
+    fn from_key(key: &str) -> Option<String> {
+        Some(key.to_string())
+    }
+}
+
+impl MapKey for bool {
+    fn to_key(&self) -> Cow<'_, str> {
+        Cow::Borrowed(if *self { "true" } else { "false" })
+    }
+
+    fn from_key(key: &str) -> Option<bool> {
+        match key {
+            "true" => Some(true),
+            "false" => Some(false),
+            _ => None,
+        }
+    }
+}
+
+macro_rules! integer_map_key {
+    ($ty:ty) => {
+        impl MapKey for $ty {
+            fn to_key(&self) -> Cow<'_, str> {
+                Cow::Owned(self.to_string())
+            }
+
+            fn from_key(key: &str) -> Option<$ty> {
+                parse_integer(key).and_then(|value| <$ty>::try_from(value).ok())
+            }
+        }
+    };
+}
+
+integer_map_key!(i32);
+integer_map_key!(u32);
+integer_map_key!(i64);
+integer_map_key!(u64);
+
+macro_rules! wrapper {
+    ($ty:ty) => {
+        impl Json for $ty {
+            fn write_json_fields(&self, writer: &mut Writer<'_>) -> Result<(), JsonError> {
+                writer.field("value", "value", |writer| writer.scalar(self))
+            }
+
+            fn merge_json_field(
+                &mut self,
+                name: &str,
+                parser: &mut Parser<'_>,
+            ) -> Result<bool, JsonError> {
+                if name != "value" {
+                    return Ok(false);
+                }
+                parser.field(self, |parser| parser.scalar())?;
+                Ok(true)
+            }
+
+            fn write_json_value(&self, writer: &mut Writer<'_>) -> Result<(), JsonError> {
+                writer.scalar(self)
+            }
+
+            fn merge_json_value(&mut self, parser: &mut Parser<'_>) -> Result<(), JsonError> {
+                *self = parser.scalar()?;
+                Ok(())
+            }
+        }
+    };
+}
+
+wrapper!(bool);
+wrapper!(u32);
+wrapper!(u64);
+wrapper!(i32);
+wrapper!(i64);
+wrapper!(f32);
+wrapper!(f64);
+wrapper!(String);
+wrapper!(Vec<u8>);
+wrapper!(Bytes);
+
+/// `google.protobuf.Empty`
+impl Json for () {
+    fn write_json_fields(&self, _writer: &mut Writer<'_>) -> Result<(), JsonError> {
+        Ok(())
+    }
+
+    fn merge_json_field(
+        &mut self,
+        _name: &str,
+        _parser: &mut Parser<'_>,
+    ) -> Result<bool, JsonError> {
+        Ok(false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use alloc::vec;
+
+    fn parse<T: Scalar>(json: &str) -> Result<T, JsonError> {
+        let options = JsonOptions::new();
+        let mut parser = Parser {
+            input: json,
+            pos: 0,
+            options: &options,
+            recurse_count: crate::RECURSION_LIMIT,
+        };
+        let value = T::parse(&mut parser)?;
+        match parser.peek() {
+            None => Ok(value),
+            Some(_) => Err(parser.error("trailing input")),
+        }
+    }
+
+    fn write<T: Scalar>(value: T) -> String {
+        let mut buf = String::new();
+        value.write(&mut buf);
+        buf
+    }
+
+    #[test]
+    fn integers() {
+        assert_eq!(parse::<i32>("42"), Ok(42));
+        assert_eq!(parse::<i32>("-42"), Ok(-42));
+        assert_eq!(parse::<i32>("\"42\""), Ok(42));
+        assert_eq!(parse::<i32>("1e5"), Ok(100_000));
+        assert_eq!(parse::<i32>("100000.000"), Ok(100_000));
+        assert_eq!(parse::<i64>("9223372036854775807"), Ok(i64::MAX));
+        assert_eq!(parse::<i64>("\"-9223372036854775808\""), Ok(i64::MIN));
+        assert_eq!(parse::<u64>("18446744073709551615"), Ok(u64::MAX));
+        assert!(parse::<i32>("2147483648").is_err());
+        assert!(parse::<u32>("-1").is_err());
+        assert!(parse::<i32>("0.5").is_err());
+        assert!(parse::<i32>("01").is_err());
+        assert!(parse::<i32>("+1").is_err());
+        assert!(parse::<i32>("\" 1\"").is_err());
+        assert!(parse::<i32>("0x10").is_err());
+
+        assert_eq!(write(-1i32), "-1");
+        assert_eq!(write(u32::MAX), "4294967295");
+        assert_eq!(write(i64::MIN), "\"-9223372036854775808\"");
+        assert_eq!(write(u64::MAX), "\"18446744073709551615\"");
+    }
+
+    #[test]
+    fn floats() {
+        assert_eq!(parse::<f64>("1.5"), Ok(1.5));
+        assert_eq!(parse::<f64>("-2e-3"), Ok(-0.002));
+        assert_eq!(parse::<f64>("\"1.5\""), Ok(1.5));
+        assert_eq!(parse::<f64>("\"Infinity\""), Ok(f64::INFINITY));
+        assert_eq!(parse::<f32>("\"-Infinity\""), Ok(f32::NEG_INFINITY));
+        assert!(parse::<f64>("\"NaN\"").unwrap().is_nan());
+        assert!(parse::<f64>("NaN").is_err());
+        assert!(parse::<f64>("\"nan\"").is_err());
+        assert!(parse::<f64>("1e400").is_err());
+        assert!(parse::<f32>("3.502823e+38").is_err());
+        assert!(parse::<f64>(".5").is_err());
+
+        assert_eq!(write(1.5f64), "1.5");
+        assert_eq!(write(0.1f32), "0.1");
+        assert_eq!(write(1e300f64), "1e300");
+        assert_eq!(write(f64::NAN), "\"NaN\"");
+        assert_eq!(write(f32::NEG_INFINITY), "\"-Infinity\"");
+    }
+
+    #[test]
+    fn strings() {
+        assert_eq!(parse::<String>(r#""a\"\\\/\né😀""#).unwrap(), "a\"\\/\né😀");
+        assert!(parse::<String>(r#""\ud83d""#).is_err());
+        assert!(parse::<String>(r#""\x41""#).is_err());
+        assert!(parse::<String>("\"a\nb\"").is_err());
+        assert!(parse::<String>("\"abc").is_err());
+
+        assert_eq!(write("a\"\\\n\x01é".to_string()), r#""a\"\\\n\u0001é""#);
+    }
+
+    #[test]
+    fn base64() {
+        assert_eq!(write(b"".to_vec()), "\"\"");
+        assert_eq!(write(b"f".to_vec()), "\"Zg==\"");
+        assert_eq!(write(b"fo".to_vec()), "\"Zm8=\"");
+        assert_eq!(write(b"foo".to_vec()), "\"Zm9v\"");
+        assert_eq!(write(vec![0xfb, 0xff]), "\"+/8=\"");
+
+        assert_eq!(parse::<Vec<u8>>("\"Zm9vYg==\""), Ok(b"foob".to_vec()));
+        assert_eq!(parse::<Vec<u8>>("\"Zm9vYg\""), Ok(b"foob".to_vec()));
+        assert_eq!(parse::<Vec<u8>>("\"-_8\""), Ok(vec![0xfb, 0xff]));
+        assert!(parse::<Vec<u8>>("\"Zm9vY\"").is_err());
+        assert!(parse::<Vec<u8>>("\"Zm9v!\"").is_err());
+    }
+
+    #[test]
+    fn wrappers() {
+        assert_eq!(5i64.to_json().unwrap(), "\"5\"");
+        assert_eq!(i64::parse_json(" \"5\" ").unwrap(), 5);
+        assert_eq!(().to_json().unwrap(), "{}");
+        assert!(<()>::parse_json("{\"a\": 1}").is_err());
+        assert!(<()>::parse_json_with_options(
+            "{\"a\": [1, {\"b\": null}]}",
+            &JsonOptions::new().ignore_unknown_fields(true)
+        )
+        .is_ok());
+
+        let error = bool::parse_json("\n  tru").unwrap_err();
+        assert_eq!((error.line(), error.column()), (Some(2), Some(3)));
+    }
+
+    #[test]
+    fn any() {
+        let mut registry = TypeRegistry::new();
+        registry.register_full_name::<i32>("google.protobuf.Int32Value");
+        let options = JsonOptions::new().type_registry(&registry);
+
+        let mut buf = String::new();
+        let mut writer = Writer {
+            buf: &mut buf,
+            options: &options,
+            first: true,
+        };
+        let value = 7i32.encode_to_vec();
+        writer
+            .any("type.googleapis.com/google.protobuf.Int32Value", &value)
+            .unwrap();
+        assert_eq!(
+            buf,
+            r#"{"@type":"type.googleapis.com/google.protobuf.Int32Value","value":7}"#
+        );
+
+        let json = r#"{"value": 7, "@type": "type.googleapis.com/google.protobuf.Int32Value"}"#;
+        let mut parser = Parser {
+            input: json,
+            pos: 0,
+            options: &options,
+            recurse_count: crate::RECURSION_LIMIT,
+        };
+        let (type_url, encoded) = parser.any().unwrap();
+        assert_eq!(type_url, "type.googleapis.com/google.protobuf.Int32Value");
+        assert_eq!(encoded, value);
+    }
+}
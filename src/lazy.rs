@@ -0,0 +1,375 @@
+//! Lazily decoded message fields.
+
+use core::fmt;
+
+use ::bytes::{BufMut, Bytes, BytesMut};
+
+use crate::{DecodeError, Message};
+
+#[cfg(feature = "std")]
+type OnceCell<T> = std::sync::OnceLock<T>;
+#[cfg(not(feature = "std"))]
+type OnceCell<T> = core::cell::OnceCell<T>;
+
+/// A message field which is decoded on first access.
+///
+/// When the containing message is decoded, a `Lazy` field only keeps the encoded bytes of the
+/// sub-message, which are decoded the first time the message is accessed. As long as the message
+/// is not modified, encoding the field writes the original bytes again verbatim, so passing a
+/// large message through a service which only inspects some of its fields is cheap.
+///
+/// Lazy fields are declared with the `lazy` attribute of message fields, or generated by
+/// `prost-build` for fields with the `[lazy = true]` option:
+///
+/// ```rust
+/// # use prost::{Lazy, Message};
+/// #[derive(Clone, PartialEq, Message)]
+/// struct Payload {
+///     #[prost(string, tag = "1")]
+///     data: String,
+/// }
+///
+/// #[derive(Clone, PartialEq, Message)]
+/// struct Envelope {
+///     #[prost(string, tag = "1")]
+///     route: String,
+///     #[prost(message, optional, lazy, tag = "2")]
+///     payload: Option<Lazy<Payload>>,
+/// }
+///
+/// let envelope = Envelope {
+///     route: "/".to_string(),
+///     payload: Some(Lazy::new(Payload { data: "data".to_string() })),
+/// };
+/// let buf = envelope.encode_to_vec();
+///
+/// let decoded = Envelope::decode(buf.as_slice()).unwrap();
+/// let payload = decoded.payload.as_ref().unwrap();
+/// assert!(!payload.is_decoded());
+/// assert_eq!(payload.get().unwrap().data, "data");
+/// assert_eq!(decoded.encode_to_vec(), buf);
+/// ```
+///
+/// Errors in the encoded message are only detected when it is decoded, and are returned by the
+/// accessors.
+///
+/// When the containing message is decoded with [`DecodeOptions`], the encoded bytes count against
+/// its [`max_field_bytes`] and [`max_total_bytes`] budgets like the contents of a bytes field.
+/// The options do not carry over to the deferred decode of the sub-message, though: it is decoded
+/// with the default options when it is first accessed, so the recursion limit starts again from
+/// the default and none of the budgets apply to its fields. Only an occurrence of the field which
+/// is merged into a sub-message that was already decoded is decoded right away, with the options.
+///
+/// [`DecodeOptions`]: crate::DecodeOptions
+/// [`max_field_bytes`]: crate::DecodeOptions::max_field_bytes
+/// [`max_total_bytes`]: crate::DecodeOptions::max_total_bytes
+///
+/// With the `std` feature, the decoded message is cached in a [`OnceLock`](std::sync::OnceLock),
+/// so `Lazy<M>` is `Sync` if `M` is. Without it, a [`OnceCell`](core::cell::OnceCell) is used.
+pub struct Lazy<M> {
+    /// The encoded message, if the message has not been modified since it was decoded.
+    encoded: Option<Bytes>,
+    /// The decoded message, which is set if `encoded` is `None`.
+    message: OnceCell<M>,
+}
+
+impl<M> Lazy<M>
+where
+    M: Message + Default,
+{
+    /// Creates a lazy field holding a decoded message.
+    pub fn new(message: M) -> Lazy<M> {
+        Lazy {
+            encoded: None,
+            message: OnceCell::from(message),
+        }
+    }
+
+    /// Creates a lazy field holding an encoded message, which is decoded on first access.
+    pub fn from_encoded(encoded: Bytes) -> Lazy<M> {
+        Lazy {
+            encoded: Some(encoded),
+            message: OnceCell::new(),
+        }
+    }
+
+    /// Returns the message, decoding it if it has not been decoded yet.
+    pub fn get(&self) -> Result<&M, DecodeError> {
+        if let Some(message) = self.message.get() {
+            return Ok(message);
+        }
+        let message = M::decode(self.encoded.clone().unwrap_or_default())?;
+        Ok(self.message.get_or_init(|| message))
+    }
+
+    /// Returns a mutable reference to the message, decoding it if it has not been decoded yet.
+    ///
+    /// The encoded bytes are discarded, so the message is encoded again from its fields.
+    pub fn get_mut(&mut self) -> Result<&mut M, DecodeError> {
+        self.get()?;
+        self.encoded = None;
+        Ok(self.message.get_mut().expect("message was decoded"))
+    }
+
+    /// Consumes the field, returning the message.
+    pub fn into_inner(self) -> Result<M, DecodeError> {
+        match self.message.into_inner() {
+            Some(message) => Ok(message),
+            None => M::decode(self.encoded.unwrap_or_default()),
+        }
+    }
+
+    /// Replaces the message.
+    pub fn set(&mut self, message: M) {
+        *self = Lazy::new(message);
+    }
+
+    /// Clears the message, setting it to its default value.
+    pub fn clear(&mut self) {
+        *self = Lazy::default();
+    }
+
+    /// Returns `true` if the message has been decoded.
+    pub fn is_decoded(&self) -> bool {
+        self.message.get().is_some()
+    }
+
+    /// Returns the encoded message if it has not been modified since the field was decoded.
+    pub fn encoded(&self) -> Option<&Bytes> {
+        self.encoded.as_ref()
+    }
+
+    /// Returns the encoded length of the message, without a length delimiter.
+    pub(crate) fn encoded_len(&self) -> usize {
+        match self.encoded {
+            Some(ref encoded) => encoded.len(),
+            None => self.message.get().map_or(0, Message::encoded_len),
+        }
+    }
+
+    /// Encodes the message to a buffer, without a length delimiter.
+    pub(crate) fn encode_raw<B>(&self, buf: &mut B)
+    where
+        B: BufMut,
+    {
+        match self.encoded {
+            Some(ref encoded) => buf.put_slice(encoded),
+            None => {
+                if let Some(message) = self.message.get() {
+                    message.encode_raw(buf);
+                }
+            }
+        }
+    }
+
+    /// Encodes the message to a buffer, without a length delimiter. The message is encoded
+    /// deterministically if it has been modified since the field was decoded, and is otherwise
+    /// written as it was decoded.
+    pub(crate) fn encode_raw_deterministic<B>(&self, buf: &mut B)
+    where
+        B: BufMut,
+    {
+        match self.encoded {
+            Some(ref encoded) => buf.put_slice(encoded),
+            None => {
+                if let Some(message) = self.message.get() {
+                    message.encode_raw_deterministic(buf);
+                }
+            }
+        }
+    }
+
+    /// Merges another occurrence of the encoded message into the field.
+    pub(crate) fn merge_encoded(&mut self, encoded: Bytes) -> Result<(), DecodeError> {
+        match self.encoded {
+            // The concatenation of two encoded messages is the encoding of the merged message.
+            Some(ref mut existing) if self.message.get().is_none() => {
+                if existing.is_empty() {
+                    *existing = encoded;
+                } else {
+                    let mut merged = BytesMut::with_capacity(existing.len() + encoded.len());
+                    merged.put_slice(existing);
+                    merged.put_slice(&encoded);
+                    *existing = merged.freeze();
+                }
+                Ok(())
+            }
+            _ => self.get_mut()?.merge(encoded),
+        }
+    }
+
+    /// Merges another lazy field into the field.
+    ///
+    /// The messages are merged directly if both have been decoded, and otherwise by concatenating
+    /// their encodings, so neither message is decoded.
+    pub(crate) fn merge_from(&mut self, other: &Lazy<M>) {
+        match (self.message.get_mut(), other.message.get()) {
+            (Some(message), Some(other)) => {
+                message.merge_from(other);
+                self.encoded = None;
+            }
+            _ => {
+                let mut merged = BytesMut::with_capacity(self.encoded_len() + other.encoded_len());
+                self.encode_raw(&mut merged);
+                other.encode_raw(&mut merged);
+                *self = Lazy::from_encoded(merged.freeze());
+            }
+        }
+    }
+}
+
+impl<M> Default for Lazy<M> {
+    fn default() -> Lazy<M> {
+        Lazy {
+            encoded: Some(Bytes::new()),
+            message: OnceCell::new(),
+        }
+    }
+}
+
+impl<M> Clone for Lazy<M>
+where
+    M: Clone,
+{
+    fn clone(&self) -> Lazy<M> {
+        Lazy {
+            encoded: self.encoded.clone(),
+            message: self.message.clone(),
+        }
+    }
+}
+
+impl<M> From<M> for Lazy<M>
+where
+    M: Message + Default,
+{
+    fn from(message: M) -> Lazy<M> {
+        Lazy::new(message)
+    }
+}
+
+/// Lazy fields are equal if their encoded bytes are equal, or else if their decoded messages are
+/// equal. A message which fails to decode is only equal to the same encoded bytes.
+impl<M> PartialEq for Lazy<M>
+where
+    M: Message + Default + PartialEq,
+{
+    fn eq(&self, other: &Lazy<M>) -> bool {
+        if let (Some(a), Some(b)) = (&self.encoded, &other.encoded) {
+            if a == b {
+                return true;
+            }
+        }
+        match (self.get(), other.get()) {
+            (Ok(a), Ok(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+impl<M> fmt::Debug for Lazy<M>
+where
+    M: Message + Default + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.get() {
+            Ok(message) => message.fmt(f),
+            Err(error) => f
+                .debug_struct("Lazy")
+                .field("encoded_len", &self.encoded_len())
+                .field("error", &error)
+                .finish(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use alloc::vec::Vec;
+
+    use crate::encoding::{encode_key, encode_varint, WireType};
+    use crate::UnknownFieldSet;
+
+    fn encoded(fields: &[(u32, u64)]) -> Bytes {
+        let mut buf = Vec::new();
+        for &(tag, value) in fields {
+            encode_key(tag, WireType::Varint, &mut buf);
+            encode_varint(value, &mut buf);
+        }
+        buf.into()
+    }
+
+    #[test]
+    fn decodes_on_first_access() {
+        let bytes = encoded(&[(1, 150)]);
+        let lazy = Lazy::<UnknownFieldSet>::from_encoded(bytes.clone());
+        assert!(!lazy.is_decoded());
+        assert_eq!(lazy.encoded_len(), bytes.len());
+
+        assert_eq!(lazy.get().unwrap().len(), 1);
+        assert!(lazy.is_decoded());
+        assert_eq!(lazy.encoded(), Some(&bytes));
+    }
+
+    #[test]
+    fn modified_message_is_encoded_again() {
+        let mut lazy = Lazy::<UnknownFieldSet>::from_encoded(encoded(&[(1, 1)]));
+        lazy.get_mut().unwrap().clear();
+        assert_eq!(lazy.encoded(), None);
+        assert_eq!(lazy.encoded_len(), 0);
+
+        let mut buf = Vec::new();
+        lazy.encode_raw(&mut buf);
+        assert!(buf.is_empty());
+    }
+
+    #[test]
+    fn merge_encoded() {
+        let mut lazy = Lazy::<UnknownFieldSet>::default();
+        lazy.merge_encoded(encoded(&[(1, 1)])).unwrap();
+        lazy.merge_encoded(encoded(&[(2, 2)])).unwrap();
+        assert_eq!(lazy.encoded(), Some(&encoded(&[(1, 1), (2, 2)])));
+
+        lazy.get().unwrap();
+        lazy.merge_encoded(encoded(&[(3, 3)])).unwrap();
+        assert_eq!(lazy.encoded(), None);
+        assert_eq!(
+            lazy.into_inner().unwrap(),
+            UnknownFieldSet::decode(encoded(&[(1, 1), (2, 2), (3, 3)])).unwrap()
+        );
+    }
+
+    #[test]
+    fn invalid_message() {
+        let invalid = Bytes::from_static(&[0x08]);
+        let lazy = Lazy::<UnknownFieldSet>::from_encoded(invalid.clone());
+        assert!(lazy.get().is_err());
+        assert!(!lazy.is_decoded());
+        assert_eq!(lazy, Lazy::from_encoded(invalid.clone()));
+        assert_ne!(lazy, Lazy::default());
+
+        let mut lazy = lazy;
+        assert!(lazy.get_mut().is_err());
+        assert_eq!(lazy.encoded(), Some(&invalid));
+        assert!(lazy.into_inner().is_err());
+    }
+
+    #[test]
+    fn equality() {
+        let message = UnknownFieldSet::decode(encoded(&[(1, 1)])).unwrap();
+        assert_eq!(
+            Lazy::from_encoded(encoded(&[(1, 1)])),
+            Lazy::new(message.clone())
+        );
+        assert_ne!(
+            Lazy::from_encoded(encoded(&[(1, 2)])),
+            Lazy::new(message.clone())
+        );
+        assert_eq!(
+            Lazy::<UnknownFieldSet>::default(),
+            Lazy::new(Default::default())
+        );
+    }
+}
@@ -9,17 +9,52 @@ pub extern crate alloc;
 // Re-export the bytes crate for use within derived code.
 pub use bytes;
 
+mod cached_size;
+#[cfg(feature = "std")]
+mod delimited;
 mod error;
+mod incremental;
+mod lazy;
 mod message;
 mod name;
+mod options;
+mod projection;
+mod required;
+mod strict;
 mod types;
+mod unknown;
+mod writer;
 
+#[cfg(feature = "tokio-util")]
+pub mod codec;
 #[doc(hidden)]
 pub mod encoding;
+pub mod extension;
+pub mod field_mask;
+pub mod json;
+pub mod text_format;
+pub mod view;
+pub mod wire;
 
-pub use crate::error::{DecodeError, EncodeError};
+pub use crate::cached_size::CachedSize;
+#[cfg(feature = "std")]
+pub use crate::delimited::{DelimitedReader, DelimitedWriter, DEFAULT_MAX_FRAME_LEN};
+pub use crate::error::{DecodeBudget, DecodeError, DecodeErrorKind, EncodeError, EncodeErrorKind};
+pub use crate::extension::{Extendable, Extension, ExtensionSet};
+pub use crate::field_mask::FieldMasked;
+pub use crate::incremental::{DecodeStatus, IncrementalDecoder};
+pub use crate::json::Json;
+pub use crate::lazy::Lazy;
 pub use crate::message::Message;
 pub use crate::name::Name;
+pub use crate::options::DecodeOptions;
+pub use crate::projection::Projection;
+pub use crate::text_format::TextFormat;
+pub use crate::unknown::{UnknownField, UnknownFieldSet, UnknownFieldValue};
+pub use crate::wire::{decode_raw, WireReader};
+#[cfg(feature = "std")]
+pub use crate::writer::IoMessageWriter;
+pub use crate::writer::MessageWriter;
 
 use bytes::{Buf, BufMut};
 
@@ -29,6 +64,8 @@ use crate::encoding::{decode_varint, encode_varint, encoded_len_varint};
 // 100 is the default recursion limit in the C++ implementation.
 #[cfg(not(feature = "no-recursion-limit"))]
 const RECURSION_LIMIT: u32 = 100;
+#[cfg(feature = "no-recursion-limit")]
+const RECURSION_LIMIT: u32 = u32::MAX;
 
 /// Encodes a length delimiter to the buffer.
 ///
@@ -75,7 +112,8 @@ where
 {
     let length = decode_varint(&mut buf)?;
     if length > usize::max_value() as u64 {
-        return Err(DecodeError::new(
+        return Err(DecodeError::with_kind(
+            DecodeErrorKind::InvalidLength,
             "length delimiter exceeds maximum usize value",
         ));
     }
@@ -3,13 +3,24 @@ use alloc::vec::Vec;
 
 use core::fmt::Debug;
 use core::usize;
+#[cfg(feature = "std")]
+use std::io::{self, Read, Write};
 
 use bytes::{Buf, BufMut};
 
+#[cfg(feature = "std")]
+use crate::delimited::{read_frame, read_length_delimiter};
 use crate::encoding::{
-    decode_key, encode_varint, encoded_len_varint, message, DecodeContext, WireType,
+    decode_key, encode_varint, encoded_len_varint, message, DecodeContext, EncodedLenTable,
+    WireType,
 };
+use crate::options::Budget;
+use crate::projection::{self, Projection};
+use crate::required::{self, MissingFieldPaths};
+use crate::strict::{FieldChecks, SingularMessageField};
+use crate::CachedSize;
 use crate::DecodeError;
+use crate::DecodeOptions;
 use crate::EncodeError;
 
 /// A Protocol Buffers message.
@@ -25,6 +36,41 @@ pub trait Message: Debug + Send + Sync {
         B: BufMut,
         Self: Sized;
 
+    /// Encodes the message to a buffer, with map entries in key order and fields in field number
+    /// order.
+    ///
+    /// This method will panic if the buffer has insufficient capacity.
+    ///
+    /// Meant to be used only by `Message` implementations.
+    #[doc(hidden)]
+    fn encode_raw_deterministic<B>(&self, buf: &mut B)
+    where
+        B: BufMut,
+        Self: Sized,
+    {
+        self.encode_raw(buf)
+    }
+
+    /// Encodes the message to a buffer, taking the encoded lengths of its nested messages from the
+    /// table filled in by `encoded_len_with_table`, deterministically if the table is for a
+    /// deterministic encode.
+    ///
+    /// This method will panic if the buffer has insufficient capacity.
+    ///
+    /// Meant to be used only by `Message` implementations.
+    #[doc(hidden)]
+    fn encode_raw_with_table<B>(&self, table: &mut EncodedLenTable, buf: &mut B)
+    where
+        B: BufMut,
+        Self: Sized,
+    {
+        if table.is_deterministic() {
+            self.encode_raw_deterministic(buf)
+        } else {
+            self.encode_raw(buf)
+        }
+    }
+
     /// Decodes a field from a buffer, and merges it into `self`.
     ///
     /// Meant to be used only by `Message` implementations.
@@ -43,6 +89,77 @@ pub trait Message: Debug + Send + Sync {
     /// Returns the encoded length of the message without a length delimiter.
     fn encoded_len(&self) -> usize;
 
+    /// Records the paths of the missing required fields of the message and its nested messages
+    /// which are selected by the projection, if any.
+    ///
+    /// Meant to be used only by `Message` implementations.
+    #[doc(hidden)]
+    fn find_missing_fields(&self, projection: Option<&Projection>, paths: &mut MissingFieldPaths) {
+        let _ = (projection, paths);
+    }
+
+    /// Returns the field with the given number, if it is a message or group field which is not
+    /// repeated, or a oneof whose variant with that number holds a message or group.
+    ///
+    /// Such fields may occur only once in decodes with [`DecodeOptions::strict`], and are decoded
+    /// field by field by an [`IncrementalDecoder`](crate::IncrementalDecoder).
+    ///
+    /// Meant to be used only by `Message` implementations.
+    #[doc(hidden)]
+    fn singular_message_field(tag: u32) -> Option<SingularMessageField>
+    where
+        Self: Sized,
+    {
+        let _ = tag;
+        None
+    }
+
+    /// Returns the `#[prost(cached_size)]` field of the message, if any, which holds its encoded
+    /// length while it is encoded as a nested message, instead of the `EncodedLenTable`.
+    ///
+    /// Meant to be used only by `Message` implementations.
+    #[doc(hidden)]
+    fn cached_size(&self) -> Option<&CachedSize> {
+        None
+    }
+
+    /// Returns the encoded length of the message without a length delimiter, recording the
+    /// encoded lengths of its nested messages in the table, in the order they are encoded.
+    ///
+    /// Implementations which don't record the lengths of their nested messages must encode
+    /// without taking lengths from the table in `encode_raw_with_table`.
+    ///
+    /// Meant to be used only by `Message` implementations.
+    #[doc(hidden)]
+    fn encoded_len_with_table(&self, table: &mut EncodedLenTable) -> usize {
+        let _ = table;
+        self.encoded_len()
+    }
+
+    /// Returns `true` if no required field of the message or of its nested messages is missing.
+    ///
+    /// A required field is missing if it holds no value, which can only be told for fields
+    /// declared `#[prost(required, presence)]`, stored as an `Option`. `prost-build` generates
+    /// them for the fields selected by `Config::required_field_presence`. Other required fields
+    /// are never missing, although the nested messages of required message fields are checked.
+    fn is_initialized(&self) -> bool {
+        let mut paths = MissingFieldPaths::default();
+        self.find_missing_fields(None, &mut paths);
+        paths.is_empty()
+    }
+
+    /// Returns an error naming the path of every missing required field of the message and of its
+    /// nested messages, e.g. `members[1].email`, if any, like C++ and Java do before encoding a
+    /// message.
+    ///
+    /// See [`is_initialized`](Message::is_initialized) for which fields can be missing. The error
+    /// has kind [`MissingRequiredField`](crate::EncodeErrorKind::MissingRequiredField), and the
+    /// paths are returned by [`EncodeError::missing_fields`]. The encode methods do not check the
+    /// message themselves.
+    fn check_initialized(&self) -> Result<(), EncodeError> {
+        required::check_initialized(self)
+    }
+
     /// Encodes the message to a buffer.
     ///
     /// An error will be returned if the buffer does not have sufficient capacity.
@@ -51,13 +168,14 @@ pub trait Message: Debug + Send + Sync {
         B: BufMut,
         Self: Sized,
     {
-        let required = self.encoded_len();
+        let mut table = EncodedLenTable::new();
+        let required = self.encoded_len_with_table(&mut table);
         let remaining = buf.remaining_mut();
         if required > remaining {
             return Err(EncodeError::new(required, remaining));
         }
 
-        self.encode_raw(buf);
+        self.encode_raw_with_table(&mut table, buf);
         Ok(())
     }
 
@@ -66,12 +184,64 @@ pub trait Message: Debug + Send + Sync {
     where
         Self: Sized,
     {
-        let mut buf = Vec::with_capacity(self.encoded_len());
+        let mut table = EncodedLenTable::new();
+        let mut buf = Vec::with_capacity(self.encoded_len_with_table(&mut table));
+
+        self.encode_raw_with_table(&mut table, &mut buf);
+        buf
+    }
+
+    /// Encodes the message to a buffer deterministically.
+    ///
+    /// The same message is always encoded to the same bytes: map entries are encoded in key order,
+    /// rather than in the iteration order of the map, and fields are encoded in field number order,
+    /// including the members of oneofs. Unknown fields are encoded as they were decoded, after the
+    /// known fields.
+    ///
+    /// An error will be returned if the buffer does not have sufficient capacity.
+    fn encode_deterministic<B>(&self, buf: &mut B) -> Result<(), EncodeError>
+    where
+        B: BufMut,
+        Self: Sized,
+    {
+        let mut table = EncodedLenTable::deterministic();
+        let required = self.encoded_len_with_table(&mut table);
+        let remaining = buf.remaining_mut();
+        if required > remaining {
+            return Err(EncodeError::new(required, remaining));
+        }
+
+        self.encode_raw_with_table(&mut table, buf);
+        Ok(())
+    }
+
+    /// Encodes the message deterministically to a newly allocated buffer.
+    ///
+    /// See [`encode_deterministic`](Message::encode_deterministic) for the encoding.
+    fn encode_to_vec_deterministic(&self) -> Vec<u8>
+    where
+        Self: Sized,
+    {
+        let mut table = EncodedLenTable::deterministic();
+        let mut buf = Vec::with_capacity(self.encoded_len_with_table(&mut table));
 
-        self.encode_raw(&mut buf);
+        self.encode_raw_with_table(&mut table, &mut buf);
         buf
     }
 
+    /// Encodes the message to a writer.
+    ///
+    /// The message is encoded to a buffer of its `encoded_len` first, and written with a single
+    /// call to `write_all`.
+    #[cfg(feature = "std")]
+    fn encode_to_writer<W>(&self, mut writer: W) -> io::Result<()>
+    where
+        W: Write,
+        Self: Sized,
+    {
+        writer.write_all(&self.encode_to_vec())
+    }
+
     /// Encodes the message with a length-delimiter to a buffer.
     ///
     /// An error will be returned if the buffer does not have sufficient capacity.
@@ -80,14 +250,15 @@ pub trait Message: Debug + Send + Sync {
         B: BufMut,
         Self: Sized,
     {
-        let len = self.encoded_len();
+        let mut table = EncodedLenTable::new();
+        let len = self.encoded_len_with_table(&mut table);
         let required = len + encoded_len_varint(len as u64);
         let remaining = buf.remaining_mut();
         if required > remaining {
             return Err(EncodeError::new(required, remaining));
         }
         encode_varint(len as u64, buf);
-        self.encode_raw(buf);
+        self.encode_raw_with_table(&mut table, buf);
         Ok(())
     }
 
@@ -96,14 +267,65 @@ pub trait Message: Debug + Send + Sync {
     where
         Self: Sized,
     {
-        let len = self.encoded_len();
+        let mut table = EncodedLenTable::new();
+        let len = self.encoded_len_with_table(&mut table);
         let mut buf = Vec::with_capacity(len + encoded_len_varint(len as u64));
 
         encode_varint(len as u64, &mut buf);
-        self.encode_raw(&mut buf);
+        self.encode_raw_with_table(&mut table, &mut buf);
         buf
     }
 
+    /// Encodes the message deterministically with a length-delimiter to a buffer.
+    ///
+    /// See [`encode_deterministic`](Message::encode_deterministic) for the encoding. An error will
+    /// be returned if the buffer does not have sufficient capacity.
+    fn encode_length_delimited_deterministic<B>(&self, buf: &mut B) -> Result<(), EncodeError>
+    where
+        B: BufMut,
+        Self: Sized,
+    {
+        let mut table = EncodedLenTable::deterministic();
+        let len = self.encoded_len_with_table(&mut table);
+        let required = len + encoded_len_varint(len as u64);
+        let remaining = buf.remaining_mut();
+        if required > remaining {
+            return Err(EncodeError::new(required, remaining));
+        }
+        encode_varint(len as u64, buf);
+        self.encode_raw_with_table(&mut table, buf);
+        Ok(())
+    }
+
+    /// Encodes the message deterministically with a length-delimiter to a newly allocated buffer.
+    ///
+    /// See [`encode_deterministic`](Message::encode_deterministic) for the encoding.
+    fn encode_length_delimited_to_vec_deterministic(&self) -> Vec<u8>
+    where
+        Self: Sized,
+    {
+        let mut table = EncodedLenTable::deterministic();
+        let len = self.encoded_len_with_table(&mut table);
+        let mut buf = Vec::with_capacity(len + encoded_len_varint(len as u64));
+
+        encode_varint(len as u64, &mut buf);
+        self.encode_raw_with_table(&mut table, &mut buf);
+        buf
+    }
+
+    /// Encodes the message with a length-delimiter to a writer.
+    ///
+    /// The message is encoded to a buffer of its `encoded_len` first, and written with a single
+    /// call to `write_all`.
+    #[cfg(feature = "std")]
+    fn encode_length_delimited_to_writer<W>(&self, mut writer: W) -> io::Result<()>
+    where
+        W: Write,
+        Self: Sized,
+    {
+        writer.write_all(&self.encode_length_delimited_to_vec())
+    }
+
     /// Decodes an instance of the message from a buffer.
     ///
     /// The entire buffer will be consumed.
@@ -116,6 +338,71 @@ pub trait Message: Debug + Send + Sync {
         Self::merge(&mut message, &mut buf).map(|_| message)
     }
 
+    /// Decodes an instance of the message from a buffer, using the given decode options.
+    ///
+    /// The entire buffer will be consumed.
+    fn decode_with_options<B>(mut buf: B, options: &DecodeOptions) -> Result<Self, DecodeError>
+    where
+        B: Buf,
+        Self: Default,
+    {
+        let mut message = Self::default();
+        Self::merge_with_options(&mut message, &mut buf, options).map(|_| message)
+    }
+
+    /// Decodes an instance of the message from a buffer, decoding only the fields selected by the
+    /// projection.
+    ///
+    /// Fields which are not selected are skipped without being decoded, and are left at their
+    /// default values. See [`Projection`] for how fields are selected.
+    ///
+    /// The entire buffer will be consumed.
+    fn decode_projected<B>(buf: B, projection: &Projection) -> Result<Self, DecodeError>
+    where
+        B: Buf,
+        Self: Default,
+    {
+        Self::decode_projected_with_options(buf, projection, &DecodeOptions::default())
+    }
+
+    /// Decodes an instance of the message from a buffer using the given decode options, decoding
+    /// only the fields selected by the projection.
+    ///
+    /// Fields which are skipped by the projection are not checked by
+    /// [`DecodeOptions::check_required_fields`] or [`DecodeOptions::strict`]: a required field
+    /// which is not selected is not reported missing, and a skipped field whose number is not part
+    /// of the schema, or which occurs more than once, is not rejected. The budgets of the options
+    /// only count the fields which are decoded.
+    ///
+    /// The entire buffer will be consumed.
+    fn decode_projected_with_options<B>(
+        mut buf: B,
+        projection: &Projection,
+        options: &DecodeOptions,
+    ) -> Result<Self, DecodeError>
+    where
+        B: Buf,
+        Self: Default,
+    {
+        let mut message = Self::default();
+        merge(&mut message, &mut buf, options, Some(projection)).map(|_| message)
+    }
+
+    /// Decodes an instance of the message from a reader.
+    ///
+    /// The reader is read until the end of the stream. Decoding errors are returned as `io::Error`
+    /// with kind `InvalidData`.
+    #[cfg(feature = "std")]
+    fn decode_from_reader<R>(reader: R) -> io::Result<Self>
+    where
+        R: Read,
+        Self: Default,
+    {
+        let mut message = Self::default();
+        message.merge_from_reader(reader)?;
+        Ok(message)
+    }
+
     /// Decodes a length-delimited instance of the message from the buffer.
     fn decode_length_delimited<B>(buf: B) -> Result<Self, DecodeError>
     where
@@ -127,19 +414,62 @@ pub trait Message: Debug + Send + Sync {
         Ok(message)
     }
 
+    /// Decodes a length-delimited instance of the message from a reader.
+    ///
+    /// Only the length delimiter and the message are read, so the reader can be used to read any
+    /// data following the message. Decoding errors are returned as `io::Error` with kind
+    /// `InvalidData`, and an end of stream before the end of the message as `UnexpectedEof`.
+    #[cfg(feature = "std")]
+    fn decode_length_delimited_from_reader<R>(reader: R) -> io::Result<Self>
+    where
+        R: Read,
+        Self: Default,
+    {
+        let mut message = Self::default();
+        message.merge_length_delimited_from_reader(reader)?;
+        Ok(message)
+    }
+
     /// Decodes an instance of the message from a buffer, and merges it into `self`.
     ///
     /// The entire buffer will be consumed.
-    fn merge<B>(&mut self, mut buf: B) -> Result<(), DecodeError>
+    fn merge<B>(&mut self, buf: B) -> Result<(), DecodeError>
     where
         B: Buf,
         Self: Sized,
     {
-        let ctx = DecodeContext::default();
-        while buf.has_remaining() {
-            let (tag, wire_type) = decode_key(&mut buf)?;
-            self.merge_field(tag, wire_type, &mut buf, ctx.clone())?;
-        }
+        self.merge_with_options(buf, &DecodeOptions::default())
+    }
+
+    /// Decodes an instance of the message from a buffer using the given decode options, and
+    /// merges it into `self`.
+    ///
+    /// The entire buffer will be consumed.
+    fn merge_with_options<B>(
+        &mut self,
+        mut buf: B,
+        options: &DecodeOptions,
+    ) -> Result<(), DecodeError>
+    where
+        B: Buf,
+        Self: Sized,
+    {
+        merge(self, &mut buf, options, None)
+    }
+
+    /// Decodes an instance of the message from a reader, and merges it into `self`.
+    ///
+    /// The reader is read until the end of the stream. Decoding errors are returned as `io::Error`
+    /// with kind `InvalidData`.
+    #[cfg(feature = "std")]
+    fn merge_from_reader<R>(&mut self, mut reader: R) -> io::Result<()>
+    where
+        R: Read,
+        Self: Sized,
+    {
+        let mut buf = Vec::new();
+        reader.read_to_end(&mut buf)?;
+        self.merge(buf.as_slice())?;
         Ok(())
     }
 
@@ -150,18 +480,100 @@ pub trait Message: Debug + Send + Sync {
         B: Buf,
         Self: Sized,
     {
+        let len = buf.remaining();
         message::merge(
             WireType::LengthDelimited,
             self,
             &mut buf,
             DecodeContext::default(),
         )
+        .map_err(|mut error| {
+            error.set_offset(len - buf.remaining());
+            error
+        })
+    }
+
+    /// Decodes a length-delimited instance of the message from a reader, and merges it into
+    /// `self`.
+    ///
+    /// Only the length delimiter and the message are read, so the reader can be used to read any
+    /// data following the message. Decoding errors are returned as `io::Error` with kind
+    /// `InvalidData`, and an end of stream before the end of the message as `UnexpectedEof`.
+    #[cfg(feature = "std")]
+    fn merge_length_delimited_from_reader<R>(&mut self, mut reader: R) -> io::Result<()>
+    where
+        R: Read,
+        Self: Sized,
+    {
+        let len = read_length_delimiter(&mut reader)?.ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "stream ended before the length delimiter",
+            )
+        })?;
+        let mut buf = Vec::new();
+        read_frame(&mut reader, len, &mut buf)?;
+        self.merge(buf.as_slice())?;
+        Ok(())
+    }
+
+    /// Merges `other` into `self`, as if `other` were encoded and merged with [`Message::merge`].
+    ///
+    /// Singular fields which are set in `other` overwrite those in `self`, repeated fields are
+    /// appended, nested messages are merged recursively, map entries are inserted by key, and a set
+    /// oneof replaces the oneof in `self`, or is merged into it if both hold the same message
+    /// variant.
+    ///
+    /// The derived implementation merges the fields directly. The default implementation encodes
+    /// `other` and merges the encoding. It never fails: an implementation which can't decode its
+    /// own encoding keeps the fields merged before the first one it fails to decode.
+    fn merge_from(&mut self, other: &Self)
+    where
+        Self: Sized,
+    {
+        let mut buf = Vec::with_capacity(other.encoded_len());
+        other.encode_raw(&mut buf);
+        // The encoding of a message is always valid, and may only exceed the recursion limit.
+        let options = DecodeOptions::new().recursion_limit(u32::MAX);
+        let _ = merge(self, &mut buf.as_slice(), &options, None);
     }
 
     /// Clears the message, resetting all fields to their default.
     fn clear(&mut self);
 }
 
+/// Decodes fields from a buffer until it is exhausted, and merges them into a message.
+fn merge<M, B>(
+    msg: &mut M,
+    buf: &mut B,
+    options: &DecodeOptions,
+    projection: Option<&Projection>,
+) -> Result<(), DecodeError>
+where
+    M: Message,
+    B: Buf,
+{
+    let budget = Budget::new(options);
+    let ctx = DecodeContext::new(options, budget.as_ref()).with_projection(projection);
+    let len = buf.remaining();
+    let mut checks = FieldChecks::new::<M>(&ctx);
+    let mut merge = || {
+        while buf.has_remaining() {
+            let (tag, wire_type) = decode_key(buf)?;
+            projection::merge_field(msg, tag, wire_type, buf, ctx.clone(), &mut checks)?;
+        }
+        Ok(())
+    };
+    merge().map_err(|mut error: DecodeError| {
+        error.set_offset(len - buf.remaining());
+        error
+    })?;
+    if options.check_required_fields {
+        required::check(msg, projection)?;
+    }
+    Ok(())
+}
+
 impl<M> Message for Box<M>
 where
     M: Message,
@@ -172,6 +584,18 @@ where
     {
         (**self).encode_raw(buf)
     }
+    fn encode_raw_deterministic<B>(&self, buf: &mut B)
+    where
+        B: BufMut,
+    {
+        (**self).encode_raw_deterministic(buf)
+    }
+    fn encode_raw_with_table<B>(&self, table: &mut EncodedLenTable, buf: &mut B)
+    where
+        B: BufMut,
+    {
+        (**self).encode_raw_with_table(table, buf)
+    }
     fn merge_field<B>(
         &mut self,
         tag: u32,
@@ -187,6 +611,21 @@ where
     fn encoded_len(&self) -> usize {
         (**self).encoded_len()
     }
+    fn find_missing_fields(&self, projection: Option<&Projection>, paths: &mut MissingFieldPaths) {
+        (**self).find_missing_fields(projection, paths)
+    }
+    fn singular_message_field(tag: u32) -> Option<SingularMessageField> {
+        M::singular_message_field(tag)
+    }
+    fn encoded_len_with_table(&self, table: &mut EncodedLenTable) -> usize {
+        (**self).encoded_len_with_table(table)
+    }
+    fn cached_size(&self) -> Option<&CachedSize> {
+        (**self).cached_size()
+    }
+    fn merge_from(&mut self, other: &Self) {
+        (**self).merge_from(other)
+    }
     fn clear(&mut self) {
         (**self).clear()
     }
@@ -197,4 +636,128 @@ mod tests {
     use super::*;
 
     const _MESSAGE_IS_OBJECT_SAFE: Option<&dyn Message> = None;
+
+    #[cfg(feature = "std")]
+    mod io {
+        use super::*;
+
+        use alloc::string::{String, ToString};
+        use std::io::{Cursor, ErrorKind};
+
+        #[test]
+        fn reader_writer_roundtrip() {
+            let msg = "hello".to_string();
+            let mut buf = Vec::new();
+            msg.encode_to_writer(&mut buf).unwrap();
+            assert_eq!(buf, msg.encode_to_vec());
+            assert_eq!(String::decode_from_reader(buf.as_slice()).unwrap(), msg);
+
+            let mut merged = 42u32;
+            merged.merge_from_reader(&[][..]).unwrap();
+            assert_eq!(merged, 42);
+        }
+
+        #[test]
+        fn length_delimited_reader_writer() {
+            let mut buf = Vec::new();
+            "first"
+                .to_string()
+                .encode_length_delimited_to_writer(&mut buf)
+                .unwrap();
+            "second"
+                .to_string()
+                .encode_length_delimited_to_writer(&mut buf)
+                .unwrap();
+
+            let mut reader = Cursor::new(buf);
+            assert_eq!(
+                String::decode_length_delimited_from_reader(&mut reader).unwrap(),
+                "first"
+            );
+            let mut second = String::new();
+            second
+                .merge_length_delimited_from_reader(&mut reader)
+                .unwrap();
+            assert_eq!(second, "second");
+
+            let error = String::decode_length_delimited_from_reader(&mut reader).unwrap_err();
+            assert_eq!(error.kind(), ErrorKind::UnexpectedEof);
+        }
+
+        #[test]
+        fn reader_errors() {
+            let error = String::decode_from_reader(&[0x0a, 0x05, b'x'][..]).unwrap_err();
+            assert_eq!(error.kind(), ErrorKind::InvalidData);
+
+            let error = String::decode_length_delimited_from_reader(&[0x07, 0x0a, 0x05, b'x'][..])
+                .unwrap_err();
+            assert_eq!(error.kind(), ErrorKind::UnexpectedEof);
+
+            let error = String::decode_length_delimited_from_reader(&[0x03, 0x0a, 0x05, b'x'][..])
+                .unwrap_err();
+            assert_eq!(error.kind(), ErrorKind::InvalidData);
+        }
+
+        #[test]
+        fn length_delimited_eof_in_prefix() {
+            // The first byte of a two byte length delimiter, followed by the end of the stream.
+            let error = String::decode_length_delimited_from_reader(&[0x80][..]).unwrap_err();
+            assert_eq!(error.kind(), ErrorKind::UnexpectedEof);
+
+            let mut buf = Vec::new();
+            "x".repeat(200)
+                .encode_length_delimited_to_writer(&mut buf)
+                .unwrap();
+            assert!(buf[0] >= 0x80);
+            let error = String::decode_length_delimited_from_reader(&buf[..1]).unwrap_err();
+            assert_eq!(error.kind(), ErrorKind::UnexpectedEof);
+        }
+
+        #[test]
+        fn length_delimited_eof_in_body() {
+            let mut buf = Vec::new();
+            "hello"
+                .to_string()
+                .encode_length_delimited_to_writer(&mut buf)
+                .unwrap();
+
+            for len in 1..buf.len() {
+                let error = String::decode_length_delimited_from_reader(&buf[..len]).unwrap_err();
+                assert_eq!(
+                    error.kind(),
+                    ErrorKind::UnexpectedEof,
+                    "truncated to {}",
+                    len
+                );
+            }
+            assert_eq!(
+                String::decode_length_delimited_from_reader(buf.as_slice()).unwrap(),
+                "hello"
+            );
+        }
+
+        #[test]
+        fn length_delimited_back_to_back() {
+            let messages = ["", "a", "hello", &"x".repeat(300), ""];
+
+            let mut buf = Vec::new();
+            for message in &messages {
+                message
+                    .to_string()
+                    .encode_length_delimited_to_writer(&mut buf)
+                    .unwrap();
+            }
+
+            let mut reader = buf.as_slice();
+            for message in &messages {
+                assert_eq!(
+                    String::decode_length_delimited_from_reader(&mut reader).unwrap(),
+                    *message
+                );
+            }
+            assert!(reader.is_empty());
+            let error = String::decode_length_delimited_from_reader(&mut reader).unwrap_err();
+            assert_eq!(error.kind(), ErrorKind::UnexpectedEof);
+        }
+    }
 }
@@ -0,0 +1,402 @@
+//! Options controlling how messages are decoded.
+
+use core::cell::Cell;
+
+use crate::{DecodeBudget, DecodeError};
+
+/// Options for decoding a message, used with [`Message::decode_with_options`] and
+/// [`Message::merge_with_options`].
+///
+/// The options apply to a single decode call, so services with different trust levels can use
+/// different limits with the same message types:
+///
+/// ```rust
+/// # use prost::{DecodeOptions, Message};
+/// #[derive(Clone, PartialEq, Message)]
+/// struct Tree {
+///     #[prost(message, repeated, tag = "1")]
+///     children: Vec<Tree>,
+/// }
+///
+/// let options = DecodeOptions::new().recursion_limit(500);
+/// let tree = Tree::decode_with_options(&b"\x0a\x00"[..], &options).unwrap();
+/// assert_eq!(tree.children.len(), 1);
+/// ```
+///
+/// [`Message::decode_with_options`]: crate::Message::decode_with_options
+/// [`Message::merge_with_options`]: crate::Message::merge_with_options
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DecodeOptions {
+    pub(crate) recursion_limit: u32,
+    max_total_bytes: Option<usize>,
+    max_repeated_elements: Option<usize>,
+    max_field_bytes: Option<usize>,
+    max_unknown_fields: Option<usize>,
+    pub(crate) check_required_fields: bool,
+    pub(crate) strict: bool,
+}
+
+impl DecodeOptions {
+    /// Creates the default decode options.
+    pub fn new() -> DecodeOptions {
+        DecodeOptions::default()
+    }
+
+    /// Sets the maximum depth of nested messages and groups.
+    ///
+    /// Decoding fails with a `DecodeError` if the input is nested more deeply than the limit.
+    /// Defaults to 100, the default limit of the C++ implementation, or to no limit when Prost is
+    /// built with the `no-recursion-limit` feature.
+    pub fn recursion_limit(mut self, limit: u32) -> DecodeOptions {
+        self.recursion_limit = limit;
+        self
+    }
+
+    /// Sets the budget for the total amount of data allocated by a decode, in bytes.
+    ///
+    /// The contents of string and bytes fields count against the budget, as does the in-memory
+    /// size of each element of a repeated or map field and of each unknown field. Decoding fails
+    /// with a `DecodeError` whose [`exceeded_budget`] is [`DecodeBudget::TotalBytes`] if the
+    /// budget is exceeded. Not limited by default.
+    ///
+    /// [`exceeded_budget`]: crate::DecodeError::exceeded_budget
+    pub fn max_total_bytes(mut self, max: usize) -> DecodeOptions {
+        self.max_total_bytes = Some(max);
+        self
+    }
+
+    /// Sets the maximum number of elements in a single repeated or map field.
+    ///
+    /// Decoding fails with a `DecodeError` whose [`exceeded_budget`] is
+    /// [`DecodeBudget::RepeatedElements`] if a field has more elements. Not limited by default.
+    ///
+    /// [`exceeded_budget`]: crate::DecodeError::exceeded_budget
+    pub fn max_repeated_elements(mut self, max: usize) -> DecodeOptions {
+        self.max_repeated_elements = Some(max);
+        self
+    }
+
+    /// Sets the maximum length of a single string or bytes field, in bytes.
+    ///
+    /// Decoding fails with a `DecodeError` whose [`exceeded_budget`] is
+    /// [`DecodeBudget::FieldBytes`] if a field is longer. Not limited by default.
+    ///
+    /// [`exceeded_budget`]: crate::DecodeError::exceeded_budget
+    pub fn max_field_bytes(mut self, max: usize) -> DecodeOptions {
+        self.max_field_bytes = Some(max);
+        self
+    }
+
+    /// Sets the maximum number of unknown fields kept by a single message.
+    ///
+    /// Unknown fields are kept by messages with an unknown field set, and are not limited by
+    /// [`max_repeated_elements`](DecodeOptions::max_repeated_elements), although their in-memory
+    /// size counts against [`max_total_bytes`](DecodeOptions::max_total_bytes). Decoding fails
+    /// with a `DecodeError` whose [`exceeded_budget`] is [`DecodeBudget::UnknownFields`] if a
+    /// message has more unknown fields. Not limited by default.
+    ///
+    /// [`exceeded_budget`]: crate::DecodeError::exceeded_budget
+    pub fn max_unknown_fields(mut self, max: usize) -> DecodeOptions {
+        self.max_unknown_fields = Some(max);
+        self
+    }
+
+    /// Sets whether to check that the fields declared `required` are present in each decoded
+    /// message, like the C++ and Java implementations do for proto2 messages.
+    ///
+    /// Once the input is decoded, the message is checked like [`Message::check_initialized`]
+    /// does, and decoding fails with a `DecodeError` of kind [`MissingRequiredField`] naming the
+    /// path of each missing field if the message, or one of its nested messages, lacks any of its
+    /// required fields. Only the fields which track their presence can be missing, see
+    /// [`Message::is_initialized`]. Fields which were present in a message before merging into it
+    /// are not missing. Required fields which are skipped by a projection, and the fields of
+    /// lazily decoded messages, are not checked. Not checked by default.
+    ///
+    /// [`Message::check_initialized`]: crate::Message::check_initialized
+    /// [`Message::is_initialized`]: crate::Message::is_initialized
+    /// [`MissingRequiredField`]: crate::DecodeErrorKind::MissingRequiredField
+    pub fn check_required_fields(mut self, check: bool) -> DecodeOptions {
+        self.check_required_fields = check;
+        self
+    }
+
+    /// Sets whether to reject input which does not match the schema of the decoded messages,
+    /// rather than skipping or merging it, to detect when the sender uses a different version of
+    /// the schema.
+    ///
+    /// When decoding strictly, decoding fails with a `DecodeError` whose path leads to the
+    /// offending field if:
+    ///
+    /// - a message contains a field number which is not part of its schema, with kind
+    ///   [`UnknownField`], even if the message keeps unknown fields. The path leads to the
+    ///   message, and the description names the field number.
+    /// - an enumeration field contains a value which is not a variant of the enumeration, with
+    ///   kind [`InvalidEnumValue`].
+    /// - a message or group field which is not repeated occurs more than once in a message, with
+    ///   kind [`DuplicateField`], including a message variant of a oneof.
+    ///
+    /// Fields which are skipped by a projection, and the fields of lazily decoded messages, are
+    /// not checked. Not strict by default.
+    ///
+    /// [`UnknownField`]: crate::DecodeErrorKind::UnknownField
+    /// [`InvalidEnumValue`]: crate::DecodeErrorKind::InvalidEnumValue
+    /// [`DuplicateField`]: crate::DecodeErrorKind::DuplicateField
+    pub fn strict(mut self, strict: bool) -> DecodeOptions {
+        self.strict = strict;
+        self
+    }
+}
+
+impl Default for DecodeOptions {
+    fn default() -> DecodeOptions {
+        DecodeOptions {
+            recursion_limit: crate::RECURSION_LIMIT,
+            max_total_bytes: None,
+            max_repeated_elements: None,
+            max_field_bytes: None,
+            max_unknown_fields: None,
+            check_required_fields: false,
+            strict: false,
+        }
+    }
+}
+
+/// The state of the budgets of a single decode, shared by all levels of nesting through
+/// `DecodeContext`.
+#[derive(Debug)]
+pub(crate) struct Budget {
+    remaining_bytes: Cell<usize>,
+    max_repeated_elements: usize,
+    max_field_bytes: usize,
+    max_unknown_fields: usize,
+}
+
+impl Budget {
+    /// Creates the budget for a decode with the given options, or `None` if no budgets are
+    /// configured.
+    pub(crate) fn new(options: &DecodeOptions) -> Option<Budget> {
+        if options.max_total_bytes.is_none()
+            && options.max_repeated_elements.is_none()
+            && options.max_field_bytes.is_none()
+            && options.max_unknown_fields.is_none()
+        {
+            return None;
+        }
+        Some(Budget {
+            remaining_bytes: Cell::new(options.max_total_bytes.unwrap_or(usize::MAX)),
+            max_repeated_elements: options.max_repeated_elements.unwrap_or(usize::MAX),
+            max_field_bytes: options.max_field_bytes.unwrap_or(usize::MAX),
+            max_unknown_fields: options.max_unknown_fields.unwrap_or(usize::MAX),
+        })
+    }
+
+    pub(crate) fn charge_bytes(&self, len: usize) -> Result<(), DecodeError> {
+        if len > self.max_field_bytes {
+            return Err(DecodeError::budget_exceeded(DecodeBudget::FieldBytes));
+        }
+        self.charge_total(len)
+    }
+
+    /// Checks a value of `len` bytes which is buffered before it is decoded, without charging it.
+    pub(crate) fn check_buffered(&self, len: usize) -> Result<(), DecodeError> {
+        if len > self.max_field_bytes {
+            return Err(DecodeError::budget_exceeded(DecodeBudget::FieldBytes));
+        }
+        if len > self.remaining_bytes.get() {
+            return Err(DecodeError::budget_exceeded(DecodeBudget::TotalBytes));
+        }
+        Ok(())
+    }
+
+    pub(crate) fn charge_element(&self, len: usize, size: usize) -> Result<(), DecodeError> {
+        if len >= self.max_repeated_elements {
+            return Err(DecodeError::budget_exceeded(DecodeBudget::RepeatedElements));
+        }
+        self.charge_total(size)
+    }
+
+    pub(crate) fn charge_unknown_field(&self, len: usize, size: usize) -> Result<(), DecodeError> {
+        if len >= self.max_unknown_fields {
+            return Err(DecodeError::budget_exceeded(DecodeBudget::UnknownFields));
+        }
+        self.charge_total(size)
+    }
+
+    fn charge_total(&self, len: usize) -> Result<(), DecodeError> {
+        match self.remaining_bytes.get().checked_sub(len) {
+            Some(remaining) => {
+                self.remaining_bytes.set(remaining);
+                Ok(())
+            }
+            None => Err(DecodeError::budget_exceeded(DecodeBudget::TotalBytes)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use alloc::string::String;
+    use alloc::vec;
+    use alloc::vec::Vec;
+    use core::mem;
+
+    use bytes::{Buf, BufMut};
+
+    use crate::encoding::{encode_key, skip_field, string, uint32, DecodeContext, WireType};
+    use crate::{Message, UnknownFieldSet};
+
+    /// A message with a hand-written implementation, which names `DecodeContext` without its
+    /// lifetime like implementations written before it had one.
+    #[derive(Debug, Default, PartialEq)]
+    struct Values {
+        values: Vec<u32>,
+        names: Vec<String>,
+    }
+
+    impl Message for Values {
+        fn encode_raw<B>(&self, buf: &mut B)
+        where
+            B: BufMut,
+        {
+            uint32::encode_packed(1, &self.values, buf);
+            string::encode_repeated(2, &self.names, buf);
+        }
+
+        fn merge_field<B>(
+            &mut self,
+            tag: u32,
+            wire_type: WireType,
+            buf: &mut B,
+            ctx: DecodeContext,
+        ) -> Result<(), DecodeError>
+        where
+            B: Buf,
+        {
+            match tag {
+                1 => uint32::merge_repeated(wire_type, &mut self.values, buf, ctx),
+                2 => string::merge_repeated(wire_type, &mut self.names, buf, ctx),
+                _ => skip_field(wire_type, tag, buf, ctx),
+            }
+        }
+
+        fn encoded_len(&self) -> usize {
+            uint32::encoded_len_packed(1, &self.values)
+                + string::encoded_len_repeated(2, &self.names)
+        }
+
+        fn clear(&mut self) {
+            self.values.clear();
+            self.names.clear();
+        }
+    }
+
+    fn values(values: usize, names: &[&str]) -> Vec<u8> {
+        Values {
+            values: vec![1; values],
+            names: names.iter().map(|&name| String::from(name)).collect(),
+        }
+        .encode_to_vec()
+    }
+
+    fn exceeded<M>(buf: &[u8], options: &DecodeOptions) -> Option<DecodeBudget>
+    where
+        M: Message + Default,
+    {
+        M::decode_with_options(buf, options)
+            .err()
+            .map(|error| error.exceeded_budget().expect("budget exceeded"))
+    }
+
+    fn nested_groups(depth: u32) -> Vec<u8> {
+        let mut buf = Vec::new();
+        for _ in 0..depth {
+            encode_key(1, WireType::StartGroup, &mut buf);
+        }
+        for _ in 0..depth {
+            encode_key(1, WireType::EndGroup, &mut buf);
+        }
+        buf
+    }
+
+    #[test]
+    fn recursion_limit() {
+        let buf = nested_groups(20);
+
+        let options = DecodeOptions::new().recursion_limit(10);
+        assert!(UnknownFieldSet::decode_with_options(buf.as_slice(), &options).is_err());
+
+        let options = DecodeOptions::new().recursion_limit(20);
+        let set = UnknownFieldSet::decode_with_options(buf.as_slice(), &options).unwrap();
+        assert_eq!(set.encode_to_vec(), buf);
+
+        let mut set = UnknownFieldSet::new();
+        assert!(set.merge_with_options(buf.as_slice(), &options).is_ok());
+        assert_eq!(set.encode_to_vec(), buf);
+    }
+
+    #[test]
+    #[cfg(not(feature = "no-recursion-limit"))]
+    fn default_recursion_limit() {
+        let buf = nested_groups(150);
+        assert!(UnknownFieldSet::decode(buf.as_slice()).is_err());
+
+        let options = DecodeOptions::new().recursion_limit(150);
+        assert!(UnknownFieldSet::decode_with_options(buf.as_slice(), &options).is_ok());
+    }
+
+    #[test]
+    fn max_repeated_elements() {
+        let buf = values(10, &[]);
+        let options = DecodeOptions::new().max_repeated_elements(10);
+        assert_eq!(exceeded::<Values>(&buf, &options), None);
+        let options = DecodeOptions::new().max_repeated_elements(9);
+        assert_eq!(
+            exceeded::<Values>(&buf, &options),
+            Some(DecodeBudget::RepeatedElements)
+        );
+    }
+
+    #[test]
+    fn max_field_bytes() {
+        let buf = values(0, &["abcd", "ab"]);
+        let options = DecodeOptions::new().max_field_bytes(4);
+        assert_eq!(exceeded::<Values>(&buf, &options), None);
+        let options = DecodeOptions::new().max_field_bytes(3);
+        assert_eq!(
+            exceeded::<Values>(&buf, &options),
+            Some(DecodeBudget::FieldBytes)
+        );
+    }
+
+    #[test]
+    fn max_total_bytes() {
+        let buf = values(2, &["abcd", "ab"]);
+        // The packed values and the names, and the contents of the names.
+        let total = 2 * mem::size_of::<u32>() + 2 * mem::size_of::<String>() + 6;
+        let options = DecodeOptions::new().max_total_bytes(total);
+        assert_eq!(exceeded::<Values>(&buf, &options), None);
+        let options = DecodeOptions::new().max_total_bytes(total - 1);
+        assert_eq!(
+            exceeded::<Values>(&buf, &options),
+            Some(DecodeBudget::TotalBytes)
+        );
+    }
+
+    #[test]
+    fn max_unknown_fields() {
+        let buf = values(0, &["a", "b", "c"]);
+        // Unknown fields are not repeated elements.
+        let options = DecodeOptions::new().max_repeated_elements(1);
+        assert_eq!(exceeded::<UnknownFieldSet>(&buf, &options), None);
+
+        let options = DecodeOptions::new().max_unknown_fields(3);
+        assert_eq!(exceeded::<UnknownFieldSet>(&buf, &options), None);
+        let options = DecodeOptions::new().max_unknown_fields(2);
+        assert_eq!(
+            exceeded::<UnknownFieldSet>(&buf, &options),
+            Some(DecodeBudget::UnknownFields)
+        );
+    }
+}
@@ -0,0 +1,191 @@
+//! Decoding only selected fields of a message.
+//!
+//! A [`Projection`] is a set of paths of field numbers. Fields which are not selected by the
+//! projection are skipped without being decoded, and message fields which are skipped are never
+//! allocated, which makes decoding a few fields of large messages much cheaper:
+//!
+//! ```rust
+//! # use prost::{Message, Projection};
+//! #[derive(Clone, PartialEq, Message)]
+//! struct Record {
+//!     #[prost(string, tag = "1")]
+//!     id: String,
+//!     #[prost(message, optional, tag = "2")]
+//!     header: Option<Header>,
+//!     #[prost(bytes = "vec", tag = "3")]
+//!     payload: Vec<u8>,
+//! }
+//!
+//! #[derive(Clone, PartialEq, Message)]
+//! struct Header {
+//!     #[prost(int64, tag = "1")]
+//!     timestamp: i64,
+//!     #[prost(string, repeated, tag = "2")]
+//!     labels: Vec<String>,
+//! }
+//!
+//! let record = Record {
+//!     id: "r1".to_string(),
+//!     header: Some(Header { timestamp: 42, labels: vec!["a".to_string()] }),
+//!     payload: vec![0; 1024],
+//! };
+//! let buf = record.encode_to_vec();
+//!
+//! // Select `id` and `header.timestamp`.
+//! let projection = Projection::new().path(&[1]).path(&[2, 1]);
+//! let projected = Record::decode_projected(buf.as_slice(), &projection).unwrap();
+//! assert_eq!(projected.id, "r1");
+//! assert_eq!(projected.header, Some(Header { timestamp: 42, labels: vec![] }));
+//! assert!(projected.payload.is_empty());
+//! ```
+//!
+//! A path which descends into a repeated message field applies to each of its elements, and a path
+//! which descends into a map field applies to the fields of each of its values. Fields which are
+//! skipped are not kept as unknown fields.
+//!
+//! `prost-build` can generate projections from paths of field names with `Config::projection`.
+
+use alloc::collections::BTreeMap;
+
+use bytes::Buf;
+
+use crate::encoding::{skip_field, DecodeContext, WireType};
+use crate::strict::FieldChecks;
+use crate::{DecodeError, Message};
+
+/// A set of paths of field numbers selecting the fields to decode.
+///
+/// A path selects the whole field named by its last field number, including all of its nested
+/// fields when it is a message field. Paths which are covered by another path of the projection
+/// are redundant.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Projection {
+    fields: BTreeMap<u32, Projection>,
+}
+
+impl Projection {
+    /// Creates an empty projection, which selects no fields.
+    pub fn new() -> Projection {
+        Projection::default()
+    }
+
+    /// Creates a projection selecting the given paths of field numbers.
+    pub fn from_paths<I, P>(paths: I) -> Projection
+    where
+        I: IntoIterator<Item = P>,
+        P: AsRef<[u32]>,
+    {
+        paths
+            .into_iter()
+            .fold(Projection::new(), |projection, path| {
+                projection.path(path.as_ref())
+            })
+    }
+
+    /// Adds a path of field numbers to the projection.
+    ///
+    /// The first field number names a field of the message being decoded, and each following
+    /// field number names a field of the message type of the previous field. Empty paths are
+    /// ignored.
+    pub fn path(mut self, path: &[u32]) -> Projection {
+        let (last, parents) = match path.split_last() {
+            Some(split) => split,
+            None => return self,
+        };
+        let mut node = &mut self;
+        for tag in parents {
+            let exists = node.fields.contains_key(tag);
+            node = node.fields.entry(*tag).or_default();
+            if exists && node.fields.is_empty() {
+                // The parent field is already selected as a whole.
+                return self;
+            }
+        }
+        node.fields.insert(*last, Projection::new());
+        self
+    }
+
+    /// Returns `true` if the projection selects the field, or some of its nested fields.
+    pub fn contains(&self, tag: u32) -> bool {
+        self.fields.contains_key(&tag)
+    }
+
+    /// Returns the projection of the nested fields of a selected field, or `None` if the whole
+    /// field is selected or the field is not selected at all.
+    pub fn get(&self, tag: u32) -> Option<&Projection> {
+        self.fields
+            .get(&tag)
+            .filter(|projection| !projection.fields.is_empty())
+    }
+
+    /// Returns `true` if the projection selects no fields.
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty()
+    }
+}
+
+/// Decodes a field into a message and records it in `checks`, or skips it if the projection of
+/// the context does not select it.
+///
+/// Skipped fields are not recorded, so a required field which is skipped is not reported missing,
+/// and a skipped field is not checked by [`DecodeOptions::strict`].
+///
+/// [`DecodeOptions::strict`]: crate::DecodeOptions::strict
+#[inline]
+pub(crate) fn merge_field<M, B>(
+    msg: &mut M,
+    tag: u32,
+    wire_type: WireType,
+    buf: &mut B,
+    ctx: DecodeContext,
+    checks: &mut FieldChecks,
+) -> Result<(), DecodeError>
+where
+    M: Message,
+    B: Buf,
+{
+    match ctx.projection() {
+        None => {
+            checks.decoded(tag)?;
+            msg.merge_field(tag, wire_type, buf, ctx)
+        }
+        Some(projection) => match projection.fields.get(&tag) {
+            None => skip_field(wire_type, tag, buf, ctx),
+            Some(field) => {
+                checks.decoded(tag)?;
+                let field = Some(field).filter(|field| !field.fields.is_empty());
+                msg.merge_field(tag, wire_type, buf, ctx.with_projection(field))
+            }
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn path() {
+        let projection = Projection::new().path(&[1, 2]).path(&[1, 3]).path(&[4]);
+        assert!(projection.contains(1));
+        assert!(!projection.contains(2));
+        assert_eq!(projection.get(4), None);
+        let nested = projection.get(1).unwrap();
+        assert!(nested.contains(2) && nested.contains(3));
+
+        // Paths covered by another path are redundant.
+        assert_eq!(
+            Projection::new().path(&[1]).path(&[1, 2]),
+            Projection::new().path(&[1])
+        );
+        assert_eq!(
+            Projection::new().path(&[1, 2]).path(&[1]),
+            Projection::new().path(&[1])
+        );
+        assert_eq!(
+            Projection::from_paths([&[1, 2][..], &[3]]),
+            Projection::new().path(&[1, 2]).path(&[3])
+        );
+        assert!(Projection::new().path(&[]).is_empty());
+    }
+}
@@ -0,0 +1,160 @@
+//! Checking the presence of required fields.
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use core::fmt;
+use core::fmt::Write;
+
+use crate::{DecodeError, DecodeErrorKind, EncodeError, Message, Projection};
+
+/// The paths of the missing required fields found in a message and its nested messages.
+///
+/// A path names the fields leading to the missing field, starting from the outermost message,
+/// with the index of repeated elements and the key of map entries, e.g. `members[1].email`.
+///
+/// Meant to be used only by `Message` implementations.
+#[doc(hidden)]
+#[derive(Debug, Default)]
+pub struct MissingFieldPaths {
+    /// The path of the message being searched, ending with a `.` unless it is the outermost.
+    path: String,
+    missing: Vec<String>,
+}
+
+impl MissingFieldPaths {
+    /// Returns `true` if no missing required field was found.
+    pub fn is_empty(&self) -> bool {
+        self.missing.is_empty()
+    }
+
+    /// Records a missing required field of the message being searched, unless the projection
+    /// does not select it.
+    pub fn missing(&mut self, projection: Option<&Projection>, tag: u32, field: &str) {
+        if projection.map_or(true, |projection| projection.contains(tag)) {
+            self.missing.push(format!("{}{}", self.path, field));
+        }
+    }
+
+    /// Searches a message field of the message being searched, unless the projection does not
+    /// select it.
+    pub fn message<M>(&mut self, projection: Option<&Projection>, tag: u32, field: &str, msg: &M)
+    where
+        M: Message + ?Sized,
+    {
+        self.nested(projection, tag, |paths, projection| {
+            paths.search(projection, format_args!("{}", field), msg)
+        });
+    }
+
+    /// Searches the elements of a repeated message field.
+    pub fn repeated<M>(
+        &mut self,
+        projection: Option<&Projection>,
+        tag: u32,
+        field: &str,
+        messages: &[M],
+    ) where
+        M: Message,
+    {
+        self.nested(projection, tag, |paths, projection| {
+            for (index, msg) in messages.iter().enumerate() {
+                paths.search(projection, format_args!("{}[{}]", field, index), msg);
+            }
+        });
+    }
+
+    /// Searches the values of a map field with message values.
+    pub fn map<'a, K, M, I>(
+        &mut self,
+        projection: Option<&Projection>,
+        tag: u32,
+        field: &str,
+        entries: I,
+    ) where
+        K: fmt::Debug + 'a,
+        M: Message + 'a,
+        I: Iterator<Item = (&'a K, &'a M)>,
+    {
+        self.nested(projection, tag, |paths, projection| {
+            for (key, msg) in entries {
+                paths.search(projection, format_args!("{}[{:?}]", field, key), msg);
+            }
+        });
+    }
+
+    /// Calls `search` with the projection of the nested fields of a field, unless the projection
+    /// does not select the field.
+    fn nested<F>(&mut self, projection: Option<&Projection>, tag: u32, search: F)
+    where
+        F: FnOnce(&mut MissingFieldPaths, Option<&Projection>),
+    {
+        match projection {
+            None => search(self, None),
+            Some(projection) if projection.contains(tag) => search(self, projection.get(tag)),
+            Some(_) => (),
+        }
+    }
+
+    /// Searches a nested message, whose path is the path of the message being searched followed
+    /// by `segment`.
+    fn search<M>(&mut self, projection: Option<&Projection>, segment: fmt::Arguments, msg: &M)
+    where
+        M: Message + ?Sized,
+    {
+        let len = self.path.len();
+        // Writing to a `String` can't fail.
+        let _ = write!(self.path, "{}.", segment);
+        msg.find_missing_fields(projection, self);
+        self.path.truncate(len);
+    }
+}
+
+/// Returns the paths of the missing required fields of a message and its nested messages, which
+/// are selected by the projection, if any.
+fn find<M>(msg: &M, projection: Option<&Projection>) -> Vec<String>
+where
+    M: Message + ?Sized,
+{
+    let mut paths = MissingFieldPaths::default();
+    msg.find_missing_fields(projection, &mut paths);
+    paths.missing
+}
+
+/// Returns the description of an error for the paths of missing required fields.
+pub(crate) fn describe(missing: &[String]) -> String {
+    if missing.len() == 1 {
+        format!("missing required field: {}", missing[0])
+    } else {
+        format!("missing required fields: {}", missing.join(", "))
+    }
+}
+
+/// Returns an error naming the missing required fields of a decoded message and its nested
+/// messages, which are selected by the projection, if any.
+pub(crate) fn check<M>(msg: &M, projection: Option<&Projection>) -> Result<(), DecodeError>
+where
+    M: Message + ?Sized,
+{
+    let missing = find(msg, projection);
+    if missing.is_empty() {
+        return Ok(());
+    }
+    let mut error =
+        DecodeError::with_kind(DecodeErrorKind::MissingRequiredField, describe(&missing));
+    error.set_missing_fields(missing);
+    Err(error)
+}
+
+/// Returns an error naming the missing required fields of a message and its nested messages.
+pub(crate) fn check_initialized<M>(msg: &M) -> Result<(), EncodeError>
+where
+    M: Message + ?Sized,
+{
+    let missing = find(msg, None);
+    if missing.is_empty() {
+        return Ok(());
+    }
+    Err(EncodeError::missing_required_fields(missing))
+}
@@ -0,0 +1,129 @@
+//! Checking the fields of messages decoded strictly.
+
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+
+use crate::encoding::{DecodeContext, WireType};
+use crate::{DecodeError, DecodeErrorKind, Message};
+
+/// Returns the singular message field with a given number of a message type, if any.
+pub(crate) type MessageFields = fn(u32) -> Option<SingularMessageField>;
+
+/// A message or group field which is not repeated, or a variant of a oneof which holds a message
+/// or group, as returned by `Message::singular_message_field`.
+///
+/// Meant to be used only by `Message` implementations.
+#[doc(hidden)]
+#[derive(Clone, Copy, Debug)]
+pub struct SingularMessageField {
+    /// The name of the message type which has the field.
+    pub(crate) message: &'static str,
+    /// The name of the field.
+    pub(crate) field: &'static str,
+    /// The wire type of the field, either `LengthDelimited` or `StartGroup`.
+    pub(crate) wire_type: WireType,
+    /// The singular message fields of the message type of the field, or `None` if the field is
+    /// decoded lazily.
+    pub(crate) nested: Option<MessageFields>,
+}
+
+impl SingularMessageField {
+    /// Describes a field holding a message of type `M`, or a group if `group` is `true`.
+    pub fn new<M>(
+        message: &'static str,
+        field: &'static str,
+        group: bool,
+        _: PhantomData<M>,
+    ) -> SingularMessageField
+    where
+        M: Message,
+    {
+        SingularMessageField {
+            message,
+            field,
+            wire_type: if group {
+                WireType::StartGroup
+            } else {
+                WireType::LengthDelimited
+            },
+            nested: Some(M::singular_message_field),
+        }
+    }
+
+    /// Describes a field holding an optional message of type `M`, or an optional group if `group`
+    /// is `true`.
+    pub fn optional<M>(
+        message: &'static str,
+        field: &'static str,
+        group: bool,
+        _: PhantomData<Option<M>>,
+    ) -> SingularMessageField
+    where
+        M: Message,
+    {
+        SingularMessageField::new(message, field, group, PhantomData::<M>)
+    }
+
+    /// Describes a field holding a lazily decoded message.
+    pub fn lazy(message: &'static str, field: &'static str) -> SingularMessageField {
+        SingularMessageField {
+            message,
+            field,
+            wire_type: WireType::LengthDelimited,
+            nested: None,
+        }
+    }
+}
+
+/// The singular message fields decoded so far for an occurrence of a message in the input, which
+/// may occur only once when decoding with [`DecodeOptions::strict`].
+///
+/// A message with no such fields, or a decode which is not strict, does not allocate.
+///
+/// [`DecodeOptions::strict`]: crate::DecodeOptions::strict
+#[derive(Debug)]
+pub(crate) struct FieldChecks {
+    /// Returns the singular message field with a given number, when decoding strictly.
+    singular: Option<MessageFields>,
+    /// The numbers of the singular message fields which have been decoded.
+    decoded: Vec<u32>,
+}
+
+impl FieldChecks {
+    /// Starts checking the fields of a message of type `M` decoded with `ctx`.
+    #[inline]
+    pub(crate) fn new<M>(ctx: &DecodeContext) -> FieldChecks
+    where
+        M: Message,
+    {
+        FieldChecks::of(M::singular_message_field, ctx)
+    }
+
+    /// Starts checking the fields of a message whose singular message fields are looked up with
+    /// `fields`, decoded with `ctx`.
+    #[inline]
+    pub(crate) fn of(fields: MessageFields, ctx: &DecodeContext) -> FieldChecks {
+        FieldChecks {
+            singular: if ctx.strict() { Some(fields) } else { None },
+            decoded: Vec::new(),
+        }
+    }
+
+    /// Records that a field with the given number was decoded, failing if it is a singular message
+    /// field which was already decoded.
+    #[inline]
+    pub(crate) fn decoded(&mut self, tag: u32) -> Result<(), DecodeError> {
+        if let Some(field) = self.singular.and_then(|singular| singular(tag)) {
+            if self.decoded.contains(&tag) {
+                let mut error = DecodeError::with_kind(
+                    DecodeErrorKind::DuplicateField,
+                    "message field occurs more than once",
+                );
+                error.push(field.message, field.field);
+                return Err(error);
+            }
+            self.decoded.push(tag);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,1160 @@
+//! The Protobuf text format.
+//!
+//! The text format is a human-readable representation of messages, commonly used for
+//! configuration files and test fixtures:
+//!
+//! ```text
+//! name: "prost"
+//! version { major: 0 minor: 12 }
+//! kind: LIBRARY
+//! tags: ["protobuf", "serialization"]
+//! ```
+//!
+//! Implementations of [`TextFormat`] are generated by `prost-build` when enabled with
+//! `Config::enable_text_format`. Fields are named by their names in the `.proto` definition,
+//! groups by the name of their message type, and enumeration values by their names, falling back
+//! to the number of values which are not known to the enumeration. String and bytes values use the
+//! same escape sequences as default values in `.proto` files.
+//!
+//! Extension fields and expanded `Any` messages (`[type.googleapis.com/foo.Bar] { ... }`) can not
+//! be parsed, and unknown fields are only printed when enabled with
+//! [`PrintOptions::unknown_fields`], since they can not be parsed either.
+
+use alloc::borrow::Cow;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+
+use bytes::Bytes;
+
+use crate::{Lazy, Message, UnknownField, UnknownFieldValue};
+
+/// A message which can be printed and parsed in the Protobuf text format.
+pub trait TextFormat: Message {
+    /// Prints the fields of the message.
+    ///
+    /// Meant to be used only by `TextFormat` implementations.
+    #[doc(hidden)]
+    fn print_fields(&self, printer: &mut Printer<'_>);
+
+    /// Parses the value of the field with the given name, and merges it into `self`.
+    ///
+    /// Returns `false` if the message has no field with the name. Meant to be used only by
+    /// `TextFormat` implementations.
+    #[doc(hidden)]
+    fn merge_text_field(&mut self, name: &str, parser: &mut Parser<'_>)
+        -> Result<bool, ParseError>;
+
+    /// Prints the message in the text format, on a single line.
+    fn to_text(&self) -> String
+    where
+        Self: Sized,
+    {
+        self.to_text_with_options(&PrintOptions::new())
+    }
+
+    /// Prints the message in the text format, with a field per line and nested messages indented.
+    fn to_text_pretty(&self) -> String
+    where
+        Self: Sized,
+    {
+        self.to_text_with_options(&PrintOptions::new().pretty(true))
+    }
+
+    /// Prints the message in the text format, using the given options.
+    fn to_text_with_options(&self, options: &PrintOptions) -> String
+    where
+        Self: Sized,
+    {
+        let mut buf = String::new();
+        self.print_fields(&mut Printer {
+            buf: &mut buf,
+            options,
+            depth: 0,
+        });
+        buf
+    }
+
+    /// Parses a message in the text format, and merges it into `self`.
+    fn merge_text(&mut self, text: &str) -> Result<(), ParseError>
+    where
+        Self: Sized,
+    {
+        let mut parser = Parser::new(text);
+        parser.merge_fields(
+            &mut |parser, name| self.merge_text_field(name, parser),
+            None,
+        )
+    }
+
+    /// Parses a message in the text format.
+    fn parse_text(text: &str) -> Result<Self, ParseError>
+    where
+        Self: Default,
+    {
+        let mut message = Self::default();
+        message.merge_text(text)?;
+        Ok(message)
+    }
+}
+
+/// Options for printing a message in the text format, used with
+/// [`TextFormat::to_text_with_options`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PrintOptions {
+    pretty: bool,
+    unknown_fields: bool,
+}
+
+impl PrintOptions {
+    /// Creates the default print options.
+    pub fn new() -> PrintOptions {
+        PrintOptions::default()
+    }
+
+    /// Sets whether each field is printed on its own line, with nested messages indented.
+    ///
+    /// Defaults to `false`, which prints the message on a single line.
+    pub fn pretty(mut self, pretty: bool) -> PrintOptions {
+        self.pretty = pretty;
+        self
+    }
+
+    /// Sets whether unknown fields and extensions are printed, by field number.
+    ///
+    /// Defaults to `false`, since the output can not be parsed if it contains unknown fields.
+    pub fn unknown_fields(mut self, unknown_fields: bool) -> PrintOptions {
+        self.unknown_fields = unknown_fields;
+        self
+    }
+}
+
+/// A text format parse error.
+///
+/// The line and column of the error are counted from 1, with columns counted in characters.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    description: Cow<'static, str>,
+    line: usize,
+    column: usize,
+}
+
+impl ParseError {
+    /// Returns the line of the input where the error occurred.
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// Returns the column of the input where the error occurred.
+    pub fn column(&self) -> usize {
+        self.column
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "failed to parse Protobuf text format: {} at line {}, column {}",
+            self.description, self.line, self.column
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseError {}
+
+/// Prints the fields of a message in the text format.
+///
+/// Meant to be used only by `TextFormat` implementations.
+#[doc(hidden)]
+pub struct Printer<'a> {
+    buf: &'a mut String,
+    options: &'a PrintOptions,
+    depth: usize,
+}
+
+impl Printer<'_> {
+    /// Prints a string, bytes, bool or numeric field.
+    pub fn scalar<T>(&mut self, name: &str, value: &T)
+    where
+        T: Scalar,
+    {
+        self.start_field(name);
+        self.buf.push_str(": ");
+        value.print(self.buf);
+        self.end_field();
+    }
+
+    /// Prints an enumeration field, by the name of its value if the value is known.
+    pub fn enumeration(
+        &mut self,
+        name: &str,
+        value: i32,
+        as_str_name: fn(i32) -> Option<&'static str>,
+    ) {
+        self.start_field(name);
+        self.buf.push_str(": ");
+        match as_str_name(value) {
+            Some(value) => self.buf.push_str(value),
+            None => value.print(self.buf),
+        }
+        self.end_field();
+    }
+
+    /// Prints a message or group field.
+    pub fn message<M>(&mut self, name: &str, message: &M)
+    where
+        M: TextFormat,
+    {
+        self.block(name, |printer| message.print_fields(printer));
+    }
+
+    /// Prints a lazily decoded message field.
+    ///
+    /// If the message can not be decoded, it is printed as an unknown field.
+    pub fn lazy<M>(&mut self, name: &str, number: u32, lazy: &Lazy<M>)
+    where
+        M: TextFormat + Default,
+    {
+        match (lazy.get(), lazy.encoded()) {
+            (Ok(message), _) => self.message(name, message),
+            (Err(_), Some(encoded)) => self.scalar(&number.to_string(), encoded),
+            (Err(_), None) => (),
+        }
+    }
+
+    /// Prints a field in a block, such as a message or a map entry.
+    pub fn block(&mut self, name: &str, fields: impl FnOnce(&mut Printer<'_>)) {
+        self.start_field(name);
+        self.buf.push_str(" {");
+        if self.options.pretty {
+            self.buf.push('\n');
+        }
+        self.depth += 1;
+        fields(self);
+        self.depth -= 1;
+        if self.options.pretty {
+            self.indent();
+            self.buf.push_str("}\n");
+        } else {
+            self.buf.push_str(" }");
+        }
+    }
+
+    /// Prints unknown fields or extensions, if enabled in the options.
+    pub fn unknown_fields<'b>(&mut self, fields: impl IntoIterator<Item = &'b UnknownField>) {
+        if !self.options.unknown_fields {
+            return;
+        }
+        for field in fields {
+            let name = field.number.to_string();
+            match field.value {
+                UnknownFieldValue::Varint(value) => self.scalar(&name, &value),
+                UnknownFieldValue::SixtyFourBit(value) => {
+                    self.start_field(&name);
+                    self.buf.push_str(&format!(": 0x{:016x}", value));
+                    self.end_field();
+                }
+                UnknownFieldValue::LengthDelimited(ref value) => self.scalar(&name, value),
+                UnknownFieldValue::Group(ref fields) => {
+                    self.block(&name, |printer| printer.unknown_fields(fields.iter()))
+                }
+                UnknownFieldValue::ThirtyTwoBit(value) => {
+                    self.start_field(&name);
+                    self.buf.push_str(&format!(": 0x{:08x}", value));
+                    self.end_field();
+                }
+            }
+        }
+    }
+
+    fn start_field(&mut self, name: &str) {
+        if self.options.pretty {
+            self.indent();
+        } else if !self.buf.is_empty() {
+            self.buf.push(' ');
+        }
+        self.buf.push_str(name);
+    }
+
+    fn end_field(&mut self) {
+        if self.options.pretty {
+            self.buf.push('\n');
+        }
+    }
+
+    fn indent(&mut self) {
+        for _ in 0..self.depth {
+            self.buf.push_str("  ");
+        }
+    }
+}
+
+/// Parses the fields of a message in the text format.
+///
+/// Meant to be used only by `TextFormat` implementations.
+#[doc(hidden)]
+pub struct Parser<'a> {
+    input: &'a str,
+    pos: usize,
+    /// The remaining number of nested blocks which may be parsed.
+    recurse_count: u32,
+}
+
+type MergeField<'p, 'a> = dyn FnMut(&mut Parser<'a>, &str) -> Result<bool, ParseError> + 'p;
+
+impl<'a> Parser<'a> {
+    fn new(input: &'a str) -> Parser<'a> {
+        Parser {
+            input,
+            pos: 0,
+            recurse_count: crate::RECURSION_LIMIT,
+        }
+    }
+
+    /// Parses a string, bytes, bool or numeric field.
+    pub fn scalar<T>(&mut self, value: &mut T) -> Result<(), ParseError>
+    where
+        T: Scalar,
+    {
+        self.expect(b':')?;
+        *value = T::parse(self)?;
+        Ok(())
+    }
+
+    /// Parses a repeated string, bytes, bool or numeric field.
+    pub fn repeated_scalar<T>(&mut self, values: &mut Vec<T>) -> Result<(), ParseError>
+    where
+        T: Scalar,
+    {
+        self.repeated(false, |parser| {
+            values.push(T::parse(parser)?);
+            Ok(())
+        })
+    }
+
+    /// Parses an enumeration field, given by the name or the number of its value.
+    pub fn enumeration(
+        &mut self,
+        value: &mut i32,
+        from_str_name: fn(&str) -> Option<i32>,
+    ) -> Result<(), ParseError> {
+        self.expect(b':')?;
+        *value = self.enumeration_value(from_str_name)?;
+        Ok(())
+    }
+
+    /// Parses a repeated enumeration field.
+    pub fn repeated_enumeration(
+        &mut self,
+        values: &mut Vec<i32>,
+        from_str_name: fn(&str) -> Option<i32>,
+    ) -> Result<(), ParseError> {
+        self.repeated(false, |parser| {
+            values.push(parser.enumeration_value(from_str_name)?);
+            Ok(())
+        })
+    }
+
+    /// Parses a message or group field, and merges it into the message.
+    pub fn message<M>(&mut self, message: &mut M) -> Result<(), ParseError>
+    where
+        M: TextFormat,
+    {
+        self.try_consume(b':');
+        self.message_value(message)
+    }
+
+    /// Parses a repeated message or group field.
+    pub fn repeated_message<M>(&mut self, messages: &mut Vec<M>) -> Result<(), ParseError>
+    where
+        M: TextFormat + Default,
+    {
+        self.repeated(true, |parser| {
+            let mut message = M::default();
+            parser.message_value(&mut message)?;
+            messages.push(message);
+            Ok(())
+        })
+    }
+
+    /// Parses a lazily decoded message field, and merges it into the message.
+    pub fn lazy<M>(&mut self, lazy: &mut Lazy<M>) -> Result<(), ParseError>
+    where
+        M: TextFormat + Default,
+    {
+        self.try_consume(b':');
+        self.lazy_value(lazy)
+    }
+
+    /// Parses a repeated lazily decoded message field.
+    pub fn repeated_lazy<M>(&mut self, values: &mut Vec<Lazy<M>>) -> Result<(), ParseError>
+    where
+        M: TextFormat + Default,
+    {
+        self.repeated(true, |parser| {
+            let mut lazy = Lazy::default();
+            parser.lazy_value(&mut lazy)?;
+            values.push(lazy);
+            Ok(())
+        })
+    }
+
+    /// Parses the values of a repeated field, which are either given by a single value or by a
+    /// list of values in brackets. The separating colon is optional for message values.
+    pub fn repeated(
+        &mut self,
+        message: bool,
+        mut value: impl FnMut(&mut Parser<'a>) -> Result<(), ParseError>,
+    ) -> Result<(), ParseError> {
+        if message {
+            self.try_consume(b':');
+        } else {
+            self.expect(b':')?;
+        }
+        if !self.try_consume(b'[') {
+            return value(self);
+        }
+        if self.try_consume(b']') {
+            return Ok(());
+        }
+        loop {
+            value(self)?;
+            if self.try_consume(b']') {
+                return Ok(());
+            }
+            self.expect(b',')?;
+        }
+    }
+
+    /// Parses the fields of a block in braces or angle brackets, such as a message or a map entry.
+    ///
+    /// `merge_field` is called with the name of each field, and returns `false` if the name is not
+    /// known.
+    pub fn block(
+        &mut self,
+        mut merge_field: impl FnMut(&mut Parser<'a>, &str) -> Result<bool, ParseError>,
+    ) -> Result<(), ParseError> {
+        let close = if self.try_consume(b'{') {
+            b'}'
+        } else if self.try_consume(b'<') {
+            b'>'
+        } else {
+            return Err(self.error("expected '{' or '<'"));
+        };
+        if self.recurse_count == 0 {
+            return Err(self.error("recursion limit reached"));
+        }
+        self.recurse_count -= 1;
+        self.merge_fields(&mut merge_field, Some(close))?;
+        self.recurse_count += 1;
+        Ok(())
+    }
+
+    /// Parses a message value in braces or angle brackets, and merges it into the message.
+    pub fn message_value<M>(&mut self, message: &mut M) -> Result<(), ParseError>
+    where
+        M: TextFormat,
+    {
+        self.block(|parser, name| message.merge_text_field(name, parser))
+    }
+
+    /// Parses an enumeration value, given by its name or number.
+    pub fn enumeration_value(
+        &mut self,
+        from_str_name: fn(&str) -> Option<i32>,
+    ) -> Result<i32, ParseError> {
+        self.skip_whitespace();
+        let start = self.pos;
+        match self.identifier() {
+            Some(name) => from_str_name(name).ok_or_else(|| {
+                self.error_at(start, format!("unknown enumeration value '{}'", name))
+            }),
+            None => i32::parse(self),
+        }
+    }
+
+    fn lazy_value<M>(&mut self, lazy: &mut Lazy<M>) -> Result<(), ParseError>
+    where
+        M: TextFormat + Default,
+    {
+        let start = self.pos;
+        match lazy.get_mut() {
+            Ok(message) => self.message_value(message),
+            Err(error) => Err(self.error_at(start, error.to_string())),
+        }
+    }
+
+    /// Parses fields until the closing delimiter, or until the end of the input if there is none.
+    fn merge_fields(
+        &mut self,
+        merge_field: &mut MergeField<'_, 'a>,
+        close: Option<u8>,
+    ) -> Result<(), ParseError> {
+        loop {
+            match (self.peek(), close) {
+                (None, None) => return Ok(()),
+                (None, Some(close)) => {
+                    return Err(self.error(format!("expected '{}'", close as char)))
+                }
+                (Some(next), Some(close)) if next == close => {
+                    self.pos += 1;
+                    return Ok(());
+                }
+                (Some(b'['), _) => {
+                    return Err(
+                        self.error("extension and Any type URL field names are not supported")
+                    )
+                }
+                _ => (),
+            }
+            let start = self.pos;
+            let name = self
+                .identifier()
+                .ok_or_else(|| self.error("expected a field name"))?;
+            if !merge_field(self, name)? {
+                return Err(self.error_at(start, format!("unknown field '{}'", name)));
+            }
+            if !self.try_consume(b',') {
+                self.try_consume(b';');
+            }
+        }
+    }
+
+    /// Skips whitespace and comments, and returns the next byte of the input.
+    fn peek(&mut self) -> Option<u8> {
+        self.skip_whitespace();
+        self.input.as_bytes().get(self.pos).copied()
+    }
+
+    fn skip_whitespace(&mut self) {
+        let bytes = self.input.as_bytes();
+        while let Some(&byte) = bytes.get(self.pos) {
+            match byte {
+                b' ' | b'\t' | b'\n' | b'\r' | b'\x0b' | b'\x0c' => self.pos += 1,
+                b'#' => {
+                    while bytes.get(self.pos).is_some_and(|&byte| byte != b'\n') {
+                        self.pos += 1;
+                    }
+                }
+                _ => break,
+            }
+        }
+    }
+
+    fn try_consume(&mut self, byte: u8) -> bool {
+        if self.peek() == Some(byte) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, byte: u8) -> Result<(), ParseError> {
+        if self.try_consume(byte) {
+            Ok(())
+        } else {
+            Err(self.error(format!("expected '{}'", byte as char)))
+        }
+    }
+
+    /// Consumes an identifier, if the next token is one.
+    fn identifier(&mut self) -> Option<&'a str> {
+        match self.peek() {
+            Some(byte) if byte.is_ascii_alphabetic() || byte == b'_' => (),
+            _ => return None,
+        }
+        let start = self.pos;
+        let bytes = self.input.as_bytes();
+        while bytes
+            .get(self.pos)
+            .is_some_and(|&byte| byte.is_ascii_alphanumeric() || byte == b'_')
+        {
+            self.pos += 1;
+        }
+        Some(&self.input[start..self.pos])
+    }
+
+    /// Consumes a number, if the next token is one.
+    fn number(&mut self) -> Option<&'a str> {
+        let bytes = self.input.as_bytes();
+        match self.peek() {
+            Some(byte) if byte.is_ascii_digit() => (),
+            Some(b'.') if bytes.get(self.pos + 1).is_some_and(u8::is_ascii_digit) => (),
+            _ => return None,
+        }
+        let start = self.pos;
+        let hex = bytes[start..].starts_with(b"0x") || bytes[start..].starts_with(b"0X");
+        while let Some(&byte) = bytes.get(self.pos) {
+            let exponent_sign = (byte == b'-' || byte == b'+')
+                && !hex
+                && matches!(bytes[self.pos - 1], b'e' | b'E');
+            if !(byte.is_ascii_alphanumeric() || byte == b'.' || exponent_sign) {
+                break;
+            }
+            self.pos += 1;
+        }
+        Some(&self.input[start..self.pos])
+    }
+
+    /// Parses a sign and an integer, returning whether it is negative and its magnitude.
+    fn integer(&mut self) -> Result<(bool, u64), ParseError> {
+        let negative = self.try_consume(b'-');
+        self.skip_whitespace();
+        let start = self.pos;
+        let number = self
+            .number()
+            .ok_or_else(|| self.error("expected an integer"))?;
+        let magnitude = parse_integer(number)
+            .ok_or_else(|| self.error_at(start, format!("invalid integer '{}'", number)))?;
+        Ok((negative, magnitude))
+    }
+
+    /// Parses a signed integer, checking that it is within the range of `T`.
+    fn signed<T>(&mut self) -> Result<T, ParseError>
+    where
+        T: TryFrom<i64>,
+    {
+        self.skip_whitespace();
+        let start = self.pos;
+        let value = match self.integer()? {
+            (false, magnitude) => i64::try_from(magnitude).ok(),
+            (true, magnitude) if magnitude <= 1 << 63 => Some((magnitude as i64).wrapping_neg()),
+            (true, _) => None,
+        };
+        value
+            .and_then(|value| T::try_from(value).ok())
+            .ok_or_else(|| self.error_at(start, "integer out of range"))
+    }
+
+    /// Parses an unsigned integer, checking that it is within the range of `T`.
+    fn unsigned<T>(&mut self) -> Result<T, ParseError>
+    where
+        T: TryFrom<u64>,
+    {
+        self.skip_whitespace();
+        let start = self.pos;
+        match self.integer()? {
+            (false, magnitude) => T::try_from(magnitude).ok(),
+            (true, _) => None,
+        }
+        .ok_or_else(|| self.error_at(start, "integer out of range"))
+    }
+
+    fn float(&mut self) -> Result<f64, ParseError> {
+        let negative = self.try_consume(b'-');
+        self.skip_whitespace();
+        let start = self.pos;
+        let value = if let Some(name) = self.identifier() {
+            if name.eq_ignore_ascii_case("inf") || name.eq_ignore_ascii_case("infinity") {
+                Some(f64::INFINITY)
+            } else if name.eq_ignore_ascii_case("nan") {
+                Some(f64::NAN)
+            } else {
+                None
+            }
+        } else if let Some(number) = self.number() {
+            match parse_integer(number) {
+                Some(value) => Some(value as f64),
+                None => number
+                    .strip_suffix(|c| c == 'f' || c == 'F')
+                    .unwrap_or(number)
+                    .parse()
+                    .ok(),
+            }
+        } else {
+            return Err(self.error("expected a number"));
+        };
+        let value = value.ok_or_else(|| {
+            self.error_at(
+                start,
+                format!("invalid number '{}'", &self.input[start..self.pos]),
+            )
+        })?;
+        Ok(if negative { -value } else { value })
+    }
+
+    /// Parses one or more adjacent quoted strings, and returns their concatenated contents.
+    fn string(&mut self) -> Result<Vec<u8>, ParseError> {
+        let mut value = Vec::new();
+        let quote = match self.peek() {
+            Some(quote @ (b'"' | b'\'')) => quote,
+            _ => return Err(self.error("expected a string")),
+        };
+        self.quoted(quote, &mut value)?;
+        while let Some(quote @ (b'"' | b'\'')) = self.peek() {
+            self.quoted(quote, &mut value)?;
+        }
+        Ok(value)
+    }
+
+    fn quoted(&mut self, quote: u8, value: &mut Vec<u8>) -> Result<(), ParseError> {
+        let bytes = self.input.as_bytes();
+        self.pos += 1;
+        loop {
+            let byte = match bytes.get(self.pos) {
+                Some(b'\n') | None => return Err(self.error("unterminated string")),
+                Some(&byte) => byte,
+            };
+            self.pos += 1;
+            if byte == quote {
+                return Ok(());
+            }
+            if byte != b'\\' {
+                value.push(byte);
+                continue;
+            }
+
+            let start = self.pos - 1;
+            let escape = bytes.get(self.pos).copied().unwrap_or_default();
+            self.pos += 1;
+            match escape {
+                b'n' => value.push(b'\n'),
+                b'r' => value.push(b'\r'),
+                b't' => value.push(b'\t'),
+                b'a' => value.push(b'\x07'),
+                b'b' => value.push(b'\x08'),
+                b'f' => value.push(b'\x0c'),
+                b'v' => value.push(b'\x0b'),
+                b'\\' | b'\'' | b'"' | b'?' => value.push(escape),
+                b'0'..=b'7' => {
+                    let mut byte = u32::from(escape - b'0');
+                    for _ in 0..2 {
+                        match bytes.get(self.pos) {
+                            Some(&digit @ b'0'..=b'7') => {
+                                byte = byte * 8 + u32::from(digit - b'0');
+                                self.pos += 1;
+                            }
+                            _ => break,
+                        }
+                    }
+                    value.push(byte as u8);
+                }
+                b'x' | b'X' => {
+                    let digits = self.hex_digits(2);
+                    if digits.is_empty() {
+                        return Err(self.error_at(start, "invalid escape sequence"));
+                    }
+                    value.push(u8::from_str_radix(digits, 16).unwrap());
+                }
+                b'u' | b'U' => {
+                    let len = if escape == b'u' { 4 } else { 8 };
+                    let digits = self.hex_digits(len);
+                    let c = u32::from_str_radix(digits, 16)
+                        .ok()
+                        .filter(|_| digits.len() == len)
+                        .and_then(char::from_u32)
+                        .ok_or_else(|| self.error_at(start, "invalid escape sequence"))?;
+                    value.extend_from_slice(c.encode_utf8(&mut [0; 4]).as_bytes());
+                }
+                _ => return Err(self.error_at(start, "invalid escape sequence")),
+            }
+        }
+    }
+
+    /// Consumes up to `max` hexadecimal digits.
+    fn hex_digits(&mut self, max: usize) -> &'a str {
+        let start = self.pos;
+        let bytes = self.input.as_bytes();
+        while self.pos - start < max && bytes.get(self.pos).is_some_and(u8::is_ascii_hexdigit) {
+            self.pos += 1;
+        }
+        &self.input[start..self.pos]
+    }
+
+    fn error(&self, description: impl Into<Cow<'static, str>>) -> ParseError {
+        self.error_at(self.pos, description)
+    }
+
+    #[cold]
+    fn error_at(&self, pos: usize, description: impl Into<Cow<'static, str>>) -> ParseError {
+        let before = &self.input[..pos];
+        let line_start = before.rfind('\n').map_or(0, |index| index + 1);
+        ParseError {
+            description: description.into(),
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+        }
+    }
+}
+
+/// Parses the magnitude of a decimal, hexadecimal or octal integer.
+fn parse_integer(number: &str) -> Option<u64> {
+    let (digits, radix) = if let Some(digits) = number
+        .strip_prefix("0x")
+        .or_else(|| number.strip_prefix("0X"))
+    {
+        (digits, 16)
+    } else if number.len() > 1 && number.starts_with('0') {
+        (&number[1..], 8)
+    } else {
+        (number, 10)
+    };
+    if digits.is_empty() || !digits.chars().all(|c| c.is_digit(radix)) {
+        return None;
+    }
+    u64::from_str_radix(digits, radix).ok()
+}
+
+/// Appends a string or bytes value in quotes, escaping quotes, backslashes and non-printable
+/// characters. Non-ASCII characters are escaped only if `utf8` is `false`.
+pub(crate) fn print_quoted(value: &[u8], utf8: bool, buf: &mut String) {
+    buf.push('"');
+    let mut rest = value;
+    while let Some((&byte, tail)) = rest.split_first() {
+        match byte {
+            b'\n' => buf.push_str("\\n"),
+            b'\r' => buf.push_str("\\r"),
+            b'\t' => buf.push_str("\\t"),
+            b'"' => buf.push_str("\\\""),
+            b'\'' => buf.push_str("\\'"),
+            b'\\' => buf.push_str("\\\\"),
+            b' '..=b'~' => buf.push(byte as char),
+            0x80.. if utf8 => {
+                // The value is a valid string, so the character is complete.
+                let len = match byte {
+                    0xf0.. => 4,
+                    0xe0.. => 3,
+                    _ => 2,
+                };
+                buf.push_str(core::str::from_utf8(&rest[..len]).unwrap());
+                rest = &rest[len..];
+                continue;
+            }
+            _ => {
+                buf.push('\\');
+                for shift in [6, 3, 0] {
+                    buf.push((b'0' + ((byte >> shift) & 7)) as char);
+                }
+            }
+        }
+        rest = tail;
+    }
+    buf.push('"');
+}
+
+/// Returns `true` if a field without presence is set to its default value, and so is not printed.
+///
+/// Meant to be used only by `TextFormat` and `Json` implementations.
+#[doc(hidden)]
+pub fn is_default<T>(value: &T) -> bool
+where
+    T: Default + PartialEq,
+{
+    *value == T::default()
+}
+
+/// A value of a string, bytes, bool or numeric field.
+///
+/// Meant to be used only by `TextFormat` implementations.
+#[doc(hidden)]
+pub trait Scalar: Sized {
+    /// Appends the value to the buffer.
+    fn print(&self, buf: &mut String);
+
+    /// Parses a value.
+    fn parse(parser: &mut Parser<'_>) -> Result<Self, ParseError>;
+}
+
+impl Scalar for bool {
+    fn print(&self, buf: &mut String) {
+        buf.push_str(if *self { "true" } else { "false" });
+    }
+
+    fn parse(parser: &mut Parser<'_>) -> Result<bool, ParseError> {
+        parser.skip_whitespace();
+        let start = parser.pos;
+        match parser.identifier().or_else(|| parser.number()) {
+            Some("true" | "True" | "t" | "1") => Ok(true),
+            Some("false" | "False" | "f" | "0") => Ok(false),
+            _ => Err(parser.error_at(start, "expected a bool")),
+        }
+    }
+}
+
+macro_rules! integer_scalar {
+    ($ty:ty, $parse:ident) => {
+        impl Scalar for $ty {
+            fn print(&self, buf: &mut String) {
+                buf.push_str(&self.to_string());
+            }
+
+            fn parse(parser: &mut Parser<'_>) -> Result<$ty, ParseError> {
+                parser.$parse()
+            }
+        }
+    };
+}
+
+integer_scalar!(i32, signed);
+integer_scalar!(i64, signed);
+integer_scalar!(u32, unsigned);
+integer_scalar!(u64, unsigned);
+
+macro_rules! float_scalar {
+    ($ty:ty) => {
+        impl Scalar for $ty {
+            fn print(&self, buf: &mut String) {
+                if self.is_nan() {
+                    buf.push_str("nan");
+                } else {
+                    // The debug representation is the shortest one which parses to the same
+                    // value, using an exponent for very large and very small values.
+                    buf.push_str(&format!("{:?}", self));
+                }
+            }
+
+            fn parse(parser: &mut Parser<'_>) -> Result<$ty, ParseError> {
+                parser.float().map(|value| value as $ty)
+            }
+        }
+    };
+}
+
+float_scalar!(f32);
+float_scalar!(f64);
+
+impl Scalar for String {
+    fn print(&self, buf: &mut String) {
+        print_quoted(self.as_bytes(), true, buf);
+    }
+
+    fn parse(parser: &mut Parser<'_>) -> Result<String, ParseError> {
+        parser.skip_whitespace();
+        let start = parser.pos;
+        String::from_utf8(parser.string()?)
+            .map_err(|_| parser.error_at(start, "invalid string value: data is not UTF-8 encoded"))
+    }
+}
+
+impl Scalar for Vec<u8> {
+    fn print(&self, buf: &mut String) {
+        print_quoted(self, false, buf);
+    }
+
+    fn parse(parser: &mut Parser<'_>) -> Result<Vec<u8>, ParseError> {
+        parser.string()
+    }
+}
+
+impl Scalar for Bytes {
+    fn print(&self, buf: &mut String) {
+        print_quoted(self, false, buf);
+    }
+
+    fn parse(parser: &mut Parser<'_>) -> Result<Bytes, ParseError> {
+        parser.string().map(Bytes::from)
+    }
+}
+
+/// Implements `TextFormat` for a Rust standard library type which corresponds to a Protobuf
+/// well-known wrapper type, with a single `value` field.
+macro_rules! wrapper {
+    ($ty:ty, $default:expr) => {
+        impl TextFormat for $ty {
+            fn print_fields(&self, printer: &mut Printer<'_>) {
+                if *self != $default {
+                    printer.scalar("value", self);
+                }
+            }
+
+            fn merge_text_field(
+                &mut self,
+                name: &str,
+                parser: &mut Parser<'_>,
+            ) -> Result<bool, ParseError> {
+                if name != "value" {
+                    return Ok(false);
+                }
+                parser.scalar(self)?;
+                Ok(true)
+            }
+        }
+    };
+}
+
+wrapper!(bool, false);
+wrapper!(u32, 0);
+wrapper!(u64, 0);
+wrapper!(i32, 0);
+wrapper!(i64, 0);
+wrapper!(f32, 0.0);
+wrapper!(f64, 0.0);
+wrapper!(String, "");
+wrapper!(Vec<u8>, b"" as &[u8]);
+wrapper!(Bytes, b"" as &[u8]);
+
+/// `google.protobuf.Empty`
+impl TextFormat for () {
+    fn print_fields(&self, _printer: &mut Printer<'_>) {}
+
+    fn merge_text_field(
+        &mut self,
+        _name: &str,
+        _parser: &mut Parser<'_>,
+    ) -> Result<bool, ParseError> {
+        Ok(false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use alloc::vec;
+
+    fn parse<T: Scalar>(text: &str) -> Result<T, ParseError> {
+        let mut parser = Parser::new(text);
+        let value = T::parse(&mut parser)?;
+        match parser.peek() {
+            None => Ok(value),
+            Some(_) => Err(parser.error("trailing input")),
+        }
+    }
+
+    fn print<T: Scalar>(value: T) -> String {
+        let mut buf = String::new();
+        value.print(&mut buf);
+        buf
+    }
+
+    #[test]
+    fn integers() {
+        assert_eq!(parse::<i32>("42"), Ok(42));
+        assert_eq!(parse::<i32>("-42"), Ok(-42));
+        assert_eq!(parse::<i32>("- 42"), Ok(-42));
+        assert_eq!(parse::<i32>("0x7fffffff"), Ok(i32::MAX));
+        assert_eq!(parse::<i32>("-0x80000000"), Ok(i32::MIN));
+        assert_eq!(parse::<i32>("017"), Ok(15));
+        assert_eq!(parse::<i64>("-9223372036854775808"), Ok(i64::MIN));
+        assert_eq!(parse::<u64>("18446744073709551615"), Ok(u64::MAX));
+        assert!(parse::<i32>("2147483648").is_err());
+        assert!(parse::<i64>("9223372036854775808").is_err());
+        assert!(parse::<u32>("-1").is_err());
+        assert!(parse::<u64>("18446744073709551616").is_err());
+        assert!(parse::<i32>("1.0").is_err());
+        assert!(parse::<i32>("09").is_err());
+        assert!(parse::<i32>("0x").is_err());
+        assert!(parse::<i32>("abc").is_err());
+
+        assert_eq!(print(i32::MIN), "-2147483648");
+        assert_eq!(print(u64::MAX), "18446744073709551615");
+    }
+
+    #[test]
+    fn floats() {
+        assert_eq!(parse::<f64>("1.5"), Ok(1.5));
+        assert_eq!(parse::<f64>("-.5"), Ok(-0.5));
+        assert_eq!(parse::<f64>("1."), Ok(1.0));
+        assert_eq!(parse::<f64>("1e-3"), Ok(0.001));
+        assert_eq!(parse::<f64>("2E+2"), Ok(200.0));
+        assert_eq!(parse::<f32>("1.5f"), Ok(1.5));
+        assert_eq!(parse::<f32>("2F"), Ok(2.0));
+        assert_eq!(parse::<f64>("10"), Ok(10.0));
+        assert_eq!(parse::<f64>("0x10"), Ok(16.0));
+        assert_eq!(parse::<f64>("-inf"), Ok(f64::NEG_INFINITY));
+        assert_eq!(parse::<f32>("Infinity"), Ok(f32::INFINITY));
+        assert!(parse::<f64>("nan").unwrap().is_nan());
+        assert!(parse::<f64>("1.5.5").is_err());
+        assert!(parse::<f64>("infinite").is_err());
+
+        assert_eq!(print(1.5f64), "1.5");
+        assert_eq!(print(1.0f32), "1.0");
+        assert_eq!(print(0.1f32), "0.1");
+        assert_eq!(print(1e300f64), "1e300");
+        assert_eq!(print(f64::NEG_INFINITY), "-inf");
+        assert_eq!(print(f32::NAN), "nan");
+        for value in [f64::MAX, f64::MIN_POSITIVE, -1.0 / 3.0, 5e-324] {
+            assert_eq!(parse::<f64>(&print(value)), Ok(value));
+        }
+    }
+
+    #[test]
+    fn bools() {
+        for text in ["true", "True", "t", "1"] {
+            assert_eq!(parse::<bool>(text), Ok(true));
+        }
+        for text in ["false", "False", "f", "0"] {
+            assert_eq!(parse::<bool>(text), Ok(false));
+        }
+        assert!(parse::<bool>("yes").is_err());
+        assert!(parse::<bool>("2").is_err());
+    }
+
+    #[test]
+    fn strings() {
+        assert_eq!(parse::<String>(r#""hello""#), Ok("hello".to_string()));
+        assert_eq!(parse::<String>(r#"'it''s' "!""#), Ok("its!".to_string()));
+        assert_eq!(
+            parse::<String>(r#""\"\'\\\n\r\t\a\b\f\v\?""#),
+            Ok("\"'\\\n\r\t\x07\x08\x0c\x0b?".to_string())
+        );
+        assert_eq!(parse::<String>(r#""é\U0001F600""#), Ok("é😀".to_string()));
+        assert_eq!(parse::<String>(r#""\303\251""#), Ok("é".to_string()));
+        assert_eq!(parse::<String>("\"é\""), Ok("é".to_string()));
+        assert!(parse::<String>(r#""\377""#).is_err());
+        assert!(parse::<String>(r#""\ud800""#).is_err());
+        assert!(parse::<String>(r#""\q""#).is_err());
+        assert!(parse::<String>("\"unterminated").is_err());
+        assert!(parse::<String>("\"line\nbreak\"").is_err());
+
+        assert_eq!(
+            parse::<Vec<u8>>(r#""\0\001\x02\xfe\377a""#),
+            Ok(vec![0, 1, 2, 0xfe, 0xff, b'a'])
+        );
+        assert!(parse::<Vec<u8>>(r#""\x""#).is_err());
+
+        assert_eq!(
+            print("a\"b'c\\\n\x01é".to_string()),
+            r#""a\"b\'c\\\n\001é""#
+        );
+        assert_eq!(
+            print(vec![0u8, b'a', 0xc3, 0xa9, 0xff]),
+            r#""\000a\303\251\377""#
+        );
+        let bytes: Vec<u8> = (0..=255).collect();
+        assert_eq!(parse::<Vec<u8>>(&print(bytes.clone())), Ok(bytes));
+    }
+
+    #[test]
+    fn errors() {
+        let mut value = 0i32;
+        let error = parse_field("\n  # comment\n  value: x", &mut value).unwrap_err();
+        assert_eq!((error.line(), error.column()), (3, 10));
+        assert_eq!(
+            error.to_string(),
+            "failed to parse Protobuf text format: expected an integer at line 3, column 10"
+        );
+
+        let error = parse_field("value: 1 é", &mut value).unwrap_err();
+        assert_eq!((error.line(), error.column()), (1, 10));
+        let error = parse_field("other: 1", &mut value).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "failed to parse Protobuf text format: unknown field 'other' at line 1, column 1"
+        );
+        assert!(parse_field("value 1", &mut value).is_err());
+        assert!(parse_field("[ext]: 1", &mut value).is_err());
+        assert!(parse_field("value: 1 value: 2", &mut value).is_ok());
+        assert_eq!(value, 2);
+        assert!(parse_field("value: 1, value: 3;", &mut value).is_ok());
+        assert_eq!(value, 3);
+    }
+
+    fn parse_field(text: &str, value: &mut i32) -> Result<(), ParseError> {
+        value.merge_text(text)
+    }
+
+    #[test]
+    fn wrappers() {
+        assert_eq!(5u32.to_text(), "value: 5");
+        assert_eq!(0u32.to_text(), "");
+        assert_eq!("a".to_string().to_text_pretty(), "value: \"a\"\n");
+        assert_eq!(i64::parse_text("value: -1"), Ok(-1));
+        assert_eq!(<()>::parse_text(""), Ok(()));
+        assert!(<()>::parse_text("value: 1").is_err());
+    }
+}
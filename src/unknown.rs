@@ -0,0 +1,280 @@
+//! Storage for fields which are not described by a message's definition.
+
+use alloc::vec::Vec;
+use core::slice;
+
+use ::bytes::{Buf, BufMut, Bytes};
+
+use crate::encoding::{bytes, fixed32, fixed64, group, uint64, DecodeContext, WireType};
+use crate::{DecodeError, DecodeErrorKind, Message};
+
+/// A set of fields which were encountered while decoding a message, but are not known to its
+/// definition.
+///
+/// Fields are kept in the order they appeared on the wire, and are re-emitted in the same order
+/// after the known fields when the message is encoded. This allows proxies and other intermediate
+/// services to pass through data added by newer versions of a schema without losing it.
+///
+/// A message type opts into preserving unknown fields by declaring a field of this type with the
+/// `#[prost(unknown_fields)]` attribute:
+///
+/// ```rust
+/// # use prost::{Message, UnknownFieldSet};
+/// #[derive(Clone, PartialEq, Message)]
+/// struct Person {
+///     #[prost(string, tag = "1")]
+///     name: String,
+///     #[prost(unknown_fields)]
+///     unknown_fields: UnknownFieldSet,
+/// }
+/// ```
+///
+/// `UnknownFieldSet` is itself a [`Message`], so it can also be used to decode and re-encode
+/// arbitrary Protobuf data without a schema.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct UnknownFieldSet {
+    fields: Vec<UnknownField>,
+}
+
+/// A single field stored in an [`UnknownFieldSet`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UnknownField {
+    /// The field number.
+    pub number: u32,
+    /// The field value.
+    pub value: UnknownFieldValue,
+}
+
+/// The value of an [`UnknownField`], tagged by its wire type.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum UnknownFieldValue {
+    /// A varint-encoded value.
+    Varint(u64),
+    /// A 64-bit value.
+    SixtyFourBit(u64),
+    /// A length-delimited value.
+    LengthDelimited(Bytes),
+    /// A group of nested fields.
+    Group(UnknownFieldSet),
+    /// A 32-bit value.
+    ThirtyTwoBit(u32),
+}
+
+impl UnknownFieldSet {
+    /// Creates an empty `UnknownFieldSet`.
+    pub fn new() -> UnknownFieldSet {
+        UnknownFieldSet::default()
+    }
+
+    /// Returns the number of stored fields.
+    pub fn len(&self) -> usize {
+        self.fields.len()
+    }
+
+    /// Returns `true` if no fields are stored.
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty()
+    }
+
+    /// Returns an iterator over the stored fields, in wire order.
+    pub fn iter(&self) -> slice::Iter<'_, UnknownField> {
+        self.fields.iter()
+    }
+
+    /// Returns an iterator over the values stored with the given field number, in wire order.
+    pub fn get(&self, number: u32) -> impl Iterator<Item = &UnknownFieldValue> {
+        self.fields
+            .iter()
+            .filter(move |field| field.number == number)
+            .map(|field| &field.value)
+    }
+
+    /// Appends a field to the set.
+    pub fn push(&mut self, number: u32, value: UnknownFieldValue) {
+        self.fields.push(UnknownField { number, value });
+    }
+
+    /// Removes all fields with the given field number from the set.
+    pub fn remove(&mut self, number: u32) {
+        self.fields.retain(|field| field.number != number);
+    }
+}
+
+impl<'a> IntoIterator for &'a UnknownFieldSet {
+    type Item = &'a UnknownField;
+    type IntoIter = slice::Iter<'a, UnknownField>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl UnknownField {
+    /// Encodes the field to a buffer.
+    pub fn encode<B>(&self, buf: &mut B)
+    where
+        B: BufMut,
+    {
+        match self.value {
+            UnknownFieldValue::Varint(ref value) => uint64::encode(self.number, value, buf),
+            UnknownFieldValue::SixtyFourBit(ref value) => fixed64::encode(self.number, value, buf),
+            UnknownFieldValue::LengthDelimited(ref value) => bytes::encode(self.number, value, buf),
+            UnknownFieldValue::Group(ref value) => group::encode(self.number, value, buf),
+            UnknownFieldValue::ThirtyTwoBit(ref value) => fixed32::encode(self.number, value, buf),
+        }
+    }
+
+    /// Returns the encoded length of the field, including the key.
+    pub fn encoded_len(&self) -> usize {
+        match self.value {
+            UnknownFieldValue::Varint(ref value) => uint64::encoded_len(self.number, value),
+            UnknownFieldValue::SixtyFourBit(ref value) => fixed64::encoded_len(self.number, value),
+            UnknownFieldValue::LengthDelimited(ref value) => bytes::encoded_len(self.number, value),
+            UnknownFieldValue::Group(ref value) => group::encoded_len(self.number, value),
+            UnknownFieldValue::ThirtyTwoBit(ref value) => fixed32::encoded_len(self.number, value),
+        }
+    }
+}
+
+impl Message for UnknownFieldSet {
+    fn encode_raw<B>(&self, buf: &mut B)
+    where
+        B: BufMut,
+    {
+        for field in &self.fields {
+            field.encode(buf);
+        }
+    }
+
+    fn merge_field<B>(
+        &mut self,
+        tag: u32,
+        wire_type: WireType,
+        buf: &mut B,
+        ctx: DecodeContext,
+    ) -> Result<(), DecodeError>
+    where
+        B: Buf,
+    {
+        ctx.charge_unknown_field::<UnknownField>(self.fields.len())?;
+        let value = match wire_type {
+            WireType::Varint => {
+                let mut value = 0;
+                uint64::merge(wire_type, &mut value, buf, ctx)?;
+                UnknownFieldValue::Varint(value)
+            }
+            WireType::SixtyFourBit => {
+                let mut value = 0;
+                fixed64::merge(wire_type, &mut value, buf, ctx)?;
+                UnknownFieldValue::SixtyFourBit(value)
+            }
+            WireType::LengthDelimited => {
+                let mut value = Bytes::new();
+                bytes::merge(wire_type, &mut value, buf, ctx)?;
+                UnknownFieldValue::LengthDelimited(value)
+            }
+            WireType::StartGroup => {
+                let mut value = UnknownFieldSet::default();
+                group::merge(tag, wire_type, &mut value, buf, ctx)?;
+                UnknownFieldValue::Group(value)
+            }
+            WireType::ThirtyTwoBit => {
+                let mut value = 0;
+                fixed32::merge(wire_type, &mut value, buf, ctx)?;
+                UnknownFieldValue::ThirtyTwoBit(value)
+            }
+            WireType::EndGroup => {
+                return Err(DecodeError::with_kind(
+                    DecodeErrorKind::UnexpectedEndGroup,
+                    "unexpected end group tag",
+                ))
+            }
+        };
+        self.push(tag, value);
+        Ok(())
+    }
+
+    fn encoded_len(&self) -> usize {
+        self.fields.iter().map(UnknownField::encoded_len).sum()
+    }
+
+    fn merge_from(&mut self, other: &UnknownFieldSet) {
+        self.fields.extend_from_slice(&other.fields);
+    }
+
+    fn clear(&mut self) {
+        self.fields.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use alloc::vec;
+
+    use crate::encoding::{encode_key, encode_varint, string};
+
+    #[test]
+    fn roundtrip_in_wire_order() {
+        let mut buf = Vec::new();
+        fixed32::encode(7, &0xdead_beef, &mut buf);
+        uint64::encode(3, &300, &mut buf);
+        string::encode(12, &"hello".into(), &mut buf);
+        encode_key(5, WireType::StartGroup, &mut buf);
+        uint64::encode(1, &1, &mut buf);
+        fixed64::encode(2, &u64::MAX, &mut buf);
+        encode_key(5, WireType::EndGroup, &mut buf);
+        uint64::encode(3, &0, &mut buf);
+
+        let set = UnknownFieldSet::decode(buf.as_slice()).unwrap();
+        assert_eq!(set.len(), 5);
+        assert_eq!(
+            set.iter().map(|field| field.number).collect::<Vec<_>>(),
+            vec![7, 3, 12, 5, 3]
+        );
+        assert_eq!(
+            set.get(3).collect::<Vec<_>>(),
+            vec![
+                &UnknownFieldValue::Varint(300),
+                &UnknownFieldValue::Varint(0)
+            ]
+        );
+
+        assert_eq!(set.encoded_len(), buf.len());
+        assert_eq!(set.encode_to_vec(), buf);
+    }
+
+    #[test]
+    fn mismatched_end_group() {
+        let mut buf = Vec::new();
+        encode_key(5, WireType::StartGroup, &mut buf);
+        encode_key(6, WireType::EndGroup, &mut buf);
+        assert!(UnknownFieldSet::decode(buf.as_slice()).is_err());
+
+        let mut buf = Vec::new();
+        encode_key(5, WireType::EndGroup, &mut buf);
+        assert!(UnknownFieldSet::decode(buf.as_slice()).is_err());
+    }
+
+    #[test]
+    fn truncated_length_delimited() {
+        let mut buf = Vec::new();
+        encode_key(1, WireType::LengthDelimited, &mut buf);
+        encode_varint(10, &mut buf);
+        buf.extend_from_slice(b"short");
+        assert!(UnknownFieldSet::decode(buf.as_slice()).is_err());
+    }
+
+    #[test]
+    fn clear() {
+        let mut set = UnknownFieldSet::new();
+        set.push(1, UnknownFieldValue::Varint(1));
+        set.push(2, UnknownFieldValue::ThirtyTwoBit(2));
+        set.remove(1);
+        assert_eq!(set.len(), 1);
+        set.clear();
+        assert!(set.is_empty());
+        assert_eq!(set.encoded_len(), 0);
+    }
+}
@@ -0,0 +1,642 @@
+//! Zero-copy views of encoded messages.
+//!
+//! A message view borrows string and bytes fields directly from the encoded input instead of
+//! copying them into owned `String` and `Vec<u8>` values, which makes decoding read-mostly data
+//! cheap. Views are generated by `prost-build` alongside the owned message types when enabled with
+//! `Config::message_views`: for a message `Foo`, a `FooView<'a>` struct is generated with the
+//! same fields, where
+//!
+//!  * `string` and `bytes` fields are `&'a str` and `&'a [u8]`,
+//!  * singular message fields are views of the nested message,
+//!  * repeated `string`, `bytes` and message fields are [`RepeatedView`]s, which decode their
+//!    elements on demand, and return an error for an element which fails to decode,
+//!  * map fields are vectors of key-value pairs, in the order they appear in the input,
+//!  * oneof fields are enums of views, and
+//!  * scalar, enumeration and repeated scalar fields have the same types as in the owned message.
+//!
+//! Unknown fields and extensions are skipped when decoding a view, and custom default values of
+//! proto2 fields are not applied. A view can be converted to the owned message with
+//! [`MessageView::to_message`], which returns an error if an element of a repeated field fails to
+//! decode.
+
+use alloc::vec::Vec;
+use core::fmt;
+use core::marker::PhantomData;
+
+use crate::encoding::{
+    check_wire_type, decode_key, decode_varint, skip_field, DecodeContext, WireType,
+};
+use crate::{DecodeError, DecodeErrorKind};
+
+/// A borrowed view of an encoded Protobuf message.
+///
+/// Implementations are generated by `prost-build`, see the [module documentation](self).
+pub trait MessageView<'a>: Default {
+    /// The owned message type of the view.
+    type Owned;
+
+    /// Decodes a field from a buffer, and merges it into `self`.
+    ///
+    /// Meant to be used only by generated `MessageView` implementations.
+    #[doc(hidden)]
+    fn merge_field(
+        &mut self,
+        tag: u32,
+        wire_type: WireType,
+        buf: &mut &'a [u8],
+        ctx: DecodeContext,
+    ) -> Result<(), DecodeError>;
+
+    /// Decodes a view of an instance of the message from a buffer.
+    ///
+    /// The entire buffer will be consumed.
+    fn decode(mut buf: &'a [u8]) -> Result<Self, DecodeError> {
+        let mut view = Self::default();
+        let ctx = DecodeContext::default();
+        let len = buf.len();
+        let mut merge = || {
+            while !buf.is_empty() {
+                let (tag, wire_type) = decode_key(&mut buf)?;
+                view.merge_field(tag, wire_type, &mut buf, ctx.clone())?;
+            }
+            Ok(())
+        };
+        merge().map_err(|mut error: DecodeError| {
+            error.set_offset(len - buf.len());
+            error
+        })?;
+        Ok(view)
+    }
+
+    /// Converts the view to an owned message, copying the borrowed fields.
+    ///
+    /// The elements of repeated `string`, `bytes` and message fields are decoded, and the first
+    /// error decoding one of them is returned.
+    fn to_message(&self) -> Result<Self::Owned, DecodeError>;
+}
+
+/// A field value which can be decoded as an element of a [`RepeatedView`].
+///
+/// Implemented for `&str`, `&[u8]` and message views.
+pub trait ViewElement<'a>: Default {
+    /// Decodes a value of the field, and merges it into `value`.
+    #[doc(hidden)]
+    fn merge(
+        tag: u32,
+        wire_type: WireType,
+        value: &mut Self,
+        buf: &mut &'a [u8],
+        ctx: DecodeContext,
+    ) -> Result<(), DecodeError>;
+}
+
+impl<'a> ViewElement<'a> for &'a str {
+    fn merge(
+        _tag: u32,
+        wire_type: WireType,
+        value: &mut Self,
+        buf: &mut &'a [u8],
+        ctx: DecodeContext,
+    ) -> Result<(), DecodeError> {
+        merge_str(wire_type, value, buf, ctx)
+    }
+}
+
+impl<'a> ViewElement<'a> for &'a [u8] {
+    fn merge(
+        _tag: u32,
+        wire_type: WireType,
+        value: &mut Self,
+        buf: &mut &'a [u8],
+        ctx: DecodeContext,
+    ) -> Result<(), DecodeError> {
+        merge_bytes(wire_type, value, buf, ctx)
+    }
+}
+
+impl<'a, V> ViewElement<'a> for V
+where
+    V: MessageView<'a>,
+{
+    fn merge(
+        tag: u32,
+        wire_type: WireType,
+        value: &mut Self,
+        buf: &mut &'a [u8],
+        ctx: DecodeContext,
+    ) -> Result<(), DecodeError> {
+        if wire_type == WireType::StartGroup {
+            merge_group(tag, wire_type, value, buf, ctx)
+        } else {
+            merge_message(wire_type, value, buf, ctx)
+        }
+    }
+}
+
+/// A view of a repeated `string`, `bytes` or message field.
+///
+/// Only the location of each element in the input is stored when the containing view is decoded,
+/// after checking its wire type and length. The elements are decoded when accessed, so an invalid
+/// element, such as a string which is not UTF-8 encoded, is returned as an error by the accessors
+/// rather than when decoding the view.
+pub struct RepeatedView<'a, T> {
+    tag: u32,
+    elements: Vec<(WireType, &'a [u8])>,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<'a, T> RepeatedView<'a, T>
+where
+    T: ViewElement<'a>,
+{
+    /// Returns the number of elements.
+    pub fn len(&self) -> usize {
+        self.elements.len()
+    }
+
+    /// Returns `true` if the field has no elements.
+    pub fn is_empty(&self) -> bool {
+        self.elements.is_empty()
+    }
+
+    /// Decodes the element at `index`, or returns `None` if it is out of bounds.
+    pub fn get(&self, index: usize) -> Option<Result<T, DecodeError>> {
+        let &(wire_type, buf) = self.elements.get(index)?;
+        Some(decode_element(self.tag, wire_type, buf))
+    }
+
+    /// Returns an iterator decoding the elements in order.
+    pub fn iter(&self) -> Iter<'_, 'a, T> {
+        Iter {
+            tag: self.tag,
+            elements: self.elements.iter(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Splits an element off a buffer, and appends it to the view.
+    ///
+    /// Meant to be used only by generated `MessageView` implementations.
+    #[doc(hidden)]
+    pub fn merge(
+        &mut self,
+        tag: u32,
+        wire_type: WireType,
+        buf: &mut &'a [u8],
+        ctx: DecodeContext,
+    ) -> Result<(), DecodeError> {
+        // Message elements may be groups, other elements are rejected when decoded.
+        if wire_type != WireType::StartGroup {
+            check_wire_type(WireType::LengthDelimited, wire_type)?;
+        }
+        let start = *buf;
+        skip_field(wire_type, tag, buf, ctx)?;
+        let len = start.len() - buf.len();
+        self.tag = tag;
+        self.elements.push((wire_type, &start[..len]));
+        Ok(())
+    }
+}
+
+fn decode_element<'a, T>(tag: u32, wire_type: WireType, mut buf: &'a [u8]) -> Result<T, DecodeError>
+where
+    T: ViewElement<'a>,
+{
+    let mut value = T::default();
+    T::merge(
+        tag,
+        wire_type,
+        &mut value,
+        &mut buf,
+        DecodeContext::default(),
+    )?;
+    Ok(value)
+}
+
+impl<T> Default for RepeatedView<'_, T> {
+    fn default() -> Self {
+        RepeatedView {
+            tag: 0,
+            elements: Vec::new(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T> Clone for RepeatedView<'_, T> {
+    fn clone(&self) -> Self {
+        RepeatedView {
+            tag: self.tag,
+            elements: self.elements.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<'a, T> fmt::Debug for RepeatedView<'a, T>
+where
+    T: ViewElement<'a> + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut list = f.debug_list();
+        for element in self.iter() {
+            match element {
+                Ok(value) => list.entry(&value),
+                Err(error) => list.entry(&error),
+            };
+        }
+        list.finish()
+    }
+}
+
+impl<'a, T> PartialEq for RepeatedView<'a, T>
+where
+    T: ViewElement<'a> + PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+impl<'r, 'a, T> IntoIterator for &'r RepeatedView<'a, T>
+where
+    T: ViewElement<'a>,
+{
+    type Item = Result<T, DecodeError>;
+    type IntoIter = Iter<'r, 'a, T>;
+
+    fn into_iter(self) -> Iter<'r, 'a, T> {
+        self.iter()
+    }
+}
+
+/// An iterator over the elements of a [`RepeatedView`].
+pub struct Iter<'r, 'a, T> {
+    tag: u32,
+    elements: core::slice::Iter<'r, (WireType, &'a [u8])>,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<'r, 'a, T> Iterator for Iter<'r, 'a, T>
+where
+    T: ViewElement<'a>,
+{
+    type Item = Result<T, DecodeError>;
+
+    fn next(&mut self) -> Option<Result<T, DecodeError>> {
+        let &(wire_type, buf) = self.elements.next()?;
+        Some(decode_element(self.tag, wire_type, buf))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.elements.size_hint()
+    }
+}
+
+impl<'r, 'a, T> DoubleEndedIterator for Iter<'r, 'a, T>
+where
+    T: ViewElement<'a>,
+{
+    fn next_back(&mut self) -> Option<Result<T, DecodeError>> {
+        let &(wire_type, buf) = self.elements.next_back()?;
+        Some(decode_element(self.tag, wire_type, buf))
+    }
+}
+
+impl<'r, 'a, T> ExactSizeIterator for Iter<'r, 'a, T> where T: ViewElement<'a> {}
+
+/// Decodes the length of a length-delimited value, and splits it off the buffer.
+fn split_delimited<'a>(wire_type: WireType, buf: &mut &'a [u8]) -> Result<&'a [u8], DecodeError> {
+    check_wire_type(WireType::LengthDelimited, wire_type)?;
+    let len = decode_varint(buf)?;
+    if len > buf.len() as u64 {
+        return Err(DecodeError::with_kind(
+            DecodeErrorKind::UnexpectedEof,
+            "buffer underflow",
+        ));
+    }
+    let (value, rest) = buf.split_at(len as usize);
+    *buf = rest;
+    Ok(value)
+}
+
+#[doc(hidden)]
+pub fn merge_str<'a>(
+    wire_type: WireType,
+    value: &mut &'a str,
+    buf: &mut &'a [u8],
+    _ctx: DecodeContext,
+) -> Result<(), DecodeError> {
+    let bytes = split_delimited(wire_type, buf)?;
+    *value = core::str::from_utf8(bytes).map_err(|_| {
+        DecodeError::with_kind(
+            DecodeErrorKind::InvalidUtf8,
+            "invalid string value: data is not UTF-8 encoded",
+        )
+    })?;
+    Ok(())
+}
+
+#[doc(hidden)]
+pub fn merge_bytes<'a>(
+    wire_type: WireType,
+    value: &mut &'a [u8],
+    buf: &mut &'a [u8],
+    _ctx: DecodeContext,
+) -> Result<(), DecodeError> {
+    *value = split_delimited(wire_type, buf)?;
+    Ok(())
+}
+
+#[doc(hidden)]
+pub fn merge_message<'a, V>(
+    wire_type: WireType,
+    value: &mut V,
+    buf: &mut &'a [u8],
+    ctx: DecodeContext,
+) -> Result<(), DecodeError>
+where
+    V: MessageView<'a>,
+{
+    ctx.limit_reached()?;
+    let mut msg = split_delimited(wire_type, buf)?;
+    let ctx = ctx.enter_recursion();
+    while !msg.is_empty() {
+        let (tag, wire_type) = decode_key(&mut msg)?;
+        value.merge_field(tag, wire_type, &mut msg, ctx.clone())?;
+    }
+    Ok(())
+}
+
+#[doc(hidden)]
+pub fn merge_group<'a, V>(
+    tag: u32,
+    wire_type: WireType,
+    value: &mut V,
+    buf: &mut &'a [u8],
+    ctx: DecodeContext,
+) -> Result<(), DecodeError>
+where
+    V: MessageView<'a>,
+{
+    check_wire_type(WireType::StartGroup, wire_type)?;
+    ctx.limit_reached()?;
+    loop {
+        let (field_tag, field_wire_type) = decode_key(buf)?;
+        if field_wire_type == WireType::EndGroup {
+            if field_tag != tag {
+                return Err(DecodeError::with_kind(
+                    DecodeErrorKind::UnexpectedEndGroup,
+                    "unexpected end group tag",
+                ));
+            }
+            return Ok(());
+        }
+        value.merge_field(field_tag, field_wire_type, buf, ctx.enter_recursion())?;
+    }
+}
+
+/// Decodes a map entry, and appends it to the entries of a map field view.
+#[doc(hidden)]
+pub fn merge_map<'a, K, V, KM, VM>(
+    key_merge: KM,
+    val_merge: VM,
+    val_default: V,
+    entries: &mut Vec<(K, V)>,
+    buf: &mut &'a [u8],
+    ctx: DecodeContext,
+) -> Result<(), DecodeError>
+where
+    K: Default,
+    KM: Fn(WireType, &mut K, &mut &'a [u8], DecodeContext) -> Result<(), DecodeError>,
+    VM: Fn(WireType, &mut V, &mut &'a [u8], DecodeContext) -> Result<(), DecodeError>,
+{
+    let mut key = K::default();
+    let mut val = val_default;
+    ctx.limit_reached()?;
+    let mut entry = split_delimited(WireType::LengthDelimited, buf)?;
+    let ctx = ctx.enter_recursion();
+    while !entry.is_empty() {
+        let (tag, wire_type) = decode_key(&mut entry)?;
+        match tag {
+            1 => key_merge(wire_type, &mut key, &mut entry, ctx.clone())?,
+            2 => val_merge(wire_type, &mut val, &mut entry, ctx.clone())?,
+            _ => skip_field(wire_type, tag, &mut entry, ctx.clone())?,
+        }
+    }
+    entries.push((key, val));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use alloc::string::String;
+    use alloc::vec;
+
+    use crate::encoding::{encode_key, encode_varint, int32, string};
+
+    /// A hand-written view, equivalent to the generated view of:
+    ///
+    /// ```proto
+    /// message Node {
+    ///   string name = 1;
+    ///   repeated string tags = 2;
+    ///   repeated Node children = 3;
+    ///   int32 value = 4;
+    /// }
+    /// ```
+    #[derive(Clone, Debug, Default, PartialEq)]
+    struct NodeView<'a> {
+        name: &'a str,
+        tags: RepeatedView<'a, &'a str>,
+        children: RepeatedView<'a, NodeView<'a>>,
+        value: i32,
+    }
+
+    impl<'a> MessageView<'a> for NodeView<'a> {
+        type Owned = (String, usize);
+
+        fn merge_field(
+            &mut self,
+            tag: u32,
+            wire_type: WireType,
+            buf: &mut &'a [u8],
+            ctx: DecodeContext,
+        ) -> Result<(), DecodeError> {
+            match tag {
+                1 => merge_str(wire_type, &mut self.name, buf, ctx),
+                2 => self.tags.merge(tag, wire_type, buf, ctx),
+                3 => self.children.merge(tag, wire_type, buf, ctx),
+                4 => int32::merge(wire_type, &mut self.value, buf, ctx),
+                _ => skip_field(wire_type, tag, buf, ctx),
+            }
+        }
+
+        fn to_message(&self) -> Result<(String, usize), DecodeError> {
+            for tag in &self.tags {
+                tag?;
+            }
+            Ok((self.name.into(), self.children.len()))
+        }
+    }
+
+    fn encode_child(tag: u32, child: &[u8], buf: &mut Vec<u8>) {
+        encode_key(tag, WireType::LengthDelimited, buf);
+        encode_varint(child.len() as u64, buf);
+        buf.extend_from_slice(child);
+    }
+
+    #[test]
+    fn borrows_from_input() {
+        let mut child = Vec::new();
+        string::encode(1, &String::from("child"), &mut child);
+
+        let mut buf = Vec::new();
+        string::encode(1, &String::from("root"), &mut buf);
+        string::encode(2, &String::from("a"), &mut buf);
+        encode_child(3, &child, &mut buf);
+        int32::encode(4, &42, &mut buf);
+        string::encode(2, &String::from("b"), &mut buf);
+        encode_child(3, &[], &mut buf);
+        string::encode(2, &String::from("c"), &mut buf);
+
+        let view = NodeView::decode(&buf).unwrap();
+        assert_eq!(view.name, "root");
+        assert!(buf.as_ptr_range().contains(&view.name.as_ptr()));
+        assert_eq!(view.value, 42);
+        assert_eq!(view.tags.len(), 3);
+        assert_eq!(
+            view.tags.iter().collect::<Result<Vec<_>, _>>(),
+            Ok(vec!["a", "b", "c"])
+        );
+        assert_eq!(view.children.len(), 2);
+        let children = view.children.iter().collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(children[0].name, "child");
+        assert_eq!(children[1], NodeView::default());
+        assert_eq!(view.to_message(), Ok((String::from("root"), 2)));
+    }
+
+    #[test]
+    fn invalid_elements() {
+        // Elements are decoded when accessed, and an invalid element is returned as an error.
+        let mut buf = Vec::new();
+        string::encode(2, &String::from("a"), &mut buf);
+        encode_key(2, WireType::LengthDelimited, &mut buf);
+        encode_varint(1, &mut buf);
+        buf.push(0xff);
+        let view = NodeView::decode(&buf).unwrap();
+        assert_eq!(view.tags.get(0), Some(Ok("a")));
+        let error = view.tags.get(1).unwrap().unwrap_err();
+        assert_eq!(error.kind(), DecodeErrorKind::InvalidUtf8);
+        assert_eq!(view.to_message(), Err(error));
+        assert_eq!(
+            format!("{:?}", view.tags),
+            format!("[\"a\", {:?}]", view.tags.get(1).unwrap().unwrap_err())
+        );
+
+        let mut buf = Vec::new();
+        encode_child(3, &[0x0a, 0x01, 0xff], &mut buf);
+        let view = NodeView::decode(&buf).unwrap();
+        assert!(view.children.get(0).unwrap().is_err());
+
+        // The wire type and length of elements are checked when decoding the view.
+        let buf = [0x12, 0x05, b'a'];
+        assert!(NodeView::decode(&buf).is_err());
+
+        let mut buf = Vec::new();
+        int32::encode(2, &1, &mut buf);
+        assert!(NodeView::decode(&buf).is_err());
+    }
+
+    #[test]
+    fn split_messages() {
+        // A message occurring several times is merged, and the elements of its repeated fields
+        // are concatenated.
+        let mut first = Vec::new();
+        string::encode(2, &String::from("a"), &mut first);
+        string::encode(2, &String::from("b"), &mut first);
+        let mut second = Vec::new();
+        string::encode(2, &String::from("c"), &mut second);
+
+        #[derive(Debug, Default)]
+        struct Outer<'a> {
+            node: Option<NodeView<'a>>,
+        }
+
+        impl<'a> MessageView<'a> for Outer<'a> {
+            type Owned = ();
+
+            fn merge_field(
+                &mut self,
+                tag: u32,
+                wire_type: WireType,
+                buf: &mut &'a [u8],
+                ctx: DecodeContext,
+            ) -> Result<(), DecodeError> {
+                match tag {
+                    1 => merge_message(
+                        wire_type,
+                        self.node.get_or_insert_with(Default::default),
+                        buf,
+                        ctx,
+                    ),
+                    _ => skip_field(wire_type, tag, buf, ctx),
+                }
+            }
+
+            fn to_message(&self) -> Result<(), DecodeError> {
+                Ok(())
+            }
+        }
+
+        let mut buf = Vec::new();
+        encode_child(1, &first, &mut buf);
+        int32::encode(2, &1, &mut buf);
+        encode_child(1, &second, &mut buf);
+
+        let outer = Outer::decode(&buf).unwrap();
+        let tags = &outer.node.as_ref().unwrap().tags;
+        assert_eq!(tags.len(), 3);
+        assert_eq!(
+            tags.iter().collect::<Result<Vec<_>, _>>(),
+            Ok(vec!["a", "b", "c"])
+        );
+        assert_eq!(tags.get(2), Some(Ok("c")));
+        assert_eq!(tags.get(3), None);
+        assert_eq!(
+            tags.iter().rev().collect::<Result<Vec<_>, _>>(),
+            Ok(vec!["c", "b", "a"])
+        );
+        assert_eq!(format!("{:?}", tags), r#"["a", "b", "c"]"#);
+    }
+
+    #[test]
+    fn map_entries() {
+        let mut entry = Vec::new();
+        string::encode(1, &String::from("key"), &mut entry);
+        int32::encode(2, &7, &mut entry);
+        let mut buf = Vec::new();
+        encode_varint(entry.len() as u64, &mut buf);
+        buf.extend_from_slice(&entry);
+        // An entry without a value gets the default value.
+        buf.extend_from_slice(&[0x00]);
+
+        let mut entries: Vec<(&str, i32)> = vec![];
+        let mut input = buf.as_slice();
+        for _ in 0..2 {
+            merge_map(
+                merge_str,
+                int32::merge,
+                -1,
+                &mut entries,
+                &mut input,
+                DecodeContext::default(),
+            )
+            .unwrap();
+        }
+        assert!(input.is_empty());
+        assert_eq!(entries, [("key", 7), ("", -1)]);
+    }
+}
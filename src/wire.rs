@@ -0,0 +1,368 @@
+//! Reading Protobuf data without a schema.
+//!
+//! A [`WireReader`] walks the fields of encoded Protobuf data as a sequence of [`WireToken`]s,
+//! without knowing the type of the message. It can be used to inspect payloads of unknown types,
+//! or to extract a few fields without decoding the whole message:
+//!
+//! ```rust
+//! use prost::wire::{WireReader, WireValue};
+//!
+//! // Field 1 is the varint 150, and field 2 is a nested message whose field 1 is "hi".
+//! let buf = b"\x08\x96\x01\x12\x04\x0a\x02hi";
+//! let mut reader = WireReader::new(buf);
+//!
+//! let token = reader.next().unwrap().unwrap();
+//! assert_eq!((token.tag, token.value), (1, WireValue::Varint(150)));
+//!
+//! let token = reader.next().unwrap().unwrap();
+//! let nested = token.reader().unwrap().next().unwrap().unwrap();
+//! assert_eq!((nested.tag, nested.value), (1, WireValue::LengthDelimited(b"hi")));
+//!
+//! assert!(reader.next().is_none());
+//! ```
+//!
+//! [`decode_raw`] prints encoded data in the text format, like `protoc --decode_raw`.
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use ::bytes::Buf;
+
+use crate::encoding::{decode_key, decode_varint};
+use crate::text_format::print_quoted;
+use crate::{DecodeError, DecodeErrorKind};
+
+pub use crate::encoding::WireType;
+
+/// A field or a group boundary read by a [`WireReader`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WireToken<'a> {
+    /// The field number.
+    pub tag: u32,
+    /// The wire type of the field.
+    pub wire_type: WireType,
+    /// The field value.
+    pub value: WireValue<'a>,
+}
+
+/// The value of a [`WireToken`], tagged by its wire type.
+///
+/// The fields of a group follow its `StartGroup` token, up to the matching `EndGroup` token.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WireValue<'a> {
+    /// A varint-encoded value.
+    Varint(u64),
+    /// A 64-bit value.
+    SixtyFourBit(u64),
+    /// A length-delimited value, such as a string or a nested message.
+    LengthDelimited(&'a [u8]),
+    /// The start of a group.
+    StartGroup,
+    /// The end of a group.
+    EndGroup,
+    /// A 32-bit value.
+    ThirtyTwoBit(u32),
+}
+
+impl<'a> WireToken<'a> {
+    /// Returns a reader over the payload of a length-delimited field, to read it as a nested
+    /// message. Returns `None` for other wire types.
+    pub fn reader(&self) -> Option<WireReader<'a>> {
+        match self.value {
+            WireValue::LengthDelimited(payload) => Some(WireReader::new(payload)),
+            _ => None,
+        }
+    }
+}
+
+/// An iterator over the fields of encoded Protobuf data.
+///
+/// Fields are returned in the order they appear on the wire. The payloads of length-delimited
+/// fields are not interpreted, and can be read as nested messages with [`WireToken::reader`].
+/// Groups are returned as a `StartGroup` token, followed by the fields of the group and a matching
+/// `EndGroup` token.
+///
+/// The iterator returns an error if the data is not well-formed, including if a group is not
+/// terminated by the end of the data, and ends after the first error.
+#[derive(Clone, Debug)]
+pub struct WireReader<'a> {
+    buf: &'a [u8],
+    len: usize,
+    groups: Vec<u32>,
+    failed: bool,
+}
+
+impl<'a> WireReader<'a> {
+    /// Creates a reader over the fields of encoded data.
+    pub fn new(buf: &'a [u8]) -> WireReader<'a> {
+        WireReader {
+            buf,
+            len: buf.len(),
+            groups: Vec::new(),
+            failed: false,
+        }
+    }
+
+    /// Returns the offset of the next token in the data.
+    pub fn offset(&self) -> usize {
+        self.len - self.buf.len()
+    }
+
+    /// Returns the number of groups which have been started but not ended.
+    pub fn depth(&self) -> usize {
+        self.groups.len()
+    }
+
+    fn read_token(&mut self) -> Result<WireToken<'a>, DecodeError> {
+        let (tag, wire_type) = decode_key(&mut self.buf)?;
+        let value = match wire_type {
+            WireType::Varint => WireValue::Varint(decode_varint(&mut self.buf)?),
+            WireType::SixtyFourBit => {
+                self.check_remaining(8)?;
+                WireValue::SixtyFourBit(self.buf.get_u64_le())
+            }
+            WireType::LengthDelimited => {
+                let len = decode_varint(&mut self.buf)?;
+                self.check_remaining(len)?;
+                let (payload, rest) = self.buf.split_at(len as usize);
+                self.buf = rest;
+                WireValue::LengthDelimited(payload)
+            }
+            WireType::StartGroup => {
+                self.groups.push(tag);
+                WireValue::StartGroup
+            }
+            WireType::EndGroup => {
+                if self.groups.pop() != Some(tag) {
+                    return Err(DecodeError::with_kind(
+                        DecodeErrorKind::UnexpectedEndGroup,
+                        "unexpected end group tag",
+                    ));
+                }
+                WireValue::EndGroup
+            }
+            WireType::ThirtyTwoBit => {
+                self.check_remaining(4)?;
+                WireValue::ThirtyTwoBit(self.buf.get_u32_le())
+            }
+        };
+        Ok(WireToken {
+            tag,
+            wire_type,
+            value,
+        })
+    }
+
+    fn check_remaining(&self, len: u64) -> Result<(), DecodeError> {
+        if len > self.buf.len() as u64 {
+            return Err(DecodeError::with_kind(
+                DecodeErrorKind::UnexpectedEof,
+                "buffer underflow",
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl<'a> Iterator for WireReader<'a> {
+    type Item = Result<WireToken<'a>, DecodeError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed {
+            return None;
+        }
+        let result = if self.buf.is_empty() {
+            if self.groups.is_empty() {
+                return None;
+            }
+            Err(DecodeError::with_kind(
+                DecodeErrorKind::UnexpectedEof,
+                "unterminated group",
+            ))
+        } else {
+            self.read_token()
+        };
+        Some(result.map_err(|mut error| {
+            self.failed = true;
+            error.set_offset(self.offset());
+            error
+        }))
+    }
+}
+
+/// Prints encoded Protobuf data in the text format without a schema, like `protoc --decode_raw`.
+///
+/// Fields are printed by their field number. Varints are printed as unsigned integers, and 32-bit
+/// and 64-bit values in hexadecimal. A length-delimited value is printed as a nested message if it
+/// is not empty and can be read as one, and as a quoted string otherwise.
+///
+/// ```rust
+/// let buf = b"\x08\x96\x01\x12\x07\x0a\x05hello\x1d\x00\x00\x80\x3f";
+/// assert_eq!(
+///     prost::decode_raw(buf).unwrap(),
+///     "1: 150\n2 {\n  1: \"hello\"\n}\n3: 0x3f800000\n"
+/// );
+/// ```
+///
+/// Returns an error if the data is not well-formed.
+pub fn decode_raw(buf: &[u8]) -> Result<String, DecodeError> {
+    let mut output = String::new();
+    print_raw(buf, 0, crate::RECURSION_LIMIT, &mut output)?;
+    Ok(output)
+}
+
+/// Prints the fields of encoded data at the given indentation depth, printing length-delimited
+/// values as nested messages up to `recursion_budget` levels deep.
+fn print_raw(
+    buf: &[u8],
+    mut depth: usize,
+    recursion_budget: u32,
+    output: &mut String,
+) -> Result<(), DecodeError> {
+    for token in WireReader::new(buf) {
+        let token = token?;
+        if token.value == WireValue::EndGroup {
+            depth -= 1;
+            indent(depth, output);
+            output.push_str("}\n");
+            continue;
+        }
+
+        indent(depth, output);
+        match token.value {
+            WireValue::Varint(value) => output.push_str(&format!("{}: {}\n", token.tag, value)),
+            WireValue::SixtyFourBit(value) => {
+                output.push_str(&format!("{}: 0x{:016x}\n", token.tag, value))
+            }
+            WireValue::ThirtyTwoBit(value) => {
+                output.push_str(&format!("{}: 0x{:08x}\n", token.tag, value))
+            }
+            WireValue::StartGroup => {
+                output.push_str(&format!("{} {{\n", token.tag));
+                depth += 1;
+            }
+            WireValue::EndGroup => unreachable!(),
+            WireValue::LengthDelimited(payload) => {
+                let mut nested = String::new();
+                if !payload.is_empty()
+                    && recursion_budget > 0
+                    && print_raw(payload, depth + 1, recursion_budget - 1, &mut nested).is_ok()
+                {
+                    output.push_str(&format!("{} {{\n", token.tag));
+                    output.push_str(&nested);
+                    indent(depth, output);
+                    output.push_str("}\n");
+                } else {
+                    output.push_str(&format!("{}: ", token.tag));
+                    print_quoted(payload, core::str::from_utf8(payload).is_ok(), output);
+                    output.push('\n');
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+fn indent(depth: usize, output: &mut String) {
+    for _ in 0..depth {
+        output.push_str("  ");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use alloc::vec;
+
+    use crate::encoding::{encode_key, encode_varint};
+
+    fn tokens(buf: &[u8]) -> Vec<(u32, WireValue<'_>)> {
+        WireReader::new(buf)
+            .map(|token| token.map(|token| (token.tag, token.value)).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn read_tokens() {
+        let mut buf = Vec::new();
+        encode_key(1, WireType::Varint, &mut buf);
+        encode_varint(300, &mut buf);
+        encode_key(2, WireType::SixtyFourBit, &mut buf);
+        buf.extend_from_slice(&7u64.to_le_bytes());
+        encode_key(3, WireType::StartGroup, &mut buf);
+        encode_key(4, WireType::ThirtyTwoBit, &mut buf);
+        buf.extend_from_slice(&9u32.to_le_bytes());
+        encode_key(3, WireType::EndGroup, &mut buf);
+        encode_key(5, WireType::LengthDelimited, &mut buf);
+        encode_varint(3, &mut buf);
+        buf.extend_from_slice(b"\x62\x01c");
+
+        assert_eq!(
+            tokens(&buf),
+            vec![
+                (1, WireValue::Varint(300)),
+                (2, WireValue::SixtyFourBit(7)),
+                (3, WireValue::StartGroup),
+                (4, WireValue::ThirtyTwoBit(9)),
+                (3, WireValue::EndGroup),
+                (5, WireValue::LengthDelimited(b"\x62\x01c")),
+            ]
+        );
+
+        let mut reader = WireReader::new(&buf);
+        assert_eq!(
+            reader.nth(2).unwrap().unwrap().wire_type,
+            WireType::StartGroup
+        );
+        assert_eq!(reader.depth(), 1);
+        assert!(reader.next().unwrap().unwrap().reader().is_none());
+        reader.next();
+        assert_eq!(reader.depth(), 0);
+        let nested = reader.next().unwrap().unwrap().reader().unwrap();
+        assert_eq!(
+            nested.collect::<Result<Vec<_>, _>>().unwrap(),
+            vec![WireToken {
+                tag: 12,
+                wire_type: WireType::LengthDelimited,
+                value: WireValue::LengthDelimited(b"c"),
+            }]
+        );
+        assert_eq!(reader.offset(), buf.len());
+    }
+
+    #[test]
+    fn invalid_data() {
+        for (buf, kind) in [
+            (&b"\x08"[..], DecodeErrorKind::InvalidVarint),
+            (b"\x0a\x05abc", DecodeErrorKind::UnexpectedEof),
+            (b"\x0d\x00\x00", DecodeErrorKind::UnexpectedEof),
+            (b"\x0b\x08\x01", DecodeErrorKind::UnexpectedEof),
+            (b"\x0b\x14", DecodeErrorKind::UnexpectedEndGroup),
+            (b"\x0c", DecodeErrorKind::UnexpectedEndGroup),
+            (b"\x00", DecodeErrorKind::InvalidTag),
+        ] {
+            let mut reader = WireReader::new(buf);
+            let error = reader.find_map(Result::err).unwrap();
+            assert_eq!(error.kind(), kind, "{:?}", buf);
+            assert!(error.offset().is_some());
+            assert!(reader.next().is_none());
+            assert!(decode_raw(buf).is_err());
+        }
+    }
+
+    #[test]
+    fn decode_raw_output() {
+        assert_eq!(decode_raw(b"").unwrap(), "");
+        assert_eq!(
+            decode_raw(b"\x0a\x00\x12\x02\xff\x01\x1a\x03caf\x22\x05\xc3\xa9\x0a\x01\"").unwrap(),
+            "1: \"\"\n2: \"\\377\\001\"\n3: \"caf\"\n4: \"é\\n\\001\\\"\"\n"
+        );
+        assert_eq!(
+            decode_raw(b"\x0b\x11\x01\x00\x00\x00\x00\x00\x00\x00\x0c\x12\x04\x12\x02\x08\x01")
+                .unwrap(),
+            "1 {\n  2: 0x0000000000000001\n}\n2 {\n  2 {\n    1: 1\n  }\n}\n"
+        );
+    }
+}
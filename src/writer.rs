@@ -0,0 +1,314 @@
+//! Writing messages one field at a time.
+
+use alloc::vec::Vec;
+use core::borrow::Borrow;
+use core::fmt;
+#[cfg(feature = "std")]
+use std::io::{self, Write};
+
+use bytes::BufMut;
+
+use crate::encoding::{encode_key, encode_varint, WireType};
+
+/// A writer which encodes a message one field at a time into a [`BufMut`].
+///
+/// Fields are written in the order of the calls, using the per-type helpers in
+/// [`prost::encoding`](crate::encoding), so a message can be written without ever building it in
+/// memory. [`Message::encode`](crate::Message::encode) writes fields in tag order, and callers
+/// should do the same to produce identical output. Repeated fields are written from iterators,
+/// one element at a time.
+///
+/// Nested messages are written with sub-writers. Since a length-delimited field must be prefixed
+/// with its length, the contents of a sub-writer are buffered until it completes; large repeated
+/// fields should be written at the top level to keep memory usage constant.
+///
+/// ```rust
+/// # use prost::{Message, MessageWriter};
+/// use prost::encoding::{message, string, uint64};
+///
+/// #[derive(Clone, PartialEq, Message)]
+/// struct Entry {
+///     #[prost(uint64, tag = "1")]
+///     id: u64,
+/// }
+///
+/// #[derive(Clone, PartialEq, Message)]
+/// struct Export {
+///     #[prost(string, tag = "1")]
+///     name: String,
+///     #[prost(message, repeated, tag = "2")]
+///     entries: Vec<Entry>,
+/// }
+///
+/// let mut writer = MessageWriter::new(Vec::new());
+/// writer
+///     .field(1, String::from("export"), string::encode)
+///     .repeated(2, (0..3).map(|id| Entry { id }), message::encode)
+///     .message(2, |entry| {
+///         entry.field(1, &3, uint64::encode);
+///     });
+///
+/// let export = Export::decode(writer.into_inner().as_slice()).unwrap();
+/// assert_eq!(export.name, "export");
+/// assert_eq!(export.entries.len(), 4);
+/// assert_eq!(export.entries[3].id, 3);
+/// ```
+pub struct MessageWriter<B> {
+    buf: B,
+}
+
+impl<B> MessageWriter<B>
+where
+    B: BufMut,
+{
+    /// Creates a writer which encodes fields into `buf`.
+    pub fn new(buf: B) -> MessageWriter<B> {
+        MessageWriter { buf }
+    }
+
+    /// Writes a field with `encode`, such as [`string::encode`](crate::encoding::string::encode).
+    pub fn field<T, V, F>(&mut self, tag: u32, value: V, encode: F) -> &mut MessageWriter<B>
+    where
+        T: ?Sized,
+        V: Borrow<T>,
+        F: FnOnce(u32, &T, &mut B),
+    {
+        encode(tag, value.borrow(), &mut self.buf);
+        self
+    }
+
+    /// Writes a repeated field with `encode`, one element at a time.
+    ///
+    /// Each element is written as a separate field, which is also valid for packable scalar types.
+    /// A packed field must be prefixed with its length, and can be written with
+    /// [`MessageWriter::packed`] instead.
+    pub fn repeated<T, I, F>(&mut self, tag: u32, values: I, mut encode: F) -> &mut MessageWriter<B>
+    where
+        T: ?Sized,
+        I: IntoIterator,
+        I::Item: Borrow<T>,
+        F: FnMut(u32, &T, &mut B),
+    {
+        for value in values {
+            encode(tag, value.borrow(), &mut self.buf);
+        }
+        self
+    }
+
+    /// Writes a packed repeated field of scalar values, encoding each value with `encode`.
+    ///
+    /// `encode` writes a value without a key, such as
+    /// [`encode_varint`](crate::encoding::encode_varint). The encoded values are buffered until
+    /// the iterator is exhausted. Nothing is written if there are no values.
+    pub fn packed<T, I, F>(&mut self, tag: u32, values: I, mut encode: F) -> &mut MessageWriter<B>
+    where
+        I: IntoIterator<Item = T>,
+        F: FnMut(T, &mut Vec<u8>),
+    {
+        let mut packed = Vec::new();
+        for value in values {
+            encode(value, &mut packed);
+        }
+        if !packed.is_empty() {
+            encode_key(tag, WireType::LengthDelimited, &mut self.buf);
+            encode_varint(packed.len() as u64, &mut self.buf);
+            self.buf.put_slice(&packed);
+        }
+        self
+    }
+
+    /// Writes a nested message field with a sub-writer.
+    ///
+    /// The fields written by `f` are buffered, and written with their length once `f` returns.
+    pub fn message<F>(&mut self, tag: u32, f: F) -> &mut MessageWriter<B>
+    where
+        F: FnOnce(&mut MessageWriter<Vec<u8>>),
+    {
+        let mut nested = MessageWriter::new(Vec::new());
+        f(&mut nested);
+        encode_key(tag, WireType::LengthDelimited, &mut self.buf);
+        encode_varint(nested.buf.len() as u64, &mut self.buf);
+        self.buf.put_slice(&nested.buf);
+        self
+    }
+
+    /// Writes a group field with a sub-writer.
+    ///
+    /// Groups are delimited by start and end keys rather than a length, so the fields written by
+    /// `f` are not buffered.
+    pub fn group<F>(&mut self, tag: u32, f: F) -> &mut MessageWriter<B>
+    where
+        F: FnOnce(&mut MessageWriter<&mut B>),
+    {
+        encode_key(tag, WireType::StartGroup, &mut self.buf);
+        f(&mut MessageWriter::new(&mut self.buf));
+        encode_key(tag, WireType::EndGroup, &mut self.buf);
+        self
+    }
+
+    /// Returns a reference to the underlying buffer.
+    pub fn get_ref(&self) -> &B {
+        &self.buf
+    }
+
+    /// Returns a mutable reference to the underlying buffer.
+    ///
+    /// Writing to the underlying buffer directly may corrupt the message.
+    pub fn get_mut(&mut self) -> &mut B {
+        &mut self.buf
+    }
+
+    /// Consumes the `MessageWriter`, returning the underlying buffer.
+    pub fn into_inner(self) -> B {
+        self.buf
+    }
+}
+
+impl<B> fmt::Debug for MessageWriter<B>
+where
+    B: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MessageWriter")
+            .field("buf", &self.buf)
+            .finish()
+    }
+}
+
+/// The number of buffered bytes at which [`IoMessageWriter`] writes to the underlying writer.
+#[cfg(feature = "std")]
+const IO_BUF_LEN: usize = 8 * 1024;
+
+/// A writer which encodes a message one field at a time to an [`io::Write`].
+///
+/// This is the [`io::Write`] counterpart of [`MessageWriter`], with the same methods. Encoded
+/// fields are buffered, and written to the underlying writer once a few kilobytes have
+/// accumulated, so memory usage stays constant while writing a large repeated field. Any
+/// buffered bytes are written by [`IoMessageWriter::flush`] and
+/// [`IoMessageWriter::into_inner`].
+#[cfg(feature = "std")]
+pub struct IoMessageWriter<W> {
+    writer: W,
+    buf: Vec<u8>,
+}
+
+#[cfg(feature = "std")]
+impl<W> IoMessageWriter<W>
+where
+    W: Write,
+{
+    /// Creates a writer which encodes fields to `writer`.
+    pub fn new(writer: W) -> IoMessageWriter<W> {
+        IoMessageWriter {
+            writer,
+            buf: Vec::new(),
+        }
+    }
+
+    /// Writes a field with `encode`. See [`MessageWriter::field`].
+    pub fn field<T, V, F>(&mut self, tag: u32, value: V, encode: F) -> io::Result<&mut Self>
+    where
+        T: ?Sized,
+        V: Borrow<T>,
+        F: FnOnce(u32, &T, &mut Vec<u8>),
+    {
+        encode(tag, value.borrow(), &mut self.buf);
+        self.write_buf(IO_BUF_LEN)?;
+        Ok(self)
+    }
+
+    /// Writes a repeated field with `encode`, one element at a time. See
+    /// [`MessageWriter::repeated`].
+    pub fn repeated<T, I, F>(&mut self, tag: u32, values: I, mut encode: F) -> io::Result<&mut Self>
+    where
+        T: ?Sized,
+        I: IntoIterator,
+        I::Item: Borrow<T>,
+        F: FnMut(u32, &T, &mut Vec<u8>),
+    {
+        for value in values {
+            encode(tag, value.borrow(), &mut self.buf);
+            self.write_buf(IO_BUF_LEN)?;
+        }
+        Ok(self)
+    }
+
+    /// Writes a packed repeated field of scalar values. See [`MessageWriter::packed`].
+    pub fn packed<T, I, F>(&mut self, tag: u32, values: I, encode: F) -> io::Result<&mut Self>
+    where
+        I: IntoIterator<Item = T>,
+        F: FnMut(T, &mut Vec<u8>),
+    {
+        MessageWriter::new(&mut self.buf).packed(tag, values, encode);
+        self.write_buf(IO_BUF_LEN)?;
+        Ok(self)
+    }
+
+    /// Writes a nested message field with a sub-writer. See [`MessageWriter::message`].
+    pub fn message<F>(&mut self, tag: u32, f: F) -> io::Result<&mut Self>
+    where
+        F: FnOnce(&mut MessageWriter<Vec<u8>>),
+    {
+        MessageWriter::new(&mut self.buf).message(tag, f);
+        self.write_buf(IO_BUF_LEN)?;
+        Ok(self)
+    }
+
+    /// Writes a group field with a sub-writer. See [`MessageWriter::group`].
+    pub fn group<F>(&mut self, tag: u32, f: F) -> io::Result<&mut Self>
+    where
+        F: FnOnce(&mut MessageWriter<&mut Vec<u8>>),
+    {
+        encode_key(tag, WireType::StartGroup, &mut self.buf);
+        f(&mut MessageWriter::new(&mut self.buf));
+        encode_key(tag, WireType::EndGroup, &mut self.buf);
+        self.write_buf(IO_BUF_LEN)?;
+        Ok(self)
+    }
+
+    /// Writes any buffered bytes, and flushes the underlying writer.
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.write_buf(1)?;
+        self.writer.flush()
+    }
+
+    /// Returns a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.writer
+    }
+
+    /// Returns a mutable reference to the underlying writer.
+    ///
+    /// Writing to the underlying writer directly may corrupt the message.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.writer
+    }
+
+    /// Writes any buffered bytes, and returns the underlying writer.
+    pub fn into_inner(mut self) -> io::Result<W> {
+        self.write_buf(1)?;
+        Ok(self.writer)
+    }
+
+    /// Writes the buffered bytes to the underlying writer if there are at least `min_len`.
+    fn write_buf(&mut self, min_len: usize) -> io::Result<()> {
+        if self.buf.len() >= min_len {
+            self.writer.write_all(&self.buf)?;
+            self.buf.clear();
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl<W> fmt::Debug for IoMessageWriter<W>
+where
+    W: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IoMessageWriter")
+            .field("writer", &self.writer)
+            .field("buffered", &self.buf.len())
+            .finish()
+    }
+}
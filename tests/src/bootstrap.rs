@@ -28,6 +28,9 @@ fn bootstrap() {
     prost_build::Config::new()
         .compile_well_known_types()
         .btree_map(["."])
+        .enable_text_format()
+        .enable_json()
+        .enable_field_masks()
         .out_dir(tempdir.path())
         .compile_protos(
             &[
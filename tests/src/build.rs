@@ -159,6 +159,83 @@ fn main() {
         .compile_protos(&[src.join("type_names.proto")], includes)
         .unwrap();
 
+    prost_build::Config::new()
+        .enable_extensions()
+        .compile_protos(&[src.join("extensions.proto")], includes)
+        .unwrap();
+
+    prost_build::Config::new()
+        .preserve_unknown_fields([".unknown_fields.Old", ".unknown_fields.OldNested"])
+        .compile_protos(&[src.join("unknown_fields.proto")], includes)
+        .unwrap();
+
+    prost_build::Config::new()
+        .btree_map(["."])
+        .cached_size([".cached_size.Tree"])
+        .compile_protos(&[src.join("cached_size.proto")], includes)
+        .unwrap();
+
+    prost_build::Config::new()
+        .btree_map(["."])
+        .file_descriptor_set_path(
+            PathBuf::from(env::var("OUT_DIR").expect("OUT_DIR environment variable not set"))
+                .join("dynamic.bin"),
+        )
+        .compile_protos(&[src.join("dynamic.proto")], includes)
+        .unwrap();
+
+    prost_build::Config::new()
+        .btree_map(["."])
+        .bytes([".message_views.Document.checksum"])
+        .message_views([
+            ".message_views.Document",
+            ".message_views.Author",
+            ".message_views.Section",
+        ])
+        .compile_protos(&[src.join("message_views.proto")], includes)
+        .unwrap();
+
+    prost_build::Config::new()
+        .message_views([".lazy_fields.Envelope"])
+        .compile_protos(&[src.join("lazy_fields.proto")], includes)
+        .unwrap();
+
+    prost_build::Config::new()
+        .enable_text_format()
+        .compile_protos(&[src.join("text_format.proto")], includes)
+        .unwrap();
+
+    prost_build::Config::new()
+        .enable_json()
+        .compile_protos(&[src.join("json.proto")], includes)
+        .unwrap();
+
+    prost_build::Config::new()
+        .enable_field_masks()
+        .compile_protos(&[src.join("field_mask.proto")], includes)
+        .unwrap();
+
+    prost_build::Config::new()
+        .projection(
+            ".projection.Record",
+            "summary_projection",
+            ["id", "header.timestamp", "history.labels", "nested.parent"],
+        )
+        .compile_protos(&[src.join("projection.proto")], includes)
+        .unwrap();
+
+    prost_build::Config::new()
+        .enable_text_format()
+        .enable_json()
+        .enable_field_masks()
+        .required_field_presence([
+            ".required_fields.Person.name",
+            ".required_fields.Person.address",
+            ".required_fields.Address",
+        ])
+        .compile_protos(&[src.join("required_fields.proto")], includes)
+        .unwrap();
+
     // Check that attempting to compile a .proto without a package declaration does not result in an error.
     config
         .compile_protos(&[src.join("no_package.proto")], includes)
@@ -0,0 +1,152 @@
+//! Tests for caching the encoded lengths of nested messages.
+
+use prost::alloc::{boxed::Box, format, string::ToString, vec, vec::Vec};
+#[cfg(feature = "std")]
+use prost::bytes::{buf::UninitSlice, BufMut};
+use prost::encoding::{message, WireType};
+use prost::Message;
+
+include!(concat!(env!("OUT_DIR"), "/cached_size.rs"));
+
+fn leaf(name: &str) -> Tree {
+    Tree {
+        name: name.to_string(),
+        choice: Some(tree::Choice::Leaf(1)),
+        ..Tree::default()
+    }
+}
+
+fn tree() -> Tree {
+    Tree {
+        name: "root".to_string(),
+        children: vec![leaf("a"), Tree::default(), leaf("b")],
+        named: [
+            ("c".to_string(), leaf("c")),
+            ("d".to_string(), Tree::default()),
+        ]
+        .into(),
+        choice: Some(tree::Choice::Left(Box::new(Tree {
+            children: vec![leaf("e")],
+            ..Tree::default()
+        }))),
+        ..Tree::default()
+    }
+}
+
+/// Returns `true` if no message in the tree holds a cached length.
+fn is_cleared(tree: &Tree) -> bool {
+    tree.cached_size.get().is_none()
+        && tree.children.iter().all(is_cleared)
+        && tree.named.values().all(is_cleared)
+        && match tree.choice {
+            Some(tree::Choice::Left(ref left)) => is_cleared(left),
+            _ => true,
+        }
+}
+
+/// Encodes a message as field 1 of another message.
+fn encode_field<M: Message>(msg: &M) -> Vec<u8> {
+    let mut buf = Vec::new();
+    message::encode(1, msg, &mut buf);
+    buf
+}
+
+#[test]
+fn encode() {
+    let tree = tree();
+    let encoded = tree.encode_to_vec();
+    assert_eq!(tree.encoded_len(), encoded.len());
+    assert_eq!(Tree::decode(encoded.as_slice()).unwrap(), tree);
+    assert!(is_cleared(&tree));
+    assert_eq!(tree.encode_to_vec_deterministic(), encoded);
+    assert!(is_cleared(&tree));
+
+    // Encoding a message as a field of another message with the encoding functions doesn't use
+    // the cached lengths.
+    let mut expected = vec![(1 << 3) | WireType::LengthDelimited as u8];
+    expected.extend(tree.encode_length_delimited_to_vec());
+    assert_eq!(encode_field(&tree), expected);
+    assert!(is_cleared(&tree));
+
+    let forest = Forest {
+        trees: vec![tree.clone(), Tree::default(), tree],
+    };
+    assert_eq!(
+        Forest::decode(forest.encode_to_vec().as_slice()).unwrap(),
+        forest
+    );
+    assert_eq!(
+        Forest::decode_length_delimited(&encode_field(&forest)[1..]).unwrap(),
+        forest
+    );
+    assert!(forest.trees.iter().all(is_cleared));
+}
+
+#[test]
+fn modified() {
+    let mut tree = tree();
+    let encoded = encode_field(&tree);
+    tree.children[0].name = "a longer name".to_string();
+    tree.named.get_mut("d").unwrap().name = "d".to_string();
+    assert_ne!(encode_field(&tree), encoded);
+    assert_eq!(
+        Tree::decode_length_delimited(&encode_field(&tree)[1..]).unwrap(),
+        tree
+    );
+}
+
+#[test]
+fn ignored() {
+    let tree = tree();
+    assert_eq!(tree, tree.clone());
+    assert!(!format!("{:?}", tree).contains("cached_size"));
+}
+
+/// A buffer which panics once it holds `limit` bytes.
+#[cfg(feature = "std")]
+struct AbortingBuf {
+    buf: Vec<u8>,
+    limit: usize,
+}
+
+#[cfg(feature = "std")]
+unsafe impl BufMut for AbortingBuf {
+    fn remaining_mut(&self) -> usize {
+        self.buf.remaining_mut()
+    }
+
+    unsafe fn advance_mut(&mut self, cnt: usize) {
+        self.buf.advance_mut(cnt)
+    }
+
+    fn chunk_mut(&mut self) -> &mut UninitSlice {
+        assert!(self.buf.len() < self.limit, "encode aborted");
+        self.buf.chunk_mut()
+    }
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn aborted() {
+    let mut tree = tree();
+    let mut buf = AbortingBuf {
+        buf: Vec::new(),
+        limit: 8,
+    };
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| tree.encode(&mut buf)));
+    assert!(result.is_err());
+
+    // The aborted encode leaves lengths in the cells of the messages it didn't reach, which are
+    // not cloned.
+    assert!(!is_cleared(&tree));
+    let last = tree.children.last().unwrap();
+    assert!(last.cached_size.get().is_some());
+    assert_eq!(last.clone().cached_size.get(), None);
+    assert_eq!(last, &last.clone());
+
+    // They are not used by the next encode.
+    tree.children.last_mut().unwrap().name = "a longer name".to_string();
+    let encoded = tree.encode_to_vec();
+    assert_eq!(Tree::decode(encoded.as_slice()).unwrap(), tree);
+    assert!(is_cleared(&tree));
+}
@@ -0,0 +1,145 @@
+//! Tests for the kind, offset and field path of decode errors.
+
+use prost::alloc::{boxed::Box, string::String, vec, vec::Vec};
+use prost::{DecodeBudget, DecodeError, DecodeErrorKind, DecodeOptions, Enumeration, Message};
+
+#[derive(Clone, PartialEq, Message)]
+struct Outer {
+    #[prost(string, tag = "1")]
+    name: String,
+    #[prost(message, optional, tag = "2")]
+    inner: Option<Inner>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct Inner {
+    #[prost(int32, tag = "1")]
+    value: i32,
+    #[prost(string, tag = "2")]
+    text: String,
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct Node {
+    #[prost(message, optional, boxed, tag = "1")]
+    child: Option<Box<Node>>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Enumeration)]
+enum Color {
+    Red = 0,
+    Green = 1,
+}
+
+fn decode_error(buf: &[u8]) -> DecodeError {
+    Outer::decode(buf).unwrap_err()
+}
+
+fn path(error: &DecodeError) -> Vec<(&'static str, &'static str)> {
+    error.path().collect()
+}
+
+#[test]
+fn invalid_utf8() {
+    let error = decode_error(&[0x12, 0x04, 0x12, 0x02, 0xff, 0xff]);
+    assert_eq!(error.kind(), DecodeErrorKind::InvalidUtf8);
+    assert_eq!(error.offset(), Some(6));
+    assert_eq!(path(&error), vec![("Outer", "inner"), ("Inner", "text")]);
+}
+
+#[test]
+fn unexpected_eof() {
+    let error = decode_error(&[0x0a, 0x05, b'a']);
+    assert_eq!(error.kind(), DecodeErrorKind::UnexpectedEof);
+    assert_eq!(error.offset(), Some(2));
+    assert_eq!(path(&error), vec![("Outer", "name")]);
+
+    let error = decode_error(&[0x12, 0x02, 0x08, 0x80]);
+    assert_eq!(error.kind(), DecodeErrorKind::InvalidVarint);
+    assert_eq!(path(&error), vec![("Outer", "inner"), ("Inner", "value")]);
+}
+
+#[test]
+fn wire_type_mismatch() {
+    let error = decode_error(&[0x0a, 0x00, 0x08, 0x01]);
+    assert_eq!(error.kind(), DecodeErrorKind::WireTypeMismatch);
+    assert_eq!(error.offset(), Some(3));
+    assert_eq!(path(&error), vec![("Outer", "name")]);
+}
+
+#[test]
+fn invalid_key() {
+    let error = decode_error(&[0x0a, 0x00, 0x00]);
+    assert_eq!(error.kind(), DecodeErrorKind::InvalidTag);
+    assert_eq!(error.offset(), Some(3));
+    assert_eq!(path(&error), vec![]);
+
+    let error = decode_error(&[0x0f]);
+    assert_eq!(error.kind(), DecodeErrorKind::InvalidWireType);
+    assert_eq!(error.offset(), Some(1));
+
+    let error = decode_error(&[0xff; 11]);
+    assert_eq!(error.kind(), DecodeErrorKind::InvalidVarint);
+    assert!(error.offset().is_some());
+}
+
+#[test]
+fn recursion_limit() {
+    let node = (0..200).fold(Node::default(), |child, _| Node {
+        child: Some(Box::new(child)),
+    });
+    let buf = node.encode_to_vec();
+    let error = Node::decode(buf.as_slice()).unwrap_err();
+    assert_eq!(error.kind(), DecodeErrorKind::RecursionLimit);
+    assert!(!path(&error).is_empty());
+    assert!(path(&error).iter().all(|&entry| entry == ("Node", "child")));
+}
+
+#[test]
+fn invalid_enum_value() {
+    assert_eq!(Color::try_from(1), Ok(Color::Green));
+    let error = Color::try_from(2).unwrap_err();
+    assert_eq!(error.kind(), DecodeErrorKind::InvalidEnumValue);
+    assert_eq!(error.offset(), None);
+}
+
+#[test]
+fn budget_exceeded() {
+    let options = DecodeOptions::new().max_field_bytes(1);
+    let error = Outer::decode_with_options(&[0x0a, 0x02, b'a', b'b'][..], &options).unwrap_err();
+    assert_eq!(error.kind(), DecodeErrorKind::BudgetExceeded);
+    assert_eq!(error.exceeded_budget(), Some(DecodeBudget::FieldBytes));
+    assert_eq!(path(&error), vec![("Outer", "name")]);
+}
+
+#[test]
+fn length_delimited_offset() {
+    let error = Outer::decode_length_delimited(&[0x03, 0x0a, 0x05, b'a'][..]).unwrap_err();
+    assert_eq!(error.kind(), DecodeErrorKind::UnexpectedEof);
+    assert_eq!(error.offset(), Some(3));
+
+    let error = Outer::decode_length_delimited(&[0x05, 0x0a, 0x00][..]).unwrap_err();
+    assert_eq!(error.kind(), DecodeErrorKind::UnexpectedEof);
+    assert_eq!(error.offset(), Some(1));
+}
+
+#[test]
+fn new_is_other() {
+    let error = DecodeError::new("custom");
+    assert_eq!(error.kind(), DecodeErrorKind::Other);
+    assert_eq!(error.offset(), None);
+    assert_eq!(path(&error), vec![]);
+}
+
+#[test]
+fn equality_ignores_kind_and_offset() {
+    let error = decode_error(&[0x0a, 0x05, b'a']);
+    let mut expected = DecodeError::new("buffer underflow");
+    expected.push("Outer", "name");
+    assert_ne!(error.kind(), expected.kind());
+    assert_ne!(error.offset(), expected.offset());
+    assert_eq!(error, expected);
+
+    expected.push("Outer", "inner");
+    assert_ne!(error, expected);
+}
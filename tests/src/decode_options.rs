@@ -0,0 +1,163 @@
+//! Tests for decoding with `DecodeOptions` budgets.
+
+use prost::alloc::{collections::BTreeMap, string::String, vec, vec::Vec};
+use prost::bytes::Bytes;
+use prost::{DecodeBudget, DecodeOptions, Message, UnknownFieldSet};
+
+#[derive(Clone, PartialEq, Message)]
+struct Budgeted {
+    #[prost(string, tag = "1")]
+    name: String,
+    #[prost(bytes = "bytes", tag = "2")]
+    data: Bytes,
+    #[prost(int32, repeated, tag = "3")]
+    packed: Vec<i32>,
+    #[prost(string, repeated, tag = "4")]
+    names: Vec<String>,
+    #[prost(message, repeated, tag = "5")]
+    children: Vec<Budgeted>,
+    #[prost(btree_map = "int32, string", tag = "6")]
+    map: BTreeMap<i32, String>,
+}
+
+fn exceeded(buf: &[u8], options: &DecodeOptions) -> Option<DecodeBudget> {
+    Budgeted::decode_with_options(buf, options)
+        .unwrap_err()
+        .exceeded_budget()
+}
+
+#[test]
+fn unlimited_by_default() {
+    let msg = Budgeted {
+        name: "name".into(),
+        data: Bytes::from(vec![0; 1024]),
+        packed: (0..1000).collect(),
+        ..Budgeted::default()
+    };
+    let buf = msg.encode_to_vec();
+    assert_eq!(
+        Budgeted::decode_with_options(buf.as_slice(), &DecodeOptions::new()).unwrap(),
+        msg
+    );
+}
+
+#[test]
+fn field_bytes() {
+    let buf = Budgeted {
+        name: "0123456789".into(),
+        ..Budgeted::default()
+    }
+    .encode_to_vec();
+    let options = DecodeOptions::new().max_field_bytes(10);
+    assert!(Budgeted::decode_with_options(buf.as_slice(), &options).is_ok());
+    let options = DecodeOptions::new().max_field_bytes(9);
+    assert_eq!(exceeded(&buf, &options), Some(DecodeBudget::FieldBytes));
+
+    let buf = Budgeted {
+        data: Bytes::from(vec![0; 100]),
+        ..Budgeted::default()
+    }
+    .encode_to_vec();
+    assert_eq!(exceeded(&buf, &options), Some(DecodeBudget::FieldBytes));
+}
+
+#[test]
+fn repeated_elements() {
+    let options = DecodeOptions::new().max_repeated_elements(3);
+
+    let msg = Budgeted {
+        packed: vec![1, 2, 3],
+        names: vec!["a".into(), "b".into(), "c".into()],
+        children: vec![Budgeted::default(); 3],
+        map: (0..3).map(|i| (i, String::new())).collect(),
+        ..Budgeted::default()
+    };
+    let buf = msg.encode_to_vec();
+    assert_eq!(
+        Budgeted::decode_with_options(buf.as_slice(), &options).unwrap(),
+        msg
+    );
+
+    let too_many = [
+        Budgeted {
+            packed: vec![1, 2, 3, 4],
+            ..Budgeted::default()
+        },
+        Budgeted {
+            names: vec![String::new(); 4],
+            ..Budgeted::default()
+        },
+        Budgeted {
+            children: vec![Budgeted::default(); 4],
+            ..Budgeted::default()
+        },
+        Budgeted {
+            map: (0..4).map(|i| (i, String::new())).collect(),
+            ..Budgeted::default()
+        },
+    ];
+    for msg in &too_many {
+        assert_eq!(
+            exceeded(&msg.encode_to_vec(), &options),
+            Some(DecodeBudget::RepeatedElements)
+        );
+    }
+}
+
+#[test]
+fn total_bytes() {
+    // Each nested message only takes two bytes on the wire, but allocates a `Budgeted`.
+    let msg = Budgeted {
+        children: vec![Budgeted::default(); 100],
+        ..Budgeted::default()
+    };
+    let buf = msg.encode_to_vec();
+    assert_eq!(buf.len(), 200);
+
+    let size = core::mem::size_of::<Budgeted>();
+    let options = DecodeOptions::new().max_total_bytes(100 * size);
+    assert!(Budgeted::decode_with_options(buf.as_slice(), &options).is_ok());
+    let options = DecodeOptions::new().max_total_bytes(100 * size - 1);
+    assert_eq!(exceeded(&buf, &options), Some(DecodeBudget::TotalBytes));
+
+    // The budget is shared by all fields and levels of nesting.
+    let msg = Budgeted {
+        name: "x".repeat(60),
+        children: vec![Budgeted {
+            name: "y".repeat(60),
+            ..Budgeted::default()
+        }],
+        ..Budgeted::default()
+    };
+    let options = DecodeOptions::new().max_total_bytes(100 + size);
+    assert_eq!(
+        exceeded(&msg.encode_to_vec(), &options),
+        Some(DecodeBudget::TotalBytes)
+    );
+}
+
+#[test]
+fn unknown_fields() {
+    let buf = Budgeted {
+        packed: vec![1; 10],
+        names: vec![String::new(); 10],
+        ..Budgeted::default()
+    }
+    .encode_to_vec();
+    // Unknown fields have their own limit, rather than counting as repeated elements.
+    let options = DecodeOptions::new().max_repeated_elements(10);
+    assert!(UnknownFieldSet::decode_with_options(buf.as_slice(), &options).is_ok());
+    let options = DecodeOptions::new().max_unknown_fields(10);
+    assert_eq!(
+        UnknownFieldSet::decode_with_options(buf.as_slice(), &options)
+            .unwrap_err()
+            .exceeded_budget(),
+        Some(DecodeBudget::UnknownFields)
+    );
+}
+
+#[test]
+fn other_errors_have_no_budget() {
+    let err = Budgeted::decode(&[0x0a, 0x01, 0xff][..]).unwrap_err();
+    assert_eq!(err.exceeded_budget(), None);
+}
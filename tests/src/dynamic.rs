@@ -0,0 +1,290 @@
+//! Tests for dynamic messages, against the descriptors of generated messages.
+
+use prost::alloc::{string::ToString, vec, vec::Vec};
+use prost::bytes::Bytes;
+use prost::Message;
+use prost_types::dynamic::{DescriptorPool, DynamicMessage, Kind, MapKey, Value};
+use prost_types::FileDescriptorSet;
+
+include!(concat!(env!("OUT_DIR"), "/dynamic.rs"));
+
+fn pool() -> DescriptorPool {
+    let file_descriptor_set = include_bytes!(concat!(env!("OUT_DIR"), "/dynamic.bin"));
+    DescriptorPool::new(FileDescriptorSet::decode(&file_descriptor_set[..]).unwrap()).unwrap()
+}
+
+fn address(street: &str, number: u32) -> account::Address {
+    account::Address {
+        street: street.to_string(),
+        number,
+    }
+}
+
+fn account() -> Account {
+    Account {
+        name: "alice".to_string(),
+        status: Status::Active as i32,
+        role: account::Role::Admin as i32,
+        addresses: vec![address("main", 1), account::Address::default()],
+        counters: vec![("a".to_string(), 1), ("b".to_string(), -2)]
+            .into_iter()
+            .collect(),
+        addresses_by_id: vec![(7, address("side", 2)), (0, account::Address::default())]
+            .into_iter()
+            .collect(),
+        statuses: vec![("x".to_string(), Status::Disabled as i32)]
+            .into_iter()
+            .collect(),
+        contact: Some(account::Contact::MailingAddress(address("box", 3))),
+        age: Some(0),
+        deltas: vec![-1, 0, 1],
+        avatar: vec![0xff, 0],
+        home: Some(address("home", 4)),
+    }
+}
+
+fn dynamic_address(pool: &DescriptorPool, street: &str, number: u32) -> Value {
+    let descriptor = pool.get_message_by_name("dynamic.Account.Address").unwrap();
+    let mut address = DynamicMessage::new(descriptor);
+    address.set_field_by_name("street", street.into()).unwrap();
+    address.set_field_by_name("number", number.into()).unwrap();
+    Value::Message(address)
+}
+
+#[test]
+fn descriptors() {
+    let pool = pool();
+    let account = pool.get_message_by_name("dynamic.Account").unwrap();
+    assert_eq!(account.name(), "Account");
+    let address = pool
+        .get_message_by_name(".dynamic.Account.Address")
+        .unwrap();
+    assert_eq!(address.full_name(), "dynamic.Account.Address");
+    assert!(pool.get_message_by_name("dynamic.Status").is_none());
+    assert!(pool.get_enum_by_name("dynamic.Account").is_none());
+
+    // Nested message types.
+    let field = |name| account.get_field_by_name(name).unwrap();
+    assert_eq!(field("home").kind(), Kind::Message(address.clone()));
+    assert!(field("home").supports_presence());
+    assert_eq!(field("addresses").kind(), Kind::Message(address.clone()));
+    assert!(field("addresses").is_list());
+
+    // Enums, at the top level and nested in a message.
+    let status = pool.get_enum_by_name("dynamic.Status").unwrap();
+    assert_eq!(field("status").kind(), Kind::Enum(status.clone()));
+    assert_eq!(
+        status.values().collect::<Vec<_>>(),
+        [
+            ("STATUS_UNKNOWN", 0),
+            ("STATUS_ACTIVE", 1),
+            ("STATUS_DISABLED", 2)
+        ]
+    );
+    let role = match field("role").kind() {
+        Kind::Enum(role) => role,
+        kind => panic!("unexpected kind: {:?}", kind),
+    };
+    assert_eq!(role.full_name(), "dynamic.Account.Role");
+    assert_eq!(role.get_value(1), Some("ROLE_ADMIN"));
+    assert_eq!(role.get_value_by_name("ROLE_USER"), Some(0));
+
+    // Maps, with scalar, message and enum values.
+    let map_value = |name| {
+        let field = field(name);
+        assert!(field.is_map());
+        assert!(!field.is_list());
+        assert_eq!(field.default_value(), Value::Map(Default::default()));
+        match field.kind() {
+            Kind::Message(entry) => {
+                assert!(entry.is_map_entry());
+                (
+                    entry.map_entry_key_field().kind(),
+                    entry.map_entry_value_field().kind(),
+                )
+            }
+            kind => panic!("unexpected kind: {:?}", kind),
+        }
+    };
+    assert_eq!(map_value("counters"), (Kind::String, Kind::Int64));
+    assert_eq!(
+        map_value("addresses_by_id"),
+        (Kind::Uint32, Kind::Message(address))
+    );
+    assert_eq!(map_value("statuses"), (Kind::String, Kind::Enum(status)));
+
+    // Oneofs, excluding the synthetic oneof of the optional field.
+    let oneofs = account.oneofs().collect::<Vec<_>>();
+    assert_eq!(oneofs.len(), 1);
+    assert_eq!(oneofs[0].name(), "contact");
+    assert_eq!(
+        oneofs[0]
+            .fields()
+            .map(|field| field.number())
+            .collect::<Vec<_>>(),
+        [8, 9, 10]
+    );
+    assert_eq!(field("email").containing_oneof(), Some(oneofs[0].clone()));
+    assert_eq!(field("age").containing_oneof(), None);
+    assert!(field("age").supports_presence());
+    assert!(!field("name").supports_presence());
+
+    assert_eq!(field("addresses_by_id").json_name(), "addressesById");
+}
+
+#[test]
+fn roundtrip() {
+    let account = account();
+    let encoded = account.encode_to_vec();
+    let descriptor = pool().get_message_by_name("dynamic.Account").unwrap();
+    let dynamic = DynamicMessage::decode(descriptor, encoded.as_slice()).unwrap();
+    assert_eq!(dynamic.encoded_len(), encoded.len());
+    assert_eq!(dynamic.encode_to_vec(), encoded);
+    assert_eq!(
+        dynamic.encode_to_vec_deterministic(),
+        account.encode_to_vec_deterministic()
+    );
+    assert_eq!(
+        Account::decode(dynamic.encode_to_vec().as_slice()),
+        Ok(account)
+    );
+
+    let descriptor = dynamic.descriptor().clone();
+    let empty = DynamicMessage::decode(descriptor, &[][..]).unwrap();
+    assert_eq!(empty.fields().count(), 0);
+    assert!(empty.encode_to_vec().is_empty());
+}
+
+#[test]
+fn values() {
+    let encoded = account().encode_to_vec();
+    let pool = pool();
+    let descriptor = pool.get_message_by_name("dynamic.Account").unwrap();
+    let dynamic = DynamicMessage::decode(descriptor.clone(), encoded.as_slice()).unwrap();
+    let get = |name| dynamic.get_field_by_name(name).unwrap().into_owned();
+
+    assert_eq!(get("name"), Value::from("alice"));
+    assert_eq!(get("status"), Value::EnumNumber(Status::Active as i32));
+    assert_eq!(get("role"), Value::EnumNumber(account::Role::Admin as i32));
+    assert_eq!(
+        get("addresses"),
+        Value::List(vec![
+            dynamic_address(&pool, "main", 1),
+            dynamic_address(&pool, "", 0)
+        ])
+    );
+    let counters = get("counters");
+    let counters = counters.as_map().unwrap();
+    assert_eq!(counters.len(), 2);
+    assert_eq!(counters[&MapKey::String("b".to_string())], Value::I64(-2));
+    let addresses_by_id = get("addresses_by_id");
+    let addresses_by_id = addresses_by_id.as_map().unwrap();
+    assert_eq!(
+        addresses_by_id[&MapKey::U32(7)],
+        dynamic_address(&pool, "side", 2)
+    );
+    assert_eq!(
+        addresses_by_id[&MapKey::U32(0)],
+        dynamic_address(&pool, "", 0)
+    );
+    assert_eq!(
+        get("statuses").as_map().unwrap()[&MapKey::String("x".to_string())],
+        Value::EnumNumber(Status::Disabled as i32)
+    );
+
+    assert_eq!(get("mailing_address"), dynamic_address(&pool, "box", 3));
+    assert!(dynamic.has_field(&descriptor.get_field_by_name("mailing_address").unwrap()));
+    assert!(!dynamic.has_field(&descriptor.get_field_by_name("email").unwrap()));
+    assert_eq!(get("phone"), Value::U64(0));
+
+    assert_eq!(get("age"), Value::I32(0));
+    assert!(dynamic.has_field(&descriptor.get_field_by_name("age").unwrap()));
+    assert_eq!(
+        get("deltas"),
+        Value::List(vec![Value::I32(-1), Value::I32(0), Value::I32(1)])
+    );
+    assert_eq!(get("avatar"), Value::Bytes(Bytes::from_static(&[0xff, 0])));
+    assert_eq!(get("home"), dynamic_address(&pool, "home", 4));
+    assert_eq!(dynamic.fields().count(), 12);
+}
+
+#[test]
+fn build_message() {
+    let pool = pool();
+    let descriptor = pool.get_message_by_name("dynamic.Account").unwrap();
+    let mut dynamic = DynamicMessage::new(descriptor.clone());
+    dynamic.set_field_by_name("name", "bob".into()).unwrap();
+    dynamic
+        .set_field_by_name("status", Value::EnumNumber(Status::Disabled as i32))
+        .unwrap();
+    dynamic
+        .set_field_by_name(
+            "addresses",
+            Value::List(vec![dynamic_address(&pool, "a", 1)]),
+        )
+        .unwrap();
+    let counters = descriptor.get_field_by_name("counters").unwrap();
+    dynamic
+        .get_field_mut(&counters)
+        .as_map_mut()
+        .unwrap()
+        .insert(MapKey::String("n".to_string()), Value::I64(5));
+    dynamic
+        .set_field_by_name("email", "bob@example.com".into())
+        .unwrap();
+    dynamic
+        .set_field_by_name("home", dynamic_address(&pool, "home", 2))
+        .unwrap();
+
+    let expected = Account {
+        name: "bob".to_string(),
+        status: Status::Disabled as i32,
+        addresses: vec![address("a", 1)],
+        counters: vec![("n".to_string(), 5)].into_iter().collect(),
+        contact: Some(account::Contact::Email("bob@example.com".to_string())),
+        home: Some(address("home", 2)),
+        ..Account::default()
+    };
+    assert_eq!(dynamic.encode_to_vec(), expected.encode_to_vec());
+
+    // Setting another field of the oneof clears the current one.
+    dynamic.set_field_by_name("phone", Value::U64(0)).unwrap();
+    assert!(!dynamic.has_field(&descriptor.get_field_by_name("email").unwrap()));
+    let expected = Account {
+        contact: Some(account::Contact::Phone(0)),
+        ..expected
+    };
+    assert_eq!(
+        Account::decode(dynamic.encode_to_vec().as_slice()),
+        Ok(expected)
+    );
+
+    // Values must have the type of the field, including the type of nested messages.
+    assert!(dynamic.set_field_by_name("status", Value::I32(1)).is_err());
+    assert!(dynamic
+        .set_field_by_name(
+            "home",
+            Value::Message(DynamicMessage::new(descriptor.clone()))
+        )
+        .is_err());
+    assert!(dynamic
+        .set_field_by_name("deltas", Value::List(vec![Value::I64(1)]))
+        .is_err());
+}
+
+#[test]
+fn undefined_enum_values() {
+    let account = Account {
+        status: 7,
+        statuses: vec![("y".to_string(), -1)].into_iter().collect(),
+        ..Account::default()
+    };
+    let encoded = account.encode_to_vec();
+    let descriptor = pool().get_message_by_name("dynamic.Account").unwrap();
+    let dynamic = DynamicMessage::decode(descriptor, encoded.as_slice()).unwrap();
+    assert_eq!(
+        *dynamic.get_field_by_name("status").unwrap(),
+        Value::EnumNumber(7)
+    );
+    assert_eq!(dynamic.encode_to_vec(), encoded);
+}
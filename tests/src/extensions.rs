@@ -0,0 +1,125 @@
+//! Tests for Protobuf extensions.
+
+use prost::alloc::{borrow::ToOwned, vec, vec::Vec};
+use prost::encoding::{int32, WireType};
+use prost::{Extendable, Extension, ExtensionSet, Message};
+
+include!(concat!(env!("OUT_DIR"), "/extensions.rs"));
+
+#[test]
+fn generated_descriptors() {
+    assert_eq!(NUMBER.number(), 100);
+    assert_eq!(NUMBER.name(), "extensions.number");
+    assert_eq!(NUMBER.wire_type(), WireType::Varint);
+    assert_eq!(PACKED_VALUES.wire_type(), WireType::LengthDelimited);
+    assert_eq!(TAGS.wire_type(), WireType::LengthDelimited);
+    assert_eq!(DATA.wire_type(), WireType::StartGroup);
+    assert_eq!(scope::FLAG.number(), 1000);
+    assert_eq!(scope::FLAG.name(), "extensions.Scope.flag");
+}
+
+#[test]
+fn get_set_clear() {
+    let mut msg = Container {
+        name: Some("name".to_owned()),
+        ..Container::default()
+    };
+    assert!(!msg.has_extension(&NUMBER));
+    assert_eq!(msg.get_extension(&NUMBER).unwrap(), None);
+
+    msg.set_extension(&NUMBER, 42);
+    msg.set_extension(&TAGS, vec!["a".to_owned(), "b".to_owned()]);
+    msg.set_extension(&PACKED_VALUES, vec![-1, 0, 1]);
+    msg.set_extension(&PAYLOAD, Payload { value: Some(7) });
+    msg.set_extension(&COLOR, Color::Green as i32);
+    msg.set_extension(&DATA, Data { a: Some(3) });
+    msg.set_extension(&scope::FLAG, true);
+
+    let decoded = Container::decode(msg.encode_to_vec().as_slice()).unwrap();
+    assert_eq!(decoded, msg);
+    assert_eq!(decoded.name.as_deref(), Some("name"));
+    assert_eq!(decoded.get_extension(&NUMBER).unwrap(), Some(42));
+    assert_eq!(
+        decoded.get_extension(&TAGS).unwrap(),
+        Some(vec!["a".to_owned(), "b".to_owned()])
+    );
+    assert_eq!(
+        decoded.get_extension(&PACKED_VALUES).unwrap(),
+        Some(vec![-1, 0, 1])
+    );
+    assert_eq!(
+        decoded.get_extension(&PAYLOAD).unwrap(),
+        Some(Payload { value: Some(7) })
+    );
+    assert_eq!(
+        decoded.get_extension(&COLOR).unwrap(),
+        Some(Color::Green as i32)
+    );
+    assert_eq!(
+        decoded.get_extension(&DATA).unwrap(),
+        Some(Data { a: Some(3) })
+    );
+    assert_eq!(decoded.get_extension(&scope::FLAG).unwrap(), Some(true));
+
+    msg.clear_extension(&NUMBER);
+    assert!(!msg.has_extension(&NUMBER));
+    assert!(msg.has_extension(&TAGS));
+}
+
+#[test]
+fn unclaimed_extensions_are_preserved() {
+    // Field 150 is within an extension range, but no extension is declared for it.
+    let mut buf = Vec::new();
+    int32::encode(150, &5, &mut buf);
+    int32::encode(100, &1, &mut buf);
+
+    let msg = Container::decode(buf.as_slice()).unwrap();
+    assert_eq!(msg.extension_set.iter().count(), 2);
+    assert_eq!(msg.get_extension(&NUMBER).unwrap(), Some(1));
+    assert_eq!(msg.encode_to_vec(), buf);
+
+    let unclaimed: Extension<Container, prost::extension::Int32> =
+        Extension::new(150, "extensions.unclaimed");
+    assert_eq!(msg.get_extension(&unclaimed).unwrap(), Some(5));
+}
+
+#[test]
+fn fields_outside_extension_ranges_are_not_extensions() {
+    let mut buf = Vec::new();
+    int32::encode(50, &5, &mut buf);
+
+    let msg = Container::decode(buf.as_slice()).unwrap();
+    assert!(msg.extension_set.is_empty());
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct Derived {
+    #[prost(int32, tag = "1")]
+    a: i32,
+    #[prost(extensions = "10..20, 30")]
+    extensions: ExtensionSet,
+    #[prost(int32, tag = "15")]
+    in_range: i32,
+}
+
+#[test]
+fn derived_extension_ranges() {
+    const TEN: Extension<Derived, prost::extension::Int32> = Extension::new(10, "ten");
+    const NINETEEN: Extension<Derived, prost::extension::Int32> = Extension::new(19, "nineteen");
+    const TWENTY: Extension<Derived, prost::extension::Int32> = Extension::new(20, "twenty");
+    const THIRTY: Extension<Derived, prost::extension::Int32> = Extension::new(30, "thirty");
+
+    let mut buf = Vec::new();
+    for tag in [10, 15, 19, 20, 30] {
+        int32::encode(tag, &1, &mut buf);
+    }
+    let msg = Derived::decode(buf.as_slice()).unwrap();
+
+    assert!(msg.has_extension(&TEN));
+    assert!(msg.has_extension(&NINETEEN));
+    assert!(!msg.has_extension(&TWENTY));
+    assert!(msg.has_extension(&THIRTY));
+    // Declared fields take precedence over the extension ranges.
+    assert_eq!(msg.in_range, 1);
+    assert_eq!(msg.extensions.iter().count(), 3);
+}
@@ -0,0 +1,239 @@
+//! Tests for field mask operations on generated messages.
+
+use prost::alloc::{
+    boxed::Box,
+    string::{String, ToString},
+    vec,
+};
+use prost::field_mask::MergeOptions;
+use prost::FieldMasked;
+
+include!(concat!(env!("OUT_DIR"), "/field_mask.rs"));
+
+fn author(name: &str, email: &str) -> Author {
+    Author {
+        name: name.to_string(),
+        email: email.to_string(),
+        mentor: None,
+    }
+}
+
+fn book() -> Book {
+    Book {
+        title: "Dune".to_string(),
+        author: Some(author("Frank Herbert", "frank@example.com")),
+        tags: vec!["fiction".to_string()],
+        ratings: [("critics".to_string(), 4)].into_iter().collect(),
+        format: Some(book::Format::Isbn("0441172717".to_string())),
+        published: Some(prost_types::Timestamp {
+            seconds: -139_000_000,
+            nanos: 0,
+        }),
+        pages: Some(412),
+        editors: vec![author("Sterling Lanier", "")],
+    }
+}
+
+fn update() -> Book {
+    Book {
+        title: "Dune Messiah".to_string(),
+        author: Some(author("F. Herbert", "")),
+        tags: vec!["sequel".to_string()],
+        ratings: [("readers".to_string(), 5)].into_iter().collect(),
+        format: Some(book::Format::Edition(Edition {
+            number: 2,
+            publisher: "Putnam".to_string(),
+        })),
+        published: None,
+        pages: None,
+        editors: vec![],
+    }
+}
+
+#[test]
+fn validate_mask() {
+    for path in [
+        "title",
+        "author",
+        "author.name",
+        "author.mentor.mentor.email",
+        "tags",
+        "ratings",
+        "isbn",
+        "edition.publisher",
+        "published.seconds",
+        "pages",
+    ] {
+        Book::validate_mask(&[path]).unwrap();
+    }
+
+    for (path, error_path) in [
+        ("subtitle", "subtitle"),
+        ("author.nickname", "author.nickname"),
+        ("author.mentor.age", "author.mentor.age"),
+        ("tags.length", "tags.length"),
+        ("editors.name", "editors.name"),
+        ("ratings.critics", "ratings.critics"),
+        ("isbn.prefix", "isbn.prefix"),
+        ("published.days", "published.days"),
+        ("format", "format"),
+        ("author..name", "author..name"),
+    ] {
+        let error = Book::validate_mask(&["title", path]).unwrap_err();
+        assert_eq!(error.path(), error_path);
+    }
+}
+
+#[test]
+fn merge_with_mask() {
+    let mut book = book();
+    book.merge_with_mask(
+        &update(),
+        &["title", "author.name", "tags", "ratings", "published"],
+    )
+    .unwrap();
+
+    let mut expected = self::book();
+    expected.title = "Dune Messiah".to_string();
+    expected.author = Some(author("F. Herbert", "frank@example.com"));
+    expected.tags = vec!["sequel".to_string()];
+    expected.ratings = [("readers".to_string(), 5)].into_iter().collect();
+    expected.published = None;
+    assert_eq!(book, expected);
+
+    // Unmasked fields are left unchanged, even if they are set in the source message.
+    let mut book = self::book();
+    book.merge_with_mask(&update(), &["pages"]).unwrap();
+    assert_eq!(book.pages, None);
+    assert_eq!(book.title, "Dune");
+    assert_eq!(book.format, self::book().format);
+}
+
+#[test]
+fn merge_with_mask_options() {
+    let options = MergeOptions::new()
+        .replace_repeated_fields(false)
+        .replace_message_fields(false);
+    let mut book = book();
+    book.merge_with_mask_options(&update(), &["tags", "ratings", "author"], &options)
+        .unwrap();
+    assert_eq!(book.tags, vec!["fiction", "sequel"]);
+    assert_eq!(
+        book.ratings,
+        [("critics".to_string(), 4), ("readers".to_string(), 5)]
+            .into_iter()
+            .collect()
+    );
+    // The source author is merged, which leaves the email unchanged since it is not set in the
+    // source.
+    assert_eq!(book.author, Some(author("F. Herbert", "frank@example.com")));
+
+    // A message field which is not set in the source is replaced by default, or left unchanged
+    // when merging.
+    let mut book = self::book();
+    book.merge_with_mask_options(&update(), &["published"], &options)
+        .unwrap();
+    assert_eq!(book.published, self::book().published);
+    book.merge_with_mask(&update(), &["published"]).unwrap();
+    assert_eq!(book.published, None);
+}
+
+#[test]
+fn merge_nested_path() {
+    // Descending into a message field which is not set in the source clears the masked fields.
+    let mut book = book();
+    book.merge_with_mask(&Book::default(), &["author.email"])
+        .unwrap();
+    assert_eq!(book.author, Some(author("Frank Herbert", "")));
+
+    // Descending into a message field which is not set in either message leaves it unset.
+    let mut book = Book::default();
+    book.merge_with_mask(&Book::default(), &["author.mentor.name"])
+        .unwrap();
+    assert_eq!(book.author, None);
+
+    // Descending into a message field which is only set in the source creates it.
+    let src = Book {
+        author: Some(Author {
+            mentor: Some(Box::new(author("Mentor", "mentor@example.com"))),
+            ..author("Author", "")
+        }),
+        ..Book::default()
+    };
+    let mut book = Book::default();
+    book.merge_with_mask(&src, &["author.mentor.name"]).unwrap();
+    assert_eq!(
+        book.author,
+        Some(Author {
+            mentor: Some(Box::new(author("Mentor", ""))),
+            ..Author::default()
+        })
+    );
+}
+
+#[test]
+fn merge_oneof() {
+    // Masking a oneof field which is set in the source replaces the oneof.
+    let mut book = book();
+    book.merge_with_mask(&update(), &["edition.number"])
+        .unwrap();
+    assert_eq!(
+        book.format,
+        Some(book::Format::Edition(Edition {
+            number: 2,
+            publisher: String::new(),
+        }))
+    );
+
+    // Masking a oneof field which is set in `self` but not in the source clears the oneof.
+    let mut book = self::book();
+    book.merge_with_mask(&update(), &["isbn"]).unwrap();
+    assert_eq!(book.format, None);
+
+    // Masking a oneof field which is set in neither message leaves the oneof unchanged.
+    let mut book = self::book();
+    book.merge_with_mask(&Book::default(), &["edition"])
+        .unwrap();
+    assert_eq!(book.format, self::book().format);
+}
+
+#[test]
+fn prune() {
+    let mut book = book();
+    book.prune(&["title", "author.name", "isbn", "editors"])
+        .unwrap();
+    assert_eq!(
+        book,
+        Book {
+            title: "Dune".to_string(),
+            author: Some(author("Frank Herbert", "")),
+            format: Some(book::Format::Isbn("0441172717".to_string())),
+            editors: vec![author("Sterling Lanier", "")],
+            ..Book::default()
+        }
+    );
+
+    let mut book = self::book();
+    book.prune(&["edition", "published.nanos"]).unwrap();
+    assert_eq!(
+        book,
+        Book {
+            published: Some(prost_types::Timestamp::default()),
+            ..Book::default()
+        }
+    );
+
+    let mut book = self::book();
+    book.prune::<&str>(&[]).unwrap();
+    assert_eq!(book, Book::default());
+}
+
+#[test]
+fn invalid_mask_leaves_message_unchanged() {
+    let mut book = book();
+    assert!(book
+        .merge_with_mask(&update(), &["title", "bogus"])
+        .is_err());
+    assert!(book.prune(&["title", "bogus"]).is_err());
+    assert_eq!(book, self::book());
+}
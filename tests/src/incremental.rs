@@ -0,0 +1,253 @@
+//! Tests for decoding messages from input which arrives in chunks.
+
+use prost::alloc::{string::String, string::ToString, vec, vec::Vec};
+use prost::{
+    DecodeBudget, DecodeErrorKind, DecodeOptions, DecodeStatus, IncrementalDecoder, Message,
+};
+
+#[derive(Clone, PartialEq, Message)]
+struct Record {
+    #[prost(string, tag = "1")]
+    name: String,
+    #[prost(int64, tag = "2")]
+    id: i64,
+    #[prost(fixed32, tag = "3")]
+    checksum: u32,
+    #[prost(double, tag = "4")]
+    score: f64,
+    #[prost(message, optional, tag = "5")]
+    inner: Option<Inner>,
+    #[prost(uint64, repeated, tag = "6")]
+    values: Vec<u64>,
+    #[prost(group, optional, tag = "7")]
+    group: Option<Group>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct Inner {
+    #[prost(bytes = "vec", tag = "1")]
+    data: Vec<u8>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct Group {
+    #[prost(sint32, tag = "8")]
+    value: i32,
+    #[prost(group, optional, tag = "9")]
+    nested: Option<Nested>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct Nested {
+    #[prost(string, tag = "10")]
+    text: String,
+}
+
+fn record() -> Record {
+    Record {
+        name: "incremental".to_string(),
+        id: -1,
+        checksum: 0xdead_beef,
+        score: 0.5,
+        inner: Some(Inner {
+            data: vec![0x80; 200],
+        }),
+        values: vec![0, 127, 128, 16_384, u64::MAX],
+        group: Some(Group {
+            value: -300,
+            nested: Some(Nested {
+                text: "nested".to_string(),
+            }),
+        }),
+    }
+}
+
+#[test]
+fn decode_in_chunks() {
+    let buf = record().encode_to_vec();
+    for size in 1..=buf.len() {
+        let mut decoder = IncrementalDecoder::<Record>::new();
+        for mut chunk in buf.chunks(size) {
+            assert_eq!(decoder.decode(&mut chunk), Ok(DecodeStatus::NeedMoreData));
+            assert!(chunk.is_empty());
+        }
+        assert_eq!(decoder.finish(), Ok(record()), "chunk size {}", size);
+        assert!(decoder.is_empty());
+    }
+}
+
+#[test]
+fn decode_length_delimited_in_chunks() {
+    let buf = record().encode_length_delimited_to_vec();
+    for size in 1..=buf.len() {
+        let mut decoder = IncrementalDecoder::<Record>::length_delimited();
+        let mut chunks = buf.chunks(size);
+        let decoded = loop {
+            let mut chunk = chunks.next().unwrap();
+            if let DecodeStatus::Done(decoded) = decoder.decode(&mut chunk).unwrap() {
+                assert!(chunk.is_empty());
+                break decoded;
+            }
+        };
+        assert_eq!(decoded, record(), "chunk size {}", size);
+        assert!(chunks.next().is_none());
+    }
+}
+
+#[test]
+fn decode_stream() {
+    let second = Record {
+        name: "second".to_string(),
+        ..Record::default()
+    };
+    let mut buf = record().encode_length_delimited_to_vec();
+    buf.extend(Record::default().encode_length_delimited_to_vec());
+    buf.extend(second.encode_length_delimited_to_vec());
+
+    let mut decoder = IncrementalDecoder::<Record>::length_delimited();
+    let mut chunk = buf.as_slice();
+    assert_eq!(decoder.decode(&mut chunk), Ok(DecodeStatus::Done(record())));
+    assert_eq!(
+        decoder.decode(&mut chunk),
+        Ok(DecodeStatus::Done(Record::default()))
+    );
+    assert_eq!(decoder.decode(&mut chunk), Ok(DecodeStatus::Done(second)));
+    assert!(chunk.is_empty());
+    assert_eq!(decoder.decode(&mut chunk), Ok(DecodeStatus::NeedMoreData));
+    assert!(decoder.is_empty());
+}
+
+#[test]
+fn truncated_input() {
+    let buf = record().encode_to_vec();
+    let mut decoder = IncrementalDecoder::<Record>::new();
+    let mut chunk = &buf[..buf.len() - 1];
+    assert_eq!(decoder.decode(&mut chunk), Ok(DecodeStatus::NeedMoreData));
+    let error = decoder.finish().unwrap_err();
+    assert_eq!(error.kind(), DecodeErrorKind::UnexpectedEof);
+    assert_eq!(error.offset(), Some(buf.len() - 1));
+    assert!(decoder.is_empty());
+
+    let buf = record().encode_length_delimited_to_vec();
+    let mut decoder = IncrementalDecoder::<Record>::length_delimited();
+    let mut chunk = &buf[..buf.len() - 1];
+    assert_eq!(decoder.decode(&mut chunk), Ok(DecodeStatus::NeedMoreData));
+    assert!(decoder.finish().is_err());
+}
+
+#[test]
+fn invalid_input() {
+    for (buf, kind, offset) in [
+        // A string field with the varint wire type.
+        (&b"\x08\x01"[..], DecodeErrorKind::WireTypeMismatch, 1),
+        (
+            b"\x10\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff",
+            DecodeErrorKind::InvalidVarint,
+            11,
+        ),
+        (b"\x3b\x44", DecodeErrorKind::UnexpectedEndGroup, 2),
+        (b"\x00", DecodeErrorKind::InvalidTag, 1),
+    ] {
+        let mut decoder = IncrementalDecoder::<Record>::new();
+        let error = buf
+            .chunks(1)
+            .find_map(|mut chunk| decoder.decode(&mut chunk).err())
+            .unwrap();
+        assert_eq!(error.kind(), kind, "{:?}", buf);
+        assert_eq!(error.offset(), Some(offset), "{:?}", buf);
+        assert!(decoder.is_empty());
+    }
+
+    // A field extending past the end of the length-delimited message.
+    let mut decoder = IncrementalDecoder::<Record>::length_delimited();
+    let error = decoder.decode(&mut &b"\x02\x0a\x02ab"[..]).unwrap_err();
+    assert_eq!(error.kind(), DecodeErrorKind::InvalidLength);
+    let error = decoder.decode(&mut &b"\x02\x10\x81\x01"[..]).unwrap_err();
+    assert_eq!(error.kind(), DecodeErrorKind::InvalidLength);
+}
+
+#[test]
+fn with_options() {
+    let options = DecodeOptions::new().max_field_bytes(100);
+    let mut decoder = IncrementalDecoder::<Record>::new().with_options(&options);
+    let error = decoder
+        .decode(&mut record().encode_to_vec().as_slice())
+        .unwrap_err();
+    assert_eq!(error.exceeded_budget(), Some(DecodeBudget::FieldBytes));
+    assert_eq!(error.path().collect::<Vec<_>>(), vec![("Record", "inner")]);
+
+    let options = DecodeOptions::new().recursion_limit(1);
+    let mut decoder = IncrementalDecoder::<Record>::new().with_options(&options);
+    let error = decoder
+        .decode(&mut record().encode_to_vec().as_slice())
+        .unwrap_err();
+    assert_eq!(error.kind(), DecodeErrorKind::RecursionLimit);
+}
+
+#[test]
+fn nested_fields() {
+    // Nested messages are received field by field, so an empty one is set, and one longer than
+    // `max_field_bytes` is decoded as long as its fields are not.
+    let options = DecodeOptions::new().max_field_bytes(200);
+    for record in [
+        Record {
+            inner: Some(Inner::default()),
+            ..Record::default()
+        },
+        Record {
+            inner: Some(Inner {
+                data: vec![0x80; 200],
+            }),
+            group: Some(Group {
+                value: 1,
+                nested: Some(Nested::default()),
+            }),
+            ..Record::default()
+        },
+    ] {
+        let buf = record.encode_to_vec();
+        for size in 1..=buf.len() {
+            let mut decoder = IncrementalDecoder::<Record>::new().with_options(&options);
+            for mut chunk in buf.chunks(size) {
+                assert_eq!(decoder.decode(&mut chunk), Ok(DecodeStatus::NeedMoreData));
+            }
+            assert_eq!(
+                decoder.finish().as_ref(),
+                Ok(&record),
+                "chunk size {}",
+                size
+            );
+        }
+    }
+
+    // A buffered field fails as soon as its length is received.
+    let buf = Record {
+        inner: Some(Inner {
+            data: vec![0x80; 200],
+        }),
+        ..Record::default()
+    }
+    .encode_to_vec();
+    for (options, budget) in [
+        (
+            DecodeOptions::new().max_field_bytes(199),
+            DecodeBudget::FieldBytes,
+        ),
+        (
+            DecodeOptions::new().max_total_bytes(199),
+            DecodeBudget::TotalBytes,
+        ),
+    ] {
+        let mut decoder = IncrementalDecoder::<Record>::new().with_options(&options);
+        let error = decoder.decode(&mut &buf[..6]).unwrap_err();
+        assert_eq!(error.exceeded_budget(), Some(budget));
+        assert_eq!(error.offset(), Some(6));
+        assert_eq!(error.path().collect::<Vec<_>>(), vec![("Record", "inner")]);
+    }
+
+    // So does a field which does not fit in its message.
+    let mut decoder = IncrementalDecoder::<Record>::new();
+    let error = decoder.decode(&mut &b"\x2a\x02\x0a\x02"[..]).unwrap_err();
+    assert_eq!(error.kind(), DecodeErrorKind::InvalidLength);
+    assert_eq!(error.path().collect::<Vec<_>>(), vec![("Record", "inner")]);
+}
@@ -0,0 +1,181 @@
+//! Tests for the canonical Protobuf JSON mapping.
+
+use prost::alloc::{boxed::Box, string::ToString, vec};
+use prost::json::{Json, JsonOptions, TypeRegistry};
+use prost::Message;
+
+include!(concat!(env!("OUT_DIR"), "/json.rs"));
+
+fn project() -> Project {
+    Project {
+        name: "prost".to_string(),
+        downloads: 1_000_000,
+        rating: 4.5,
+        checksum: vec![0, 0xff, b'a'],
+        status: Status::Active as i32,
+        public: true,
+        stars: Some(0),
+        owner: Some(Owner {
+            display_name: "Tokio".to_string(),
+            id: u64::MAX,
+        }),
+        tags: vec!["protobuf".to_string(), "serialization".to_string()],
+        history: vec![Status::Archived as i32, 7],
+        maintainers: [("a".to_string(), Owner::default())].into(),
+        releases: [(12, 3), (-1, 0)].into(),
+        license: Some(project::License::SpdxId("Apache-2.0".to_string())),
+        progress: 0.25,
+        nothing: 0,
+        parent: Some(Box::new(Project {
+            name: "protobuf".to_string(),
+            ..Project::default()
+        })),
+    }
+}
+
+#[test]
+fn write() {
+    assert_eq!(Owner::default().to_json().unwrap(), "{}");
+    assert_eq!(Project::default().to_json().unwrap(), "{}");
+    assert_eq!(
+        project().to_json().unwrap(),
+        "{\"name\":\"prost\",\"downloads\":\"1000000\",\"rating\":4.5,\"checksum\":\"AP9h\",\
+         \"status\":\"ACTIVE\",\"public\":true,\"stars\":0,\
+         \"owner\":{\"displayName\":\"Tokio\",\"id\":\"18446744073709551615\"},\
+         \"tags\":[\"protobuf\",\"serialization\"],\"history\":[\"ARCHIVED\",7],\
+         \"maintainers\":{\"a\":{}},\"releases\":{\"-1\":\"0\",\"12\":\"3\"},\
+         \"spdxId\":\"Apache-2.0\",\"percentDone\":0.25,\"parent\":{\"name\":\"protobuf\"}}"
+    );
+}
+
+#[test]
+fn write_with_options() {
+    let options = JsonOptions::new()
+        .emit_defaults(true)
+        .proto_field_names(true);
+    assert_eq!(
+        Owner::default().to_json_with_options(&options).unwrap(),
+        "{\"display_name\":\"\",\"id\":\"0\"}"
+    );
+    let json = Project::default().to_json_with_options(&options).unwrap();
+    assert!(
+        json.contains("\"status\":\"STATUS_UNSPECIFIED\""),
+        "{}",
+        json
+    );
+    assert!(json.contains("\"nothing\":null"), "{}", json);
+    assert!(json.contains("\"tags\":[]"), "{}", json);
+    assert!(json.contains("\"progress\":0.0"), "{}", json);
+    // Fields with presence are not written when unset.
+    assert!(!json.contains("stars"), "{}", json);
+    assert!(!json.contains("owner"), "{}", json);
+}
+
+#[test]
+fn roundtrip() {
+    let project = project();
+    assert_eq!(
+        Project::parse_json(&project.to_json().unwrap()).unwrap(),
+        project
+    );
+    let options = JsonOptions::new()
+        .emit_defaults(true)
+        .proto_field_names(true);
+    assert_eq!(
+        Project::parse_json(&project.to_json_with_options(&options).unwrap()).unwrap(),
+        project
+    );
+}
+
+#[test]
+fn parse() {
+    let json = r#"
+        {
+            "name": "prost",
+            "downloads": 12,
+            "rating": "NaN",
+            "checksum": "AP9h",
+            "status": 2,
+            "stars": null,
+            "owner": { "display_name": "Tokio", "id": "1e3" },
+            "tags": null,
+            "history": ["ACTIVE", 5],
+            "releases": { "1": "2" },
+            "custom_license": null,
+            "progress": 1.5,
+            "nothing": "NULL_VALUE"
+        }
+    "#;
+    let project = Project::parse_json(json).unwrap();
+    assert_eq!(project.downloads, 12);
+    assert!(project.rating.is_nan());
+    assert_eq!(project.status(), Status::Archived);
+    assert_eq!(project.stars, None);
+    assert_eq!(project.owner.unwrap().id, 1000);
+    assert!(project.tags.is_empty());
+    assert_eq!(project.history, vec![Status::Active as i32, 5]);
+    assert_eq!(project.releases[&1], 2);
+    // `null` is a value of `google.protobuf.Value`.
+    assert_eq!(
+        project.license,
+        Some(project::License::CustomLicense(prost_types::Value {
+            kind: Some(prost_types::value::Kind::NullValue(0))
+        }))
+    );
+    assert_eq!(project.progress, 1.5);
+}
+
+#[test]
+fn parse_errors() {
+    let error = Project::parse_json("{\n  \"status\": \"DELETED\"\n}").unwrap_err();
+    assert_eq!((error.line(), error.column()), (Some(2), Some(13)));
+
+    assert!(Project::parse_json(r#"{"unknown": 1}"#).is_err());
+    assert!(Project::parse_json(r#"{"spdxId": "MIT", "customLicense": 1}"#).is_err());
+    assert!(Project::parse_json(r#"{"stars": 2147483648}"#).is_err());
+    assert!(Project::parse_json(r#"{"stars": 1.5}"#).is_err());
+    assert!(Project::parse_json(r#"{"public": "true"}"#).is_err());
+    assert!(Project::parse_json(r#"{"releases": {"a": "1"}}"#).is_err());
+    assert!(Project::parse_json(r#"{"name": "a"} {}"#).is_err());
+
+    let options = JsonOptions::new().ignore_unknown_fields(true);
+    assert_eq!(
+        Project::parse_json_with_options(r#"{"unknown": [{"a": null}], "name": "a"}"#, &options)
+            .unwrap()
+            .name,
+        "a"
+    );
+}
+
+#[test]
+fn well_known_types() {
+    let mut registry = TypeRegistry::new();
+    prost_types::register_well_known_types(&mut registry);
+    registry.register_full_name::<Owner>("json.Owner");
+    let options = JsonOptions::new().type_registry(&registry);
+
+    let json = r#"{"created":"1972-01-01T10:00:20.021Z","timeout":"1.500s","mask":"displayName,id","metadata":{"a":[1.0,"b",null]},"value":true,"list":[],"details":{"@type":"type.googleapis.com/json.Owner","displayName":"Tokio"},"count":"3","label":"","payload":"AA==","empty":{},"scores":[1,2]}"#;
+    let message = WellKnownTypes::parse_json_with_options(json, &options).unwrap();
+    assert_eq!(message.timeout.as_ref().unwrap().nanos, 500_000_000);
+    assert_eq!(
+        message.mask.as_ref().unwrap().paths,
+        vec!["display_name".to_string(), "id".to_string()]
+    );
+    assert_eq!(message.count, Some(3));
+    assert_eq!(message.label.as_deref(), Some(""));
+    assert_eq!(message.empty, Some(()));
+    assert_eq!(message.scores, vec![1, 2]);
+    assert_eq!(message.to_json_with_options(&options).unwrap(), json);
+
+    let details = message.details.unwrap();
+    assert_eq!(
+        Owner::decode(&*details.value).unwrap(),
+        Owner {
+            display_name: "Tokio".to_string(),
+            id: 0,
+        }
+    );
+    // Any messages can not be written or parsed without their type in the registry.
+    assert!(details.to_json().is_err());
+    assert!(WellKnownTypes::parse_json(json).is_err());
+}
@@ -0,0 +1,141 @@
+//! Tests for message fields with the `lazy` option.
+
+use prost::alloc::{boxed::Box, string::ToString, vec, vec::Vec};
+use prost::view::MessageView;
+use prost::{DecodeBudget, DecodeOptions, Lazy, Message};
+
+include!(concat!(env!("OUT_DIR"), "/lazy_fields.rs"));
+
+fn payload(data: &str) -> Payload {
+    Payload {
+        data: data.to_string(),
+        values: vec![1, 2, 3],
+    }
+}
+
+fn envelope() -> Envelope {
+    Envelope {
+        route: "/route".to_string(),
+        payload: Some(Lazy::new(payload("payload"))),
+        attachments: vec![Lazy::new(payload("a")), Lazy::new(payload("b"))],
+        forwarded: Some(Box::new(Lazy::new(Envelope {
+            route: "/forwarded".to_string(),
+            ..Envelope::default()
+        }))),
+        body: Some(envelope::Body::Inline(Lazy::new(payload("inline")))),
+    }
+}
+
+#[test]
+fn decoded_on_access() {
+    let buf = envelope().encode_to_vec();
+    let decoded = Envelope::decode(buf.as_slice()).unwrap();
+
+    let lazy = decoded.payload.as_ref().unwrap();
+    assert!(!lazy.is_decoded());
+    assert_eq!(lazy.get().unwrap(), &payload("payload"));
+    assert!(lazy.is_decoded());
+
+    let attachments = decoded
+        .attachments
+        .iter()
+        .map(|lazy| lazy.get().unwrap().data.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(attachments, ["a", "b"]);
+
+    let forwarded = decoded.forwarded.as_ref().unwrap().get().unwrap();
+    assert_eq!(forwarded.route, "/forwarded");
+
+    match decoded.body {
+        Some(envelope::Body::Inline(ref lazy)) => {
+            assert_eq!(lazy.get().unwrap(), &payload("inline"))
+        }
+        ref body => panic!("unexpected body: {:?}", body),
+    }
+
+    assert_eq!(decoded, envelope());
+}
+
+#[test]
+fn reencoded_verbatim() {
+    // Field 1 of the payload is encoded twice, which is only preserved if the payload is not
+    // decoded and re-encoded.
+    let mut inner = Vec::new();
+    payload("first").encode(&mut inner).unwrap();
+    payload("second").encode(&mut inner).unwrap();
+    let mut buf = Vec::new();
+    prost::encoding::bytes::encode(2, &inner, &mut buf);
+
+    let mut decoded = Envelope::decode(buf.as_slice()).unwrap();
+    assert_eq!(decoded.encode_to_vec(), buf);
+    assert_eq!(
+        decoded.payload.as_ref().unwrap().get().unwrap().data,
+        "second"
+    );
+    assert_eq!(decoded.encode_to_vec(), buf);
+
+    decoded.payload.as_mut().unwrap().get_mut().unwrap().data = "third".to_string();
+    let reencoded = decoded.encode_to_vec();
+    assert_eq!(reencoded.len(), decoded.encoded_len());
+    assert_eq!(
+        Envelope::decode(reencoded.as_slice())
+            .unwrap()
+            .payload
+            .unwrap()
+            .into_inner()
+            .unwrap(),
+        Payload {
+            data: "third".to_string(),
+            values: vec![1, 2, 3, 1, 2, 3],
+        }
+    );
+}
+
+#[test]
+fn invalid_payload() {
+    let mut buf = Vec::new();
+    prost::encoding::bytes::encode(2, &vec![0x0a, 0x05], &mut buf);
+
+    // The invalid payload is only detected when it is accessed.
+    let decoded = Envelope::decode(buf.as_slice()).unwrap();
+    assert!(decoded.payload.as_ref().unwrap().get().is_err());
+    assert_eq!(decoded.encode_to_vec(), buf);
+}
+
+#[test]
+fn decode_options() {
+    let buf = Envelope {
+        payload: Some(Lazy::new(payload("payload"))),
+        ..Envelope::default()
+    }
+    .encode_to_vec();
+    let len = payload("payload").encoded_len();
+
+    // The encoded payload counts against the budgets of the decode.
+    let options = DecodeOptions::new().max_field_bytes(len);
+    assert!(Envelope::decode_with_options(buf.as_slice(), &options).is_ok());
+    let options = DecodeOptions::new().max_field_bytes(len - 1);
+    let error = Envelope::decode_with_options(buf.as_slice(), &options).unwrap_err();
+    assert_eq!(error.exceeded_budget(), Some(DecodeBudget::FieldBytes));
+    let options = DecodeOptions::new().max_total_bytes(len - 1);
+    let error = Envelope::decode_with_options(buf.as_slice(), &options).unwrap_err();
+    assert_eq!(error.exceeded_budget(), Some(DecodeBudget::TotalBytes));
+
+    // A payload which was already decoded is merged within the budgets.
+    let mut decoded = Envelope::decode(buf.as_slice()).unwrap();
+    decoded.payload.as_ref().unwrap().get().unwrap();
+    let options = DecodeOptions::new().max_field_bytes(1);
+    let error = decoded
+        .merge_with_options(buf.as_slice(), &options)
+        .unwrap_err();
+    assert_eq!(error.exceeded_budget(), Some(DecodeBudget::FieldBytes));
+}
+
+#[test]
+fn message_view() {
+    let buf = envelope().encode_to_vec();
+    let view = EnvelopeView::decode(buf.as_slice()).unwrap();
+    assert_eq!(view.route, "/route");
+    assert!(!view.payload.as_ref().unwrap().is_decoded());
+    assert_eq!(view.to_message().unwrap(), envelope());
+}
@@ -33,22 +33,56 @@ pub mod unittest;
 #[cfg(test)]
 mod bootstrap;
 #[cfg(test)]
+mod cached_size;
+#[cfg(test)]
 mod debug;
 #[cfg(test)]
+mod decode_error;
+#[cfg(test)]
+mod decode_options;
+#[cfg(test)]
 mod deprecated_field;
 #[cfg(test)]
+mod dynamic;
+#[cfg(test)]
+mod extensions;
+#[cfg(test)]
+mod field_mask;
+#[cfg(test)]
 mod generic_derive;
 #[cfg(test)]
+mod incremental;
+#[cfg(test)]
+mod json;
+#[cfg(test)]
+mod lazy_fields;
+#[cfg(test)]
+mod merge_from;
+#[cfg(test)]
 mod message_encoding;
 #[cfg(test)]
+mod message_views;
+#[cfg(test)]
+mod message_writer;
+#[cfg(test)]
 mod no_unused_results;
 #[cfg(test)]
+mod projection;
+#[cfg(test)]
+mod required_fields;
+#[cfg(test)]
 #[cfg(feature = "std")]
 mod skip_debug;
 #[cfg(test)]
+mod strict_decoding;
+#[cfg(test)]
 mod submessage_without_package;
 #[cfg(test)]
+mod text_format;
+#[cfg(test)]
 mod type_names;
+#[cfg(test)]
+mod unknown_fields;
 
 mod test_enum_named_option_value {
     include!(concat!(env!("OUT_DIR"), "/myenum.optionn.rs"));
@@ -251,6 +285,11 @@ where
     msg.encode(&mut buf).unwrap();
     assert_eq!(expected_len, buf.len());
 
+    // Encoding with the lengths of the nested messages computed up front matches encoding without.
+    let mut raw = Vec::with_capacity(expected_len);
+    msg.encode_raw(&mut raw);
+    assert_eq!(raw, buf);
+
     let mut buf = buf.as_slice();
     let roundtrip = M::decode(&mut buf).unwrap();
 
@@ -506,6 +545,29 @@ mod tests {
         assert!(build_and_roundtrip(101).is_err());
     }
 
+    #[test]
+    fn test_deep_nesting_with_options() {
+        use crate::nesting::C;
+        use prost::DecodeOptions;
+
+        let mut c = C::default();
+        for _ in 0..200 {
+            let mut next = C::default();
+            next.r.push(c);
+            c = next;
+        }
+        let buf = c.encode_to_vec();
+
+        let trusted = DecodeOptions::new().recursion_limit(200);
+        assert_eq!(C::decode_with_options(buf.as_slice(), &trusted).unwrap(), c);
+
+        let strict = DecodeOptions::new().recursion_limit(10);
+        assert!(C::decode_with_options(buf.as_slice(), &strict).is_err());
+
+        let mut merged = C::default();
+        assert!(merged.merge_with_options(buf.as_slice(), &strict).is_err());
+    }
+
     #[test]
     fn test_deep_nesting_map() {
         fn build_and_roundtrip(depth: usize) -> Result<(), prost::DecodeError> {
@@ -0,0 +1,335 @@
+//! Tests for merging messages with `Message::merge_from`.
+
+use prost::alloc::{
+    boxed::Box, collections::BTreeMap, string::String, string::ToString, vec, vec::Vec,
+};
+use prost::bytes::Bytes;
+use prost::{Lazy, Message, Oneof, UnknownFieldSet};
+
+#[derive(Clone, PartialEq, Message)]
+struct Config {
+    #[prost(string, tag = "1")]
+    name: String,
+    #[prost(int32, tag = "2")]
+    retries: i32,
+    #[prost(bool, optional, tag = "3")]
+    enabled: Option<bool>,
+    #[prost(bytes = "bytes", tag = "4")]
+    token: Bytes,
+    #[prost(string, repeated, tag = "5")]
+    hosts: Vec<String>,
+    #[prost(uint32, repeated, tag = "6")]
+    ports: Vec<u32>,
+    #[prost(message, optional, tag = "7")]
+    limits: Option<Limits>,
+    #[prost(message, optional, boxed, tag = "8")]
+    fallback: Option<Box<Config>>,
+    #[prost(message, repeated, tag = "9")]
+    rules: Vec<Limits>,
+    #[prost(btree_map = "string, message", tag = "10")]
+    overrides: BTreeMap<String, Limits>,
+    #[prost(btree_map = "string, int64", tag = "11")]
+    weights: BTreeMap<String, i64>,
+    #[prost(oneof = "Source", tags = "12, 13, 14")]
+    source: Option<Source>,
+    #[prost(group, optional, tag = "15")]
+    group: Option<Limits>,
+    #[prost(message, optional, lazy, tag = "17")]
+    lazy: Option<Lazy<Limits>>,
+    #[prost(unknown_fields)]
+    unknown_fields: UnknownFieldSet,
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct Limits {
+    #[prost(uint64, tag = "1")]
+    max_bytes: u64,
+    #[prost(uint64, tag = "2")]
+    max_items: u64,
+    #[prost(double, optional, tag = "3")]
+    ratio: Option<f64>,
+}
+
+#[derive(Clone, PartialEq, Oneof)]
+enum Source {
+    #[prost(string, tag = "12")]
+    Path(String),
+    #[prost(message, tag = "13")]
+    Inline(Limits),
+    #[prost(int32, tag = "14")]
+    Index(i32),
+}
+
+fn limits(max_bytes: u64, max_items: u64) -> Limits {
+    Limits {
+        max_bytes,
+        max_items,
+        ratio: None,
+    }
+}
+
+fn base() -> Config {
+    Config {
+        name: "base".to_string(),
+        retries: 3,
+        enabled: Some(true),
+        token: Bytes::from_static(b"base"),
+        hosts: vec!["a".to_string()],
+        ports: vec![80],
+        limits: Some(limits(100, 10)),
+        fallback: Some(Box::new(Config {
+            name: "fallback".to_string(),
+            retries: 1,
+            ..Config::default()
+        })),
+        rules: vec![limits(1, 1)],
+        overrides: [
+            ("x".to_string(), limits(1, 2)),
+            ("y".to_string(), limits(3, 4)),
+        ]
+        .into_iter()
+        .collect(),
+        weights: [("x".to_string(), 1), ("y".to_string(), 2)]
+            .into_iter()
+            .collect(),
+        source: Some(Source::Inline(limits(5, 0))),
+        group: Some(limits(6, 0)),
+        lazy: Some(Lazy::new(limits(7, 0))),
+        unknown_fields: UnknownFieldSet::default(),
+    }
+}
+
+fn overlay() -> Config {
+    let mut overlay = Config {
+        name: "overlay".to_string(),
+        enabled: Some(false),
+        hosts: vec!["b".to_string()],
+        ports: vec![443, 8443],
+        limits: Some(Limits {
+            max_items: 20,
+            ratio: Some(0.0),
+            ..Limits::default()
+        }),
+        fallback: Some(Box::new(Config {
+            retries: 2,
+            ..Config::default()
+        })),
+        rules: vec![limits(2, 2)],
+        overrides: [
+            ("y".to_string(), limits(0, 5)),
+            ("z".to_string(), limits(6, 7)),
+        ]
+        .into_iter()
+        .collect(),
+        weights: [("y".to_string(), 0)].into_iter().collect(),
+        source: Some(Source::Inline(limits(0, 8))),
+        group: Some(limits(0, 9)),
+        lazy: Some(Lazy::new(limits(0, 10))),
+        ..Config::default()
+    };
+    overlay.unknown_fields =
+        UnknownFieldSet::decode(&b"\xa8\x01\x01"[..]).expect("valid unknown field");
+    overlay
+}
+
+/// Merges `other` into `msg` by encoding it, as the derived `merge_from` must match.
+fn merge_encoded(msg: &mut Config, other: &Config) {
+    msg.merge(other.encode_to_vec().as_slice()).unwrap();
+}
+
+#[test]
+fn merge_fields() {
+    let mut config = base();
+    config.merge_from(&overlay());
+
+    assert_eq!(config.name, "overlay");
+    // Default scalars in the overlay are not set, and do not overwrite.
+    assert_eq!(config.retries, 3);
+    assert_eq!(config.token, Bytes::from_static(b"base"));
+    // Optional scalars overwrite when set, even to their default value.
+    assert_eq!(config.enabled, Some(false));
+    assert_eq!(config.hosts, ["a", "b"]);
+    assert_eq!(config.ports, [80, 443, 8443]);
+    assert_eq!(
+        config.limits,
+        Some(Limits {
+            max_bytes: 100,
+            max_items: 20,
+            ratio: Some(0.0),
+        })
+    );
+    let fallback = config.fallback.as_ref().unwrap();
+    assert_eq!((fallback.name.as_str(), fallback.retries), ("fallback", 2));
+    assert_eq!(config.rules, [limits(1, 1), limits(2, 2)]);
+    // Map entries are replaced by key, rather than merged.
+    assert_eq!(
+        config.overrides,
+        [
+            ("x".to_string(), limits(1, 2)),
+            ("y".to_string(), limits(0, 5)),
+            ("z".to_string(), limits(6, 7)),
+        ]
+        .into_iter()
+        .collect()
+    );
+    assert_eq!(
+        config.weights,
+        [("x".to_string(), 1), ("y".to_string(), 0)]
+            .into_iter()
+            .collect()
+    );
+    assert_eq!(config.source, Some(Source::Inline(limits(5, 8))));
+    assert_eq!(config.group, Some(limits(6, 9)));
+    assert_eq!(config.lazy, Some(Lazy::new(limits(7, 10))));
+    assert_eq!(config.unknown_fields.encode_to_vec(), b"\xa8\x01\x01");
+
+    let mut expected = base();
+    merge_encoded(&mut expected, &overlay());
+    assert_eq!(config, expected);
+}
+
+#[test]
+fn merge_into_default() {
+    let mut config = Config::default();
+    config.merge_from(&base());
+    assert_eq!(config, base());
+
+    let mut config = base();
+    config.merge_from(&Config::default());
+    assert_eq!(config, base());
+}
+
+#[test]
+fn merge_oneof() {
+    let cases = [
+        // Different variants replace the oneof.
+        (
+            Some(Source::Path("path".to_string())),
+            Some(Source::Index(0)),
+            Some(Source::Index(0)),
+        ),
+        (
+            Some(Source::Inline(limits(1, 0))),
+            Some(Source::Path(String::new())),
+            Some(Source::Path(String::new())),
+        ),
+        // The same scalar variant is replaced, and the same message variant is merged.
+        (
+            Some(Source::Index(1)),
+            Some(Source::Index(2)),
+            Some(Source::Index(2)),
+        ),
+        (
+            Some(Source::Inline(limits(1, 0))),
+            Some(Source::Inline(limits(0, 2))),
+            Some(Source::Inline(limits(1, 2))),
+        ),
+        // An unset oneof leaves the oneof unchanged.
+        (Some(Source::Index(1)), None, Some(Source::Index(1))),
+        (None, Some(Source::Index(0)), Some(Source::Index(0))),
+    ];
+    for (source, other, expected) in cases {
+        let mut config = Config {
+            source: source.clone(),
+            ..Config::default()
+        };
+        let other = Config {
+            source: other,
+            ..Config::default()
+        };
+        config.merge_from(&other);
+        assert_eq!(
+            config.source, expected,
+            "{:?} <- {:?}",
+            source, other.source
+        );
+
+        let mut encoded = Config {
+            source,
+            ..Config::default()
+        };
+        merge_encoded(&mut encoded, &other);
+        assert_eq!(config, encoded);
+    }
+}
+
+#[test]
+fn merge_lazy() {
+    // Lazy fields which have not been decoded are merged without being decoded.
+    let mut config = Config::decode(base().encode_to_vec().as_slice()).unwrap();
+    let other = Config::decode(overlay().encode_to_vec().as_slice()).unwrap();
+    config.merge_from(&other);
+    let lazy = config.lazy.as_ref().unwrap();
+    assert!(!lazy.is_decoded());
+    assert_eq!(lazy.get().unwrap(), &limits(7, 10));
+
+    // A decoded field is merged with a field which has not been decoded.
+    let mut config = base();
+    config.merge_from(&other);
+    assert_eq!(config.lazy, Some(Lazy::new(limits(7, 10))));
+}
+
+/// A message with a manual `Message` implementation uses the default `merge_from`, which merges
+/// the encoding of the other message.
+#[test]
+fn default_merge_from() {
+    let mut value = 1u64;
+    value.merge_from(&2);
+    assert_eq!(value, 2);
+    value.merge_from(&0);
+    assert_eq!(value, 2);
+
+    let mut value = "a".to_string();
+    value.merge_from(&"b".to_string());
+    assert_eq!(value, "b");
+}
+
+/// A message whose manual implementation can't decode its second field.
+#[derive(Debug, Default, PartialEq)]
+struct Unmergeable {
+    a: u32,
+    b: u32,
+}
+
+impl Message for Unmergeable {
+    fn encode_raw<B>(&self, buf: &mut B)
+    where
+        B: prost::bytes::BufMut,
+    {
+        prost::encoding::uint32::encode(1, &self.a, buf);
+        prost::encoding::uint32::encode(2, &self.b, buf);
+    }
+
+    fn merge_field<B>(
+        &mut self,
+        tag: u32,
+        wire_type: prost::encoding::WireType,
+        buf: &mut B,
+        ctx: prost::encoding::DecodeContext,
+    ) -> Result<(), prost::DecodeError>
+    where
+        B: prost::bytes::Buf,
+    {
+        match tag {
+            1 => prost::encoding::uint32::merge(wire_type, &mut self.a, buf, ctx),
+            _ => Err(prost::DecodeError::new("unsupported field")),
+        }
+    }
+
+    fn encoded_len(&self) -> usize {
+        prost::encoding::uint32::encoded_len(1, &self.a)
+            + prost::encoding::uint32::encoded_len(2, &self.b)
+    }
+
+    fn clear(&mut self) {
+        *self = Unmergeable::default();
+    }
+}
+
+/// The default `merge_from` does not panic if the encoding can't be merged.
+#[test]
+fn default_merge_from_failure() {
+    let mut value = Unmergeable::default();
+    value.merge_from(&Unmergeable { a: 1, b: 2 });
+    assert_eq!(value, Unmergeable { a: 1, b: 0 });
+}
@@ -372,6 +372,25 @@ pub struct Basic {
     pub bytes_map: ::std::collections::HashMap<String, Vec<u8>>,
 }
 
+#[test]
+fn check_compound() {
+    let basic = Basic {
+        int32: 1,
+        string: "basic".to_owned(),
+        oneof: Some(BasicOneof::String("oneof".to_owned())),
+        ..Basic::default()
+    };
+    let compound = Compound {
+        optional_message: Some(basic.clone()),
+        required_message: Basic::default(),
+        repeated_message: vec![Basic::default(), basic.clone()],
+        #[cfg(feature = "std")]
+        message_map: [(0, Basic::default()), (1, basic.clone())].into(),
+        message_btree_map: [(-1, basic), (0, Basic::default())].into(),
+    };
+    check_message(&compound);
+}
+
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, Message)]
 pub struct Compound {
@@ -400,3 +419,59 @@ pub enum BasicOneof {
     #[prost(string, tag = "9")]
     String(String),
 }
+
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, Message)]
+pub struct Deterministic {
+    #[prost(oneof = "DeterministicOneof", tags = "2, 4")]
+    pub oneof: Option<DeterministicOneof>,
+
+    #[prost(message, optional, tag = "3")]
+    pub optional_message: Option<Basic>,
+
+    #[prost(map = "sint32, message", tag = "5")]
+    #[cfg(feature = "std")]
+    pub message_map: ::std::collections::HashMap<i32, Basic>,
+}
+
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, Oneof)]
+pub enum DeterministicOneof {
+    #[prost(string, tag = "2")]
+    String(String),
+    #[prost(message, tag = "4")]
+    Message(Basic),
+}
+
+#[test]
+fn check_deterministic() {
+    let basic = Basic {
+        int32: 1,
+        string: "basic".to_owned(),
+        oneof: Some(BasicOneof::String("oneof".to_owned())),
+        ..Basic::default()
+    };
+    let message = Deterministic {
+        oneof: Some(DeterministicOneof::Message(basic.clone())),
+        optional_message: Some(basic.clone()),
+        #[cfg(feature = "std")]
+        message_map: (0..16).map(|key| (key, basic.clone())).collect(),
+    };
+
+    // The oneof is encoded at the position of its variant, after the optional message.
+    let mut expected = Vec::new();
+    message.encode_raw_deterministic(&mut expected);
+    assert_eq!(expected[0], (3 << 3) | 2);
+    assert_eq!(message.encode_to_vec_deterministic(), expected);
+
+    let mut buf = Vec::with_capacity(expected.len());
+    message.encode_deterministic(&mut buf).unwrap();
+    assert_eq!(buf, expected);
+
+    let delimited = message.encode_length_delimited_to_vec_deterministic();
+    assert_eq!(
+        Deterministic::decode_length_delimited(delimited.as_slice()).unwrap(),
+        message
+    );
+    assert_eq!(&delimited[delimited.len() - expected.len()..], expected);
+}
@@ -0,0 +1,184 @@
+//! Tests for generated zero-copy message views.
+
+use prost::alloc::{boxed::Box, format, string::ToString, vec, vec::Vec};
+use prost::bytes::Bytes;
+use prost::view::MessageView;
+use prost::Message;
+
+include!(concat!(env!("OUT_DIR"), "/message_views.rs"));
+
+fn author(name: &str) -> Author {
+    Author {
+        name: name.to_string(),
+        emails: vec![format!("{}@example.com", name)],
+    }
+}
+
+fn document() -> Document {
+    Document {
+        title: "title".to_string(),
+        body: b"body".to_vec(),
+        checksum: Bytes::from_static(b"\x01\x02"),
+        id: -7,
+        color: Color::Blue as i32,
+        subtitle: Some("subtitle".to_string()),
+        scores: vec![1, 2, 3],
+        tags: vec!["a".to_string(), "b".to_string()],
+        chunks: vec![b"x".to_vec(), Vec::new()],
+        author: Some(author("alice")),
+        sections: vec![
+            Section {
+                heading: "intro".to_string(),
+                parent: None,
+                children: vec![Section {
+                    heading: "nested".to_string(),
+                    kind: Some(section::Kind::Level(2)),
+                    ..Section::default()
+                }],
+                kind: Some(section::Kind::Level(1)),
+            },
+            Section {
+                heading: "notes".to_string(),
+                parent: Some(Box::new(Section {
+                    heading: "parent".to_string(),
+                    ..Section::default()
+                })),
+                children: Vec::new(),
+                kind: Some(section::Kind::Appendix(true)),
+            },
+        ],
+        labels: [("k", "v"), ("x", "")]
+            .iter()
+            .map(|&(k, v)| (k.to_string(), v.to_string()))
+            .collect(),
+        authors: vec![(1, author("bob"))].into_iter().collect(),
+        colors: vec![("red".to_string(), Color::Red as i32)]
+            .into_iter()
+            .collect(),
+        stamp: Some(Stamp {
+            signature: "sig".to_string(),
+        }),
+        source: Some(document::Source::Editor(author("carol"))),
+    }
+}
+
+#[test]
+fn borrows_from_input() {
+    let buf = document().encode_to_vec();
+    let view = DocumentView::decode(&buf).unwrap();
+
+    let input = buf.as_ptr_range();
+    assert_eq!(view.title, "title");
+    assert!(input.contains(&view.title.as_ptr()));
+    assert_eq!(view.body, b"body");
+    assert!(input.contains(&view.body.as_ptr()));
+    assert_eq!(view.checksum, b"\x01\x02");
+    assert_eq!(view.id, -7);
+    assert_eq!(view.color, Color::Blue as i32);
+    assert_eq!(view.subtitle, Some("subtitle"));
+    assert_eq!(view.scores, [1, 2, 3]);
+    assert_eq!(
+        view.tags.iter().collect::<Result<Vec<_>, _>>(),
+        Ok(vec!["a", "b"])
+    );
+    assert_eq!(view.chunks.get(0), Some(Ok(&b"x"[..])));
+
+    let author = view.author.as_ref().unwrap();
+    assert_eq!(author.name, "alice");
+    assert!(input.contains(&author.name.as_ptr()));
+    assert_eq!(author.emails.get(0), Some(Ok("alice@example.com")));
+
+    assert_eq!(view.sections.len(), 2);
+    let intro = view.sections.get(0).unwrap().unwrap();
+    assert_eq!(intro.heading, "intro");
+    assert_eq!(intro.kind, Some(section::KindView::Level(1)));
+    assert_eq!(intro.children.get(0).unwrap().unwrap().heading, "nested");
+    let notes = view.sections.get(1).unwrap().unwrap();
+    assert_eq!(notes.parent.as_ref().unwrap().heading, "parent");
+    assert_eq!(notes.kind, Some(section::KindView::Appendix(true)));
+
+    assert_eq!(view.labels, [("k", "v"), ("x", "")]);
+    assert_eq!(view.authors[0].0, 1);
+    assert_eq!(view.authors[0].1.name, "bob");
+    assert_eq!(view.colors, [("red", Color::Red as i32)]);
+
+    // Messages without a view are decoded into the owned type.
+    assert_eq!(view.stamp.as_ref().unwrap().signature, "sig");
+
+    match view.source {
+        Some(document::SourceView::Editor(ref editor)) => assert_eq!(editor.name, "carol"),
+        ref source => panic!("unexpected source: {:?}", source),
+    }
+}
+
+#[test]
+fn to_message() {
+    let document = document();
+    let buf = document.encode_to_vec();
+    let view = DocumentView::decode(&buf).unwrap();
+    assert_eq!(view.to_message().unwrap(), document);
+
+    let empty = DocumentView::decode(&[]).unwrap();
+    assert_eq!(empty, DocumentView::default());
+    assert_eq!(empty.to_message().unwrap(), Document::default());
+}
+
+#[test]
+fn oneof_variants() {
+    for source in [
+        document::Source::Url("https://example.com".to_string()),
+        document::Source::Page(42),
+    ] {
+        let document = Document {
+            source: Some(source),
+            ..Document::default()
+        };
+        let buf = document.encode_to_vec();
+        assert_eq!(
+            DocumentView::decode(&buf).unwrap().to_message().unwrap(),
+            document
+        );
+    }
+}
+
+#[test]
+fn invalid_input() {
+    // Invalid UTF-8 in a string field.
+    let err = DocumentView::decode(&[0x0a, 0x01, 0xff]).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "failed to decode Protobuf message: Document.title: invalid string value: data is not UTF-8 encoded"
+    );
+
+    // Invalid UTF-8 in an element of a repeated field of a nested message, which is returned
+    // when the element is accessed, and when the view is converted to the owned message.
+    let buf = Document {
+        author: Some(Author {
+            emails: vec!["\u{0}".to_string()],
+            ..Author::default()
+        }),
+        ..Document::default()
+    }
+    .encode_to_vec();
+    let mut invalid = buf.clone();
+    *invalid.last_mut().unwrap() = 0xff;
+    assert!(DocumentView::decode(&buf).unwrap().to_message().is_ok());
+    let view = DocumentView::decode(&invalid).unwrap();
+    let err = view
+        .author
+        .as_ref()
+        .unwrap()
+        .emails
+        .get(0)
+        .unwrap()
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "failed to decode Protobuf message: invalid string value: data is not UTF-8 encoded"
+    );
+    assert_eq!(view.to_message(), Err(err));
+
+    // Truncated input.
+    let buf = document().encode_to_vec();
+    assert!(DocumentView::decode(&buf[..buf.len() - 1]).is_err());
+}
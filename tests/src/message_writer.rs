@@ -0,0 +1,158 @@
+//! Tests for writing messages one field at a time.
+
+use prost::alloc::{string::String, string::ToString, vec, vec::Vec};
+use prost::encoding::{encode_varint, group, int32, message, sint64, string};
+use prost::{Message, MessageWriter};
+
+#[derive(Clone, PartialEq, Message)]
+struct Export {
+    #[prost(string, tag = "1")]
+    name: String,
+    #[prost(int32, repeated, packed = "false", tag = "2")]
+    counts: Vec<i32>,
+    #[prost(int32, repeated, tag = "3")]
+    packed: Vec<i32>,
+    #[prost(message, repeated, tag = "4")]
+    entries: Vec<Entry>,
+    #[prost(group, optional, tag = "5")]
+    summary: Option<Summary>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct Entry {
+    #[prost(sint64, tag = "1")]
+    id: i64,
+    #[prost(string, repeated, tag = "2")]
+    tags: Vec<String>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct Summary {
+    #[prost(message, optional, tag = "6")]
+    last: Option<Entry>,
+}
+
+fn entry(id: i64) -> Entry {
+    Entry {
+        id,
+        tags: vec![id.to_string(), "entry".to_string()],
+    }
+}
+
+fn export() -> Export {
+    Export {
+        name: "export".to_string(),
+        counts: vec![1, -1, 300],
+        packed: vec![0, 128, 16_384],
+        entries: (0..4).map(entry).collect(),
+        summary: Some(Summary {
+            last: Some(entry(3)),
+        }),
+    }
+}
+
+fn write_entry(writer: &mut MessageWriter<Vec<u8>>, id: i64) {
+    writer.field(1, id, sint64::encode).repeated(
+        2,
+        [id.to_string(), "entry".to_string()],
+        string::encode,
+    );
+}
+
+#[test]
+fn matches_message_encoding() {
+    let export = export();
+    let mut writer = MessageWriter::new(Vec::new());
+    writer
+        .field(1, &export.name, string::encode)
+        .repeated(2, &export.counts, int32::encode)
+        .packed(3, &export.packed, |value, buf| {
+            encode_varint(*value as u64, buf)
+        })
+        .repeated(4, (0..2).map(entry), message::encode)
+        .message(4, |writer| write_entry(writer, 2))
+        .field(4, entry(3), message::encode)
+        .group(5, |summary| {
+            summary.message(6, |last| write_entry(last, 3));
+        });
+    assert_eq!(writer.into_inner(), export.encode_to_vec());
+}
+
+#[test]
+fn empty_fields() {
+    let mut writer = MessageWriter::new(Vec::new());
+    writer
+        .repeated(2, Vec::<i32>::new(), int32::encode)
+        .packed(3, Vec::<i32>::new(), |value, buf| {
+            encode_varint(value as u64, buf)
+        })
+        .message(4, |_| {})
+        .field(5, Summary::default(), group::encode);
+    let export = Export::decode(writer.get_ref().as_slice()).unwrap();
+    assert_eq!(
+        export,
+        Export {
+            entries: vec![Entry::default()],
+            summary: Some(Summary::default()),
+            ..Export::default()
+        }
+    );
+    assert_eq!(writer.into_inner(), b"\x22\x00\x2b\x2c");
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn io_writer() {
+    use prost::IoMessageWriter;
+    use std::io::{self, Write};
+
+    /// A writer which records the length of every write.
+    #[derive(Default)]
+    struct Recorder {
+        data: Vec<u8>,
+        writes: Vec<usize>,
+    }
+
+    impl Write for Recorder {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.data.extend_from_slice(buf);
+            self.writes.push(buf.len());
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let mut writer = IoMessageWriter::new(Recorder::default());
+    writer
+        .field(1, String::from("export"), string::encode)
+        .unwrap()
+        .repeated(4, (0..10_000).map(entry), message::encode)
+        .unwrap()
+        .group(5, |summary| {
+            summary.message(6, |last| write_entry(last, 3));
+        })
+        .unwrap();
+    let recorder = writer.into_inner().unwrap();
+
+    // The entries are written as they are encoded, rather than all at once.
+    assert!(recorder.writes.len() > 10);
+    assert!(recorder.writes.iter().all(|&len| len < 9 * 1024));
+
+    let export = Export::decode(recorder.data.as_slice()).unwrap();
+    assert_eq!(export.name, "export");
+    assert_eq!(export.entries.len(), 10_000);
+    assert_eq!(export.entries[9_999], entry(9_999));
+    assert_eq!(export.summary.unwrap().last, Some(entry(3)));
+
+    // Errors from the underlying writer are returned.
+    let mut buf = [0u8; 4];
+    let mut writer = IoMessageWriter::new(&mut buf[..]);
+    let error = writer
+        .repeated(1, ["a", "b"].map(String::from), string::encode)
+        .and_then(|writer| writer.flush())
+        .unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::WriteZero);
+}
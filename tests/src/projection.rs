@@ -0,0 +1,297 @@
+//! Tests for decoding only the fields selected by a projection.
+
+use prost::alloc::{boxed::Box, string::String, string::ToString, vec, vec::Vec};
+use prost::{DecodeErrorKind, DecodeOptions, Message, Projection};
+
+include!(concat!(env!("OUT_DIR"), "/projection.rs"));
+
+fn header(timestamp: i64, label: &str) -> Header {
+    Header {
+        timestamp,
+        labels: vec![label.to_string()],
+        parent: Some(Box::new(Header {
+            timestamp: timestamp - 1,
+            labels: vec![],
+            parent: None,
+        })),
+    }
+}
+
+fn record() -> Record {
+    Record {
+        id: "r1".to_string(),
+        header: Some(header(42, "current")),
+        payload: vec![0xff; 256],
+        history: vec![header(40, "first"), header(41, "second")],
+        headers: [("main".to_string(), header(43, "main"))]
+            .into_iter()
+            .collect(),
+        body: Some(record::Body::Nested(header(44, "body"))),
+    }
+}
+
+fn decode(projection: &Projection) -> Record {
+    Record::decode_projected(record().encode_to_vec().as_slice(), projection).unwrap()
+}
+
+#[test]
+fn generated_projection() {
+    let projection = Record::summary_projection();
+    assert_eq!(
+        projection,
+        Projection::new()
+            .path(&[1])
+            .path(&[2, 1])
+            .path(&[4, 2])
+            .path(&[7, 3])
+    );
+
+    assert_eq!(
+        decode(&projection),
+        Record {
+            id: "r1".to_string(),
+            header: Some(Header {
+                timestamp: 42,
+                ..Header::default()
+            }),
+            history: vec![
+                Header {
+                    labels: vec!["first".to_string()],
+                    ..Header::default()
+                },
+                Header {
+                    labels: vec!["second".to_string()],
+                    ..Header::default()
+                },
+            ],
+            body: Some(record::Body::Nested(Header {
+                parent: Some(Box::new(Header {
+                    timestamp: 43,
+                    ..Header::default()
+                })),
+                ..Header::default()
+            })),
+            ..Record::default()
+        }
+    );
+}
+
+#[test]
+fn whole_fields() {
+    // Selecting a message field selects all of its nested fields.
+    let record = decode(&Projection::new().path(&[2]).path(&[3]));
+    assert_eq!(record.header, self::record().header);
+    assert_eq!(record.payload, self::record().payload);
+    assert_eq!(record.id, "");
+    assert!(record.history.is_empty());
+    assert!(record.headers.is_empty());
+    assert_eq!(record.body, None);
+
+    // Skipped message fields are not allocated.
+    let record = decode(&Projection::new().path(&[1]));
+    assert_eq!(record.header, None);
+    assert_eq!(record.body, None);
+
+    assert_eq!(decode(&Projection::new()), Record::default());
+}
+
+fn parent(timestamp: i64) -> Header {
+    Header {
+        parent: Some(Box::new(Header {
+            timestamp,
+            ..Header::default()
+        })),
+        ..Header::default()
+    }
+}
+
+#[test]
+fn nested_paths() {
+    let record = decode(&Projection::new().path(&[2, 3, 1]).path(&[2, 2]));
+    assert_eq!(
+        record.header,
+        Some(Header {
+            labels: vec!["current".to_string()],
+            ..parent(41)
+        })
+    );
+    assert_eq!(record.id, "");
+
+    // A path through a message field which is not present selects nothing.
+    let record = decode(&Projection::new().path(&[2, 3, 3, 1]));
+    assert_eq!(
+        record.header,
+        Some(Header {
+            parent: Some(Box::default()),
+            ..Header::default()
+        })
+    );
+}
+
+#[test]
+fn repeated_paths() {
+    let record = decode(&Projection::new().path(&[4, 3, 1]));
+    assert_eq!(record.history, vec![parent(39), parent(40)]);
+    assert_eq!(record.header, None);
+
+    let record = decode(&Projection::new().path(&[4, 1]).path(&[4, 3]));
+    assert_eq!(
+        record.history,
+        vec![
+            Header {
+                labels: vec![],
+                ..header(40, "first")
+            },
+            Header {
+                labels: vec![],
+                ..header(41, "second")
+            },
+        ]
+    );
+}
+
+#[test]
+fn map_values() {
+    let record = decode(&Projection::new().path(&[5, 1]));
+    assert_eq!(
+        record.headers,
+        [(
+            "main".to_string(),
+            Header {
+                timestamp: 43,
+                ..Header::default()
+            }
+        )]
+        .into_iter()
+        .collect()
+    );
+
+    let record = decode(&Projection::new().path(&[5, 3, 1]).path(&[5, 2]));
+    assert_eq!(
+        record.headers,
+        [(
+            "main".to_string(),
+            Header {
+                labels: vec!["main".to_string()],
+                ..parent(42)
+            }
+        )]
+        .into_iter()
+        .collect()
+    );
+
+    // The paths apply to map values, so the map keys are always decoded.
+    let record = decode(&Projection::new().path(&[5, 4]));
+    assert_eq!(
+        record.headers,
+        [("main".to_string(), Header::default())]
+            .into_iter()
+            .collect()
+    );
+}
+
+#[test]
+fn skipped_fields_are_checked() {
+    // Skipped fields must still be well-formed, since they are skipped on the wire.
+    let mut buf = record().encode_to_vec();
+    buf.extend_from_slice(&[0x1a, 0x05, 0x00]);
+    assert!(Record::decode_projected(buf.as_slice(), &Projection::new().path(&[1])).is_err());
+
+    let buf: Vec<u8> = vec![0x0a, 0x01, b'x'];
+    assert!(Record::decode_projected(buf.as_slice(), &Projection::new().path(&[2])).is_ok());
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct Required {
+    #[prost(string, required, presence, tag = "1")]
+    id: Option<String>,
+    #[prost(message, required, presence, tag = "2")]
+    inner: Option<Inner>,
+    #[prost(message, optional, tag = "3")]
+    other: Option<Inner>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct Inner {
+    #[prost(int32, required, presence, tag = "1")]
+    value: Option<i32>,
+    #[prost(int32, optional, tag = "2")]
+    extra: Option<i32>,
+}
+
+#[test]
+fn with_options() {
+    let options = DecodeOptions::new()
+        .check_required_fields(true)
+        .strict(true);
+    let encoded = Required {
+        id: Some("r".to_string()),
+        inner: Some(Inner {
+            value: Some(1),
+            extra: Some(2),
+        }),
+        other: Some(Inner {
+            value: Some(3),
+            extra: None,
+        }),
+    }
+    .encode_to_vec();
+
+    // Required fields which are skipped by the projection are not reported missing.
+    let projected = Required::decode_projected_with_options(
+        &encoded[..],
+        &Projection::new().path(&[2, 2]),
+        &options,
+    )
+    .unwrap();
+    assert_eq!(projected.id, None);
+    assert_eq!(
+        projected.inner,
+        Some(Inner {
+            value: None,
+            extra: Some(2),
+        })
+    );
+
+    // Required fields which are selected, or nested in a selected field, are checked.
+    let missing = Required {
+        other: Some(Inner {
+            value: None,
+            extra: Some(1),
+        }),
+        ..Required::default()
+    }
+    .encode_to_vec();
+    let error = Required::decode_projected_with_options(
+        &missing[..],
+        &Projection::new().path(&[3]),
+        &options,
+    )
+    .unwrap_err();
+    assert_eq!(error.kind(), DecodeErrorKind::MissingRequiredField);
+    assert_eq!(
+        error.missing_fields().collect::<Vec<_>>(),
+        vec!["other.value"]
+    );
+    assert!(Required::decode_projected_with_options(
+        &encoded[..],
+        &Projection::new().path(&[3]),
+        &options
+    )
+    .is_ok());
+
+    // Unknown and duplicated fields are only rejected when they are selected.
+    let mut duplicated = encoded.clone();
+    duplicated.extend_from_slice(&[0x1a, 0x02, 0x08, 0x00, 0x20, 0x01]);
+    let projection = Projection::new().path(&[1]);
+    assert!(
+        Required::decode_projected_with_options(&duplicated[..], &projection, &options).is_ok()
+    );
+    let error = Required::decode_projected_with_options(
+        &duplicated[..],
+        &Projection::new().path(&[3]),
+        &options,
+    )
+    .unwrap_err();
+    assert_eq!(error.kind(), DecodeErrorKind::DuplicateField);
+}
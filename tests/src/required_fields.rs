@@ -0,0 +1,381 @@
+//! Tests for checking the presence of required fields.
+
+use prost::alloc::collections::BTreeMap;
+use prost::alloc::{format, string::String, string::ToString, vec, vec::Vec};
+use prost::{
+    DecodeErrorKind, DecodeOptions, DecodeStatus, EncodeErrorKind, IncrementalDecoder, Message,
+};
+
+include!(concat!(env!("OUT_DIR"), "/required_fields.rs"));
+
+#[derive(Clone, PartialEq, Message)]
+struct Account {
+    #[prost(int64, required, presence, tag = "1")]
+    id: Option<i64>,
+    #[prost(message, required, presence, tag = "2")]
+    owner: Option<User>,
+    #[prost(message, optional, tag = "3")]
+    manager: Option<User>,
+    #[prost(message, repeated, tag = "4")]
+    members: Vec<User>,
+    #[prost(group, optional, tag = "5")]
+    limits: Option<Limits>,
+    #[prost(bool, required, tag = "6")]
+    active: bool,
+    #[prost(btree_map = "string, message", tag = "7")]
+    teams: BTreeMap<String, User>,
+    #[prost(oneof = "Contact", tags = "8, 9")]
+    contact: Option<Contact>,
+}
+
+#[derive(Clone, PartialEq, prost::Oneof)]
+enum Contact {
+    #[prost(message, tag = "8")]
+    Delegate(User),
+    #[prost(string, tag = "9")]
+    Phone(String),
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct User {
+    #[prost(string, required, presence, tag = "1")]
+    name: Option<String>,
+    #[prost(string, required, presence, tag = "2")]
+    email: Option<String>,
+    #[prost(string, optional, tag = "3")]
+    phone: Option<String>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct Limits {
+    #[prost(uint32, required, presence, tag = "6")]
+    max_members: Option<u32>,
+}
+
+fn user(name: &str) -> User {
+    User {
+        name: Some(name.to_string()),
+        email: Some(format!("{}@example.com", name)),
+        phone: None,
+    }
+}
+
+fn account() -> Account {
+    Account {
+        id: Some(1),
+        owner: Some(user("owner")),
+        manager: Some(user("manager")),
+        members: vec![user("a"), user("b")],
+        limits: Some(Limits {
+            max_members: Some(10),
+        }),
+        active: true,
+        teams: BTreeMap::from([("x".to_string(), user("x"))]),
+        contact: Some(Contact::Delegate(user("delegate"))),
+    }
+}
+
+fn decode(account: &Account) -> Result<Account, prost::DecodeError> {
+    let options = DecodeOptions::new().check_required_fields(true);
+    Account::decode_with_options(account.encode_to_vec().as_slice(), &options)
+}
+
+#[test]
+fn complete_message() {
+    assert!(account().is_initialized());
+    assert_eq!(decode(&account()).unwrap(), account());
+
+    // Messages which are not present are not checked, and required fields whose presence is not
+    // tracked always hold a value.
+    let account = Account {
+        manager: None,
+        members: vec![],
+        limits: None,
+        active: false,
+        teams: BTreeMap::new(),
+        contact: Some(Contact::Phone("555".to_string())),
+        ..account()
+    };
+    assert!(account.is_initialized());
+    assert!(decode(&account).is_ok());
+}
+
+#[test]
+fn missing_fields() {
+    let cases = [
+        (
+            Account {
+                id: None,
+                ..account()
+            },
+            vec!["id"],
+            "missing required field: id",
+        ),
+        (
+            Account {
+                owner: None,
+                ..account()
+            },
+            vec!["owner"],
+            "missing required field: owner",
+        ),
+        (
+            Account {
+                manager: Some(User::default()),
+                ..account()
+            },
+            vec!["manager.name", "manager.email"],
+            "missing required fields: manager.name, manager.email",
+        ),
+        (
+            Account {
+                members: vec![
+                    user("a"),
+                    User {
+                        email: None,
+                        ..user("b")
+                    },
+                ],
+                ..account()
+            },
+            vec!["members[1].email"],
+            "missing required field: members[1].email",
+        ),
+        (
+            Account {
+                limits: Some(Limits::default()),
+                ..account()
+            },
+            vec!["limits.max_members"],
+            "missing required field: limits.max_members",
+        ),
+        (
+            Account {
+                teams: BTreeMap::from([
+                    ("x".to_string(), user("x")),
+                    (
+                        "y".to_string(),
+                        User {
+                            name: None,
+                            ..user("y")
+                        },
+                    ),
+                ]),
+                ..account()
+            },
+            vec!["teams[\"y\"].name"],
+            "missing required field: teams[\"y\"].name",
+        ),
+        (
+            Account {
+                contact: Some(Contact::Delegate(User {
+                    email: None,
+                    ..user("delegate")
+                })),
+                ..account()
+            },
+            vec!["Delegate.email"],
+            "missing required field: Delegate.email",
+        ),
+        (
+            Account {
+                id: None,
+                owner: Some(User {
+                    name: None,
+                    ..user("owner")
+                }),
+                members: vec![User::default(), user("b")],
+                ..account()
+            },
+            vec!["id", "owner.name", "members[0].name", "members[0].email"],
+            "missing required fields: id, owner.name, members[0].name, members[0].email",
+        ),
+    ];
+    for (account, paths, description) in cases {
+        assert!(!account.is_initialized());
+        let error = account.check_initialized().unwrap_err();
+        assert_eq!(error.kind(), EncodeErrorKind::MissingRequiredField);
+        assert_eq!(error.missing_fields().collect::<Vec<_>>(), paths);
+        assert!(
+            error.to_string().ends_with(description),
+            "{} ends with {}",
+            error,
+            description
+        );
+
+        let error = decode(&account).unwrap_err();
+        assert_eq!(error.kind(), DecodeErrorKind::MissingRequiredField);
+        assert_eq!(error.missing_fields().collect::<Vec<_>>(), paths);
+        assert!(
+            error.to_string().ends_with(description),
+            "{} ends with {}",
+            error,
+            description
+        );
+
+        // The fields are not checked by default.
+        let decoded = Account::decode(account.encode_to_vec().as_slice()).unwrap();
+        assert_eq!(decoded, account);
+    }
+}
+
+#[test]
+fn constructed_message() {
+    let mut account = Account::default();
+    assert!(!account.is_initialized());
+    assert_eq!(
+        account
+            .check_initialized()
+            .unwrap_err()
+            .missing_fields()
+            .collect::<Vec<_>>(),
+        vec!["id", "owner"]
+    );
+
+    account.id = Some(1);
+    account.owner = Some(User::default());
+    assert_eq!(
+        account
+            .check_initialized()
+            .unwrap_err()
+            .missing_fields()
+            .collect::<Vec<_>>(),
+        vec!["owner.name", "owner.email"]
+    );
+    account.owner = Some(user("owner"));
+    assert!(account.is_initialized());
+    assert!(account.check_initialized().is_ok());
+
+    account.clear();
+    assert_eq!(account, Account::default());
+}
+
+#[test]
+fn merge() {
+    let options = DecodeOptions::new().check_required_fields(true);
+    let mut decoded = decode(&account()).unwrap();
+
+    // Fields which are present before merging are not missing.
+    let partial = Account {
+        owner: Some(User {
+            name: None,
+            ..user("new owner")
+        }),
+        ..Default::default()
+    };
+    decoded
+        .merge_with_options(partial.encode_to_vec().as_slice(), &options)
+        .unwrap();
+    assert_eq!(decoded.id, Some(1));
+    let owner = decoded.owner.as_ref().unwrap();
+    assert_eq!(owner.name.as_deref(), Some("owner"));
+    assert_eq!(owner.email.as_deref(), Some("new owner@example.com"));
+
+    let mut merged = partial.clone();
+    merged.merge_from(&decoded);
+    assert!(merged.is_initialized());
+    assert_eq!(merged, decoded);
+
+    // Missing fields don't replace present ones.
+    let mut complete = account();
+    complete.merge_from(&partial);
+    assert!(complete.is_initialized());
+    assert_eq!(complete.id, Some(1));
+    assert_eq!(complete.owner.unwrap().name.as_deref(), Some("owner"));
+}
+
+#[test]
+fn encode() {
+    // Messages with missing fields are encoded with the fields which are present.
+    let mut decoded = Account::decode(&[0x30, 0x01][..]).unwrap();
+    assert!(decoded.active);
+    assert_eq!(decoded.id, None);
+    let mut buf = Vec::new();
+    decoded.encode(&mut buf).unwrap();
+    assert_eq!(buf, [0x30, 0x01]);
+
+    // Fields which are set after decoding are encoded.
+    decoded.id = Some(5);
+    let encoded = decoded.encode_to_vec();
+    assert_eq!(encoded, [0x08, 0x05, 0x30, 0x01]);
+    let mut buf = Vec::new();
+    decoded.encode_deterministic(&mut buf).unwrap();
+    assert_eq!(buf, encoded);
+    assert_eq!(Account::decode(encoded.as_slice()).unwrap(), decoded);
+}
+
+#[test]
+fn incremental_decoder() {
+    let options = DecodeOptions::new().check_required_fields(true);
+    let mut decoder = IncrementalDecoder::<Account>::length_delimited().with_options(&options);
+
+    let complete = account().encode_length_delimited_to_vec();
+    let incomplete = Account {
+        id: None,
+        ..account()
+    }
+    .encode_length_delimited_to_vec();
+    let input = [complete.as_slice(), &incomplete, &complete].concat();
+    let mut buf = input.as_slice();
+
+    assert!(matches!(
+        decoder.decode(&mut buf),
+        Ok(DecodeStatus::Done(_))
+    ));
+    let error = decoder.decode(&mut buf).unwrap_err();
+    assert_eq!(error.kind(), DecodeErrorKind::MissingRequiredField);
+    assert_eq!(error.missing_fields().collect::<Vec<_>>(), vec!["id"]);
+    // The decoder is reset after the error.
+    assert!(matches!(
+        decoder.decode(&mut buf),
+        Ok(DecodeStatus::Done(_))
+    ));
+
+    let mut decoder = IncrementalDecoder::<User>::new().with_options(&options);
+    let input = User {
+        email: None,
+        ..user("a")
+    }
+    .encode_to_vec();
+    assert_eq!(
+        decoder.decode(&mut input.as_slice()),
+        Ok(DecodeStatus::NeedMoreData)
+    );
+    let error = decoder.finish().unwrap_err();
+    assert_eq!(error.kind(), DecodeErrorKind::MissingRequiredField);
+    assert_eq!(error.missing_fields().collect::<Vec<_>>(), vec!["email"]);
+}
+
+#[test]
+fn generated_presence() {
+    let mut person = Person::default();
+    assert_eq!(
+        person
+            .check_initialized()
+            .unwrap_err()
+            .missing_fields()
+            .collect::<Vec<_>>(),
+        vec!["name", "address"]
+    );
+
+    person.name = Some("name".to_string());
+    person.address = Some(Address::default());
+    assert_eq!(
+        person
+            .check_initialized()
+            .unwrap_err()
+            .missing_fields()
+            .collect::<Vec<_>>(),
+        vec!["address.city"]
+    );
+
+    person.address = Some(Address {
+        city: Some("city".to_string()),
+    });
+    assert!(person.is_initialized());
+    assert_eq!(person.id, 0);
+    assert_eq!(
+        Person::decode(person.encode_to_vec().as_slice()).unwrap(),
+        person
+    );
+}
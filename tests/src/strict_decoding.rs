@@ -0,0 +1,237 @@
+//! Tests for rejecting input which does not match the schema with `DecodeOptions::strict`.
+
+use prost::alloc::{
+    collections::BTreeMap, format, string::String, string::ToString, vec, vec::Vec,
+};
+use prost::encoding::{bytes, int32, message};
+use prost::{
+    DecodeErrorKind, DecodeOptions, DecodeStatus, Enumeration, IncrementalDecoder, Message, Oneof,
+    UnknownFieldSet,
+};
+
+#[derive(Clone, PartialEq, Message)]
+struct Order {
+    #[prost(string, tag = "1")]
+    id: String,
+    #[prost(enumeration = "Status", tag = "2")]
+    status: i32,
+    #[prost(enumeration = "Status", repeated, tag = "3")]
+    history: Vec<i32>,
+    #[prost(message, optional, tag = "4")]
+    customer: Option<Customer>,
+    #[prost(message, repeated, tag = "5")]
+    items: Vec<Customer>,
+    #[prost(btree_map = "string, enumeration(Status)", tag = "6")]
+    item_status: BTreeMap<String, i32>,
+    #[prost(oneof = "Payment", tags = "7, 8, 10")]
+    payment: Option<Payment>,
+    #[prost(group, optional, tag = "9")]
+    notes: Option<Customer>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct Customer {
+    #[prost(string, tag = "1")]
+    name: String,
+    #[prost(enumeration = "Status", optional, tag = "2")]
+    status: Option<i32>,
+    #[prost(unknown_fields)]
+    unknown_fields: UnknownFieldSet,
+}
+
+#[derive(Clone, PartialEq, Oneof)]
+enum Payment {
+    #[prost(enumeration = "Status", tag = "7")]
+    Status(i32),
+    #[prost(string, tag = "8")]
+    Reference(String),
+    #[prost(message, tag = "10")]
+    Card(Customer),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Enumeration)]
+enum Status {
+    Pending = 0,
+    Shipped = 1,
+}
+
+fn customer(name: &str) -> Customer {
+    Customer {
+        name: name.to_string(),
+        status: Some(Status::Pending as i32),
+        unknown_fields: UnknownFieldSet::default(),
+    }
+}
+
+fn order() -> Order {
+    Order {
+        id: "o1".to_string(),
+        status: Status::Shipped as i32,
+        history: vec![Status::Pending as i32, Status::Shipped as i32],
+        customer: Some(customer("c1")),
+        items: vec![customer("i1"), customer("i2")],
+        item_status: [("i1".to_string(), Status::Shipped as i32)]
+            .into_iter()
+            .collect(),
+        payment: Some(Payment::Status(Status::Pending as i32)),
+        notes: Some(customer("n1")),
+    }
+}
+
+fn strict() -> DecodeOptions {
+    DecodeOptions::new().strict(true)
+}
+
+#[test]
+fn matching_input() {
+    let buf = order().encode_to_vec();
+    assert_eq!(
+        Order::decode_with_options(buf.as_slice(), &strict()),
+        Ok(order())
+    );
+}
+
+#[test]
+fn unknown_fields() {
+    let mut top_level = order().encode_to_vec();
+    int32::encode(20, &1, &mut top_level);
+
+    // Customer keeps unknown fields, which does not make them acceptable.
+    let mut nested = Vec::new();
+    int32::encode(21, &1, &mut nested);
+    let mut customer_buf = customer("c1").encode_to_vec();
+    customer_buf.extend_from_slice(&nested);
+    let mut nested = Order {
+        customer: None,
+        ..order()
+    }
+    .encode_to_vec();
+    bytes::encode(4, &customer_buf, &mut nested);
+
+    let mut map_entry = Vec::new();
+    int32::encode(3, &1, &mut map_entry);
+    let mut in_map_entry = order().encode_to_vec();
+    bytes::encode(6, &map_entry, &mut in_map_entry);
+
+    let cases = [
+        (top_level, vec![], 20),
+        (nested, vec![("Order", "customer")], 21),
+        (in_map_entry, vec![("Order", "item_status")], 3),
+    ];
+    for (buf, path, tag) in cases {
+        let error = Order::decode_with_options(buf.as_slice(), &strict()).unwrap_err();
+        assert_eq!(error.kind(), DecodeErrorKind::UnknownField);
+        assert_eq!(error.path().collect::<Vec<_>>(), path);
+        assert!(error
+            .to_string()
+            .ends_with(&format!("unknown field number {}", tag)));
+
+        assert!(Order::decode(buf.as_slice()).is_ok());
+    }
+}
+
+#[test]
+fn invalid_enum_values() {
+    let cases = [
+        (
+            Order {
+                status: 2,
+                ..order()
+            },
+            vec![("Order", "status")],
+        ),
+        (
+            Order {
+                history: vec![1, -1],
+                ..order()
+            },
+            vec![("Order", "history")],
+        ),
+        (
+            Order {
+                customer: Some(Customer {
+                    status: Some(3),
+                    ..customer("c1")
+                }),
+                ..order()
+            },
+            vec![("Order", "customer"), ("Customer", "status")],
+        ),
+        (
+            Order {
+                item_status: [("i1".to_string(), 4)].into_iter().collect(),
+                ..order()
+            },
+            vec![("Order", "item_status")],
+        ),
+        (
+            Order {
+                payment: Some(Payment::Status(5)),
+                ..order()
+            },
+            vec![("Order", "payment")],
+        ),
+    ];
+    for (order, path) in cases {
+        let buf = order.encode_to_vec();
+        let error = Order::decode_with_options(buf.as_slice(), &strict()).unwrap_err();
+        assert_eq!(error.kind(), DecodeErrorKind::InvalidEnumValue);
+        assert_eq!(error.path().collect::<Vec<_>>(), path);
+
+        // The values are kept when not decoding strictly.
+        assert_eq!(Order::decode(buf.as_slice()), Ok(order));
+    }
+}
+
+#[test]
+fn duplicate_message_fields() {
+    let mut duplicate_message = order().encode_to_vec();
+    message::encode(4, &customer("c2"), &mut duplicate_message);
+    let mut duplicate_group = order().encode_to_vec();
+    prost::encoding::group::encode(9, &customer("n2"), &mut duplicate_group);
+    let mut duplicate_variant = Order {
+        payment: Some(Payment::Card(customer("p1"))),
+        ..order()
+    }
+    .encode_to_vec();
+    message::encode(10, &customer("p2"), &mut duplicate_variant);
+
+    let cases = [
+        (duplicate_message, ("Order", "customer")),
+        (duplicate_group, ("Order", "notes")),
+        (duplicate_variant, ("Order", "payment")),
+    ];
+    for (buf, field) in cases {
+        let error = Order::decode_with_options(buf.as_slice(), &strict()).unwrap_err();
+        assert_eq!(error.kind(), DecodeErrorKind::DuplicateField);
+        assert_eq!(error.path().collect::<Vec<_>>(), vec![field]);
+
+        assert!(Order::decode(buf.as_slice()).is_ok());
+
+        let mut decoder = IncrementalDecoder::<Order>::new().with_options(&strict());
+        assert_eq!(
+            decoder.decode(&mut buf.as_slice()).unwrap_err().kind(),
+            DecodeErrorKind::DuplicateField
+        );
+    }
+
+    // Repeated message fields may occur any number of times.
+    let mut buf = order().encode_to_vec();
+    message::encode(5, &customer("i3"), &mut buf);
+    let decoded = Order::decode_with_options(buf.as_slice(), &strict()).unwrap();
+    assert_eq!(decoded.items.len(), 3);
+
+    // Each merge is checked separately, so a field which is already set may occur once.
+    let mut order = order();
+    let mut buf = Vec::new();
+    message::encode(4, &customer("c2"), &mut buf);
+    order.merge_with_options(buf.as_slice(), &strict()).unwrap();
+    assert_eq!(order.customer.unwrap().name, "c2");
+
+    let mut decoder = IncrementalDecoder::<Order>::new().with_options(&strict());
+    assert_eq!(
+        decoder.decode(&mut buf.as_slice()),
+        Ok(DecodeStatus::NeedMoreData)
+    );
+    assert!(decoder.finish().is_ok());
+}
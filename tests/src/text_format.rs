@@ -0,0 +1,135 @@
+//! Tests for the Protobuf text format.
+
+use prost::alloc::{boxed::Box, string::ToString, vec};
+use prost::text_format::{PrintOptions, TextFormat};
+
+include!(concat!(env!("OUT_DIR"), "/text_format.rs"));
+
+fn shape() -> Shape {
+    Shape {
+        name: Some("triangle \"A\"\n".to_string()),
+        points: vec![Point { x: 0, y: 0 }, Point { x: 3, y: -4 }],
+        color: Some(Color::Green as i32),
+        weights: vec![0.5, -1.0],
+        data: Some(vec![0, 0xff, b'a']),
+        anchors: [("top".to_string(), Point { x: 1, y: 2 })].into(),
+        layers: [(2, Color::Red as i32), (1, 7)].into(),
+        fill: Some(shape::Fill::Center(Point { x: 1, y: 1 })),
+        style: Some(shape::Style {
+            stroke: Some(1.5),
+            palette: vec![Color::Red as i32],
+        }),
+        parent: Some(Box::new(Shape {
+            name: Some("parent".to_string()),
+            ..Shape::default()
+        })),
+        area: Some(-6),
+        visible: Some(false),
+    }
+}
+
+#[test]
+fn print() {
+    assert_eq!(Point { x: 1, y: -2 }.to_text(), "x: 1 y: -2");
+    assert_eq!(Shape::default().to_text(), "");
+    assert_eq!(
+        shape().to_text(),
+        "name: \"triangle \\\"A\\\"\\n\" \
+         points { x: 0 y: 0 } points { x: 3 y: -4 } \
+         color: GREEN \
+         weights: 0.5 weights: -1.0 \
+         data: \"\\000\\377a\" \
+         anchors { key: \"top\" value { x: 1 y: 2 } } \
+         layers { key: 1 value: 7 } layers { key: 2 value: RED } \
+         center { x: 1 y: 1 } \
+         Style { stroke: 1.5 palette: RED } \
+         parent { name: \"parent\" } \
+         area: -6 \
+         visible: false"
+    );
+}
+
+#[test]
+fn print_pretty() {
+    let shape = Shape {
+        name: Some("square".to_string()),
+        points: vec![Point { x: 1, y: 2 }],
+        style: Some(shape::Style::default()),
+        ..Shape::default()
+    };
+    assert_eq!(
+        shape.to_text_pretty(),
+        "name: \"square\"\npoints {\n  x: 1\n  y: 2\n}\nStyle {\n}\n"
+    );
+    assert_eq!(
+        shape.to_text_with_options(&PrintOptions::new().pretty(true)),
+        shape.to_text_pretty()
+    );
+}
+
+#[test]
+fn roundtrip() {
+    let shape = shape();
+    assert_eq!(Shape::parse_text(&shape.to_text()).unwrap(), shape);
+    assert_eq!(Shape::parse_text(&shape.to_text_pretty()).unwrap(), shape);
+}
+
+#[test]
+fn parse() {
+    let text = r#"
+        # Comments and both field separators are accepted.
+        name: "a" 'b' ;
+        points < x: 1 y: 2 >,
+        points: [{ x: 3, y: 4 }, { x: 5 y: 6 }]
+        color: 2
+        weights: [1, inf, -2.5f]
+        layers { key: 3 value: GREEN }
+        pattern: "dots"
+        Style { palette: [RED, 2] }
+        area: 0x10
+        visible: t
+    "#;
+    let shape = Shape::parse_text(text).unwrap();
+    assert_eq!(shape.name.as_deref(), Some("ab"));
+    assert_eq!(
+        shape.points,
+        vec![
+            Point { x: 1, y: 2 },
+            Point { x: 3, y: 4 },
+            Point { x: 5, y: 6 }
+        ]
+    );
+    assert_eq!(shape.color(), Color::Green);
+    assert_eq!(shape.weights, vec![1.0, f64::INFINITY, -2.5]);
+    assert_eq!(shape.layers[&3], Color::Green as i32);
+    assert_eq!(shape.fill, Some(shape::Fill::Pattern("dots".to_string())));
+    assert_eq!(shape.style.unwrap().palette, vec![1, 2]);
+    assert_eq!(shape.area, Some(16));
+    assert_eq!(shape.visible, Some(true));
+}
+
+#[test]
+fn merge() {
+    let mut shape = Shape {
+        name: Some("a".to_string()),
+        points: vec![Point { x: 1, y: 1 }],
+        ..Shape::default()
+    };
+    shape
+        .merge_text("name: \"b\" points { x: 2 y: 2 }")
+        .unwrap();
+    assert_eq!(shape.name.as_deref(), Some("b"));
+    assert_eq!(shape.points.len(), 2);
+}
+
+#[test]
+fn parse_errors() {
+    let error = Shape::parse_text("name: \"a\"\ncolor: BLUE").unwrap_err();
+    assert_eq!((error.line(), error.column()), (2, 8));
+
+    assert!(Shape::parse_text("unknown: 1").is_err());
+    assert!(Shape::parse_text("points { x: 1").is_err());
+    assert!(Shape::parse_text("Style { stroke: \"1\" }").is_err());
+    assert!(Shape::parse_text("[ext.field]: 1").is_err());
+    assert!(Point::parse_text("x: 2147483648").is_err());
+}
@@ -0,0 +1,108 @@
+//! Tests for preserving unknown fields.
+
+use prost::alloc::{format, string::String, vec, vec::Vec};
+use prost::{Message, UnknownFieldSet, UnknownFieldValue};
+
+include!(concat!(env!("OUT_DIR"), "/unknown_fields.rs"));
+
+fn new_message() -> New {
+    New {
+        a: 42,
+        extra: "extra".into(),
+        nested: Some(NewNested {
+            b: "b".into(),
+            c: -1,
+        }),
+        more: vec![1, 2, 3],
+    }
+}
+
+#[test]
+fn generated_roundtrip() {
+    let new = new_message();
+    let encoded = new.encode_to_vec();
+
+    let old = Old::decode(encoded.as_slice()).unwrap();
+    assert_eq!(old.a, 42);
+    assert_eq!(
+        old.unknown_fields
+            .iter()
+            .map(|f| f.number)
+            .collect::<Vec<_>>(),
+        vec![2, 5]
+    );
+    let nested = old.nested.as_ref().unwrap();
+    assert_eq!(nested.b, "b");
+    assert_eq!(nested.unknown_fields.len(), 1);
+
+    assert_eq!(old.encoded_len(), encoded.len());
+    assert_eq!(New::decode(old.encode_to_vec().as_slice()).unwrap(), new);
+}
+
+#[test]
+fn generated_clear() {
+    let mut old = Old::decode(new_message().encode_to_vec().as_slice()).unwrap();
+    old.clear();
+    assert_eq!(old, Old::default());
+    assert!(old.encode_to_vec().is_empty());
+}
+
+#[test]
+fn generated_without_preservation() {
+    let lossy = Lossy::decode(new_message().encode_to_vec().as_slice()).unwrap();
+    assert_eq!(
+        New::decode(lossy.encode_to_vec().as_slice()).unwrap(),
+        New {
+            a: 42,
+            ..New::default()
+        }
+    );
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct Known {
+    #[prost(unknown_fields)]
+    unknown: UnknownFieldSet,
+    #[prost(string, tag = "2")]
+    name: String,
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct KnownTuple(
+    #[prost(int32, tag = "1")] i32,
+    #[prost(unknown_fields)] UnknownFieldSet,
+    #[prost(bool, tag = "2")] bool,
+);
+
+#[test]
+fn unknown_fields_are_emitted_after_known_fields() {
+    let mut unknown = UnknownFieldSet::new();
+    unknown.push(3, UnknownFieldValue::Varint(150));
+    unknown.push(1, UnknownFieldValue::ThirtyTwoBit(7));
+    let msg = Known {
+        unknown,
+        name: "x".into(),
+    };
+    assert_eq!(
+        msg.encode_to_vec(),
+        vec![0x12, 0x01, b'x', 0x18, 0x96, 0x01, 0x0d, 0x07, 0x00, 0x00, 0x00]
+    );
+    assert_eq!(msg.encoded_len(), 11);
+    assert_eq!(Known::decode(msg.encode_to_vec().as_slice()).unwrap(), msg);
+}
+
+#[test]
+fn unknown_fields_in_tuple_struct() {
+    let msg = KnownTuple::decode(&[0x08, 0x01, 0x18, 0x02, 0x10, 0x01][..]).unwrap();
+    assert_eq!(msg.0, 1);
+    assert!(msg.2);
+    assert_eq!(
+        msg.1.get(3).collect::<Vec<_>>(),
+        vec![&UnknownFieldValue::Varint(2)]
+    );
+    assert_eq!(
+        msg.encode_to_vec(),
+        vec![0x08, 0x01, 0x10, 0x01, 0x18, 0x02]
+    );
+    assert!(format!("{:?}", msg).starts_with("KnownTuple(1, UnknownFieldSet"));
+}